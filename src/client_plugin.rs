@@ -0,0 +1,55 @@
+use bevy::prelude::{App, Resource};
+use bevy_egui::egui;
+
+use crate::ui::UiWindowId;
+
+pub struct ClientPluginWindow {
+    pub id: UiWindowId,
+    pub title: &'static str,
+
+    // Pressed together with Alt to toggle the window
+    pub hotkey: Option<egui::Key>,
+}
+
+// Implemented by downstream crates to add their own systems and windows to the
+// client, plugins are passed to the client with SystemsConfig::client_plugins.
+//
+// Window systems should be added to UiSystemSets::Ui and use
+// UiStateWindows::open_mut with the window id to control visibility.
+pub trait ClientPlugin {
+    fn name(&self) -> &'static str;
+
+    fn windows(&self) -> Vec<ClientPluginWindow> {
+        Vec::new()
+    }
+
+    fn build(&self, app: &mut App);
+}
+
+#[derive(Default, Resource)]
+pub struct ClientPluginRegistry {
+    pub plugins: Vec<&'static str>,
+    pub windows: Vec<ClientPluginWindow>,
+}
+
+impl ClientPluginRegistry {
+    pub fn find_window(&self, name: &str) -> Option<UiWindowId> {
+        self.windows
+            .iter()
+            .map(|window| window.id)
+            .find(|id| id.0 == name)
+    }
+}
+
+pub fn add_client_plugins(app: &mut App, client_plugins: Vec<Box<dyn ClientPlugin>>) {
+    let mut registry = ClientPluginRegistry::default();
+
+    for client_plugin in client_plugins {
+        log::info!("Adding client plugin {}", client_plugin.name());
+        client_plugin.build(app);
+        registry.plugins.push(client_plugin.name());
+        registry.windows.extend(client_plugin.windows());
+    }
+
+    app.insert_resource(registry);
+}
@@ -20,6 +20,16 @@ pub enum CharacterModelPart {
     SubWeapon,
 }
 
+impl CharacterModelPart {
+    // The model collider and ModelHeight are calculated from these parts
+    pub fn affects_model_height(&self) -> bool {
+        matches!(
+            self,
+            CharacterModelPart::Body | CharacterModelPart::Hands | CharacterModelPart::Feet
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct CharacterModelPartIndex {
     pub id: usize,
@@ -4,6 +4,8 @@ use std::num::NonZeroUsize;
 use rose_data::{ClanMemberPosition, SkillId};
 use rose_game_common::components::{ClanLevel, ClanMark, ClanPoints, ClanUniqueId, Level, Money};
 
+use crate::protocol::ClanNotice;
+
 #[derive(Clone)]
 pub struct ClanMember {
     pub name: String,
@@ -12,6 +14,31 @@ pub struct ClanMember {
     pub level: Level,
     pub job: u16,
     pub channel_id: Option<NonZeroUsize>,
+    // Unix timestamp of when the member was last online, only sent by servers
+    // which support it
+    pub last_online: Option<i64>,
+}
+
+pub const MAX_CLAN_NOTICE_LENGTH: usize = 80;
+
+// The server counts the kills and deaths of a war, ends is the unix timestamp
+// of when the war runs out
+#[derive(Clone)]
+pub struct ClanWar {
+    pub opponent: String,
+    pub ends: i64,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+impl ClanWar {
+    pub fn remaining_seconds(&self, now: i64) -> i64 {
+        (self.ends - now).max(0)
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.remaining_seconds(now) > 0
+    }
 }
 
 #[derive(Component)]
@@ -25,6 +52,9 @@ pub struct Clan {
     pub level: ClanLevel,
     pub members: Vec<ClanMember>,
     pub skills: Vec<SkillId>,
+    // Sent separately by servers which support clan notices and wars
+    pub notices: Vec<ClanNotice>,
+    pub wars: Vec<ClanWar>,
 }
 
 impl Clan {
@@ -35,4 +65,11 @@ impl Clan {
     pub fn find_member_mut(&mut self, name: &str) -> Option<&mut ClanMember> {
         self.members.iter_mut().find(|member| member.name == name)
     }
+
+    pub fn find_war(&self, clan_name: &str) -> Option<&ClanWar> {
+        let now = chrono::Utc::now().timestamp();
+        self.wars
+            .iter()
+            .find(|war| war.opponent.eq_ignore_ascii_case(clan_name) && war.is_active(now))
+    }
 }
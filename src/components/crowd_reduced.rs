@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+// Another player who is shown with reduced detail by crowded area mode
+#[derive(Component)]
+pub struct CrowdReduced;
@@ -0,0 +1,78 @@
+use bevy::{math::Vec3, prelude::Component, reflect::Reflect};
+
+use crate::components::MonsterSpawnTelegraph;
+
+#[derive(Copy, Clone, Component, Reflect)]
+pub struct DynamicPointLight {
+    pub color: Vec3,
+    pub intensity: f32,
+    pub radius: f32,
+
+    // When set, the light fades out over this many seconds and is then removed
+    pub fade_duration: Option<f32>,
+    pub age: f32,
+}
+
+impl DynamicPointLight {
+    pub fn new(color: Vec3, intensity: f32, radius: f32) -> Self {
+        Self {
+            color,
+            intensity,
+            radius,
+            fade_duration: None,
+            age: 0.0,
+        }
+    }
+
+    pub fn with_fade_duration(mut self, fade_duration: f32) -> Self {
+        self.fade_duration = Some(fade_duration);
+        self
+    }
+
+    pub fn fire() -> Self {
+        Self::new(Vec3::new(1.0, 0.6, 0.25), 1.5, 6.0)
+    }
+
+    pub fn zone_light() -> Self {
+        Self::new(Vec3::new(1.0, 0.85, 0.6), 1.0, 8.0)
+    }
+
+    pub fn monster_spawn() -> Self {
+        Self::new(Vec3::new(0.8, 0.3, 1.0), 2.5, 5.0)
+            .with_fade_duration(MonsterSpawnTelegraph::DURATION)
+    }
+
+    pub fn warp_gate() -> Self {
+        Self::new(Vec3::new(0.45, 0.7, 1.0), 2.0, 8.0)
+    }
+
+    pub fn skill_impact() -> Self {
+        Self::new(Vec3::new(1.0, 0.9, 0.75), 2.0, 5.0).with_fade_duration(0.5)
+    }
+
+    // There is no light data in .eft files, so we guess from the file name
+    pub fn from_effect_path(path: &str) -> Option<Self> {
+        let path = path.to_ascii_uppercase();
+
+        if path.contains("FIRE") || path.contains("TORCH") || path.contains("CANDLE") {
+            Some(Self::fire())
+        } else if path.contains("LAMP") || path.contains("LIGHT") {
+            Some(Self::zone_light())
+        } else {
+            None
+        }
+    }
+
+    pub fn current_intensity(&self) -> f32 {
+        if let Some(fade_duration) = self.fade_duration {
+            self.intensity * (1.0 - self.age / fade_duration).clamp(0.0, 1.0)
+        } else {
+            self.intensity
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.fade_duration
+            .map_or(false, |fade_duration| self.age >= fade_duration)
+    }
+}
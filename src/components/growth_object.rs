@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use bevy::prelude::Component;
+
+// A planted seed, each growth stage is a separate npc spawned by the server
+#[derive(Component)]
+pub struct GrowthObject {
+    pub stage: usize,
+    pub num_stages: usize,
+    pub stage_duration: Option<Duration>,
+    pub stage_elapsed: Duration,
+    pub base_scale: f32,
+}
+
+impl GrowthObject {
+    pub fn is_harvestable(&self) -> bool {
+        self.stage + 1 >= self.num_stages
+    }
+
+    // Progress through the current stage from 0.0 to 1.0
+    pub fn stage_progress(&self) -> f32 {
+        match self.stage_duration {
+            Some(duration) if !duration.is_zero() => {
+                (self.stage_elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            }
+            _ => 1.0,
+        }
+    }
+
+    // Progress through all stages from 0.0 to 1.0
+    pub fn total_progress(&self) -> f32 {
+        if self.is_harvestable() {
+            1.0
+        } else {
+            (self.stage as f32 + self.stage_progress()) / (self.num_stages - 1) as f32
+        }
+    }
+}
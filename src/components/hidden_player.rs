@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+// Another player who is hidden by the hide other players setting
+#[derive(Component)]
+pub struct HiddenPlayer;
@@ -8,27 +8,37 @@ mod client_entity_name;
 mod collision;
 mod command;
 mod cooldowns;
+mod crowd_reduced;
 mod damage_digits;
 mod dead;
 mod dummy_bone_offset;
+mod dynamic_point_light;
 mod effect;
 mod event_object;
 mod facing_direction;
+mod growth_object;
+mod hidden_player;
 mod item_drop_model;
 mod model_height;
+mod monster_spawn_telegraph;
 mod name_tag_entity;
 mod night_time_effect;
 mod npc_model;
 mod particle_sequence;
 mod party_info;
+mod party_member;
 mod passive_recovery_time;
 mod pending_damage_list;
+mod pending_model;
 mod pending_skill_effect_list;
 mod personal_store;
+mod placeholder_model;
 mod player_character;
 mod position;
+mod position_correction;
 mod projectile;
 mod sound_category;
+mod summon;
 mod vehicle;
 mod vehicle_model;
 mod vehicle_sound;
@@ -40,7 +50,7 @@ mod zone_object;
 pub use bank::Bank;
 pub use character_model::{CharacterModel, CharacterModelPart, CharacterModelPartIndex};
 pub use character_model_blink_timer::CharacterBlinkTimer;
-pub use clan::{Clan, ClanMember};
+pub use clan::{Clan, ClanMember, ClanWar, MAX_CLAN_NOTICE_LENGTH};
 pub use clan_membership::ClanMembership;
 pub use client_entity::{ClientEntity, ClientEntityId, ClientEntityType};
 pub use client_entity_name::ClientEntityName;
@@ -57,32 +67,44 @@ pub use command::{
     CommandEmote, CommandMove, CommandSit, NextCommand,
 };
 pub use cooldowns::{ConsumableCooldownGroup, Cooldowns};
+pub use crowd_reduced::CrowdReduced;
 pub use damage_digits::DamageDigits;
 pub use dead::Dead;
 pub use dummy_bone_offset::DummyBoneOffset;
+pub use dynamic_point_light::DynamicPointLight;
 pub use effect::{Effect, EffectMesh, EffectParticle};
 pub use event_object::EventObject;
 pub use facing_direction::FacingDirection;
+pub use growth_object::GrowthObject;
+pub use hidden_player::HiddenPlayer;
 pub use item_drop_model::ItemDropModel;
 pub use model_height::ModelHeight;
+pub use monster_spawn_telegraph::MonsterSpawnTelegraph;
 pub use name_tag_entity::{
     NameTag, NameTagClanName, NameTagEntity, NameTagHealthbarBackground,
-    NameTagHealthbarForeground, NameTagName, NameTagTargetMark, NameTagType, RemoveNameTagCommand,
+    NameTagHealthbarForeground, NameTagHealthbarGhost, NameTagLifetimebarBackground,
+    NameTagLifetimebarForeground, NameTagName, NameTagTargetMark, NameTagType,
+    RemoveNameTagCommand,
 };
 pub use night_time_effect::NightTimeEffect;
 pub use npc_model::NpcModel;
 pub use particle_sequence::{ActiveParticle, ParticleSequence};
 pub use party_info::{PartyInfo, PartyOwner};
+pub use party_member::PartyMember;
 pub use passive_recovery_time::PassiveRecoveryTime;
 pub use pending_damage_list::{PendingDamage, PendingDamageList};
+pub use pending_model::{PendingModel, PendingVehicleModel};
 pub use pending_skill_effect_list::{
     PendingSkillEffect, PendingSkillEffectList, PendingSkillTarget, PendingSkillTargetList,
 };
 pub use personal_store::{PersonalStore, PersonalStoreModel};
+pub use placeholder_model::PlaceholderModel;
 pub use player_character::PlayerCharacter;
 pub use position::Position;
+pub use position_correction::PositionCorrection;
 pub use projectile::{Projectile, ProjectileParabola, ProjectileTarget};
 pub use sound_category::SoundCategory;
+pub use summon::Summon;
 pub use vehicle::Vehicle;
 pub use vehicle_model::VehicleModel;
 pub use vehicle_sound::{VehicleSound, VehicleSoundState};
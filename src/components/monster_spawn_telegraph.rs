@@ -0,0 +1,16 @@
+use bevy::prelude::Component;
+
+// A monster which the server has just spawned, it emerges from the ground and
+// cannot be targeted until the telegraph has finished
+#[derive(Component, Default)]
+pub struct MonsterSpawnTelegraph {
+    pub elapsed: f32,
+}
+
+impl MonsterSpawnTelegraph {
+    pub const DURATION: f32 = 1.2;
+
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / Self::DURATION).min(1.0)
+    }
+}
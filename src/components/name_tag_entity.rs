@@ -9,6 +9,7 @@ pub enum NameTagType {
     Character,
     Monster,
     Npc,
+    Summon,
 }
 
 #[derive(Component)]
@@ -30,11 +31,35 @@ pub struct NameTagHealthbarForeground {
     pub uv_min_x: f32,
     pub uv_max_x: f32,
     pub full_width: f32,
+
+    // Health shown by the bar, which moves smoothly towards the current health
+    pub displayed_percent: Option<f32>,
+}
+
+// Trails behind the foreground to show recent damage
+#[derive(Component)]
+pub struct NameTagHealthbarGhost {
+    pub uv_min_x: f32,
+    pub uv_max_x: f32,
+    pub full_width: f32,
+    pub displayed_percent: Option<f32>,
+    pub last_health_percent: f32,
+    pub hold_time: f32,
 }
 
 #[derive(Component)]
 pub struct NameTagHealthbarBackground;
 
+#[derive(Component)]
+pub struct NameTagLifetimebarForeground {
+    pub uv_min_x: f32,
+    pub uv_max_x: f32,
+    pub full_width: f32,
+}
+
+#[derive(Component)]
+pub struct NameTagLifetimebarBackground;
+
 #[derive(Component, Deref, DerefMut)]
 pub struct NameTagEntity(pub Entity);
 
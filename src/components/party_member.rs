@@ -0,0 +1,8 @@
+use bevy::prelude::Component;
+
+// Added to visible characters which are in the player's party, index is their
+// position in the party member list and is used to pick their marker colour
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct PartyMember {
+    pub index: usize,
+}
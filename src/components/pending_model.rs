@@ -0,0 +1,10 @@
+use bevy::prelude::Component;
+
+// Inserted while the skeleton for an entity's model is loading in the
+// background, the model update systems retry every frame until it is ready.
+#[derive(Component)]
+pub struct PendingModel;
+
+// Inserted while the skeleton for a vehicle is loading, the vehicle is mounted once it is ready
+#[derive(Component)]
+pub struct PendingVehicleModel;
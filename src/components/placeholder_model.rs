@@ -0,0 +1,7 @@
+use bevy::prelude::{Component, Entity};
+
+// Shown in place of a model while its skeleton is loading, or when it failed to load
+#[derive(Component)]
+pub struct PlaceholderModel {
+    pub entity: Entity,
+}
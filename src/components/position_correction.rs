@@ -0,0 +1,20 @@
+use bevy::prelude::{Component, Vec2, Vec3};
+
+// A position reported by the server which is blended in over a few frames
+// rather than snapping the entity there
+#[derive(Component)]
+pub struct PositionCorrection {
+    pub server_position: Vec2,
+    pub offset: Option<Vec2>,
+    pub remaining_time: f32,
+}
+
+impl PositionCorrection {
+    pub fn new(server_position: Vec3, duration: f32) -> Self {
+        Self {
+            server_position: server_position.truncate(),
+            offset: None,
+            remaining_time: duration,
+        }
+    }
+}
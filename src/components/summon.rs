@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use bevy::prelude::Component;
+
+use crate::components::ClientEntityId;
+
+#[derive(Component)]
+pub struct Summon {
+    pub owner_entity_id: ClientEntityId,
+    pub is_player_owned: bool,
+    pub remaining_lifetime: Option<Duration>,
+    pub total_lifetime: Option<Duration>,
+}
+
+impl Summon {
+    // Fraction of the lifetime remaining, None if the summon does not expire
+    pub fn lifetime_percent(&self) -> Option<f32> {
+        let remaining = self.remaining_lifetime?;
+        let total = self.total_lifetime.filter(|total| !total.is_zero())?;
+        Some((remaining.as_secs_f32() / total.as_secs_f32()).clamp(0.0, 1.0))
+    }
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+
+// Stores account passwords and password hashes in the OS keychain (Windows Credential
+// Manager, macOS Keychain or the Secret Service on Linux)
+const CREDENTIAL_SERVICE: &str = "rose-offline-client";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CredentialKind {
+    Password,
+    PasswordMd5,
+}
+
+fn credential_entry(
+    kind: CredentialKind,
+    ip: &str,
+    port: &str,
+    username: &str,
+) -> Option<keyring::Entry> {
+    let user = match kind {
+        CredentialKind::Password => format!("{}@{}:{}", username, ip, port),
+        CredentialKind::PasswordMd5 => format!("{}@{}:{}#md5", username, ip, port),
+    };
+
+    keyring::Entry::new(CREDENTIAL_SERVICE, &user)
+        .map_err(|error| log::warn!("Failed to open OS keychain entry with error {}", error))
+        .ok()
+}
+
+pub fn load_credential(
+    kind: CredentialKind,
+    ip: &str,
+    port: &str,
+    username: &str,
+) -> Option<String> {
+    if username.is_empty() {
+        return None;
+    }
+
+    match credential_entry(kind, ip, port, username)?.get_password() {
+        Ok(secret) => Some(secret),
+        Err(keyring::Error::NoEntry) => None,
+        Err(error) => {
+            log::warn!("Failed to read from OS keychain with error {}", error);
+            None
+        }
+    }
+}
+
+pub fn store_credential(
+    kind: CredentialKind,
+    ip: &str,
+    port: &str,
+    username: &str,
+    secret: &str,
+) -> bool {
+    let Some(entry) = credential_entry(kind, ip, port, username) else {
+        return false;
+    };
+
+    if let Err(error) = entry.set_password(secret) {
+        log::warn!("Failed to write to OS keychain with error {}", error);
+        false
+    } else {
+        true
+    }
+}
+
+pub fn delete_credential(kind: CredentialKind, ip: &str, port: &str, username: &str) {
+    let Some(entry) = credential_entry(kind, ip, port, username) else {
+        return;
+    };
+
+    match entry.delete_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => {}
+        Err(error) => log::warn!("Failed to delete from OS keychain with error {}", error),
+    }
+}
+
+// Removes the [account] lines holding secret from config.toml, everything else in the
+// file including comments is written back unchanged
+fn remove_config_credential(
+    kind: CredentialKind,
+    config_path: &Path,
+    secret: &str,
+) -> std::io::Result<bool> {
+    let keys: &[&str] = match kind {
+        CredentialKind::Password => &["password"],
+        CredentialKind::PasswordMd5 => &["password_md5", "token"],
+    };
+    let toml_str = std::fs::read_to_string(config_path)?;
+    let mut in_account_table = false;
+    let mut removed = false;
+    let mut output = String::with_capacity(toml_str.len());
+
+    for line in toml_str.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_account_table = trimmed == "[account]";
+        } else if in_account_table {
+            if let Ok(table) = toml::from_str::<toml::Table>(trimmed) {
+                if keys
+                    .iter()
+                    .any(|key| table.get(*key).and_then(|value| value.as_str()) == Some(secret))
+                {
+                    removed = true;
+                    continue;
+                }
+            }
+        }
+        output.push_str(line);
+    }
+
+    if removed {
+        std::fs::write(config_path, output)?;
+    }
+    Ok(removed)
+}
+
+// Moves a plaintext password or password hash from config.toml into the keychain, and
+// falls back to the keychain when config.toml no longer contains one
+pub fn migrate_config_credential(
+    kind: CredentialKind,
+    ip: &str,
+    port: &str,
+    username: &str,
+    config_secret: Option<&str>,
+    config_path: Option<&Path>,
+) -> Option<String> {
+    match config_secret.filter(|secret| !secret.is_empty()) {
+        Some(secret) => {
+            if !username.is_empty() && store_credential(kind, ip, port, username, secret) {
+                let secret_name = match kind {
+                    CredentialKind::Password => "password",
+                    CredentialKind::PasswordMd5 => "password hash",
+                };
+
+                match config_path.map(|path| remove_config_credential(kind, path, secret)) {
+                    Some(Ok(true)) => log::info!(
+                        "Moved the account {} from config.toml to the OS keychain",
+                        secret_name
+                    ),
+                    Some(Err(error)) => log::warn!(
+                        "Stored the account {} in the OS keychain but failed to remove it from config.toml with error {}",
+                        secret_name,
+                        error
+                    ),
+                    _ => log::info!("Stored the account {} in the OS keychain", secret_name),
+                }
+            }
+            Some(secret.to_string())
+        }
+        None => load_credential(kind, ip, port, username),
+    }
+}
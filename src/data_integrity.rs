@@ -0,0 +1,195 @@
+use bevy::prelude::Resource;
+
+use rose_file_readers::{
+    IdFile, LtbFile, StbFile, StlFile, TsiFile, VfsFile, VirtualFilesystem, ZscFile,
+};
+
+use crate::{protocol::ZoneDataFileChecksum, resources::UI_DIALOG_FILENAMES, ui::widgets::Dialog};
+
+const REQUIRED_STB_FILES_IROSE: &[&str] = &[
+    "3DDATA/STB/LIST_ZONE.STB",
+    "3DDATA/STB/LIST_NPC.STB",
+    "3DDATA/STB/LIST_SKILL.STB",
+    "3DDATA/STB/LIST_QUEST.STB",
+    "3DDATA/STB/LIST_STATUS.STB",
+    "3DDATA/STB/LIST_EFFECT.STB",
+    "3DDATA/STB/LIST_SKY.STB",
+    "3DDATA/STB/LIST_CLASS.STB",
+    "3DDATA/STB/TYPE_MOTION.STB",
+    "3DDATA/STB/FILE_MOTION.STB",
+    "3DDATA/STB/LIST_WEAPON.STB",
+    "3DDATA/STB/LIST_MORPH_OBJECT.STB",
+];
+
+const REQUIRED_STL_FILES_IROSE: &[&str] = &[
+    "3DDATA/STB/LIST_ZONE_S.STL",
+    "3DDATA/STB/LIST_NPC_S.STL",
+    "3DDATA/STB/LIST_SKILL_S.STL",
+    "3DDATA/STB/LIST_QUEST_S.STL",
+    "3DDATA/STB/LIST_STATUS_S.STL",
+    "3DDATA/STB/LIST_WEAPON_S.STL",
+];
+
+const REQUIRED_ZSC_FILES_IROSE: &[&str] = &[
+    "3DDATA/SPECIAL/EVENT_OBJECT.ZSC",
+    "3DDATA/SPECIAL/LIST_DECO_SPECIAL.ZSC",
+    "3DDATA/AVATAR/LIST_MFACE.ZSC",
+    "3DDATA/AVATAR/LIST_WFACE.ZSC",
+    "3DDATA/WEAPON/LIST_WEAPON.ZSC",
+    "3DDATA/NPC/PART_NPC.ZSC",
+];
+
+const REQUIRED_LTB_FILES_IROSE: &[&str] = &["3DDATA/EVENT/ULNGTB_CON.LTB"];
+
+const REQUIRED_TSI_FILES_IROSE: &[&str] = &[
+    "3DDATA/CONTROL/RES/UI.TSI",
+    "3DDATA/CONTROL/RES/EXUI.TSI",
+    "3DDATA/CONTROL/RES/SKILLICON.TSI",
+    "3DDATA/CONTROL/RES/ITEM1.TSI",
+];
+
+const REQUIRED_ID_FILES_IROSE: &[&str] = &[
+    "3DDATA/CONTROL/XML/UI_STRID.ID",
+    "3DDATA/CONTROL/XML/EXUI_STRID.ID",
+];
+
+pub enum DataIntegrityIssue {
+    Missing { path: String },
+    Corrupt { path: String, error: String },
+}
+
+// A data file which differs from the server's copy for the current zone
+pub enum ZoneDataIssue {
+    Missing { path: String },
+    Mismatch { path: String },
+}
+
+#[derive(Resource)]
+pub struct DataIntegrityReport {
+    pub profile: Option<String>,
+    pub issues: Vec<DataIntegrityIssue>,
+}
+
+fn check_file<T>(
+    vfs: &VirtualFilesystem,
+    path: &str,
+    parse: impl FnOnce(&VirtualFilesystem, &str) -> Result<T, anyhow::Error>,
+    issues: &mut Vec<DataIntegrityIssue>,
+) {
+    if vfs.open_file(path).is_err() {
+        issues.push(DataIntegrityIssue::Missing { path: path.into() });
+    } else if let Err(error) = parse(vfs, path) {
+        issues.push(DataIntegrityIssue::Corrupt {
+            path: path.into(),
+            error: error.to_string(),
+        });
+    }
+}
+
+fn read_file_bytes(vfs: &VirtualFilesystem, path: &str) -> Result<Vec<u8>, anyhow::Error> {
+    Ok(match vfs.open_file(path)? {
+        VfsFile::Buffer(buffer) => buffer,
+        VfsFile::View(view) => view.into(),
+    })
+}
+
+fn parse_dialog(vfs: &VirtualFilesystem, path: &str) -> Result<Dialog, anyhow::Error> {
+    let bytes = read_file_bytes(vfs, path)?;
+    let bytes_str = std::str::from_utf8(&bytes)?;
+    Ok(quick_xml::de::from_str(bytes_str)?)
+}
+
+pub fn check_data_integrity(
+    vfs: &VirtualFilesystem,
+    data_version: &str,
+) -> Vec<DataIntegrityIssue> {
+    let mut issues = Vec::new();
+    if data_version != "irose" {
+        return issues;
+    }
+
+    for path in REQUIRED_STB_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<StbFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for path in REQUIRED_STL_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<StlFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for path in REQUIRED_ZSC_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<ZscFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for path in REQUIRED_LTB_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<LtbFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for path in REQUIRED_TSI_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<TsiFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for path in REQUIRED_ID_FILES_IROSE {
+        check_file(
+            vfs,
+            path,
+            |vfs, path| vfs.read_file::<IdFile, _>(path),
+            &mut issues,
+        );
+    }
+
+    for filename in UI_DIALOG_FILENAMES {
+        check_file(
+            vfs,
+            &format!("3DDATA/CONTROL/XML/{}", filename),
+            parse_dialog,
+            &mut issues,
+        );
+    }
+
+    issues
+}
+
+// Compares the checksums sent by the server with our local files, any
+// differences can cause desyncs such as ghost walls or wrong item names
+pub fn check_zone_data_checksums(
+    vfs: &VirtualFilesystem,
+    checksums: &[ZoneDataFileChecksum],
+) -> Vec<ZoneDataIssue> {
+    checksums
+        .iter()
+        .filter_map(|checksum| match read_file_bytes(vfs, &checksum.path) {
+            Err(_) => Some(ZoneDataIssue::Missing {
+                path: checksum.path.clone(),
+            }),
+            Ok(bytes) if md5::compute(bytes).0 != checksum.md5 => Some(ZoneDataIssue::Mismatch {
+                path: checksum.path.clone(),
+            }),
+            Ok(_) => None,
+        })
+        .collect()
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::Event;
+
+#[derive(Event, Clone, Debug)]
+pub enum ChatCommandEvent {
+    ClanChat(String),
+    Clear,
+    Fps,
+    Help(Option<String>),
+    Macro(String),
+    PlayerShop,
+    ReloadUi,
+    Time,
+    Where,
+}
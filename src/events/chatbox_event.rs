@@ -4,6 +4,8 @@ use bevy::prelude::Event;
 pub enum ChatboxEvent {
     Say(String, String),
     Shout(String, String),
+    Trade(String, String),
+    Clan(String, String),
     Whisper(String, String),
     Announce(Option<String>, String),
     System(String),
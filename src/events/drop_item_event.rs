@@ -0,0 +1,10 @@
+use bevy::prelude::Event;
+
+use rose_game_common::components::ItemSlot;
+
+// Drops an inventory item on the ground at the player's feet, after asking the
+// player for a quantity or confirmation
+#[derive(Event, Copy, Clone, Debug)]
+pub struct DropItemEvent {
+    pub item_slot: ItemSlot,
+}
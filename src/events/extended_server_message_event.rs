@@ -0,0 +1,8 @@
+use bevy::prelude::Event;
+
+use crate::protocol::ExtendedServerMessage;
+
+// Extended server messages are received by game_connection_system and passed on
+// to the systems of the features which handle them
+#[derive(Event)]
+pub struct ExtendedServerMessageEvent(pub ExtendedServerMessage);
@@ -1,7 +1,18 @@
 use bevy::prelude::Event;
 
+use rose_game_common::data::Password;
+
 #[derive(Event)]
 pub enum LoginEvent {
-    Login { username: String, password: String },
-    SelectServer { server_id: usize, channel_id: usize },
+    Login {
+        username: String,
+        password: Password,
+    },
+    SelectServer {
+        server_id: usize,
+        channel_id: usize,
+    },
+    SubmitChallenge {
+        code: String,
+    },
 }
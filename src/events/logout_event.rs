@@ -0,0 +1,10 @@
+use bevy::prelude::Event;
+
+// Sent once the logout countdown has finished to leave the game
+#[derive(Event, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogoutEvent {
+    ReturnToCharacterSelect,
+    SwitchCharacter,
+    Logout,
+    Exit,
+}
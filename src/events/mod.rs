@@ -1,12 +1,16 @@
 mod bank_event;
 mod character_select_event;
+mod chat_command_event;
 mod chatbox_event;
 mod clan_dialog_event;
 mod client_entity_event;
 mod conversation_dialog_event;
+mod drop_item_event;
+mod extended_server_message_event;
 mod game_connection_event;
 mod hit_event;
 mod login_event;
+mod logout_event;
 mod message_box_event;
 mod move_destination_effect_event;
 mod network_event;
@@ -16,6 +20,7 @@ mod party_event;
 mod personal_store_event;
 mod player_command_event;
 mod quest_trigger_event;
+mod report_player_event;
 mod spawn_effect_event;
 mod spawn_projectile_event;
 mod system_func_event;
@@ -25,13 +30,17 @@ mod zone_event;
 
 pub use bank_event::BankEvent;
 pub use character_select_event::CharacterSelectEvent;
+pub use chat_command_event::ChatCommandEvent;
 pub use chatbox_event::ChatboxEvent;
 pub use clan_dialog_event::ClanDialogEvent;
 pub use client_entity_event::ClientEntityEvent;
 pub use conversation_dialog_event::ConversationDialogEvent;
+pub use drop_item_event::DropItemEvent;
+pub use extended_server_message_event::ExtendedServerMessageEvent;
 pub use game_connection_event::GameConnectionEvent;
 pub use hit_event::HitEvent;
 pub use login_event::LoginEvent;
+pub use logout_event::LogoutEvent;
 pub use message_box_event::MessageBoxEvent;
 pub use move_destination_effect_event::MoveDestinationEffectEvent;
 pub use network_event::NetworkEvent;
@@ -41,6 +50,7 @@ pub use party_event::PartyEvent;
 pub use personal_store_event::PersonalStoreEvent;
 pub use player_command_event::PlayerCommandEvent;
 pub use quest_trigger_event::QuestTriggerEvent;
+pub use report_player_event::ReportPlayerEvent;
 pub use spawn_effect_event::{SpawnEffect, SpawnEffectData, SpawnEffectEvent};
 pub use spawn_projectile_event::SpawnProjectileEvent;
 pub use system_func_event::SystemFuncEvent;
@@ -1,5 +1,7 @@
 use bevy::prelude::Event;
 
+use rose_game_common::data::Password;
+
 #[derive(Event)]
 #[allow(clippy::enum_variant_names)]
 pub enum NetworkEvent {
@@ -12,13 +14,13 @@ pub enum NetworkEvent {
         port: u16,
         packet_codec_seed: u32,
         login_token: u32,
-        password: String,
+        password: Password,
     },
     ConnectGame {
         ip: String,
         port: u16,
         packet_codec_seed: u32,
         login_token: u32,
-        password: String,
+        password: Password,
     },
 }
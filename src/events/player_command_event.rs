@@ -8,7 +8,8 @@ use crate::components::Position;
 #[derive(Event, Clone)]
 pub enum PlayerCommandEvent {
     UseSkill(SkillSlot),
-    DropItem(ItemSlot),
+    DropItem(ItemSlot, usize),
+    SplitItemStack(ItemSlot, ItemSlot, usize),
     UseItem(ItemSlot),
     UseHotbar(usize, usize),
     SetHotbar(usize, usize, Option<HotbarSlot>),
@@ -0,0 +1,7 @@
+use bevy::prelude::Event;
+
+// Opens the report player dialog for the character with this name
+#[derive(Event)]
+pub struct ReportPlayerEvent {
+    pub name: String,
+}
@@ -3,6 +3,8 @@ use bevy::prelude::{Entity, Event, Transform};
 use rose_data::EffectFileId;
 use rose_file_readers::VfsPathBuf;
 
+use crate::components::DynamicPointLight;
+
 pub enum SpawnEffect {
     FileId(EffectFileId),
     Path(VfsPathBuf),
@@ -11,6 +13,7 @@ pub enum SpawnEffect {
 pub struct SpawnEffectData {
     pub effect: SpawnEffect,
     pub manual_despawn: bool,
+    pub point_light: Option<DynamicPointLight>,
 }
 
 impl SpawnEffectData {
@@ -18,6 +21,7 @@ impl SpawnEffectData {
         Self {
             effect: SpawnEffect::Path(path),
             manual_despawn: false,
+            point_light: None,
         }
     }
 
@@ -25,6 +29,7 @@ impl SpawnEffectData {
         Self {
             effect: SpawnEffect::FileId(effect_file_id),
             manual_despawn: false,
+            point_light: None,
         }
     }
 
@@ -32,6 +37,11 @@ impl SpawnEffectData {
         self.manual_despawn = manual_despawn;
         self
     }
+
+    pub fn with_point_light(mut self, point_light: DynamicPointLight) -> Self {
+        self.point_light = Some(point_light);
+        self
+    }
 }
 
 #[derive(Event)]
@@ -1,20 +1,21 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 
-use animation::RoseAnimationPlugin;
+use animation::{RoseAnimationPlugin, RoseAnimationSystem};
 use bevy::{
     core_pipeline::{bloom::BloomSettings, clear_color::ClearColor},
     ecs::event::Events,
     log::Level,
+    pbr::StandardMaterial,
     prelude::{
         apply_deferred, in_state, AddAsset, App, AssetServer, Assets, Camera, Camera3dBundle,
-        Color, Commands, IntoSystemConfigs, IntoSystemSetConfigs, Msaa, OnEnter, OnExit,
-        PluginGroup, PostStartup, PostUpdate, PreUpdate, Quat, Res, ResMut, Startup, State,
-        SystemSet, Transform, Update, Vec3,
+        Color, Commands, IVec2, IntoSystemConfigs, IntoSystemSetConfigs, Mesh, Msaa, OnEnter,
+        OnExit, OnTransition, PluginGroup, PostStartup, PostUpdate, PreUpdate, Quat, Res, ResMut,
+        Startup, State, SystemSet, Transform, Update, Vec2, Vec3,
     },
     render::{render_resource::WgpuFeatures, settings::WgpuSettings},
     transform::TransformSystem,
-    window::{Window, WindowMode},
+    window::{Cursor, CursorGrabMode, Window, WindowMode, WindowPosition},
 };
 use bevy_egui::{egui, EguiContexts, EguiSet};
 use bevy_rapier3d::plugin::PhysicsSet;
@@ -22,11 +23,18 @@ use enum_map::enum_map;
 use exe_resource_loader::{ExeResourceCursor, ExeResourceLoader};
 use serde::Deserialize;
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
-use rose_data::{CharacterMotionDatabaseOptions, NpcDatabaseOptions, ZoneId};
+use rose_data::{
+    CharacterMotionDatabaseOptions, MotionId, NpcDatabaseOptions, NpcId, SoundId, ZoneId,
+};
 use rose_file_readers::{
     AruaVfsIndex, HostFilesystemDevice, IrosePhVfsIndex, LtbFile, StbFile, TitanVfsIndex, VfsIndex,
     VirtualFilesystem, VirtualFilesystemDevice, ZscFile,
@@ -35,10 +43,14 @@ use rose_file_readers::{
 pub mod animation;
 pub mod audio;
 pub mod bundles;
+pub mod client_plugin;
 pub mod components;
+pub mod credential_store;
+pub mod data_integrity;
 pub mod effect_loader;
 pub mod events;
 pub mod exe_resource_loader;
+pub mod minigame;
 pub mod model_loader;
 pub mod protocol;
 pub mod render;
@@ -47,74 +59,113 @@ pub mod scripting;
 pub mod systems;
 pub mod ui;
 pub mod vfs_asset_io;
+pub mod vfs_mods;
 pub mod zms_asset_loader;
 pub mod zone_loader;
 
 use audio::OddioPlugin;
+use client_plugin::{add_client_plugins, ClientPlugin};
+use credential_store::CredentialKind;
+use data_integrity::{check_data_integrity, DataIntegrityIssue, DataIntegrityReport};
 use events::{
-    BankEvent, CharacterSelectEvent, ChatboxEvent, ClanDialogEvent, ClientEntityEvent,
-    ConversationDialogEvent, GameConnectionEvent, HitEvent, LoadZoneEvent, LoginEvent,
-    MessageBoxEvent, MoveDestinationEffectEvent, NetworkEvent, NpcStoreEvent,
-    NumberInputDialogEvent, PartyEvent, PersonalStoreEvent, PlayerCommandEvent, QuestTriggerEvent,
-    SpawnEffectEvent, SpawnProjectileEvent, SystemFuncEvent, UseItemEvent, WorldConnectionEvent,
-    ZoneEvent,
+    BankEvent, CharacterSelectEvent, ChatCommandEvent, ChatboxEvent, ClanDialogEvent,
+    ClientEntityEvent, ConversationDialogEvent, DropItemEvent, ExtendedServerMessageEvent,
+    GameConnectionEvent, HitEvent, LoadZoneEvent, LoginEvent, LogoutEvent, MessageBoxEvent,
+    MoveDestinationEffectEvent, NetworkEvent, NpcStoreEvent, NumberInputDialogEvent, PartyEvent,
+    PersonalStoreEvent, PlayerCommandEvent, QuestTriggerEvent, ReportPlayerEvent, SpawnEffectEvent,
+    SpawnProjectileEvent, SystemFuncEvent, UseItemEvent, WorldConnectionEvent, ZoneEvent,
 };
+use minigame::{FishingSettings, RoseMinigamePlugin};
 use model_loader::ModelLoader;
-use render::{DamageDigitMaterial, RoseRenderPlugin};
+use render::{DamageDigitMaterial, RoseRenderPlugin, TerrainMaterialQuality};
 use resources::{
-    load_ui_resources, run_network_thread, ui_requested_cursor_apply_system, update_ui_resources,
-    AppState, ClientEntityList, DamageDigitsSpawner, DebugRenderConfig, GameData, NameTagSettings,
-    NetworkThread, NetworkThreadMessage, PendingClanInvites, RenderConfiguration, SelectedTarget,
-    ServerConfiguration, SoundCache, SoundSettings, SpecularTexture, VfsResource, WorldTime,
-    ZoneTime,
+    cinematic_mode_disabled, game_data_loaded, load_ui_resources, run_network_thread,
+    store_low_power_mode_inactive, ui_requested_cursor_apply_system, update_ui_resources,
+    AccountSettings, AppState, AutoLogin, CharacterSwitch, CinematicMode, ClientCapabilities,
+    ClientEntityList, ClientEntityRegistry, ClientSettings, CombatState, ConnectionState,
+    CrowdSettings, DamageDigitsSpawner, DataProfileSelect, DataProfileSelectEntry,
+    DebugRenderConfig, DebugRenderPlayerCollision, FarmingSeed, FarmingSettings, FarmingStage,
+    GameData, HeadlessState, IgnoreList, ItemSetDatabase, ModelPool, NameTagSettings,
+    NetworkThread, NetworkThreadMessage, PartyMemberTargets, PendingClanInvites,
+    PlaceholderModelAssets, PlayerReports, PlayerSummons, PositionCorrectionStats,
+    RenderConfiguration, Replay, ReplayPlayback, ReplayRecorder, SelectedTarget,
+    ServerConfiguration, ServerMessageHandlerAppExt, ServerMessageHandlers, ServerMessageMetrics,
+    SkeletonCache, SkillResetItemDatabase, SoundCache, SoundSettings, SpecularTexture,
+    StoreLowPowerMode, TextureStreaming, UnionDatabase, UnsupportedPackets, VfsModHotReload,
+    VfsResource, WorldTime, ZoneDataVerification, ZoneNavigation, ZoneTime, ZoneTransition,
+    HEADLESS_EXIT_DATA_ERROR, HEADLESS_EXIT_DISCONNECTED,
 };
-use scripting::RoseScriptingPlugin;
+use scripting::{RoseScriptingPlugin, UserScriptManager};
 use systems::{
-    ability_values_system, animation_effect_system, animation_sound_system, auto_login_system,
-    background_music_system, character_model_add_collider_system, character_model_blink_system,
+    ability_values_system, account_settings_system, animation_effect_system,
+    animation_sound_system, auto_login_system, background_music_system, bank_message_handler,
+    character_model_add_collider_system, character_model_blink_system,
     character_model_update_system, character_select_enter_system, character_select_event_system,
     character_select_exit_system, character_select_input_system, character_select_models_system,
-    character_select_system, clan_system, client_entity_event_system, collision_height_only_system,
+    character_select_system, character_switch_system, chat_command_system, cinematic_mode_system,
+    clan_extended_message_handler, clan_message_handler, clan_system, client_entity_event_system,
+    client_entity_registry_system, client_settings_system, collision_height_only_system,
     collision_player_system, collision_player_system_join_zoin, command_system,
-    conversation_dialog_system, cooldown_system, damage_digit_render_system,
+    conversation_dialog_system, cooldown_system, crowd_density_system, damage_digit_render_system,
     debug_render_collider_system, debug_render_directional_light_system,
-    debug_render_skeleton_system, directional_light_system, effect_system, facing_direction_system,
-    free_camera_system, game_connection_system, game_mouse_input_system, game_state_enter_system,
-    game_zone_change_system, hit_event_system, item_drop_model_add_collider_system,
-    item_drop_model_system, login_connection_system, login_event_system, login_state_enter_system,
-    login_state_exit_system, login_system, model_viewer_enter_system, model_viewer_exit_system,
-    model_viewer_system, move_destination_effect_system, name_tag_system,
-    name_tag_update_color_system, name_tag_update_healthbar_system, name_tag_vehicle_height_system,
-    name_tag_visibility_system,
-    network_thread_system, npc_idle_sound_system, npc_model_add_collider_system,
-    npc_model_update_system, orbit_camera_system, particle_sequence_system,
-    passive_recovery_system, pending_damage_system, pending_skill_effect_system,
+    debug_render_skeleton_system, directional_light_system, dynamic_point_light_system,
+    effect_system, facing_direction_system, free_camera_system, game_connection_system,
+    game_mouse_input_system, game_state_enter_system, game_zone_change_system,
+    growth_object_system, headless_system, hit_event_system, interact_highlight_system,
+    item_drop_model_add_collider_system, item_drop_model_system, login_connection_system,
+    login_event_system, login_state_enter_system, login_state_exit_system, login_system,
+    logout_system, model_viewer_enter_system, model_viewer_exit_system, model_viewer_system,
+    monster_spawn_telegraph_system, move_destination_effect_system, movement_validation_system,
+    name_tag_system, name_tag_update_color_system, name_tag_update_healthbar_system,
+    name_tag_update_lifetimebar_system, name_tag_vehicle_height_system, name_tag_visibility_system,
+    network_thread_system, npc_ambient_system, npc_idle_sound_system,
+    npc_model_add_collider_system, npc_model_update_system, orbit_camera_system,
+    particle_sequence_system, party_member_system, party_target_system, passive_recovery_system,
+    pending_damage_system, pending_skill_effect_system, personal_store_message_handler,
     personal_store_model_add_collider_system, personal_store_model_system, player_command_system,
-    projectile_system, quest_trigger_system, spawn_effect_system, spawn_projectile_system,
-    status_effect_system, system_func_event_system, update_position_system, use_item_event_system,
-    vehicle_model_system, vehicle_sound_system, visible_status_effects_system,
-    world_connection_system, world_time_system, zone_time_system, zone_viewer_enter_system,
-    DebugInspectorPlugin,
+    player_visibility_system, position_correction_system, projectile_system, quest_trigger_system,
+    replay_record_system, replay_viewer_enter_system, replay_viewer_system, spawn_effect_system,
+    spawn_projectile_system, status_effect_system, store_low_power_mode_system, summon_system,
+    system_func_event_system, texture_streaming_system, update_position_system,
+    use_item_event_system, user_script_system, vehicle_model_system, vehicle_sound_system,
+    vfs_mod_hot_reload_system, visible_status_effects_system, world_connection_system,
+    world_time_system, zone_data_verification_system, zone_navigation_system, zone_time_system,
+    zone_transition_system, zone_viewer_enter_system, DebugInspectorPlugin, BANK_SERVER_MESSAGES,
+    CLAN_EXTENDED_SERVER_MESSAGES, CLAN_SERVER_MESSAGES, PERSONAL_STORE_SERVER_MESSAGES,
 };
 use ui::{
-    load_dialog_sprites_system, ui_bank_system, ui_character_create_system,
-    ui_character_info_system, ui_character_select_name_tag_system, ui_character_select_system,
-    ui_chatbox_system, ui_clan_invite_system, ui_clan_system, ui_create_clan_system, ui_debug_camera_info_system,
-    ui_debug_client_entity_list_system, ui_debug_command_viewer_system,
-    ui_debug_diagnostics_system, ui_debug_dialog_list_system, ui_debug_effect_list_system,
-    ui_debug_entity_inspector_system, ui_debug_item_list_system, ui_debug_menu_system,
-    ui_debug_npc_list_system, ui_debug_physics_system, ui_debug_render_system,
-    ui_debug_skill_list_system, ui_debug_zone_lighting_system, ui_debug_zone_list_system,
-    ui_debug_zone_time_system, ui_drag_and_drop_system, ui_game_menu_system, ui_hotbar_system,
-    ui_inventory_system, ui_item_browser_system, ui_item_drop_name_system, ui_login_system,
-    ui_message_box_system, ui_minimap_system, ui_npc_store_system, ui_number_input_dialog_system,
-    ui_party_option_system, ui_party_system, ui_personal_store_system, ui_player_info_system,
-    ui_player_shop_system, ui_quest_list_system, ui_respawn_system, ui_selected_target_system,
-    ui_server_select_system, ui_settings_system, ui_skill_list_system, ui_skill_tree_system,
-    ui_sound_event_system, ui_status_effects_system, ui_window_sound_system, widgets::Dialog,
-    DialogLoader, UiSoundEvent, UiStateDebugWindows, UiStateDragAndDrop, UiStateWindows,
+    load_dialog_sprites_system, ui_accessibility_system, ui_bank_system,
+    ui_character_create_system, ui_character_info_system, ui_character_select_name_tag_system,
+    ui_character_select_system, ui_character_stats_system, ui_chat_bubble_system,
+    ui_chatbox_system, ui_clan_invite_system, ui_clan_system, ui_compass_system,
+    ui_connection_error_system, ui_create_clan_system, ui_data_integrity_system,
+    ui_data_profile_select_system, ui_debug_camera_info_system, ui_debug_client_entity_list_system,
+    ui_debug_command_viewer_system, ui_debug_diagnostics_system, ui_debug_dialog_list_system,
+    ui_debug_effect_list_system, ui_debug_entity_inspector_system, ui_debug_item_list_system,
+    ui_debug_menu_system, ui_debug_npc_list_system, ui_debug_physics_system,
+    ui_debug_render_system, ui_debug_skill_list_system, ui_debug_unsupported_packets_system,
+    ui_debug_zone_lighting_system, ui_debug_zone_list_system, ui_debug_zone_time_system,
+    ui_drag_and_drop_system, ui_drop_item_system, ui_escape_menu_system,
+    ui_focus_navigation_system, ui_focus_navigation_text_input_system, ui_game_menu_system,
+    ui_growth_object_system, ui_hotbar_system, ui_idle_warning_system, ui_input_display_system,
+    ui_interact_prompt_system, ui_inventory_system, ui_item_browser_system,
+    ui_item_drop_name_system, ui_login_challenge_system, ui_login_queue_system, ui_login_system,
+    ui_macros_system, ui_message_box_system, ui_minimap_system, ui_mount_system,
+    ui_movement_warning_system, ui_npc_store_system, ui_number_input_dialog_system,
+    ui_party_marker_system, ui_party_option_system, ui_party_system, ui_personal_store_system,
+    ui_player_info_system, ui_player_shop_system, ui_quest_list_system, ui_replay_viewer_system,
+    ui_report_player_system, ui_respawn_system, ui_resurrection_system, ui_script_manager_system,
+    ui_selected_target_system, ui_server_select_system, ui_settings_system, ui_skill_list_system,
+    ui_skill_tree_system, ui_social_system, ui_sound_event_system, ui_status_effects_system,
+    ui_store_low_power_system, ui_subtitle_system, ui_summon_system,
+    ui_texture_memory_overlay_system, ui_tooltip_settings_system, ui_union_system,
+    ui_vehicle_garage_system, ui_warp_gate_system, ui_window_sound_system,
+    ui_zone_data_warning_system, ui_zone_title_system, ui_zone_transition_system, widgets::Dialog,
+    DialogLoader, UiFocusNavigation, UiSoundEvent, UiStateDebugWindows, UiStateDragAndDrop,
+    UiStateWindows,
 };
 use vfs_asset_io::VfsAssetIo;
+use vfs_mods::{discover_mod_layers, print_mod_conflict_report, VfsModLayer};
 use zms_asset_loader::{ZmsAssetLoader, ZmsMaterialNumFaces, ZmsNoSkinAssetLoader};
 use zone_loader::{zone_loader_system, ZoneLoader, ZoneLoaderAsset};
 
@@ -125,6 +176,11 @@ use crate::components::SoundCategory;
 pub struct AccountConfig {
     pub username: String,
     pub password: String,
+
+    // MD5 hash of the password, used instead of password when set. This is not
+    // a session token, anyone with the hash can log in as if they had the password
+    #[serde(alias = "token")]
+    pub password_md5: Option<String>,
 }
 
 #[derive(Default, Deserialize)]
@@ -134,9 +190,11 @@ pub struct AutoLoginConfig {
     pub channel_id: Option<usize>,
     pub server_id: Option<usize>,
     pub character_name: Option<String>,
+    pub zone_id: Option<u16>,
+    pub position: Option<[f32; 2]>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 #[serde(tag = "type", content = "path")]
 pub enum FilesystemDeviceConfig {
     #[serde(rename = "vfs")]
@@ -151,16 +209,112 @@ pub enum FilesystemDeviceConfig {
     IrosePh(String),
 }
 
-#[derive(Default, Deserialize)]
+impl FilesystemDeviceConfig {
+    pub fn path(&self) -> &str {
+        match self {
+            FilesystemDeviceConfig::Vfs(path)
+            | FilesystemDeviceConfig::Directory(path)
+            | FilesystemDeviceConfig::AruaVfs(path)
+            | FilesystemDeviceConfig::TitanVfs(path)
+            | FilesystemDeviceConfig::IrosePh(path) => path,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct FilesystemModsConfig {
+    pub enabled: bool,
+    pub path: String,
+    pub hot_reload: bool,
+
+    // Layers with a higher priority override those with a lower priority, layers
+    // not listed here have priority 0 and are then ordered by name
+    pub priority: HashMap<String, i32>,
+}
+
+impl Default for FilesystemModsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "mods".into(),
+            hot_reload: false,
+            priority: HashMap::default(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
 #[serde(default)]
 pub struct FilesystemConfig {
     pub devices: Vec<FilesystemDeviceConfig>,
+    pub mods: FilesystemModsConfig,
+
+    // When set all game data is read from this extracted directory and devices are ignored
+    pub unpacked_path: Option<String>,
+
+    // Directory for generated data such as zone navigation grids
+    pub cache_path: String,
+}
+
+impl Default for FilesystemConfig {
+    fn default() -> Self {
+        Self {
+            devices: Vec::new(),
+            mods: FilesystemModsConfig::default(),
+            unpacked_path: None,
+            cache_path: "cache".into(),
+        }
+    }
 }
 
 impl FilesystemConfig {
+    pub fn mod_layers(&self) -> Vec<VfsModLayer> {
+        if self.mods.enabled {
+            discover_mod_layers(Path::new(&self.mods.path), &self.mods.priority)
+        } else {
+            Vec::new()
+        }
+    }
+
     pub fn create_virtual_filesystem(&self) -> Option<Arc<VirtualFilesystem>> {
+        self.create_layered_virtual_filesystem(&self.mod_layers())
+    }
+
+    pub fn create_base_virtual_filesystem(&self) -> Option<Arc<VirtualFilesystem>> {
+        self.create_layered_virtual_filesystem(&[])
+    }
+
+    fn create_layered_virtual_filesystem(
+        &self,
+        mod_layers: &[VfsModLayer],
+    ) -> Option<Arc<VirtualFilesystem>> {
         let mut vfs_devices: Vec<Box<dyn VirtualFilesystemDevice + Send + Sync>> = Vec::new();
-        for device_config in self.devices.iter() {
+
+        // Devices are searched in order, so mods must come before the game data
+        for layer in mod_layers.iter() {
+            log::info!(
+                "Loading mod layer {} (priority {}) from {}",
+                layer.name,
+                layer.priority,
+                layer.path.to_string_lossy()
+            );
+            vfs_devices.push(Box::new(HostFilesystemDevice::new(layer.path.clone())));
+        }
+        let num_mod_devices = vfs_devices.len();
+
+        let devices = if let Some(unpacked_path) = self.unpacked_path.as_ref() {
+            log::info!(
+                "Loading game data from unpacked directory {}",
+                unpacked_path
+            );
+            vfs_devices.push(Box::new(HostFilesystemDevice::new(unpacked_path.into())));
+            &[][..]
+        } else {
+            self.devices.as_slice()
+        };
+
+        for device_config in devices.iter() {
             match device_config {
                 FilesystemDeviceConfig::Directory(path) => {
                     log::info!("Loading game data from host directory {}", path);
@@ -234,7 +388,7 @@ impl FilesystemConfig {
             }
         }
 
-        if vfs_devices.is_empty() {
+        if vfs_devices.len() == num_mod_devices {
             None
         } else {
             Some(Arc::new(VirtualFilesystem::new(vfs_devices)))
@@ -247,6 +401,7 @@ impl FilesystemConfig {
 pub struct ServerConfig {
     pub ip: String,
     pub port: u16,
+    pub account_settings_sync: bool,
 }
 
 impl Default for ServerConfig {
@@ -254,6 +409,7 @@ impl Default for ServerConfig {
         Self {
             ip: "127.0.0.1".into(),
             port: 29000,
+            account_settings_sync: false,
         }
     }
 }
@@ -281,10 +437,21 @@ impl Default for GameConfig {
 pub enum GraphicsModeConfig {
     #[serde(rename = "window")]
     Window { width: f32, height: f32 },
+    #[serde(rename = "borderless")]
+    Borderless { width: f32, height: f32 },
     #[serde(rename = "fullscreen")]
     Fullscreen,
 }
 
+#[derive(Clone, Copy, Default, Deserialize)]
+pub enum TerrainQualityConfig {
+    #[default]
+    #[serde(rename = "standard")]
+    Standard,
+    #[serde(rename = "high")]
+    High,
+}
+
 #[derive(Deserialize)]
 #[serde(default)]
 pub struct GraphicsConfig {
@@ -292,6 +459,13 @@ pub struct GraphicsConfig {
     pub passthrough_terrain_textures: bool,
     pub trail_effect_duration_multiplier: f32,
     pub disable_vsync: bool,
+    pub max_dynamic_point_lights: usize,
+    pub terrain_quality: TerrainQualityConfig,
+    pub terrain_detail_texture: Option<String>,
+    pub window_position: Option<[i32; 2]>,
+    pub confine_cursor: bool,
+    pub texture_memory_budget_mb: Option<usize>,
+    pub texture_high_detail_distance: f32,
 }
 
 impl Default for GraphicsConfig {
@@ -301,6 +475,13 @@ impl Default for GraphicsConfig {
             passthrough_terrain_textures: false,
             trail_effect_duration_multiplier: 1.0,
             disable_vsync: false,
+            max_dynamic_point_lights: 8,
+            terrain_quality: TerrainQualityConfig::Standard,
+            terrain_detail_texture: None,
+            window_position: None,
+            confine_cursor: false,
+            texture_memory_budget_mb: None,
+            texture_high_detail_distance: 100.0,
         }
     }
 }
@@ -338,6 +519,8 @@ impl Default for SoundVolumeConfig {
 pub struct SoundConfig {
     pub enabled: bool,
     pub volume: SoundVolumeConfig,
+    // Sound played when an item is dropped on a slot which does not accept it
+    pub invalid_drop_sound: Option<u16>,
 }
 
 impl Default for SoundConfig {
@@ -345,20 +528,127 @@ impl Default for SoundConfig {
         Self {
             enabled: true,
             volume: SoundVolumeConfig::default(),
+            invalid_drop_sound: None,
         }
     }
 }
 
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ScriptsConfig {
+    pub enabled: bool,
+    pub path: String,
+
+    // Names of scripts which should not be loaded at startup
+    pub disabled: Vec<String>,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "scripts".into(),
+            disabled: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct FishingConfig {
+    // Weapon item numbers which can be used as a fishing rod
+    pub rod_items: Vec<usize>,
+    pub cast_motion: Option<u16>,
+    pub reel_motion: Option<u16>,
+    pub cast_sound: Option<u16>,
+    pub bite_sound: Option<u16>,
+    pub catch_sound: Option<u16>,
+    pub escape_sound: Option<u16>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct FarmingSeedConfig {
+    // Consumable item number of the seed
+    pub item: usize,
+    // Npc spawned by the server for each growth stage, the last stage can be harvested
+    pub stage_npcs: Vec<u16>,
+    // Seconds until the server replaces each stage with the next one
+    pub stage_seconds: Vec<u64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct FarmingConfig {
+    pub seeds: Vec<FarmingSeedConfig>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct NameTagConfig {
+    // Npc types from LIST_NPC.STB shown with an elite or boss icon on their name tag
+    pub elite_npc_types: Vec<u16>,
+    pub boss_npc_types: Vec<u16>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct DataProfileConfig {
+    pub name: String,
+    pub devices: Vec<FilesystemDeviceConfig>,
+    pub data_version: Option<String>,
+}
+
 #[derive(Default, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub account: AccountConfig,
     pub auto_login: AutoLoginConfig,
+    pub farming: FarmingConfig,
     pub filesystem: FilesystemConfig,
+    pub fishing: FishingConfig,
     pub game: GameConfig,
     pub graphics: GraphicsConfig,
+    pub name_tags: NameTagConfig,
     pub server: ServerConfig,
+    pub scripts: ScriptsConfig,
     pub sound: SoundConfig,
+    pub profile: Option<String>,
+    pub profiles: Vec<DataProfileConfig>,
+
+    // Set from --config-profile, shown in the window title to tell instances apart
+    #[serde(skip)]
+    pub config_profile: Option<String>,
+
+    // The file this was loaded from, used to remove secrets once they are in the OS keychain
+    #[serde(skip)]
+    pub config_path: Option<PathBuf>,
+}
+
+impl Config {
+    // Replaces the filesystem devices with those from the named profile
+    pub fn apply_profile(&mut self, name: &str) -> bool {
+        let Some(profile_index) = self
+            .profiles
+            .iter()
+            .position(|profile| profile.name.eq_ignore_ascii_case(name))
+        else {
+            return false;
+        };
+        let profile = self.profiles.remove(profile_index);
+
+        log::info!("Using game data profile {}", profile.name);
+        self.filesystem.devices = profile.devices;
+        if let Some(data_version) = profile.data_version {
+            self.game.data_version = data_version;
+        }
+        self.profile = Some(profile.name);
+        true
+    }
+
+    // With several profiles and none given the profile is chosen on the profile screen
+    pub fn needs_profile_select(&self) -> bool {
+        self.profile.is_none() && !self.profiles.is_empty()
+    }
 }
 
 pub fn load_config(path: &Path) -> Config {
@@ -374,9 +664,10 @@ pub fn load_config(path: &Path) -> Config {
         }
     };
 
-    match toml::from_str(&toml_str) {
-        Ok(config) => {
+    match toml::from_str::<Config>(&toml_str) {
+        Ok(mut config) => {
             println!("Read configuration from {}", path.to_string_lossy());
+            config.config_path = Some(path.to_path_buf());
             config
         }
         Err(error) => {
@@ -390,16 +681,55 @@ pub fn load_config(path: &Path) -> Config {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct HeadlessConfig {
+    pub run_duration: f32,
+    pub timeout: f32,
+}
+
 #[derive(Default)]
 pub struct SystemsConfig {
     pub disable_player_command_system: bool,
     pub add_custom_systems: Option<Box<dyn FnOnce(&mut App)>>,
+    pub headless: Option<HeadlessConfig>,
+    pub client_plugins: Vec<Box<dyn ClientPlugin>>,
+    pub record_replay: Option<PathBuf>,
 }
 
 pub fn run_game(config: &Config, systems_config: SystemsConfig) {
     run_client(config, AppState::GameLogin, systems_config);
 }
 
+// Runs without a window or renderer, returns the exit code for the process
+pub fn run_headless(config: &Config, headless_config: HeadlessConfig) -> i32 {
+    let exit_code = Arc::new(AtomicI32::new(HEADLESS_EXIT_DISCONNECTED));
+    let headless_exit_code = exit_code.clone();
+
+    let started = run_client(
+        config,
+        AppState::GameLogin,
+        SystemsConfig {
+            add_custom_systems: Some(Box::new(move |app| {
+                app.insert_resource(HeadlessState {
+                    run_duration: headless_config.run_duration,
+                    timeout: headless_config.timeout,
+                    entered_game_time: None,
+                    exit_code: headless_exit_code,
+                })
+                .add_systems(Update, headless_system);
+            })),
+            headless: Some(headless_config),
+            ..Default::default()
+        },
+    );
+
+    if !started {
+        return HEADLESS_EXIT_DATA_ERROR;
+    }
+
+    exit_code.load(Ordering::SeqCst)
+}
+
 pub fn run_model_viewer(config: &Config) {
     run_client(config, AppState::ModelViewer, SystemsConfig::default());
 }
@@ -421,6 +751,54 @@ pub fn run_zone_viewer(config: &Config, zone_id: Option<ZoneId>) {
     );
 }
 
+pub fn run_replay_viewer(config: &Config, path: &Path) {
+    let replay = match Replay::read(path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            println!(
+                "Failed to read replay {} with error: {}",
+                path.to_string_lossy(),
+                error
+            );
+            return;
+        }
+    };
+
+    run_client(
+        config,
+        AppState::ReplayViewer,
+        SystemsConfig {
+            add_custom_systems: Some(Box::new(move |app| {
+                app.insert_resource(ReplayPlayback::new(replay));
+            })),
+            ..Default::default()
+        },
+    );
+}
+
+pub fn run_mod_conflict_report(config: &Config) {
+    let base_vfs = config.filesystem.create_base_virtual_filesystem();
+    print_mod_conflict_report(&config.filesystem.mod_layers(), base_vfs.as_deref());
+}
+
+fn run_data_integrity_error(data_integrity_report: DataIntegrityReport) {
+    App::new()
+        .add_plugins((
+            bevy::prelude::DefaultPlugins.set(bevy::window::WindowPlugin {
+                primary_window: Some(Window {
+                    title: "rose-offline-client".to_string(),
+                    resolution: bevy::window::WindowResolution::new(1024.0, 768.0),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            bevy_egui::EguiPlugin,
+        ))
+        .insert_resource(data_integrity_report)
+        .add_systems(Update, ui_data_integrity_system)
+        .run();
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
 enum GameStages {
     ZoneChange,
@@ -437,7 +815,7 @@ enum GameSystemSets {
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
-enum UiSystemSets {
+pub enum UiSystemSets {
     UiDebugMenu,
     UiFirst,
     Ui,
@@ -445,31 +823,150 @@ enum UiSystemSets {
     UiDebug,
 }
 
-fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsConfig) {
-    let virtual_filesystem =
-        if let Some(virtual_filesystem) = config.filesystem.create_virtual_filesystem() {
-            virtual_filesystem
-        } else {
-            log::error!("No filesystem devices");
-            return;
-        };
+// Returns false when the client could not be started because the game data is missing or corrupt
+fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsConfig) -> bool {
+    let virtual_filesystem = if config.needs_profile_select() {
+        // The game data is mounted once a profile has been chosen on the profile screen
+        None
+    } else if let Some(virtual_filesystem) = config.filesystem.create_virtual_filesystem() {
+        Some(virtual_filesystem)
+    } else {
+        eprintln!("No filesystem devices");
+        return false;
+    };
 
-    let (window_width, window_height) =
-        if let GraphicsModeConfig::Window { width, height } = config.graphics.mode {
-            (width, height)
-        } else {
-            (1920.0, 1080.0)
-        };
+    if let Some(virtual_filesystem) = virtual_filesystem.as_ref() {
+        let data_integrity_issues =
+            check_data_integrity(virtual_filesystem, &config.game.data_version);
+        if !data_integrity_issues.is_empty() {
+            // The logger is not installed until the app is built, so print directly
+            // to stderr where headless runs in CI will see it
+            for issue in data_integrity_issues.iter() {
+                match issue {
+                    DataIntegrityIssue::Missing { path } => {
+                        eprintln!("Missing required game data file {}", path)
+                    }
+                    DataIntegrityIssue::Corrupt { path, error } => {
+                        eprintln!("Failed to read game data file {}: {}", path, error)
+                    }
+                }
+            }
+
+            if systems_config.headless.is_none() {
+                run_data_integrity_error(DataIntegrityReport {
+                    profile: config.profile.clone(),
+                    issues: data_integrity_issues,
+                });
+            }
+            return false;
+        }
+    }
+
+    let (window_width, window_height) = match config.graphics.mode {
+        GraphicsModeConfig::Window { width, height }
+        | GraphicsModeConfig::Borderless { width, height } => (width, height),
+        GraphicsModeConfig::Fullscreen => (1920.0, 1080.0),
+    };
 
     let mut app = App::new();
 
     // Must Initialise asset server before asset plugin
-    app.insert_resource(VfsResource {
-        vfs: virtual_filesystem.clone(),
-    })
-    .insert_resource(AssetServer::new(VfsAssetIo::new(virtual_filesystem)));
+    let data_profile_select = if let Some(virtual_filesystem) = virtual_filesystem {
+        app.insert_resource(VfsResource {
+            vfs: virtual_filesystem.clone(),
+        })
+        .insert_resource(AssetServer::new(VfsAssetIo::new(virtual_filesystem)));
+        None
+    } else {
+        let (asset_io, asset_io_mount) = VfsAssetIo::unmounted();
+        app.insert_resource(AssetServer::new(asset_io));
+        Some(DataProfileSelect {
+            profiles: config
+                .profiles
+                .iter()
+                .cloned()
+                .map(DataProfileSelectEntry::new)
+                .collect(),
+            filesystem: config.filesystem.clone(),
+            data_version: config.game.data_version.clone(),
+            next_state: app_state,
+            asset_io_mount,
+            mount_error: None,
+            data_integrity_report: None,
+        })
+    };
+
+    if config.filesystem.mods.hot_reload {
+        app.insert_resource(VfsModHotReload::new(config.filesystem.mod_layers()))
+            .add_systems(Update, vfs_mod_hot_reload_system);
+    }
 
     // Initialise bevy engine
+    let headless = systems_config.headless.is_some();
+    let mut default_plugins = bevy::prelude::DefaultPlugins
+        .set(bevy::render::RenderPlugin {
+            wgpu_settings: WgpuSettings {
+                features: WgpuFeatures::TEXTURE_COMPRESSION_BC,
+                // backends: Some(Backends::DX12),
+                // Without any backends the render app is never created
+                backends: if headless {
+                    None
+                } else {
+                    WgpuSettings::default().backends
+                },
+                ..Default::default()
+            },
+        })
+        .set(bevy::window::WindowPlugin {
+            // In headless mode we still need a primary window entity for egui,
+            // but without winit it is never created on screen
+            primary_window: Some(Window {
+                title: match config.config_profile.as_ref() {
+                    Some(config_profile) => format!("rose-offline-client ({})", config_profile),
+                    None => "rose-offline-client".to_string(),
+                },
+                present_mode: if config.graphics.disable_vsync {
+                    bevy::window::PresentMode::Immediate
+                } else {
+                    bevy::window::PresentMode::Fifo
+                },
+                resolution: bevy::window::WindowResolution::new(window_width, window_height),
+                mode: if matches!(config.graphics.mode, GraphicsModeConfig::Fullscreen) {
+                    WindowMode::BorderlessFullscreen
+                } else {
+                    WindowMode::Windowed
+                },
+                decorations: !matches!(config.graphics.mode, GraphicsModeConfig::Borderless { .. }),
+                position: config
+                    .graphics
+                    .window_position
+                    .map_or(WindowPosition::Automatic, |[x, y]| {
+                        WindowPosition::At(IVec2::new(x, y))
+                    }),
+                cursor: Cursor {
+                    grab_mode: if config.graphics.confine_cursor {
+                        CursorGrabMode::Confined
+                    } else {
+                        CursorGrabMode::None
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .set(bevy::log::LogPlugin {
+            level: Level::INFO,
+            filter: "wgpu=error,packets=debug,quest=trace,lua=debug,con=trace,animation=info"
+                .to_string(),
+        })
+        .set(bevy::pbr::PbrPlugin {
+            prepass_enabled: false,
+        });
+    if headless {
+        default_plugins = default_plugins.disable::<bevy::winit::WinitPlugin>();
+    }
+
     app.insert_resource(Msaa::Sample4)
         .insert_resource(ClearColor(Color::rgb(0.70, 0.90, 1.0)))
         .insert_resource(bevy::gizmos::GizmoConfig {
@@ -477,48 +974,17 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             ..Default::default()
         })
         .add_plugins((
-            bevy::prelude::DefaultPlugins
-                .set(bevy::render::RenderPlugin {
-                    wgpu_settings: WgpuSettings {
-                        features: WgpuFeatures::TEXTURE_COMPRESSION_BC,
-                        // backends: Some(Backends::DX12),
-                        ..Default::default()
-                    },
-                })
-                .set(bevy::window::WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "rose-offline-client".to_string(),
-                        present_mode: if config.graphics.disable_vsync {
-                            bevy::window::PresentMode::Immediate
-                        } else {
-                            bevy::window::PresentMode::Fifo
-                        },
-                        resolution: bevy::window::WindowResolution::new(
-                            window_width,
-                            window_height,
-                        ),
-                        mode: if matches!(config.graphics.mode, GraphicsModeConfig::Fullscreen) {
-                            WindowMode::BorderlessFullscreen
-                        } else {
-                            WindowMode::Windowed
-                        },
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                })
-                .set(bevy::log::LogPlugin {
-                    level: Level::INFO,
-                    filter:
-                        "wgpu=error,packets=debug,quest=trace,lua=debug,con=trace,animation=info"
-                            .to_string(),
-                })
-                .set(bevy::pbr::PbrPlugin {
-                    prepass_enabled: false,
-                }),
+            default_plugins,
             bevy::diagnostic::EntityCountDiagnosticsPlugin,
             bevy::diagnostic::FrameTimeDiagnosticsPlugin,
         ));
 
+    if headless {
+        app.add_plugins(bevy::app::ScheduleRunnerPlugin::run_loop(
+            Duration::from_secs_f64(1.0 / 60.0),
+        ));
+    }
+
     // Initialise 3rd party bevy plugins
     app.insert_resource(bevy_rapier3d::prelude::RapierConfiguration {
         physics_pipeline_active: false,
@@ -536,6 +1002,35 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         OddioPlugin,
     ));
 
+    let client_settings =
+        ClientSettings::load(Path::new(&config.filesystem.cache_path).join("client_settings.toml"));
+    let server_port = format!("{}", config.server.port);
+    let (preset_password, preset_password_md5) = if client_settings.login.use_os_keychain {
+        (
+            credential_store::migrate_config_credential(
+                CredentialKind::Password,
+                &config.server.ip,
+                &server_port,
+                &config.account.username,
+                Some(config.account.password.as_str()),
+                config.config_path.as_deref(),
+            ),
+            credential_store::migrate_config_credential(
+                CredentialKind::PasswordMd5,
+                &config.server.ip,
+                &server_port,
+                &config.account.username,
+                config.account.password_md5.as_deref(),
+                config.config_path.as_deref(),
+            ),
+        )
+    } else {
+        (
+            Some(config.account.password.clone()),
+            config.account.password_md5.clone(),
+        )
+    };
+
     // Initialise rose stuff
     app.init_asset_loader::<ZmsAssetLoader>()
         .init_asset_loader::<ZmsNoSkinAssetLoader>()
@@ -548,15 +1043,84 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         .insert_resource(RenderConfiguration {
             passthrough_terrain_textures: config.graphics.passthrough_terrain_textures,
             trail_effect_duration_multiplier: config.graphics.trail_effect_duration_multiplier,
+            max_dynamic_point_lights: config.graphics.max_dynamic_point_lights,
+            terrain_quality: match config.graphics.terrain_quality {
+                TerrainQualityConfig::Standard => TerrainMaterialQuality::Standard,
+                TerrainQualityConfig::High => TerrainMaterialQuality::High,
+            },
+            terrain_detail_texture: config.graphics.terrain_detail_texture.clone(),
+            confine_cursor: config.graphics.confine_cursor,
         })
+        .insert_resource(TextureStreaming::new(
+            config
+                .graphics
+                .texture_memory_budget_mb
+                .map(|budget_mb| budget_mb * 1024 * 1024),
+            config.graphics.texture_high_detail_distance,
+        ))
+        .insert_resource(client_settings)
+        .insert_resource(AccountSettings::new(
+            config.server.account_settings_sync,
+            Path::new(&config.filesystem.cache_path).join("account_settings"),
+        ))
+        .insert_resource(FarmingSettings {
+            seeds: config
+                .farming
+                .seeds
+                .iter()
+                .map(|seed| FarmingSeed {
+                    item_number: seed.item,
+                    stages: seed
+                        .stage_npcs
+                        .iter()
+                        .filter_map(|npc| NpcId::new(*npc))
+                        .enumerate()
+                        .map(|(index, npc_id)| FarmingStage {
+                            npc_id,
+                            duration: seed
+                                .stage_seconds
+                                .get(index)
+                                .map(|seconds| Duration::from_secs(*seconds)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        })
+        .insert_resource(FishingSettings {
+            rod_items: config.fishing.rod_items.clone(),
+            cast_motion: config.fishing.cast_motion.map(MotionId::new),
+            reel_motion: config.fishing.reel_motion.map(MotionId::new),
+            cast_sound: config.fishing.cast_sound.and_then(SoundId::new),
+            bite_sound: config.fishing.bite_sound.and_then(SoundId::new),
+            catch_sound: config.fishing.catch_sound.and_then(SoundId::new),
+            escape_sound: config.fishing.escape_sound.and_then(SoundId::new),
+        })
+        .insert_resource(UiStateDragAndDrop {
+            dragged_item: None,
+            invalid_drop_sound: config.sound.invalid_drop_sound.and_then(SoundId::new),
+        })
+        .insert_resource(NameTagSettings {
+            elite_npc_types: config.name_tags.elite_npc_types.clone(),
+            boss_npc_types: config.name_tags.boss_npc_types.clone(),
+            ..Default::default()
+        })
+        .insert_resource(ZoneNavigation::new(
+            Path::new(&config.filesystem.cache_path).join("navigation"),
+        ))
+        .insert_resource(PlayerReports::new(
+            Path::new(&config.filesystem.cache_path).join("player_reports.toml"),
+        ))
         .insert_resource(ServerConfiguration {
             ip: config.server.ip.clone(),
-            port: format!("{}", config.server.port),
+            port: server_port,
             preset_username: Some(config.account.username.clone()),
-            preset_password: Some(config.account.password.clone()),
+            preset_password,
+            preset_password_md5,
             preset_server_id: config.auto_login.server_id,
             preset_channel_id: config.auto_login.channel_id,
             preset_character_name: config.auto_login.character_name.clone(),
+            preset_zone_id: config.auto_login.zone_id.and_then(ZoneId::new),
+            preset_position: config.auto_login.position.map(|[x, y]| Vec2::new(x, y)),
             auto_login: config.auto_login.enabled,
         })
         .insert_resource(SoundSettings {
@@ -576,22 +1140,31 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             RoseAnimationPlugin,
             RoseRenderPlugin,
             RoseScriptingPlugin,
+            RoseMinigamePlugin,
             DebugInspectorPlugin,
         ));
 
     // Setup state
     app.add_state::<AppState>()
-        .insert_resource(State::new(app_state));
+        .insert_resource(State::new(if data_profile_select.is_some() {
+            AppState::DataProfileSelect
+        } else {
+            app_state
+        }));
 
     app.add_event::<BankEvent>()
+        .add_event::<ChatCommandEvent>()
         .add_event::<ChatboxEvent>()
         .add_event::<CharacterSelectEvent>()
         .add_event::<ClanDialogEvent>()
         .add_event::<ClientEntityEvent>()
         .add_event::<ConversationDialogEvent>()
+        .add_event::<DropItemEvent>()
+        .add_event::<ExtendedServerMessageEvent>()
         .add_event::<GameConnectionEvent>()
         .add_event::<HitEvent>()
         .add_event::<LoginEvent>()
+        .add_event::<LogoutEvent>()
         .add_event::<LoadZoneEvent>()
         .add_event::<MessageBoxEvent>()
         .add_event::<MoveDestinationEffectEvent>()
@@ -602,6 +1175,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         .add_event::<PersonalStoreEvent>()
         .add_event::<PlayerCommandEvent>()
         .add_event::<QuestTriggerEvent>()
+        .add_event::<ReportPlayerEvent>()
         .add_event::<SystemFuncEvent>()
         .add_event::<SpawnEffectEvent>()
         .add_event::<SpawnProjectileEvent>()
@@ -672,40 +1246,68 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
                 update_ui_resources,
                 spawn_effect_system,
                 move_destination_effect_system.after(game_mouse_input_system),
+                npc_ambient_system.after(command_system),
+                crowd_density_system.before(spawn_effect_system),
+                player_visibility_system.run_if(in_state(AppState::Game)),
                 npc_idle_sound_system,
                 name_tag_system,
                 name_tag_visibility_system.after(game_mouse_input_system),
                 name_tag_update_color_system,
+                name_tag_update_lifetimebar_system,
                 world_time_system,
                 system_func_event_system,
                 load_dialog_sprites_system,
                 zone_time_system.after(world_time_system),
                 directional_light_system,
+                dynamic_point_light_system.after(zone_time_system),
+                texture_streaming_system,
+                zone_navigation_system,
             ),
-        ),
+        )
+            .run_if(game_data_loaded),
     );
 
     app.add_systems(
         PostUpdate,
-        ui_requested_cursor_apply_system.after(EguiSet::ProcessOutput),
+        ui_requested_cursor_apply_system
+            .after(EguiSet::ProcessOutput)
+            .run_if(game_data_loaded),
+    );
+
+    app.init_resource::<UiFocusNavigation>().add_systems(
+        PreUpdate,
+        ui_focus_navigation_system
+            .after(EguiSet::ProcessInput)
+            .before(EguiSet::BeginFrame)
+            .run_if(game_data_loaded),
     );
 
     app.add_systems(
         Update,
-        ui_item_drop_name_system.in_set(UiSystemSets::UiFirst),
+        (ui_item_drop_name_system, ui_tooltip_settings_system).in_set(UiSystemSets::UiFirst),
     );
 
     app.add_systems(
         Update,
-        (ui_message_box_system, ui_number_input_dialog_system).in_set(UiSystemSets::UiLast),
+        (
+            ui_connection_error_system,
+            ui_message_box_system,
+            ui_number_input_dialog_system,
+            ui_texture_memory_overlay_system,
+        )
+            .in_set(UiSystemSets::UiLast),
     );
     app.add_systems(
         Update,
         (
             ui_window_sound_system.before(ui_sound_event_system),
             ui_sound_event_system,
+            ui_focus_navigation_text_input_system,
+            ui_accessibility_system,
+            ui_subtitle_system,
         )
-            .after(UiSystemSets::UiLast),
+            .after(UiSystemSets::UiLast)
+            .run_if(game_data_loaded),
     );
     app.add_systems(
         Update,
@@ -726,6 +1328,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             ui_debug_physics_system,
             ui_debug_render_system,
             ui_debug_skill_list_system,
+            ui_debug_unsupported_packets_system,
             ui_debug_zone_lighting_system,
             ui_debug_zone_list_system,
             ui_debug_zone_time_system,
@@ -736,7 +1339,10 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
 
     // character_model_blink_system in PostUpdate to avoid any conflicts with model destruction
     // e.g. through the character select exit system.
-    app.add_systems(PostUpdate, character_model_blink_system);
+    app.add_systems(
+        PostUpdate,
+        character_model_blink_system.run_if(game_data_loaded),
+    );
 
     // vehicle_model_system in after ::Update but before ::PostUpdate to avoid any conflicts,
     // with model destruction but to also be before global transform is calculated.
@@ -779,6 +1385,19 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
     // Zone Viewer
     app.add_systems(OnEnter(AppState::ZoneViewer), zone_viewer_enter_system);
 
+    // Replay Viewer
+    app.add_systems(OnEnter(AppState::ReplayViewer), replay_viewer_enter_system)
+        .add_systems(
+            Update,
+            replay_viewer_system.run_if(in_state(AppState::ReplayViewer)),
+        )
+        .add_systems(
+            Update,
+            ui_replay_viewer_system
+                .run_if(in_state(AppState::ReplayViewer))
+                .in_set(UiSystemSets::Ui),
+        );
+
     // Model Viewer, we avoid deleting any entities during CoreStage::Update by using a custom
     // stage which runs after Update. We cannot run before Update because the on_enter system
     // below will have not run yet.
@@ -803,7 +1422,12 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
 
     app.add_systems(
         Update,
-        (ui_login_system, ui_server_select_system)
+        (
+            ui_login_challenge_system,
+            ui_login_queue_system,
+            ui_login_system,
+            ui_server_select_system,
+        )
             .run_if(in_state(AppState::GameLogin))
             .in_set(UiSystemSets::Ui)
             .after(login_system)
@@ -827,6 +1451,9 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             character_select_input_system,
             character_select_models_system,
             character_select_event_system,
+            character_switch_system
+                .after(character_select_system)
+                .before(character_select_event_system),
         )
             .run_if(in_state(AppState::GameCharacterSelect)),
     );
@@ -846,16 +1473,47 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
     );
 
     // Game
-    app.init_resource::<UiStateDragAndDrop>()
-        .init_resource::<UiStateWindows>()
+    app.init_resource::<UiStateWindows>()
         .init_resource::<UiStateDebugWindows>()
         .init_resource::<ClientEntityList>()
+        .init_resource::<ClientEntityRegistry>()
+        .init_resource::<AutoLogin>()
+        .init_resource::<CharacterSwitch>()
+        .init_resource::<ConnectionState>()
+        .init_resource::<ClientCapabilities>()
         .init_resource::<DebugRenderConfig>()
+        .init_resource::<DebugRenderPlayerCollision>()
         .init_resource::<WorldTime>()
+        .init_resource::<ZoneDataVerification>()
         .init_resource::<ZoneTime>()
         .init_resource::<SelectedTarget>()
-        .init_resource::<NameTagSettings>()
-        .init_resource::<PendingClanInvites>();
+        .init_resource::<IgnoreList>()
+        .init_resource::<CinematicMode>()
+        .init_resource::<StoreLowPowerMode>()
+        .init_resource::<CrowdSettings>()
+        .init_resource::<CombatState>()
+        .init_resource::<ModelPool>()
+        .init_resource::<PendingClanInvites>()
+        .init_resource::<PlayerSummons>()
+        .init_resource::<PositionCorrectionStats>()
+        .init_resource::<ServerMessageHandlers>()
+        .init_resource::<ServerMessageMetrics>()
+        .init_resource::<UnsupportedPackets>()
+        .init_resource::<PartyMemberTargets>()
+        .init_resource::<ZoneTransition>();
+
+    for kind in BANK_SERVER_MESSAGES {
+        app.add_server_message_handler(kind, bank_message_handler);
+    }
+    for kind in CLAN_SERVER_MESSAGES {
+        app.add_server_message_handler(kind, clan_message_handler);
+    }
+    for kind in CLAN_EXTENDED_SERVER_MESSAGES {
+        app.add_extended_server_message_handler(kind, clan_extended_message_handler);
+    }
+    for kind in PERSONAL_STORE_SERVER_MESSAGES {
+        app.add_server_message_handler(kind, personal_store_message_handler);
+    }
 
     app.add_systems(OnEnter(AppState::Game), game_state_enter_system);
 
@@ -877,15 +1535,35 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             collision_player_system.after(update_position_system),
             cooldown_system.before(GameSystemSets::Ui),
             client_entity_event_system.before(spawn_effect_system),
+            growth_object_system.after(npc_model_update_system),
+            monster_spawn_telegraph_system.after(npc_model_update_system),
+            interact_highlight_system.after(game_mouse_input_system),
             use_item_event_system.before(spawn_effect_system),
             status_effect_system,
+            party_member_system,
             passive_recovery_system,
             quest_trigger_system,
+            zone_transition_system.after(GameStages::ZoneChange),
             game_mouse_input_system.after(GameSystemSets::Ui),
         )
             .run_if(in_state(AppState::Game)),
     );
 
+    app.add_systems(
+        Update,
+        (
+            chat_command_system,
+            movement_validation_system
+                .after(ability_values_system)
+                .before(update_position_system),
+            position_correction_system
+                .after(update_position_system)
+                .before(collision_height_only_system)
+                .before(collision_player_system),
+        )
+            .run_if(in_state(AppState::Game)),
+    );
+
     app.add_systems(
         Update,
         (
@@ -902,9 +1580,16 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
                 ui_hotbar_system,
                 ui_minimap_system,
                 ui_npc_store_system,
+                ui_union_system,
+                ui_vehicle_garage_system,
             ),
             (
+                ui_character_stats_system,
                 ui_clan_invite_system,
+                ui_compass_system,
+                ui_input_display_system,
+                ui_macros_system,
+                ui_mount_system,
                 ui_party_system,
                 ui_party_option_system,
                 ui_personal_store_system,
@@ -915,9 +1600,25 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
                 ui_skill_list_system,
                 ui_skill_tree_system,
                 ui_settings_system,
+                ui_zone_title_system,
             ),
             (
+                ui_chat_bubble_system,
+                ui_drop_item_system,
+                ui_escape_menu_system,
+                ui_growth_object_system,
+                ui_idle_warning_system,
+                ui_interact_prompt_system,
+                ui_movement_warning_system,
+                ui_party_marker_system,
+                ui_report_player_system,
+                ui_resurrection_system,
+                ui_social_system,
                 ui_status_effects_system,
+                ui_summon_system,
+                ui_warp_gate_system,
+                ui_zone_data_warning_system,
+                ui_zone_transition_system,
                 conversation_dialog_system,
             ),
         )
@@ -925,6 +1626,44 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             .in_set(UiSystemSets::Ui),
     );
 
+    if let Some(record_replay) = systems_config.record_replay.as_ref() {
+        match ReplayRecorder::new(record_replay) {
+            Ok(replay_recorder) => {
+                log::info!("Recording replay to {}", record_replay.to_string_lossy());
+                app.insert_resource(replay_recorder).add_systems(
+                    Update,
+                    replay_record_system.run_if(in_state(AppState::Game)),
+                );
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to create replay {} with error: {}",
+                    record_replay.to_string_lossy(),
+                    error
+                );
+            }
+        }
+    }
+
+    if config.scripts.enabled {
+        app.insert_resource(UserScriptManager::new(
+            Path::new(&config.scripts.path),
+            &config.scripts.disabled,
+        ))
+        .add_systems(
+            Update,
+            user_script_system
+                .after(GameSystemSets::Ui)
+                .run_if(in_state(AppState::Game)),
+        )
+        .add_systems(
+            Update,
+            ui_script_manager_system
+                .run_if(in_state(AppState::Game))
+                .in_set(UiSystemSets::Ui),
+        );
+    }
+
     if !systems_config.disable_player_command_system {
         app.add_systems(
             Update,
@@ -935,7 +1674,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         );
     }
 
-    app.add_systems(PostUpdate, ui_drag_and_drop_system);
+    app.add_systems(PostUpdate, ui_drag_and_drop_system.run_if(game_data_loaded));
 
     // Setup network
     let (network_thread_tx, network_thread_rx) =
@@ -950,10 +1689,18 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             login_connection_system,
             world_connection_system,
             game_connection_system,
-        ),
+            client_entity_registry_system.after(game_connection_system),
+            account_settings_system.after(game_connection_system),
+            client_settings_system.after(account_settings_system),
+            logout_system.after(game_connection_system),
+            summon_system.after(game_connection_system),
+            party_target_system.after(game_connection_system),
+            zone_data_verification_system.after(game_connection_system),
+        )
+            .run_if(game_data_loaded),
     );
 
-    app.add_systems(PostStartup, load_common_game_data);
+    add_client_plugins(&mut app, std::mem::take(&mut systems_config.client_plugins));
 
     if let Some(app_builder) = systems_config.add_custom_systems.take() {
         app_builder(&mut app);
@@ -967,21 +1714,53 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         unknown => panic!("Unknown game network version {}", unknown),
     };
 
-    match config.game.ui_version.as_str() {
-        "irose" => {
-            app.add_systems(Startup, load_ui_resources);
-        }
-        "custom" => {}
+    let load_ui_irose = match config.game.ui_version.as_str() {
+        "irose" => true,
+        "custom" => false,
         unknown => panic!("Unknown game ui version {}", unknown),
     };
 
-    match config.game.data_version.as_str() {
-        "irose" => {
-            app.add_systems(Startup, load_game_data_irose);
+    if let Some(data_profile_select) = data_profile_select {
+        // The game data is loaded when leaving the profile screen, with the data
+        // version of the chosen profile as profiles can override it
+        let next_state = data_profile_select.next_state;
+        app.insert_resource(data_profile_select)
+            .add_systems(
+                Update,
+                ui_data_profile_select_system.run_if(in_state(AppState::DataProfileSelect)),
+            )
+            .add_systems(
+                OnExit(AppState::DataProfileSelect),
+                load_game_data_irose.run_if(|data_profile_select: Res<DataProfileSelect>| {
+                    data_profile_select.data_version == "irose"
+                }),
+            )
+            .add_systems(
+                OnTransition {
+                    from: AppState::DataProfileSelect,
+                    to: next_state,
+                },
+                load_common_game_data,
+            );
+
+        if load_ui_irose {
+            app.add_systems(OnExit(AppState::DataProfileSelect), load_ui_resources);
+        }
+    } else {
+        if load_ui_irose {
+            app.add_systems(Startup, load_ui_resources);
         }
-        "custom" => {}
-        unknown => panic!("Unknown game data version {}", unknown),
-    };
+
+        match config.game.data_version.as_str() {
+            "irose" => {
+                app.add_systems(Startup, load_game_data_irose);
+            }
+            "custom" => {}
+            unknown => panic!("Unknown game data version {}", unknown),
+        };
+
+        app.add_systems(PostStartup, load_common_game_data);
+    }
 
     app.configure_sets(
         PostUpdate,
@@ -1019,6 +1798,34 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             .in_set(GameSystemSets::Ui),
     );
 
+    // Cinematic mode and store low power mode hide all UI
+    app.configure_set(
+        Update,
+        GameSystemSets::Ui
+            .run_if(cinematic_mode_disabled)
+            .run_if(store_low_power_mode_inactive),
+    )
+    .add_systems(
+        Update,
+        (
+            cinematic_mode_system.after(name_tag_visibility_system),
+            store_low_power_mode_system,
+            ui_store_low_power_system.after(store_low_power_mode_system),
+        )
+            .run_if(game_data_loaded),
+    );
+
+    // Nothing but the profile screen runs until the game data has been loaded
+    app.configure_set(
+        Update,
+        GameSystemSets::UpdateCamera.run_if(game_data_loaded),
+    )
+    .configure_set(Update, GameSystemSets::Ui.run_if(game_data_loaded))
+    .configure_set(Update, GameStages::ZoneChange.run_if(game_data_loaded))
+    .configure_set(Update, GameStages::DebugRender.run_if(game_data_loaded))
+    .configure_set(PostUpdate, GameStages::AfterUpdate.run_if(game_data_loaded))
+    .configure_set(PostUpdate, RoseAnimationSystem.run_if(game_data_loaded));
+
     app.configure_sets(
         Update,
         (GameSystemSets::UpdateCamera, GameSystemSets::Ui).chain(),
@@ -1028,6 +1835,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
 
     network_thread_tx.send(NetworkThreadMessage::Exit).ok();
     network_thread.join().ok();
+    true
 }
 
 fn load_game_data_irose(
@@ -1092,6 +1900,7 @@ fn load_game_data_irose(
         effect_database: rose_data_irose::get_effect_database(&vfs_resource.vfs)
             .expect("Failed to load effect database"),
         items,
+        item_sets: Arc::new(ItemSetDatabase::load(&vfs_resource.vfs)),
         job_class: Arc::new(
             rose_data_irose::get_job_class_database(&vfs_resource.vfs, string_database.clone())
                 .expect("Failed to load job class database"),
@@ -1102,6 +1911,7 @@ fn load_game_data_irose(
                 .expect("Failed to load quest database"),
         ),
         skills,
+        skill_reset_items: Arc::new(SkillResetItemDatabase::load(&vfs_resource.vfs)),
         skybox: rose_data_irose::get_skybox_database(&vfs_resource.vfs)
             .expect("Failed to load skybox database"),
         sounds,
@@ -1110,6 +1920,7 @@ fn load_game_data_irose(
                 .expect("Failed to load status effect database"),
         ),
         string_database,
+        unions: Arc::new(UnionDatabase::load(&vfs_resource.vfs)),
         zone_list,
         ltb_event: vfs_resource
             .vfs
@@ -1127,6 +1938,10 @@ fn load_game_data_irose(
             .vfs
             .read_file::<StbFile, _>("3DDATA/STB/LIST_MORPH_OBJECT.STB")
             .expect("Failed to load 3DDATA/STB/LIST_MORPH_OBJECT.STB"),
+        stb_warp: vfs_resource
+            .vfs
+            .read_file::<StbFile, _>("3DDATA/STB/WARP.STB")
+            .expect("Failed to load 3DDATA/STB/WARP.STB"),
         character_select_positions: vec![
             Transform::from_translation(Vec3::new(5205.0, 1.0, -5205.0))
                 .with_rotation(Quat::from_xyzw(0.0, 1.0, 0.0, 0.0))
@@ -1153,12 +1968,19 @@ fn load_common_game_data(
     game_data: Res<GameData>,
     asset_server: Res<AssetServer>,
     mut damage_digit_materials: ResMut<Assets<DamageDigitMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut standard_materials: ResMut<Assets<StandardMaterial>>,
     mut egui_context: EguiContexts,
 ) {
     commands.insert_resource(SpecularTexture {
         image: asset_server.load("ETC/SPECULAR_SPHEREMAP.DDS"),
     });
 
+    commands.insert_resource(SkeletonCache::new(vfs_resource.vfs.clone()));
+    commands.insert_resource(PlaceholderModelAssets::new(
+        &mut meshes,
+        &mut standard_materials,
+    ));
     commands.insert_resource(
         ModelLoader::new(
             vfs_resource.vfs.clone(),
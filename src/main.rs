@@ -1,11 +1,23 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use rose_data::ZoneId;
 use rose_offline_client::{
-    load_config, run_game, run_model_viewer, run_zone_viewer, Config, FilesystemDeviceConfig,
-    SystemsConfig,
+    load_config, run_game, run_headless, run_mod_conflict_report, run_model_viewer,
+    run_replay_viewer, run_zone_viewer, Config, FilesystemDeviceConfig, GraphicsModeConfig,
+    HeadlessConfig, SystemsConfig, TerrainQualityConfig,
 };
 
+// config.toml with --config-profile=alt becomes config-alt.toml
+fn config_profile_path(config_path: &Path, config_profile: &str) -> PathBuf {
+    let stem = config_path
+        .file_stem()
+        .map_or_else(|| "config".into(), |stem| stem.to_string_lossy());
+    let extension = config_path
+        .extension()
+        .map_or_else(|| "toml".into(), |extension| extension.to_string_lossy());
+    config_path.with_file_name(format!("{}-{}.{}", stem, config_profile, extension))
+}
+
 fn main() {
     let command = clap::Command::new("rose-offline-client")
         .arg(
@@ -14,6 +26,18 @@ fn main() {
                 .help("Path to config.toml")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("profile")
+                .long("profile")
+                .help("Name of game data profile from config.toml to use")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("config-profile")
+                .long("config-profile")
+                .help("Load config-<name>.toml instead of config.toml, to run multiple clients with separate settings")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("data-idx")
                 .long("data-idx")
@@ -44,10 +68,48 @@ fn main() {
                 .help("Optional path to extracted data, any files here override ones in data.idx")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("unpacked-data-path")
+                .long("unpacked-data-path")
+                .help("Path to fully extracted game data, runs without any data.idx")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("mods-path")
+                .long("mods-path")
+                .help("Path to mods directory, each sub-directory is a layer which overrides game data")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("disable-mods")
+                .long("disable-mods")
+                .help("Do not load any files from the mods directory"),
+        )
+        .arg(
+            clap::Arg::new("mods-hot-reload")
+                .long("mods-hot-reload")
+                .help("Reload assets when files in the mods directory are changed"),
+        )
+        .arg(
+            clap::Arg::new("scripts-path")
+                .long("scripts-path")
+                .help("Path to user scripts directory")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("disable-scripts")
+                .long("disable-scripts")
+                .help("Do not load any user scripts"),
+        )
+        .arg(
+            clap::Arg::new("mod-conflicts")
+                .long("mod-conflicts")
+                .help("Print which game data files are overridden by each mod layer and exit"),
+        )
         .arg(
             clap::Arg::new("zone")
                 .long("zone")
-                .help("Runs as zone viewer, loading the specified zone")
+                .help("Zone to load in zone viewer, or with --auto-login the zone to warp to after entering the game")
                 .takes_value(true),
         )
         .arg(
@@ -55,16 +117,81 @@ fn main() {
                 .long("zone-viewer")
                 .help("Run zone viewer"),
         )
+        .arg(
+            clap::Arg::new("record-replay")
+                .long("record-replay")
+                .help("Record a replay of the game to the given file")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("replay")
+                .long("replay")
+                .help("Play back a replay file recorded with --record-replay")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("model-viewer")
                 .long("model-viewer")
                 .help("Run model viewer"),
         )
+        .arg(
+            clap::Arg::new("headless")
+                .long("headless")
+                .help("Run without a window, auto login and enter the game then exit. Exits with 0 on success, 1 if disconnected, 2 on timeout."),
+        )
+        .arg(
+            clap::Arg::new("headless-duration")
+                .long("headless-duration")
+                .help("Number of seconds to stay in game for --headless")
+                .takes_value(true)
+                .default_value("10"),
+        )
+        .arg(
+            clap::Arg::new("headless-timeout")
+                .long("headless-timeout")
+                .help("Number of seconds to wait to enter the game for --headless")
+                .takes_value(true)
+                .default_value("60"),
+        )
         .arg(
             clap::Arg::new("disable-vsync")
                 .long("disable-vsync")
                 .help("Disable v-sync to see accurate frame times"),
         )
+        .arg(
+            clap::Arg::new("borderless")
+                .long("borderless")
+                .help("Use a borderless window instead of fullscreen"),
+        )
+        .arg(
+            clap::Arg::new("window-size")
+                .long("window-size")
+                .help("Window size as <width>x<height>")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("window-position")
+                .long("window-position")
+                .help("Window position on the desktop as x,y")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("confine-cursor")
+                .long("confine-cursor")
+                .help("Confine the mouse cursor to the window"),
+        )
+        .arg(
+            clap::Arg::new("position")
+                .long("position")
+                .help("With --auto-login, the x,y position to warp to after entering the game")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("server")
+                .long("server")
+                .help("Server address for game login as ip:port")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("ip")
                 .long("ip")
@@ -90,6 +217,13 @@ fn main() {
                 .help("Password for game login")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("password-md5")
+                .long("password-md5")
+                .alias("token")
+                .help("MD5 hash of password for game login, used instead of --password")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("server-id")
                 .long("server-id")
@@ -105,6 +239,7 @@ fn main() {
         .arg(
             clap::Arg::new("character-name")
                 .long("character-name")
+                .alias("character")
                 .help("If --auto-login is set, this will also auto login to the given character")
                 .takes_value(true),
         )
@@ -113,11 +248,22 @@ fn main() {
                 .long("auto-login")
                 .help("Automatically login to server"),
         )
+        .arg(
+            clap::Arg::new("texture-budget")
+                .long("texture-budget")
+                .help("Texture memory budget in MB, distant textures have their largest mip levels removed to stay within the budget")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("passthrough-terrain-textures")
                 .long("passthrough-terrain-textures")
                 .help("Assume all terrain textures are the same format such that we can pass through compressed textures to the GPU without decompression on the CPU. Note: This is not true for default irose 129_129en assets."),
         )
+        .arg(
+            clap::Arg::new("high-quality-terrain")
+                .long("high-quality-terrain")
+                .help("Enable smoother terrain tile blending, triplanar mapping on steep slopes and terrain tile detail textures."),
+        )
         .arg(
             clap::Arg::new("disable-sound")
                 .long("disable-sound")
@@ -146,10 +292,49 @@ fn main() {
         );
     let matches = command.get_matches();
 
-    let mut config = matches
-        .value_of("config")
-        .map(Path::new)
-        .map_or_else(Config::default, load_config);
+    let config_path = matches.value_of("config").map(Path::new);
+    let mut config = if let Some(config_profile) = matches.value_of("config-profile") {
+        let profile_path = config_profile_path(
+            config_path.unwrap_or_else(|| Path::new("config.toml")),
+            config_profile,
+        );
+
+        let mut config = if profile_path.exists() {
+            load_config(&profile_path)
+        } else {
+            println!(
+                "Config file {} for profile {} does not exist",
+                profile_path.to_string_lossy(),
+                config_profile
+            );
+            config_path.map_or_else(Config::default, load_config)
+        };
+        config.config_profile = Some(config_profile.into());
+        config
+    } else {
+        config_path.map_or_else(Config::default, load_config)
+    };
+
+    if let Some(profile) = matches.value_of("profile") {
+        config.profile = Some(profile.into());
+    }
+
+    if !config.profiles.is_empty() {
+        let profile = config
+            .profile
+            .clone()
+            .or_else(|| (config.profiles.len() == 1).then(|| config.profiles[0].name.clone()));
+
+        if let Some(profile) = profile {
+            if !config.apply_profile(&profile) {
+                println!("Unknown game data profile {}", profile);
+                return;
+            }
+        } else {
+            // The devices of the profile chosen on the profile screen are used instead
+            config.filesystem.devices.clear();
+        }
+    }
 
     if let Some(ip) = matches.value_of("ip") {
         config.server.ip = ip.into();
@@ -159,6 +344,17 @@ fn main() {
         config.server.port = port;
     }
 
+    if let Some(server) = matches.value_of("server") {
+        if let Some((ip, port)) = server.rsplit_once(':') {
+            config.server.ip = ip.into();
+            if let Ok(port) = port.parse::<u16>() {
+                config.server.port = port;
+            }
+        } else {
+            config.server.ip = server.into();
+        }
+    }
+
     if let Some(username) = matches.value_of("username") {
         config.account.username = username.into();
     }
@@ -167,6 +363,10 @@ fn main() {
         config.account.password = password.into();
     }
 
+    if let Some(password_md5) = matches.value_of("password-md5") {
+        config.account.password_md5 = Some(password_md5.into());
+    }
+
     if matches.is_present("auto-login") {
         config.auto_login.enabled = true;
     }
@@ -189,14 +389,77 @@ fn main() {
         config.auto_login.character_name = Some(character_name.into());
     }
 
+    // Without auto login --zone is only used to pick the zone viewer zone
+    if config.auto_login.enabled || matches.is_present("headless") {
+        if let Some(zone_id) = matches.value_of("zone").and_then(|s| s.parse::<u16>().ok()) {
+            config.auto_login.zone_id = Some(zone_id);
+        }
+
+        if let Some((x, y)) = matches.value_of("position").and_then(|s| s.split_once(',')) {
+            if let (Ok(x), Ok(y)) = (x.trim().parse::<f32>(), y.trim().parse::<f32>()) {
+                config.auto_login.position = Some([x, y]);
+            }
+        }
+    }
+
     if matches.is_present("disable-vsync") {
         config.graphics.disable_vsync = true;
     }
 
+    if let Some((width, height)) = matches
+        .value_of("window-size")
+        .and_then(|s| s.split_once('x'))
+        .and_then(|(width, height)| {
+            Some((
+                width.trim().parse::<f32>().ok()?,
+                height.trim().parse::<f32>().ok()?,
+            ))
+        })
+    {
+        config.graphics.mode = GraphicsModeConfig::Window { width, height };
+    }
+
+    if matches.is_present("borderless") {
+        config.graphics.mode = match config.graphics.mode {
+            GraphicsModeConfig::Window { width, height }
+            | GraphicsModeConfig::Borderless { width, height } => {
+                GraphicsModeConfig::Borderless { width, height }
+            }
+            GraphicsModeConfig::Fullscreen => GraphicsModeConfig::Borderless {
+                width: 1280.0,
+                height: 720.0,
+            },
+        };
+    }
+
+    if let Some((x, y)) = matches
+        .value_of("window-position")
+        .and_then(|s| s.split_once(','))
+    {
+        if let (Ok(x), Ok(y)) = (x.trim().parse::<i32>(), y.trim().parse::<i32>()) {
+            config.graphics.window_position = Some([x, y]);
+        }
+    }
+
+    if matches.is_present("confine-cursor") {
+        config.graphics.confine_cursor = true;
+    }
+
+    if let Some(budget_mb) = matches
+        .value_of("texture-budget")
+        .and_then(|s| s.parse::<usize>().ok())
+    {
+        config.graphics.texture_memory_budget_mb = Some(budget_mb);
+    }
+
     if matches.is_present("passthrough-terrain-textures") {
         config.graphics.passthrough_terrain_textures = true;
     }
 
+    if matches.is_present("high-quality-terrain") {
+        config.graphics.terrain_quality = TerrainQualityConfig::High;
+    }
+
     if matches.is_present("disable-sound") {
         config.sound.enabled = false;
     }
@@ -248,14 +511,85 @@ fn main() {
             .push(FilesystemDeviceConfig::Directory(directory_path.into()));
     }
 
-    if config.filesystem.devices.is_empty() && Path::exists(Path::new("data.idx")) {
-        config
-            .filesystem
-            .devices
-            .push(FilesystemDeviceConfig::Vfs("data.idx".into()));
+    if let Some(unpacked_path) = matches.value_of("unpacked-data-path") {
+        config.filesystem.unpacked_path = Some(unpacked_path.into());
+    }
+
+    if config.filesystem.devices.is_empty()
+        && config.filesystem.unpacked_path.is_none()
+        && !config.needs_profile_select()
+    {
+        if Path::exists(Path::new("data.idx")) {
+            config
+                .filesystem
+                .devices
+                .push(FilesystemDeviceConfig::Vfs("data.idx".into()));
+        } else if Path::new("3DDATA").is_dir() {
+            config.filesystem.unpacked_path = Some(".".into());
+        }
+    }
+
+    if let Some(mods_path) = matches.value_of("mods-path") {
+        config.filesystem.mods.path = mods_path.into();
+    }
+
+    if matches.is_present("disable-mods") {
+        config.filesystem.mods.enabled = false;
+    }
+
+    if matches.is_present("mods-hot-reload") {
+        config.filesystem.mods.hot_reload = true;
     }
 
-    if matches.is_present("model-viewer") {
+    if let Some(scripts_path) = matches.value_of("scripts-path") {
+        config.scripts.path = scripts_path.into();
+    }
+
+    if matches.is_present("disable-scripts") {
+        config.scripts.enabled = false;
+    }
+
+    // Only the game shows the profile screen, other modes need the profile from --profile
+    if config.needs_profile_select()
+        && ["headless", "replay", "model-viewer", "zone-viewer"]
+            .iter()
+            .any(|arg| matches.is_present(arg))
+    {
+        println!(
+            "Choose a game data profile with --profile, one of: {}",
+            config
+                .profiles
+                .iter()
+                .map(|profile| profile.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        return;
+    }
+
+    if matches.is_present("mod-conflicts") {
+        run_mod_conflict_report(&config);
+    } else if matches.is_present("headless") {
+        config.auto_login.enabled = true;
+        config.sound.enabled = false;
+
+        let exit_code = run_headless(
+            &config,
+            HeadlessConfig {
+                run_duration: matches
+                    .value_of("headless-duration")
+                    .and_then(|s| s.parse::<f32>().ok())
+                    .unwrap_or(10.0),
+                timeout: matches
+                    .value_of("headless-timeout")
+                    .and_then(|s| s.parse::<f32>().ok())
+                    .unwrap_or(60.0),
+            },
+        );
+        std::process::exit(exit_code);
+    } else if let Some(replay_path) = matches.value_of("replay") {
+        run_replay_viewer(&config, Path::new(replay_path));
+    } else if matches.is_present("model-viewer") {
         run_model_viewer(&config);
     } else if matches.is_present("zone-viewer") {
         run_zone_viewer(
@@ -266,6 +600,12 @@ fn main() {
                 .and_then(ZoneId::new),
         );
     } else {
-        run_game(&config, SystemsConfig::default());
+        run_game(
+            &config,
+            SystemsConfig {
+                record_replay: matches.value_of("record-replay").map(PathBuf::from),
+                ..Default::default()
+            },
+        );
     }
 }
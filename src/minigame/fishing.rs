@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use bevy_egui::egui;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use rose_data::{MotionId, SoundId};
+
+use crate::minigame::{Minigame, MinigameCue, MinigameInput, MinigameStatus};
+
+pub const FISHING_MINIGAME_ID: u8 = 1;
+
+// Maximum distance in metres from the player to the water which can be fished
+pub const FISHING_MAX_CAST_DISTANCE: f32 = 15.0;
+
+// Width of the area around the centre of the cast bar which gives a perfect cast
+const FISHING_PERFECT_CAST_WIDTH: f32 = 0.1;
+const FISHING_CAST_BAR_SPEED: f32 = 1.5;
+const FISHING_BITE_WINDOW: Duration = Duration::from_millis(1200);
+
+// Size of the catch zone the player moves while reeling, as a fraction of the bar
+const FISHING_CATCH_ZONE_SIZE: f32 = 0.25;
+const FISHING_CATCH_ZONE_LIFT: f32 = 2.5;
+const FISHING_CATCH_ZONE_GRAVITY: f32 = 2.0;
+const FISHING_REEL_PROGRESS_RATE: f32 = 0.25;
+const FISHING_REEL_ESCAPE_RATE: f32 = 0.2;
+
+#[derive(Clone, Default, Resource)]
+pub struct FishingSettings {
+    // Weapon item numbers which can be used to fish
+    pub rod_items: Vec<usize>,
+    pub cast_motion: Option<MotionId>,
+    pub reel_motion: Option<MotionId>,
+    pub cast_sound: Option<SoundId>,
+    pub bite_sound: Option<SoundId>,
+    pub catch_sound: Option<SoundId>,
+    pub escape_sound: Option<SoundId>,
+}
+
+impl FishingSettings {
+    pub fn is_rod(&self, item_number: usize) -> bool {
+        self.rod_items.contains(&item_number)
+    }
+}
+
+enum FishingStage {
+    Casting {
+        position: f32,
+        direction: f32,
+    },
+    Waiting {
+        remaining: Duration,
+    },
+    Bite {
+        remaining: Duration,
+    },
+    Reeling {
+        fish_position: f32,
+        fish_target: f32,
+        catch_zone_position: f32,
+        catch_zone_velocity: f32,
+        progress: f32,
+    },
+    Finished {
+        success: bool,
+    },
+}
+
+pub struct FishingMinigame {
+    settings: FishingSettings,
+    stage: FishingStage,
+    rng: StdRng,
+
+    // 0 to 1, how close the cast was to the centre of the bar
+    cast_quality: f32,
+}
+
+impl FishingMinigame {
+    pub fn new(settings: FishingSettings, seed: u32) -> Self {
+        Self {
+            settings,
+            stage: FishingStage::Casting {
+                position: 0.0,
+                direction: 1.0,
+            },
+            rng: StdRng::seed_from_u64(seed as u64),
+            cast_quality: 0.0,
+        }
+    }
+
+    fn finish(&mut self, success: bool, cues: &mut Vec<MinigameCue>) -> MinigameStatus {
+        let sound = if success {
+            self.settings.catch_sound
+        } else {
+            self.settings.escape_sound
+        };
+        cues.extend(sound.map(MinigameCue::Sound));
+
+        self.stage = FishingStage::Finished { success };
+        MinigameStatus::Finished { success }
+    }
+}
+
+fn draw_bar(ui: &mut egui::Ui, markers: &[(f32, f32, egui::Color32)]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(240.0, 20.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(40));
+
+    for &(start, end, color) in markers {
+        let marker_rect = egui::Rect::from_min_max(
+            egui::pos2(
+                rect.left() + rect.width() * start.clamp(0.0, 1.0),
+                rect.top(),
+            ),
+            egui::pos2(
+                rect.left() + rect.width() * end.clamp(0.0, 1.0),
+                rect.bottom(),
+            ),
+        );
+        painter.rect_filled(marker_rect, 2.0, color);
+    }
+}
+
+impl Minigame for FishingMinigame {
+    fn title(&self) -> &str {
+        "Fishing"
+    }
+
+    fn update(&mut self, input: &MinigameInput, cues: &mut Vec<MinigameCue>) -> MinigameStatus {
+        let delta = input.delta.as_secs_f32();
+        let rng = &mut self.rng;
+
+        match &mut self.stage {
+            FishingStage::Casting {
+                position,
+                direction,
+            } => {
+                if input.action_pressed {
+                    self.cast_quality = 1.0
+                        - ((*position - 0.5).abs() - FISHING_PERFECT_CAST_WIDTH / 2.0).max(0.0)
+                            * 2.0;
+
+                    // A good cast gets a bite sooner
+                    let wait_seconds = rng.gen_range(2.0..6.0) * (1.5 - self.cast_quality);
+                    self.stage = FishingStage::Waiting {
+                        remaining: Duration::from_secs_f32(wait_seconds),
+                    };
+                    cues.extend(self.settings.cast_motion.map(MinigameCue::Motion));
+                    cues.extend(self.settings.cast_sound.map(MinigameCue::Sound));
+                } else {
+                    *position += *direction * FISHING_CAST_BAR_SPEED * delta;
+                    if *position >= 1.0 {
+                        *position = 1.0;
+                        *direction = -1.0;
+                    } else if *position <= 0.0 {
+                        *position = 0.0;
+                        *direction = 1.0;
+                    }
+                }
+            }
+            FishingStage::Waiting { remaining } => {
+                if input.action_pressed {
+                    // Pulled the line in too early
+                    return self.finish(false, cues);
+                }
+
+                *remaining = remaining.saturating_sub(input.delta);
+                if remaining.is_zero() {
+                    self.stage = FishingStage::Bite {
+                        remaining: FISHING_BITE_WINDOW,
+                    };
+                    cues.extend(self.settings.bite_sound.map(MinigameCue::Sound));
+                }
+            }
+            FishingStage::Bite { remaining } => {
+                if input.action_pressed {
+                    self.stage = FishingStage::Reeling {
+                        fish_position: 0.5,
+                        fish_target: rng.gen_range(0.0..1.0),
+                        catch_zone_position: 0.5 - FISHING_CATCH_ZONE_SIZE / 2.0,
+                        catch_zone_velocity: 0.0,
+                        progress: 0.3,
+                    };
+                    cues.extend(self.settings.reel_motion.map(MinigameCue::Motion));
+                } else {
+                    *remaining = remaining.saturating_sub(input.delta);
+                    if remaining.is_zero() {
+                        return self.finish(false, cues);
+                    }
+                }
+            }
+            FishingStage::Reeling {
+                fish_position,
+                fish_target,
+                catch_zone_position,
+                catch_zone_velocity,
+                progress,
+            } => {
+                // The fish darts between random targets along the bar
+                if (*fish_target - *fish_position).abs() < 0.02 || rng.gen_bool(0.01) {
+                    *fish_target = rng.gen_range(0.0..1.0);
+                }
+                *fish_position += (*fish_target - *fish_position) * (2.0 * delta).min(1.0);
+
+                // Holding the action lifts the catch zone, otherwise it sinks
+                if input.action_held {
+                    *catch_zone_velocity += FISHING_CATCH_ZONE_LIFT * delta;
+                } else {
+                    *catch_zone_velocity -= FISHING_CATCH_ZONE_GRAVITY * delta;
+                }
+                *catch_zone_position += *catch_zone_velocity * delta;
+                if *catch_zone_position <= 0.0
+                    || *catch_zone_position >= 1.0 - FISHING_CATCH_ZONE_SIZE
+                {
+                    *catch_zone_position =
+                        catch_zone_position.clamp(0.0, 1.0 - FISHING_CATCH_ZONE_SIZE);
+                    *catch_zone_velocity = 0.0;
+                }
+
+                if *fish_position >= *catch_zone_position
+                    && *fish_position <= *catch_zone_position + FISHING_CATCH_ZONE_SIZE
+                {
+                    *progress += FISHING_REEL_PROGRESS_RATE * delta;
+                } else {
+                    *progress -= FISHING_REEL_ESCAPE_RATE * delta;
+                }
+
+                if *progress >= 1.0 {
+                    return self.finish(true, cues);
+                } else if *progress <= 0.0 {
+                    return self.finish(false, cues);
+                }
+            }
+            FishingStage::Finished { .. } => {}
+        }
+
+        MinigameStatus::Running
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        match &self.stage {
+            FishingStage::Casting { position, .. } => {
+                ui.label("Press Space to cast when the marker is in the centre");
+                draw_bar(
+                    ui,
+                    &[
+                        (
+                            0.5 - FISHING_PERFECT_CAST_WIDTH / 2.0,
+                            0.5 + FISHING_PERFECT_CAST_WIDTH / 2.0,
+                            egui::Color32::DARK_GREEN,
+                        ),
+                        (position - 0.01, position + 0.01, egui::Color32::YELLOW),
+                    ],
+                );
+            }
+            FishingStage::Waiting { .. } => {
+                ui.label("Waiting for a bite...");
+            }
+            FishingStage::Bite { .. } => {
+                ui.colored_label(egui::Color32::YELLOW, "Bite! Press Space to hook it!");
+            }
+            FishingStage::Reeling {
+                fish_position,
+                catch_zone_position,
+                progress,
+                ..
+            } => {
+                ui.label("Hold Space to keep the fish inside the green zone");
+                draw_bar(
+                    ui,
+                    &[
+                        (
+                            *catch_zone_position,
+                            catch_zone_position + FISHING_CATCH_ZONE_SIZE,
+                            egui::Color32::DARK_GREEN,
+                        ),
+                        (
+                            fish_position - 0.015,
+                            fish_position + 0.015,
+                            egui::Color32::LIGHT_BLUE,
+                        ),
+                    ],
+                );
+                ui.add(egui::ProgressBar::new(progress.clamp(0.0, 1.0)).desired_width(240.0));
+            }
+            FishingStage::Finished { success } => {
+                ui.label(if *success {
+                    "You caught something!"
+                } else {
+                    "It got away..."
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use bevy::{
+    input::Input,
+    prelude::{EventReader, EventWriter, Events, KeyCode, Res, ResMut, Time},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::messages::client::ClientMessage;
+
+use crate::{
+    events::ExtendedServerMessageEvent,
+    minigame::{
+        ActiveMinigame, FishingMinigame, FishingSettings, Minigame, MinigameCue, MinigameEvent,
+        MinigameInput, MinigameStatus, FISHING_MINIGAME_ID,
+    },
+    protocol::{
+        ExtendedClientMessage, ExtendedServerMessage, MinigameInputEvent, MinigameInputs,
+        ProtocolCapabilities,
+    },
+    resources::{ClientCapabilities, GameConnection},
+    ui::UiSoundEvent,
+};
+
+// How long the result is shown before the minigame window closes
+const MINIGAME_RESULT_DISPLAY_TIME: Duration = Duration::from_secs(2);
+
+// Minigames are only played in sessions started by the server
+pub fn minigame_server_message_system(
+    fishing_settings: Res<FishingSettings>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+    mut minigame_events: EventWriter<MinigameEvent>,
+) {
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        let ExtendedServerMessage::MinigameStart(start) = message else {
+            continue;
+        };
+
+        let minigame: Box<dyn Minigame> = match start.minigame_id {
+            FISHING_MINIGAME_ID => {
+                Box::new(FishingMinigame::new(fishing_settings.clone(), start.seed))
+            }
+            minigame_id => {
+                log::warn!("Server started unknown minigame {}", minigame_id);
+                continue;
+            }
+        };
+
+        minigame_events.send(MinigameEvent::Start {
+            session_id: start.session_id,
+            minigame,
+        });
+    }
+}
+
+// Drains the events so the boxed minigame can be moved out of the start event
+pub fn minigame_start_system(
+    mut active_minigame: ResMut<ActiveMinigame>,
+    mut minigame_events: ResMut<Events<MinigameEvent>>,
+    game_connection: Option<Res<GameConnection>>,
+    client_capabilities: Res<ClientCapabilities>,
+) {
+    for event in minigame_events.drain() {
+        match event {
+            MinigameEvent::Request(minigame_id) => {
+                if active_minigame.minigame.is_some()
+                    || !client_capabilities.supports(ProtocolCapabilities::MINIGAMES)
+                {
+                    continue;
+                }
+
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .extended_client_message_tx
+                        .send(ExtendedClientMessage::MinigameRequest(minigame_id))
+                        .ok();
+                }
+            }
+            MinigameEvent::Start {
+                session_id,
+                minigame,
+            } => {
+                // Ignore sessions started while a minigame is still being played
+                if active_minigame.minigame.is_none() {
+                    active_minigame.minigame = Some(minigame);
+                    active_minigame.session_id = session_id;
+                    active_minigame.elapsed = Duration::ZERO;
+                    active_minigame.inputs.clear();
+                    active_minigame.action_held = false;
+                    active_minigame.finished_timer = None;
+                }
+            }
+            MinigameEvent::Cancel => {
+                // The server does not need to know about a minigame which already finished
+                if active_minigame.minigame.is_some() && active_minigame.finished_timer.is_none() {
+                    if let Some(game_connection) = game_connection.as_ref() {
+                        game_connection
+                            .extended_client_message_tx
+                            .send(ExtendedClientMessage::MinigameCancel(
+                                active_minigame.session_id,
+                            ))
+                            .ok();
+                    }
+                }
+
+                active_minigame.minigame = None;
+                active_minigame.finished_timer = None;
+            }
+        }
+    }
+}
+
+pub fn minigame_update_system(
+    mut active_minigame: ResMut<ActiveMinigame>,
+    mut egui_context: EguiContexts,
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    game_connection: Option<Res<GameConnection>>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+) {
+    let active_minigame = &mut *active_minigame;
+    if active_minigame.minigame.is_none() {
+        return;
+    }
+
+    let Some(game_connection) = game_connection else {
+        active_minigame.minigame = None;
+        active_minigame.finished_timer = None;
+        return;
+    };
+
+    if let Some(finished_timer) = active_minigame.finished_timer.as_mut() {
+        *finished_timer = finished_timer.saturating_sub(time.delta());
+        if finished_timer.is_zero() {
+            active_minigame.minigame = None;
+            active_minigame.finished_timer = None;
+        }
+        return;
+    }
+
+    let Some(minigame) = active_minigame.minigame.as_mut() else {
+        return;
+    };
+
+    let accepts_keyboard = !egui_context.ctx_mut().wants_keyboard_input();
+    let input = MinigameInput {
+        delta: time.delta(),
+        action_pressed: accepts_keyboard && keyboard_input.just_pressed(KeyCode::Space),
+        action_held: accepts_keyboard && keyboard_input.pressed(KeyCode::Space),
+    };
+
+    active_minigame.elapsed += input.delta;
+    let action_held = input.action_held || input.action_pressed;
+    if action_held != active_minigame.action_held {
+        active_minigame.action_held = action_held;
+        active_minigame.inputs.push(MinigameInputEvent {
+            time: active_minigame.elapsed,
+            held: action_held,
+        });
+    }
+
+    let mut cues = Vec::new();
+    let status = minigame.update(&input, &mut cues);
+
+    for cue in cues {
+        match cue {
+            MinigameCue::Motion(motion_id) => {
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::UseEmote {
+                        motion_id,
+                        is_stop: true,
+                    })
+                    .ok();
+            }
+            MinigameCue::Sound(sound_id) => {
+                ui_sound_events.send(UiSoundEvent::new(sound_id));
+            }
+        }
+    }
+
+    if let MinigameStatus::Finished { .. } = status {
+        game_connection
+            .extended_client_message_tx
+            .send(ExtendedClientMessage::MinigameInputs(MinigameInputs {
+                session_id: active_minigame.session_id,
+                inputs: std::mem::take(&mut active_minigame.inputs),
+            }))
+            .ok();
+        active_minigame.finished_timer = Some(MINIGAME_RESULT_DISPLAY_TIME);
+    }
+}
+
+pub fn ui_minigame_system(
+    mut egui_context: EguiContexts,
+    mut active_minigame: ResMut<ActiveMinigame>,
+    mut minigame_events: EventWriter<MinigameEvent>,
+) {
+    let is_finished = active_minigame.finished_timer.is_some();
+    let Some(minigame) = active_minigame.minigame.as_mut() else {
+        return;
+    };
+
+    egui::Window::new(minigame.title().to_string())
+        .id(egui::Id::new("minigame_window"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -160.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            minigame.ui(ui);
+
+            if !is_finished {
+                ui.separator();
+                if ui.button("Cancel").clicked() {
+                    minigame_events.send(MinigameEvent::Cancel);
+                }
+            }
+        });
+}
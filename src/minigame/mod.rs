@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use bevy::prelude::{in_state, App, Event, IntoSystemConfigs, Plugin, Resource, Update};
+use bevy_egui::egui;
+
+use rose_data::{MotionId, SoundId};
+
+use crate::{protocol::MinigameInputEvent, resources::AppState, UiSystemSets};
+
+mod fishing;
+mod minigame_system;
+
+pub use fishing::{
+    FishingMinigame, FishingSettings, FISHING_MAX_CAST_DISTANCE, FISHING_MINIGAME_ID,
+};
+
+use minigame_system::{
+    minigame_server_message_system, minigame_start_system, minigame_update_system,
+    ui_minigame_system,
+};
+
+pub struct MinigameInput {
+    pub delta: Duration,
+
+    // The action key was pressed this frame, or is being held
+    pub action_pressed: bool,
+    pub action_held: bool,
+}
+
+// Requested by a minigame to be played on the player, motions are sent to the
+// server as an emote so other players can see them
+#[derive(Copy, Clone, Debug)]
+pub enum MinigameCue {
+    Motion(MotionId),
+    Sound(SoundId),
+}
+
+// The outcome shown to the player, the server decides the real outcome from
+// the inputs which were sent to it
+pub enum MinigameStatus {
+    Running,
+    Finished { success: bool },
+}
+
+// Implemented by each minigame, the minigame systems take care of input, the
+// window, cancelling and sending the inputs to the server. Any randomness must
+// come from the seed of the session so the server can replay the inputs.
+pub trait Minigame: Send + Sync {
+    fn title(&self) -> &str;
+
+    fn update(&mut self, input: &MinigameInput, cues: &mut Vec<MinigameCue>) -> MinigameStatus;
+
+    fn ui(&mut self, ui: &mut egui::Ui);
+}
+
+#[derive(Default, Resource)]
+pub struct ActiveMinigame {
+    pub minigame: Option<Box<dyn Minigame>>,
+    pub session_id: u32,
+
+    // Changes of the action key since the session started, sent to the server
+    // when the minigame finishes
+    pub elapsed: Duration,
+    pub inputs: Vec<MinigameInputEvent>,
+    pub action_held: bool,
+
+    // Set once the minigame has finished, the result stays visible until it expires
+    pub finished_timer: Option<Duration>,
+}
+
+impl ActiveMinigame {
+    pub fn is_active(&self) -> bool {
+        self.minigame.is_some()
+    }
+}
+
+#[derive(Event)]
+pub enum MinigameEvent {
+    // Asks the server to start a session of the minigame with this id
+    Request(u8),
+    Start {
+        session_id: u32,
+        minigame: Box<dyn Minigame>,
+    },
+    Cancel,
+}
+
+#[derive(Default)]
+pub struct RoseMinigamePlugin;
+
+impl Plugin for RoseMinigamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveMinigame>()
+            .add_event::<MinigameEvent>();
+
+        app.add_systems(
+            Update,
+            (
+                minigame_server_message_system,
+                minigame_start_system,
+                minigame_update_system,
+            )
+                .chain()
+                .run_if(in_state(AppState::Game)),
+        )
+        .add_systems(
+            Update,
+            ui_minigame_system
+                .run_if(in_state(AppState::Game))
+                .in_set(UiSystemSets::Ui),
+        );
+    }
+}
@@ -4,9 +4,8 @@ use arrayvec::ArrayVec;
 use bevy::{
     math::{Mat4, Quat, Vec3},
     prelude::{
-        AssetServer, Assets, BuildChildren, Color, Commands, ComputedVisibility,
-        DespawnRecursiveExt, Entity, GlobalTransform, Handle, Image, Mesh, Resource, Transform,
-        Visibility,
+        AssetServer, Assets, BuildChildren, Color, Commands, ComputedVisibility, Entity,
+        GlobalTransform, Handle, Image, Mesh, Resource, Transform, Visibility,
     },
     render::mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
 };
@@ -30,6 +29,7 @@ use crate::{
     },
     effect_loader::spawn_effect,
     render::{EffectMeshMaterial, ObjectMaterial, ParticleMaterial, TrailEffect},
+    resources::{CachedSkeleton, ModelPartPoolKey, ModelPool, ModelPoolCommands},
     zms_asset_loader::ZmsMaterialNumFaces,
 };
 
@@ -56,7 +56,6 @@ pub struct ModelLoader {
     specular_image: Handle<Image>,
 
     // Male
-    skeleton_male: ZmdFile,
     face_male: ZscFile,
     hair_male: ZscFile,
     head_male: ZscFile,
@@ -65,7 +64,6 @@ pub struct ModelLoader {
     feet_male: ZscFile,
 
     // Female
-    skeleton_female: ZmdFile,
     face_female: ZscFile,
     hair_female: ZscFile,
     head_female: ZscFile,
@@ -80,8 +78,6 @@ pub struct ModelLoader {
     sub_weapon: ZscFile,
 
     // Vehicle
-    skeleton_cart: ZmdFile,
-    skeleton_castle_gear: ZmdFile,
     cart_driver_seat_height: f32,
     castle_gear_driver_seat_height: f32,
     vehicle: ZscFile,
@@ -105,16 +101,16 @@ impl ModelLoader {
         trail_effect_image: Handle<Image>,
         specular_image: Handle<Image>,
     ) -> Result<ModelLoader, anyhow::Error> {
-        // Pre-load vehicle skeletons and compute their heights
-        let skeleton_cart = vfs.read_file::<ZmdFile, _>("3DDATA/PAT/CART/CART01.ZMD")?;
-        let skeleton_castle_gear = vfs
-            .read_file::<ZmdFile, _>("3DDATA/PAT/CASTLEGEAR/CASTLEGEAR02/CASTLEGEAR02.ZMD")?;
+        // Compute the vehicle seat heights, the skeletons are spawned through SkeletonCache
+        let skeleton_cart =
+            vfs.read_file::<ZmdFile, _>(Self::get_vehicle_skeleton_path(VehicleType::Cart))?;
+        let skeleton_castle_gear =
+            vfs.read_file::<ZmdFile, _>(Self::get_vehicle_skeleton_path(VehicleType::CastleGear))?;
         let cart_driver_seat_height = compute_driver_seat_height(&skeleton_cart);
         let castle_gear_driver_seat_height = compute_driver_seat_height(&skeleton_castle_gear);
 
         Ok(ModelLoader {
             // Male
-            skeleton_male: vfs.read_file::<ZmdFile, _>("3DDATA/AVATAR/MALE.ZMD")?,
             face_male: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_MFACE.ZSC")?,
             hair_male: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_MHAIR.ZSC")?,
             head_male: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_MCAP.ZSC")?,
@@ -123,7 +119,6 @@ impl ModelLoader {
             feet_male: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_MFOOT.ZSC")?,
 
             // Female
-            skeleton_female: vfs.read_file::<ZmdFile, _>("3DDATA/AVATAR/FEMALE.ZMD")?,
             face_female: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_WFACE.ZSC")?,
             hair_female: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_WHAIR.ZSC")?,
             head_female: vfs.read_file::<ZscFile, _>("3DDATA/AVATAR/LIST_WCAP.ZSC")?,
@@ -138,8 +133,6 @@ impl ModelLoader {
             sub_weapon: vfs.read_file::<ZscFile, _>("3DDATA/WEAPON/LIST_SUBWPN.ZSC")?,
 
             // Vehicle
-            skeleton_cart,
-            skeleton_castle_gear,
             cart_driver_seat_height,
             castle_gear_driver_seat_height,
             vehicle: vfs.read_file::<ZscFile, _>("3DDATA/PAT/LIST_PAT.ZSC")?,
@@ -162,13 +155,38 @@ impl ModelLoader {
         })
     }
 
-    pub fn get_skeleton(&self, gender: CharacterGender) -> &ZmdFile {
+    pub fn get_skeleton_path(gender: CharacterGender) -> &'static str {
         match gender {
-            CharacterGender::Male => &self.skeleton_male,
-            CharacterGender::Female => &self.skeleton_female,
+            CharacterGender::Male => "3DDATA/AVATAR/MALE.ZMD",
+            CharacterGender::Female => "3DDATA/AVATAR/FEMALE.ZMD",
         }
     }
 
+    pub fn get_npc_skeleton_path(&self, npc_id: NpcId) -> Option<String> {
+        let npc_model_data = self.npc_chr.npcs.get(&npc_id.get())?;
+        self.npc_chr
+            .skeleton_files
+            .get(npc_model_data.skeleton_index as usize)
+            .map(|path| path.path().to_string_lossy().into_owned())
+    }
+
+    pub fn get_vehicle_skeleton_path(vehicle_type: VehicleType) -> &'static str {
+        match vehicle_type {
+            VehicleType::Cart => "3DDATA/PAT/CART/CART01.ZMD",
+            VehicleType::CastleGear => "3DDATA/PAT/CASTLEGEAR/CASTLEGEAR02/CASTLEGEAR02.ZMD",
+        }
+    }
+
+    pub fn get_vehicle_type(&self, equipment: &Equipment) -> Option<VehicleType> {
+        equipment.equipped_vehicle[VehiclePartIndex::Body]
+            .as_ref()
+            .and_then(|equipment_item| {
+                self.item_database
+                    .get_vehicle_item(equipment_item.item.item_number)
+            })
+            .map(|body_item_data| body_item_data.vehicle_type)
+    }
+
     pub fn get_model_list(
         &self,
         gender: CharacterGender,
@@ -214,34 +232,23 @@ impl ModelLoader {
         effect_mesh_materials: &mut Assets<EffectMeshMaterial>,
         particle_materials: &mut Assets<ParticleMaterial>,
         object_materials: &mut Assets<ObjectMaterial>,
-        skinned_mesh_inverse_bindposes_assets: &mut Assets<SkinnedMeshInverseBindposes>,
         model_entity: Entity,
         npc_id: NpcId,
+        skeleton: Option<&CachedSkeleton>,
     ) -> Option<(NpcModel, SkinnedMesh, DummyBoneOffset)> {
         let npc_model_data = self.npc_chr.npcs.get(&npc_id.get())?;
-        let (skinned_mesh, root_bone_position, dummy_bone_offset) = if let Some(skeleton) = self
-            .npc_chr
-            .skeleton_files
-            .get(npc_model_data.skeleton_index as usize)
-            .and_then(|p| self.vfs.read_file::<ZmdFile, _>(p).ok())
+        let (skinned_mesh, root_bone_position, dummy_bone_offset) = if let Some(skeleton) = skeleton
         {
             (
-                spawn_skeleton(
+                spawn_skeleton_entities(
                     commands,
                     model_entity,
-                    &skeleton,
-                    skinned_mesh_inverse_bindposes_assets,
+                    &skeleton.bind_pose,
+                    skeleton.inverse_bindposes.clone(),
+                    None,
                 ),
-                if let Some(root_bone) = skeleton.bones.first() {
-                    Vec3::new(
-                        root_bone.position.x,
-                        root_bone.position.z,
-                        -root_bone.position.y,
-                    ) / 100.0
-                } else {
-                    Vec3::ZERO
-                },
-                skeleton.bones.len(),
+                skeleton.bind_pose.root_bone_position,
+                skeleton.bind_pose.dummy_bone_offset,
             )
         } else {
             (SkinnedMesh::default(), Vec3::ZERO, 0)
@@ -253,6 +260,7 @@ impl ModelLoader {
                 commands,
                 asset_server,
                 object_materials,
+                None,
                 model_entity,
                 &self.npc_zsc,
                 *model_id as usize,
@@ -294,6 +302,7 @@ impl ModelLoader {
                     commands,
                     asset_server,
                     object_materials,
+                    None,
                     model_entity,
                     &self.weapon,
                     npc_data.right_hand_part_index as usize,
@@ -311,6 +320,7 @@ impl ModelLoader {
                     commands,
                     asset_server,
                     object_materials,
+                    None,
                     model_entity,
                     &self.sub_weapon,
                     npc_data.left_hand_part_index as usize,
@@ -368,6 +378,7 @@ impl ModelLoader {
             commands,
             asset_server,
             object_materials,
+            None,
             root_bone,
             &self.field_item,
             260 + skin,
@@ -421,6 +432,7 @@ impl ModelLoader {
                     commands,
                     asset_server,
                     object_materials,
+                    None,
                     root_bone,
                     &self.field_item,
                     model_id,
@@ -675,18 +687,19 @@ impl ModelLoader {
         object_materials: &mut Assets<ObjectMaterial>,
         particle_materials: &mut Assets<ParticleMaterial>,
         effect_mesh_materials: &mut Assets<EffectMeshMaterial>,
-        skinned_mesh_inverse_bindposes_assets: &mut Assets<SkinnedMeshInverseBindposes>,
         model_entity: Entity,
         character_info: &CharacterInfo,
         equipment: &Equipment,
+        skeleton: &CachedSkeleton,
+        model_pool: &mut ModelPool,
     ) -> (CharacterModel, SkinnedMesh, DummyBoneOffset) {
-        let skeleton = self.get_skeleton(character_info.gender);
-        let dummy_bone_offset = skeleton.bones.len();
-        let skinned_mesh = spawn_skeleton(
+        let dummy_bone_offset = skeleton.bind_pose.dummy_bone_offset;
+        let skinned_mesh = spawn_skeleton_entities(
             commands,
             model_entity,
-            skeleton,
-            skinned_mesh_inverse_bindposes_assets,
+            &skeleton.bind_pose,
+            skeleton.inverse_bindposes.clone(),
+            model_pool.take_skeleton(skeleton.inverse_bindposes.id()),
         );
         let mut model_parts = EnumMap::default();
 
@@ -720,6 +733,7 @@ impl ModelLoader {
                         equipment,
                         particle_materials,
                         effect_mesh_materials,
+                        model_pool,
                     ),
                 );
             }
@@ -754,6 +768,7 @@ impl ModelLoader {
         equipment: &Equipment,
         particle_materials: &mut Assets<ParticleMaterial>,
         effect_mesh_materials: &mut Assets<EffectMeshMaterial>,
+        model_pool: &mut ModelPool,
     ) -> Vec<Entity> {
         let model_list = self.get_model_list(character_info.gender, model_part);
 
@@ -761,6 +776,7 @@ impl ModelLoader {
             commands,
             asset_server,
             object_materials,
+            Some(model_pool),
             model_entity,
             model_list,
             model_id,
@@ -847,7 +863,8 @@ impl ModelLoader {
         character_model: &mut CharacterModel,
         dummy_bone_offset: &DummyBoneOffset,
         skinned_mesh: &SkinnedMesh,
-    ) {
+        model_pool: &mut ModelPool,
+    ) -> bool {
         let weapon_model_index = get_model_part_index(
             &self.item_database,
             character_info,
@@ -864,6 +881,8 @@ impl ModelLoader {
                 self.load_character_action_motions(asset_server, character_info, equipment);
         }
 
+        let mut model_height_changed = false;
+
         for model_part in [
             CharacterModelPart::CharacterFace,
             CharacterModelPart::CharacterHair,
@@ -885,11 +904,16 @@ impl ModelLoader {
                     });
 
             if model_id != character_model.model_parts[model_part].0 {
-                // Despawn previous model
-                for &entity in character_model.model_parts[model_part].1.iter() {
-                    commands.entity(entity).despawn_recursive();
+                // Gem and grade do not change the mesh
+                if model_part.affects_model_height()
+                    && model_id.id != character_model.model_parts[model_part].0.id
+                {
+                    model_height_changed = true;
                 }
 
+                // Return previous model to the pool
+                commands.pool_model_parts(character_model.model_parts[model_part].1.clone());
+
                 // Spawn new model
                 if model_id.id != 0
                     || matches!(
@@ -912,6 +936,7 @@ impl ModelLoader {
                             equipment,
                             particle_materials,
                             effect_mesh_materials,
+                            model_pool,
                         ),
                     );
                 } else {
@@ -920,6 +945,8 @@ impl ModelLoader {
                 }
             }
         }
+
+        model_height_changed
     }
 
     pub fn get_vehicle_driver_seat_height(&self, equipment: &Equipment) -> f32 {
@@ -946,10 +973,10 @@ impl ModelLoader {
         object_materials: &mut Assets<ObjectMaterial>,
         particle_materials: &mut Assets<ParticleMaterial>,
         effect_mesh_materials: &mut Assets<EffectMeshMaterial>,
-        skinned_mesh_inverse_bindposes_assets: &mut Assets<SkinnedMeshInverseBindposes>,
         vehicle_model_entity: Entity,
         driver_model_entity: Entity,
         equipment: &Equipment,
+        skeleton: &CachedSkeleton,
     ) -> (VehicleModel, SkinnedMesh, DummyBoneOffset) {
         let body_item_data = equipment.equipped_vehicle[VehiclePartIndex::Body]
             .as_ref()
@@ -959,16 +986,13 @@ impl ModelLoader {
             })
             .unwrap(); // TODO: No panic on invalid vehicle
         let is_cart = matches!(body_item_data.vehicle_type, VehicleType::Cart);
-        let skeleton = match body_item_data.vehicle_type {
-            VehicleType::Cart => &self.skeleton_cart,
-            VehicleType::CastleGear => &self.skeleton_castle_gear,
-        };
-        let dummy_bone_offset = skeleton.bones.len();
-        let skinned_mesh = spawn_skeleton(
+        let dummy_bone_offset = skeleton.bind_pose.dummy_bone_offset;
+        let skinned_mesh = spawn_skeleton_entities(
             commands,
             vehicle_model_entity,
-            skeleton,
-            skinned_mesh_inverse_bindposes_assets,
+            &skeleton.bind_pose,
+            skeleton.inverse_bindposes.clone(),
+            None,
         );
         let mut model_parts = EnumMap::default();
 
@@ -988,6 +1012,7 @@ impl ModelLoader {
                         commands,
                         asset_server,
                         object_materials,
+                        None,
                         vehicle_model_entity,
                         &self.vehicle,
                         model_id,
@@ -1151,7 +1176,7 @@ fn compute_driver_seat_height(skeleton: &ZmdFile) -> f32 {
     transform_children(skeleton, &mut bind_pose, 0);
 
     // Compute world-space transform for the first dummy bone (driver seat)
-    // Match the same multiplication order as spawn_skeleton: dummy_local * parent_world
+    // Match the same multiplication order as SkeletonBindPose::new: dummy_local * parent_world
     let driver_seat_world = bind_pose[dummy_bone_offset] * bind_pose[skeleton.dummy_bones[0].parent as usize];
 
     driver_seat_world.translation.y.max(0.0)
@@ -1168,74 +1193,112 @@ fn transform_children(skeleton: &ZmdFile, bone_transforms: &mut Vec<Transform>,
     }
 }
 
-fn spawn_skeleton(
-    commands: &mut Commands,
-    model_entity: Entity,
-    skeleton: &ZmdFile,
-    skinned_mesh_inverse_bindposes_assets: &mut Assets<SkinnedMeshInverseBindposes>,
-) -> SkinnedMesh {
-    let mut bind_pose = Vec::with_capacity(skeleton.bones.len());
-    let mut bone_entities = Vec::with_capacity(skeleton.bones.len());
-    let dummy_bone_offset = skeleton.bones.len();
+// Bone transforms and inverse bind pose for a skeleton, this is built on a
+// background task by SkeletonCache and shared by every model using the skeleton
+pub struct SkeletonBindPose {
+    pub bone_transforms: Vec<Transform>,
+    pub bone_parents: Vec<usize>,
+    pub dummy_bone_offset: usize,
+    pub root_bone_position: Vec3,
+    pub inverse_bindposes: Vec<Mat4>,
+}
 
-    for bone in skeleton.bones.iter().chain(skeleton.dummy_bones.iter()) {
-        let position = Vec3::new(bone.position.x, bone.position.z, -bone.position.y) / 100.0;
+impl SkeletonBindPose {
+    pub fn new(skeleton: &ZmdFile) -> Self {
+        let mut bone_transforms = Vec::with_capacity(skeleton.bones.len());
+        let mut bone_parents = Vec::with_capacity(skeleton.bones.len());
+        let dummy_bone_offset = skeleton.bones.len();
 
-        let rotation = Quat::from_xyzw(
-            bone.rotation.x,
-            bone.rotation.z,
-            -bone.rotation.y,
-            bone.rotation.w,
-        );
+        for bone in skeleton.bones.iter().chain(skeleton.dummy_bones.iter()) {
+            let position = Vec3::new(bone.position.x, bone.position.z, -bone.position.y) / 100.0;
+
+            let rotation = Quat::from_xyzw(
+                bone.rotation.x,
+                bone.rotation.z,
+                -bone.rotation.y,
+                bone.rotation.w,
+            );
 
-        let transform = Transform::default()
-            .with_translation(position)
-            .with_rotation(rotation);
+            bone_transforms.push(
+                Transform::default()
+                    .with_translation(position)
+                    .with_rotation(rotation),
+            );
+            bone_parents.push(bone.parent as usize);
+        }
 
-        bind_pose.push(transform);
+        // Apply parent-child transform hierarchy to calculate bind pose for each bone
+        let mut bind_pose = bone_transforms.clone();
+        transform_children(skeleton, &mut bind_pose, 0);
+        for (dummy_id, dummy_bone) in skeleton.dummy_bones.iter().enumerate() {
+            bind_pose[dummy_id + dummy_bone_offset] =
+                bind_pose[dummy_id + dummy_bone_offset] * bind_pose[dummy_bone.parent as usize];
+        }
 
-        bone_entities.push(
-            commands
-                .spawn((
-                    Visibility::default(),
-                    ComputedVisibility::default(),
-                    transform,
-                    GlobalTransform::default(),
-                ))
-                .id(),
-        );
-    }
+        let inverse_bindposes = bind_pose
+            .iter()
+            .map(|x| x.compute_matrix().inverse())
+            .collect();
 
-    // Apply parent-child transform hierarchy to calculate bind pose for each bone
-    transform_children(skeleton, &mut bind_pose, 0);
-    for (dummy_id, dummy_bone) in skeleton.dummy_bones.iter().enumerate() {
-        bind_pose[dummy_id + dummy_bone_offset] =
-            bind_pose[dummy_id + dummy_bone_offset] * bind_pose[dummy_bone.parent as usize];
+        let root_bone_position = bone_transforms
+            .first()
+            .map_or(Vec3::ZERO, |root_bone| root_bone.translation);
+
+        Self {
+            bone_transforms,
+            bone_parents,
+            dummy_bone_offset,
+            root_bone_position,
+            inverse_bindposes,
+        }
     }
+}
 
-    let inverse_bind_pose: Vec<Mat4> = bind_pose
-        .iter()
-        .map(|x| x.compute_matrix().inverse())
-        .collect();
+fn spawn_skeleton_entities(
+    commands: &mut Commands,
+    model_entity: Entity,
+    bind_pose: &SkeletonBindPose,
+    inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+    pooled_joints: Option<Vec<Entity>>,
+) -> SkinnedMesh {
+    let is_pooled = pooled_joints.is_some();
+    let bone_entities: Vec<Entity> = if let Some(joints) = pooled_joints {
+        // Reset the pooled skeleton back to the bind pose, the joints are already parented
+        for (&joint, &transform) in joints.iter().zip(bind_pose.bone_transforms.iter()) {
+            commands
+                .entity(joint)
+                .insert((transform, Visibility::default()));
+        }
+        joints
+    } else {
+        bind_pose
+            .bone_transforms
+            .iter()
+            .map(|&transform| {
+                commands
+                    .spawn((
+                        Visibility::default(),
+                        ComputedVisibility::default(),
+                        transform,
+                        GlobalTransform::default(),
+                    ))
+                    .id()
+            })
+            .collect()
+    };
 
-    for (i, bone) in skeleton
-        .bones
-        .iter()
-        .chain(skeleton.dummy_bones.iter())
-        .enumerate()
-    {
-        if let Some(&bone_entity) = bone_entities.get(i) {
-            if bone.parent as usize == i {
-                commands.entity(model_entity).add_child(bone_entity);
-            } else if let Some(&parent_entity) = bone_entities.get(bone.parent as usize) {
-                commands.entity(parent_entity).add_child(bone_entity);
-            }
+    for (i, &parent) in bind_pose.bone_parents.iter().enumerate() {
+        if parent == i {
+            commands.entity(model_entity).add_child(bone_entities[i]);
+        } else if is_pooled {
+            continue;
+        } else if let Some(&parent_entity) = bone_entities.get(parent) {
+            commands.entity(parent_entity).add_child(bone_entities[i]);
         }
     }
 
     SkinnedMesh {
-        inverse_bindposes: skinned_mesh_inverse_bindposes_assets
-            .add(SkinnedMeshInverseBindposes::from(inverse_bind_pose)),
+        inverse_bindposes,
         joints: bone_entities,
     }
 }
@@ -1245,6 +1308,7 @@ fn spawn_model(
     commands: &mut Commands,
     asset_server: &AssetServer,
     object_materials: &mut Assets<ObjectMaterial>,
+    mut model_pool: Option<&mut ModelPool>,
     model_entity: Entity,
     model_list: &ZscFile,
     model_id: usize,
@@ -1266,7 +1330,7 @@ fn spawn_model(
         let mesh = asset_server.load::<Mesh, _>(model_list.meshes[mesh_id].path());
         let material_id = object_part.material_id as usize;
         let zsc_material = &model_list.materials[material_id];
-        let material = object_materials.add(ObjectMaterial {
+        let object_material = ObjectMaterial {
             base_texture: Some(asset_server.load(zsc_material.path.path())),
             lightmap_texture: None,
             alpha_value: if zsc_material.alpha != 1.0 {
@@ -1286,18 +1350,26 @@ fn spawn_model(
             },
             skinned: zsc_material.is_skin,
             ..Default::default()
+        };
+        let pooled_entity = model_pool.as_deref_mut().and_then(|model_pool| {
+            model_pool.take_part(ModelPartPoolKey::new(&mesh, &object_material))
         });
+        let mut entity_commands = if let Some(pooled_entity) = pooled_entity {
+            let mut entity_commands = commands.entity(pooled_entity);
+            entity_commands.insert((Transform::default(), Visibility::default()));
+            entity_commands
+        } else {
+            commands.spawn((
+                mesh,
+                object_materials.add(object_material),
+                Transform::default(),
+                GlobalTransform::default(),
+                Visibility::default(),
+                ComputedVisibility::default(),
+            ))
+        };
 
-        let mut entity_commands = commands.spawn((
-            mesh,
-            material,
-            Transform::default(),
-            GlobalTransform::default(),
-            Visibility::default(),
-            ComputedVisibility::default(),
-        ));
-
-        if load_clip_faces {
+        if load_clip_faces && pooled_entity.is_none() {
             let zms_material_num_faces = asset_server.load::<ZmsMaterialNumFaces, _>(format!(
                 "{}#material_num_faces",
                 model_list.meshes[mesh_id].path().to_string_lossy()
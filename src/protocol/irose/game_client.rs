@@ -1,20 +1,21 @@
 use async_trait::async_trait;
 use num_traits::FromPrimitive;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 use tokio::net::TcpStream;
 
-use rose_data::{QuestTriggerHash, SkillId};
+use rose_data::{QuestTriggerHash, SkillId, ZoneId};
 use rose_game_common::{
-    components::MoveMode,
+    components::{ItemSlot, MoveMode},
     messages::{
         client::ClientMessage,
         server::{
             CharacterData, CharacterDataItems, ConnectionRequestError, ServerMessage,
             SpawnEntityCharacter,
         },
+        ClientEntityId,
     },
 };
-use rose_network_common::{Connection, Packet, PacketCodec};
+use rose_network_common::{Connection, Packet, PacketCodec, PacketWriter};
 use rose_network_irose::{
     game_client_packets::{
         PacketClientAttack, PacketClientBankMoveItem, PacketClientBankOpen,
@@ -65,12 +66,374 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    report_unsupported_packet, AccountSettingsBlob, AccountSettingsMessage, ClanChat,
+    ClanMarkChange, ClanMemberLastOnline, ClanNotice, ClanWarInfo, ExtendedClientMessage,
+    ExtendedServerMessage, IdleWarning, ItemStackSplit, MinigameInputs, MinigameStart,
+    PartyMemberTarget, PlayerReport, ProtocolClient, ProtocolClientError, ResurrectionOffer,
+    ResurrectionResponse, SkillRefund, SummonCommand, SummonCommandKind, SummonInfo, TradeChat,
+    UnsupportedPacket, ZoneDataChecksums, ZoneDataFileChecksum,
+};
+
+// Not part of the original irose protocol, used to store client settings on
+// servers which support it. A settings blob is a null terminated key, u64
+// timestamp, u32 data length and then the data.
+const PACKET_CLIENT_REQUEST_ACCOUNT_SETTINGS: u16 = 0x7fb;
+const PACKET_CLIENT_SAVE_ACCOUNT_SETTINGS: u16 = 0x7fc;
+const PACKET_SERVER_ACCOUNT_SETTINGS: u16 = 0x7fd;
+
+// Not part of the original irose protocol, the client requests a u8 minigame
+// id and the server starts the session with a u32 session id, u8 minigame id
+// and u32 seed. When the minigame finishes the client sends the u32 session id
+// and a u16 count of inputs, each a u32 time in milliseconds and u8 which is 1
+// when the action key was pressed. A cancel is the u32 session id.
+const PACKET_CLIENT_MINIGAME_REQUEST: u16 = 0x7f6;
+const PACKET_SERVER_MINIGAME_START: u16 = 0x7e2;
+const PACKET_CLIENT_MINIGAME_INPUTS: u16 = 0x7e1;
+const PACKET_CLIENT_MINIGAME_CANCEL: u16 = 0x7e0;
+
+// Not part of the original irose protocol, the summon list is a u8 count of
+// visible summons, each a u16 entity id, u16 owner entity id and u32 remaining
+// and u32 total lifetime in milliseconds, a total of 0 means no time limit.
+// A summon command is a u16 summon entity id, u8 command and u16 target entity
+// id which is 0 when there is no target.
+const PACKET_CLIENT_SUMMON_COMMAND: u16 = 0x7f4;
+const PACKET_SERVER_SUMMON_LIST: u16 = 0x7f5;
+
+// Not part of the original irose protocol, the client sends a u16 entity id of
+// its selected target whenever it changes while in a party. The server sends a
+// u8 count of party members, each a u16 entity id and u16 target entity id, an
+// entity id of 0 means there is no target.
+const PACKET_CLIENT_PARTY_TARGET: u16 = 0x7f2;
+const PACKET_SERVER_PARTY_MEMBER_TARGETS: u16 = 0x7f3;
+
+// Not part of the original irose protocol, a resurrection offer is a u16 caster
+// entity id and u16 skill id. The response is the u16 caster entity id and a u8
+// which is 1 when the offer was accepted.
+const PACKET_CLIENT_RESURRECTION_RESPONSE: u16 = 0x7f0;
+const PACKET_SERVER_RESURRECTION_OFFER: u16 = 0x7f1;
+
+// Not part of the original irose protocol, a player report is the null
+// terminated name of the reported player, u8 category, null terminated
+// description and a u8 count of null terminated chat lines.
+const PACKET_CLIENT_PLAYER_REPORT: u16 = 0x7ef;
+
+// Not part of the original irose protocol, an idle warning is a u32 number of
+// seconds until the player is disconnected. The keep alive has no data.
+const PACKET_CLIENT_IDLE_KEEP_ALIVE: u16 = 0x7ed;
+const PACKET_SERVER_IDLE_WARNING: u16 = 0x7ee;
+
+// Not part of the original irose protocol, a stack split is the u8 page and
+// u16 slot of the source and destination inventory slots followed by the u32
+// quantity to move. The server replies with the usual inventory update.
+const PACKET_CLIENT_ITEM_STACK_SPLIT: u16 = 0x7ec;
+
+// Not part of the original irose protocol, the client requests the checksums
+// for a u16 zone id. The reply is the u16 zone id and a u8 count of files, each
+// a null terminated vfs path followed by the 16 byte md5 of the file.
+const PACKET_CLIENT_REQUEST_ZONE_DATA_CHECKSUMS: u16 = 0x7e8;
+const PACKET_SERVER_ZONE_DATA_CHECKSUMS: u16 = 0x7e9;
+
+// Not part of the original irose protocol, sent after the spawn packet of a
+// monster which has just been spawned rather than one which came into view,
+// the data is the u16 entity id of the monster.
+const PACKET_SERVER_MONSTER_SPAWNED: u16 = 0x7e7;
+
+// Not part of the original irose protocol, the client sends the null
+// terminated text and the server broadcasts the null terminated sender name
+// and text to the zone. The server is responsible for any rate limiting.
+const PACKET_CLIENT_TRADE_CHAT: u16 = 0x7e5;
+const PACKET_SERVER_TRADE_CHAT: u16 = 0x7e6;
+
+// Not part of the original irose protocol, the same format as trade chat but
+// the server only relays the message to the clan of the sender.
+const PACKET_CLIENT_CLAN_CHAT: u16 = 0x7e3;
+const PACKET_SERVER_CLAN_CHAT: u16 = 0x7e4;
+
+// Not part of the original irose protocol, a notice is posted with its null
+// terminated text and deleted with its u32 id. The server sends a u8 count of
+// notices when the player logs in and whenever they change, each a u32 id, u64
+// timestamp and the null terminated author and text.
+const PACKET_CLIENT_CLAN_NOTICE_POST: u16 = 0x7df;
+const PACKET_CLIENT_CLAN_NOTICE_DELETE: u16 = 0x7de;
+const PACKET_SERVER_CLAN_NOTICES: u16 = 0x7dd;
+
+// Not part of the original irose protocol, the clan master declares war on or
+// surrenders to the null terminated name of another clan. The server sends
+// both clans a u8 count of their wars when the player logs in and whenever a
+// war starts, ends or its score changes, each the null terminated name of the
+// opposing clan, u32 seconds remaining and u32 kills and deaths.
+const PACKET_CLIENT_CLAN_WAR_DECLARE: u16 = 0x7dc;
+const PACKET_CLIENT_CLAN_WAR_SURRENDER: u16 = 0x7db;
+const PACKET_SERVER_CLAN_WARS: u16 = 0x7da;
+
+// Not part of the original irose protocol, the clan master hands over the clan
+// to the member with the null terminated name. The server replies with the
+// member position updates of the original protocol.
+const PACKET_CLIENT_CLAN_ENTRUST: u16 = 0x7d9;
+
+// Not part of the original irose protocol, sent after the clan member list and
+// whenever a member disconnects. A u16 count of offline members, each the null
+// terminated name and u64 timestamp of when they were last online.
+const PACKET_SERVER_CLAN_MEMBER_LAST_ONLINE: u16 = 0x7d8;
+
+// Not part of the original irose protocol, a member uses the u16 id of a clan
+// skill. The server checks the clan grade and member rank, takes the clan
+// points and replies with the clan info and skill effect of the original protocol.
+const PACKET_CLIENT_CLAN_SKILL_ACTIVATE: u16 = 0x7d7;
+
+// Not part of the original irose protocol, asks the character with the u16
+// entity id to trade. The server relays the request and opens the trade using
+// the trade packets of the original protocol.
+const PACKET_CLIENT_TRADE_REQUEST: u16 = 0x7d6;
+
+// Not part of the original irose protocol, the clan master changes the clan
+// mark. A u8 0 followed by the u16 background and foreground of a premade mark,
+// or a u8 1 followed by the u16 width, height and RGB565 pixels of a custom
+// mark. The server replies with the clan updates of the original protocol.
+const PACKET_CLIENT_CLAN_MARK_CHANGE: u16 = 0x7d5;
+
+// Not part of the original irose protocol, uses the skill reset item in the u8
+// inventory page and u16 slot to refund the u16 skill id. The server replies
+// with the inventory, skill and skill point updates of the original protocol.
+const PACKET_CLIENT_SKILL_REFUND: u16 = 0x7d4;
+
+// Server packets which can fail to parse without disconnecting
+const OPTIONAL_SERVER_PACKETS: &[u16] = &[
+    PACKET_SERVER_ACCOUNT_SETTINGS,
+    PACKET_SERVER_MINIGAME_START,
+    PACKET_SERVER_SUMMON_LIST,
+    PACKET_SERVER_PARTY_MEMBER_TARGETS,
+    PACKET_SERVER_RESURRECTION_OFFER,
+    PACKET_SERVER_IDLE_WARNING,
+    PACKET_SERVER_ZONE_DATA_CHECKSUMS,
+    PACKET_SERVER_MONSTER_SPAWNED,
+    PACKET_SERVER_TRADE_CHAT,
+    PACKET_SERVER_CLAN_CHAT,
+    PACKET_SERVER_CLAN_NOTICES,
+    PACKET_SERVER_CLAN_WARS,
+    PACKET_SERVER_CLAN_MEMBER_LAST_ONLINE,
+];
+
+fn read_resurrection_offer_packet(data: &[u8]) -> Option<ResurrectionOffer> {
+    let caster_entity_id = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?);
+    let skill_id = u16::from_le_bytes(data.get(2..4)?.try_into().ok()?);
+
+    Some(ResurrectionOffer {
+        caster_entity_id: ClientEntityId(caster_entity_id as usize),
+        skill_id: SkillId::new(skill_id)?,
+    })
+}
+
+fn read_zone_data_checksums_packet(data: &[u8]) -> Option<ZoneDataChecksums> {
+    let zone_id = ZoneId::new(u16::from_le_bytes(data.get(0..2)?.try_into().ok()?))?;
+    let count = *data.get(2)? as usize;
+    let mut data = &data[3..];
+    let mut files = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let path_length = data.iter().position(|&c| c == 0)?;
+        let path = String::from_utf8_lossy(&data[..path_length]).into_owned();
+        let md5: [u8; 16] = data
+            .get(path_length + 1..path_length + 17)?
+            .try_into()
+            .ok()?;
+        files.push(ZoneDataFileChecksum { path, md5 });
+        data = &data[path_length + 17..];
+    }
+
+    Some(ZoneDataChecksums { zone_id, files })
+}
+
+fn read_idle_warning_packet(data: &[u8]) -> Option<IdleWarning> {
+    let seconds = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+
+    Some(IdleWarning {
+        disconnect_in: Duration::from_secs(seconds as u64),
+    })
+}
+
+fn read_monster_spawned_packet(data: &[u8]) -> Option<ClientEntityId> {
+    let entity_id = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?);
+
+    Some(ClientEntityId(entity_id as usize))
+}
+
+fn read_trade_chat_packet(data: &[u8]) -> Option<TradeChat> {
+    let name_length = data.iter().position(|&c| c == 0)?;
+    let name = String::from_utf8_lossy(&data[..name_length]).into_owned();
+    let data = &data[name_length + 1..];
+
+    let text_length = data.iter().position(|&c| c == 0)?;
+    let text = String::from_utf8_lossy(&data[..text_length]).into_owned();
+
+    Some(TradeChat { name, text })
+}
+
+fn read_clan_chat_packet(data: &[u8]) -> Option<ClanChat> {
+    let trade_chat = read_trade_chat_packet(data)?;
+
+    Some(ClanChat {
+        name: trade_chat.name,
+        text: trade_chat.text,
+    })
+}
+
+fn read_minigame_start_packet(data: &[u8]) -> Option<MinigameStart> {
+    let session_id = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+    let minigame_id = *data.get(4)?;
+    let seed = u32::from_le_bytes(data.get(5..9)?.try_into().ok()?);
+
+    Some(MinigameStart {
+        session_id,
+        minigame_id,
+        seed,
+    })
+}
+
+fn read_clan_notices_packet(data: &[u8]) -> Option<Vec<ClanNotice>> {
+    let count = *data.first()? as usize;
+    let mut data = &data[1..];
+    let mut notices = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let id = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+        let timestamp = u64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+        data = &data[12..];
+
+        let author_length = data.iter().position(|&c| c == 0)?;
+        let author = String::from_utf8_lossy(&data[..author_length]).into_owned();
+        data = &data[author_length + 1..];
+
+        let text_length = data.iter().position(|&c| c == 0)?;
+        let text = String::from_utf8_lossy(&data[..text_length]).into_owned();
+        data = &data[text_length + 1..];
+
+        notices.push(ClanNotice {
+            id,
+            timestamp: timestamp as i64,
+            author,
+            text,
+        });
+    }
+
+    Some(notices)
+}
+
+fn read_clan_wars_packet(data: &[u8]) -> Option<Vec<ClanWarInfo>> {
+    let count = *data.first()? as usize;
+    let mut data = &data[1..];
+    let mut wars = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let opponent_length = data.iter().position(|&c| c == 0)?;
+        let opponent = String::from_utf8_lossy(&data[..opponent_length]).into_owned();
+        data = &data[opponent_length + 1..];
+
+        let remaining_seconds = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+        let kills = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?);
+        let deaths = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?);
+        data = &data[12..];
+
+        wars.push(ClanWarInfo {
+            opponent,
+            remaining_time: Duration::from_secs(remaining_seconds as u64),
+            kills,
+            deaths,
+        });
+    }
+
+    Some(wars)
+}
+
+fn read_clan_member_last_online_packet(data: &[u8]) -> Option<Vec<ClanMemberLastOnline>> {
+    let count = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?) as usize;
+    let mut data = &data[2..];
+    let mut members = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let name_length = data.iter().position(|&c| c == 0)?;
+        let name = String::from_utf8_lossy(&data[..name_length]).into_owned();
+        data = &data[name_length + 1..];
+
+        let timestamp = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?);
+        data = &data[8..];
+
+        members.push(ClanMemberLastOnline {
+            name,
+            timestamp: timestamp as i64,
+        });
+    }
+
+    Some(members)
+}
+
+fn read_party_member_targets_packet(data: &[u8]) -> Option<Vec<PartyMemberTarget>> {
+    let count = *data.first()? as usize;
+    Some(
+        data.get(1..1 + count * 4)?
+            .chunks_exact(4)
+            .map(|member| {
+                let target_entity_id = u16::from_le_bytes([member[2], member[3]]);
+
+                PartyMemberTarget {
+                    entity_id: ClientEntityId(u16::from_le_bytes([member[0], member[1]]) as usize),
+                    target_entity_id: (target_entity_id != 0)
+                        .then(|| ClientEntityId(target_entity_id as usize)),
+                }
+            })
+            .collect(),
+    )
+}
+
+fn read_summon_list_packet(data: &[u8]) -> Option<Vec<SummonInfo>> {
+    let count = *data.first()? as usize;
+    Some(
+        data.get(1..1 + count * 12)?
+            .chunks_exact(12)
+            .map(|summon| {
+                let read_u16 =
+                    |offset: usize| u16::from_le_bytes([summon[offset], summon[offset + 1]]);
+                let read_u32 = |offset: usize| {
+                    u32::from_le_bytes(summon[offset..offset + 4].try_into().unwrap())
+                };
+                let has_lifetime = read_u32(8) != 0;
+
+                SummonInfo {
+                    entity_id: ClientEntityId(read_u16(0) as usize),
+                    owner_entity_id: ClientEntityId(read_u16(2) as usize),
+                    remaining_lifetime: has_lifetime
+                        .then(|| Duration::from_millis(read_u32(4) as u64)),
+                    total_lifetime: has_lifetime.then(|| Duration::from_millis(read_u32(8) as u64)),
+                }
+            })
+            .collect(),
+    )
+}
+
+fn read_account_settings_packet(data: &[u8]) -> Option<AccountSettingsBlob> {
+    let key_length = data.iter().position(|&c| c == 0)?;
+    let key = String::from_utf8_lossy(&data[..key_length]).into_owned();
+    let data = &data[key_length + 1..];
+
+    let timestamp = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?);
+    let data_length = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?) as usize;
+    let data = data.get(12..12 + data_length)?.to_vec();
+
+    Some(AccountSettingsBlob {
+        key,
+        timestamp,
+        data,
+    })
+}
 
 pub struct GameClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
     server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    extended_client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ExtendedClientMessage>,
+    extended_server_message_tx: crossbeam_channel::Sender<ExtendedServerMessage>,
+    unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
 }
 
@@ -80,16 +443,139 @@ impl GameClient {
         packet_codec_seed: u32,
         client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
         server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+        extended_client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ExtendedClientMessage>,
+        extended_server_message_tx: crossbeam_channel::Sender<ExtendedServerMessage>,
+        unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     ) -> Self {
         Self {
             server_address,
             client_message_rx,
             server_message_tx,
+            extended_client_message_rx,
+            extended_server_message_tx,
+            unsupported_packet_tx,
             packet_codec: Box::new(ClientPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
         }
     }
 
     async fn handle_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+        if packet.command == PACKET_SERVER_ACCOUNT_SETTINGS {
+            let blob = read_account_settings_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid account settings packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::AccountSettings(blob))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_MINIGAME_START {
+            let start = read_minigame_start_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid minigame start packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::MinigameStart(start))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_SUMMON_LIST {
+            let summons = read_summon_list_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid summon list packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::SummonList(summons))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_PARTY_MEMBER_TARGETS {
+            let targets = read_party_member_targets_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid party member targets packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::PartyMemberTargets(targets))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_RESURRECTION_OFFER {
+            let offer = read_resurrection_offer_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid resurrection offer packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ResurrectionOffer(offer))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_IDLE_WARNING {
+            let warning = read_idle_warning_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid idle warning packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::IdleWarning(warning))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_ZONE_DATA_CHECKSUMS {
+            let checksums = read_zone_data_checksums_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid zone data checksums packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ZoneDataChecksums(checksums))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_MONSTER_SPAWNED {
+            let entity_id = read_monster_spawned_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid monster spawned packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::MonsterSpawned(entity_id))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_TRADE_CHAT {
+            let trade_chat = read_trade_chat_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid trade chat packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::TradeChat(trade_chat))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CLAN_CHAT {
+            let clan_chat = read_clan_chat_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid clan chat packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ClanChat(clan_chat))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CLAN_NOTICES {
+            let notices = read_clan_notices_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid clan notices packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ClanNotices(notices))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CLAN_WARS {
+            let wars = read_clan_wars_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid clan wars packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ClanWars(wars))
+                .ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CLAN_MEMBER_LAST_ONLINE {
+            let members = read_clan_member_last_online_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid clan member last online packet"))?;
+            self.extended_server_message_tx
+                .send(ExtendedServerMessage::ClanMemberLastOnline(members))
+                .ok();
+            return Ok(());
+        }
+
         match FromPrimitive::from_u16(packet.command) {
             Some(ServerPackets::ConnectReply) => {
                 let response = PacketConnectionReply::try_from(packet)?;
@@ -1159,8 +1645,22 @@ impl GameClient {
                     "Unimplemented ServerPackets::RepairedItemUsingItem {:?}",
                     packet
                 );
+                report_unsupported_packet(
+                    &self.unsupported_packet_tx,
+                    "GameClient",
+                    packet,
+                    "Unimplemented RepairedItemUsingItem",
+                );
+            }
+            None => {
+                log::info!("Unhandled GameClient packet {:?}", packet);
+                report_unsupported_packet(
+                    &self.unsupported_packet_tx,
+                    "GameClient",
+                    packet,
+                    "Unhandled packet",
+                );
             }
-            None => log::info!("Unhandled GameClient packet {:?}", packet),
         }
 
         Ok(())
@@ -1688,6 +2188,389 @@ impl GameClient {
         }
         Ok(())
     }
+
+    async fn handle_extended_client_message(
+        &self,
+        connection: &mut Connection<'_>,
+        message: ExtendedClientMessage,
+    ) -> Result<(), anyhow::Error> {
+        match message {
+            ExtendedClientMessage::AccountSettings(message) => {
+                self.handle_account_settings_message(connection, message)
+                    .await
+            }
+            ExtendedClientMessage::MinigameRequest(minigame_id) => {
+                self.handle_minigame_request(connection, minigame_id).await
+            }
+            ExtendedClientMessage::MinigameInputs(inputs) => {
+                self.handle_minigame_inputs(connection, inputs).await
+            }
+            ExtendedClientMessage::MinigameCancel(session_id) => {
+                self.handle_minigame_cancel(connection, session_id).await
+            }
+            ExtendedClientMessage::SummonCommand(command) => {
+                self.handle_summon_command(connection, command).await
+            }
+            ExtendedClientMessage::PartyTarget(target_entity_id) => {
+                self.handle_party_target(connection, target_entity_id).await
+            }
+            ExtendedClientMessage::ResurrectionResponse(response) => {
+                self.handle_resurrection_response(connection, response)
+                    .await
+            }
+            ExtendedClientMessage::PlayerReport(report) => {
+                self.handle_player_report(connection, report).await
+            }
+            ExtendedClientMessage::IdleKeepAlive => self.handle_idle_keep_alive(connection).await,
+            ExtendedClientMessage::ItemStackSplit(split) => {
+                self.handle_item_stack_split(connection, split).await
+            }
+            ExtendedClientMessage::SkillRefund(refund) => {
+                self.handle_skill_refund(connection, refund).await
+            }
+            ExtendedClientMessage::RequestZoneDataChecksums(zone_id) => {
+                self.handle_zone_data_checksums_request(connection, zone_id)
+                    .await
+            }
+            ExtendedClientMessage::TradeChat(text) => {
+                self.handle_trade_chat(connection, text).await
+            }
+            ExtendedClientMessage::TradeRequest(target_entity_id) => {
+                self.handle_trade_request(connection, target_entity_id)
+                    .await
+            }
+            ExtendedClientMessage::ClanChat(text) => self.handle_clan_chat(connection, text).await,
+            ExtendedClientMessage::ClanNoticePost(text) => {
+                self.handle_clan_notice_post(connection, text).await
+            }
+            ExtendedClientMessage::ClanNoticeDelete(id) => {
+                self.handle_clan_notice_delete(connection, id).await
+            }
+            ExtendedClientMessage::ClanWarDeclare(clan_name) => {
+                self.handle_clan_war_command(connection, PACKET_CLIENT_CLAN_WAR_DECLARE, clan_name)
+                    .await
+            }
+            ExtendedClientMessage::ClanWarSurrender(clan_name) => {
+                self.handle_clan_war_command(
+                    connection,
+                    PACKET_CLIENT_CLAN_WAR_SURRENDER,
+                    clan_name,
+                )
+                .await
+            }
+            ExtendedClientMessage::ClanEntrust(name) => {
+                self.handle_clan_entrust(connection, name).await
+            }
+            ExtendedClientMessage::ClanSkillActivate(skill_id) => {
+                self.handle_clan_skill_activate(connection, skill_id).await
+            }
+            ExtendedClientMessage::ClanMarkChange(mark) => {
+                self.handle_clan_mark_change(connection, mark).await
+            }
+        }
+    }
+
+    async fn handle_account_settings_message(
+        &self,
+        connection: &mut Connection<'_>,
+        message: AccountSettingsMessage,
+    ) -> Result<(), anyhow::Error> {
+        match message {
+            AccountSettingsMessage::RequestAll => {
+                connection
+                    .write_packet(PacketWriter::new(PACKET_CLIENT_REQUEST_ACCOUNT_SETTINGS).into())
+                    .await?;
+            }
+            AccountSettingsMessage::Save(blob) => {
+                let mut writer = PacketWriter::new(PACKET_CLIENT_SAVE_ACCOUNT_SETTINGS);
+                writer.write_null_terminated_utf8(&blob.key);
+                writer.write_u64(blob.timestamp);
+                writer.write_u32(blob.data.len() as u32);
+                writer.write_bytes(&blob.data);
+                connection.write_packet(writer.into()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_minigame_request(
+        &self,
+        connection: &mut Connection<'_>,
+        minigame_id: u8,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_MINIGAME_REQUEST);
+        writer.write_u8(minigame_id);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_minigame_inputs(
+        &self,
+        connection: &mut Connection<'_>,
+        inputs: MinigameInputs,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_MINIGAME_INPUTS);
+        writer.write_u32(inputs.session_id);
+        writer.write_u16(inputs.inputs.len().min(u16::MAX as usize) as u16);
+        for input in inputs.inputs.iter().take(u16::MAX as usize) {
+            writer.write_u32(input.time.as_millis().min(u32::MAX as u128) as u32);
+            writer.write_u8(input.held as u8);
+        }
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_minigame_cancel(
+        &self,
+        connection: &mut Connection<'_>,
+        session_id: u32,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_MINIGAME_CANCEL);
+        writer.write_u32(session_id);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_summon_command(
+        &self,
+        connection: &mut Connection<'_>,
+        command: SummonCommand,
+    ) -> Result<(), anyhow::Error> {
+        let (command_id, target_entity_id) = match command.kind {
+            SummonCommandKind::Attack(target_entity_id) => (0, target_entity_id.0 as u16),
+            SummonCommandKind::Passive => (1, 0),
+            SummonCommandKind::Dismiss => (2, 0),
+        };
+
+        let mut writer = PacketWriter::new(PACKET_CLIENT_SUMMON_COMMAND);
+        writer.write_u16(command.summon_entity_id.0 as u16);
+        writer.write_u8(command_id);
+        writer.write_u16(target_entity_id);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_party_target(
+        &self,
+        connection: &mut Connection<'_>,
+        target_entity_id: Option<ClientEntityId>,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_PARTY_TARGET);
+        writer.write_u16(target_entity_id.map_or(0, |entity_id| entity_id.0 as u16));
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_trade_request(
+        &self,
+        connection: &mut Connection<'_>,
+        target_entity_id: ClientEntityId,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_TRADE_REQUEST);
+        writer.write_u16(target_entity_id.0 as u16);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_resurrection_response(
+        &self,
+        connection: &mut Connection<'_>,
+        response: ResurrectionResponse,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_RESURRECTION_RESPONSE);
+        writer.write_u16(response.caster_entity_id.0 as u16);
+        writer.write_u8(response.accepted as u8);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_player_report(
+        &self,
+        connection: &mut Connection<'_>,
+        report: PlayerReport,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_PLAYER_REPORT);
+        writer.write_null_terminated_utf8(&report.name);
+        writer.write_u8(report.category as u8);
+        writer.write_null_terminated_utf8(&report.description);
+        writer.write_u8(report.chat_lines.len().min(u8::MAX as usize) as u8);
+        for line in report.chat_lines.iter().take(u8::MAX as usize) {
+            writer.write_null_terminated_utf8(line);
+        }
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_idle_keep_alive(
+        &self,
+        connection: &mut Connection<'_>,
+    ) -> Result<(), anyhow::Error> {
+        connection
+            .write_packet(PacketWriter::new(PACKET_CLIENT_IDLE_KEEP_ALIVE).into())
+            .await?;
+        Ok(())
+    }
+
+    async fn handle_item_stack_split(
+        &self,
+        connection: &mut Connection<'_>,
+        split: ItemStackSplit,
+    ) -> Result<(), anyhow::Error> {
+        let (
+            ItemSlot::Inventory(source_page, source_slot),
+            ItemSlot::Inventory(destination_page, destination_slot),
+        ) = (split.source, split.destination)
+        else {
+            return Ok(());
+        };
+
+        let mut writer = PacketWriter::new(PACKET_CLIENT_ITEM_STACK_SPLIT);
+        writer.write_u8(source_page as u8);
+        writer.write_u16(source_slot as u16);
+        writer.write_u8(destination_page as u8);
+        writer.write_u16(destination_slot as u16);
+        writer.write_u32(split.quantity as u32);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_skill_refund(
+        &self,
+        connection: &mut Connection<'_>,
+        refund: SkillRefund,
+    ) -> Result<(), anyhow::Error> {
+        let ItemSlot::Inventory(page, slot) = refund.item_slot else {
+            return Ok(());
+        };
+
+        let mut writer = PacketWriter::new(PACKET_CLIENT_SKILL_REFUND);
+        writer.write_u8(page as u8);
+        writer.write_u16(slot as u16);
+        writer.write_u16(refund.skill_id.get());
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_zone_data_checksums_request(
+        &self,
+        connection: &mut Connection<'_>,
+        zone_id: ZoneId,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_REQUEST_ZONE_DATA_CHECKSUMS);
+        writer.write_u16(zone_id.get());
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_trade_chat(
+        &self,
+        connection: &mut Connection<'_>,
+        text: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_TRADE_CHAT);
+        writer.write_null_terminated_utf8(&text);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_chat(
+        &self,
+        connection: &mut Connection<'_>,
+        text: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_CHAT);
+        writer.write_null_terminated_utf8(&text);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_notice_post(
+        &self,
+        connection: &mut Connection<'_>,
+        text: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_NOTICE_POST);
+        writer.write_null_terminated_utf8(&text);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_notice_delete(
+        &self,
+        connection: &mut Connection<'_>,
+        id: u32,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_NOTICE_DELETE);
+        writer.write_u32(id);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_war_command(
+        &self,
+        connection: &mut Connection<'_>,
+        command: u16,
+        clan_name: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(command);
+        writer.write_null_terminated_utf8(&clan_name);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_entrust(
+        &self,
+        connection: &mut Connection<'_>,
+        name: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_ENTRUST);
+        writer.write_null_terminated_utf8(&name);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_skill_activate(
+        &self,
+        connection: &mut Connection<'_>,
+        skill_id: SkillId,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_SKILL_ACTIVATE);
+        writer.write_u16(skill_id.get());
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_clan_mark_change(
+        &self,
+        connection: &mut Connection<'_>,
+        mark: ClanMarkChange,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CLAN_MARK_CHANGE);
+        match mark {
+            ClanMarkChange::Premade {
+                background,
+                foreground,
+            } => {
+                writer.write_u8(0);
+                writer.write_u16(background.get());
+                writer.write_u16(foreground.get());
+            }
+            ClanMarkChange::Custom {
+                width,
+                height,
+                pixels,
+            } => {
+                writer.write_u8(1);
+                writer.write_u16(width);
+                writer.write_u16(height);
+                for pixel in pixels {
+                    writer.write_u16(pixel);
+                }
+            }
+        }
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
 }
 
-implement_protocol_client! { GameClient }
+implement_protocol_client! { GameClient, extended_client_message_rx => handle_extended_client_message }
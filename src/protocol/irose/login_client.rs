@@ -9,7 +9,7 @@ use rose_game_common::messages::{
         ChannelListError, ConnectionRequestError, JoinServerError, LoginError, ServerMessage,
     },
 };
-use rose_network_common::{Connection, Packet, PacketCodec};
+use rose_network_common::{Connection, Packet, PacketCodec, PacketWriter};
 use rose_network_irose::{
     login_client_packets::{
         PacketClientChannelList, PacketClientConnect, PacketClientLoginRequest,
@@ -22,12 +22,62 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    report_unsupported_packet, LoginChallenge, LoginChallengeKind, ProtocolCapabilities,
+    ProtocolClient, ProtocolClientError, ServerCapabilities, UnsupportedPacket,
+    CLIENT_CAPABILITIES, CLIENT_PROTOCOL_VERSION,
+};
+
+// Not part of the original irose protocol, sent by servers which require a PIN
+// or one time password before sending the server list. The challenge contains
+// u8 kind (0 = PIN, 1 = OTP), u8 code length and u8 set when the previous code
+// was wrong. The response contains the null terminated code.
+const PACKET_CLIENT_LOGIN_CHALLENGE_RESPONSE: u16 = 0x7f9;
+const PACKET_SERVER_LOGIN_CHALLENGE: u16 = 0x7fa;
+
+// Not part of the original irose protocol, servers which support it send their
+// u16 protocol version and u32 capability flags after the connection reply. The
+// client only replies with its own version and capabilities once the server has
+// sent them, so older servers never receive a packet they do not know.
+const PACKET_CLIENT_CAPABILITIES: u16 = 0x7ea;
+const PACKET_SERVER_CAPABILITIES: u16 = 0x7eb;
+
+// Server packets which can fail to parse without disconnecting
+const OPTIONAL_SERVER_PACKETS: &[u16] = &[PACKET_SERVER_CAPABILITIES];
+
+fn read_server_capabilities_packet(data: &[u8]) -> Option<ServerCapabilities> {
+    Some(ServerCapabilities {
+        protocol_version: u16::from_le_bytes(data.get(0..2)?.try_into().ok()?),
+        capabilities: ProtocolCapabilities::from_bits_truncate(u32::from_le_bytes(
+            data.get(2..6)?.try_into().ok()?,
+        )),
+    })
+}
+
+fn read_login_challenge_packet(data: &[u8]) -> Option<LoginChallenge> {
+    let kind = match data.first()? {
+        0 => LoginChallengeKind::Pin,
+        1 => LoginChallengeKind::OneTimePassword,
+        _ => return None,
+    };
+
+    Some(LoginChallenge {
+        kind,
+        code_length: *data.get(1)? as usize,
+        previous_attempt_failed: *data.get(2)? != 0,
+    })
+}
 
 pub struct LoginClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
     server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    login_challenge_tx: crossbeam_channel::Sender<LoginChallenge>,
+    login_challenge_response_rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+    server_capabilities_tx: crossbeam_channel::Sender<ServerCapabilities>,
+    client_capabilities_tx: tokio::sync::mpsc::UnboundedSender<ProtocolCapabilities>,
+    client_capabilities_rx: tokio::sync::mpsc::UnboundedReceiver<ProtocolCapabilities>,
+    unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
 }
 
@@ -36,16 +86,44 @@ impl LoginClient {
         server_address: SocketAddr,
         client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
         server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+        login_challenge_tx: crossbeam_channel::Sender<LoginChallenge>,
+        login_challenge_response_rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+        server_capabilities_tx: crossbeam_channel::Sender<ServerCapabilities>,
+        unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     ) -> Self {
+        let (client_capabilities_tx, client_capabilities_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+
         Self {
             server_address,
             client_message_rx,
             server_message_tx,
+            login_challenge_tx,
+            login_challenge_response_rx,
+            server_capabilities_tx,
+            client_capabilities_tx,
+            client_capabilities_rx,
+            unsupported_packet_tx,
             packet_codec: Box::new(ClientPacketCodec::default(&IROSE_112_TABLE)),
         }
     }
 
     async fn handle_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+        if packet.command == PACKET_SERVER_LOGIN_CHALLENGE {
+            let challenge = read_login_challenge_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid login challenge packet"))?;
+            self.login_challenge_tx.send(challenge).ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CAPABILITIES {
+            let capabilities = read_server_capabilities_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid server capabilities packet"))?;
+            self.server_capabilities_tx.send(capabilities).ok();
+            self.client_capabilities_tx.send(CLIENT_CAPABILITIES).ok();
+            return Ok(());
+        }
+
         match FromPrimitive::from_u16(packet.command) {
             Some(ServerPackets::NetworkStatus) => {
                 let response = PacketConnectionReply::try_from(packet)?;
@@ -121,7 +199,15 @@ impl LoginClient {
                 };
                 self.server_message_tx.send(message).ok();
             }
-            _ => log::info!("Unhandled LoginClient packet {:?}", packet),
+            _ => {
+                log::info!("Unhandled LoginClient packet {:?}", packet);
+                report_unsupported_packet(
+                    &self.unsupported_packet_tx,
+                    "LoginClient",
+                    packet,
+                    "Unhandled packet",
+                );
+            }
         }
 
         Ok(())
@@ -171,6 +257,33 @@ impl LoginClient {
         }
         Ok(())
     }
+
+    async fn handle_client_capabilities(
+        &self,
+        connection: &mut Connection<'_>,
+        capabilities: ProtocolCapabilities,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_CAPABILITIES);
+        writer.write_u16(CLIENT_PROTOCOL_VERSION);
+        writer.write_u32(capabilities.bits());
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
+
+    async fn handle_login_challenge_response(
+        &self,
+        connection: &mut Connection<'_>,
+        code: String,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_LOGIN_CHALLENGE_RESPONSE);
+        writer.write_null_terminated_utf8(&code);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
 }
 
-implement_protocol_client! { LoginClient }
+implement_protocol_client! {
+    LoginClient,
+    login_challenge_response_rx => handle_login_challenge_response,
+    client_capabilities_rx => handle_client_capabilities
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use num_traits::FromPrimitive;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 use tokio::net::TcpStream;
 
 use rose_game_common::{
@@ -10,7 +10,7 @@ use rose_game_common::{
         server::{ConnectionRequestError, CreateCharacterError, ServerMessage},
     },
 };
-use rose_network_common::{Connection, Packet, PacketCodec};
+use rose_network_common::{Connection, Packet, PacketCodec, PacketWriter};
 use rose_network_irose::{
     world_client_packets::{
         PacketClientCharacterList, PacketClientClanCommand, PacketClientConnectRequest,
@@ -24,31 +24,109 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    report_unsupported_packet, CharacterSlotInfo, LoginQueueStatus, ProtocolClient,
+    ProtocolClientError, UnsupportedPacket,
+};
+
+// Not part of the original irose protocol, sent by servers which queue logins
+// when the world is full. Contains a u16 queue position, u32 estimated wait in
+// seconds (0 when unknown) and a null terminated message of the day.
+const PACKET_SERVER_LOGIN_QUEUE: u16 = 0x7fe;
+
+// Not part of the original irose protocol, sent by servers with a variable
+// number of character slots. Contains u8 slot count, u8 max slot count and u32
+// price to unlock the next slot (0 when it can not be purchased). The purchase
+// request contains the u8 slot index to unlock.
+const PACKET_CLIENT_PURCHASE_CHARACTER_SLOT: u16 = 0x7f7;
+const PACKET_SERVER_CHARACTER_SLOTS: u16 = 0x7f8;
+
+// Server packets which can fail to parse without disconnecting
+const OPTIONAL_SERVER_PACKETS: &[u16] = &[PACKET_SERVER_CHARACTER_SLOTS];
+
+fn read_character_slots_packet(data: &[u8]) -> Option<CharacterSlotInfo> {
+    let unlock_price = u32::from_le_bytes(data.get(2..6)?.try_into().ok()?);
+
+    Some(CharacterSlotInfo {
+        slot_count: *data.first()? as usize,
+        max_slot_count: *data.get(1)? as usize,
+        unlock_price: if unlock_price > 0 {
+            Some(unlock_price)
+        } else {
+            None
+        },
+    })
+}
+
+fn read_login_queue_packet(data: &[u8]) -> Option<LoginQueueStatus> {
+    let position = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?);
+    let estimated_wait_seconds = u32::from_le_bytes(data.get(2..6)?.try_into().ok()?);
+    let message_of_the_day = data
+        .get(6..)
+        .and_then(|message| message.split(|&c| c == 0).next())
+        .unwrap_or_default();
+
+    Some(LoginQueueStatus {
+        position: position as u32,
+        estimated_wait: if estimated_wait_seconds > 0 {
+            Some(Duration::from_secs(estimated_wait_seconds as u64))
+        } else {
+            None
+        },
+        message_of_the_day: String::from_utf8_lossy(message_of_the_day).into_owned(),
+    })
+}
 
 pub struct WorldClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
     server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    login_queue_tx: crossbeam_channel::Sender<LoginQueueStatus>,
+    character_slots_tx: crossbeam_channel::Sender<CharacterSlotInfo>,
+    purchase_character_slot_rx: tokio::sync::mpsc::UnboundedReceiver<usize>,
+    unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
 }
 
 impl WorldClient {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         server_address: SocketAddr,
         packet_codec_seed: u32,
         client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
         server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+        login_queue_tx: crossbeam_channel::Sender<LoginQueueStatus>,
+        character_slots_tx: crossbeam_channel::Sender<CharacterSlotInfo>,
+        purchase_character_slot_rx: tokio::sync::mpsc::UnboundedReceiver<usize>,
+        unsupported_packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
     ) -> Self {
         Self {
             server_address,
             client_message_rx,
             server_message_tx,
+            login_queue_tx,
+            character_slots_tx,
+            purchase_character_slot_rx,
+            unsupported_packet_tx,
             packet_codec: Box::new(ClientPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
         }
     }
 
     async fn handle_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+        if packet.command == PACKET_SERVER_LOGIN_QUEUE {
+            let status = read_login_queue_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid login queue packet"))?;
+            self.login_queue_tx.send(status).ok();
+            return Ok(());
+        }
+
+        if packet.command == PACKET_SERVER_CHARACTER_SLOTS {
+            let character_slots = read_character_slots_packet(&packet.data)
+                .ok_or_else(|| anyhow::anyhow!("Invalid character slots packet"))?;
+            self.character_slots_tx.send(character_slots).ok();
+            return Ok(());
+        }
+
         match FromPrimitive::from_u16(packet.command) {
             Some(ServerPackets::ConnectReply) => {
                 let response = PacketConnectionReply::try_from(packet)?;
@@ -121,7 +199,15 @@ impl WorldClient {
                 self.server_message_tx.send(message).ok();
             }
             // ServerPackets::ReturnToCharacterSelect -> ServerMessage::ReturnToCharacterSelect
-            _ => log::info!("Unhandled WorldClient packet {:?}", packet),
+            _ => {
+                log::info!("Unhandled WorldClient packet {:?}", packet);
+                report_unsupported_packet(
+                    &self.unsupported_packet_tx,
+                    "WorldClient",
+                    packet,
+                    "Unhandled packet",
+                );
+            }
         }
 
         Ok(())
@@ -209,6 +295,17 @@ impl WorldClient {
         }
         Ok(())
     }
+
+    async fn handle_purchase_character_slot(
+        &self,
+        connection: &mut Connection<'_>,
+        slot: usize,
+    ) -> Result<(), anyhow::Error> {
+        let mut writer = PacketWriter::new(PACKET_CLIENT_PURCHASE_CHARACTER_SLOT);
+        writer.write_u8(slot as u8);
+        connection.write_packet(writer.into()).await?;
+        Ok(())
+    }
 }
 
-implement_protocol_client! { WorldClient }
+implement_protocol_client! { WorldClient, purchase_character_slot_rx => handle_purchase_character_slot }
@@ -1,12 +1,380 @@
+use std::{num::NonZeroU16, time::Duration};
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use rose_data::{SkillId, ZoneId};
+use rose_game_common::{components::ItemSlot, messages::ClientEntityId};
+
 #[derive(Debug, Error)]
 pub enum ProtocolClientError {
     #[error("client initiated disconnect")]
     ClientInitiatedDisconnect,
 }
 
+// Sent by the world server while waiting in the login queue, a position of 0
+// means the queue is complete
+#[derive(Clone, Debug)]
+pub struct LoginQueueStatus {
+    pub position: u32,
+    pub estimated_wait: Option<Duration>,
+    pub message_of_the_day: String,
+}
+
+// Sent by the world server when an account has a variable number of character
+// slots, slots between slot_count and max_slot_count can be purchased
+#[derive(Clone, Debug)]
+pub struct CharacterSlotInfo {
+    pub slot_count: usize,
+    pub max_slot_count: usize,
+    pub unlock_price: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoginChallengeKind {
+    Pin,
+    OneTimePassword,
+}
+
+// Exchanged with the login server when connecting, servers which do not reply
+// are older servers and are treated as supporting no optional features
+pub const CLIENT_PROTOCOL_VERSION: u16 = 1;
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ProtocolCapabilities: u32 {
+        const ACCOUNT_SETTINGS    = (1 << 0);
+        const PLAYER_REPORTS      = (1 << 1);
+        const CLAN_STORAGE        = (1 << 2);
+        const MAIL                = (1 << 3);
+        const AUCTION             = (1 << 4);
+        const ZONE_DATA_CHECKSUMS = (1 << 5);
+        const MONSTER_SPAWNED     = (1 << 6);
+        const TRADE_CHAT          = (1 << 7);
+        const CLAN_CHAT           = (1 << 8);
+        const PARTY_TARGETS       = (1 << 9);
+        const ITEM_STACK_SPLIT    = (1 << 10);
+        const MINIGAMES           = (1 << 11);
+        const SUMMON_COMMANDS     = (1 << 12);
+        const CLAN_NOTICES        = (1 << 13);
+        const CLAN_WARS           = (1 << 14);
+        const CLAN_ENTRUST        = (1 << 15);
+        const CLAN_LAST_ONLINE    = (1 << 16);
+        const CLAN_SKILLS         = (1 << 17);
+        const TRADE_REQUESTS      = (1 << 18);
+        const CLAN_MARKS          = (1 << 19);
+        const SKILL_REFUNDS       = (1 << 20);
+    }
+}
+
+// The optional features this client handles, sent to the login server so it
+// only uses those which both sides support
+pub const CLIENT_CAPABILITIES: ProtocolCapabilities = ProtocolCapabilities::ACCOUNT_SETTINGS
+    .union(ProtocolCapabilities::PLAYER_REPORTS)
+    .union(ProtocolCapabilities::ZONE_DATA_CHECKSUMS)
+    .union(ProtocolCapabilities::MONSTER_SPAWNED)
+    .union(ProtocolCapabilities::TRADE_CHAT)
+    .union(ProtocolCapabilities::CLAN_CHAT)
+    .union(ProtocolCapabilities::PARTY_TARGETS)
+    .union(ProtocolCapabilities::ITEM_STACK_SPLIT)
+    .union(ProtocolCapabilities::MINIGAMES)
+    .union(ProtocolCapabilities::SUMMON_COMMANDS)
+    .union(ProtocolCapabilities::CLAN_NOTICES)
+    .union(ProtocolCapabilities::CLAN_WARS)
+    .union(ProtocolCapabilities::CLAN_ENTRUST)
+    .union(ProtocolCapabilities::CLAN_LAST_ONLINE)
+    .union(ProtocolCapabilities::CLAN_SKILLS)
+    .union(ProtocolCapabilities::TRADE_REQUESTS)
+    .union(ProtocolCapabilities::CLAN_MARKS)
+    .union(ProtocolCapabilities::SKILL_REFUNDS);
+
+#[derive(Clone, Debug)]
+pub struct ServerCapabilities {
+    pub protocol_version: u16,
+    pub capabilities: ProtocolCapabilities,
+}
+
+// Sent by the login server when a PIN or one time password is required after
+// the account password was accepted
+#[derive(Clone, Debug)]
+pub struct LoginChallenge {
+    pub kind: LoginChallengeKind,
+    pub code_length: usize,
+    pub previous_attempt_failed: bool,
+}
+
+// A client settings blob stored server side so it follows the account across
+// machines, timestamp is in seconds since the unix epoch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountSettingsBlob {
+    pub key: String,
+    pub timestamp: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub enum AccountSettingsMessage {
+    RequestAll,
+    Save(AccountSettingsBlob),
+}
+
+// Sent by the server to start a minigame session the client requested, the
+// seed lets the server replay the inputs of the player against the same game
+#[derive(Clone, Debug)]
+pub struct MinigameStart {
+    pub session_id: u32,
+    pub minigame_id: u8,
+    pub seed: u32,
+}
+
+// The action key was pressed or released, time is since the session started
+#[derive(Clone, Debug)]
+pub struct MinigameInputEvent {
+    pub time: Duration,
+    pub held: bool,
+}
+
+// Sent when a minigame finishes, the server decides the outcome from the inputs
+// and sends any catch with the usual inventory update
+#[derive(Clone, Debug)]
+pub struct MinigameInputs {
+    pub session_id: u32,
+    pub inputs: Vec<MinigameInputEvent>,
+}
+
+// A visible summon and its owner, lifetimes are None for summons which do not expire
+#[derive(Clone, Debug)]
+pub struct SummonInfo {
+    pub entity_id: ClientEntityId,
+    pub owner_entity_id: ClientEntityId,
+    pub remaining_lifetime: Option<Duration>,
+    pub total_lifetime: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+pub enum SummonCommandKind {
+    Attack(ClientEntityId),
+    Passive,
+    Dismiss,
+}
+
+#[derive(Clone, Debug)]
+pub struct SummonCommand {
+    pub summon_entity_id: ClientEntityId,
+    pub kind: SummonCommandKind,
+}
+
+// The currently selected target of a party member, sent by the server so party
+// members can assist each other
+#[derive(Clone, Debug)]
+pub struct PartyMemberTarget {
+    pub entity_id: ClientEntityId,
+    pub target_entity_id: Option<ClientEntityId>,
+}
+
+// Sent by the server to a dead player when another character casts a
+// resurrection skill on them, the player is only revived if they accept
+#[derive(Clone, Debug)]
+pub struct ResurrectionOffer {
+    pub caster_entity_id: ClientEntityId,
+    pub skill_id: SkillId,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResurrectionResponse {
+    pub caster_entity_id: ClientEntityId,
+    pub accepted: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerReportCategory {
+    Harassment,
+    Spam,
+    Cheating,
+    Scamming,
+    OffensiveName,
+    Other,
+}
+
+// Sent when the player reports another player, recent chat lines from the
+// reported player are attached as evidence
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerReport {
+    pub name: String,
+    pub category: PlayerReportCategory,
+    pub description: String,
+    pub chat_lines: Vec<String>,
+}
+
+// Received from the clan channel, the server takes the sender from the connection
+// and only relays the message to members of their clan
+#[derive(Clone, Debug)]
+pub struct ClanChat {
+    pub name: String,
+    pub text: String,
+}
+
+// A notice on the clan notice board, the server sets the author and the time
+// it was posted in seconds since the unix epoch
+#[derive(Clone, Debug)]
+pub struct ClanNotice {
+    pub id: u32,
+    pub timestamp: i64,
+    pub author: String,
+    pub text: String,
+}
+
+// A war between the clan of the player and another clan, the server counts the
+// kills and deaths between the clans and ends the war when the time runs out
+#[derive(Clone, Debug)]
+pub struct ClanWarInfo {
+    pub opponent: String,
+    pub remaining_time: Duration,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+// When an offline clan member was last online in seconds since the unix epoch
+#[derive(Clone, Debug)]
+pub struct ClanMemberLastOnline {
+    pub name: String,
+    pub timestamp: i64,
+}
+
+// Sent by the clan master to change the mark of their clan, a custom mark is a
+// 20x20 image of RGB565 pixels which the server stores and shares with other clients
+#[derive(Clone, Debug)]
+pub enum ClanMarkChange {
+    Premade {
+        background: NonZeroU16,
+        foreground: NonZeroU16,
+    },
+    Custom {
+        width: u16,
+        height: u16,
+        pixels: Vec<u16>,
+    },
+}
+
+// Received from the zone wide trade channel, which is separate to shouts so the
+// server can validate and rate limit trade messages
+#[derive(Clone, Debug)]
+pub struct TradeChat {
+    pub name: String,
+    pub text: String,
+}
+
+// Sent by the server when the player is about to be disconnected for being
+// idle, the player stays connected by responding with a keep alive
+#[derive(Clone, Debug)]
+pub struct IdleWarning {
+    pub disconnect_in: Duration,
+}
+
+// Sent to use a skill reset item to refund the points spent on a single skill,
+// the server consumes the item and removes the skill
+#[derive(Clone, Debug)]
+pub struct SkillRefund {
+    pub item_slot: ItemSlot,
+    pub skill_id: SkillId,
+}
+
+// Sent to move part of a stack of items into another inventory slot, which
+// must be empty or contain the same item
+#[derive(Clone, Debug)]
+pub struct ItemStackSplit {
+    pub source: ItemSlot,
+    pub destination: ItemSlot,
+    pub quantity: usize,
+}
+
+// The md5 checksums of the zone, NPC and item data files the server uses for a
+// zone, so the client can detect when its data differs from the server
+#[derive(Clone, Debug)]
+pub struct ZoneDataChecksums {
+    pub zone_id: ZoneId,
+    pub files: Vec<ZoneDataFileChecksum>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ZoneDataFileChecksum {
+    pub path: String,
+    pub md5: [u8; 16],
+}
+
+// Messages for the optional features which are not part of ClientMessage, only
+// sent when the server supports the feature
+#[derive(Clone, Debug)]
+pub enum ExtendedClientMessage {
+    AccountSettings(AccountSettingsMessage),
+    MinigameRequest(u8),
+    MinigameInputs(MinigameInputs),
+    MinigameCancel(u32),
+    SummonCommand(SummonCommand),
+    PartyTarget(Option<ClientEntityId>),
+    ResurrectionResponse(ResurrectionResponse),
+    PlayerReport(PlayerReport),
+    IdleKeepAlive,
+    ItemStackSplit(ItemStackSplit),
+    SkillRefund(SkillRefund),
+    RequestZoneDataChecksums(ZoneId),
+    TradeChat(String),
+    TradeRequest(ClientEntityId),
+    ClanChat(String),
+    ClanNoticePost(String),
+    ClanNoticeDelete(u32),
+    ClanWarDeclare(String),
+    ClanWarSurrender(String),
+    ClanEntrust(String),
+    ClanSkillActivate(SkillId),
+    ClanMarkChange(ClanMarkChange),
+}
+
+// Messages for the optional features which are not part of ServerMessage
+#[derive(Clone, Debug)]
+pub enum ExtendedServerMessage {
+    AccountSettings(AccountSettingsBlob),
+    MinigameStart(MinigameStart),
+    SummonList(Vec<SummonInfo>),
+    PartyMemberTargets(Vec<PartyMemberTarget>),
+    ResurrectionOffer(ResurrectionOffer),
+    IdleWarning(IdleWarning),
+    ZoneDataChecksums(ZoneDataChecksums),
+    MonsterSpawned(ClientEntityId),
+    TradeChat(TradeChat),
+    ClanChat(ClanChat),
+    ClanNotices(Vec<ClanNotice>),
+    ClanWars(Vec<ClanWarInfo>),
+    ClanMemberLastOnline(Vec<ClanMemberLastOnline>),
+}
+
+// A server packet which was unknown or could not be handled, the payload is
+// kept so protocol differences between server forks can be investigated
+#[derive(Clone, Debug)]
+pub struct UnsupportedPacket {
+    pub client: &'static str,
+    pub command: u16,
+    pub reason: String,
+    pub data: Vec<u8>,
+}
+
+pub fn report_unsupported_packet(
+    unsupported_packet_tx: &crossbeam_channel::Sender<UnsupportedPacket>,
+    client: &'static str,
+    packet: &rose_network_common::Packet,
+    reason: impl ToString,
+) {
+    unsupported_packet_tx
+        .send(UnsupportedPacket {
+            client,
+            command: packet.command,
+            reason: reason.to_string(),
+            data: packet.data[..].to_vec(),
+        })
+        .ok();
+}
+
 #[async_trait]
 pub trait ProtocolClient {
     async fn run_connection(&mut self) -> Result<(), anyhow::Error>;
@@ -14,7 +382,7 @@ pub trait ProtocolClient {
 
 #[macro_export]
 macro_rules! implement_protocol_client {
-    ( $x:ident ) => {
+    ( $x:ident $(, $extra_rx:ident => $extra_handler:ident )* ) => {
         #[async_trait]
         impl ProtocolClient for $x {
             async fn run_connection(&mut self) -> Result<(), anyhow::Error> {
@@ -30,7 +398,19 @@ macro_rules! implement_protocol_client {
                                         Ok(_) => {},
                                         Err(error) => {
                                             log::warn!("Error {} handling packet [{:03X}] {:02x?}", error, packet.command, &packet.data[..]);
-                                            return Err(error);
+                                            $crate::protocol::report_unsupported_packet(
+                                                &self.unsupported_packet_tx,
+                                                stringify!($x),
+                                                &packet,
+                                                &error,
+                                            );
+
+                                            // Optional packets which fail to parse are skipped, as
+                                            // server forks may send a different format. The client
+                                            // can not continue without the other packets.
+                                            if !OPTIONAL_SERVER_PACKETS.contains(&packet.command) {
+                                                return Err(error);
+                                            }
                                         },
                                     }
                                 },
@@ -46,6 +426,15 @@ macro_rules! implement_protocol_client {
                                 return Err(ProtocolClientError::ClientInitiatedDisconnect.into());
                             }
                         }
+                        $(
+                            extra_message = self.$extra_rx.recv() => {
+                                if let Some(message) = extra_message {
+                                    self.$extra_handler(&mut connection, message).await?;
+                                } else {
+                                    return Err(ProtocolClientError::ClientInitiatedDisconnect.into());
+                                }
+                            }
+                        )*
                     };
                 }
 
@@ -45,16 +45,17 @@ impl Plugin for DamageDigitRenderPlugin {
             Shader::from_wgsl
         );
 
-        let render_app = app.sub_app_mut(RenderApp);
-        render_app
-            .add_systems(ExtractSchedule, extract_damage_digits)
-            .add_systems(Render, prepare_damage_digits.in_set(RenderSet::Prepare))
-            .add_systems(Render, queue_damage_digits.in_set(RenderSet::Queue))
-            .init_resource::<DamageDigitMeta>()
-            .init_resource::<ExtractedDamageDigits>()
-            .init_resource::<MaterialBindGroups>()
-            .init_resource::<SpecializedRenderPipelines<DamageDigitPipeline>>()
-            .add_render_command::<Transparent3d, DrawDamageDigit>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_systems(ExtractSchedule, extract_damage_digits)
+                .add_systems(Render, prepare_damage_digits.in_set(RenderSet::Prepare))
+                .add_systems(Render, queue_damage_digits.in_set(RenderSet::Queue))
+                .init_resource::<DamageDigitMeta>()
+                .init_resource::<ExtractedDamageDigits>()
+                .init_resource::<MaterialBindGroups>()
+                .init_resource::<SpecializedRenderPipelines<DamageDigitPipeline>>()
+                .add_render_command::<Transparent3d, DrawDamageDigit>();
+        }
     }
 
     fn finish(&self, app: &mut App) {
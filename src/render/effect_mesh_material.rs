@@ -248,6 +248,14 @@ impl Material for EffectMeshMaterial {
             return Ok(());
         }
 
+        // Effects are usually the source of point lights, so do not light them again
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.push(ShaderDefVal::Bool(
+                "ZONE_LIGHTING_DISABLE_POINT_LIGHTS".into(),
+                true,
+            ));
+        }
+
         if matches!(key.bind_group_data.blend_op, BlendOperation::Add) {
             // Do not apply color fog to additive blended mesh
             if let Some(fragment) = descriptor.fragment.as_mut() {
@@ -39,12 +39,13 @@ pub use particle_material::ParticleMaterial;
 pub use particle_render_data::{ParticleRenderBillboardType, ParticleRenderData};
 pub use sky_material::SkyMaterial;
 pub use terrain_material::{
-    TerrainMaterial, TERRAIN_MATERIAL_MAX_TEXTURES, TERRAIN_MESH_ATTRIBUTE_TILE_INFO,
+    TerrainMaterial, TerrainMaterialQuality, TERRAIN_MATERIAL_MAX_TEXTURES,
+    TERRAIN_MESH_ATTRIBUTE_TILE_INFO,
 };
 pub use trail_effect::TrailEffect;
 pub use water_material::WaterMaterial;
 pub use world_ui::WorldUiRect;
-pub use zone_lighting::ZoneLighting;
+pub use zone_lighting::{ZoneLighting, ZoneLightingPointLight, ZONE_LIGHTING_MAX_POINT_LIGHTS};
 
 use damage_digit_material::DamageDigitMaterialPlugin;
 use damage_digit_pipeline::DamageDigitRenderPlugin;
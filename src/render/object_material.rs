@@ -160,6 +160,7 @@ bitflags::bitflags! {
         const ALPHA_MODE_BLEND           = (1 << 2);
         const HAS_ALPHA_VALUE            = (1 << 3);
         const SPECULAR                   = (1 << 4);
+        const RIM_LIGHT                  = (1 << 5);
         const NONE                       = 0;
     }
 }
@@ -171,6 +172,7 @@ pub struct ObjectMaterialUniformData {
     pub alpha_value: f32,
     pub lightmap_uv_offset: Vec2,
     pub lightmap_uv_scale: f32,
+    pub rim_color: Vec3,
 }
 
 impl From<&ObjectMaterial> for ObjectMaterialUniformData {
@@ -204,12 +206,17 @@ impl From<&ObjectMaterial> for ObjectMaterialUniformData {
             }
         }
 
+        if material.rim_light.is_some() {
+            flags |= ObjectMaterialFlags::RIM_LIGHT;
+        }
+
         ObjectMaterialUniformData {
             flags: flags.bits(),
             alpha_cutoff,
             alpha_value,
             lightmap_uv_offset: material.lightmap_uv_offset,
             lightmap_uv_scale: material.lightmap_uv_scale,
+            rim_color: material.rim_light.unwrap_or(Vec3::ZERO),
         }
     }
 }
@@ -289,6 +296,9 @@ pub struct ObjectMaterial {
     pub skinned: bool,
     pub blend: ObjectMaterialBlend,
     pub glow: Option<ObjectMaterialGlow>,
+
+    // Colour added around the silhouette, used to highlight interactable objects
+    pub rim_light: Option<Vec3>,
 }
 
 #[derive(Clone)]
@@ -457,6 +467,7 @@ impl Default for ObjectMaterial {
             skinned: false,
             blend: ObjectMaterialBlend::Normal,
             glow: None,
+            rim_light: None,
             lightmap_texture: None,
             lightmap_uv_offset: Vec2::new(0.0, 0.0),
             lightmap_uv_scale: 1.0,
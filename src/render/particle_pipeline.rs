@@ -55,16 +55,17 @@ impl Plugin for ParticleRenderPlugin {
             compute_particles_aabb.in_set(VisibilitySystems::CalculateBounds),
         );
 
-        let render_app = app.sub_app_mut(RenderApp);
-        render_app
-            .add_systems(ExtractSchedule, extract_particles)
-            .add_systems(Render, prepare_particles.in_set(RenderSet::Prepare))
-            .add_systems(Render, queue_particles.in_set(RenderSet::Queue))
-            .init_resource::<ParticleMeta>()
-            .init_resource::<ExtractedParticles>()
-            .init_resource::<MaterialBindGroups>()
-            .init_resource::<SpecializedRenderPipelines<ParticlePipeline>>()
-            .add_render_command::<Transparent3d, DrawParticle>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_systems(ExtractSchedule, extract_particles)
+                .add_systems(Render, prepare_particles.in_set(RenderSet::Prepare))
+                .add_systems(Render, queue_particles.in_set(RenderSet::Queue))
+                .init_resource::<ParticleMeta>()
+                .init_resource::<ExtractedParticles>()
+                .init_resource::<MaterialBindGroups>()
+                .init_resource::<SpecializedRenderPipelines<ParticlePipeline>>()
+                .add_render_command::<Transparent3d, DrawParticle>();
+        }
     }
 
     fn finish(&self, app: &mut App) {
@@ -102,6 +102,7 @@ struct StaticMeshMaterialData {
     alpha_value: f32,
     lightmap_uv_offset: vec2<f32>,
     lightmap_uv_scale: f32,
+    rim_color: vec3<f32>,
 };
 
 const OBJECT_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE: u32              = 1u;
@@ -109,6 +110,7 @@ const OBJECT_MATERIAL_FLAGS_ALPHA_MODE_MASK: u32                = 2u;
 const OBJECT_MATERIAL_FLAGS_ALPHA_MODE_BLEND: u32               = 4u;
 const OBJECT_MATERIAL_FLAGS_HAS_ALPHA_VALUE: u32                = 8u;
 const OBJECT_MATERIAL_FLAGS_SPECULAR: u32                       = 16u;
+const OBJECT_MATERIAL_FLAGS_RIM_LIGHT: u32                      = 32u;
 
 struct FragmentInput {
     @builtin(position) frag_coord: vec4<f32>,
@@ -179,7 +181,16 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         }
     }
 
-    return apply_zone_lighting(in.world_position, in.world_normal, output_color, view_z);
+    output_color = apply_zone_lighting(in.world_position, in.world_normal, output_color, view_z);
+
+    if ((material.flags & OBJECT_MATERIAL_FLAGS_RIM_LIGHT) != 0u) {
+        let N = normalize(in.world_normal);
+        let V = normalize(view.world_position.xyz - in.world_position.xyz);
+        let rim = pow(1.0 - max(dot(N, V), 0.0), 2.0);
+        output_color = vec4<f32>(output_color.rgb + material.rim_color * rim, output_color.a);
+    }
+
+    return output_color;
 }
 
 #endif  // else ifdef DEPTH_PREPASS
@@ -37,6 +37,44 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 var tile_array_texture: binding_array<texture_2d<f32>>;
 @group(1) @binding(1)
 var tile_array_sampler: sampler;
+@group(1) @binding(2)
+var tile_repeat_sampler: sampler;
+@group(1) @binding(3)
+var detail_array_texture: binding_array<texture_2d<f32>>;
+@group(1) @binding(4)
+var<uniform> detail_mask: vec4<u32>;
+
+// Each tile covers 10m of terrain
+const TERRAIN_TILE_SIZE: f32 = 10.0;
+const TERRAIN_DETAIL_UV_SCALE: f32 = 8.0;
+const TERRAIN_DETAIL_STRENGTH: f32 = 0.35;
+// Triplanar mapping fades in as the normal goes from TERRAIN_TRIPLANAR_FLAT_Y to TERRAIN_TRIPLANAR_STEEP_Y
+const TERRAIN_TRIPLANAR_STEEP_Y: f32 = 0.55;
+const TERRAIN_TRIPLANAR_FLAT_Y: f32 = 0.75;
+
+#ifdef TERRAIN_DETAIL_MAPS
+// Overlay the tile's detail texture at a higher frequency than the tile, centered around mid-grey
+fn apply_detail_map(color: vec4<f32>, tile_id: u32, uv: vec2<f32>) -> vec4<f32> {
+    let detail = textureSample(detail_array_texture[tile_id], tile_repeat_sampler, uv * TERRAIN_DETAIL_UV_SCALE);
+    let detail_luminance = dot(detail.rgb, vec3<f32>(0.299, 0.587, 0.114));
+    let has_detail = ((detail_mask[tile_id / 32u] >> (tile_id % 32u)) & 1u) != 0u;
+    let strength = select(0.0, TERRAIN_DETAIL_STRENGTH, has_detail);
+    return vec4<f32>(color.rgb * mix(1.0, detail_luminance * 2.0, strength), color.a);
+}
+#endif
+
+#ifdef TERRAIN_TRIPLANAR
+fn sample_triplanar(tile_id: u32, world_position: vec3<f32>, world_normal: vec3<f32>) -> vec4<f32> {
+    var weights = abs(world_normal);
+    weights = weights / max(weights.x + weights.y + weights.z, 0.0001);
+
+    let uv_position = world_position / TERRAIN_TILE_SIZE;
+    let x_axis = textureSample(tile_array_texture[tile_id], tile_repeat_sampler, uv_position.zy);
+    let y_axis = textureSample(tile_array_texture[tile_id], tile_repeat_sampler, uv_position.xz);
+    let z_axis = textureSample(tile_array_texture[tile_id], tile_repeat_sampler, uv_position.xy);
+    return x_axis * weights.x + y_axis * weights.y + z_axis * weights.z;
+}
+#endif
 
 struct FragmentInput {
     @builtin(position) frag_coord: vec4<f32>,
@@ -77,13 +115,34 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         layer2_uv.y = x;
     }
 
-    let layer1 = textureSample(tile_array_texture[tile_layer1_id], tile_array_sampler, in.uv1);
-    let layer2 = textureSample(tile_array_texture[tile_layer2_id], tile_array_sampler, layer2_uv);
+    var layer1 = textureSample(tile_array_texture[tile_layer1_id], tile_array_sampler, in.uv1);
+    var layer2 = textureSample(tile_array_texture[tile_layer2_id], tile_array_sampler, layer2_uv);
+
+#ifdef TERRAIN_TRIPLANAR
+    // NOTE: All samples must be taken outside of non-uniform control flow, so always sample and mix
+    let world_normal = normalize(in.world_normal);
+    let triplanar_weight = 1.0 - smoothstep(TERRAIN_TRIPLANAR_STEEP_Y, TERRAIN_TRIPLANAR_FLAT_Y, world_normal.y);
+    let triplanar_layer1 = sample_triplanar(tile_layer1_id, in.world_position.xyz, world_normal);
+    let triplanar_layer2 = sample_triplanar(tile_layer2_id, in.world_position.xyz, world_normal);
+    layer1 = mix(layer1, triplanar_layer1, triplanar_weight);
+    layer2 = mix(layer2, triplanar_layer2, triplanar_weight);
+#endif
+
+#ifdef TERRAIN_DETAIL_MAPS
+    layer1 = apply_detail_map(layer1, tile_layer1_id, in.uv1);
+    layer2 = apply_detail_map(layer2, tile_layer2_id, layer2_uv);
+#endif
+
+#ifdef TERRAIN_SMOOTH_BLEND
+    let layer2_blend = smoothstep(0.0, 1.0, layer2.a);
+#else
+    let layer2_blend = layer2.a;
+#endif
     var lightmap = textureSample(tile_array_texture[0], tile_array_sampler, in.uv0);
     let shadow = fetch_directional_shadow(0u, in.world_position, in.world_normal, view_z);
     lightmap = vec4<f32>(lightmap.xyz * (shadow * 0.2 + 0.8), lightmap.w);
 
-    let terrain_color = mix(layer1, layer2, layer2.a) * lightmap * 2.0;
+    let terrain_color = mix(layer1, layer2, layer2_blend) * lightmap * 2.0;
 
     return apply_zone_lighting(in.world_position, in.world_normal, vec4<f32>(terrain_color.rgb, 1.0), view_z);
 }
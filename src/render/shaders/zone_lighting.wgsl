@@ -1,5 +1,12 @@
 #define_import_path rose_client::zone_lighting
 
+const ZONE_LIGHTING_MAX_POINT_LIGHTS: u32 = 8u;
+
+struct ZoneLightingPointLight {
+    position_radius: vec4<f32>,
+    color_intensity: vec4<f32>,
+};
+
 struct ZoneLighting {
     map_ambient_color: vec4<f32>,
     character_ambient_color: vec4<f32>,
@@ -11,6 +18,8 @@ struct ZoneLighting {
     fog_max_density: f32,
     fog_alpha_range_start: f32,
     fog_alpha_range_end: f32,
+    num_point_lights: u32,
+    point_lights: array<ZoneLightingPointLight, ZONE_LIGHTING_MAX_POINT_LIGHTS>,
 };
 
 #ifdef ZONE_LIGHTING_GROUP_2
@@ -39,13 +48,37 @@ fn apply_zone_lighting_fog(world_position: vec4<f32>, fragment_color: vec4<f32>,
     return fog_color;
 }
 
+fn apply_zone_lighting_point_lights(world_position: vec4<f32>, world_normal: vec3<f32>) -> vec3<f32> {
+    var light = vec3<f32>(0.0, 0.0, 0.0);
+    let normal = normalize(world_normal);
+
+    for (var i: u32 = 0u; i < min(zone_lighting.num_point_lights, ZONE_LIGHTING_MAX_POINT_LIGHTS); i = i + 1u) {
+        let point_light = zone_lighting.point_lights[i];
+        let to_light = point_light.position_radius.xyz - world_position.xyz;
+        let distance = length(to_light);
+        let radius = point_light.position_radius.w;
+        if (distance < radius) {
+            let attenuation = 1.0 - (distance / radius);
+            let n_dot_l = clamp(dot(normal, to_light / max(distance, 0.0001)), 0.0, 1.0);
+            light = light + point_light.color_intensity.rgb * point_light.color_intensity.w * attenuation * attenuation * n_dot_l;
+        }
+    }
+
+    return light;
+}
+
 fn apply_zone_lighting(world_position: vec4<f32>, world_normal: vec3<f32>, fragment_color: vec4<f32>, view_z: f32) -> vec4<f32> {
 #ifdef ZONE_LIGHTING_CHARACTER
-    let light = saturate(zone_lighting.character_ambient_color.rgb + zone_lighting.character_diffuse_color.rgb * clamp(dot(world_normal, zone_lighting.light_direction.xyz), 0.0, 1.0));
-    let lit_color = vec4<f32>(fragment_color.rgb * light.rgb, fragment_color.a);
+    var light = saturate(zone_lighting.character_ambient_color.rgb + zone_lighting.character_diffuse_color.rgb * clamp(dot(world_normal, zone_lighting.light_direction.xyz), 0.0, 1.0));
 #else
-    let lit_color = vec4<f32>(fragment_color.rgb * zone_lighting.map_ambient_color.rgb, fragment_color.a);
+    var light = zone_lighting.map_ambient_color.rgb;
 #endif
 
+#ifndef ZONE_LIGHTING_DISABLE_POINT_LIGHTS
+    light = light + apply_zone_lighting_point_lights(world_position, world_normal);
+#endif
+
+    let lit_color = vec4<f32>(fragment_color.rgb * light, fragment_color.a);
+
     return apply_zone_lighting_fog(world_position, lit_color, view_z);
 }
@@ -9,7 +9,7 @@ use bevy::{
     prelude::{
         AlphaMode, App, FromWorld, HandleUntyped, Material, MaterialPlugin, Mesh, Plugin, World,
     },
-    reflect::{TypePath, TypeUuid},
+    reflect::{Reflect, TypePath, TypeUuid},
     render::{
         mesh::{MeshVertexAttribute, MeshVertexBufferLayout},
         prelude::Shader,
@@ -18,10 +18,11 @@ use bevy::{
         render_resource::{
             AddressMode, AsBindGroup, AsBindGroupError, BindGroupDescriptor, BindGroupEntry,
             BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
-            BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, FilterMode,
+            BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState,
+            BufferBindingType, BufferInitDescriptor, BufferSize, BufferUsages, FilterMode,
             PreparedBindGroup, RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor,
-            ShaderStages, SpecializedMeshPipelineError, TextureSampleType, TextureViewDimension,
-            VertexFormat,
+            ShaderDefVal, ShaderStages, SpecializedMeshPipelineError, TextureSampleType,
+            TextureViewDimension, VertexFormat,
         },
         renderer::RenderDevice,
         texture::{FallbackImage, Image},
@@ -82,10 +83,39 @@ impl FromWorld for TerrainMaterialPipelineData {
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum TerrainMaterialQuality {
+    // Matches the original client: two blended tile layers with a lightmap
+    #[default]
+    Standard,
+
+    // Adds smoothed tile blending, triplanar mapping on steep slopes and per tile detail textures
+    High,
+}
+
 #[derive(Debug, Clone, TypeUuid, TypePath)]
 #[uuid = "403e3628-46d2-4d2a-b74c-ce84be2b1ba2"]
 pub struct TerrainMaterial {
     pub textures: Vec<Handle<Image>>,
+    pub quality: TerrainMaterialQuality,
+
+    // Detail texture for each entry of textures, tiles without one are drawn without detail
+    pub detail_textures: Vec<Option<Handle<Image>>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TerrainMaterialKey {
+    quality: TerrainMaterialQuality,
+    detail_map: bool,
+}
+
+impl From<&TerrainMaterial> for TerrainMaterialKey {
+    fn from(material: &TerrainMaterial) -> Self {
+        TerrainMaterialKey {
+            quality: material.quality,
+            detail_map: material.detail_textures.iter().any(Option::is_some),
+        }
+    }
 }
 
 impl Material for TerrainMaterial {
@@ -132,6 +162,22 @@ impl Material for TerrainMaterial {
             }
         }
 
+        if matches!(key.bind_group_data.quality, TerrainMaterialQuality::High) {
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                if key.bind_group_data.detail_map {
+                    fragment
+                        .shader_defs
+                        .push(ShaderDefVal::Bool("TERRAIN_DETAIL_MAPS".into(), true));
+                }
+                fragment
+                    .shader_defs
+                    .push(ShaderDefVal::Bool("TERRAIN_SMOOTH_BLEND".into(), true));
+                fragment
+                    .shader_defs
+                    .push(ShaderDefVal::Bool("TERRAIN_TRIPLANAR".into(), true));
+            }
+        }
+
         descriptor
             .layout
             .insert(3, pipeline.data.zone_lighting_layout.clone());
@@ -150,7 +196,7 @@ impl Material for TerrainMaterial {
 }
 
 impl AsBindGroup for TerrainMaterial {
-    type Data = ();
+    type Data = TerrainMaterialKey;
 
     fn as_bind_group(
         &self,
@@ -172,6 +218,33 @@ impl AsBindGroup for TerrainMaterial {
             textures[id] = &*image.texture_view;
         }
 
+        // One bit per tile id which has a detail texture, as the fallback image is not neutral
+        let mut detail_textures =
+            vec![&*fallback_image.d2.texture_view; TERRAIN_MATERIAL_MAX_TEXTURES];
+        let mut detail_mask = [0u32; 4];
+        for (id, handle) in self
+            .detail_textures
+            .iter()
+            .enumerate()
+            .take(TERRAIN_MATERIAL_MAX_TEXTURES)
+        {
+            let Some(handle) = handle.as_ref() else {
+                continue;
+            };
+
+            match image_assets.get(handle) {
+                Some(image) => detail_textures[id] = &*image.texture_view,
+                None => return Err(AsBindGroupError::RetryNextUpdate),
+            }
+            detail_mask[id / 32] |= 1 << (id % 32);
+        }
+
+        let detail_mask_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("terrain_material_detail_mask"),
+            contents: bytemuck::cast_slice(&detail_mask),
+            usage: BufferUsages::UNIFORM,
+        });
+
         let sampler = render_device.create_sampler(&SamplerDescriptor {
             address_mode_u: AddressMode::ClampToEdge,
             address_mode_v: AddressMode::ClampToEdge,
@@ -180,6 +253,16 @@ impl AsBindGroup for TerrainMaterial {
             ..Default::default()
         });
 
+        // The detail texture and triplanar mapping sample outside of the 0..1 tile uv range
+        let repeat_sampler = render_device.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
         let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
             label: "terrain_material_bind_group".into(),
             layout,
@@ -192,13 +275,25 @@ impl AsBindGroup for TerrainMaterial {
                     binding: 1,
                     resource: BindingResource::Sampler(&sampler),
                 },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&repeat_sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureViewArray(&detail_textures[..]),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: detail_mask_buffer.as_entire_binding(),
+                },
             ],
         });
 
         Ok(PreparedBindGroup {
             bindings: vec![],
             bind_group,
-            data: (),
+            data: self.into(),
         })
     }
 
@@ -225,6 +320,32 @@ impl AsBindGroup for TerrainMaterial {
                     ty: BindingType::Sampler(SamplerBindingType::Filtering),
                     count: None,
                 },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: NonZeroU32::new(TERRAIN_MATERIAL_MAX_TEXTURES as u32),
+                },
+                BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: BufferSize::new(16),
+                    },
+                    count: None,
+                },
             ],
         })
     }
@@ -52,16 +52,17 @@ impl Plugin for TrailEffectRenderPlugin {
         app.add_systems(Update, (initialise_trail_effects,));
         app.add_systems(PostUpdate, (update_trail_effects,));
 
-        let render_app = app.sub_app_mut(RenderApp);
-        render_app
-            .add_systems(ExtractSchedule, extract_trail_effects)
-            .add_systems(Render, prepare_trail_effects.in_set(RenderSet::Prepare))
-            .add_systems(Render, queue_trail_effects.in_set(RenderSet::Queue))
-            .init_resource::<TrailEffectMeta>()
-            .init_resource::<ExtractedTrailEffects>()
-            .init_resource::<MaterialBindGroups>()
-            .init_resource::<SpecializedRenderPipelines<TrailEffectPipeline>>()
-            .add_render_command::<Transparent3d, DrawTrailEffect>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_systems(ExtractSchedule, extract_trail_effects)
+                .add_systems(Render, prepare_trail_effects.in_set(RenderSet::Prepare))
+                .add_systems(Render, queue_trail_effects.in_set(RenderSet::Queue))
+                .init_resource::<TrailEffectMeta>()
+                .init_resource::<ExtractedTrailEffects>()
+                .init_resource::<MaterialBindGroups>()
+                .init_resource::<SpecializedRenderPipelines<TrailEffectPipeline>>()
+                .add_render_command::<Transparent3d, DrawTrailEffect>();
+        }
     }
 
     fn finish(&self, app: &mut App) {
@@ -28,6 +28,9 @@ use bevy::{
 pub const ZONE_LIGHTING_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 0x444949d32b35d5d9);
 
+// NOTE: This must match ZONE_LIGHTING_MAX_POINT_LIGHTS in shaders/zone_lighting.wgsl!
+pub const ZONE_LIGHTING_MAX_POINT_LIGHTS: usize = 8;
+
 fn default_light_transform() -> Transform {
     Transform::from_rotation(Quat::from_euler(
         EulerRot::ZYX,
@@ -92,6 +95,14 @@ fn spawn_lights(mut commands: Commands) {
     });
 }
 
+#[derive(Clone, Default, Reflect)]
+pub struct ZoneLightingPointLight {
+    pub position: Vec3,
+    pub radius: f32,
+    pub color: Vec3,
+    pub intensity: f32,
+}
+
 #[derive(Resource, Reflect)]
 #[reflect(Resource)]
 pub struct ZoneLighting {
@@ -109,6 +120,9 @@ pub struct ZoneLighting {
     pub alpha_fog_enabled: bool,
     pub fog_alpha_weight_start: f32,
     pub fog_alpha_weight_end: f32,
+
+    // Updated every frame by dynamic_point_light_system, at most ZONE_LIGHTING_MAX_POINT_LIGHTS
+    pub point_lights: Vec<ZoneLightingPointLight>,
 }
 
 impl Default for ZoneLighting {
@@ -126,10 +140,17 @@ impl Default for ZoneLighting {
             alpha_fog_enabled: true,
             fog_alpha_weight_start: 0.85,
             fog_alpha_weight_end: 0.98,
+            point_lights: Vec::new(),
         }
     }
 }
 
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct ZoneLightingPointLightUniformData {
+    pub position_radius: Vec4,
+    pub color_intensity: Vec4,
+}
+
 #[derive(Clone, ShaderType, Resource)]
 pub struct ZoneLightingUniformData {
     pub map_ambient_color: Vec4,
@@ -146,6 +167,9 @@ pub struct ZoneLightingUniformData {
     // far = sqrt(log2(1.0 - fog_alpha_weight_end) / (-fog_density * fog_density * 1.442695))
     pub fog_alpha_weight_start: f32,
     pub fog_alpha_weight_end: f32,
+
+    pub num_point_lights: u32,
+    pub point_lights: [ZoneLightingPointLightUniformData; ZONE_LIGHTING_MAX_POINT_LIGHTS],
 }
 
 #[derive(Resource)]
@@ -199,6 +223,20 @@ impl FromWorld for ZoneLightingUniformMeta {
 }
 
 fn extract_uniform_data(mut commands: Commands, zone_lighting: Extract<Res<ZoneLighting>>) {
+    let mut point_lights =
+        [ZoneLightingPointLightUniformData::default(); ZONE_LIGHTING_MAX_POINT_LIGHTS];
+    let num_point_lights = zone_lighting
+        .point_lights
+        .len()
+        .min(ZONE_LIGHTING_MAX_POINT_LIGHTS);
+    for (uniform_data, point_light) in point_lights
+        .iter_mut()
+        .zip(zone_lighting.point_lights.iter())
+    {
+        uniform_data.position_radius = point_light.position.extend(point_light.radius);
+        uniform_data.color_intensity = point_light.color.extend(point_light.intensity);
+    }
+
     commands.insert_resource(ZoneLightingUniformData {
         map_ambient_color: zone_lighting.map_ambient_color.extend(1.0),
         character_ambient_color: zone_lighting.character_ambient_color.extend(1.0),
@@ -230,6 +268,8 @@ fn extract_uniform_data(mut commands: Commands, zone_lighting: Extract<Res<ZoneL
         } else {
             99999999999.0
         },
+        num_point_lights: num_point_lights as u32,
+        point_lights,
     });
 }
 
@@ -1,7 +1,9 @@
 use bevy::prelude::Resource;
 
+use rose_game_common::data::Password;
+
 #[derive(Resource)]
 pub struct Account {
     pub username: String,
-    pub password: String,
+    pub password: Password,
 }
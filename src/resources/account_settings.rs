@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use bevy::prelude::Resource;
+
+use crate::protocol::AccountSettingsBlob;
+
+fn read_account_settings_file(path: &Path) -> Option<Vec<AccountSettingsBlob>> {
+    let file = std::fs::read(path).ok()?;
+    let mut data = &file[..];
+    let mut blobs = Vec::new();
+
+    while !data.is_empty() {
+        let key_length = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?) as usize;
+        let key = String::from_utf8(data.get(2..2 + key_length)?.to_vec()).ok()?;
+        data = &data[2 + key_length..];
+
+        let timestamp = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?);
+        let data_length = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?) as usize;
+        let blob_data = data.get(12..12 + data_length)?.to_vec();
+        data = &data[12 + data_length..];
+
+        blobs.push(AccountSettingsBlob {
+            key,
+            timestamp,
+            data: blob_data,
+        });
+    }
+
+    Some(blobs)
+}
+
+fn write_account_settings_file<'a>(
+    path: &Path,
+    blobs: impl Iterator<Item = &'a AccountSettingsBlob>,
+) -> Result<(), std::io::Error> {
+    let mut file = Vec::new();
+    for blob in blobs {
+        file.extend_from_slice(&(blob.key.len() as u16).to_le_bytes());
+        file.extend_from_slice(blob.key.as_bytes());
+        file.extend_from_slice(&blob.timestamp.to_le_bytes());
+        file.extend_from_slice(&(blob.data.len() as u32).to_le_bytes());
+        file.extend_from_slice(&blob.data);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, file)
+}
+
+// Client settings which are synced with the server for the logged in account,
+// a local copy is kept so the newest version wins when settings were changed
+// on another machine or while offline.
+#[derive(Resource)]
+pub struct AccountSettings {
+    pub sync_enabled: bool,
+    pub cache_path: PathBuf,
+    pub username: Option<String>,
+    blobs: HashMap<String, AccountSettingsBlob>,
+}
+
+impl AccountSettings {
+    pub fn new(sync_enabled: bool, cache_path: PathBuf) -> Self {
+        Self {
+            sync_enabled,
+            cache_path,
+            username: None,
+            blobs: HashMap::new(),
+        }
+    }
+
+    // The username is chosen by the user, so it is hashed rather than used directly as a file name
+    fn account_path(&self) -> Option<PathBuf> {
+        self.username.as_ref().map(|username| {
+            self.cache_path
+                .join(format!("{:x}.settings", md5::compute(username.as_bytes())))
+        })
+    }
+
+    pub fn load_account(&mut self, username: &str) {
+        if self.username.as_deref() == Some(username) {
+            return;
+        }
+
+        self.username = Some(username.to_string());
+        self.blobs = self
+            .account_path()
+            .and_then(|path| read_account_settings_file(&path))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|blob| (blob.key.clone(), blob))
+            .collect();
+    }
+
+    pub fn get(&self, key: &str) -> Option<&AccountSettingsBlob> {
+        self.blobs.get(key)
+    }
+
+    // Stores a blob received from the server, returns true if it was newer
+    // than our local copy and should be applied
+    pub fn merge_remote(&mut self, blob: AccountSettingsBlob) -> bool {
+        if let Some(local) = self.blobs.get(&blob.key) {
+            if local.timestamp >= blob.timestamp || local.data == blob.data {
+                return false;
+            }
+        }
+
+        self.blobs.insert(blob.key.clone(), blob);
+        self.save();
+        true
+    }
+
+    // Stores locally changed settings, returns the blob to be sent to the server
+    pub fn update_local(&mut self, key: &str, data: Vec<u8>) -> Option<AccountSettingsBlob> {
+        if self
+            .blobs
+            .get(key)
+            .map_or(false, |local| local.data == data)
+        {
+            return None;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let blob = AccountSettingsBlob {
+            key: key.to_string(),
+            timestamp,
+            data,
+        };
+        self.blobs.insert(key.to_string(), blob.clone());
+        self.save();
+        Some(blob)
+    }
+
+    fn save(&self) {
+        let Some(path) = self.account_path() else {
+            return;
+        };
+
+        if let Err(error) = write_account_settings_file(&path, self.blobs.values()) {
+            log::warn!(
+                "Failed to write account settings {} with error {}",
+                path.to_string_lossy(),
+                error
+            );
+        }
+    }
+}
@@ -4,8 +4,10 @@ use bevy::prelude::States;
 pub enum AppState {
     #[default]
     GameLogin,
+    DataProfileSelect,
     GameCharacterSelect,
     Game,
     ModelViewer,
     ZoneViewer,
+    ReplayViewer,
 }
@@ -0,0 +1,17 @@
+use bevy::prelude::Resource;
+
+#[derive(Default)]
+pub enum AutoLoginState {
+    #[default]
+    Login,
+    WaitServerList,
+    WaitCharacterList,
+    WaitGame,
+    Finished,
+}
+
+// Drives the login, server select and character select flow from ServerConfiguration presets
+#[derive(Default, Resource)]
+pub struct AutoLogin {
+    pub state: AutoLoginState,
+}
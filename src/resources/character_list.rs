@@ -6,3 +6,12 @@ use rose_game_common::messages::server::CharacterListItem;
 pub struct CharacterList {
     pub characters: Vec<CharacterListItem>,
 }
+
+// Number of usable character slots when the server sends them, otherwise
+// every character select position can be used
+#[derive(Resource)]
+pub struct CharacterSlots {
+    pub slot_count: usize,
+    pub max_slot_count: usize,
+    pub unlock_price: Option<u32>,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::Resource;
+
+// Character to play as soon as character select is reached, set when
+// switching character from in game
+#[derive(Default, Resource)]
+pub struct CharacterSwitch {
+    pub character_name: Option<String>,
+}
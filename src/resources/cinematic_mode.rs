@@ -0,0 +1,45 @@
+use bevy::{
+    prelude::{Entity, Res, Resource, Visibility},
+    winit::UpdateMode,
+};
+
+#[derive(Resource)]
+pub struct CinematicMode {
+    pub enabled: bool,
+
+    // Degrees per second the camera orbits around the player
+    pub orbit_speed: f32,
+    pub orbit_radius: f32,
+
+    // Seconds without input before the camera starts drifting, 0 disables
+    pub idle_delay: f32,
+    pub idle_time: f32,
+
+    // Set when cinematic mode was entered automatically after being idle
+    pub idle: bool,
+
+    // State to restore when leaving cinematic mode
+    pub saved_follow_distance: Option<f32>,
+    pub saved_name_tag_visibility: Vec<(Entity, Visibility)>,
+    pub saved_update_mode: Option<UpdateMode>,
+}
+
+impl Default for CinematicMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            orbit_speed: 10.0,
+            orbit_radius: 8.0,
+            idle_delay: 0.0,
+            idle_time: 0.0,
+            idle: false,
+            saved_follow_distance: None,
+            saved_name_tag_visibility: Vec::new(),
+            saved_update_mode: None,
+        }
+    }
+}
+
+pub fn cinematic_mode_disabled(cinematic_mode: Res<CinematicMode>) -> bool {
+    !cinematic_mode.enabled
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::Resource;
+
+use crate::protocol::ProtocolCapabilities;
+
+// Optional features supported by the server we are connected to, reset to an
+// older server without any optional features when connecting to a login server
+#[derive(Resource)]
+pub struct ClientCapabilities {
+    pub server_protocol_version: Option<u16>,
+    pub capabilities: ProtocolCapabilities,
+}
+
+impl Default for ClientCapabilities {
+    fn default() -> Self {
+        Self {
+            server_protocol_version: None,
+            capabilities: ProtocolCapabilities::empty(),
+        }
+    }
+}
+
+impl ClientCapabilities {
+    pub fn supports(&self, capabilities: ProtocolCapabilities) -> bool {
+        self.capabilities.contains(capabilities)
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Entity, Resource};
+
+use rose_game_common::messages::ClientEntityId;
+
+use crate::components::ClientEntityType;
+
+pub struct ClientEntityRegistryEntry {
+    pub id: ClientEntityId,
+    pub entity_type: ClientEntityType,
+    pub name: String,
+}
+
+// Looks up visible client entities by character name, id or entity without
+// scanning queries, kept up to date by client_entity_registry_system
+#[derive(Resource, Default)]
+pub struct ClientEntityRegistry {
+    entries: HashMap<Entity, ClientEntityRegistryEntry>,
+    characters_by_name: HashMap<String, Entity>,
+    entities_by_id: HashMap<usize, Entity>,
+}
+
+impl ClientEntityRegistry {
+    pub fn insert(&mut self, entity: Entity, entry: ClientEntityRegistryEntry) {
+        self.remove(entity);
+
+        if entry.entity_type == ClientEntityType::Character {
+            self.characters_by_name
+                .insert(entry.name.to_lowercase(), entity);
+        }
+        self.entities_by_id.insert(entry.id.0, entity);
+        self.entries.insert(entity, entry);
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(entry) = self.entries.remove(&entity) {
+            let name = entry.name.to_lowercase();
+            if self.characters_by_name.get(&name) == Some(&entity) {
+                self.characters_by_name.remove(&name);
+            }
+
+            if self.entities_by_id.get(&entry.id.0) == Some(&entity) {
+                self.entities_by_id.remove(&entry.id.0);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.characters_by_name.clear();
+        self.entities_by_id.clear();
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&ClientEntityRegistryEntry> {
+        self.entries.get(&entity)
+    }
+
+    pub fn get_name(&self, entity: Entity) -> Option<&str> {
+        self.entries.get(&entity).map(|entry| entry.name.as_str())
+    }
+
+    pub fn find_id(&self, id: ClientEntityId) -> Option<Entity> {
+        self.entities_by_id.get(&id.0).copied()
+    }
+
+    // Character names are not case sensitive
+    pub fn find_character(&self, name: &str) -> Option<Entity> {
+        self.characters_by_name.get(&name.to_lowercase()).copied()
+    }
+}
@@ -0,0 +1,329 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy::prelude::{KeyCode, Resource};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsSettings {
+    pub texture_budget_mb: Option<usize>,
+    pub texture_high_detail_distance: f32,
+    pub show_texture_overlay: bool,
+    pub crowd_mode: bool,
+    pub crowd_max_detailed_players: usize,
+    pub crowd_detailed_distance: f32,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            texture_budget_mb: None,
+            texture_high_detail_distance: 100.0,
+            show_texture_overlay: false,
+            crowd_mode: false,
+            crowd_max_detailed_players: 20,
+            crowd_detailed_distance: 30.0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub enabled: bool,
+    pub global: f32,
+    pub background_music: f32,
+    pub player_footstep: f32,
+    pub other_footstep: f32,
+    pub player_combat: f32,
+    pub other_combat: f32,
+    pub npc_sounds: f32,
+    pub ui_sounds: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            global: 0.6,
+            background_music: 0.15,
+            player_footstep: 0.9,
+            other_footstep: 0.5,
+            player_combat: 1.0,
+            other_combat: 0.5,
+            npc_sounds: 0.6,
+            ui_sounds: 0.5,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputDisplayPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterfaceSettings {
+    pub show_character_names: bool,
+    pub show_npc_names: bool,
+    pub show_summon_names: bool,
+    pub name_tag_font_size: f32,
+    pub show_chat_bubbles: bool,
+    pub show_zone_titles: bool,
+
+    // Seconds the pointer must rest on a slot before its tooltip is shown
+    pub tooltip_delay: f32,
+
+    // Shows recently pressed keys and used skills, for streaming and tutorials
+    pub show_input_display: bool,
+    pub input_display_position: InputDisplayPosition,
+    pub input_display_fade_time: f32,
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            show_character_names: true,
+            show_npc_names: true,
+            show_summon_names: true,
+            name_tag_font_size: 16.0,
+            show_chat_bubbles: true,
+            show_zone_titles: true,
+            tooltip_delay: 0.3,
+            show_input_display: false,
+            input_display_position: InputDisplayPosition::BottomLeft,
+            input_display_fade_time: 2.0,
+        }
+    }
+}
+
+// Key used to talk to NPCs, harvest and enter warp gates
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InteractKey {
+    E,
+    F,
+    G,
+    T,
+    Space,
+}
+
+impl InteractKey {
+    pub fn key_code(self) -> KeyCode {
+        match self {
+            InteractKey::E => KeyCode::E,
+            InteractKey::F => KeyCode::F,
+            InteractKey::G => KeyCode::G,
+            InteractKey::T => KeyCode::T,
+            InteractKey::Space => KeyCode::Space,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InteractKey::E => "E",
+            InteractKey::F => "F",
+            InteractKey::G => "G",
+            InteractKey::T => "T",
+            InteractKey::Space => "Space",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlsSettings {
+    // Degrees per second the cinematic camera orbits around the player
+    pub cinematic_orbit_speed: f32,
+    pub cinematic_orbit_radius: f32,
+
+    // Minutes without input before the idle camera starts, 0 disables
+    pub idle_camera_minutes: u32,
+
+    // Vehicle frame item number equipped by the mount key, set from the vehicle garage
+    pub mount_vehicle_item: Option<usize>,
+    pub mount_cast_time: f32,
+
+    pub interact_key: InteractKey,
+
+    // Last selected hotbar page by character name, the slots themselves are stored by the server
+    pub hotbar_pages: BTreeMap<String, usize>,
+}
+
+impl Default for ControlsSettings {
+    fn default() -> Self {
+        Self {
+            cinematic_orbit_speed: 10.0,
+            cinematic_orbit_radius: 8.0,
+            idle_camera_minutes: 0,
+            mount_vehicle_item: None,
+            mount_cast_time: 1.5,
+            interact_key: InteractKey::F,
+            hotbar_pages: BTreeMap::new(),
+        }
+    }
+}
+
+// Chat text sent when typing /name, see expand_chat_macro for the placeholders
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatMacro {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SocialSettings {
+    pub hide_other_players: bool,
+    pub show_trade_in_all_tab: bool,
+    pub show_clan_in_all_tab: bool,
+    pub chat_macros: Vec<ChatMacro>,
+}
+
+impl Default for SocialSettings {
+    fn default() -> Self {
+        Self {
+            hide_other_players: false,
+            show_trade_in_all_tab: false,
+            show_clan_in_all_tab: true,
+            chat_macros: Vec::new(),
+        }
+    }
+}
+
+// Passwords are never stored in the profile, see LoginSettings::remembered_username
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginProfile {
+    pub name: String,
+    pub ip: String,
+    pub port: String,
+    pub username: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginSettings {
+    pub profiles: Vec<LoginProfile>,
+    pub last_profile: Option<String>,
+
+    // Set when "remember me" was ticked, the password is only ever kept in the OS keychain
+    pub remembered_username: Option<String>,
+    pub use_os_keychain: bool,
+
+    // Return to character select instead of only warning when the server reports
+    // different zone data checksums than our local files
+    pub refuse_mismatched_zone_data: bool,
+}
+
+impl Default for LoginSettings {
+    fn default() -> Self {
+        Self {
+            profiles: Vec::new(),
+            last_profile: None,
+            remembered_username: None,
+            use_os_keychain: true,
+            refuse_mismatched_zone_data: false,
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CombatSettings {
+    pub show_monster_names: bool,
+    pub show_party_health: bool,
+    pub show_clan_health: bool,
+    pub simple_level_colors: bool,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    // Writes damage taken, level ups, whispers and tooltips to a text log
+    // which can be followed by a screen reader
+    pub screen_reader_log: bool,
+    pub high_visibility_cursor: bool,
+    pub high_visibility_target: bool,
+
+    // Shows chat and NPC dialogue in a strip at the bottom of the screen
+    pub subtitles: bool,
+    pub subtitle_font_size: f32,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            screen_reader_log: false,
+            high_visibility_cursor: false,
+            high_visibility_target: false,
+            subtitles: false,
+            subtitle_font_size: 24.0,
+        }
+    }
+}
+
+// All of the user configurable client settings, saved to a file and applied
+// to the resources used by the individual features by client_settings_system
+#[derive(Resource, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientSettings {
+    pub graphics: GraphicsSettings,
+    pub audio: AudioSettings,
+    pub interface: InterfaceSettings,
+    pub controls: ControlsSettings,
+    pub social: SocialSettings,
+    pub combat: CombatSettings,
+    pub accessibility: AccessibilitySettings,
+    pub login: LoginSettings,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+
+    // True when the settings were read from file rather than taken from config.toml
+    #[serde(skip)]
+    pub loaded: bool,
+}
+
+impl ClientSettings {
+    pub fn load(path: PathBuf) -> Self {
+        let loaded = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| toml::from_str::<ClientSettings>(&data).ok());
+
+        if let Some(settings) = loaded {
+            Self {
+                path,
+                loaded: true,
+                ..settings
+            }
+        } else {
+            Self {
+                path,
+                ..Default::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let result = toml::to_string(self)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+            .and_then(|data| {
+                if let Some(parent) = self.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&self.path, data)
+            });
+        if let Err(error) = result {
+            log::warn!(
+                "Failed to write client settings {} with error {}",
+                self.path.to_string_lossy(),
+                error
+            );
+        }
+    }
+}
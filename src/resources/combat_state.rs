@@ -0,0 +1,20 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::Resource;
+
+// How long the player is considered to be in combat after being attacked
+const COMBAT_DURATION: Duration = Duration::from_secs(10);
+
+// Tracks when the player was last attacked, e.g. to delay logging out during combat
+#[derive(Default, Resource)]
+pub struct CombatState {
+    pub last_attacked: Option<Instant>,
+}
+
+impl CombatState {
+    pub fn in_combat(&self) -> bool {
+        self.last_attacked.map_or(false, |last_attacked| {
+            last_attacked.elapsed() < COMBAT_DURATION
+        })
+    }
+}
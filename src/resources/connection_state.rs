@@ -0,0 +1,63 @@
+use bevy::prelude::Resource;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionServer {
+    Login,
+    World,
+    Game,
+}
+
+impl ConnectionServer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConnectionServer::Login => "login server",
+            ConnectionServer::World => "world server",
+            ConnectionServer::Game => "game server",
+        }
+    }
+}
+
+// Tracks the progress from connecting to the login server until being in game,
+// so failures can be shown to the user instead of only being logged
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    #[default]
+    Disconnected,
+    Connecting(ConnectionServer),
+    Authenticating,
+    SelectingServer,
+    SelectingCharacter,
+    LoadingZone,
+    InGame,
+    Error {
+        server: ConnectionServer,
+        reason: String,
+    },
+}
+
+impl ConnectionState {
+    pub fn error(server: ConnectionServer, reason: impl ToString) -> Self {
+        ConnectionState::Error {
+            server,
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, ConnectionState::Error { .. })
+    }
+
+    // The server whose connection failing should be reported, earlier servers
+    // are expected to disconnect once we have moved on from them
+    pub fn active_server(&self) -> Option<ConnectionServer> {
+        match self {
+            ConnectionState::Connecting(server) => Some(*server),
+            ConnectionState::Authenticating | ConnectionState::SelectingServer => {
+                Some(ConnectionServer::Login)
+            }
+            ConnectionState::SelectingCharacter => Some(ConnectionServer::World),
+            ConnectionState::LoadingZone | ConnectionState::InGame => Some(ConnectionServer::Game),
+            ConnectionState::Disconnected | ConnectionState::Error { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::Resource;
+
+// Crowded area mode hides skill effects, vehicles and costume accessories of
+// other players beyond a count or distance, party and clan members are always
+// shown in full detail
+#[derive(Resource)]
+pub struct CrowdSettings {
+    pub enabled: bool,
+    pub max_detailed_players: usize,
+
+    // Distance in metres
+    pub detailed_distance: f32,
+
+    // Hide all other players except for party members and personal stores
+    pub hide_other_players: bool,
+}
+
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_detailed_players: 20,
+            detailed_distance: 30.0,
+            hide_other_players: false,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{path::Path, sync::Arc};
+
+use bevy::prelude::{Res, Resource};
+
+use rose_file_readers::VirtualFilesystem;
+
+use crate::{
+    data_integrity::{check_data_integrity, DataIntegrityReport},
+    resources::{AppState, GameData},
+    vfs_asset_io::VfsAssetIoMount,
+    DataProfileConfig, FilesystemConfig,
+};
+
+pub struct DataProfileSelectEntry {
+    pub profile: DataProfileConfig,
+
+    // Whether each filesystem device of the profile exists
+    pub devices_exist: Vec<bool>,
+}
+
+impl DataProfileSelectEntry {
+    pub fn new(profile: DataProfileConfig) -> Self {
+        let devices_exist = profile
+            .devices
+            .iter()
+            .map(|device| Path::new(device.path()).exists())
+            .collect();
+
+        Self {
+            profile,
+            devices_exist,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct DataProfileSelect {
+    pub profiles: Vec<DataProfileSelectEntry>,
+
+    // Devices from the command line are searched after those of the chosen profile
+    pub filesystem: FilesystemConfig,
+    pub data_version: String,
+
+    // The state entered once the game data of the chosen profile has been loaded
+    pub next_state: AppState,
+
+    pub asset_io_mount: VfsAssetIoMount,
+    pub mount_error: Option<String>,
+    pub data_integrity_report: Option<DataIntegrityReport>,
+}
+
+impl DataProfileSelect {
+    // Mounts the game data of the profile for the asset server, unless any
+    // of the files required to run the client are missing or corrupt
+    pub fn mount(&mut self, index: usize) -> Option<Arc<VirtualFilesystem>> {
+        let profile = &self.profiles[index].profile;
+        let mut filesystem = self.filesystem.clone();
+        filesystem.devices = profile
+            .devices
+            .iter()
+            .chain(self.filesystem.devices.iter())
+            .cloned()
+            .collect();
+
+        self.mount_error = None;
+        self.data_integrity_report = None;
+
+        let Some(virtual_filesystem) = filesystem.create_virtual_filesystem() else {
+            self.mount_error = Some(format!(
+                "Profile {} has no filesystem devices",
+                profile.name
+            ));
+            return None;
+        };
+
+        let data_version = profile
+            .data_version
+            .clone()
+            .unwrap_or_else(|| self.data_version.clone());
+        let issues = check_data_integrity(&virtual_filesystem, &data_version);
+        if !issues.is_empty() {
+            self.data_integrity_report = Some(DataIntegrityReport {
+                profile: Some(profile.name.clone()),
+                issues,
+            });
+            return None;
+        }
+
+        log::info!("Using game data profile {}", profile.name);
+        self.asset_io_mount.mount(virtual_filesystem.clone());
+        self.data_version = data_version;
+        Some(virtual_filesystem)
+    }
+}
+
+// Game systems only run once the game data of the chosen profile has been loaded
+pub fn game_data_loaded(game_data: Option<Res<GameData>>) -> bool {
+    game_data.is_some()
+}
@@ -1,4 +1,4 @@
-use bevy::prelude::{Color, Entity, Resource};
+use bevy::prelude::{Color, Entity, Resource, Vec3};
 
 const DEBUG_RENDER_COLOR_LIST: [Color; 8] = [
     Color::RED,
@@ -11,17 +11,51 @@ const DEBUG_RENDER_COLOR_LIST: [Color; 8] = [
     Color::BLACK,
 ];
 
-#[derive(Default, Resource)]
+#[derive(Resource)]
 pub struct DebugRenderConfig {
     pub colliders: bool,
+    pub collider_wireframes: bool,
+    pub collider_wireframe_distance: f32,
+    pub player_movement_raycast: bool,
     pub skeleton: bool,
     pub bone_up: bool,
     pub directional_light_frustum: bool,
     pub directional_light_frustum_freeze: bool,
 }
 
+impl Default for DebugRenderConfig {
+    fn default() -> Self {
+        Self {
+            colliders: false,
+            collider_wireframes: false,
+            collider_wireframe_distance: 50.0,
+            player_movement_raycast: false,
+            skeleton: false,
+            bone_up: false,
+            directional_light_frustum: false,
+            directional_light_frustum_freeze: false,
+        }
+    }
+}
+
 impl DebugRenderConfig {
     pub fn color_for_entity(&self, entity: Entity) -> Color {
         DEBUG_RENDER_COLOR_LIST[entity.index() as usize % DEBUG_RENDER_COLOR_LIST.len()]
     }
 }
+
+#[derive(Clone, Copy)]
+pub struct DebugRenderRaycast {
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub max_distance: f32,
+    pub hit_distance: Option<f32>,
+}
+
+// Written by collision_player_system when DebugRenderConfig::player_movement_raycast is set
+#[derive(Default, Resource)]
+pub struct DebugRenderPlayerCollision {
+    pub ground_ray: Option<DebugRenderRaycast>,
+    pub movement_cast: Option<DebugRenderRaycast>,
+    pub movement_cast_radius: f32,
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+
+use rose_data::NpcId;
+
+#[derive(Clone)]
+pub struct FarmingStage {
+    pub npc_id: NpcId,
+
+    // Time until the server replaces this stage with the next, None for the final stage
+    pub duration: Option<Duration>,
+}
+
+#[derive(Clone)]
+pub struct FarmingSeed {
+    pub item_number: usize,
+    pub stages: Vec<FarmingStage>,
+}
+
+#[derive(Clone, Default, Resource)]
+pub struct FarmingSettings {
+    pub seeds: Vec<FarmingSeed>,
+}
+
+impl FarmingSettings {
+    pub fn is_seed(&self, item_number: usize) -> bool {
+        self.seeds
+            .iter()
+            .any(|seed| seed.item_number == item_number)
+    }
+
+    // Returns the seed and stage index for a growth stage npc
+    pub fn find_stage(&self, npc_id: NpcId) -> Option<(&FarmingSeed, usize)> {
+        self.seeds.iter().find_map(|seed| {
+            seed.stages
+                .iter()
+                .position(|stage| stage.npc_id == npc_id)
+                .map(|index| (seed, index))
+        })
+    }
+}
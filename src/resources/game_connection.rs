@@ -5,16 +5,22 @@ use rose_game_common::{
     messages::{client::ClientMessage, server::ServerMessage},
 };
 
+use crate::protocol::{ExtendedClientMessage, ExtendedServerMessage};
+
 #[derive(Resource)]
 pub struct GameConnection {
     pub client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
     pub server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+    pub extended_client_message_tx: tokio::sync::mpsc::UnboundedSender<ExtendedClientMessage>,
+    pub extended_server_message_rx: crossbeam_channel::Receiver<ExtendedServerMessage>,
 }
 
 impl GameConnection {
     pub fn new(
         client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
         server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+        extended_client_message_tx: tokio::sync::mpsc::UnboundedSender<ExtendedClientMessage>,
+        extended_server_message_rx: crossbeam_channel::Receiver<ExtendedServerMessage>,
         login_token: u32,
         password: Password,
     ) -> Self {
@@ -28,6 +34,8 @@ impl GameConnection {
         Self {
             client_message_tx,
             server_message_rx,
+            extended_client_message_tx,
+            extended_server_message_rx,
         }
     }
 }
@@ -9,6 +9,8 @@ use rose_data::{
 use rose_file_readers::{LtbFile, StbFile, ZscFile};
 use rose_game_common::data::AbilityValueCalculator;
 
+use crate::resources::{ItemSetDatabase, SkillResetItemDatabase, UnionDatabase};
+
 #[derive(Resource)]
 pub struct GameData {
     pub ability_value_calculator: Box<dyn AbilityValueCalculator + Send + Sync>,
@@ -18,18 +20,22 @@ pub struct GameData {
     pub data_decoder: Box<dyn DataDecoder + Send + Sync>,
     pub effect_database: Arc<EffectDatabase>,
     pub items: Arc<ItemDatabase>,
+    pub item_sets: Arc<ItemSetDatabase>,
     pub job_class: Arc<JobClassDatabase>,
     pub npcs: Arc<NpcDatabase>,
     pub quests: Arc<QuestDatabase>,
     pub skills: Arc<SkillDatabase>,
+    pub skill_reset_items: Arc<SkillResetItemDatabase>,
     pub skybox: Arc<SkyboxDatabase>,
     pub sounds: Arc<SoundDatabase>,
     pub status_effects: Arc<StatusEffectDatabase>,
     pub string_database: Arc<StringDatabase>,
+    pub unions: Arc<UnionDatabase>,
     pub zone_list: Arc<ZoneList>,
     pub ltb_event: LtbFile,
     pub zsc_event_object: ZscFile,
     pub zsc_special_object: ZscFile,
     pub stb_morph_object: StbFile,
+    pub stb_warp: StbFile,
     pub character_select_positions: Vec<Transform>,
 }
@@ -0,0 +1,21 @@
+use bevy::prelude::Resource;
+use std::sync::{atomic::AtomicI32, Arc};
+
+pub const HEADLESS_EXIT_SUCCESS: i32 = 0;
+pub const HEADLESS_EXIT_DISCONNECTED: i32 = 1;
+pub const HEADLESS_EXIT_TIMEOUT: i32 = 2;
+pub const HEADLESS_EXIT_DATA_ERROR: i32 = 3;
+
+#[derive(Resource)]
+pub struct HeadlessState {
+    // Seconds to keep running after entering the game
+    pub run_duration: f32,
+
+    // Seconds to wait for entering the game before failing
+    pub timeout: f32,
+
+    pub entered_game_time: Option<f32>,
+
+    // Shared with run_headless so the exit code can be read after the app exits
+    pub exit_code: Arc<AtomicI32>,
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::Resource;
+
+// Names of players whose chat, whispers and invites are hidden or rejected,
+// synced per account by account_settings_system
+#[derive(Default, Resource)]
+pub struct IgnoreList {
+    pub names: Vec<String>,
+}
+
+impl IgnoreList {
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|ignored| ignored.eq_ignore_ascii_case(name))
+    }
+
+    // Returns false if the name was already ignored
+    pub fn add(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.is_ignored(name) {
+            return false;
+        }
+
+        self.names.push(name.to_string());
+        true
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.names
+            .retain(|ignored| !ignored.eq_ignore_ascii_case(name));
+    }
+}
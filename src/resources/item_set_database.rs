@@ -0,0 +1,151 @@
+use serde::Deserialize;
+
+use rose_data::{ItemReference, ItemType};
+use rose_file_readers::{VfsFile, VirtualFilesystem};
+use rose_game_common::components::Equipment;
+
+// Item sets are not part of the irose data files, servers with sets can
+// describe them in this file and ship it with a mod
+const ITEM_SET_DATABASE_PATH: &str = "3DDATA/STB/ITEM_SETS.TOML";
+
+#[derive(Deserialize)]
+struct ItemSetPieceConfig {
+    item_type: String,
+    item_number: usize,
+}
+
+#[derive(Deserialize)]
+struct ItemSetBonusConfig {
+    pieces: usize,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct ItemSetConfig {
+    name: String,
+    items: Vec<ItemSetPieceConfig>,
+    #[serde(default)]
+    bonuses: Vec<ItemSetBonusConfig>,
+}
+
+#[derive(Default, Deserialize)]
+struct ItemSetDatabaseConfig {
+    #[serde(default)]
+    sets: Vec<ItemSetConfig>,
+}
+
+pub struct ItemSetBonus {
+    pub required_pieces: usize,
+    pub description: String,
+}
+
+pub struct ItemSetData {
+    pub name: String,
+    pub items: Vec<ItemReference>,
+    pub bonuses: Vec<ItemSetBonus>,
+}
+
+impl ItemSetData {
+    pub fn count_equipped(&self, equipment: &Equipment) -> usize {
+        self.items
+            .iter()
+            .filter(|item| {
+                equipment
+                    .equipped_items
+                    .values()
+                    .flatten()
+                    .any(|equipped| equipped.item == **item)
+            })
+            .count()
+    }
+}
+
+#[derive(Default)]
+pub struct ItemSetDatabase {
+    sets: Vec<ItemSetData>,
+}
+
+pub(crate) fn parse_item_type(name: &str) -> Option<ItemType> {
+    match name {
+        "Face" => Some(ItemType::Face),
+        "Head" => Some(ItemType::Head),
+        "Body" => Some(ItemType::Body),
+        "Hands" => Some(ItemType::Hands),
+        "Feet" => Some(ItemType::Feet),
+        "Back" => Some(ItemType::Back),
+        "Jewellery" => Some(ItemType::Jewellery),
+        "Weapon" => Some(ItemType::Weapon),
+        "SubWeapon" => Some(ItemType::SubWeapon),
+        "Consumable" => Some(ItemType::Consumable),
+        "Gem" => Some(ItemType::Gem),
+        "Material" => Some(ItemType::Material),
+        "Quest" => Some(ItemType::Quest),
+        "Vehicle" => Some(ItemType::Vehicle),
+        _ => None,
+    }
+}
+
+impl ItemSetDatabase {
+    pub fn load(vfs: &VirtualFilesystem) -> Self {
+        let Ok(file) = vfs.open_file(ITEM_SET_DATABASE_PATH) else {
+            return Self::default();
+        };
+        let bytes: Vec<u8> = match file {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+
+        let config = match std::str::from_utf8(&bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(toml::from_str::<ItemSetDatabaseConfig>(text)?))
+        {
+            Ok(config) => config,
+            Err(error) => {
+                log::warn!("Failed to load {}: {}", ITEM_SET_DATABASE_PATH, error);
+                return Self::default();
+            }
+        };
+
+        let sets = config
+            .sets
+            .into_iter()
+            .map(|set| {
+                let mut bonuses = set
+                    .bonuses
+                    .into_iter()
+                    .map(|bonus| ItemSetBonus {
+                        required_pieces: bonus.pieces,
+                        description: bonus.description,
+                    })
+                    .collect::<Vec<_>>();
+                bonuses.sort_by_key(|bonus| bonus.required_pieces);
+
+                ItemSetData {
+                    items: set
+                        .items
+                        .iter()
+                        .filter_map(|piece| {
+                            let item_type = parse_item_type(&piece.item_type);
+                            if item_type.is_none() {
+                                log::warn!(
+                                    "Unknown item type {} in item set {}",
+                                    piece.item_type,
+                                    set.name
+                                );
+                            }
+                            Some(ItemReference::new(item_type?, piece.item_number))
+                        })
+                        .collect(),
+                    name: set.name,
+                    bonuses,
+                }
+            })
+            .collect();
+
+        Self { sets }
+    }
+
+    pub fn find_item_set(&self, item: ItemReference) -> Option<&ItemSetData> {
+        self.sets.iter().find(|set| set.items.contains(&item))
+    }
+}
@@ -4,16 +4,24 @@ use rose_game_common::{
     messages::{client::ClientMessage, server::ServerMessage},
 };
 
+use crate::protocol::{LoginChallenge, ServerCapabilities};
+
 #[derive(Resource)]
 pub struct LoginConnection {
     pub client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
     pub server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+    pub login_challenge_rx: crossbeam_channel::Receiver<LoginChallenge>,
+    pub login_challenge_response_tx: tokio::sync::mpsc::UnboundedSender<String>,
+    pub server_capabilities_rx: crossbeam_channel::Receiver<ServerCapabilities>,
 }
 
 impl LoginConnection {
     pub fn new(
         client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
         server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+        login_challenge_rx: crossbeam_channel::Receiver<LoginChallenge>,
+        login_challenge_response_tx: tokio::sync::mpsc::UnboundedSender<String>,
+        server_capabilities_rx: crossbeam_channel::Receiver<ServerCapabilities>,
     ) -> Self {
         client_message_tx
             .send(ClientMessage::ConnectionRequest {
@@ -25,6 +33,9 @@ impl LoginConnection {
         Self {
             client_message_tx,
             server_message_rx,
+            login_challenge_rx,
+            login_challenge_response_tx,
+            server_capabilities_rx,
         }
     }
 }
@@ -0,0 +1,19 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::Resource;
+
+// Present while the world server has us waiting in its login queue
+#[derive(Resource)]
+pub struct LoginQueue {
+    pub position: u32,
+    pub estimated_wait: Option<Duration>,
+    pub message_of_the_day: String,
+    pub updated: Instant,
+}
+
+impl LoginQueue {
+    pub fn remaining_wait(&self) -> Option<Duration> {
+        self.estimated_wait
+            .map(|estimated_wait| estimated_wait.saturating_sub(self.updated.elapsed()))
+    }
+}
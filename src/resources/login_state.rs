@@ -1,9 +1,12 @@
 use bevy::prelude::Resource;
 
+use crate::protocol::LoginChallenge;
+
 #[derive(Resource)]
 pub enum LoginState {
     Input,
     WaitServerList,
+    Challenge(LoginChallenge),
     ServerSelect,
     JoiningServer,
 }
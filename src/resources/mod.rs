@@ -1,63 +1,150 @@
 mod account;
+mod account_settings;
 mod app_state;
+mod auto_login;
 mod character_list;
 mod character_select_state;
+mod character_switch;
+mod cinematic_mode;
+mod client_capabilities;
 mod client_entity_list;
+mod client_entity_registry;
+mod client_settings;
+mod combat_state;
+mod connection_state;
+mod crowd_settings;
 mod current_zone;
 mod damage_digits_spawner;
+mod data_profile_select;
 mod debug_inspector;
 mod debug_render;
+mod farming_settings;
 mod game_connection;
 mod game_data;
+mod headless;
+mod ignore_list;
+mod item_set_database;
 mod login_connection;
+mod login_queue;
 mod login_state;
+mod model_pool;
 mod name_tag_cache;
 mod name_tag_settings;
 mod network_thread;
+mod party_member_targets;
 mod pending_clan_invites;
+mod placeholder_model_assets;
+mod player_reports;
+mod player_summons;
+mod position_correction_stats;
 mod render_configuration;
+mod replay;
 mod selected_target;
 mod server_configuration;
 mod server_list;
+mod server_message_handlers;
+mod server_message_metrics;
+mod skeleton_cache;
+mod skill_reset_item_database;
 mod sound_cache;
 mod sound_settings;
 mod specular_texture;
+mod store_low_power_mode;
+mod texture_streaming;
 mod ui_resources;
+mod union_database;
+mod unsupported_packets;
 mod virtual_filesystem;
 mod world_connection;
 mod world_rates;
 mod world_time;
+mod zone_data_verification;
+mod zone_navigation;
 mod zone_time;
+mod zone_transition;
 
 pub use account::Account;
+pub use account_settings::AccountSettings;
 pub use app_state::AppState;
-pub use character_list::CharacterList;
+pub use auto_login::{AutoLogin, AutoLoginState};
+pub use character_list::{CharacterList, CharacterSlots};
 pub use character_select_state::CharacterSelectState;
+pub use character_switch::CharacterSwitch;
+pub use cinematic_mode::{cinematic_mode_disabled, CinematicMode};
+pub use client_capabilities::ClientCapabilities;
 pub use client_entity_list::ClientEntityList;
+pub use client_entity_registry::{ClientEntityRegistry, ClientEntityRegistryEntry};
+pub use client_settings::{
+    AccessibilitySettings, AudioSettings, ChatMacro, ClientSettings, CombatSettings,
+    ControlsSettings, GraphicsSettings, InputDisplayPosition, InteractKey, InterfaceSettings,
+    LoginProfile, LoginSettings, SocialSettings,
+};
+pub use combat_state::CombatState;
+pub use connection_state::{ConnectionServer, ConnectionState};
+pub use crowd_settings::CrowdSettings;
 pub use current_zone::CurrentZone;
 pub use damage_digits_spawner::DamageDigitsSpawner;
+pub use data_profile_select::{game_data_loaded, DataProfileSelect, DataProfileSelectEntry};
 pub use debug_inspector::DebugInspector;
-pub use debug_render::DebugRenderConfig;
+pub use debug_render::{DebugRenderConfig, DebugRenderPlayerCollision, DebugRenderRaycast};
+pub use farming_settings::{FarmingSeed, FarmingSettings, FarmingStage};
 pub use game_connection::GameConnection;
 pub use game_data::GameData;
+pub use headless::{
+    HeadlessState, HEADLESS_EXIT_DATA_ERROR, HEADLESS_EXIT_DISCONNECTED, HEADLESS_EXIT_SUCCESS,
+    HEADLESS_EXIT_TIMEOUT,
+};
+pub use ignore_list::IgnoreList;
+pub use item_set_database::{ItemSetBonus, ItemSetData, ItemSetDatabase};
 pub use login_connection::LoginConnection;
+pub use login_queue::LoginQueue;
 pub use login_state::LoginState;
-pub use name_tag_settings::NameTagSettings;
+pub use model_pool::{ModelPartPoolKey, ModelPool, ModelPoolCommands};
+pub use name_tag_settings::{NameTagSettings, NpcRank};
 pub use network_thread::{run_network_thread, NetworkThread, NetworkThreadMessage};
-pub use pending_clan_invites::{PendingClanInvite, PendingClanInvites};
+pub use party_member_targets::PartyMemberTargets;
+pub use pending_clan_invites::{PendingClanInvite, PendingClanInvites, CLAN_INVITE_TIMEOUT};
+pub use placeholder_model_assets::{PlaceholderModelAssets, PlaceholderModelCommands};
+pub use player_reports::PlayerReports;
+pub use player_summons::PlayerSummons;
+pub use position_correction_stats::PositionCorrectionStats;
 pub use render_configuration::RenderConfiguration;
+pub use replay::{
+    Replay, ReplayChat, ReplayCommand, ReplayEntity, ReplayFrame, ReplayPlayback, ReplayRecorder,
+    REPLAY_FRAME_INTERVAL,
+};
 pub use selected_target::SelectedTarget;
 pub use server_configuration::ServerConfiguration;
 pub use server_list::{ServerList, ServerListGameServer, ServerListWorldServer};
+pub use server_message_handlers::{
+    run_extended_server_message_handlers, run_server_message_handlers,
+    ExtendedServerMessageHandler, ExtendedServerMessageKind, ServerMessageHandler,
+    ServerMessageHandlerAppExt, ServerMessageHandlers, ServerMessageKind,
+};
+pub use server_message_metrics::{ServerMessageMetric, ServerMessageMetrics};
+pub use skeleton_cache::{CachedSkeleton, SkeletonCache, SkeletonCacheState};
+pub use skill_reset_item_database::SkillResetItemDatabase;
 pub use sound_cache::SoundCache;
 pub use sound_settings::SoundSettings;
 pub use specular_texture::SpecularTexture;
+pub use store_low_power_mode::{store_low_power_mode_inactive, StoreLowPowerMode};
+pub use texture_streaming::{
+    drop_image_mip_levels, TextureStreaming, TextureStreamingImage, TextureStreamingState,
+};
 pub use ui_resources::{
     load_ui_resources, ui_requested_cursor_apply_system, update_ui_resources, UiCursorType,
     UiRequestedCursor, UiResources, UiSprite, UiSpriteSheet, UiSpriteSheetType, UiTexture,
+    UI_DIALOG_FILENAMES,
 };
-pub use virtual_filesystem::VfsResource;
+pub use union_database::{UnionData, UnionDatabase, UnionRank, UnionReward, NUM_UNIONS};
+pub use unsupported_packets::UnsupportedPackets;
+pub use virtual_filesystem::{VfsModHotReload, VfsResource};
 pub use world_connection::WorldConnection;
 pub use world_rates::WorldRates;
 pub use world_time::WorldTime;
+pub use zone_data_verification::ZoneDataVerification;
+pub use zone_navigation::{
+    NavigationGrid, NavigationGridInput, NavigationHeightmap, ZoneNavigation, NAVIGATION_CELL_SIZE,
+};
 pub use zone_time::{ZoneTime, ZoneTimeState};
+pub use zone_transition::{ActiveZoneTransition, ZoneTransition, ZONE_TRANSITION_TIPS};
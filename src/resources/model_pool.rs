@@ -0,0 +1,339 @@
+use std::hash::Hash;
+
+use bevy::{
+    asset::HandleId,
+    ecs::system::Commands,
+    hierarchy::{despawn_with_children_recursive, BuildWorldChildren, Children, Parent},
+    prelude::{Assets, Entity, Handle, Mesh, Mut, Resource, Visibility, World},
+    utils::{HashMap, HashSet},
+};
+
+use crate::render::ObjectMaterial;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ModelPartPoolKey {
+    mesh: HandleId,
+    material: ObjectMaterialPoolKey,
+}
+
+impl ModelPartPoolKey {
+    pub fn new(mesh: &Handle<Mesh>, material: &ObjectMaterial) -> Self {
+        Self {
+            mesh: mesh.id(),
+            material: ObjectMaterialPoolKey::new(material),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct ObjectMaterialPoolKey {
+    base_texture: Option<HandleId>,
+    specular_texture: Option<HandleId>,
+    alpha_value: Option<u32>,
+    alpha_enabled: bool,
+    alpha_test: Option<u32>,
+    two_sided: bool,
+    z_test_enabled: bool,
+    z_write_enabled: bool,
+    skinned: bool,
+    rim_light: Option<[u32; 3]>,
+}
+
+impl ObjectMaterialPoolKey {
+    fn new(material: &ObjectMaterial) -> Self {
+        Self {
+            base_texture: material.base_texture.as_ref().map(|handle| handle.id()),
+            specular_texture: material.specular_texture.as_ref().map(|handle| handle.id()),
+            alpha_value: material.alpha_value.map(f32::to_bits),
+            alpha_enabled: material.alpha_enabled,
+            alpha_test: material.alpha_test.map(f32::to_bits),
+            two_sided: material.two_sided,
+            z_test_enabled: material.z_test_enabled,
+            z_write_enabled: material.z_write_enabled,
+            skinned: material.skinned,
+            rim_light: material
+                .rim_light
+                .map(|rim_light| rim_light.to_array().map(f32::to_bits)),
+        }
+    }
+}
+
+struct PooledEntries<T> {
+    entries: Vec<T>,
+    last_used: u64,
+}
+
+// Pooled entries grouped by key, keys without any entries are removed so the
+// number of keys is limited by the total number of entries
+struct PoolEntries<K, T> {
+    keys: HashMap<K, PooledEntries<T>>,
+    num_entries: usize,
+}
+
+impl<K, T> Default for PoolEntries<K, T> {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::default(),
+            num_entries: 0,
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash, T> PoolEntries<K, T> {
+    fn len(&self, key: &K) -> usize {
+        self.keys.get(key).map_or(0, |pooled| pooled.entries.len())
+    }
+
+    fn push(&mut self, key: K, entry: T, tick: u64) {
+        let pooled = self.keys.entry(key).or_insert_with(|| PooledEntries {
+            entries: Vec::new(),
+            last_used: tick,
+        });
+        pooled.entries.push(entry);
+        pooled.last_used = tick;
+        self.num_entries += 1;
+    }
+
+    fn pop(&mut self, key: &K, tick: u64) -> Option<T> {
+        let pooled = self.keys.get_mut(key)?;
+        let entry = pooled.entries.pop()?;
+        pooled.last_used = tick;
+        if pooled.entries.is_empty() {
+            self.keys.remove(key);
+        }
+        self.num_entries -= 1;
+        Some(entry)
+    }
+
+    // Removes the oldest entry of the least recently used key
+    fn pop_least_recently_used(&mut self) -> Option<T> {
+        let key = self
+            .keys
+            .iter()
+            .min_by_key(|(_, pooled)| pooled.last_used)
+            .map(|(key, _)| *key)?;
+        let pooled = self.keys.get_mut(&key)?;
+        let entry = pooled.entries.remove(0);
+        if pooled.entries.is_empty() {
+            self.keys.remove(&key);
+        }
+        self.num_entries -= 1;
+        Some(entry)
+    }
+
+    fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.num_entries = 0;
+        self.keys.drain().flat_map(|(_, pooled)| pooled.entries)
+    }
+}
+
+// Keeps the entities of removed character model parts and skeletons so they
+// can be reused when the model is spawned again, e.g. after changing equipment
+// or closing a personal store, instead of despawning and respawning them.
+// Each pooled part keeps its own material instance, parts are matched by the
+// material's current values so a modified material is never reused by mistake.
+// When the pool is full the least recently used models are despawned first.
+#[derive(Resource)]
+pub struct ModelPool {
+    pub max_pooled_skeletons: usize,
+    pub max_pooled_parts: usize,
+    pub max_total_pooled_skeletons: usize,
+    pub max_total_pooled_parts: usize,
+    skeletons: PoolEntries<HandleId, Vec<Entity>>,
+    parts: PoolEntries<ModelPartPoolKey, Entity>,
+    tick: u64,
+}
+
+impl Default for ModelPool {
+    fn default() -> Self {
+        Self {
+            max_pooled_skeletons: 32,
+            max_pooled_parts: 16,
+            max_total_pooled_skeletons: 128,
+            max_total_pooled_parts: 512,
+            skeletons: PoolEntries::default(),
+            parts: PoolEntries::default(),
+            tick: 0,
+        }
+    }
+}
+
+impl ModelPool {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    pub fn take_skeleton(&mut self, inverse_bindposes: HandleId) -> Option<Vec<Entity>> {
+        let tick = self.next_tick();
+        self.skeletons.pop(&inverse_bindposes, tick)
+    }
+
+    pub fn take_part(&mut self, key: ModelPartPoolKey) -> Option<Entity> {
+        let tick = self.next_tick();
+        self.parts.pop(&key, tick)
+    }
+}
+
+fn despawn_pooled_entities(world: &mut World, entities: impl IntoIterator<Item = Entity>) {
+    for entity in entities {
+        if world.get_entity(entity).is_some() {
+            despawn_with_children_recursive(world, entity);
+        }
+    }
+}
+
+fn pool_model_part(world: &mut World, model_pool: &mut ModelPool, entity: Entity) {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+
+    // Only plain mesh parts can be reused, anything else such as effects is despawned
+    let key = match (
+        entity_ref.get::<Handle<Mesh>>(),
+        entity_ref.get::<Handle<ObjectMaterial>>(),
+        entity_ref.get::<Children>(),
+    ) {
+        (Some(mesh), Some(material), None) => world
+            .resource::<Assets<ObjectMaterial>>()
+            .get(material)
+            .map(|material| ModelPartPoolKey::new(mesh, material)),
+        _ => None,
+    };
+
+    if let Some(key) = key {
+        if model_pool.parts.len(&key) < model_pool.max_pooled_parts {
+            while model_pool.parts.num_entries >= model_pool.max_total_pooled_parts {
+                let Some(evicted) = model_pool.parts.pop_least_recently_used() else {
+                    break;
+                };
+                despawn_pooled_entities(world, [evicted]);
+            }
+
+            world
+                .entity_mut(entity)
+                .remove_parent()
+                .insert(Visibility::Hidden);
+            let tick = model_pool.next_tick();
+            model_pool.parts.push(key, entity, tick);
+            return;
+        }
+    }
+
+    despawn_with_children_recursive(world, entity);
+}
+
+fn pool_skeleton(
+    world: &mut World,
+    model_pool: &mut ModelPool,
+    inverse_bindposes: HandleId,
+    joints: Vec<Entity>,
+) {
+    if joints
+        .iter()
+        .any(|joint| world.get_entity(*joint).is_none())
+    {
+        for joint in joints {
+            if world.get_entity(joint).is_some() {
+                despawn_with_children_recursive(world, joint);
+            }
+        }
+        return;
+    }
+
+    // Despawn anything else attached to the skeleton, e.g. skill effects on a dummy bone
+    let joint_set: HashSet<Entity> = joints.iter().copied().collect();
+    let attached: Vec<Entity> = joints
+        .iter()
+        .filter_map(|joint| world.get::<Children>(*joint))
+        .flat_map(|children| children.iter().copied())
+        .filter(|child| !joint_set.contains(child))
+        .collect();
+    for entity in attached {
+        despawn_with_children_recursive(world, entity);
+    }
+
+    let root_joints: Vec<Entity> = joints
+        .iter()
+        .copied()
+        .filter(|joint| {
+            world
+                .get::<Parent>(*joint)
+                .map_or(true, |parent| !joint_set.contains(&parent.get()))
+        })
+        .collect();
+
+    if model_pool.skeletons.len(&inverse_bindposes) >= model_pool.max_pooled_skeletons {
+        for root_joint in root_joints {
+            despawn_with_children_recursive(world, root_joint);
+        }
+        return;
+    }
+
+    while model_pool.skeletons.num_entries >= model_pool.max_total_pooled_skeletons {
+        let Some(evicted) = model_pool.skeletons.pop_least_recently_used() else {
+            break;
+        };
+        despawn_pooled_entities(world, evicted);
+    }
+
+    for root_joint in root_joints {
+        world
+            .entity_mut(root_joint)
+            .remove_parent()
+            .insert(Visibility::Hidden);
+    }
+    let tick = model_pool.next_tick();
+    model_pool.skeletons.push(inverse_bindposes, joints, tick);
+}
+
+fn clear_model_pool(world: &mut World, model_pool: &mut ModelPool) {
+    let entities: Vec<Entity> = model_pool
+        .parts
+        .drain()
+        .chain(model_pool.skeletons.drain().flatten())
+        .collect();
+    despawn_pooled_entities(world, entities);
+}
+
+pub trait ModelPoolCommands {
+    fn pool_model_parts(&mut self, parts: Vec<Entity>);
+    fn pool_skeleton(&mut self, inverse_bindposes: HandleId, joints: Vec<Entity>);
+    fn clear_model_pool(&mut self);
+}
+
+impl<'w, 's> ModelPoolCommands for Commands<'w, 's> {
+    fn pool_model_parts(&mut self, parts: Vec<Entity>) {
+        if parts.is_empty() {
+            return;
+        }
+
+        self.add(move |world: &mut World| {
+            world.resource_scope(|world, mut model_pool: Mut<ModelPool>| {
+                for entity in parts {
+                    pool_model_part(world, &mut model_pool, entity);
+                }
+            });
+        });
+    }
+
+    fn pool_skeleton(&mut self, inverse_bindposes: HandleId, joints: Vec<Entity>) {
+        if joints.is_empty() {
+            return;
+        }
+
+        self.add(move |world: &mut World| {
+            world.resource_scope(|world, mut model_pool: Mut<ModelPool>| {
+                pool_skeleton(world, &mut model_pool, inverse_bindposes, joints);
+            });
+        });
+    }
+
+    fn clear_model_pool(&mut self) {
+        self.add(|world: &mut World| {
+            world.resource_scope(|world, mut model_pool: Mut<ModelPool>| {
+                clear_model_pool(world, &mut model_pool);
+            });
+        });
+    }
+}
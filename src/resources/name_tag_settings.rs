@@ -3,10 +3,42 @@ use enum_map::{enum_map, EnumMap};
 
 use crate::components::NameTagType;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NpcRank {
+    Normal,
+    Elite,
+    Boss,
+}
+
 #[derive(Resource)]
 pub struct NameTagSettings {
     pub show_all: EnumMap<NameTagType, bool>,
     pub font_size: EnumMap<NameTagType, f32>,
+
+    // Always show health bars for other characters in the player's party or clan
+    pub show_party_health: bool,
+    pub show_clan_health: bool,
+
+    // Show say and shout chat as speech bubbles above the speaker
+    pub show_chat_bubbles: bool,
+
+    // Colour monster names grey, green or red by level difference instead of the
+    // irose level colours
+    pub simple_level_colors: bool,
+
+    // Npc types from LIST_NPC.STB which are shown with an elite or boss icon
+    pub elite_npc_types: Vec<u16>,
+    pub boss_npc_types: Vec<u16>,
+}
+
+impl NameTagSettings {
+    pub fn get_npc_rank(&self, npc_type_index: Option<u16>) -> NpcRank {
+        match npc_type_index {
+            Some(npc_type) if self.boss_npc_types.contains(&npc_type) => NpcRank::Boss,
+            Some(npc_type) if self.elite_npc_types.contains(&npc_type) => NpcRank::Elite,
+            _ => NpcRank::Normal,
+        }
+    }
 }
 
 impl Default for NameTagSettings {
@@ -16,12 +48,20 @@ impl Default for NameTagSettings {
                 NameTagType::Character => true,
                 NameTagType::Npc => true,
                 NameTagType::Monster => false,
+                NameTagType::Summon => true,
             },
             font_size: enum_map! {
                 NameTagType::Character => 16.0,
                 NameTagType::Npc => 16.0,
                 NameTagType::Monster => 16.0,
+                NameTagType::Summon => 16.0,
             },
+            show_party_health: false,
+            show_clan_health: false,
+            show_chat_bubbles: true,
+            simple_level_colors: false,
+            elite_npc_types: Vec::new(),
+            boss_npc_types: Vec::new(),
         }
     }
 }
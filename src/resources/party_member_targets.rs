@@ -0,0 +1,19 @@
+use bevy::prelude::Resource;
+use rose_game_common::messages::ClientEntityId;
+
+use crate::protocol::PartyMemberTarget;
+
+// The selected target of each party member, as last sent by the server
+#[derive(Default, Resource)]
+pub struct PartyMemberTargets {
+    pub targets: Vec<PartyMemberTarget>,
+}
+
+impl PartyMemberTargets {
+    pub fn get(&self, member_entity_id: ClientEntityId) -> Option<ClientEntityId> {
+        self.targets
+            .iter()
+            .find(|target| target.entity_id == member_entity_id)
+            .and_then(|target| target.target_entity_id)
+    }
+}
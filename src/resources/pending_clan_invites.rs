@@ -1,11 +1,15 @@
 use bevy::prelude::Resource;
 use rose_game_common::components::{ClanLevel, ClanUniqueId};
 
+// Time in seconds before an unanswered invite is automatically rejected
+pub const CLAN_INVITE_TIMEOUT: f32 = 60.0;
+
 pub struct PendingClanInvite {
     pub inviter_name: String,
     pub clan_name: String,
     pub clan_unique_id: ClanUniqueId,
     pub clan_level: ClanLevel,
+    pub remaining_time: f32,
 }
 
 #[derive(Default, Resource)]
@@ -0,0 +1,91 @@
+use bevy::{
+    ecs::system::Commands,
+    hierarchy::{despawn_with_children_recursive, BuildWorldChildren},
+    pbr::{AlphaMode, PbrBundle, StandardMaterial},
+    prelude::{shape, Assets, Color, Entity, Handle, Mesh, Resource, Transform, World},
+};
+
+use crate::components::PlaceholderModel;
+
+// Shared mesh and material for the model shown while a skeleton is loading
+#[derive(Resource)]
+pub struct PlaceholderModelAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+impl PlaceholderModelAssets {
+    pub fn new(meshes: &mut Assets<Mesh>, materials: &mut Assets<StandardMaterial>) -> Self {
+        Self {
+            mesh: meshes.add(
+                shape::Capsule {
+                    radius: 0.3,
+                    depth: 1.0,
+                    ..Default::default()
+                }
+                .into(),
+            ),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgba(0.6, 0.6, 0.6, 0.5),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+fn spawn_placeholder_model(world: &mut World, entity: Entity) {
+    if world
+        .get_entity(entity)
+        .map_or(true, |entity_ref| entity_ref.contains::<PlaceholderModel>())
+    {
+        return;
+    }
+
+    let Some(assets) = world.get_resource::<PlaceholderModelAssets>() else {
+        return;
+    };
+    let bundle = PbrBundle {
+        mesh: assets.mesh.clone(),
+        material: assets.material.clone(),
+        transform: Transform::from_xyz(0.0, 0.8, 0.0),
+        ..Default::default()
+    };
+
+    let placeholder_entity = world.spawn(bundle).id();
+    world
+        .entity_mut(entity)
+        .add_child(placeholder_entity)
+        .insert(PlaceholderModel {
+            entity: placeholder_entity,
+        });
+}
+
+fn despawn_placeholder_model(world: &mut World, entity: Entity) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let Some(placeholder_model) = entity_mut.take::<PlaceholderModel>() else {
+        return;
+    };
+
+    if world.get_entity(placeholder_model.entity).is_some() {
+        despawn_with_children_recursive(world, placeholder_model.entity);
+    }
+}
+
+pub trait PlaceholderModelCommands {
+    fn spawn_placeholder_model(&mut self, entity: Entity);
+    fn despawn_placeholder_model(&mut self, entity: Entity);
+}
+
+impl<'w, 's> PlaceholderModelCommands for Commands<'w, 's> {
+    fn spawn_placeholder_model(&mut self, entity: Entity) {
+        self.add(move |world: &mut World| spawn_placeholder_model(world, entity));
+    }
+
+    fn despawn_placeholder_model(&mut self, entity: Entity) {
+        self.add(move |world: &mut World| despawn_placeholder_model(world, entity));
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::PlayerReport;
+
+#[derive(Default, Serialize, Deserialize)]
+struct QueuedPlayerReports {
+    reports: Vec<PlayerReport>,
+}
+
+// Reports made while not connected to a server are queued to a file and sent
+// the next time we are connected
+#[derive(Resource)]
+pub struct PlayerReports {
+    pub queue_path: PathBuf,
+}
+
+impl PlayerReports {
+    pub fn new(queue_path: PathBuf) -> Self {
+        Self { queue_path }
+    }
+
+    fn read_queued(&self) -> QueuedPlayerReports {
+        std::fs::read_to_string(&self.queue_path)
+            .ok()
+            .and_then(|data| toml::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn queue(&self, report: PlayerReport) {
+        let mut queued = self.read_queued();
+        queued.reports.push(report);
+
+        let result = toml::to_string(&queued)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+            .and_then(|data| {
+                if let Some(parent) = self.queue_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&self.queue_path, data)
+            });
+        if let Err(error) = result {
+            log::warn!(
+                "Failed to write player reports {} with error {}",
+                self.queue_path.to_string_lossy(),
+                error
+            );
+        }
+    }
+
+    pub fn take_queued(&self) -> Vec<PlayerReport> {
+        if !self.queue_path.exists() {
+            return Vec::new();
+        }
+
+        let queued = self.read_queued();
+        std::fs::remove_file(&self.queue_path).ok();
+        queued.reports
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::Resource;
+use rose_game_common::messages::ClientEntityId;
+
+// Summoned npcs owned by the player, as last sent by the server
+#[derive(Default, Resource)]
+pub struct PlayerSummons {
+    pub entity_ids: Vec<ClientEntityId>,
+}
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::Resource;
+
+// Our own character being corrected this many times within the window suggests
+// the client and server disagree on movement speed, rather than just lag
+const PLAYER_CORRECTION_WINDOW: f64 = 30.0;
+const PLAYER_CORRECTION_MISMATCH_COUNT: usize = 5;
+
+// Distances are in world units as sent by the server (centimetres)
+#[derive(Default, Resource)]
+pub struct PositionCorrectionStats {
+    pub num_smoothed: u32,
+    pub num_snapped: u32,
+    pub last_distance: f32,
+    pub max_distance: f32,
+
+    pub num_player_corrections: u32,
+    pub recent_player_corrections: VecDeque<f64>,
+    pub movement_mismatch: bool,
+
+    // The speed we were given and the maximum from our ability values
+    pub num_speed_clamped: u32,
+    pub last_clamped_speed: Option<(f32, f32)>,
+}
+
+impl PositionCorrectionStats {
+    pub fn record(&mut self, distance: f32, snapped: bool) {
+        if snapped {
+            self.num_snapped += 1;
+        } else {
+            self.num_smoothed += 1;
+        }
+        self.last_distance = distance;
+        self.max_distance = self.max_distance.max(distance);
+    }
+
+    // Returns true when the corrections have just become persistent
+    pub fn record_player_correction(&mut self, now: f64) -> bool {
+        self.num_player_corrections += 1;
+        self.recent_player_corrections.push_back(now);
+        while self
+            .recent_player_corrections
+            .front()
+            .map_or(false, |time| now - time > PLAYER_CORRECTION_WINDOW)
+        {
+            self.recent_player_corrections.pop_front();
+        }
+
+        if self.movement_mismatch
+            || self.recent_player_corrections.len() < PLAYER_CORRECTION_MISMATCH_COUNT
+        {
+            return false;
+        }
+
+        self.movement_mismatch = true;
+        true
+    }
+
+    pub fn record_speed_clamp(&mut self, speed: f32, max_speed: f32) {
+        self.num_speed_clamped += 1;
+        self.last_clamped_speed = Some((speed, max_speed));
+    }
+
+    pub fn dismiss_movement_mismatch(&mut self) {
+        self.movement_mismatch = false;
+        self.recent_player_corrections.clear();
+    }
+}
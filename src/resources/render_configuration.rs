@@ -1,7 +1,24 @@
-use bevy::prelude::Resource;
+use bevy::{prelude::Resource, window::CursorGrabMode};
+
+use crate::render::TerrainMaterialQuality;
 
 #[derive(Resource)]
 pub struct RenderConfiguration {
     pub passthrough_terrain_textures: bool,
     pub trail_effect_duration_multiplier: f32,
+    pub max_dynamic_point_lights: usize,
+    pub terrain_quality: TerrainMaterialQuality,
+    pub terrain_detail_texture: Option<String>,
+    pub confine_cursor: bool,
+}
+
+impl RenderConfiguration {
+    // The grab mode to restore when the cursor is not locked by camera controls
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        if self.confine_cursor {
+            CursorGrabMode::Confined
+        } else {
+            CursorGrabMode::None
+        }
+    }
 }
@@ -0,0 +1,448 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Resource, Timer, TimerMode},
+};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+};
+
+use rose_data::ZoneId;
+
+use crate::components::{ClientEntityId, ClientEntityType, Command};
+
+const REPLAY_MAGIC: &[u8; 4] = b"RRPL";
+const REPLAY_VERSION: u8 = 1;
+
+const REPLAY_RECORD_ZONE: u8 = 0;
+const REPLAY_RECORD_FRAME: u8 = 1;
+const REPLAY_RECORD_CHAT: u8 = 2;
+
+// Entity transforms are sampled at 10Hz, playback interpolates between frames
+pub const REPLAY_FRAME_INTERVAL: f32 = 0.1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayCommand {
+    Stop,
+    Move,
+    Attack,
+    Die,
+    PersonalStore,
+    PickupItem,
+    Emote,
+    Sit,
+    CastSkill,
+}
+
+impl ReplayCommand {
+    pub fn from_command(command: &Command) -> Self {
+        match command {
+            Command::Stop => Self::Stop,
+            Command::Move(_) => Self::Move,
+            Command::Attack(_) => Self::Attack,
+            Command::Die => Self::Die,
+            Command::PersonalStore => Self::PersonalStore,
+            Command::PickupItem(_) => Self::PickupItem,
+            Command::Emote(_) => Self::Emote,
+            Command::Sit(_) => Self::Sit,
+            Command::CastSkill(_) => Self::CastSkill,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Stop => 0,
+            Self::Move => 1,
+            Self::Attack => 2,
+            Self::Die => 3,
+            Self::PersonalStore => 4,
+            Self::PickupItem => 5,
+            Self::Emote => 6,
+            Self::Sit => 7,
+            Self::CastSkill => 8,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => Self::Stop,
+            1 => Self::Move,
+            2 => Self::Attack,
+            3 => Self::Die,
+            4 => Self::PersonalStore,
+            5 => Self::PickupItem,
+            6 => Self::Emote,
+            7 => Self::Sit,
+            8 => Self::CastSkill,
+            _ => return None,
+        })
+    }
+}
+
+fn entity_type_to_u8(entity_type: ClientEntityType) -> u8 {
+    match entity_type {
+        ClientEntityType::Character => 0,
+        ClientEntityType::Monster => 1,
+        ClientEntityType::Npc => 2,
+        ClientEntityType::ItemDrop => 3,
+    }
+}
+
+fn entity_type_from_u8(value: u8) -> Option<ClientEntityType> {
+    Some(match value {
+        0 => ClientEntityType::Character,
+        1 => ClientEntityType::Monster,
+        2 => ClientEntityType::Npc,
+        3 => ClientEntityType::ItemDrop,
+        _ => return None,
+    })
+}
+
+#[derive(Clone)]
+pub struct ReplayEntity {
+    pub id: ClientEntityId,
+    pub entity_type: ClientEntityType,
+    pub position: Vec3,
+    pub yaw: f32,
+    pub command: ReplayCommand,
+}
+
+pub struct ReplayFrame {
+    pub time: f32,
+    pub entities: Vec<ReplayEntity>,
+}
+
+pub struct ReplayChat {
+    pub time: f32,
+    pub name: Option<String>,
+    pub text: String,
+}
+
+#[derive(Default)]
+pub struct Replay {
+    pub zones: Vec<(f32, ZoneId)>,
+    pub frames: Vec<ReplayFrame>,
+    pub chat: Vec<ReplayChat>,
+}
+
+struct ReplayReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ReplayReader<'a> {
+    fn bytes(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.data.len() < count {
+            return None;
+        }
+        let (bytes, remaining) = self.data.split_at(count);
+        self.data = remaining;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.bytes(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.u16()? as usize;
+        self.bytes(len)
+            .map(|bytes| String::from_utf8_lossy(bytes).to_string())
+    }
+
+    fn record(&mut self, replay: &mut Replay) -> Option<()> {
+        match self.u8()? {
+            REPLAY_RECORD_ZONE => {
+                let time = self.f32()?;
+                let zone_id = ZoneId::new(self.u16()?)?;
+                replay.zones.push((time, zone_id));
+            }
+            REPLAY_RECORD_FRAME => {
+                let time = self.f32()?;
+                let count = self.u16()? as usize;
+                let mut entities = Vec::with_capacity(count);
+                for _ in 0..count {
+                    entities.push(ReplayEntity {
+                        id: ClientEntityId(self.u16()? as usize),
+                        entity_type: entity_type_from_u8(self.u8()?)?,
+                        position: Vec3::new(self.f32()?, self.f32()?, self.f32()?),
+                        yaw: self.f32()?,
+                        command: ReplayCommand::from_u8(self.u8()?)?,
+                    });
+                }
+                replay.frames.push(ReplayFrame { time, entities });
+            }
+            REPLAY_RECORD_CHAT => {
+                let time = self.f32()?;
+                let name = self.string()?;
+                let text = self.string()?;
+                replay.chat.push(ReplayChat {
+                    time,
+                    name: if name.is_empty() { None } else { Some(name) },
+                    text,
+                });
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+impl Replay {
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+
+        if data.len() < 5 || &data[0..4] != REPLAY_MAGIC {
+            anyhow::bail!("Not a replay file");
+        }
+
+        if data[4] != REPLAY_VERSION {
+            anyhow::bail!("Unsupported replay version {}", data[4]);
+        }
+
+        let mut replay = Replay::default();
+        let mut reader = ReplayReader { data: &data[5..] };
+        while !reader.data.is_empty() {
+            // The recorder writes records as it goes, so a replay from a client
+            // which crashed may have a truncated final record which we ignore
+            if reader.record(&mut replay).is_none() {
+                log::warn!("Ignoring truncated or invalid data at end of replay");
+                break;
+            }
+        }
+
+        Ok(replay)
+    }
+
+    pub fn duration(&self) -> f32 {
+        let last_frame = self.frames.last().map_or(0.0, |frame| frame.time);
+        let last_chat = self.chat.last().map_or(0.0, |chat| chat.time);
+        last_frame.max(last_chat)
+    }
+
+    pub fn zone_at(&self, time: f32) -> Option<ZoneId> {
+        self.zones
+            .iter()
+            .take_while(|(zone_time, _)| *zone_time <= time)
+            .last()
+            .or_else(|| self.zones.first())
+            .map(|(_, zone_id)| *zone_id)
+    }
+
+    // Returns the entity states at the given time, interpolated between frames
+    pub fn entities_at(&self, time: f32) -> Vec<ReplayEntity> {
+        let next_index = self.frames.partition_point(|frame| frame.time <= time);
+        if next_index == 0 {
+            return self
+                .frames
+                .first()
+                .map_or_else(Vec::new, |frame| frame.entities.clone());
+        }
+
+        let prev = &self.frames[next_index - 1];
+        let Some(next) = self.frames.get(next_index) else {
+            return prev.entities.clone();
+        };
+
+        let t = ((time - prev.time) / (next.time - prev.time).max(f32::EPSILON)).clamp(0.0, 1.0);
+        let next_entities: HashMap<usize, &ReplayEntity> = next
+            .entities
+            .iter()
+            .map(|entity| (entity.id.0, entity))
+            .collect();
+
+        prev.entities
+            .iter()
+            .map(|entity| {
+                let mut entity = entity.clone();
+                if let Some(next_entity) = next_entities.get(&entity.id.0) {
+                    if next_entity.entity_type == entity.entity_type {
+                        entity.position = entity.position.lerp(next_entity.position, t);
+                    }
+                }
+                entity
+            })
+            .collect()
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    writer: Option<BufWriter<File>>,
+    pub start_time: Option<f32>,
+    pub frame_timer: Timer,
+    pub last_zone_id: Option<ZoneId>,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: &Path) -> Result<Self, std::io::Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(REPLAY_MAGIC)?;
+        writer.write_all(&[REPLAY_VERSION])?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer: Some(writer),
+            start_time: None,
+            frame_timer: Timer::from_seconds(REPLAY_FRAME_INTERVAL, TimerMode::Repeating),
+            last_zone_id: None,
+        })
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        if let Err(error) = writer.write_all(data).and_then(|_| writer.flush()) {
+            log::error!("Stopped recording replay after write error: {}", error);
+            self.writer = None;
+        }
+    }
+
+    pub fn write_zone(&mut self, time: f32, zone_id: ZoneId) {
+        let mut data = vec![REPLAY_RECORD_ZONE];
+        data.extend_from_slice(&time.to_le_bytes());
+        data.extend_from_slice(&zone_id.get().to_le_bytes());
+        self.write(&data);
+    }
+
+    pub fn write_frame(&mut self, time: f32, entities: &[ReplayEntity]) {
+        let count = entities.len().min(u16::MAX as usize);
+        let mut data = Vec::with_capacity(7 + count * 22);
+        data.push(REPLAY_RECORD_FRAME);
+        data.extend_from_slice(&time.to_le_bytes());
+        data.extend_from_slice(&(count as u16).to_le_bytes());
+        for entity in entities.iter().take(count) {
+            data.extend_from_slice(&(entity.id.0 as u16).to_le_bytes());
+            data.push(entity_type_to_u8(entity.entity_type));
+            data.extend_from_slice(&entity.position.x.to_le_bytes());
+            data.extend_from_slice(&entity.position.y.to_le_bytes());
+            data.extend_from_slice(&entity.position.z.to_le_bytes());
+            data.extend_from_slice(&entity.yaw.to_le_bytes());
+            data.push(entity.command.to_u8());
+        }
+        self.write(&data);
+    }
+
+    pub fn write_chat(&mut self, time: f32, name: Option<&str>, text: &str) {
+        let mut data = vec![REPLAY_RECORD_CHAT];
+        data.extend_from_slice(&time.to_le_bytes());
+        for string in [name.unwrap_or(""), text] {
+            let bytes = &string.as_bytes()[..string.len().min(u16::MAX as usize)];
+            data.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+            data.extend_from_slice(bytes);
+        }
+        self.write(&data);
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub time: f32,
+    pub playing: bool,
+    pub speed: f32,
+    pub loaded_zone_id: Option<ZoneId>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            time: 0.0,
+            playing: true,
+            speed: 1.0,
+            loaded_zone_id: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_command_u8_roundtrip() {
+        for value in 0..=u8::MAX {
+            if let Some(command) = ReplayCommand::from_u8(value) {
+                assert_eq!(command.to_u8(), value);
+            }
+        }
+        assert_eq!(ReplayCommand::from_u8(9), None);
+    }
+
+    #[test]
+    fn replay_write_read_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("rose-replay-test-{}.rrpl", std::process::id()));
+        let zone_id = ZoneId::new(2).unwrap();
+
+        let mut recorder = ReplayRecorder::new(&path).unwrap();
+        recorder.write_zone(0.0, zone_id);
+        recorder.write_frame(
+            0.5,
+            &[
+                ReplayEntity {
+                    id: ClientEntityId(7),
+                    entity_type: ClientEntityType::Monster,
+                    position: Vec3::new(5200.0, 5100.0, 12.5),
+                    yaw: 1.5,
+                    command: ReplayCommand::CastSkill,
+                },
+                ReplayEntity {
+                    id: ClientEntityId(8),
+                    entity_type: ClientEntityType::Character,
+                    position: Vec3::new(5210.0, 5090.0, 0.0),
+                    yaw: -0.25,
+                    command: ReplayCommand::Sit,
+                },
+            ],
+        );
+        recorder.write_chat(0.75, Some("Tester"), "Hello");
+        recorder.write_chat(1.0, None, "Announcement");
+        drop(recorder);
+
+        let replay = Replay::read(&path);
+        std::fs::remove_file(&path).ok();
+        let replay = replay.unwrap();
+
+        assert_eq!(replay.zones, vec![(0.0, zone_id)]);
+        assert_eq!(replay.frames.len(), 1);
+        assert_eq!(replay.frames[0].time, 0.5);
+
+        let entities = &replay.frames[0].entities;
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0].id.0, 7);
+        assert_eq!(entities[0].entity_type, ClientEntityType::Monster);
+        assert_eq!(entities[0].position, Vec3::new(5200.0, 5100.0, 12.5));
+        assert_eq!(entities[0].yaw, 1.5);
+        assert_eq!(entities[0].command, ReplayCommand::CastSkill);
+        assert_eq!(entities[1].id.0, 8);
+        assert_eq!(entities[1].entity_type, ClientEntityType::Character);
+        assert_eq!(entities[1].command, ReplayCommand::Sit);
+
+        assert_eq!(replay.chat.len(), 2);
+        assert_eq!(replay.chat[0].name.as_deref(), Some("Tester"));
+        assert_eq!(replay.chat[0].text, "Hello");
+        assert_eq!(replay.chat[1].name, None);
+        assert_eq!(replay.chat[1].text, "Announcement");
+        assert_eq!(replay.duration(), 1.0);
+    }
+}
@@ -1,4 +1,6 @@
-use bevy::prelude::Resource;
+use bevy::prelude::{Resource, Vec2};
+
+use rose_data::ZoneId;
 
 #[derive(Resource)]
 pub struct ServerConfiguration {
@@ -6,8 +8,11 @@ pub struct ServerConfiguration {
     pub port: String,
     pub preset_username: Option<String>,
     pub preset_password: Option<String>,
+    pub preset_password_md5: Option<String>,
     pub preset_server_id: Option<usize>,
     pub preset_channel_id: Option<usize>,
     pub preset_character_name: Option<String>,
+    pub preset_zone_id: Option<ZoneId>,
+    pub preset_position: Option<Vec2>,
     pub auto_login: bool,
 }
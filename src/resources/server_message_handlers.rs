@@ -0,0 +1,420 @@
+use std::time::Instant;
+
+use bevy::prelude::{App, Mut, Resource, World};
+use enum_map::{Enum, EnumMap};
+
+use rose_game_common::messages::server::ServerMessage;
+
+use crate::{protocol::ExtendedServerMessage, resources::ServerMessageMetrics};
+
+pub type ServerMessageHandler = Box<dyn Fn(&ServerMessage, &mut World) + Send + Sync>;
+pub type ExtendedServerMessageHandler =
+    Box<dyn Fn(&ExtendedServerMessage, &mut World) + Send + Sync>;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Enum)]
+pub enum ServerMessageKind {
+    ConnectionRequestSuccess,
+    ConnectionRequestError,
+    CharacterData,
+    CharacterDataItems,
+    CharacterDataQuest,
+    JoinZone,
+    SpawnEntityCharacter,
+    SpawnEntityNpc,
+    SpawnEntityMonster,
+    SpawnEntityItemDrop,
+    MoveEntity,
+    AdjustPosition,
+    StopMoveEntity,
+    AttackEntity,
+    RemoveEntities,
+    DamageEntity,
+    Teleport,
+    LocalChat,
+    ShoutChat,
+    Whisper,
+    AnnounceChat,
+    UpdateAbilityValueAdd,
+    UpdateAbilityValueSet,
+    UpdateAmmo,
+    UpdateEquipment,
+    UpdateVehiclePart,
+    UpdateItemLife,
+    UpdateInventory,
+    UseInventoryItem,
+    UpdateMoney,
+    UpdateBasicStat,
+    UpdateLevel,
+    LevelUpEntity,
+    UpdateSpeed,
+    UpdateStatusEffects,
+    UpdateXpStamina,
+    PickupDropItem,
+    PickupDropMoney,
+    PickupDropError,
+    RewardItems,
+    RewardMoney,
+    QuestDeleteResult,
+    QuestTriggerResult,
+    RunNpcDeathTrigger,
+    SetHotbarSlot,
+    LearnSkillSuccess,
+    LearnSkillError,
+    LevelUpSkillSuccess,
+    LevelUpSkillError,
+    UseEmote,
+    SitToggle,
+    UseItem,
+    CastSkillSelf,
+    CastSkillTargetEntity,
+    CastSkillTargetPosition,
+    CancelCastingSkill,
+    StartCastingSkill,
+    FinishCastingSkill,
+    ApplySkillEffect,
+    NpcStoreTransactionError,
+    PartyCreate,
+    PartyInvite,
+    PartyAcceptCreate,
+    PartyAcceptInvite,
+    PartyRejectInvite,
+    PartyChangeOwner,
+    PartyDelete,
+    PartyMemberList,
+    PartyMemberLeave,
+    PartyMemberDisconnect,
+    PartyMemberKicked,
+    PartyMemberUpdateInfo,
+    PartyMemberRewardItem,
+    PartyUpdateRules,
+    UpdateSkillList,
+    OpenPersonalStore,
+    ClosePersonalStore,
+    PersonalStoreItemList,
+    PersonalStoreTransaction,
+    PersonalStoreTransactionUpdateInventory,
+    BankOpen,
+    BankSetItems,
+    BankUpdateItems,
+    BankTransaction,
+    MoveToggle,
+    ChangeNpcId,
+    ClanInfo,
+    ClanUpdateInfo,
+    CharacterUpdateClan,
+    ClanMemberConnected,
+    ClanMemberDisconnected,
+    ClanCreateError,
+    ClanMemberList,
+    ClanInvited,
+    ClanInviteResult,
+    ClanMemberJoined,
+    ClanMemberLeft,
+    ClanMemberKicked,
+    ClanKicked,
+    ClanDisbanded,
+    CraftInsertGem,
+    CraftInsertGemError,
+    RepairedItemUsingNpc,
+    LogoutSuccess,
+    LogoutFailed,
+    ReturnToCharacterSelect,
+    LoginError,
+    LoginSuccess,
+    ChannelList,
+    ChannelListError,
+    JoinServerError,
+    JoinServerSuccess,
+    CharacterList,
+    CharacterListAppend,
+    CreateCharacterSuccess,
+    CreateCharacterError,
+    SelectCharacterSuccess,
+    SelectCharacterError,
+    DeleteCharacterStart,
+    DeleteCharacterCancel,
+    DeleteCharacterError,
+}
+
+impl ServerMessageKind {
+    pub fn of(message: &ServerMessage) -> Self {
+        match message {
+            ServerMessage::ConnectionRequestSuccess { .. } => Self::ConnectionRequestSuccess,
+            ServerMessage::ConnectionRequestError { .. } => Self::ConnectionRequestError,
+            ServerMessage::CharacterData { .. } => Self::CharacterData,
+            ServerMessage::CharacterDataItems { .. } => Self::CharacterDataItems,
+            ServerMessage::CharacterDataQuest { .. } => Self::CharacterDataQuest,
+            ServerMessage::JoinZone { .. } => Self::JoinZone,
+            ServerMessage::SpawnEntityCharacter { .. } => Self::SpawnEntityCharacter,
+            ServerMessage::SpawnEntityNpc { .. } => Self::SpawnEntityNpc,
+            ServerMessage::SpawnEntityMonster { .. } => Self::SpawnEntityMonster,
+            ServerMessage::SpawnEntityItemDrop { .. } => Self::SpawnEntityItemDrop,
+            ServerMessage::MoveEntity { .. } => Self::MoveEntity,
+            ServerMessage::AdjustPosition { .. } => Self::AdjustPosition,
+            ServerMessage::StopMoveEntity { .. } => Self::StopMoveEntity,
+            ServerMessage::AttackEntity { .. } => Self::AttackEntity,
+            ServerMessage::RemoveEntities { .. } => Self::RemoveEntities,
+            ServerMessage::DamageEntity { .. } => Self::DamageEntity,
+            ServerMessage::Teleport { .. } => Self::Teleport,
+            ServerMessage::LocalChat { .. } => Self::LocalChat,
+            ServerMessage::ShoutChat { .. } => Self::ShoutChat,
+            ServerMessage::Whisper { .. } => Self::Whisper,
+            ServerMessage::AnnounceChat { .. } => Self::AnnounceChat,
+            ServerMessage::UpdateAbilityValueAdd { .. } => Self::UpdateAbilityValueAdd,
+            ServerMessage::UpdateAbilityValueSet { .. } => Self::UpdateAbilityValueSet,
+            ServerMessage::UpdateAmmo { .. } => Self::UpdateAmmo,
+            ServerMessage::UpdateEquipment { .. } => Self::UpdateEquipment,
+            ServerMessage::UpdateVehiclePart { .. } => Self::UpdateVehiclePart,
+            ServerMessage::UpdateItemLife { .. } => Self::UpdateItemLife,
+            ServerMessage::UpdateInventory { .. } => Self::UpdateInventory,
+            ServerMessage::UseInventoryItem { .. } => Self::UseInventoryItem,
+            ServerMessage::UpdateMoney { .. } => Self::UpdateMoney,
+            ServerMessage::UpdateBasicStat { .. } => Self::UpdateBasicStat,
+            ServerMessage::UpdateLevel { .. } => Self::UpdateLevel,
+            ServerMessage::LevelUpEntity { .. } => Self::LevelUpEntity,
+            ServerMessage::UpdateSpeed { .. } => Self::UpdateSpeed,
+            ServerMessage::UpdateStatusEffects { .. } => Self::UpdateStatusEffects,
+            ServerMessage::UpdateXpStamina { .. } => Self::UpdateXpStamina,
+            ServerMessage::PickupDropItem { .. } => Self::PickupDropItem,
+            ServerMessage::PickupDropMoney { .. } => Self::PickupDropMoney,
+            ServerMessage::PickupDropError { .. } => Self::PickupDropError,
+            ServerMessage::RewardItems { .. } => Self::RewardItems,
+            ServerMessage::RewardMoney { .. } => Self::RewardMoney,
+            ServerMessage::QuestDeleteResult { .. } => Self::QuestDeleteResult,
+            ServerMessage::QuestTriggerResult { .. } => Self::QuestTriggerResult,
+            ServerMessage::RunNpcDeathTrigger { .. } => Self::RunNpcDeathTrigger,
+            ServerMessage::SetHotbarSlot { .. } => Self::SetHotbarSlot,
+            ServerMessage::LearnSkillSuccess { .. } => Self::LearnSkillSuccess,
+            ServerMessage::LearnSkillError { .. } => Self::LearnSkillError,
+            ServerMessage::LevelUpSkillSuccess { .. } => Self::LevelUpSkillSuccess,
+            ServerMessage::LevelUpSkillError { .. } => Self::LevelUpSkillError,
+            ServerMessage::UseEmote { .. } => Self::UseEmote,
+            ServerMessage::SitToggle { .. } => Self::SitToggle,
+            ServerMessage::UseItem { .. } => Self::UseItem,
+            ServerMessage::CastSkillSelf { .. } => Self::CastSkillSelf,
+            ServerMessage::CastSkillTargetEntity { .. } => Self::CastSkillTargetEntity,
+            ServerMessage::CastSkillTargetPosition { .. } => Self::CastSkillTargetPosition,
+            ServerMessage::CancelCastingSkill { .. } => Self::CancelCastingSkill,
+            ServerMessage::StartCastingSkill { .. } => Self::StartCastingSkill,
+            ServerMessage::FinishCastingSkill { .. } => Self::FinishCastingSkill,
+            ServerMessage::ApplySkillEffect { .. } => Self::ApplySkillEffect,
+            ServerMessage::NpcStoreTransactionError { .. } => Self::NpcStoreTransactionError,
+            ServerMessage::PartyCreate { .. } => Self::PartyCreate,
+            ServerMessage::PartyInvite { .. } => Self::PartyInvite,
+            ServerMessage::PartyAcceptCreate { .. } => Self::PartyAcceptCreate,
+            ServerMessage::PartyAcceptInvite { .. } => Self::PartyAcceptInvite,
+            ServerMessage::PartyRejectInvite { .. } => Self::PartyRejectInvite,
+            ServerMessage::PartyChangeOwner { .. } => Self::PartyChangeOwner,
+            ServerMessage::PartyDelete => Self::PartyDelete,
+            ServerMessage::PartyMemberList { .. } => Self::PartyMemberList,
+            ServerMessage::PartyMemberLeave { .. } => Self::PartyMemberLeave,
+            ServerMessage::PartyMemberDisconnect { .. } => Self::PartyMemberDisconnect,
+            ServerMessage::PartyMemberKicked { .. } => Self::PartyMemberKicked,
+            ServerMessage::PartyMemberUpdateInfo { .. } => Self::PartyMemberUpdateInfo,
+            ServerMessage::PartyMemberRewardItem { .. } => Self::PartyMemberRewardItem,
+            ServerMessage::PartyUpdateRules { .. } => Self::PartyUpdateRules,
+            ServerMessage::UpdateSkillList { .. } => Self::UpdateSkillList,
+            ServerMessage::OpenPersonalStore { .. } => Self::OpenPersonalStore,
+            ServerMessage::ClosePersonalStore { .. } => Self::ClosePersonalStore,
+            ServerMessage::PersonalStoreItemList { .. } => Self::PersonalStoreItemList,
+            ServerMessage::PersonalStoreTransaction { .. } => Self::PersonalStoreTransaction,
+            ServerMessage::PersonalStoreTransactionUpdateInventory { .. } => {
+                Self::PersonalStoreTransactionUpdateInventory
+            }
+            ServerMessage::BankOpen => Self::BankOpen,
+            ServerMessage::BankSetItems { .. } => Self::BankSetItems,
+            ServerMessage::BankUpdateItems { .. } => Self::BankUpdateItems,
+            ServerMessage::BankTransaction { .. } => Self::BankTransaction,
+            ServerMessage::MoveToggle { .. } => Self::MoveToggle,
+            ServerMessage::ChangeNpcId { .. } => Self::ChangeNpcId,
+            ServerMessage::ClanInfo { .. } => Self::ClanInfo,
+            ServerMessage::ClanUpdateInfo { .. } => Self::ClanUpdateInfo,
+            ServerMessage::CharacterUpdateClan { .. } => Self::CharacterUpdateClan,
+            ServerMessage::ClanMemberConnected { .. } => Self::ClanMemberConnected,
+            ServerMessage::ClanMemberDisconnected { .. } => Self::ClanMemberDisconnected,
+            ServerMessage::ClanCreateError { .. } => Self::ClanCreateError,
+            ServerMessage::ClanMemberList { .. } => Self::ClanMemberList,
+            ServerMessage::ClanInvited { .. } => Self::ClanInvited,
+            ServerMessage::ClanInviteResult { .. } => Self::ClanInviteResult,
+            ServerMessage::ClanMemberJoined { .. } => Self::ClanMemberJoined,
+            ServerMessage::ClanMemberLeft { .. } => Self::ClanMemberLeft,
+            ServerMessage::ClanMemberKicked { .. } => Self::ClanMemberKicked,
+            ServerMessage::ClanKicked => Self::ClanKicked,
+            ServerMessage::ClanDisbanded => Self::ClanDisbanded,
+            ServerMessage::CraftInsertGem { .. } => Self::CraftInsertGem,
+            ServerMessage::CraftInsertGemError { .. } => Self::CraftInsertGemError,
+            ServerMessage::RepairedItemUsingNpc { .. } => Self::RepairedItemUsingNpc,
+            ServerMessage::LogoutSuccess => Self::LogoutSuccess,
+            ServerMessage::LogoutFailed { .. } => Self::LogoutFailed,
+            ServerMessage::ReturnToCharacterSelect => Self::ReturnToCharacterSelect,
+            ServerMessage::LoginError { .. } => Self::LoginError,
+            ServerMessage::LoginSuccess { .. } => Self::LoginSuccess,
+            ServerMessage::ChannelList { .. } => Self::ChannelList,
+            ServerMessage::ChannelListError { .. } => Self::ChannelListError,
+            ServerMessage::JoinServerError { .. } => Self::JoinServerError,
+            ServerMessage::JoinServerSuccess { .. } => Self::JoinServerSuccess,
+            ServerMessage::CharacterList { .. } => Self::CharacterList,
+            ServerMessage::CharacterListAppend { .. } => Self::CharacterListAppend,
+            ServerMessage::CreateCharacterSuccess { .. } => Self::CreateCharacterSuccess,
+            ServerMessage::CreateCharacterError { .. } => Self::CreateCharacterError,
+            ServerMessage::SelectCharacterSuccess { .. } => Self::SelectCharacterSuccess,
+            ServerMessage::SelectCharacterError { .. } => Self::SelectCharacterError,
+            ServerMessage::DeleteCharacterStart { .. } => Self::DeleteCharacterStart,
+            ServerMessage::DeleteCharacterCancel { .. } => Self::DeleteCharacterCancel,
+            ServerMessage::DeleteCharacterError { .. } => Self::DeleteCharacterError,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Enum)]
+pub enum ExtendedServerMessageKind {
+    AccountSettings,
+    MinigameStart,
+    SummonList,
+    PartyMemberTargets,
+    ResurrectionOffer,
+    IdleWarning,
+    ZoneDataChecksums,
+    MonsterSpawned,
+    TradeChat,
+    ClanChat,
+    ClanNotices,
+    ClanWars,
+    ClanMemberLastOnline,
+}
+
+impl ExtendedServerMessageKind {
+    pub fn of(message: &ExtendedServerMessage) -> Self {
+        match message {
+            ExtendedServerMessage::AccountSettings(_) => Self::AccountSettings,
+            ExtendedServerMessage::MinigameStart(_) => Self::MinigameStart,
+            ExtendedServerMessage::SummonList(_) => Self::SummonList,
+            ExtendedServerMessage::PartyMemberTargets(_) => Self::PartyMemberTargets,
+            ExtendedServerMessage::ResurrectionOffer(_) => Self::ResurrectionOffer,
+            ExtendedServerMessage::IdleWarning(_) => Self::IdleWarning,
+            ExtendedServerMessage::ZoneDataChecksums(_) => Self::ZoneDataChecksums,
+            ExtendedServerMessage::MonsterSpawned(_) => Self::MonsterSpawned,
+            ExtendedServerMessage::TradeChat(_) => Self::TradeChat,
+            ExtendedServerMessage::ClanChat(_) => Self::ClanChat,
+            ExtendedServerMessage::ClanNotices(_) => Self::ClanNotices,
+            ExtendedServerMessage::ClanWars(_) => Self::ClanWars,
+            ExtendedServerMessage::ClanMemberLastOnline(_) => Self::ClanMemberLastOnline,
+        }
+    }
+}
+
+// Handlers replace the built in handling in game_connection_system for their
+// kind of message. They run when the commands of that system are applied, in
+// the order the messages were received, and have full access to the world.
+// Extended messages are handled the same way, instead of being sent as an
+// ExtendedServerMessageEvent.
+#[derive(Default, Resource)]
+pub struct ServerMessageHandlers {
+    handlers: EnumMap<ServerMessageKind, Vec<ServerMessageHandler>>,
+    extended_handlers: EnumMap<ExtendedServerMessageKind, Vec<ExtendedServerMessageHandler>>,
+}
+
+impl ServerMessageHandlers {
+    pub fn register(
+        &mut self,
+        kind: ServerMessageKind,
+        handler: impl Fn(&ServerMessage, &mut World) + Send + Sync + 'static,
+    ) {
+        self.handlers[kind].push(Box::new(handler));
+    }
+
+    pub fn contains(&self, kind: ServerMessageKind) -> bool {
+        !self.handlers[kind].is_empty()
+    }
+
+    pub fn handle(&self, kind: ServerMessageKind, message: &ServerMessage, world: &mut World) {
+        for handler in self.handlers[kind].iter() {
+            handler(message, world);
+        }
+    }
+
+    pub fn register_extended(
+        &mut self,
+        kind: ExtendedServerMessageKind,
+        handler: impl Fn(&ExtendedServerMessage, &mut World) + Send + Sync + 'static,
+    ) {
+        self.extended_handlers[kind].push(Box::new(handler));
+    }
+
+    pub fn contains_extended(&self, kind: ExtendedServerMessageKind) -> bool {
+        !self.extended_handlers[kind].is_empty()
+    }
+
+    pub fn handle_extended(
+        &self,
+        kind: ExtendedServerMessageKind,
+        message: &ExtendedServerMessage,
+        world: &mut World,
+    ) {
+        for handler in self.extended_handlers[kind].iter() {
+            handler(message, world);
+        }
+    }
+}
+
+pub fn run_server_message_handlers(world: &mut World, message: ServerMessage) {
+    let kind = ServerMessageKind::of(&message);
+    let start = Instant::now();
+    world.resource_scope(
+        |world, server_message_handlers: Mut<ServerMessageHandlers>| {
+            server_message_handlers.handle(kind, &message, world);
+        },
+    );
+    world
+        .resource_mut::<ServerMessageMetrics>()
+        .record(kind, start.elapsed());
+}
+
+pub fn run_extended_server_message_handlers(world: &mut World, message: ExtendedServerMessage) {
+    let kind = ExtendedServerMessageKind::of(&message);
+    world.resource_scope(
+        |world, server_message_handlers: Mut<ServerMessageHandlers>| {
+            server_message_handlers.handle_extended(kind, &message, world);
+        },
+    );
+}
+
+pub trait ServerMessageHandlerAppExt {
+    fn add_server_message_handler(
+        &mut self,
+        kind: ServerMessageKind,
+        handler: impl Fn(&ServerMessage, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    fn add_extended_server_message_handler(
+        &mut self,
+        kind: ExtendedServerMessageKind,
+        handler: impl Fn(&ExtendedServerMessage, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl ServerMessageHandlerAppExt for App {
+    fn add_server_message_handler(
+        &mut self,
+        kind: ServerMessageKind,
+        handler: impl Fn(&ServerMessage, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.init_resource::<ServerMessageHandlers>();
+        self.world
+            .resource_mut::<ServerMessageHandlers>()
+            .register(kind, handler);
+        self
+    }
+
+    fn add_extended_server_message_handler(
+        &mut self,
+        kind: ExtendedServerMessageKind,
+        handler: impl Fn(&ExtendedServerMessage, &mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.init_resource::<ServerMessageHandlers>();
+        self.world
+            .resource_mut::<ServerMessageHandlers>()
+            .register_extended(kind, handler);
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::Resource;
+use enum_map::EnumMap;
+
+use crate::resources::ServerMessageKind;
+
+#[derive(Default)]
+pub struct ServerMessageMetric {
+    pub count: u64,
+    pub total_time: Duration,
+    pub max_time: Duration,
+}
+
+impl ServerMessageMetric {
+    pub fn average_time(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total_time / self.count as u32
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct ServerMessageMetrics {
+    pub messages: EnumMap<ServerMessageKind, ServerMessageMetric>,
+}
+
+impl ServerMessageMetrics {
+    pub fn start_timer(&mut self, kind: ServerMessageKind) -> ServerMessageTimer<'_> {
+        let metric = &mut self.messages[kind];
+        metric.count += 1;
+
+        ServerMessageTimer {
+            metric,
+            start: Instant::now(),
+        }
+    }
+
+    // Records a message which was timed elsewhere, such as by a registered handler
+    pub fn record(&mut self, kind: ServerMessageKind, time: Duration) {
+        let metric = &mut self.messages[kind];
+        metric.count += 1;
+        metric.total_time += time;
+        metric.max_time = metric.max_time.max(time);
+    }
+
+    pub fn clear(&mut self) {
+        self.messages = Default::default();
+    }
+}
+
+// Records the handling time when dropped, so messages which are dropped early
+// with a continue, such as chat from ignored players, are still timed
+pub struct ServerMessageTimer<'a> {
+    metric: &'a mut ServerMessageMetric,
+    start: Instant,
+}
+
+impl Drop for ServerMessageTimer<'_> {
+    fn drop(&mut self) {
+        let time = self.start.elapsed();
+        self.metric.total_time += time;
+        self.metric.max_time = self.metric.max_time.max(time);
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use bevy::{
+    prelude::{Assets, Handle, Resource},
+    render::mesh::skinning::SkinnedMeshInverseBindposes,
+    tasks::AsyncComputeTaskPool,
+    utils::HashMap,
+};
+use crossbeam_channel::{Receiver, Sender};
+
+use rose_file_readers::{VirtualFilesystem, ZmdFile};
+
+use crate::model_loader::SkeletonBindPose;
+
+#[derive(Clone)]
+pub struct CachedSkeleton {
+    pub bind_pose: Arc<SkeletonBindPose>,
+    pub inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+}
+
+#[derive(Clone)]
+pub enum SkeletonCacheState {
+    Loading,
+    Loaded(CachedSkeleton),
+    Failed,
+}
+
+#[derive(Resource)]
+pub struct SkeletonCache {
+    vfs: Arc<VirtualFilesystem>,
+    skeletons: HashMap<String, SkeletonCacheState>,
+    loaded_tx: Sender<(String, Option<SkeletonBindPose>)>,
+    loaded_rx: Receiver<(String, Option<SkeletonBindPose>)>,
+}
+
+impl SkeletonCache {
+    pub fn new(vfs: Arc<VirtualFilesystem>) -> Self {
+        let (loaded_tx, loaded_rx) = crossbeam_channel::unbounded();
+        Self {
+            vfs,
+            skeletons: HashMap::default(),
+            loaded_tx,
+            loaded_rx,
+        }
+    }
+
+    // Returns the skeleton if it has been loaded, otherwise starts loading it
+    // on a background task so the caller can try again on a later frame.
+    pub fn get(&mut self, path: &str) -> SkeletonCacheState {
+        if let Some(state) = self.skeletons.get(path) {
+            return state.clone();
+        }
+
+        let vfs = self.vfs.clone();
+        let loaded_tx = self.loaded_tx.clone();
+        let task_path = path.to_string();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                let bind_pose = match vfs.read_file::<ZmdFile, _>(task_path.as_str()) {
+                    Ok(skeleton) => Some(SkeletonBindPose::new(&skeleton)),
+                    Err(error) => {
+                        log::warn!("Failed to load skeleton {} with error {}", task_path, error);
+                        None
+                    }
+                };
+                loaded_tx.send((task_path, bind_pose)).ok();
+            })
+            .detach();
+
+        self.skeletons
+            .insert(path.to_string(), SkeletonCacheState::Loading);
+        SkeletonCacheState::Loading
+    }
+
+    pub fn update(
+        &mut self,
+        skinned_mesh_inverse_bindposes_assets: &mut Assets<SkinnedMeshInverseBindposes>,
+    ) {
+        while let Ok((path, bind_pose)) = self.loaded_rx.try_recv() {
+            let state = if let Some(bind_pose) = bind_pose {
+                SkeletonCacheState::Loaded(CachedSkeleton {
+                    inverse_bindposes: skinned_mesh_inverse_bindposes_assets.add(
+                        SkinnedMeshInverseBindposes::from(bind_pose.inverse_bindposes.clone()),
+                    ),
+                    bind_pose: Arc::new(bind_pose),
+                })
+            } else {
+                SkeletonCacheState::Failed
+            };
+            self.skeletons.insert(path, state);
+        }
+    }
+}
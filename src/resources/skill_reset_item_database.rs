@@ -0,0 +1,65 @@
+use serde::Deserialize;
+
+use rose_data::{ItemReference, ItemType};
+use rose_file_readers::{VfsFile, VirtualFilesystem};
+use rose_game_common::components::{Inventory, ItemSlot};
+
+// Skill reset items are handled by the server and not marked in the irose
+// data files, servers with them can list them in this file and ship it with a mod
+const SKILL_RESET_ITEM_DATABASE_PATH: &str = "3DDATA/STB/SKILL_RESET_ITEMS.TOML";
+
+#[derive(Default, Deserialize)]
+struct SkillResetItemDatabaseConfig {
+    #[serde(default)]
+    consumables: Vec<usize>,
+}
+
+#[derive(Default)]
+pub struct SkillResetItemDatabase {
+    items: Vec<ItemReference>,
+}
+
+impl SkillResetItemDatabase {
+    pub fn load(vfs: &VirtualFilesystem) -> Self {
+        let Ok(file) = vfs.open_file(SKILL_RESET_ITEM_DATABASE_PATH) else {
+            return Self::default();
+        };
+        let bytes: Vec<u8> = match file {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+
+        let config = match std::str::from_utf8(&bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(toml::from_str::<SkillResetItemDatabaseConfig>(text)?))
+        {
+            Ok(config) => config,
+            Err(error) => {
+                log::warn!(
+                    "Failed to load {}: {}",
+                    SKILL_RESET_ITEM_DATABASE_PATH,
+                    error
+                );
+                return Self::default();
+            }
+        };
+
+        Self {
+            items: config
+                .consumables
+                .into_iter()
+                .map(|item_number| ItemReference::new(ItemType::Consumable, item_number))
+                .collect(),
+        }
+    }
+
+    pub fn has_reset_item(&self, inventory: &Inventory) -> bool {
+        self.find_reset_item(inventory).is_some()
+    }
+
+    pub fn find_reset_item(&self, inventory: &Inventory) -> Option<ItemSlot> {
+        self.items
+            .iter()
+            .find_map(|item| inventory.find_item(*item))
+    }
+}
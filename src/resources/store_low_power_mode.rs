@@ -0,0 +1,43 @@
+use bevy::{
+    prelude::{Entity, Res, Resource, Visibility},
+    winit::UpdateMode,
+};
+
+// Number of recent sales kept for the status widget
+const MAX_RECENT_SALES: usize = 20;
+
+#[derive(Default, Resource)]
+pub struct StoreLowPowerMode {
+    // Requested by the player from the shop window
+    pub enabled: bool,
+
+    // Set while the low power profile is applied
+    pub active: bool,
+
+    pub recent_sales: Vec<String>,
+    pub total_sales: i64,
+
+    // State to restore when leaving low power mode
+    pub saved_window_size: Option<(f32, f32)>,
+    pub saved_update_modes: Option<(UpdateMode, UpdateMode)>,
+    pub saved_shadows_enabled: Vec<(Entity, bool)>,
+    pub saved_effect_visibility: Vec<(Entity, Visibility)>,
+}
+
+impl StoreLowPowerMode {
+    pub fn record_sale(&mut self, message: String, price: i64) {
+        if !self.active {
+            return;
+        }
+
+        self.total_sales += price;
+        self.recent_sales.push(message);
+        if self.recent_sales.len() > MAX_RECENT_SALES {
+            self.recent_sales.remove(0);
+        }
+    }
+}
+
+pub fn store_low_power_mode_inactive(store_low_power_mode: Res<StoreLowPowerMode>) -> bool {
+    !store_low_power_mode.active
+}
@@ -0,0 +1,122 @@
+use bevy::{
+    asset::{AssetPath, HandleId},
+    prelude::{Image, Resource, Timer, TimerMode},
+    render::render_resource::TextureDimension,
+    utils::HashMap,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextureStreamingState {
+    Reduced,
+    Reloading,
+}
+
+pub struct TextureStreamingImage {
+    pub path: AssetPath<'static>,
+    pub state: TextureStreamingState,
+    pub full_size: usize,
+    pub reduced_size: usize,
+}
+
+#[derive(Resource)]
+pub struct TextureStreaming {
+    // Budget in bytes for all loaded textures, streaming is disabled when None
+    pub budget: Option<usize>,
+
+    // Textures are only reduced while over budget, reduced textures used by visible
+    // materials within this distance are restored once they fit in the budget again
+    pub high_detail_distance: f32,
+
+    // Number of mip levels removed from textures which are not needed in high detail
+    pub dropped_mip_levels: u32,
+
+    // Limit the number of textures reloaded each update to avoid a hitch
+    pub max_reloads_per_update: usize,
+
+    pub show_overlay: bool,
+    pub update_timer: Timer,
+
+    // Textures which currently have their largest mip levels removed
+    pub images: HashMap<HandleId, TextureStreamingImage>,
+
+    // Updated by texture_streaming_system for the overlay
+    pub total_bytes: usize,
+    pub saved_bytes: usize,
+}
+
+impl TextureStreaming {
+    pub fn new(budget: Option<usize>, high_detail_distance: f32) -> Self {
+        Self {
+            budget,
+            high_detail_distance,
+            dropped_mip_levels: 2,
+            max_reloads_per_update: 8,
+            show_overlay: false,
+            update_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            images: HashMap::default(),
+            total_bytes: 0,
+            saved_bytes: 0,
+        }
+    }
+
+    pub fn num_reduced(&self) -> usize {
+        self.images
+            .values()
+            .filter(|image| image.state == TextureStreamingState::Reduced)
+            .count()
+    }
+}
+
+fn mip_level_size(image: &Image, level: u32) -> Option<usize> {
+    let format = image.texture_descriptor.format;
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_size(None)? as usize;
+    let width = (image.texture_descriptor.size.width >> level).max(1);
+    let height = (image.texture_descriptor.size.height >> level).max(1);
+    let blocks_x = ((width + block_width - 1) / block_width) as usize;
+    let blocks_y = ((height + block_height - 1) / block_height) as usize;
+    Some(blocks_x * blocks_y * block_size)
+}
+
+// Removes up to count of the largest mip levels from a 2D texture, the
+// remaining levels are already stored in image.data so this needs no decoding.
+// Returns the number of levels removed.
+pub fn drop_image_mip_levels(image: &mut Image, count: u32) -> u32 {
+    let descriptor = &image.texture_descriptor;
+    if descriptor.dimension != TextureDimension::D2 || descriptor.size.depth_or_array_layers != 1 {
+        return 0;
+    }
+
+    // Compressed textures must remain a multiple of the block size
+    let (block_width, block_height) = descriptor.format.block_dimensions();
+    let mut count = count.min(descriptor.mip_level_count.saturating_sub(1));
+    while count > 0
+        && ((descriptor.size.width >> count) % block_width != 0
+            || (descriptor.size.height >> count) % block_height != 0)
+    {
+        count -= 1;
+    }
+
+    if count == 0 {
+        return 0;
+    }
+
+    let mut offset = 0;
+    for level in 0..count {
+        let Some(size) = mip_level_size(image, level) else {
+            return 0;
+        };
+        offset += size;
+    }
+
+    if offset >= image.data.len() {
+        return 0;
+    }
+
+    image.data.drain(..offset);
+    image.data.shrink_to_fit();
+    image.texture_descriptor.size.width >>= count;
+    image.texture_descriptor.size.height >>= count;
+    image.texture_descriptor.mip_level_count -= count;
+    count
+}
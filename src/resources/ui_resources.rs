@@ -39,6 +39,67 @@ impl UiSprite {
         mesh.add_rect_with_uv(rect, self.uv, egui::Color32::WHITE);
         ui.painter().add(egui::epaint::Shape::mesh(mesh));
     }
+
+    // Splits the sprite into a 3x3 grid, the corners keep their size while the
+    // edges and centre stretch to fill rect
+    pub fn nine_slice(&self, rect: egui::Rect, border: egui::Margin) -> egui::Shape {
+        let left = border.left.min(rect.width() / 2.0);
+        let right = border.right.min(rect.width() / 2.0);
+        let top = border.top.min(rect.height() / 2.0);
+        let bottom = border.bottom.min(rect.height() / 2.0);
+        let uv_per_pixel = egui::vec2(
+            self.uv.width() / self.width.max(1.0),
+            self.uv.height() / self.height.max(1.0),
+        );
+
+        let xs = [
+            rect.min.x,
+            rect.min.x + left,
+            rect.max.x - right,
+            rect.max.x,
+        ];
+        let ys = [
+            rect.min.y,
+            rect.min.y + top,
+            rect.max.y - bottom,
+            rect.max.y,
+        ];
+        let us = [
+            self.uv.min.x,
+            self.uv.min.x + left * uv_per_pixel.x,
+            self.uv.max.x - right * uv_per_pixel.x,
+            self.uv.max.x,
+        ];
+        let vs = [
+            self.uv.min.y,
+            self.uv.min.y + top * uv_per_pixel.y,
+            self.uv.max.y - bottom * uv_per_pixel.y,
+            self.uv.max.y,
+        ];
+
+        let mut mesh = egui::epaint::Mesh::with_texture(self.texture_id);
+        for row in 0..3 {
+            for column in 0..3 {
+                let slice_rect = egui::Rect::from_min_max(
+                    egui::pos2(xs[column], ys[row]),
+                    egui::pos2(xs[column + 1], ys[row + 1]),
+                );
+                if slice_rect.width() <= 0.0 || slice_rect.height() <= 0.0 {
+                    continue;
+                }
+
+                mesh.add_rect_with_uv(
+                    slice_rect,
+                    egui::Rect::from_min_max(
+                        egui::pos2(us[column], vs[row]),
+                        egui::pos2(us[column + 1], vs[row + 1]),
+                    ),
+                    egui::Color32::WHITE,
+                );
+            }
+        }
+        egui::epaint::Shape::mesh(mesh)
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Enum)]
@@ -125,6 +186,7 @@ pub struct UiResources {
     pub dialog_party_option: Handle<Dialog>,
     pub dialog_personal_store: Handle<Dialog>,
     pub dialog_player_info: Handle<Dialog>,
+    pub dialog_private_store: Handle<Dialog>,
     pub dialog_quest_list: Handle<Dialog>,
     pub dialog_respawn: Handle<Dialog>,
     pub dialog_select_server: Handle<Dialog>,
@@ -393,6 +455,63 @@ pub fn update_ui_resources(
     ui_resources.loaded_all_textures = loaded_all;
 }
 
+pub const UI_DIALOG_FILENAMES: [&str; 54] = [
+    "DELIVERYSTORE.XML",
+    "DLGADDFRIEND.XML",
+    "DLGAVATA.XML",
+    "DLGAVATARSTORE.XML",
+    "DLGBANK.XML",
+    "DLGCHAT.XML",
+    "DLGCHATFILTER.XML",
+    "DLGCHATROOM.XML",
+    "DLGCLAN.XML",
+    "DLGCLANREGNOTICE.XML",
+    "DLGCOMM.XML",
+    "DLGCREATEAVATAR.XML",
+    "DLGDEAL.XML",
+    "DLGDIALOG.XML",
+    "DLGDIALOGEVENT.XML",
+    "DLGEXCHANGE.XML",
+    "DLGGOODS.XML",
+    "DLGHELP.XML",
+    "DLGINFO.XML",
+    "DLGINPUTNAME.XML",
+    "DLGITEM.XML",
+    "DLGLOGIN.XML",
+    "DLGMAKE.XML",
+    "DLGMEMO.XML",
+    "DLGMEMOVIEW.XML",
+    "DLGMENU.XML",
+    "DLGMINIMAP.XML",
+    "DLGNINPUT.XML",
+    "DLGNOTIFY.XML",
+    "DLGOPTION.XML",
+    "DLGORGANIZECLAN.XML",
+    "DLGPARTY.XML",
+    "DLGPARTYOPTION.XML",
+    "DLGPRIVATECHAT.XML",
+    "DLGPRIVATESTORE.XML",
+    "DLGQUEST.XML",
+    "DLGQUICKBAR.XML",
+    "DLGRESTART.XML",
+    "DLGSELAVATAR.XML",
+    "DLGSELECTEVENT.XML",
+    "DLGSELONLYSVR.XML",
+    "DLGSELSVR.XML",
+    "DLGSEPARATE.XML",
+    "DLGSKILL.XML",
+    "DLGSKILLTREE.XML",
+    "DLGSTORE.XML",
+    "DLGSYSTEM.XML",
+    "DLGSYSTEMMSG.XML",
+    "DLGUPGRADE.XML",
+    "MSGBOX.XML",
+    "SKILLTREE_DEALER.XML",
+    "SKILLTREE_HOWKER.XML",
+    "SKILLTREE_MUSE.XML",
+    "SKILLTREE_SOLDIER.XML",
+];
+
 pub fn load_ui_resources(
     mut commands: Commands,
     vfs_resource: Res<VfsResource>,
@@ -401,65 +520,8 @@ pub fn load_ui_resources(
 ) {
     let vfs = &vfs_resource.vfs;
 
-    let dialog_filenames = [
-        "DELIVERYSTORE.XML",
-        "DLGADDFRIEND.XML",
-        "DLGAVATA.XML",
-        "DLGAVATARSTORE.XML",
-        "DLGBANK.XML",
-        "DLGCHAT.XML",
-        "DLGCHATFILTER.XML",
-        "DLGCHATROOM.XML",
-        "DLGCLAN.XML",
-        "DLGCLANREGNOTICE.XML",
-        "DLGCOMM.XML",
-        "DLGCREATEAVATAR.XML",
-        "DLGDEAL.XML",
-        "DLGDIALOG.XML",
-        "DLGDIALOGEVENT.XML",
-        "DLGEXCHANGE.XML",
-        "DLGGOODS.XML",
-        "DLGHELP.XML",
-        "DLGINFO.XML",
-        "DLGINPUTNAME.XML",
-        "DLGITEM.XML",
-        "DLGLOGIN.XML",
-        "DLGMAKE.XML",
-        "DLGMEMO.XML",
-        "DLGMEMOVIEW.XML",
-        "DLGMENU.XML",
-        "DLGMINIMAP.XML",
-        "DLGNINPUT.XML",
-        "DLGNOTIFY.XML",
-        "DLGOPTION.XML",
-        "DLGORGANIZECLAN.XML",
-        "DLGPARTY.XML",
-        "DLGPARTYOPTION.XML",
-        "DLGPRIVATECHAT.XML",
-        "DLGPRIVATESTORE.XML",
-        "DLGQUEST.XML",
-        "DLGQUICKBAR.XML",
-        "DLGRESTART.XML",
-        "DLGSELAVATAR.XML",
-        "DLGSELECTEVENT.XML",
-        "DLGSELONLYSVR.XML",
-        "DLGSELSVR.XML",
-        "DLGSEPARATE.XML",
-        "DLGSKILL.XML",
-        "DLGSKILLTREE.XML",
-        "DLGSTORE.XML",
-        "DLGSYSTEM.XML",
-        "DLGSYSTEMMSG.XML",
-        "DLGUPGRADE.XML",
-        "MSGBOX.XML",
-        "SKILLTREE_DEALER.XML",
-        "SKILLTREE_HOWKER.XML",
-        "SKILLTREE_MUSE.XML",
-        "SKILLTREE_SOLDIER.XML",
-    ];
-
     let mut dialog_files = HashMap::new();
-    for filename in dialog_filenames {
+    for filename in UI_DIALOG_FILENAMES {
         dialog_files.insert(
             filename.to_string(),
             asset_server.load(format!("3DDATA/CONTROL/XML/{}", filename)),
@@ -537,6 +599,7 @@ pub fn load_ui_resources(
         dialog_party_option: dialog_files["DLGPARTYOPTION.XML"].clone(),
         dialog_personal_store: dialog_files["DLGAVATARSTORE.XML"].clone(),
         dialog_player_info: dialog_files["DLGINFO.XML"].clone(),
+        dialog_private_store: dialog_files["DLGPRIVATESTORE.XML"].clone(),
         dialog_quest_list: dialog_files["DLGQUEST.XML"].clone(),
         dialog_respawn: dialog_files["DLGRESTART.XML"].clone(),
         dialog_select_server: dialog_files["DLGSELSVR.XML"].clone(),
@@ -590,7 +653,7 @@ pub fn ui_requested_cursor_apply_system(
             window.cursor.icon = requested_icon.clone();
         }
     } else {
-        let world_cursor = if matches!(window.cursor.grab_mode, CursorGrabMode::None) {
+        let world_cursor = if !matches!(window.cursor.grab_mode, CursorGrabMode::Locked) {
             ui_resources.cursors[ui_requested_cursor.world_cursor]
                 .cursor
                 .as_ref()
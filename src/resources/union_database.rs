@@ -0,0 +1,157 @@
+use serde::Deserialize;
+
+use rose_data::ItemReference;
+use rose_file_readers::{VfsFile, VirtualFilesystem};
+
+use crate::resources::item_set_database::parse_item_type;
+
+// Union names, ranks and rewards are server side in irose, servers can
+// describe them in this file and ship it with a mod
+const UNION_DATABASE_PATH: &str = "3DDATA/STB/UNIONS.TOML";
+
+pub const NUM_UNIONS: usize = 10;
+
+#[derive(Deserialize)]
+struct UnionRankConfig {
+    name: String,
+    points: u32,
+}
+
+#[derive(Deserialize)]
+struct UnionRewardConfig {
+    item_type: String,
+    item_number: usize,
+    points: u32,
+}
+
+#[derive(Deserialize)]
+struct UnionConfig {
+    id: usize,
+    name: String,
+    #[serde(default)]
+    ranks: Vec<UnionRankConfig>,
+    #[serde(default)]
+    rewards: Vec<UnionRewardConfig>,
+}
+
+#[derive(Default, Deserialize)]
+struct UnionDatabaseConfig {
+    #[serde(default)]
+    unions: Vec<UnionConfig>,
+}
+
+pub struct UnionRank {
+    pub name: String,
+    pub required_points: u32,
+}
+
+pub struct UnionReward {
+    pub item: ItemReference,
+    pub points: u32,
+}
+
+pub struct UnionData {
+    pub id: usize,
+    pub name: String,
+    pub ranks: Vec<UnionRank>,
+    pub rewards: Vec<UnionReward>,
+}
+
+impl UnionData {
+    pub fn get_rank(&self, points: u32) -> Option<&UnionRank> {
+        self.ranks
+            .iter()
+            .rev()
+            .find(|rank| points >= rank.required_points)
+    }
+
+    pub fn get_next_rank(&self, points: u32) -> Option<&UnionRank> {
+        self.ranks.iter().find(|rank| points < rank.required_points)
+    }
+}
+
+#[derive(Default)]
+pub struct UnionDatabase {
+    unions: Vec<UnionData>,
+}
+
+impl UnionDatabase {
+    pub fn load(vfs: &VirtualFilesystem) -> Self {
+        let Ok(file) = vfs.open_file(UNION_DATABASE_PATH) else {
+            return Self::default();
+        };
+        let bytes: Vec<u8> = match file {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+
+        let config = match std::str::from_utf8(&bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(toml::from_str::<UnionDatabaseConfig>(text)?))
+        {
+            Ok(config) => config,
+            Err(error) => {
+                log::warn!("Failed to load {}: {}", UNION_DATABASE_PATH, error);
+                return Self::default();
+            }
+        };
+
+        let unions = config
+            .unions
+            .into_iter()
+            .filter(|union| {
+                let valid = (1..=NUM_UNIONS).contains(&union.id);
+                if !valid {
+                    log::warn!("Invalid union id {} for union {}", union.id, union.name);
+                }
+                valid
+            })
+            .map(|union| {
+                let mut ranks = union
+                    .ranks
+                    .into_iter()
+                    .map(|rank| UnionRank {
+                        name: rank.name,
+                        required_points: rank.points,
+                    })
+                    .collect::<Vec<_>>();
+                ranks.sort_by_key(|rank| rank.required_points);
+
+                UnionData {
+                    rewards: union
+                        .rewards
+                        .iter()
+                        .filter_map(|reward| {
+                            let item_type = parse_item_type(&reward.item_type);
+                            if item_type.is_none() {
+                                log::warn!(
+                                    "Unknown item type {} in union {} rewards",
+                                    reward.item_type,
+                                    union.name
+                                );
+                            }
+                            Some(UnionReward {
+                                item: ItemReference::new(item_type?, reward.item_number),
+                                points: reward.points,
+                            })
+                        })
+                        .collect(),
+                    id: union.id,
+                    name: union.name,
+                    ranks,
+                }
+            })
+            .collect();
+
+        Self { unions }
+    }
+
+    pub fn get_union(&self, id: usize) -> Option<&UnionData> {
+        self.unions.iter().find(|union| union.id == id)
+    }
+
+    pub fn get_union_name(&self, id: usize) -> String {
+        self.get_union(id)
+            .map_or_else(|| format!("Union {}", id), |union| union.name.clone())
+    }
+}
@@ -0,0 +1,46 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::Resource;
+
+use crate::protocol::UnsupportedPacket;
+
+const MAX_UNSUPPORTED_PACKETS: usize = 100;
+
+// Collects unsupported packets from every protocol client, only the most
+// recent packets are kept
+#[derive(Resource)]
+pub struct UnsupportedPackets {
+    pub packet_tx: crossbeam_channel::Sender<UnsupportedPacket>,
+    packet_rx: crossbeam_channel::Receiver<UnsupportedPacket>,
+    pub packets: VecDeque<UnsupportedPacket>,
+    pub total_count: usize,
+}
+
+impl Default for UnsupportedPackets {
+    fn default() -> Self {
+        let (packet_tx, packet_rx) = crossbeam_channel::unbounded();
+        Self {
+            packet_tx,
+            packet_rx,
+            packets: VecDeque::new(),
+            total_count: 0,
+        }
+    }
+}
+
+impl UnsupportedPackets {
+    pub fn update(&mut self) {
+        while let Ok(packet) = self.packet_rx.try_recv() {
+            if self.packets.len() == MAX_UNSUPPORTED_PACKETS {
+                self.packets.pop_front();
+            }
+            self.packets.push_back(packet);
+            self.total_count += 1;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.packets.clear();
+        self.total_count = 0;
+    }
+}
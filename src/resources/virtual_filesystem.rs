@@ -1,9 +1,37 @@
-use bevy::prelude::Resource;
-use std::sync::Arc;
+use bevy::{
+    prelude::Resource,
+    time::{Timer, TimerMode},
+};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::SystemTime};
 
 use rose_file_readers::VirtualFilesystem;
 
+use crate::vfs_mods::VfsModLayer;
+
 #[derive(Resource)]
 pub struct VfsResource {
     pub vfs: Arc<VirtualFilesystem>,
 }
+
+#[derive(Resource)]
+pub struct VfsModHotReload {
+    pub layers: Vec<VfsModLayer>,
+    pub file_modified: HashMap<PathBuf, Option<SystemTime>>,
+    pub scan_timer: Timer,
+}
+
+impl VfsModHotReload {
+    pub fn new(layers: Vec<VfsModLayer>) -> Self {
+        let file_modified = layers
+            .iter()
+            .flat_map(|layer| layer.list_files())
+            .map(|file| (file.host_path, file.modified))
+            .collect();
+
+        Self {
+            layers,
+            file_modified,
+            scan_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+        }
+    }
+}
@@ -4,16 +4,24 @@ use rose_game_common::{
     messages::{client::ClientMessage, server::ServerMessage},
 };
 
+use crate::protocol::{CharacterSlotInfo, LoginQueueStatus};
+
 #[derive(Resource)]
 pub struct WorldConnection {
     pub client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
     pub server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+    pub login_queue_rx: crossbeam_channel::Receiver<LoginQueueStatus>,
+    pub character_slots_rx: crossbeam_channel::Receiver<CharacterSlotInfo>,
+    pub purchase_character_slot_tx: tokio::sync::mpsc::UnboundedSender<usize>,
 }
 
 impl WorldConnection {
     pub fn new(
         client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
         server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
+        login_queue_rx: crossbeam_channel::Receiver<LoginQueueStatus>,
+        character_slots_rx: crossbeam_channel::Receiver<CharacterSlotInfo>,
+        purchase_character_slot_tx: tokio::sync::mpsc::UnboundedSender<usize>,
         login_token: u32,
         password: Password,
     ) -> Self {
@@ -27,6 +35,9 @@ impl WorldConnection {
         Self {
             client_message_tx,
             server_message_rx,
+            login_queue_rx,
+            character_slots_rx,
+            purchase_character_slot_tx,
         }
     }
 }
@@ -0,0 +1,13 @@
+use bevy::prelude::Resource;
+
+use rose_data::ZoneId;
+
+use crate::data_integrity::ZoneDataIssue;
+
+// Differences between our data files and the server's for the current zone,
+// shown as a warning until dismissed or the zone changes
+#[derive(Default, Resource)]
+pub struct ZoneDataVerification {
+    pub zone_id: Option<ZoneId>,
+    pub issues: Vec<ZoneDataIssue>,
+}
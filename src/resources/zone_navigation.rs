@@ -0,0 +1,327 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bevy::{
+    math::{Vec2, Vec3},
+    prelude::Resource,
+    tasks::AsyncComputeTaskPool,
+};
+use crossbeam_channel::{Receiver, Sender};
+
+use rose_data::ZoneId;
+
+const NAVIGATION_GRID_MAGIC: &[u8; 4] = b"RNAV";
+const NAVIGATION_GRID_VERSION: u8 = 1;
+
+// Each navigation cell covers one quad of the terrain heightmap
+pub const NAVIGATION_CELL_SIZE: f32 = 2.5;
+
+// Maximum rise over run across a cell which can still be walked on
+const NAVIGATION_MAX_SLOPE: f32 = 1.0;
+
+// Obstacles are ignored when they are low enough to step over, or high enough to walk under
+const NAVIGATION_STEP_HEIGHT: f32 = 0.5;
+const NAVIGATION_CHARACTER_HEIGHT: f32 = 1.8;
+
+// Heights in metres of one terrain block, copied from the zone so the grid can
+// be generated without access to the zone asset
+pub struct NavigationHeightmap {
+    pub block_x: usize,
+    pub block_y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub heights: Vec<f32>,
+}
+
+pub struct NavigationGridInput {
+    pub zone_id: ZoneId,
+    pub heightmaps: Vec<NavigationHeightmap>,
+
+    // World space bounding boxes of zone objects with collision
+    pub obstacles: Vec<(Vec3, Vec3)>,
+}
+
+impl NavigationGridInput {
+    pub fn checksum(&self) -> String {
+        let mut context = md5::Context::new();
+        context.consume([NAVIGATION_GRID_VERSION]);
+        context.consume(self.zone_id.get().to_le_bytes());
+
+        for heightmap in self.heightmaps.iter() {
+            context.consume((heightmap.block_x as u32).to_le_bytes());
+            context.consume((heightmap.block_y as u32).to_le_bytes());
+            context.consume((heightmap.width as u32).to_le_bytes());
+            context.consume((heightmap.height as u32).to_le_bytes());
+            for height in heightmap.heights.iter() {
+                context.consume(height.to_le_bytes());
+            }
+        }
+
+        for (min, max) in self.obstacles.iter() {
+            for value in min.to_array().iter().chain(max.to_array().iter()) {
+                context.consume(value.to_le_bytes());
+            }
+        }
+
+        format!("{:x}", context.compute())
+    }
+}
+
+pub struct NavigationGrid {
+    pub zone_id: ZoneId,
+
+    // World position of the minimum corner of cell (0, 0), cells extend along +x and +z
+    pub origin: Vec2,
+    pub width: usize,
+    pub height: usize,
+    walkable: Vec<bool>,
+}
+
+impl NavigationGrid {
+    pub fn generate(input: &NavigationGridInput) -> Option<Self> {
+        let cells_per_block = input.heightmaps.first()?.width.checked_sub(1)?;
+        let min_block_x = input.heightmaps.iter().map(|h| h.block_x).min()?;
+        let max_block_x = input.heightmaps.iter().map(|h| h.block_x).max()?;
+        let min_block_y = input.heightmaps.iter().map(|h| h.block_y).min()?;
+        let max_block_y = input.heightmaps.iter().map(|h| h.block_y).max()?;
+
+        let width = (max_block_x - min_block_x + 1) * cells_per_block;
+        let height = (max_block_y - min_block_y + 1) * cells_per_block;
+        let block_size = cells_per_block as f32 * NAVIGATION_CELL_SIZE;
+        let origin = Vec2::new(
+            block_size * min_block_x as f32,
+            -block_size * (65.0 - min_block_y as f32),
+        );
+
+        // Cell corner heights, NaN where there is no terrain
+        let vertex_width = width + 1;
+        let mut vertex_heights = vec![f32::NAN; vertex_width * (height + 1)];
+        for heightmap in input.heightmaps.iter() {
+            let offset_x = (heightmap.block_x - min_block_x) * cells_per_block;
+            let offset_y = (heightmap.block_y - min_block_y) * cells_per_block;
+            for y in 0..heightmap.height.min(cells_per_block + 1) {
+                for x in 0..heightmap.width.min(cells_per_block + 1) {
+                    vertex_heights[(offset_y + y) * vertex_width + offset_x + x] =
+                        heightmap.heights[y * heightmap.width + x];
+                }
+            }
+        }
+
+        let mut ground_heights = vec![f32::NAN; width * height];
+        let mut walkable = vec![false; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let corners = [
+                    vertex_heights[y * vertex_width + x],
+                    vertex_heights[y * vertex_width + x + 1],
+                    vertex_heights[(y + 1) * vertex_width + x],
+                    vertex_heights[(y + 1) * vertex_width + x + 1],
+                ];
+                if corners.iter().any(|height| !height.is_finite()) {
+                    continue;
+                }
+
+                let min = corners.iter().copied().fold(f32::MAX, f32::min);
+                let max = corners.iter().copied().fold(f32::MIN, f32::max);
+                ground_heights[y * width + x] = corners.iter().sum::<f32>() / 4.0;
+                walkable[y * width + x] =
+                    (max - min) / NAVIGATION_CELL_SIZE <= NAVIGATION_MAX_SLOPE;
+            }
+        }
+
+        for (min, max) in input.obstacles.iter() {
+            let cell_min = ((Vec2::new(min.x, min.z) - origin) / NAVIGATION_CELL_SIZE).floor();
+            let cell_max = ((Vec2::new(max.x, max.z) - origin) / NAVIGATION_CELL_SIZE).floor();
+            if cell_max.x < 0.0 || cell_max.y < 0.0 {
+                continue;
+            }
+
+            let start_x = cell_min.x.max(0.0) as usize;
+            let start_y = cell_min.y.max(0.0) as usize;
+            let end_x = (cell_max.x as usize).min(width.saturating_sub(1));
+            let end_y = (cell_max.y as usize).min(height.saturating_sub(1));
+            for y in start_y..=end_y {
+                for x in start_x..=end_x {
+                    let ground_height = ground_heights[y * width + x];
+                    if max.y > ground_height + NAVIGATION_STEP_HEIGHT
+                        && min.y < ground_height + NAVIGATION_CHARACTER_HEIGHT
+                    {
+                        walkable[y * width + x] = false;
+                    }
+                }
+            }
+        }
+
+        Some(Self {
+            zone_id: input.zone_id,
+            origin,
+            width,
+            height,
+            walkable,
+        })
+    }
+
+    pub fn get_cell(&self, position: Vec3) -> Option<(usize, usize)> {
+        let cell =
+            ((Vec2::new(position.x, position.z) - self.origin) / NAVIGATION_CELL_SIZE).floor();
+        if cell.x < 0.0
+            || cell.y < 0.0
+            || cell.x as usize >= self.width
+            || cell.y as usize >= self.height
+        {
+            return None;
+        }
+
+        Some((cell.x as usize, cell.y as usize))
+    }
+
+    // Returns the world x, z position of the centre of a cell
+    pub fn get_cell_center(&self, x: usize, y: usize) -> Vec2 {
+        self.origin + (Vec2::new(x as f32, y as f32) + 0.5) * NAVIGATION_CELL_SIZE
+    }
+
+    pub fn is_cell_walkable(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.walkable[y * self.width + x]
+    }
+
+    pub fn is_walkable(&self, position: Vec3) -> bool {
+        self.get_cell(position)
+            .map_or(false, |(x, y)| self.is_cell_walkable(x, y))
+    }
+
+    fn read(path: &Path, zone_id: ZoneId) -> Result<Self, std::io::Error> {
+        let invalid_data = || std::io::Error::from(std::io::ErrorKind::InvalidData);
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != NAVIGATION_GRID_MAGIC || header[4] != NAVIGATION_GRID_VERSION {
+            return Err(invalid_data());
+        }
+
+        let mut values = [0u8; 16];
+        reader.read_exact(&mut values)?;
+        let read_u32 =
+            |index: usize| u32::from_le_bytes(values[index * 4..index * 4 + 4].try_into().unwrap());
+        let origin = Vec2::new(f32::from_bits(read_u32(0)), f32::from_bits(read_u32(1)));
+        let width = read_u32(2) as usize;
+        let height = read_u32(3) as usize;
+
+        let mut walkable = Vec::new();
+        reader.read_to_end(&mut walkable)?;
+        if walkable.len() != width * height {
+            return Err(invalid_data());
+        }
+
+        Ok(Self {
+            zone_id,
+            origin,
+            width,
+            height,
+            walkable: walkable.into_iter().map(|value| value != 0).collect(),
+        })
+    }
+
+    fn write(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(NAVIGATION_GRID_MAGIC)?;
+        writer.write_all(&[NAVIGATION_GRID_VERSION])?;
+        writer.write_all(&self.origin.x.to_le_bytes())?;
+        writer.write_all(&self.origin.y.to_le_bytes())?;
+        writer.write_all(&(self.width as u32).to_le_bytes())?;
+        writer.write_all(&(self.height as u32).to_le_bytes())?;
+        let walkable: Vec<u8> = self.walkable.iter().map(|&value| value as u8).collect();
+        writer.write_all(&walkable)?;
+        writer.flush()
+    }
+}
+
+fn load_or_generate_navigation_grid(
+    cache_path: &Path,
+    input: &NavigationGridInput,
+) -> Option<NavigationGrid> {
+    let path = cache_path.join(format!(
+        "zone_{}_{}.nav",
+        input.zone_id.get(),
+        input.checksum()
+    ));
+
+    if let Ok(grid) = NavigationGrid::read(&path, input.zone_id) {
+        return Some(grid);
+    }
+
+    let grid = NavigationGrid::generate(input)?;
+    if let Err(error) = grid.write(&path) {
+        log::warn!(
+            "Failed to write navigation cache {} with error {}",
+            path.to_string_lossy(),
+            error
+        );
+    }
+    Some(grid)
+}
+
+#[derive(Resource)]
+pub struct ZoneNavigation {
+    pub cache_path: PathBuf,
+    pub grid: Option<Arc<NavigationGrid>>,
+    pub generating_zone: Option<ZoneId>,
+    generated_tx: Sender<(ZoneId, Option<NavigationGrid>)>,
+    generated_rx: Receiver<(ZoneId, Option<NavigationGrid>)>,
+}
+
+impl ZoneNavigation {
+    pub fn new(cache_path: PathBuf) -> Self {
+        let (generated_tx, generated_rx) = crossbeam_channel::unbounded();
+        Self {
+            cache_path,
+            grid: None,
+            generating_zone: None,
+            generated_tx,
+            generated_rx,
+        }
+    }
+
+    // Loads the grid from the cache or generates it on a background task,
+    // the previous grid is cleared until the new one is ready.
+    pub fn generate(&mut self, mut input: NavigationGridInput) {
+        self.grid = None;
+        self.generating_zone = Some(input.zone_id);
+
+        let cache_path = self.cache_path.clone();
+        let generated_tx = self.generated_tx.clone();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                // Obstacles are collected in query order, sort them so the checksum is stable
+                input.obstacles.sort_by(|(a, _), (b, _)| {
+                    a.x.total_cmp(&b.x)
+                        .then(a.y.total_cmp(&b.y))
+                        .then(a.z.total_cmp(&b.z))
+                });
+
+                let grid = load_or_generate_navigation_grid(&cache_path, &input);
+                generated_tx.send((input.zone_id, grid)).ok();
+            })
+            .detach();
+    }
+
+    pub fn update(&mut self) {
+        while let Ok((zone_id, grid)) = self.generated_rx.try_recv() {
+            // Ignore grids for a zone we have since left
+            if self.generating_zone != Some(zone_id) {
+                continue;
+            }
+
+            self.generating_zone = None;
+            self.grid = grid.map(Arc::new);
+        }
+    }
+}
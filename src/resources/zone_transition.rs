@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+
+use rose_data::ZoneId;
+
+pub const ZONE_TRANSITION_TIPS: &[&str] = &[
+    "Hold the right mouse button and drag to rotate the camera.",
+    "Use the mouse wheel to zoom the camera in and out.",
+    "Press F1 to F8 to use the skills and items on your hotbar.",
+    "Hold Shift and scroll over the hotbar or press F1 to F4 to switch hotbar pages.",
+    "Press F10 to toggle cinematic mode.",
+    "Select a party member and press F11 to assist them.",
+    "Party members can share experience and items, check the party options.",
+    "Resting by sitting down recovers health and mana faster.",
+    "Personal stores let you sell items to other players while you are away.",
+];
+
+pub struct ActiveZoneTransition {
+    pub zone_id: ZoneId,
+    pub zone_loaded: bool,
+    pub tip_index: usize,
+
+    // Time since the player joined the new zone, the transition screen fades out
+    pub fade_elapsed: Option<Duration>,
+}
+
+// Covers the world while changing zone, the UI windows are kept above it
+#[derive(Default, Resource)]
+pub struct ZoneTransition {
+    pub active: Option<ActiveZoneTransition>,
+}
+
+impl ZoneTransition {
+    pub const FADE_DURATION: Duration = Duration::from_millis(500);
+
+    pub fn opacity(&self) -> f32 {
+        match self.active.as_ref() {
+            Some(ActiveZoneTransition {
+                fade_elapsed: Some(fade_elapsed),
+                ..
+            }) => 1.0 - (fade_elapsed.as_secs_f32() / Self::FADE_DURATION.as_secs_f32()).min(1.0),
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+}
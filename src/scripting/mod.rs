@@ -11,6 +11,7 @@ mod quest_function_context;
 mod quest_reward_functions;
 mod script_function_context;
 mod script_function_resources;
+mod user_script;
 
 pub struct LuaUserValueEntity {
     pub owner_entity: Option<Entity>,
@@ -25,6 +26,9 @@ pub use quest_function_context::QuestFunctionContext;
 pub use quest_reward_functions::{quest_triggers_apply_rewards, quest_triggers_skip_rewards};
 pub use script_function_context::ScriptFunctionContext;
 pub use script_function_resources::ScriptFunctionResources;
+pub use user_script::{
+    UserScript, UserScriptCommand, UserScriptEvent, UserScriptManager, UserScriptPlayer,
+};
 
 #[derive(Default)]
 pub struct RoseScriptingPlugin;
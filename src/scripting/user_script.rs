@@ -0,0 +1,363 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::Resource;
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST};
+
+use rose_data::ZoneId;
+
+// Limits how long a single script call may run for, so a script stuck in a
+// loop cannot freeze the client
+const USER_SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+
+// Limits the size of values a script can build, so a script cannot exhaust memory
+const USER_SCRIPT_MAX_STRING_SIZE: usize = 64 * 1024;
+const USER_SCRIPT_MAX_ARRAY_SIZE: usize = 10_000;
+const USER_SCRIPT_MAX_MAP_SIZE: usize = 10_000;
+
+#[derive(Clone, Default)]
+pub struct UserScriptPlayer {
+    pub name: String,
+    pub level: u32,
+    pub health_points: i32,
+    pub max_health_points: i32,
+    pub mana_points: i32,
+    pub max_mana_points: i32,
+    pub experience_points: u64,
+    pub zone_id: Option<ZoneId>,
+}
+
+pub enum UserScriptCommand {
+    SendChat { script_name: String, text: String },
+    OpenWindow(String),
+    Print(String),
+}
+
+pub enum UserScriptEvent {
+    ZoneChange(ZoneId),
+    LevelUp(u32),
+}
+
+#[derive(Default)]
+struct UserScriptApiState {
+    player: Option<UserScriptPlayer>,
+    // Name of the script currently running, so commands can be rate limited per script
+    current_script: String,
+    commands: Vec<UserScriptCommand>,
+}
+
+pub struct UserScript {
+    pub name: String,
+    pub path: PathBuf,
+    pub enabled: bool,
+    pub error: Option<String>,
+    ast: Option<AST>,
+    scope: Scope<'static>,
+}
+
+#[derive(Resource)]
+pub struct UserScriptManager {
+    pub path: PathBuf,
+    pub scripts: Vec<UserScript>,
+    engine: Engine,
+    api_state: Arc<Mutex<UserScriptApiState>>,
+}
+
+fn with_player<T>(
+    api_state: &Mutex<UserScriptApiState>,
+    f: impl FnOnce(&UserScriptPlayer) -> T,
+) -> Option<T> {
+    api_state.lock().unwrap().player.as_ref().map(f)
+}
+
+fn create_engine(api_state: &Arc<Mutex<UserScriptApiState>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(USER_SCRIPT_MAX_OPERATIONS);
+    engine.set_max_string_size(USER_SCRIPT_MAX_STRING_SIZE);
+    engine.set_max_array_size(USER_SCRIPT_MAX_ARRAY_SIZE);
+    engine.set_max_map_size(USER_SCRIPT_MAX_MAP_SIZE);
+
+    let state = api_state.clone();
+    engine.on_print(move |text| {
+        state
+            .lock()
+            .unwrap()
+            .commands
+            .push(UserScriptCommand::Print(text.to_string()));
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("send_chat", move |text: &str| {
+        let mut state = state.lock().unwrap();
+        let script_name = state.current_script.clone();
+        state.commands.push(UserScriptCommand::SendChat {
+            script_name,
+            text: text.to_string(),
+        });
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("open_window", move |name: &str| {
+        state
+            .lock()
+            .unwrap()
+            .commands
+            .push(UserScriptCommand::OpenWindow(name.to_string()));
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_name", move || {
+        with_player(&state, |player| player.name.clone()).unwrap_or_default()
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_level", move || {
+        with_player(&state, |player| player.level as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_hp", move || {
+        with_player(&state, |player| player.health_points as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_max_hp", move || {
+        with_player(&state, |player| player.max_health_points as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_mp", move || {
+        with_player(&state, |player| player.mana_points as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_max_mp", move || {
+        with_player(&state, |player| player.max_mana_points as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("player_xp", move || {
+        with_player(&state, |player| player.experience_points as i64).unwrap_or(0)
+    });
+
+    let state = api_state.clone();
+    engine.register_fn("zone_id", move || {
+        with_player(&state, |player| {
+            player.zone_id.map_or(0, |zone_id| zone_id.get() as i64)
+        })
+        .unwrap_or(0)
+    });
+
+    engine
+}
+
+impl UserScript {
+    fn load(&mut self, engine: &Engine) {
+        self.ast = None;
+        self.scope = Scope::new();
+        self.error = None;
+
+        let source = match std::fs::read_to_string(&self.path) {
+            Ok(source) => source,
+            Err(error) => {
+                self.error = Some(error.to_string());
+                return;
+            }
+        };
+
+        let ast = match engine.compile(source) {
+            Ok(ast) => ast,
+            Err(error) => {
+                self.error = Some(error.to_string());
+                return;
+            }
+        };
+
+        // Top level statements are run once when the script is loaded
+        if let Err(error) = engine.run_ast_with_scope(&mut self.scope, &ast) {
+            self.error = Some(error.to_string());
+            return;
+        }
+
+        self.ast = Some(ast);
+    }
+
+    fn call(&mut self, engine: &Engine, function_name: &str, args: impl FuncArgs) {
+        let Some(ast) = self.ast.as_ref() else {
+            return;
+        };
+
+        // Scripts only need to define the event handlers they are interested in
+        if !ast
+            .iter_functions()
+            .any(|function| function.name == function_name)
+        {
+            return;
+        }
+
+        // The top level statements were already run by load, and the scope must not
+        // be rewound so it keeps its state between events
+        let options = CallFnOptions::new().eval_ast(false).rewind_scope(false);
+        if let Err(error) = engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut self.scope,
+            ast,
+            function_name,
+            args,
+        ) {
+            log::warn!(
+                "User script {} failed in {}: {}",
+                self.name,
+                function_name,
+                error
+            );
+            self.error = Some(error.to_string());
+        }
+    }
+}
+
+impl UserScriptManager {
+    pub fn new(path: &Path, disabled: &[String]) -> Self {
+        let api_state = Arc::new(Mutex::new(UserScriptApiState::default()));
+        let mut user_script_manager = Self {
+            path: path.into(),
+            scripts: Vec::new(),
+            engine: create_engine(&api_state),
+            api_state,
+        };
+        user_script_manager.reload(disabled);
+        user_script_manager
+    }
+
+    // Rescans the scripts directory and reloads all enabled scripts
+    pub fn reload(&mut self, disabled: &[String]) {
+        let mut scripts = Vec::new();
+        if let Ok(read_dir) = std::fs::read_dir(&self.path) {
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                if !path
+                    .extension()
+                    .map_or(false, |extension| extension.eq_ignore_ascii_case("rhai"))
+                {
+                    continue;
+                }
+
+                let name = path
+                    .file_stem()
+                    .map(|file_stem| file_stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let enabled = !disabled
+                    .iter()
+                    .any(|disabled_name| disabled_name.eq_ignore_ascii_case(&name));
+
+                scripts.push(UserScript {
+                    name,
+                    path,
+                    enabled,
+                    error: None,
+                    ast: None,
+                    scope: Scope::new(),
+                });
+            }
+        }
+        scripts.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for script in scripts.iter_mut().filter(|script| script.enabled) {
+            self.api_state.lock().unwrap().current_script = script.name.clone();
+            script.load(&self.engine);
+            if let Some(error) = script.error.as_ref() {
+                log::warn!("Failed to load user script {}: {}", script.name, error);
+            } else {
+                log::info!("Loaded user script {}", script.name);
+            }
+        }
+
+        self.scripts = scripts;
+    }
+
+    pub fn disabled_script_names(&self) -> Vec<String> {
+        self.scripts
+            .iter()
+            .filter(|script| !script.enabled)
+            .map(|script| script.name.clone())
+            .collect()
+    }
+
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        let Some(script) = self.scripts.get_mut(index) else {
+            return;
+        };
+
+        script.enabled = enabled;
+        if enabled {
+            self.api_state.lock().unwrap().current_script = script.name.clone();
+            script.load(&self.engine);
+        } else {
+            script.ast = None;
+            script.scope = Scope::new();
+            script.error = None;
+        }
+    }
+
+    pub fn set_player(&mut self, player: Option<UserScriptPlayer>) {
+        self.api_state.lock().unwrap().player = player;
+    }
+
+    pub fn handle_event(&mut self, event: &UserScriptEvent) {
+        for script in self.scripts.iter_mut().filter(|script| script.enabled) {
+            self.api_state.lock().unwrap().current_script = script.name.clone();
+            match *event {
+                UserScriptEvent::ZoneChange(zone_id) => {
+                    script.call(&self.engine, "on_zone_change", (zone_id.get() as i64,))
+                }
+                UserScriptEvent::LevelUp(level) => {
+                    script.call(&self.engine, "on_level_up", (level as i64,))
+                }
+            }
+        }
+    }
+
+    pub fn take_commands(&mut self) -> Vec<UserScriptCommand> {
+        std::mem::take(&mut self.api_state.lock().unwrap().commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_level_statements_run_once() {
+        let path =
+            std::env::temp_dir().join(format!("rose-user-script-test-{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("test.rhai"),
+            "print(\"loaded\");\nfn on_level_up(level) { send_chat(\"level \" + level); }\n",
+        )
+        .unwrap();
+
+        let mut user_script_manager = UserScriptManager::new(&path, &[]);
+        let load_commands = user_script_manager.take_commands();
+
+        user_script_manager.handle_event(&UserScriptEvent::LevelUp(2));
+        user_script_manager.handle_event(&UserScriptEvent::LevelUp(3));
+        let event_commands = user_script_manager.take_commands();
+        std::fs::remove_dir_all(&path).ok();
+
+        assert!(user_script_manager.scripts[0].error.is_none());
+        assert_eq!(
+            load_commands
+                .iter()
+                .filter(|command| matches!(command, UserScriptCommand::Print(_)))
+                .count(),
+            1
+        );
+        assert_eq!(event_commands.len(), 2);
+        assert!(event_commands
+            .iter()
+            .all(|command| matches!(command, UserScriptCommand::SendChat { .. })));
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::{EventReader, Local, Res, ResMut};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::ExtendedServerMessageEvent,
+    protocol::{
+        AccountSettingsMessage, ExtendedClientMessage, ExtendedServerMessage, ProtocolCapabilities,
+    },
+    resources::{
+        Account, AccountSettings, ClientCapabilities, ClientSettings, ControlsSettings,
+        GameConnection, IgnoreList, InputDisplayPosition, NameTagSettings, SoundSettings,
+    },
+};
+
+const ACCOUNT_SETTINGS_SOUND: &str = "sound";
+const ACCOUNT_SETTINGS_NAME_TAGS: &str = "name_tags";
+const ACCOUNT_SETTINGS_IGNORE_LIST: &str = "ignore_list";
+const ACCOUNT_SETTINGS_CONTROLS: &str = "controls";
+const ACCOUNT_SETTINGS_HUD_LAYOUT: &str = "hud_layout";
+
+const ACCOUNT_SETTINGS_KEYS: [&str; 5] = [
+    ACCOUNT_SETTINGS_SOUND,
+    ACCOUNT_SETTINGS_NAME_TAGS,
+    ACCOUNT_SETTINGS_IGNORE_LIST,
+    ACCOUNT_SETTINGS_CONTROLS,
+    ACCOUNT_SETTINGS_HUD_LAYOUT,
+];
+
+// Wait for settings to stop changing before saving, e.g. while dragging a volume slider
+const ACCOUNT_SETTINGS_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize)]
+struct SyncedSoundSettings {
+    enabled: bool,
+    global_gain: f32,
+    gains: Vec<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SyncedNameTagSettings {
+    show_all: Vec<bool>,
+    font_size: Vec<f32>,
+    #[serde(default)]
+    show_party_health: bool,
+    #[serde(default)]
+    show_clan_health: bool,
+    #[serde(default = "default_show_chat_bubbles")]
+    show_chat_bubbles: bool,
+}
+
+fn default_show_chat_bubbles() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize)]
+struct SyncedIgnoreList {
+    names: Vec<String>,
+}
+
+// The interface settings which are not already covered by the name tag settings
+#[derive(Serialize, Deserialize)]
+struct SyncedHudLayout {
+    show_zone_titles: bool,
+    tooltip_delay: f32,
+    show_input_display: bool,
+    input_display_position: InputDisplayPosition,
+    input_display_fade_time: f32,
+}
+
+fn serialize_sound_settings(sound_settings: &SoundSettings) -> Vec<u8> {
+    toml::to_string(&SyncedSoundSettings {
+        enabled: sound_settings.enabled,
+        global_gain: sound_settings.global_gain,
+        gains: sound_settings.gains.values().copied().collect(),
+    })
+    .map(String::into_bytes)
+    .unwrap_or_default()
+}
+
+fn serialize_name_tag_settings(name_tag_settings: &NameTagSettings) -> Vec<u8> {
+    toml::to_string(&SyncedNameTagSettings {
+        show_all: name_tag_settings.show_all.values().copied().collect(),
+        font_size: name_tag_settings.font_size.values().copied().collect(),
+        show_party_health: name_tag_settings.show_party_health,
+        show_clan_health: name_tag_settings.show_clan_health,
+        show_chat_bubbles: name_tag_settings.show_chat_bubbles,
+    })
+    .map(String::into_bytes)
+    .unwrap_or_default()
+}
+
+fn serialize_ignore_list(ignore_list: &IgnoreList) -> Vec<u8> {
+    toml::to_string(&SyncedIgnoreList {
+        names: ignore_list.names.clone(),
+    })
+    .map(String::into_bytes)
+    .unwrap_or_default()
+}
+
+fn serialize_controls(client_settings: &ClientSettings) -> Vec<u8> {
+    toml::to_string(&client_settings.controls)
+        .map(String::into_bytes)
+        .unwrap_or_default()
+}
+
+fn serialize_hud_layout(client_settings: &ClientSettings) -> Vec<u8> {
+    let interface = &client_settings.interface;
+    toml::to_string(&SyncedHudLayout {
+        show_zone_titles: interface.show_zone_titles,
+        tooltip_delay: interface.tooltip_delay,
+        show_input_display: interface.show_input_display,
+        input_display_position: interface.input_display_position,
+        input_display_fade_time: interface.input_display_fade_time,
+    })
+    .map(String::into_bytes)
+    .unwrap_or_default()
+}
+
+fn serialize_account_settings(
+    key: &str,
+    sound_settings: &SoundSettings,
+    name_tag_settings: &NameTagSettings,
+    ignore_list: &IgnoreList,
+    client_settings: &ClientSettings,
+) -> Vec<u8> {
+    match key {
+        ACCOUNT_SETTINGS_SOUND => serialize_sound_settings(sound_settings),
+        ACCOUNT_SETTINGS_NAME_TAGS => serialize_name_tag_settings(name_tag_settings),
+        ACCOUNT_SETTINGS_IGNORE_LIST => serialize_ignore_list(ignore_list),
+        ACCOUNT_SETTINGS_CONTROLS => serialize_controls(client_settings),
+        ACCOUNT_SETTINGS_HUD_LAYOUT => serialize_hud_layout(client_settings),
+        _ => Vec::new(),
+    }
+}
+
+fn apply_account_settings(
+    key: &str,
+    data: &[u8],
+    sound_settings: &mut SoundSettings,
+    name_tag_settings: &mut NameTagSettings,
+    ignore_list: &mut IgnoreList,
+    client_settings: &mut ClientSettings,
+) {
+    let Ok(data) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    match key {
+        ACCOUNT_SETTINGS_SOUND => {
+            if let Ok(synced) = toml::from_str::<SyncedSoundSettings>(data) {
+                sound_settings.enabled = synced.enabled;
+                sound_settings.global_gain = synced.global_gain;
+                for (gain, synced_gain) in sound_settings.gains.values_mut().zip(synced.gains) {
+                    *gain = synced_gain;
+                }
+            }
+        }
+        ACCOUNT_SETTINGS_NAME_TAGS => {
+            if let Ok(synced) = toml::from_str::<SyncedNameTagSettings>(data) {
+                for (show_all, synced_show_all) in
+                    name_tag_settings.show_all.values_mut().zip(synced.show_all)
+                {
+                    *show_all = synced_show_all;
+                }
+                for (font_size, synced_font_size) in name_tag_settings
+                    .font_size
+                    .values_mut()
+                    .zip(synced.font_size)
+                {
+                    *font_size = synced_font_size;
+                }
+                name_tag_settings.show_party_health = synced.show_party_health;
+                name_tag_settings.show_clan_health = synced.show_clan_health;
+                name_tag_settings.show_chat_bubbles = synced.show_chat_bubbles;
+            }
+        }
+        ACCOUNT_SETTINGS_IGNORE_LIST => {
+            if let Ok(synced) = toml::from_str::<SyncedIgnoreList>(data) {
+                ignore_list.names = synced.names;
+            }
+        }
+        ACCOUNT_SETTINGS_CONTROLS => {
+            if let Ok(synced) = toml::from_str::<ControlsSettings>(data) {
+                client_settings.controls = synced;
+            }
+        }
+        ACCOUNT_SETTINGS_HUD_LAYOUT => {
+            if let Ok(synced) = toml::from_str::<SyncedHudLayout>(data) {
+                let interface = &mut client_settings.interface;
+                interface.show_zone_titles = synced.show_zone_titles;
+                interface.tooltip_delay = synced.tooltip_delay;
+                interface.show_input_display = synced.show_input_display;
+                interface.input_display_position = synced.input_display_position;
+                interface.input_display_fade_time = synced.input_display_fade_time;
+            }
+        }
+        unknown => log::warn!("Received unknown account settings {}", unknown),
+    }
+}
+
+#[derive(Default)]
+pub struct AccountSettingsSyncState {
+    requested: bool,
+    pending_save: Option<Instant>,
+
+    // Last settings data which matched the server, used to detect local changes
+    synced_data: HashMap<&'static str, Vec<u8>>,
+}
+
+pub fn account_settings_system(
+    mut sync_state: Local<AccountSettingsSyncState>,
+    mut account_settings: ResMut<AccountSettings>,
+    account: Option<Res<Account>>,
+    game_connection: Option<Res<GameConnection>>,
+    mut sound_settings: ResMut<SoundSettings>,
+    mut name_tag_settings: ResMut<NameTagSettings>,
+    mut ignore_list: ResMut<IgnoreList>,
+    mut client_settings: ResMut<ClientSettings>,
+    client_capabilities: Res<ClientCapabilities>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    if !account_settings.sync_enabled
+        || !client_capabilities.supports(ProtocolCapabilities::ACCOUNT_SETTINGS)
+    {
+        return;
+    }
+
+    let (Some(account), Some(game_connection)) = (account, game_connection) else {
+        sync_state.requested = false;
+        sync_state.pending_save = None;
+        return;
+    };
+
+    if !sync_state.requested {
+        account_settings.load_account(&account.username);
+
+        // Apply our local copy until we hear back from the server
+        for key in ACCOUNT_SETTINGS_KEYS {
+            if let Some(local) = account_settings.get(key) {
+                apply_account_settings(
+                    key,
+                    &local.data,
+                    &mut sound_settings,
+                    &mut name_tag_settings,
+                    &mut ignore_list,
+                    &mut client_settings,
+                );
+            }
+        }
+
+        sync_state.synced_data.clear();
+        for key in ACCOUNT_SETTINGS_KEYS {
+            sync_state.synced_data.insert(
+                key,
+                serialize_account_settings(
+                    key,
+                    &sound_settings,
+                    &name_tag_settings,
+                    &ignore_list,
+                    &client_settings,
+                ),
+            );
+        }
+
+        game_connection
+            .extended_client_message_tx
+            .send(ExtendedClientMessage::AccountSettings(
+                AccountSettingsMessage::RequestAll,
+            ))
+            .ok();
+        sync_state.requested = true;
+        return;
+    }
+
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        let ExtendedServerMessage::AccountSettings(remote) = message else {
+            continue;
+        };
+        let key = remote.key.clone();
+        let remote_timestamp = remote.timestamp;
+
+        if account_settings.merge_remote(remote.clone()) {
+            if let Some(blob) = account_settings.get(&key) {
+                apply_account_settings(
+                    &key,
+                    &blob.data,
+                    &mut sound_settings,
+                    &mut name_tag_settings,
+                    &mut ignore_list,
+                    &mut client_settings,
+                );
+            }
+        } else if let Some(local) = account_settings
+            .get(&key)
+            .filter(|local| local.timestamp > remote_timestamp)
+        {
+            // Our local copy is newer, e.g. changed while playing on a server without sync
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::AccountSettings(
+                    AccountSettingsMessage::Save(local.clone()),
+                ))
+                .ok();
+        }
+
+        if let Some(key) = ACCOUNT_SETTINGS_KEYS
+            .into_iter()
+            .find(|known_key| *known_key == key)
+        {
+            sync_state.synced_data.insert(
+                key,
+                serialize_account_settings(
+                    key,
+                    &sound_settings,
+                    &name_tag_settings,
+                    &ignore_list,
+                    &client_settings,
+                ),
+            );
+        }
+    }
+
+    if sound_settings.is_changed()
+        || name_tag_settings.is_changed()
+        || ignore_list.is_changed()
+        || client_settings.is_changed()
+    {
+        sync_state.pending_save = Some(Instant::now());
+    }
+
+    if sync_state.pending_save.map_or(true, |changed| {
+        changed.elapsed() < ACCOUNT_SETTINGS_SAVE_DELAY
+    }) {
+        return;
+    }
+    sync_state.pending_save = None;
+
+    for key in ACCOUNT_SETTINGS_KEYS {
+        let data = serialize_account_settings(
+            key,
+            &sound_settings,
+            &name_tag_settings,
+            &ignore_list,
+            &client_settings,
+        );
+        if sync_state.synced_data.get(key) == Some(&data) {
+            continue;
+        }
+
+        if let Some(blob) = account_settings.update_local(key, data.clone()) {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::AccountSettings(
+                    AccountSettingsMessage::Save(blob),
+                ))
+                .ok();
+        }
+        sync_state.synced_data.insert(key, data);
+    }
+}
@@ -1,25 +1,25 @@
-use bevy::prelude::{EventWriter, Local, Res, State};
+use bevy::prelude::{EventWriter, Query, Res, ResMut, State, With};
+
+use rose_game_common::{data::Password, messages::client::ClientMessage};
 
 use crate::{
+    components::PlayerCharacter,
     events::{CharacterSelectEvent, LoginEvent},
-    resources::{AppState, CharacterList, ServerConfiguration, ServerList},
+    resources::{
+        AppState, AutoLogin, AutoLoginState, CharacterList, CurrentZone, GameConnection,
+        ServerConfiguration, ServerList,
+    },
 };
 
-#[derive(Default)]
-pub enum AutoLoginState {
-    #[default]
-    Login,
-    WaitServerList,
-    WaitCharacterList,
-    SelectedCharacter,
-}
-
 pub fn auto_login_system(
-    mut auto_login_state: Local<AutoLoginState>,
+    mut auto_login: ResMut<AutoLogin>,
     app_state: Res<State<AppState>>,
     character_list: Option<Res<CharacterList>>,
     server_list: Option<Res<ServerList>>,
     server_configuration: Res<ServerConfiguration>,
+    current_zone: Option<Res<CurrentZone>>,
+    game_connection: Option<Res<GameConnection>>,
+    query_player: Query<(), With<PlayerCharacter>>,
     mut login_events: EventWriter<LoginEvent>,
     mut character_select_events: EventWriter<CharacterSelectEvent>,
 ) {
@@ -27,23 +27,32 @@ pub fn auto_login_system(
         return;
     }
 
-    match *auto_login_state {
+    match auto_login.state {
         AutoLoginState::Login => {
             if matches!(app_state.get(), AppState::GameLogin) {
-                if let (Some(username), Some(password)) = (
-                    &server_configuration.preset_username,
-                    &server_configuration.preset_password,
-                ) {
+                let password =
+                    if let Some(password_md5) = server_configuration.preset_password_md5.as_ref() {
+                        Some(Password::Md5(password_md5.clone()))
+                    } else {
+                        server_configuration
+                            .preset_password
+                            .as_ref()
+                            .map(|password| Password::Plaintext(password.clone()))
+                    };
+
+                if let (Some(username), Some(password)) =
+                    (&server_configuration.preset_username, password)
+                {
                     login_events.send(LoginEvent::Login {
                         username: username.clone(),
-                        password: password.clone(),
+                        password,
                     });
-                    *auto_login_state = AutoLoginState::WaitServerList;
+                    auto_login.state = AutoLoginState::WaitServerList;
                 }
 
                 if server_list.is_some() {
                     // If the user logged in without us, move on to next stage
-                    *auto_login_state = AutoLoginState::WaitCharacterList;
+                    auto_login.state = AutoLoginState::WaitCharacterList;
                 }
             }
         }
@@ -61,7 +70,7 @@ pub fn auto_login_system(
                                         server_id,
                                         channel_id,
                                     });
-                                    *auto_login_state = AutoLoginState::WaitCharacterList;
+                                    auto_login.state = AutoLoginState::WaitCharacterList;
                                 }
                             }
                         }
@@ -73,16 +82,19 @@ pub fn auto_login_system(
                         server_id: server_list.world_servers[0].id,
                         channel_id: server_list.world_servers[0].game_servers[0].id,
                     });
-                    *auto_login_state = AutoLoginState::WaitCharacterList;
+                    auto_login.state = AutoLoginState::WaitCharacterList;
                 }
             }
 
             if matches!(app_state.get(), AppState::GameCharacterSelect) {
-                *auto_login_state = AutoLoginState::WaitCharacterList;
+                auto_login.state = AutoLoginState::WaitCharacterList;
             }
         }
         AutoLoginState::WaitCharacterList => {
-            if matches!(app_state.get(), AppState::GameCharacterSelect) {
+            if matches!(app_state.get(), AppState::Game) {
+                // If the user selected a character without us, move on to next stage
+                auto_login.state = AutoLoginState::WaitGame;
+            } else if matches!(app_state.get(), AppState::GameCharacterSelect) {
                 if let Some(preset_character_name) =
                     server_configuration.preset_character_name.as_ref()
                 {
@@ -92,13 +104,45 @@ pub fn auto_login_system(
                                 character_select_events
                                     .send(CharacterSelectEvent::SelectCharacter(i));
                                 character_select_events.send(CharacterSelectEvent::PlaySelected);
-                                *auto_login_state = AutoLoginState::SelectedCharacter;
+                                auto_login.state = AutoLoginState::WaitGame;
                             }
                         }
                     }
                 }
             }
         }
-        AutoLoginState::SelectedCharacter => {}
+        AutoLoginState::WaitGame => {
+            if !matches!(app_state.get(), AppState::Game)
+                || current_zone.is_none()
+                || query_player.is_empty()
+            {
+                return;
+            }
+
+            let zone_id = server_configuration.preset_zone_id.or_else(|| {
+                server_configuration
+                    .preset_position
+                    .and(current_zone.as_ref().map(|current_zone| current_zone.id))
+            });
+
+            if let Some(zone_id) = zone_id {
+                // Warp using the server's /mm command, which also sets the position if given
+                let text = if let Some(position) = server_configuration.preset_position {
+                    format!("/mm {} {} {}", zone_id.get(), position.x, position.y)
+                } else {
+                    format!("/mm {}", zone_id.get())
+                };
+
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::Chat { text })
+                        .ok();
+                }
+            }
+
+            auto_login.state = AutoLoginState::Finished;
+        }
+        AutoLoginState::Finished => {}
     }
 }
@@ -0,0 +1,88 @@
+use bevy::{ecs::event::Events, prelude::World};
+
+use rose_game_common::{components::Inventory, messages::server::ServerMessage};
+
+use crate::{
+    components::Bank,
+    events::BankEvent,
+    resources::{ClientEntityList, ServerMessageKind},
+};
+
+pub const BANK_SERVER_MESSAGES: [ServerMessageKind; 4] = [
+    ServerMessageKind::BankOpen,
+    ServerMessageKind::BankSetItems,
+    ServerMessageKind::BankUpdateItems,
+    ServerMessageKind::BankTransaction,
+];
+
+pub fn bank_message_handler(message: &ServerMessage, world: &mut World) {
+    if let ServerMessage::BankOpen = message {
+        world
+            .resource_mut::<Events<BankEvent>>()
+            .send(BankEvent::Show);
+        return;
+    }
+
+    let Some(player_entity) = world.resource::<ClientEntityList>().player_entity else {
+        return;
+    };
+    let Some(mut player) = world.get_entity_mut(player_entity) else {
+        return;
+    };
+
+    match message {
+        ServerMessage::BankSetItems { items } => {
+            let mut slots = vec![None; 160];
+
+            for (bank_slot_index, item) in items.iter() {
+                let bank_slot_index = *bank_slot_index as usize;
+                if bank_slot_index > slots.len() {
+                    slots.resize(bank_slot_index + 1, None);
+                }
+                slots[bank_slot_index] = item.clone();
+            }
+
+            player.insert(Bank { slots });
+            world
+                .resource_mut::<Events<BankEvent>>()
+                .send(BankEvent::Show);
+        }
+        ServerMessage::BankUpdateItems { items } => {
+            if let Some(mut bank) = player.get_mut::<Bank>() {
+                for (bank_slot_index, item) in items.iter() {
+                    let bank_slot_index = *bank_slot_index as usize;
+
+                    if bank_slot_index > bank.slots.len() {
+                        bank.slots.resize(bank_slot_index + 1, None);
+                    }
+
+                    bank.slots[bank_slot_index] = item.clone();
+                }
+            }
+        }
+        ServerMessage::BankTransaction {
+            inventory_item_slot,
+            inventory_item,
+            inventory_money,
+            bank_slot,
+            bank_item,
+        } => {
+            if let Some(mut inventory) = player.get_mut::<Inventory>() {
+                if let Some(item_slot) = inventory.get_item_slot_mut(*inventory_item_slot) {
+                    *item_slot = inventory_item.clone();
+                }
+
+                if let Some(inventory_money) = inventory_money {
+                    inventory.money = *inventory_money;
+                }
+            }
+
+            if let Some(mut bank) = player.get_mut::<Bank>() {
+                if let Some(bank_slot) = bank.slots.get_mut(*bank_slot) {
+                    *bank_slot = bank_item.clone();
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -13,9 +13,9 @@ use bevy::{
 use bevy_rapier3d::prelude::{Collider, CollisionGroups};
 
 use crate::components::{
-    CharacterModel, CharacterModelPart, ColliderEntity, ColliderParent, ModelHeight, PersonalStore,
-    PlayerCharacter, COLLISION_FILTER_CLICKABLE, COLLISION_FILTER_INSPECTABLE,
-    COLLISION_GROUP_CHARACTER, COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_PLAYER,
+    CharacterModel, ColliderEntity, ColliderParent, ModelHeight, PersonalStore, PlayerCharacter,
+    COLLISION_FILTER_CLICKABLE, COLLISION_FILTER_INSPECTABLE, COLLISION_GROUP_CHARACTER,
+    COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_PLAYER,
 };
 
 pub fn character_model_add_collider_system(
@@ -39,19 +39,11 @@ pub fn character_model_add_collider_system(
         let mut all_parts_loaded = true;
 
         // Collect the AABB of Body, Hands, Feet
-        for part_entity in character_model.model_parts[CharacterModelPart::Body]
-            .1
+        for part_entity in character_model
+            .model_parts
             .iter()
-            .chain(
-                character_model.model_parts[CharacterModelPart::Hands]
-                    .1
-                    .iter(),
-            )
-            .chain(
-                character_model.model_parts[CharacterModelPart::Feet]
-                    .1
-                    .iter(),
-            )
+            .filter(|(model_part, _)| model_part.affects_model_height())
+            .flat_map(|(_, (_, part_entities))| part_entities.iter())
         {
             match query_aabb.get(*part_entity) {
                 Ok(Some(aabb)) => {
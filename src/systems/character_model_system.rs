@@ -1,8 +1,7 @@
 use bevy::{
-    hierarchy::DespawnRecursiveExt,
     prelude::{
-        AssetServer, Assets, Changed, Commands, Entity, Or, Query, RemovedComponents, Res, ResMut,
-        Without,
+        AssetServer, Assets, Changed, Commands, Entity, EventReader, Or, Query, RemovedComponents,
+        Res, ResMut, With, Without,
     },
     render::mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
 };
@@ -11,11 +10,15 @@ use rose_game_common::components::{CharacterInfo, Equipment};
 
 use crate::{
     components::{
-        CharacterBlinkTimer, CharacterModel, DummyBoneOffset, ModelHeight, PersonalStore,
-        RemoveColliderCommand,
+        CharacterBlinkTimer, CharacterModel, DummyBoneOffset, ModelHeight, PendingModel,
+        PersonalStore, RemoveColliderCommand,
     },
+    events::LoadZoneEvent,
     model_loader::ModelLoader,
     render::{EffectMeshMaterial, ObjectMaterial, ParticleMaterial},
+    resources::{
+        ModelPool, ModelPoolCommands, PlaceholderModelCommands, SkeletonCache, SkeletonCacheState,
+    },
 };
 
 pub fn character_model_update_system(
@@ -34,18 +37,28 @@ pub fn character_model_update_system(
             Changed<CharacterInfo>,
             Changed<Equipment>,
             Changed<PersonalStore>,
+            With<PendingModel>,
         )>,
     >,
     query_restore_from_personal_store: Query<(&CharacterInfo, &Equipment), Without<PersonalStore>>,
     mut removed_personal_store: RemovedComponents<PersonalStore>,
-    query_entities: Query<Entity>,
     asset_server: Res<AssetServer>,
     model_loader: Res<ModelLoader>,
     mut object_materials: ResMut<Assets<ObjectMaterial>>,
     mut particle_materials: ResMut<Assets<ParticleMaterial>>,
     mut effect_mesh_materials: ResMut<Assets<EffectMeshMaterial>>,
     mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+    mut skeleton_cache: ResMut<SkeletonCache>,
+    mut model_pool: ResMut<ModelPool>,
+    mut load_zone_events: EventReader<LoadZoneEvent>,
 ) {
+    skeleton_cache.update(&mut skinned_mesh_inverse_bindposes_assets);
+
+    if load_zone_events.iter().last().is_some() {
+        // Pooled entities are not despawned with the zone, flush them to avoid leaking
+        commands.clear_model_pool();
+    }
+
     for (
         entity,
         character_info,
@@ -59,21 +72,17 @@ pub fn character_model_update_system(
         if personal_store.is_some() {
             if let Some(current_character_model) = current_character_model.as_mut() {
                 // Personal store has its own model; remove character model to avoid overlap.
+                // The model is pooled so it can be reused when the store closes.
                 for (_, (_, model_parts)) in current_character_model.model_parts.iter_mut() {
-                    for part_entity in model_parts.drain(..) {
-                        if query_entities.get(part_entity).is_ok() {
-                            commands.entity(part_entity).despawn_recursive();
-                        }
-                    }
+                    commands.pool_model_parts(model_parts.drain(..).collect());
                 }
             }
 
             if let Some(current_skinned_mesh) = current_skinned_mesh.as_mut() {
-                for bone_entity in current_skinned_mesh.joints.drain(..) {
-                    if query_entities.get(bone_entity).is_ok() {
-                        commands.entity(bone_entity).despawn_recursive();
-                    }
-                }
+                commands.pool_skeleton(
+                    current_skinned_mesh.inverse_bindposes.id(),
+                    current_skinned_mesh.joints.drain(..).collect(),
+                );
             }
 
             commands
@@ -82,14 +91,16 @@ pub fn character_model_update_system(
                 .remove::<CharacterBlinkTimer>()
                 .remove::<CharacterModel>()
                 .remove::<SkinnedMesh>()
-                .remove::<DummyBoneOffset>();
+                .remove::<DummyBoneOffset>()
+                .remove::<PendingModel>();
+            commands.despawn_placeholder_model(entity);
             continue;
         }
 
         if let Some(current_character_model) = current_character_model.as_mut() {
             if character_info.gender == current_character_model.gender {
-                // Update existing model
-                model_loader.update_character_equipment(
+                // Update existing model, only rebuild the collider if the model height changed
+                let model_height_changed = model_loader.update_character_equipment(
                     &mut commands,
                     &asset_server,
                     &mut object_materials,
@@ -101,30 +112,51 @@ pub fn character_model_update_system(
                     &mut *current_character_model,
                     &current_dummy_bone_offset.unwrap(),
                     &current_skinned_mesh.unwrap(),
+                    &mut model_pool,
                 );
-                commands
-                    .entity(entity)
-                    .remove_and_despawn_collider()
-                    .remove::<ModelHeight>();
+                if model_height_changed {
+                    commands
+                        .entity(entity)
+                        .remove_and_despawn_collider()
+                        .remove::<ModelHeight>();
+                }
                 continue;
             }
+        }
 
-            // Despawn model parts
-            for (_, (_, model_parts)) in current_character_model.model_parts.iter_mut() {
-                for part_entity in model_parts.drain(..) {
-                    if query_entities.get(part_entity).is_ok() {
-                        commands.entity(part_entity).despawn_recursive();
+        // Keep any previous model until the new skeleton has loaded, otherwise
+        // show a placeholder while loading or when the skeleton failed to load
+        let skeleton =
+            match skeleton_cache.get(ModelLoader::get_skeleton_path(character_info.gender)) {
+                SkeletonCacheState::Loaded(skeleton) => skeleton,
+                SkeletonCacheState::Loading => {
+                    commands.entity(entity).insert(PendingModel);
+                    if current_character_model.is_none() {
+                        commands.spawn_placeholder_model(entity);
+                    }
+                    continue;
+                }
+                SkeletonCacheState::Failed => {
+                    commands.entity(entity).remove::<PendingModel>();
+                    if current_character_model.is_none() {
+                        commands.spawn_placeholder_model(entity);
                     }
+                    continue;
                 }
+            };
+
+        if let Some(current_character_model) = current_character_model.as_mut() {
+            // Return model parts to the pool
+            for (_, (_, model_parts)) in current_character_model.model_parts.iter_mut() {
+                commands.pool_model_parts(model_parts.drain(..).collect());
             }
 
-            // Despawn model skeleton
+            // Return model skeleton to the pool
             if let Some(current_skinned_mesh) = current_skinned_mesh.as_mut() {
-                for bone_entity in current_skinned_mesh.joints.drain(..) {
-                    if query_entities.get(bone_entity).is_ok() {
-                        commands.entity(bone_entity).despawn_recursive();
-                    }
-                }
+                commands.pool_skeleton(
+                    current_skinned_mesh.inverse_bindposes.id(),
+                    current_skinned_mesh.joints.drain(..).collect(),
+                );
             }
 
             // Remove the old model collider
@@ -138,16 +170,20 @@ pub fn character_model_update_system(
                 &mut object_materials,
                 &mut particle_materials,
                 &mut effect_mesh_materials,
-                &mut skinned_mesh_inverse_bindposes_assets,
                 entity,
                 character_info,
                 equipment,
+                &skeleton,
+                &mut model_pool,
             );
 
+        commands.despawn_placeholder_model(entity);
+
         let mut entity_commands = commands.entity(entity);
         entity_commands
             .insert(CharacterBlinkTimer::new())
-            .remove_and_despawn_collider();
+            .remove_and_despawn_collider()
+            .remove::<PendingModel>();
 
         if let Some(mut current_character_model) = current_character_model {
             *current_character_model = character_model;
@@ -175,6 +211,21 @@ pub fn character_model_update_system(
             continue;
         };
 
+        let skeleton =
+            match skeleton_cache.get(ModelLoader::get_skeleton_path(character_info.gender)) {
+                SkeletonCacheState::Loaded(skeleton) => skeleton,
+                SkeletonCacheState::Loading => {
+                    // Model will be spawned by the query above once the skeleton has loaded
+                    commands.entity(entity).insert(PendingModel);
+                    commands.spawn_placeholder_model(entity);
+                    continue;
+                }
+                SkeletonCacheState::Failed => {
+                    commands.spawn_placeholder_model(entity);
+                    continue;
+                }
+            };
+
         let (character_model, skinned_mesh, dummy_bone_offset) = model_loader
             .spawn_character_model(
                 &mut commands,
@@ -182,12 +233,14 @@ pub fn character_model_update_system(
                 &mut object_materials,
                 &mut particle_materials,
                 &mut effect_mesh_materials,
-                &mut skinned_mesh_inverse_bindposes_assets,
                 entity,
                 character_info,
                 equipment,
+                &skeleton,
+                &mut model_pool,
             );
 
+        commands.despawn_placeholder_model(entity);
         commands
             .entity(entity)
             .insert(CharacterBlinkTimer::new())
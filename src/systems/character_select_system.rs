@@ -8,7 +8,7 @@ use bevy::{
         MouseButton, NextState, Query, Res, ResMut, Resource, Visibility, With,
     },
     render::mesh::skinning::SkinnedMesh,
-    window::{CursorGrabMode, PrimaryWindow, Window},
+    window::{PrimaryWindow, Window},
 };
 use bevy_egui::{egui, EguiContexts};
 use bevy_rapier3d::prelude::{CollisionGroups, QueryFilter, RapierContext};
@@ -24,8 +24,8 @@ use crate::{
     },
     events::{CharacterSelectEvent, GameConnectionEvent, LoadZoneEvent, WorldConnectionEvent},
     resources::{
-        AppState, CharacterList, CharacterSelectState, GameData, ServerConfiguration,
-        WorldConnection,
+        AppState, CharacterList, CharacterSelectState, CharacterSlots, CharacterSwitch, GameData,
+        RenderConfiguration, ServerConfiguration, WorldConnection,
     },
     systems::{FreeCamera, OrbitCamera},
 };
@@ -47,9 +47,10 @@ pub fn character_select_enter_system(
     query_cameras: Query<Entity, With<Camera3d>>,
     asset_server: Res<AssetServer>,
     game_data: Res<GameData>,
+    render_configuration: Res<RenderConfiguration>,
 ) {
     if let Ok(mut window) = query_window.get_single_mut() {
-        window.cursor.grab_mode = CursorGrabMode::None;
+        window.cursor.grab_mode = render_configuration.cursor_grab_mode();
         window.cursor.visible = true;
     }
 
@@ -97,6 +98,7 @@ pub fn character_select_exit_system(
     }
 
     commands.remove_resource::<CharacterList>();
+    commands.remove_resource::<CharacterSlots>();
     commands.remove_resource::<CharacterSelectState>();
     commands.remove_resource::<CharacterSelectModelList>();
 }
@@ -166,6 +168,7 @@ pub fn character_select_system(
     world_connection: Option<Res<WorldConnection>>,
     mut character_list: Option<ResMut<CharacterList>>,
     server_configuration: Res<ServerConfiguration>,
+    character_switch: Res<CharacterSwitch>,
     asset_server: Res<AssetServer>,
 ) {
     let character_select_state = &mut *character_select_state;
@@ -257,6 +260,7 @@ pub fn character_select_system(
             let (_, _, _, camera_motion) = query_camera.single();
             if camera_motion.map_or(true, |animation| animation.completed())
                 || server_configuration.auto_login
+                || character_switch.character_name.is_some()
             {
                 *character_select_state = CharacterSelectState::CharacterSelect(None);
             }
@@ -306,6 +310,38 @@ pub fn character_select_system(
     }
 }
 
+pub fn character_switch_system(
+    character_select_state: Res<CharacterSelectState>,
+    character_list: Option<Res<CharacterList>>,
+    mut character_switch: ResMut<CharacterSwitch>,
+    mut character_select_events: EventWriter<CharacterSelectEvent>,
+) {
+    if character_switch.character_name.is_none()
+        || !matches!(
+            *character_select_state,
+            CharacterSelectState::CharacterSelect(_)
+        )
+    {
+        return;
+    }
+
+    let Some(character_list) = character_list else {
+        return;
+    };
+    let Some(character_name) = character_switch.character_name.take() else {
+        return;
+    };
+
+    if let Some(index) = character_list
+        .characters
+        .iter()
+        .position(|character| character.info.name == character_name)
+    {
+        character_select_events.send(CharacterSelectEvent::SelectCharacter(index));
+        character_select_events.send(CharacterSelectEvent::PlaySelected);
+    }
+}
+
 pub fn character_select_event_system(
     mut commands: Commands,
     mut character_select_state: ResMut<CharacterSelectState>,
@@ -371,6 +407,11 @@ pub fn character_select_event_system(
                     }
                 }
             }
+            &CharacterSelectEvent::PurchaseSlot(slot) => {
+                if let Some(world_connection) = world_connection.as_ref() {
+                    world_connection.purchase_character_slot_tx.send(slot).ok();
+                }
+            }
             CharacterSelectEvent::Disconnect => {
                 commands.remove_resource::<WorldConnection>();
             }
@@ -0,0 +1,106 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::{AssetServer, EventReader, EventWriter, Query, Res, With},
+};
+
+use rose_game_common::{components::Level, messages::client::ClientMessage};
+
+use crate::{
+    components::{ClientEntityName, PlayerCharacter, Position},
+    events::{ChatCommandEvent, ChatboxEvent},
+    resources::{CurrentZone, GameConnection, GameData, UiResources, ZoneTime},
+    ui::expand_chat_macro,
+};
+
+pub fn chat_command_system(
+    mut chat_command_events: EventReader<ChatCommandEvent>,
+    mut chatbox_events: EventWriter<ChatboxEvent>,
+    query_player: Query<(&Position, &Level, &ClientEntityName), With<PlayerCharacter>>,
+    asset_server: Res<AssetServer>,
+    current_zone: Option<Res<CurrentZone>>,
+    diagnostics: Res<DiagnosticsStore>,
+    game_connection: Option<Res<GameConnection>>,
+    game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
+    zone_time: Res<ZoneTime>,
+) {
+    let zone_name = current_zone
+        .as_ref()
+        .map_or("Unknown zone", |current_zone| {
+            game_data
+                .zone_list
+                .get_zone(current_zone.id)
+                .map_or("Unknown zone", |zone_data| zone_data.name)
+        });
+
+    for event in chat_command_events.iter() {
+        let message = match event {
+            ChatCommandEvent::Fps => {
+                let fps = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS);
+                match (
+                    fps.and_then(|fps| fps.value()),
+                    fps.and_then(|fps| fps.average()),
+                ) {
+                    (Some(fps), Some(average)) => {
+                        format!("FPS: {:.1} (average {:.1})", fps, average)
+                    }
+                    _ => "FPS is not available yet.".to_string(),
+                }
+            }
+            ChatCommandEvent::ReloadUi => {
+                // Dialogs reload their sprites when the asset is modified
+                for filename in ui_resources.dialog_files.keys() {
+                    asset_server.reload_asset(format!("3DDATA/CONTROL/XML/{}", filename));
+                }
+                format!("Reloading {} UI dialogs.", ui_resources.dialog_files.len())
+            }
+            ChatCommandEvent::Time => {
+                let local_time = chrono::Local::now();
+                format!(
+                    "Local time: {}, zone time: {} ({:?})",
+                    local_time.format("%H:%M:%S"),
+                    zone_time.time,
+                    zone_time.state
+                )
+            }
+            ChatCommandEvent::Where => {
+                let Some(current_zone) = current_zone.as_ref() else {
+                    continue;
+                };
+
+                if let Ok((position, _, _)) = query_player.get_single() {
+                    format!(
+                        "{} ({}) at {:.0}, {:.0}",
+                        zone_name,
+                        current_zone.id.get(),
+                        position.x,
+                        position.y
+                    )
+                } else {
+                    format!("{} ({})", zone_name, current_zone.id.get())
+                }
+            }
+            ChatCommandEvent::Macro(text) => {
+                let Ok((_, level, name)) = query_player.get_single() else {
+                    continue;
+                };
+                let text = expand_chat_macro(text, &name.name, level.level, zone_name);
+
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::Chat { text })
+                        .ok();
+                }
+                continue;
+            }
+            // Handled by the chatbox
+            ChatCommandEvent::ClanChat(_)
+            | ChatCommandEvent::Clear
+            | ChatCommandEvent::Help(_)
+            | ChatCommandEvent::PlayerShop => continue,
+        };
+
+        chatbox_events.send(ChatboxEvent::System(message));
+    }
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        Input,
+    },
+    prelude::{
+        Entity, EventReader, KeyCode, Local, MouseButton, Query, Res, ResMut, State, Time,
+        Visibility, With,
+    },
+    winit::{UpdateMode, WinitSettings},
+};
+use bevy_egui::EguiContexts;
+
+use crate::{
+    components::NameTag,
+    resources::{AppState, CinematicMode},
+    systems::OrbitCamera,
+};
+
+// Frame rate while the idle camera is active, to reduce GPU use for parked characters
+const IDLE_CAMERA_FPS: f32 = 15.0;
+
+// How quickly PageUp / PageDown change the orbit speed, in degrees per second per second
+const ORBIT_SPEED_CHANGE_RATE: f32 = 30.0;
+
+#[allow(clippy::too_many_arguments)]
+pub fn cinematic_mode_system(
+    mut cinematic_mode: ResMut<CinematicMode>,
+    mut query_orbit_camera: Query<&mut OrbitCamera>,
+    mut query_name_tags: Query<(Entity, &mut Visibility), With<NameTag>>,
+    mut egui_context: EguiContexts,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    winit_settings: Option<ResMut<WinitSettings>>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    app_state: Res<State<AppState>>,
+    time: Res<Time>,
+    mut synced_orbit_radius: Local<Option<f32>>,
+) {
+    let cinematic_mode = &mut *cinematic_mode;
+    let in_game = matches!(app_state.get(), AppState::Game);
+
+    let mouse_moved = mouse_motion_events.iter().count() > 0;
+    let mouse_scrolled = mouse_wheel_events.iter().count() > 0;
+    let has_input = mouse_moved
+        || mouse_scrolled
+        || keyboard.get_pressed().next().is_some()
+        || mouse_buttons.get_pressed().next().is_some();
+
+    if has_input {
+        cinematic_mode.idle_time = 0.0;
+    } else {
+        cinematic_mode.idle_time += time.delta_seconds();
+    }
+
+    // Always leave cinematic mode when leaving the game so the UI is visible
+    let toggle = if !in_game {
+        cinematic_mode.enabled
+    } else if cinematic_mode.idle {
+        // Any input instantly restores from the idle camera
+        has_input
+    } else if keyboard.just_pressed(KeyCode::F10) && !egui_context.ctx_mut().wants_keyboard_input()
+    {
+        true
+    } else {
+        !cinematic_mode.enabled
+            && cinematic_mode.idle_delay > 0.0
+            && cinematic_mode.idle_time >= cinematic_mode.idle_delay
+    };
+
+    if toggle {
+        cinematic_mode.enabled = !cinematic_mode.enabled;
+        cinematic_mode.idle = cinematic_mode.enabled && !has_input;
+
+        if let Ok(mut orbit_camera) = query_orbit_camera.get_single_mut() {
+            if cinematic_mode.enabled {
+                cinematic_mode.saved_follow_distance = Some(orbit_camera.follow_distance);
+                orbit_camera.follow_distance = cinematic_mode.orbit_radius;
+                *synced_orbit_radius = Some(cinematic_mode.orbit_radius);
+            } else if let Some(follow_distance) = cinematic_mode.saved_follow_distance.take() {
+                orbit_camera.follow_distance = follow_distance;
+            }
+        }
+
+        if let Some(mut winit_settings) = winit_settings {
+            if cinematic_mode.idle {
+                cinematic_mode.saved_update_mode = Some(winit_settings.focused_mode);
+                winit_settings.focused_mode = UpdateMode::ReactiveLowPower {
+                    wait: Duration::from_secs_f32(1.0 / IDLE_CAMERA_FPS),
+                };
+            } else if let Some(update_mode) = cinematic_mode.saved_update_mode.take() {
+                winit_settings.focused_mode = update_mode;
+            }
+        }
+
+        if !cinematic_mode.enabled {
+            for (entity, visibility) in cinematic_mode.saved_name_tag_visibility.drain(..) {
+                if let Ok((_, mut name_tag_visibility)) = query_name_tags.get_mut(entity) {
+                    *name_tag_visibility = visibility;
+                }
+            }
+        }
+    }
+
+    if !cinematic_mode.enabled {
+        return;
+    }
+
+    let orbit_speed_change = ORBIT_SPEED_CHANGE_RATE * time.delta_seconds();
+    if keyboard.pressed(KeyCode::PageUp) {
+        cinematic_mode.orbit_speed = (cinematic_mode.orbit_speed + orbit_speed_change).min(90.0);
+    }
+
+    if keyboard.pressed(KeyCode::PageDown) {
+        cinematic_mode.orbit_speed = (cinematic_mode.orbit_speed - orbit_speed_change).max(-90.0);
+    }
+
+    // Keep the orbit radius in sync with the camera distance, a changed radius
+    // comes from the settings window and anything else is a mouse wheel zoom
+    if !cinematic_mode.idle {
+        if let Ok(mut orbit_camera) = query_orbit_camera.get_single_mut() {
+            if *synced_orbit_radius != Some(cinematic_mode.orbit_radius) {
+                orbit_camera.follow_distance = cinematic_mode.orbit_radius;
+            } else if orbit_camera.follow_distance != cinematic_mode.orbit_radius {
+                cinematic_mode.orbit_radius = orbit_camera.follow_distance;
+            }
+            *synced_orbit_radius = Some(cinematic_mode.orbit_radius);
+        }
+    }
+
+    // Hide name tags every frame, as new name tags are spawned when entities come into view
+    for (entity, mut visibility) in query_name_tags.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        if !cinematic_mode
+            .saved_name_tag_visibility
+            .iter()
+            .any(|(saved_entity, _)| *saved_entity == entity)
+        {
+            cinematic_mode
+                .saved_name_tag_visibility
+                .push((entity, *visibility));
+        }
+        *visibility = Visibility::Hidden;
+    }
+}
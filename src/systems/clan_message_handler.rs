@@ -0,0 +1,312 @@
+use bevy::{ecs::event::Events, prelude::World};
+
+use rose_game_common::{
+    components::ClanPoints,
+    messages::{
+        client::ClientMessage,
+        server::{ClanCreateError, ServerMessage},
+    },
+};
+
+use crate::{
+    components::{Clan, ClanMember, ClanMembership, ClanWar},
+    events::MessageBoxEvent,
+    protocol::ExtendedServerMessage,
+    resources::{
+        ClientEntityList, ClientEntityRegistry, ExtendedServerMessageKind, GameData,
+        PendingClanInvite, PendingClanInvites, ServerMessageKind, WorldConnection,
+        CLAN_INVITE_TIMEOUT,
+    },
+};
+
+pub const CLAN_SERVER_MESSAGES: [ServerMessageKind; 14] = [
+    ServerMessageKind::ClanInfo,
+    ServerMessageKind::ClanUpdateInfo,
+    ServerMessageKind::CharacterUpdateClan,
+    ServerMessageKind::ClanMemberConnected,
+    ServerMessageKind::ClanMemberDisconnected,
+    ServerMessageKind::ClanCreateError,
+    ServerMessageKind::ClanMemberList,
+    ServerMessageKind::ClanInvited,
+    ServerMessageKind::ClanInviteResult,
+    ServerMessageKind::ClanMemberJoined,
+    ServerMessageKind::ClanMemberLeft,
+    ServerMessageKind::ClanMemberKicked,
+    ServerMessageKind::ClanKicked,
+    ServerMessageKind::ClanDisbanded,
+];
+
+pub const CLAN_EXTENDED_SERVER_MESSAGES: [ExtendedServerMessageKind; 3] = [
+    ExtendedServerMessageKind::ClanNotices,
+    ExtendedServerMessageKind::ClanWars,
+    ExtendedServerMessageKind::ClanMemberLastOnline,
+];
+
+fn clear_visible_character_clan_membership_by_name(world: &mut World, name: &str) {
+    let Some(entity) = world
+        .resource::<ClientEntityRegistry>()
+        .find_character(name)
+    else {
+        return;
+    };
+
+    if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+        entity_mut.remove::<ClanMembership>();
+    }
+}
+
+fn remove_clan_member(world: &mut World, name: &str) {
+    if let Some(player_entity) = world.resource::<ClientEntityList>().player_entity {
+        if let Some(mut clan) = world.get_mut::<Clan>(player_entity) {
+            clan.members.retain(|member| member.name != name);
+        }
+    }
+
+    clear_visible_character_clan_membership_by_name(world, name);
+}
+
+fn leave_clan(world: &mut World) {
+    if let Some(player_entity) = world.resource::<ClientEntityList>().player_entity {
+        if let Some(mut entity_mut) = world.get_entity_mut(player_entity) {
+            entity_mut.remove::<Clan>();
+            entity_mut.remove::<ClanMembership>();
+        }
+    }
+}
+
+pub fn clan_message_handler(message: &ServerMessage, world: &mut World) {
+    let player_entity = world.resource::<ClientEntityList>().player_entity;
+
+    match message {
+        ServerMessage::ClanInfo {
+            id,
+            mark,
+            level,
+            points,
+            money,
+            name,
+            description,
+            position,
+            contribution,
+            skills,
+        } => {
+            let Some(mut entity_mut) =
+                player_entity.and_then(|entity| world.get_entity_mut(entity))
+            else {
+                return;
+            };
+            // Notices and wars are sent separately and may arrive before the clan info is updated
+            let (notices, wars) = entity_mut
+                .get::<Clan>()
+                .filter(|clan| clan.unique_id == *id)
+                .map(|clan| (clan.notices.clone(), clan.wars.clone()))
+                .unwrap_or_default();
+            entity_mut.insert((
+                Clan {
+                    unique_id: *id,
+                    name: name.clone(),
+                    description: description.clone(),
+                    mark: *mark,
+                    money: *money,
+                    points: *points,
+                    level: *level,
+                    members: Vec::new(),
+                    skills: skills.clone(),
+                    notices,
+                    wars,
+                },
+                ClanMembership {
+                    clan_unique_id: *id,
+                    mark: *mark,
+                    level: *level,
+                    name: name.clone(),
+                    position: *position,
+                    contribution: *contribution,
+                },
+            ));
+        }
+        ServerMessage::ClanUpdateInfo {
+            id,
+            mark,
+            level,
+            points,
+            money,
+            description,
+            skills,
+        } => {
+            let Some(mut clan) = player_entity.and_then(|entity| world.get_mut::<Clan>(entity))
+            else {
+                return;
+            };
+            clan.unique_id = *id;
+            clan.mark = *mark;
+            clan.level = *level;
+            clan.points = *points;
+            clan.money = *money;
+            clan.description = description.clone();
+            clan.skills = skills.clone();
+        }
+        ServerMessage::CharacterUpdateClan {
+            client_entity_id,
+            id,
+            name,
+            mark,
+            level,
+            position,
+        } => {
+            let entity = world.resource::<ClientEntityList>().get(*client_entity_id);
+            let Some(mut entity_mut) = entity.and_then(|entity| world.get_entity_mut(entity))
+            else {
+                return;
+            };
+            entity_mut.insert(ClanMembership {
+                clan_unique_id: *id,
+                mark: *mark,
+                level: *level,
+                name: name.clone(),
+                position: *position,
+                contribution: ClanPoints(0),
+            });
+        }
+        ServerMessage::ClanMemberConnected { name, channel_id } => {
+            if let Some(mut clan) = player_entity.and_then(|entity| world.get_mut::<Clan>(entity)) {
+                if let Some(member) = clan.find_member_mut(name) {
+                    member.channel_id = Some(*channel_id);
+                }
+            }
+        }
+        ServerMessage::ClanMemberDisconnected { name } => {
+            if let Some(mut clan) = player_entity.and_then(|entity| world.get_mut::<Clan>(entity)) {
+                if let Some(member) = clan.find_member_mut(name) {
+                    member.channel_id = None;
+                }
+            }
+        }
+        ServerMessage::ClanCreateError { error } => {
+            let client_strings = &world.resource::<GameData>().client_strings;
+            let message = match error {
+                ClanCreateError::Failed => client_strings.clan_create_error,
+                ClanCreateError::NameExists => client_strings.clan_create_error_name,
+                ClanCreateError::NoPermission => client_strings.clan_create_error_permission,
+                ClanCreateError::UnmetCondition => client_strings.clan_create_error_condition,
+            }
+            .to_string();
+
+            world
+                .resource_mut::<Events<MessageBoxEvent>>()
+                .send(MessageBoxEvent::Show {
+                    message,
+                    modal: false,
+                    ok: None,
+                    cancel: None,
+                });
+        }
+        ServerMessage::ClanMemberList { members } => {
+            if let Some(mut clan) = player_entity.and_then(|entity| world.get_mut::<Clan>(entity)) {
+                // The last online times are sent separately, keep those we already have
+                let previous_members = std::mem::take(&mut clan.members);
+
+                for member in members.iter() {
+                    let last_online = previous_members
+                        .iter()
+                        .find(|previous| previous.name == member.name)
+                        .and_then(|previous| previous.last_online);
+
+                    clan.members.push(ClanMember {
+                        name: member.name.clone(),
+                        position: member.position,
+                        contribution: member.contribution,
+                        level: member.level,
+                        job: member.job,
+                        channel_id: member.channel_id,
+                        last_online,
+                    });
+                }
+            }
+        }
+        ServerMessage::ClanInvited {
+            name,
+            clan_unique_id,
+            clan_level,
+            clan_name,
+            ..
+        } => {
+            // A repeated invite from the same player replaces the old one and restarts its timer
+            let mut pending_clan_invites = world.resource_mut::<PendingClanInvites>();
+            pending_clan_invites
+                .invites
+                .retain(|invite| &invite.inviter_name != name);
+            pending_clan_invites.invites.push(PendingClanInvite {
+                inviter_name: name.clone(),
+                clan_name: clan_name.clone(),
+                clan_unique_id: *clan_unique_id,
+                clan_level: *clan_level,
+                remaining_time: CLAN_INVITE_TIMEOUT,
+            });
+        }
+        ServerMessage::ClanInviteResult { response } => {
+            log::info!("Received clan invite result: {:?}", response);
+            // TODO: Show invite result message to user
+        }
+        ServerMessage::ClanMemberJoined { name } => {
+            log::info!("Clan member joined: {}", name);
+            if let Some(world_connection) = world.get_resource::<WorldConnection>() {
+                world_connection
+                    .client_message_tx
+                    .send(ClientMessage::ClanGetMemberList)
+                    .ok();
+            }
+        }
+        ServerMessage::ClanMemberLeft { name } => {
+            log::info!("Clan member left: {}", name);
+            remove_clan_member(world, name);
+        }
+        ServerMessage::ClanMemberKicked { name } => {
+            log::info!("Clan member kicked: {}", name);
+            remove_clan_member(world, name);
+        }
+        ServerMessage::ClanKicked => {
+            log::info!("You have been kicked from the clan");
+            leave_clan(world);
+        }
+        ServerMessage::ClanDisbanded => {
+            log::info!("Your clan has been disbanded");
+            leave_clan(world);
+        }
+        _ => {}
+    }
+}
+
+pub fn clan_extended_message_handler(message: &ExtendedServerMessage, world: &mut World) {
+    let player_entity = world.resource::<ClientEntityList>().player_entity;
+
+    let Some(mut clan) = player_entity.and_then(|entity| world.get_mut::<Clan>(entity)) else {
+        return;
+    };
+
+    match message {
+        ExtendedServerMessage::ClanNotices(notices) => {
+            clan.notices = notices.clone();
+        }
+        ExtendedServerMessage::ClanWars(wars) => {
+            let now = chrono::Utc::now().timestamp();
+            clan.wars = wars
+                .iter()
+                .map(|war| ClanWar {
+                    opponent: war.opponent.clone(),
+                    ends: now + war.remaining_time.as_secs() as i64,
+                    kills: war.kills,
+                    deaths: war.deaths,
+                })
+                .collect();
+        }
+        ExtendedServerMessage::ClanMemberLastOnline(last_online) => {
+            for member_last_online in last_online.iter() {
+                if let Some(member) = clan.find_member_mut(&member_last_online.name) {
+                    member.last_online = Some(member_last_online.timestamp);
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::{Changed, Entity, Or, Query, RemovedComponents, ResMut};
+
+use crate::{
+    components::{ClientEntity, ClientEntityName},
+    resources::{ClientEntityRegistry, ClientEntityRegistryEntry},
+};
+
+pub fn client_entity_registry_system(
+    mut client_entity_registry: ResMut<ClientEntityRegistry>,
+    query_changed: Query<
+        (Entity, &ClientEntity, &ClientEntityName),
+        Or<(Changed<ClientEntity>, Changed<ClientEntityName>)>,
+    >,
+    mut removed_client_entities: RemovedComponents<ClientEntity>,
+) {
+    for entity in removed_client_entities.iter() {
+        client_entity_registry.remove(entity);
+    }
+
+    for (entity, client_entity, client_entity_name) in query_changed.iter() {
+        client_entity_registry.insert(
+            entity,
+            ClientEntityRegistryEntry {
+                id: client_entity.id,
+                entity_type: client_entity.entity_type,
+                name: client_entity_name.name.clone(),
+            },
+        );
+    }
+}
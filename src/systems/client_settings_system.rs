@@ -0,0 +1,196 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::{Local, Query, ResMut};
+
+use crate::{
+    audio::SoundGain,
+    components::{NameTagType, SoundCategory},
+    resources::{
+        CinematicMode, ClientSettings, CrowdSettings, NameTagSettings, SoundSettings,
+        TextureStreaming,
+    },
+};
+
+// Wait for settings to stop changing before saving, e.g. while dragging a volume slider
+const CLIENT_SETTINGS_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+pub struct ClientSettingsState {
+    initialised: bool,
+    pending_save: Option<Instant>,
+}
+
+// Settings can also be changed outside of the settings window, e.g. by hotkeys
+// or by account settings sync, so read back the current values of each resource
+fn capture_client_settings(
+    client_settings: &ClientSettings,
+    sound_settings: &SoundSettings,
+    texture_streaming: &TextureStreaming,
+    name_tag_settings: &NameTagSettings,
+    crowd_settings: &CrowdSettings,
+    cinematic_mode: &CinematicMode,
+) -> ClientSettings {
+    let mut settings = client_settings.clone();
+
+    let graphics = &mut settings.graphics;
+    graphics.texture_budget_mb = texture_streaming
+        .budget
+        .map(|budget| budget / (1024 * 1024));
+    graphics.texture_high_detail_distance = texture_streaming.high_detail_distance;
+    graphics.show_texture_overlay = texture_streaming.show_overlay;
+    graphics.crowd_mode = crowd_settings.enabled;
+    graphics.crowd_max_detailed_players = crowd_settings.max_detailed_players;
+    graphics.crowd_detailed_distance = crowd_settings.detailed_distance;
+
+    let audio = &mut settings.audio;
+    audio.enabled = sound_settings.enabled;
+    audio.global = sound_settings.global_gain;
+    audio.background_music = sound_settings.gains[SoundCategory::BackgroundMusic];
+    audio.player_footstep = sound_settings.gains[SoundCategory::PlayerFootstep];
+    audio.other_footstep = sound_settings.gains[SoundCategory::OtherFootstep];
+    audio.player_combat = sound_settings.gains[SoundCategory::PlayerCombat];
+    audio.other_combat = sound_settings.gains[SoundCategory::OtherCombat];
+    audio.npc_sounds = sound_settings.gains[SoundCategory::NpcSounds];
+    audio.ui_sounds = sound_settings.gains[SoundCategory::Ui];
+
+    let interface = &mut settings.interface;
+    interface.show_character_names = name_tag_settings.show_all[NameTagType::Character];
+    interface.show_npc_names = name_tag_settings.show_all[NameTagType::Npc];
+    interface.show_summon_names = name_tag_settings.show_all[NameTagType::Summon];
+    interface.name_tag_font_size = name_tag_settings.font_size[NameTagType::Character];
+    interface.show_chat_bubbles = name_tag_settings.show_chat_bubbles;
+
+    settings.controls.cinematic_orbit_speed = cinematic_mode.orbit_speed;
+    settings.controls.cinematic_orbit_radius = cinematic_mode.orbit_radius;
+    settings.controls.idle_camera_minutes = (cinematic_mode.idle_delay / 60.0).round() as u32;
+
+    settings.social.hide_other_players = crowd_settings.hide_other_players;
+
+    let combat = &mut settings.combat;
+    combat.show_monster_names = name_tag_settings.show_all[NameTagType::Monster];
+    combat.show_party_health = name_tag_settings.show_party_health;
+    combat.show_clan_health = name_tag_settings.show_clan_health;
+    combat.simple_level_colors = name_tag_settings.simple_level_colors;
+
+    settings
+}
+
+fn apply_client_settings(
+    settings: &ClientSettings,
+    sound_settings: &mut SoundSettings,
+    texture_streaming: &mut TextureStreaming,
+    name_tag_settings: &mut NameTagSettings,
+    crowd_settings: &mut CrowdSettings,
+    cinematic_mode: &mut CinematicMode,
+) {
+    let graphics = &settings.graphics;
+    texture_streaming.budget = graphics
+        .texture_budget_mb
+        .map(|budget_mb| budget_mb * 1024 * 1024);
+    texture_streaming.high_detail_distance = graphics.texture_high_detail_distance;
+    texture_streaming.show_overlay = graphics.show_texture_overlay;
+    crowd_settings.enabled = graphics.crowd_mode;
+    crowd_settings.max_detailed_players = graphics.crowd_max_detailed_players;
+    crowd_settings.detailed_distance = graphics.crowd_detailed_distance;
+
+    let audio = &settings.audio;
+    sound_settings.enabled = audio.enabled;
+    sound_settings.global_gain = audio.global;
+    sound_settings.gains[SoundCategory::BackgroundMusic] = audio.background_music;
+    sound_settings.gains[SoundCategory::PlayerFootstep] = audio.player_footstep;
+    sound_settings.gains[SoundCategory::OtherFootstep] = audio.other_footstep;
+    sound_settings.gains[SoundCategory::PlayerCombat] = audio.player_combat;
+    sound_settings.gains[SoundCategory::OtherCombat] = audio.other_combat;
+    sound_settings.gains[SoundCategory::NpcSounds] = audio.npc_sounds;
+    sound_settings.gains[SoundCategory::Ui] = audio.ui_sounds;
+
+    let interface = &settings.interface;
+    name_tag_settings.show_all[NameTagType::Character] = interface.show_character_names;
+    name_tag_settings.show_all[NameTagType::Npc] = interface.show_npc_names;
+    name_tag_settings.show_all[NameTagType::Summon] = interface.show_summon_names;
+    for font_size in name_tag_settings.font_size.values_mut() {
+        *font_size = interface.name_tag_font_size;
+    }
+    name_tag_settings.show_chat_bubbles = interface.show_chat_bubbles;
+
+    cinematic_mode.orbit_speed = settings.controls.cinematic_orbit_speed;
+    cinematic_mode.orbit_radius = settings.controls.cinematic_orbit_radius;
+    cinematic_mode.idle_delay = settings.controls.idle_camera_minutes as f32 * 60.0;
+
+    crowd_settings.hide_other_players = settings.social.hide_other_players;
+
+    let combat = &settings.combat;
+    name_tag_settings.show_all[NameTagType::Monster] = combat.show_monster_names;
+    name_tag_settings.show_party_health = combat.show_party_health;
+    name_tag_settings.show_clan_health = combat.show_clan_health;
+    name_tag_settings.simple_level_colors = combat.simple_level_colors;
+}
+
+pub fn client_settings_system(
+    mut state: Local<ClientSettingsState>,
+    mut client_settings: ResMut<ClientSettings>,
+    mut sound_settings: ResMut<SoundSettings>,
+    mut texture_streaming: ResMut<TextureStreaming>,
+    mut name_tag_settings: ResMut<NameTagSettings>,
+    mut crowd_settings: ResMut<CrowdSettings>,
+    mut cinematic_mode: ResMut<CinematicMode>,
+    mut query_sounds: Query<(&SoundCategory, &mut SoundGain)>,
+) {
+    let current = capture_client_settings(
+        &client_settings,
+        &sound_settings,
+        &texture_streaming,
+        &name_tag_settings,
+        &crowd_settings,
+        &cinematic_mode,
+    );
+
+    if !state.initialised {
+        // Without a settings file we start with the values from config.toml
+        if !client_settings.loaded {
+            *client_settings.bypass_change_detection() = current;
+        } else if current != *client_settings {
+            apply_client_settings(
+                &client_settings,
+                &mut sound_settings,
+                &mut texture_streaming,
+                &mut name_tag_settings,
+                &mut crowd_settings,
+                &mut cinematic_mode,
+            );
+        }
+        state.initialised = true;
+    } else if client_settings.is_changed() {
+        if current != *client_settings {
+            apply_client_settings(
+                &client_settings,
+                &mut sound_settings,
+                &mut texture_streaming,
+                &mut name_tag_settings,
+                &mut crowd_settings,
+                &mut cinematic_mode,
+            );
+        }
+        state.pending_save = Some(Instant::now());
+    } else if current != *client_settings {
+        *client_settings = current;
+        state.pending_save = Some(Instant::now());
+    }
+
+    if sound_settings.is_changed() {
+        for (category, mut gain) in query_sounds.iter_mut() {
+            let target_gain = sound_settings.gain(*category);
+
+            if target_gain != *gain {
+                *gain = target_gain;
+            }
+        }
+    }
+
+    if state.pending_save.map_or(false, |changed| {
+        changed.elapsed() >= CLIENT_SETTINGS_SAVE_DELAY
+    }) {
+        client_settings.save();
+        state.pending_save = None;
+    }
+}
@@ -1,7 +1,8 @@
 use bevy::{
     math::{Quat, Vec3},
     prelude::{
-        Assets, Changed, Commands, Entity, EventWriter, Or, Query, Res, Time, Transform, With,
+        Assets, Changed, Commands, Entity, EventWriter, Or, Query, Res, ResMut, Time, Transform,
+        With,
     },
 };
 use bevy_rapier3d::prelude::{Collider, CollisionGroups, Group, QueryFilter, RapierContext};
@@ -16,7 +17,10 @@ use crate::{
         COLLISION_GROUP_ZONE_TERRAIN, COLLISION_GROUP_ZONE_WARP_OBJECT,
     },
     events::QuestTriggerEvent,
-    resources::{CurrentZone, GameConnection},
+    resources::{
+        CurrentZone, DebugRenderConfig, DebugRenderPlayerCollision, DebugRenderRaycast,
+        GameConnection,
+    },
     zone_loader::ZoneLoaderAsset,
 };
 
@@ -150,7 +154,13 @@ pub fn collision_player_system(
     rapier_context: Res<RapierContext>,
     time: Res<Time>,
     zone_loader_assets: Res<Assets<ZoneLoaderAsset>>,
+    debug_render_config: Res<DebugRenderConfig>,
+    mut debug_render_player_collision: ResMut<DebugRenderPlayerCollision>,
 ) {
+    // Only draw the casts from this update, the movement cast is skipped when not moving
+    debug_render_player_collision.ground_ray = None;
+    debug_render_player_collision.movement_cast = None;
+
     let current_zone = if let Some(current_zone) = current_zone {
         current_zone
     } else {
@@ -175,8 +185,7 @@ pub fn collision_player_system(
         if translation_delta.length() > 0.00001 {
             let cast_origin = transform.translation + Vec3::new(0.0, 1.2, 0.0);
             let cast_direction = translation_delta.normalize();
-
-            if let Some((_, distance)) = rapier_context.cast_shape(
+            let cast_hit = rapier_context.cast_shape(
                 cast_origin + cast_direction * collider_radius,
                 Quat::default(),
                 cast_direction,
@@ -186,7 +195,19 @@ pub fn collision_player_system(
                     COLLISION_FILTER_COLLIDABLE,
                     !COLLISION_GROUP_ZONE_TERRAIN & !COLLISION_GROUP_PHYSICS_TOY,
                 )),
-            ) {
+            );
+
+            if debug_render_config.player_movement_raycast {
+                debug_render_player_collision.movement_cast = Some(DebugRenderRaycast {
+                    origin: cast_origin + cast_direction * collider_radius,
+                    direction: cast_direction,
+                    max_distance: translation_delta.length(),
+                    hit_distance: cast_hit.map(|(_, distance)| distance.toi),
+                });
+                debug_render_player_collision.movement_cast_radius = collider_radius;
+            }
+
+            if let Some((_, distance)) = cast_hit {
                 let collision_translation =
                     cast_origin + translation_delta * (distance.toi - 0.1).max(0.0);
                 position.x = collision_translation.x * 100.0;
@@ -214,7 +235,7 @@ pub fn collision_player_system(
             -position.y / 100.0,
         );
         let ray_direction = Vec3::new(0.0, -1.0, 0.0);
-        let collision_height = if let Some((_, distance)) = rapier_context.cast_ray(
+        let ray_hit = rapier_context.cast_ray(
             ray_origin,
             ray_direction,
             1.35 + fall_distance,
@@ -223,11 +244,19 @@ pub fn collision_player_system(
                 COLLISION_FILTER_MOVEABLE,
                 !COLLISION_GROUP_PHYSICS_TOY,
             )),
-        ) {
-            Some((ray_origin + ray_direction * distance).y)
-        } else {
-            None
-        };
+        );
+
+        if debug_render_config.player_movement_raycast {
+            debug_render_player_collision.ground_ray = Some(DebugRenderRaycast {
+                origin: ray_origin,
+                direction: ray_direction,
+                max_distance: 1.35 + fall_distance,
+                hit_distance: ray_hit.map(|(_, distance)| distance),
+            });
+        }
+
+        let collision_height =
+            ray_hit.map(|(_, distance)| (ray_origin + ray_direction * distance).y);
 
         // We can never be below the heightmap
         let terrain_height = current_zone_data.get_terrain_height(position.x, position.y) / 100.0;
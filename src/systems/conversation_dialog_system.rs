@@ -16,7 +16,7 @@ use crate::{
         LuaGameConstants, LuaGameFunctions, LuaQuestFunctions, LuaUserValueEntity,
         ScriptFunctionContext, ScriptFunctionResources,
     },
-    ui::{widgets::Dialog, DataBindings, DialogInstance},
+    ui::{set_dialog_subtitle, widgets::Dialog, DataBindings, DialogInstance},
     VfsResource,
 };
 
@@ -529,11 +529,16 @@ pub fn conversation_dialog_system(
             + num_response_middles as f32 * dialog_sprites.answer_middle.height
             + dialog_sprites.answer_bottom.height;
 
-        let title = dialog_state
+        let speaker = dialog_state
             .owner_entity
             .and_then(|entity| query_name.get(entity).ok())
-            .map(|name| name.as_str())
-            .unwrap_or("Event Dialog");
+            .map(|name| name.as_str());
+        let title = speaker.unwrap_or("Event Dialog");
+        set_dialog_subtitle(
+            egui_context.ctx_mut(),
+            speaker,
+            &dialog_state.generated_dialog.message.text,
+        );
 
         let mut response_close_button = None;
         let screen_size = egui_context
@@ -0,0 +1,148 @@
+use bevy::{
+    hierarchy::{Children, HierarchyQueryExt},
+    math::Vec3Swizzles,
+    prelude::{
+        Changed, Commands, DetectChangesMut, Entity, Or, Query, Res, Visibility, With, Without,
+    },
+};
+
+use crate::{
+    components::{
+        CharacterModel, CharacterModelPart, Clan, ClientEntity, ClientEntityName, ClientEntityType,
+        CrowdReduced, Effect, PartyMember, PlayerCharacter, Position, Vehicle, VehicleModel,
+    },
+    resources::CrowdSettings,
+};
+
+fn set_reduced_detail_visibility(
+    entity: Entity,
+    reduced: bool,
+    query_models: &Query<(Option<&CharacterModel>, Option<&Vehicle>)>,
+    query_vehicle_model: &Query<&VehicleModel>,
+    query_children: &Query<&Children>,
+    query_effect: &Query<(), With<Effect>>,
+    query_visibility: &mut Query<&mut Visibility>,
+) {
+    let mut hidden_entities: Vec<Entity> = query_children
+        .iter_descendants(entity)
+        .filter(|descendant| query_effect.contains(*descendant))
+        .collect();
+
+    if let Ok((character_model, vehicle)) = query_models.get(entity) {
+        if let Some(character_model) = character_model {
+            hidden_entities.extend(
+                character_model.model_parts[CharacterModelPart::FaceItem]
+                    .1
+                    .iter(),
+            );
+
+            // The back item is already hidden by vehicle_model_system while driving
+            if vehicle.is_none() {
+                hidden_entities.extend(
+                    character_model.model_parts[CharacterModelPart::Back]
+                        .1
+                        .iter(),
+                );
+            }
+        }
+
+        if let Some(vehicle_model) =
+            vehicle.and_then(|vehicle| query_vehicle_model.get(vehicle.vehicle_model_entity).ok())
+        {
+            for (_, model_parts) in vehicle_model.model_parts.values() {
+                hidden_entities.extend(model_parts.iter());
+            }
+        }
+    }
+
+    let visibility = if reduced {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for hidden_entity in hidden_entities {
+        if let Ok(mut entity_visibility) = query_visibility.get_mut(hidden_entity) {
+            entity_visibility.set_if_neq(visibility);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn crowd_density_system(
+    mut commands: Commands,
+    query_player: Query<(&Position, Option<&Clan>), With<PlayerCharacter>>,
+    query_characters: Query<
+        (
+            Entity,
+            &ClientEntity,
+            &ClientEntityName,
+            &Position,
+            Option<&PartyMember>,
+            Option<&CrowdReduced>,
+        ),
+        Without<PlayerCharacter>,
+    >,
+    query_changed_models: Query<(), Or<(Changed<CharacterModel>, Changed<Vehicle>)>>,
+    query_models: Query<(Option<&CharacterModel>, Option<&Vehicle>)>,
+    query_vehicle_model: Query<&VehicleModel>,
+    query_children: Query<&Children>,
+    query_effect: Query<(), With<Effect>>,
+    mut query_visibility: Query<&mut Visibility>,
+    crowd_settings: Res<CrowdSettings>,
+) {
+    let Ok((player_position, player_clan)) = query_player.get_single() else {
+        return;
+    };
+
+    // Party and clan members are never reduced, everyone else is prioritised by distance
+    let mut detailed_candidates: Vec<(Entity, f32)> = Vec::new();
+    if crowd_settings.enabled {
+        detailed_candidates = query_characters
+            .iter()
+            .filter(|(_, client_entity, name, _, party_member, _)| {
+                matches!(client_entity.entity_type, ClientEntityType::Character)
+                    && party_member.is_none()
+                    && player_clan.map_or(true, |clan| clan.find_member(&name.name).is_none())
+            })
+            .map(|(entity, _, _, position, _, _)| {
+                (entity, position.xy().distance(player_position.xy()))
+            })
+            .collect();
+        detailed_candidates.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    }
+
+    let reduced_entities: Vec<Entity> = detailed_candidates
+        .iter()
+        .enumerate()
+        .filter(|(index, (_, distance))| {
+            *index >= crowd_settings.max_detailed_players
+                || *distance > crowd_settings.detailed_distance * 100.0
+        })
+        .map(|(_, (entity, _))| *entity)
+        .collect();
+
+    for (entity, _, _, _, _, crowd_reduced) in query_characters.iter() {
+        let reduced = reduced_entities.contains(&entity);
+
+        if reduced != crowd_reduced.is_some() {
+            if reduced {
+                commands.entity(entity).insert(CrowdReduced);
+            } else {
+                commands.entity(entity).remove::<CrowdReduced>();
+            }
+        } else if !reduced || !query_changed_models.contains(entity) {
+            // Nothing changed, but parts of a reduced model must be hidden again after it is updated
+            continue;
+        }
+
+        set_reduced_detail_visibility(
+            entity,
+            reduced,
+            &query_models,
+            &query_vehicle_model,
+            &query_children,
+            &query_effect,
+            &mut query_visibility,
+        );
+    }
+}
@@ -53,6 +53,7 @@ impl Plugin for DebugInspectorPlugin {
             .register_type::<DamageType>()
             .register_type::<Dead>()
             .register_type::<DummyBoneOffset>()
+            .register_type::<DynamicPointLight>()
             .register_type::<Effect>()
             .register_type::<EffectMesh>()
             .register_type::<EffectParticle>()
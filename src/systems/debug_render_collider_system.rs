@@ -1,24 +1,184 @@
-use bevy::prelude::{Entity, Gizmos, GlobalTransform, Query, Res};
-use bevy_rapier3d::prelude::Collider;
+use bevy::prelude::{
+    Camera3d, Color, Entity, Gizmos, GlobalTransform, Quat, Query, Res, Vec3, With,
+};
+use bevy_rapier3d::prelude::{Collider, CollisionGroups};
 
-use crate::resources::DebugRenderConfig;
+use crate::{
+    components::{
+        COLLISION_GROUP_CHARACTER, COLLISION_GROUP_ITEM_DROP, COLLISION_GROUP_NPC,
+        COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_PLAYER, COLLISION_GROUP_ZONE_EVENT_OBJECT,
+        COLLISION_GROUP_ZONE_OBJECT, COLLISION_GROUP_ZONE_TERRAIN,
+        COLLISION_GROUP_ZONE_WARP_OBJECT, COLLISION_GROUP_ZONE_WATER,
+    },
+    resources::{DebugRenderConfig, DebugRenderPlayerCollision, DebugRenderRaycast},
+};
+
+// Terrain blocks are 160m wide with their origin at a corner, so allow for that when culling
+const MAX_COLLIDER_EXTENT: f32 = 250.0;
+
+fn color_for_collision_groups(collision_groups: Option<&CollisionGroups>) -> Color {
+    let memberships = if let Some(collision_groups) = collision_groups {
+        collision_groups.memberships
+    } else {
+        return Color::GRAY;
+    };
+
+    if memberships.intersects(COLLISION_GROUP_ZONE_TERRAIN) {
+        Color::DARK_GREEN
+    } else if memberships.intersects(COLLISION_GROUP_ZONE_WATER) {
+        Color::CYAN
+    } else if memberships.intersects(COLLISION_GROUP_ZONE_WARP_OBJECT) {
+        Color::FUCHSIA
+    } else if memberships.intersects(COLLISION_GROUP_ZONE_EVENT_OBJECT) {
+        Color::ORANGE
+    } else if memberships.intersects(COLLISION_GROUP_ZONE_OBJECT) {
+        Color::BLUE
+    } else if memberships.intersects(COLLISION_GROUP_PLAYER) {
+        Color::WHITE
+    } else if memberships.intersects(COLLISION_GROUP_CHARACTER) {
+        Color::YELLOW
+    } else if memberships.intersects(COLLISION_GROUP_NPC) {
+        Color::RED
+    } else if memberships.intersects(COLLISION_GROUP_ITEM_DROP) {
+        Color::GOLD
+    } else if memberships.intersects(COLLISION_GROUP_PHYSICS_TOY) {
+        Color::PINK
+    } else {
+        Color::GRAY
+    }
+}
+
+fn render_raycast(gizmos: &mut Gizmos, raycast: &DebugRenderRaycast, radius: Option<f32>) {
+    let end_distance = raycast.hit_distance.unwrap_or(raycast.max_distance);
+    let end = raycast.origin + raycast.direction * end_distance;
+    let color = if raycast.hit_distance.is_some() {
+        Color::RED
+    } else {
+        Color::GREEN
+    };
+
+    gizmos.line(raycast.origin, end, color);
+
+    if let Some(radius) = radius {
+        gizmos.sphere(raycast.origin, Quat::IDENTITY, radius, color);
+        gizmos.sphere(end, Quat::IDENTITY, radius, color);
+    } else if raycast.hit_distance.is_some() {
+        gizmos.sphere(end, Quat::IDENTITY, 0.05, color);
+    }
+}
+
+fn render_triangles(
+    gizmos: &mut Gizmos,
+    global_transform: &GlobalTransform,
+    triangles: impl Iterator<Item = (Vec3, Vec3, Vec3)>,
+    camera_position: Vec3,
+    max_distance: f32,
+    color: Color,
+) {
+    for (a, b, c) in triangles {
+        let a = global_transform.transform_point(a);
+        let b = global_transform.transform_point(b);
+        let c = global_transform.transform_point(c);
+
+        if ((a + b + c) / 3.0).distance(camera_position) > max_distance {
+            continue;
+        }
+
+        gizmos.linestrip([a, b, c, a], color);
+    }
+}
 
 pub fn debug_render_collider_system(
     debug_render_config: Res<DebugRenderConfig>,
-    query_colliders: Query<(Entity, &Collider, &GlobalTransform)>,
+    debug_render_player_collision: Res<DebugRenderPlayerCollision>,
+    query_colliders: Query<(
+        Entity,
+        &Collider,
+        &GlobalTransform,
+        Option<&CollisionGroups>,
+    )>,
+    query_camera: Query<&GlobalTransform, With<Camera3d>>,
     mut gizmos: Gizmos,
 ) {
-    if !debug_render_config.colliders {
+    if debug_render_config.player_movement_raycast {
+        if let Some(ground_ray) = debug_render_player_collision.ground_ray.as_ref() {
+            render_raycast(&mut gizmos, ground_ray, None);
+        }
+
+        if let Some(movement_cast) = debug_render_player_collision.movement_cast.as_ref() {
+            render_raycast(
+                &mut gizmos,
+                movement_cast,
+                Some(debug_render_player_collision.movement_cast_radius),
+            );
+        }
+    }
+
+    if !debug_render_config.colliders && !debug_render_config.collider_wireframes {
         return;
     }
 
-    for (entity, collider, global_transform) in query_colliders.iter() {
+    let camera_position = query_camera
+        .get_single()
+        .map_or(Vec3::ZERO, |camera_transform| {
+            camera_transform.translation()
+        });
+    let max_distance = debug_render_config.collider_wireframe_distance;
+
+    for (entity, collider, global_transform, collision_groups) in query_colliders.iter() {
+        let color = if debug_render_config.collider_wireframes {
+            color_for_collision_groups(collision_groups)
+        } else {
+            debug_render_config.color_for_entity(entity)
+        };
+
         if let Some(cuboid) = collider.as_cuboid() {
             let transform = global_transform
                 .compute_transform()
                 .with_scale(cuboid.half_extents() * 2.0);
-            let color = debug_render_config.color_for_entity(entity);
             gizmos.cuboid(transform, color);
         }
+
+        if !debug_render_config.collider_wireframes {
+            continue;
+        }
+
+        if let Some(ball) = collider.as_ball() {
+            gizmos.sphere(
+                global_transform.translation(),
+                Quat::IDENTITY,
+                ball.radius(),
+                color,
+            );
+        } else if let Some(capsule) = collider.as_capsule() {
+            let segment = capsule.segment();
+            let start = global_transform.transform_point(segment.a());
+            let end = global_transform.transform_point(segment.b());
+            gizmos.sphere(start, Quat::IDENTITY, capsule.radius(), color);
+            gizmos.sphere(end, Quat::IDENTITY, capsule.radius(), color);
+            gizmos.line(start, end, color);
+        } else if global_transform.translation().distance(camera_position)
+            > max_distance + MAX_COLLIDER_EXTENT
+        {
+            continue;
+        } else if let Some(trimesh) = collider.as_trimesh() {
+            render_triangles(
+                &mut gizmos,
+                global_transform,
+                trimesh.triangles(),
+                camera_position,
+                max_distance,
+                color,
+            );
+        } else if let Some(heightfield) = collider.as_heightfield() {
+            render_triangles(
+                &mut gizmos,
+                global_transform,
+                heightfield.triangles(),
+                camera_position,
+                max_distance,
+                color,
+            );
+        }
     }
 }
@@ -0,0 +1,81 @@
+use bevy::prelude::{
+    Camera3d, Commands, ComputedVisibility, Entity, GlobalTransform, Query, Res, ResMut, Time, With,
+};
+
+use crate::{
+    components::DynamicPointLight,
+    render::{ZoneLighting, ZoneLightingPointLight, ZONE_LIGHTING_MAX_POINT_LIGHTS},
+    resources::RenderConfiguration,
+};
+
+// Lights further than this from the camera are never considered
+const MAX_POINT_LIGHT_DISTANCE: f32 = 60.0;
+
+pub fn dynamic_point_light_system(
+    mut commands: Commands,
+    mut query_lights: Query<(
+        Entity,
+        &mut DynamicPointLight,
+        &GlobalTransform,
+        &ComputedVisibility,
+    )>,
+    query_camera: Query<&GlobalTransform, With<Camera3d>>,
+    render_configuration: Res<RenderConfiguration>,
+    mut zone_lighting: ResMut<ZoneLighting>,
+    time: Res<Time>,
+) {
+    zone_lighting.point_lights.clear();
+
+    let max_point_lights = render_configuration
+        .max_dynamic_point_lights
+        .min(ZONE_LIGHTING_MAX_POINT_LIGHTS);
+    let camera_position = query_camera
+        .get_single()
+        .ok()
+        .map(|camera_transform| camera_transform.translation());
+
+    let mut candidates = Vec::new();
+    for (entity, mut point_light, global_transform, computed_visibility) in query_lights.iter_mut()
+    {
+        if point_light.fade_duration.is_some() {
+            point_light.age += time.delta_seconds();
+
+            if point_light.is_expired() {
+                commands.entity(entity).remove::<DynamicPointLight>();
+                continue;
+            }
+        }
+
+        if max_point_lights == 0 || !computed_visibility.is_visible_in_hierarchy() {
+            continue;
+        }
+
+        let position = global_transform.translation();
+        let intensity = point_light.current_intensity();
+        let distance =
+            camera_position.map_or(0.0, |camera_position| camera_position.distance(position));
+        if intensity <= 0.0 || distance - point_light.radius > MAX_POINT_LIGHT_DISTANCE {
+            continue;
+        }
+
+        // Prefer lights which are near to the camera, bright, and large
+        let priority = intensity * point_light.radius / distance.max(1.0);
+        candidates.push((
+            priority,
+            ZoneLightingPointLight {
+                position,
+                radius: point_light.radius,
+                color: point_light.color,
+                intensity,
+            },
+        ));
+    }
+
+    candidates.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    zone_lighting.point_lights.extend(
+        candidates
+            .into_iter()
+            .take(max_point_lights)
+            .map(|(_, point_light)| point_light),
+    );
+}
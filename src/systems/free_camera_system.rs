@@ -12,6 +12,8 @@ use bevy::{
 use bevy_egui::EguiContexts;
 use dolly::prelude::{CameraRig, LeftHanded, Position, Smooth, YawPitch};
 
+use crate::resources::RenderConfiguration;
+
 #[derive(Component)]
 pub struct FreeCamera {
     pub rig: CameraRig<LeftHanded>,
@@ -51,6 +53,7 @@ pub fn free_camera_system(
     mouse_buttons: Res<Input<MouseButton>>,
     mut query_window: Query<&mut Window, With<PrimaryWindow>>,
     mut egui_ctx: EguiContexts,
+    render_configuration: Res<RenderConfiguration>,
 ) {
     let Ok(mut window) = query_window.get_single_mut() else {
         return;
@@ -65,7 +68,7 @@ pub fn free_camera_system(
                 window.set_cursor_position(Some(saved_cursor_position));
             }
 
-            window.cursor.grab_mode = CursorGrabMode::None;
+            window.cursor.grab_mode = render_configuration.cursor_grab_mode();
             window.cursor.visible = true;
             control_state.is_dragging = false;
         }
@@ -168,7 +171,7 @@ pub fn free_camera_system(
             window.set_cursor_position(Some(saved_cursor_position));
         }
 
-        window.cursor.grab_mode = CursorGrabMode::None;
+        window.cursor.grab_mode = render_configuration.cursor_grab_mode();
         window.cursor.visible = true;
         control_state.is_dragging = false;
     }
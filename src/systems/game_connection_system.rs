@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use arrayvec::ArrayVec;
 use bevy::{
     ecs::event::Events,
@@ -20,11 +22,9 @@ use rose_game_common::{
         StatusEffects, StatusEffectsRegen,
     },
     messages::{
-        client::ClientMessage,
         server::{
-            ClanCreateError, LearnSkillError, LevelUpSkillError, PartyMemberInfo,
-            PartyMemberInfoOffline, PersonalStoreTransactionStatus, PickupItemDropError,
-            ServerMessage, SpawnCommandState,
+            LearnSkillError, LevelUpSkillError, PartyMemberInfo, PartyMemberInfoOffline,
+            PickupItemDropError, ServerMessage, SpawnCommandState,
         },
         PartyItemSharing, PartyXpSharing,
     },
@@ -34,21 +34,24 @@ use rose_network_common::ConnectionError;
 use crate::{
     bundles::{ability_values_add_value_exclusive, ability_values_set_value_exclusive},
     components::{
-        Bank, Clan, ClanMember, ClanMembership, ClientEntity, ClientEntityName, ClientEntityType,
-        CollisionHeightOnly, CollisionPlayer, Command, CommandCastSkillTarget, Cooldowns, Dead,
-        FacingDirection, NextCommand, PartyInfo, PartyOwner, PassiveRecoveryTime, PendingDamage,
-        PendingDamageList, PendingSkillEffect, PendingSkillEffectList, PendingSkillTarget,
-        PendingSkillTargetList, PersonalStore, PlayerCharacter, Position, VisibleStatusEffects,
+        ClanMembership, ClientEntity, ClientEntityName, ClientEntityType, CollisionHeightOnly,
+        CollisionPlayer, Command, CommandCastSkillTarget, Cooldowns, Dead, FacingDirection,
+        NextCommand, PartyInfo, PartyOwner, PassiveRecoveryTime, PendingDamage, PendingDamageList,
+        PendingSkillEffect, PendingSkillEffectList, PendingSkillTarget, PendingSkillTargetList,
+        PersonalStore, PlayerCharacter, Position, PositionCorrection, VisibleStatusEffects,
     },
     events::{
-        BankEvent, ChatboxEvent, ClientEntityEvent, GameConnectionEvent,
-        LoadZoneEvent, MessageBoxEvent, PartyEvent, PersonalStoreEvent, QuestTriggerEvent,
-        UseItemEvent,
+        ChatboxEvent, ClientEntityEvent, ExtendedServerMessageEvent, GameConnectionEvent,
+        LoadZoneEvent, PartyEvent, QuestTriggerEvent, UseItemEvent,
     },
+    protocol::ExtendedServerMessage,
     resources::{
-        AppState, ClientEntityList, GameConnection, GameData, PendingClanInvites, WorldConnection,
-        WorldRates, WorldTime,
+        run_extended_server_message_handlers, run_server_message_handlers, AppState,
+        ClientEntityList, CombatState, ConnectionServer, ConnectionState,
+        ExtendedServerMessageKind, GameConnection, GameData, IgnoreList, ServerMessageHandlers,
+        ServerMessageKind, ServerMessageMetrics, WorldRates, WorldTime,
     },
+    systems::POSITION_CORRECTION_DURATION,
 };
 
 fn to_next_command(
@@ -83,7 +86,7 @@ fn to_next_command(
     }
 }
 
-fn update_inventory_and_money(
+pub fn update_inventory_and_money(
     world: &mut World,
     player_entity: Entity,
     update_items: Vec<(ItemSlot, Option<Item>)>,
@@ -126,24 +129,6 @@ fn update_inventory_and_money(
     }
 }
 
-fn clear_visible_character_clan_membership_by_name(world: &mut World, name: &str) {
-    let mut query = world.query::<(Entity, &ClientEntity, &ClientEntityName)>();
-    let entities_to_clear = query
-        .iter(world)
-        .filter_map(|(entity, client_entity, client_entity_name)| {
-            (client_entity.entity_type == ClientEntityType::Character
-                && client_entity_name.name == name)
-                .then_some(entity)
-        })
-        .collect::<Vec<_>>();
-
-    for entity in entities_to_clear {
-        if let Some(mut entity_mut) = world.get_entity_mut(entity) {
-            entity_mut.remove::<ClanMembership>();
-        }
-    }
-}
-
 pub fn game_connection_system(
     mut commands: Commands,
     game_connection: Option<Res<GameConnection>>,
@@ -157,26 +142,67 @@ pub fn game_connection_system(
     mut use_item_events: EventWriter<UseItemEvent>,
     mut client_entity_events: EventWriter<ClientEntityEvent>,
     mut party_events: EventWriter<PartyEvent>,
-    mut personal_store_events: EventWriter<PersonalStoreEvent>,
     mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
-    mut message_box_events: EventWriter<MessageBoxEvent>,
-    (world_connection, mut pending_clan_invites): (
-        Option<Res<WorldConnection>>,
-        ResMut<PendingClanInvites>,
+    (
+        ignore_list,
+        mut connection_state,
+        server_message_handlers,
+        mut server_message_metrics,
+        mut extended_server_message_events,
+    ): (
+        Res<IgnoreList>,
+        ResMut<ConnectionState>,
+        Res<ServerMessageHandlers>,
+        ResMut<ServerMessageMetrics>,
+        EventWriter<ExtendedServerMessageEvent>,
     ),
 ) {
     let Some(game_connection) = game_connection else {
         return;
     };
 
+    while let Ok(message) = game_connection.extended_server_message_rx.try_recv() {
+        if server_message_handlers.contains_extended(ExtendedServerMessageKind::of(&message)) {
+            commands
+                .add(move |world: &mut World| run_extended_server_message_handlers(world, message));
+            continue;
+        }
+
+        match message {
+            ExtendedServerMessage::TradeChat(trade_chat) => {
+                if !ignore_list.is_ignored(&trade_chat.name) {
+                    chatbox_events.send(ChatboxEvent::Trade(trade_chat.name, trade_chat.text));
+                }
+            }
+            ExtendedServerMessage::ClanChat(clan_chat) => {
+                if !ignore_list.is_ignored(&clan_chat.name) {
+                    chatbox_events.send(ChatboxEvent::Clan(clan_chat.name, clan_chat.text));
+                }
+            }
+            message => extended_server_message_events.send(ExtendedServerMessageEvent(message)),
+        }
+    }
+
     let result: Result<(), anyhow::Error> = loop {
-        match game_connection.server_message_rx.try_recv() {
+        let message = match game_connection.server_message_rx.try_recv() {
+            Ok(message) if server_message_handlers.contains(ServerMessageKind::of(&message)) => {
+                commands.add(move |world: &mut World| run_server_message_handlers(world, message));
+                continue;
+            }
+            message => message,
+        };
+        let _server_message_timer = message
+            .as_ref()
+            .ok()
+            .map(|message| server_message_metrics.start_timer(ServerMessageKind::of(message)));
+
+        match message {
             Ok(ServerMessage::ConnectionRequestSuccess { .. }) =>{
             client_entity_list.clear();
             }
-            Ok(ServerMessage::ConnectionRequestError { .. }) =>{
-                break Err(ConnectionError::ConnectionLost.into());
-            },
+            Ok(ServerMessage::ConnectionRequestError { error }) => {
+                break Err(anyhow::anyhow!("Connection was refused: {:?}", error));
+            }
             Ok(ServerMessage::CharacterData { data: character_data }) => {
                 let status_effects = StatusEffects::default();
                 let ability_values = game_data.ability_value_calculator.calculate(
@@ -243,6 +269,7 @@ pub fn game_connection_system(
                 // Emit connected event, character select system will be responsible for
                 // starting the load of the next zone once its animations have completed
                 game_connection_events.send(GameConnectionEvent::Connected(character_data.zone_id));
+                *connection_state = ConnectionState::LoadingZone;
                 client_entity_list.zone_id = Some(character_data.zone_id);
             }
             Ok(ServerMessage::CharacterDataItems { data }) => {
@@ -258,6 +285,7 @@ pub fn game_connection_system(
                 }
             }
             Ok(ServerMessage::JoinZone { entity_id, experience_points, team, health_points, mana_points, world_ticks, craft_rate, world_price_rate, item_price_rate, town_price_rate }) => {
+                *connection_state = ConnectionState::InGame;
                 if let Some(player_entity) = client_entity_list.player_entity {
                     let mut entity_commands = commands.entity(player_entity);
                     entity_commands.insert((
@@ -565,15 +593,21 @@ pub fn game_connection_system(
             }
             Ok(ServerMessage::AdjustPosition { entity_id, position }) => {
                 if let Some(entity) = client_entity_list.get(entity_id) {
-                    commands
-                        .entity(entity)
-                        .insert(NextCommand::with_move(position, None, None));
+                    commands.entity(entity).insert(PositionCorrection::new(
+                        position,
+                        POSITION_CORRECTION_DURATION,
+                    ));
                 }
             }
-            Ok(ServerMessage::StopMoveEntity { entity_id, x: _, y: _, z: _ }) => {
-                // TODO: Lerp to XYZ ?
+            Ok(ServerMessage::StopMoveEntity { entity_id, x, y, z }) => {
                 if let Some(entity) = client_entity_list.get(entity_id) {
-                    commands.entity(entity).insert(NextCommand::with_stop());
+                    commands.entity(entity).insert((
+                        NextCommand::with_stop(),
+                        PositionCorrection::new(
+                            Vec3::new(x, y, z as f32),
+                            POSITION_CORRECTION_DURATION,
+                        ),
+                    ));
                 }
             }
             Ok(ServerMessage::AttackEntity {
@@ -606,6 +640,8 @@ pub fn game_connection_system(
                     let killed_by_player = is_killed
                         && client_entity_list.player_entity
                             == client_entity_list.get(attacker_entity_id);
+                    let defender_is_player =
+                        client_entity_list.player_entity == Some(defender_entity);
 
                     commands.add(move |world: &mut World| {
                         let mut defender = world.entity_mut(defender_entity);
@@ -621,6 +657,12 @@ pub fn game_connection_system(
                             ));
                         }
 
+                        if defender_is_player {
+                            world.resource_mut::<CombatState>().last_attacked =
+                                Some(Instant::now());
+                        }
+
+                        let defender = world.entity(defender_entity);
                         if killed_by_player {
                             if let Some(name) = defender.get::<ClientEntityName>() {
                                 let chat_message =
@@ -673,6 +715,10 @@ pub fn game_connection_system(
                     commands.add(move |world: &mut World| {
                         if let Some(name) = world.entity(chat_entity).get::<ClientEntityName>() {
                             let name = name.to_string();
+                            if world.resource::<IgnoreList>().is_ignored(&name) {
+                                return;
+                            }
+
                             world
                                 .resource_mut::<Events<ChatboxEvent>>()
                                 .send(ChatboxEvent::Say(name, text));
@@ -681,9 +727,17 @@ pub fn game_connection_system(
                 }
             }
             Ok(ServerMessage::ShoutChat { name, text }) => {
+                if ignore_list.is_ignored(&name) {
+                    continue;
+                }
+
                 chatbox_events.send(ChatboxEvent::Shout(name, text));
             }
             Ok(ServerMessage::Whisper { from, text }) => {
+                if ignore_list.is_ignored(&from) {
+                    continue;
+                }
+
                 chatbox_events.send(ChatboxEvent::Whisper(from, text));
             }
             Ok(ServerMessage::AnnounceChat { name, text }) => {
@@ -1981,242 +2035,6 @@ pub fn game_connection_system(
                     });
                 }
             }
-            Ok(ServerMessage::OpenPersonalStore {
-                entity_id,
-                skin,
-                title,
-            }) => {
-                if let Some(entity) = client_entity_list.get(entity_id) {
-                    commands.entity(entity).insert(PersonalStore {
-                        title,
-                        skin: skin as usize,
-                    });
-                }
-            }
-            Ok(ServerMessage::ClosePersonalStore { entity_id }) => {
-                let target_entity = client_entity_list
-                    .get(entity_id)
-                    .or_else(|| {
-                        if client_entity_list.player_entity_id == Some(entity_id) {
-                            client_entity_list.player_entity
-                        } else {
-                            None
-                        }
-                    });
-
-                if let Some(entity) = target_entity {
-                    log::info!(
-                        "personal-store: close received entity_id={} entity={:?}",
-                        entity_id.0,
-                        entity
-                    );
-                    let mut entity_commands = commands.entity(entity);
-                    entity_commands.remove::<PersonalStore>();
-
-                    // Ensure local command state exits PersonalStore immediately so movement
-                    // can resume even before further server movement updates arrive.
-                    entity_commands.insert(Command::with_stop());
-                    entity_commands.insert(NextCommand::with_stop());
-                } else {
-                    log::warn!(
-                        "personal-store: close received for unknown entity_id={}",
-                        entity_id.0
-                    );
-                }
-            }
-            Ok(ServerMessage::PersonalStoreItemList { sell_items, buy_items  }) => {
-                personal_store_events.send(PersonalStoreEvent::SetItemList {
-                    sell_items,
-                    buy_items,
-                });
-            }
-            Ok(ServerMessage::PersonalStoreTransaction {
-                status,
-                store_entity_id,
-                update_store,
-            }) => {
-                if !update_store.is_empty() {
-                    if let Some(entity) = client_entity_list.get(store_entity_id) {
-                        match status {
-                            PersonalStoreTransactionStatus::Cancelled => {}
-                            PersonalStoreTransactionStatus::SoldOut
-                            | PersonalStoreTransactionStatus::BoughtFromStore => {
-                                personal_store_events.send(PersonalStoreEvent::UpdateSellList {
-                                    entity,
-                                    item_list: update_store,
-                                });
-                            }
-                            PersonalStoreTransactionStatus::NoMoreNeed
-                            | PersonalStoreTransactionStatus::SoldToStore => {
-                                personal_store_events.send(PersonalStoreEvent::UpdateBuyList {
-                                    entity,
-                                    item_list: update_store,
-                                });
-                            }
-                        }
-                    }
-                }
-
-                match status {
-                    PersonalStoreTransactionStatus::Cancelled => {
-                        chatbox_events
-                            .send(ChatboxEvent::System("Transaction failed.".to_string()));
-                    }
-                    PersonalStoreTransactionStatus::SoldOut => {
-                        chatbox_events.send(ChatboxEvent::System(
-                            "Transaction failed. Item has sold out.".to_string(),
-                        ));
-                    }
-                    PersonalStoreTransactionStatus::NoMoreNeed => {
-                        chatbox_events.send(ChatboxEvent::System(
-                            "Transaction failed. Item is no longer wanted.".to_string(),
-                        ));
-                    }
-                    _ => {}
-                }
-            }
-            Ok(ServerMessage::PersonalStoreTransactionUpdateInventory { money, items }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let player = world.entity(player_entity);
-
-                        if let Some(inventory) = player.get::<Inventory>() {
-                            let transaction_price = money.0 - inventory.money.0;
-
-                            if let Some((item_slot, transaction_item)) = items.first() {
-                                let transaction_item = transaction_item.as_ref();
-                                let inventory_item = inventory.get_item(*item_slot);
-                                let (transaction_quantity, transaction_item) =
-                                    match (transaction_item, inventory_item) {
-                                        (Some(transaction_item), Some(inventory_item)) => (
-                                            transaction_item.get_quantity() as i32
-                                                - inventory_item.get_quantity() as i32,
-                                            Some(inventory_item.get_item_reference()),
-                                        ),
-                                        (None, Some(inventory_item)) => (
-                                            inventory_item.get_quantity() as i32,
-                                            Some(inventory_item.get_item_reference()),
-                                        ),
-                                        (Some(transaction_item), None) => (
-                                            transaction_item.get_quantity() as i32,
-                                            Some(transaction_item.get_item_reference()),
-                                        ),
-                                        (None, None) => (0, None),
-                                    };
-
-                                let game_data = world.resource::<GameData>();
-                                if let Some(item_data) = transaction_item
-                                    .and_then(|item| game_data.items.get_base_item(item))
-                                {
-                                    let message = if transaction_quantity > 1 {
-                                        format!(
-                                            "You have {} {}x {} for {} Zuly.",
-                                            if transaction_price < 0 {
-                                                "purchased"
-                                            } else {
-                                                "sold"
-                                            },
-                                            transaction_quantity,
-                                            item_data.name,
-                                            transaction_price.abs()
-                                        )
-                                    } else {
-                                        format!(
-                                            "You have {} {} for {} Zuly.",
-                                            if transaction_price < 0 {
-                                                "purchased"
-                                            } else {
-                                                "sold"
-                                            },
-                                            item_data.name,
-                                            transaction_price.abs()
-                                        )
-                                    };
-                                    let mut chatbox_events =
-                                        world.resource_mut::<Events<ChatboxEvent>>();
-                                    chatbox_events.send(ChatboxEvent::System(message));
-                                }
-                            }
-                        }
-
-                        update_inventory_and_money(world, player_entity, items, Some(money));
-                    });
-                }
-            }
-            Ok(ServerMessage::BankOpen) => {
-                commands.add(move |world: &mut World| {
-                    let mut chatbox_events = world.resource_mut::<Events<BankEvent>>();
-                    chatbox_events.send(BankEvent::Show);
-                });
-            }
-            Ok(ServerMessage::BankSetItems { items }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    let mut slots = vec![None; 160];
-
-                    for (bank_slot_index, item) in items {
-                        let bank_slot_index = bank_slot_index as usize;
-                        if bank_slot_index > slots.len() {
-                            slots.resize(bank_slot_index + 1, None);
-                        }
-                        slots[bank_slot_index] = item;
-                    }
-
-                    commands.add(move |world: &mut World| {
-                        world.entity_mut(player_entity).insert(Bank { slots });
-
-                        let mut chatbox_events = world.resource_mut::<Events<BankEvent>>();
-                        chatbox_events.send(BankEvent::Show);
-                    });
-                }
-            }
-            Ok(ServerMessage::BankUpdateItems { items }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        if let Some(mut bank) = world.entity_mut(player_entity).get_mut::<Bank>() {
-                            for (bank_slot_index, item) in items {
-                                let bank_slot_index = bank_slot_index as usize;
-
-                                if bank_slot_index > bank.slots.len() {
-                                    bank.slots.resize(bank_slot_index + 1, None);
-                                }
-
-                                bank.slots[bank_slot_index] = item;
-                            }
-                        }
-                    });
-                }
-            }
-            Ok(ServerMessage::BankTransaction {
-                inventory_item_slot,
-                inventory_item,
-                inventory_money,
-                bank_slot,
-                bank_item,
-            }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        if let Some(mut inventory) =
-                            world.entity_mut(player_entity).get_mut::<Inventory>()
-                        {
-                            if let Some(item_slot) =
-                                inventory.get_item_slot_mut(inventory_item_slot)
-                            {
-                                *item_slot = inventory_item;
-                            }
-
-                            if let Some(inventory_money) = inventory_money {
-                                inventory.money = inventory_money;
-                            }
-                        }
-
-                        if let Some(mut bank) = world.entity_mut(player_entity).get_mut::<Bank>() {
-                            if let Some(bank_slot) = bank.slots.get_mut(bank_slot) {
-                                *bank_slot = bank_item;
-                            }
-                        }
-                    });
-                }
-            }
             Ok(ServerMessage::MoveToggle {
                 entity_id,
                 move_mode,
@@ -2236,195 +2054,6 @@ pub fn game_connection_system(
                     });
                 }
             }
-            Ok(ServerMessage::ClanInfo { id, mark, level, points, money, name, description, position, contribution, skills }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.entity(player_entity).insert((
-                        Clan {
-                            unique_id: id,
-                            name: name.clone(),
-                            description,
-                            mark,
-                            money,
-                            points,
-                            level,
-                            members: Vec::new(),
-                            skills,
-                        },
-                        ClanMembership {
-                            clan_unique_id: id,
-                            mark,
-                            level,
-                            name,
-                            position,
-                            contribution,
-                        }));
-                }
-            }
-            Ok(ServerMessage::ClanUpdateInfo {
-                id,
-                mark,
-                level,
-                points,
-                money,
-                description,
-                skills,
-            }) => {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            clan.unique_id = id;
-                            clan.mark = mark;
-                            clan.level = level;
-                            clan.points = points;
-                            clan.money = money;
-                            clan.description = description;
-                            clan.skills = skills;
-                        }
-                    });
-                }
-            }
-            Ok(ServerMessage::CharacterUpdateClan { client_entity_id, id, name, mark, level, position  }) => {
-                if let Some(entity) = client_entity_list.get(client_entity_id) {
-                    commands.entity(entity).insert(
-                        ClanMembership {
-                            clan_unique_id: id,
-                            mark,
-                            level,
-                            name,
-                            position,
-                            contribution: ClanPoints(0),
-                        });
-                }
-            }
-            Ok(ServerMessage::ClanMemberConnected { name, channel_id  }) =>  {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            if let Some(member) = clan.find_member_mut(&name) {
-                                member.channel_id = Some(channel_id);
-                            }
-                        }
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanMemberDisconnected { name  }) =>  {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            if let Some(member) = clan.find_member_mut(&name) {
-                                member.channel_id = None;
-                            }
-                        }
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanCreateError { error }) =>  {
-                match error {
-                    ClanCreateError::Failed => {
-                        message_box_events.send(MessageBoxEvent::Show { message: game_data.client_strings.clan_create_error.into(), modal: false, ok: None, cancel: None });
-                    },
-                    ClanCreateError::NameExists => {
-                        message_box_events.send(MessageBoxEvent::Show { message: game_data.client_strings.clan_create_error_name.into(), modal: false, ok: None, cancel: None });
-                    },
-                    ClanCreateError::NoPermission => {
-                        message_box_events.send(MessageBoxEvent::Show { message: game_data.client_strings.clan_create_error_permission.into(), modal: false, ok: None, cancel: None });
-                    },
-                    ClanCreateError::UnmetCondition => {
-                        message_box_events.send(MessageBoxEvent::Show { message: game_data.client_strings.clan_create_error_condition.into(), modal: false, ok: None, cancel: None });
-                    },
-                }
-            }
-            Ok(ServerMessage::ClanMemberList { members }) =>  {
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            clan.members.clear();
-
-                            for member in members {
-                                clan.members.push(ClanMember {
-                                    name: member.name,
-                                    position: member.position,
-                                    contribution: member.contribution,
-                                    level: member.level,
-                                    job: member.job,
-                                    channel_id: member.channel_id,
-                                });
-                            }
-                        }
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanInvited { name, clan_unique_id, clan_mark: _, clan_level, clan_name, inviter_entity_id: _ }) => {
-                pending_clan_invites.invites.push(crate::resources::PendingClanInvite {
-                    inviter_name: name,
-                    clan_name,
-                    clan_unique_id,
-                    clan_level,
-                });
-            }
-            Ok(ServerMessage::ClanInviteResult { response }) => {
-                log::info!("Received clan invite result: {:?}", response);
-                // TODO: Show invite result message to user
-            }
-            Ok(ServerMessage::ClanMemberJoined { name }) => {
-                log::info!("Clan member joined: {}", name);
-                if let Some(world_connection) = world_connection.as_ref() {
-                    world_connection
-                        .client_message_tx
-                        .send(ClientMessage::ClanGetMemberList)
-                        .ok();
-                }
-            }
-            Ok(ServerMessage::ClanMemberLeft { name }) => {
-                log::info!("Clan member left: {}", name);
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            clan.members.retain(|member| member.name != name);
-                        }
-
-                        clear_visible_character_clan_membership_by_name(world, &name);
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanMemberKicked { name }) => {
-                log::info!("Clan member kicked: {}", name);
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        if let Some(mut clan) = entity_mut.get_mut::<Clan>() {
-                            clan.members.retain(|member| member.name != name);
-                        }
-
-                        clear_visible_character_clan_membership_by_name(world, &name);
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanKicked) => {
-                log::info!("You have been kicked from the clan");
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        entity_mut.remove::<Clan>();
-                        entity_mut.remove::<ClanMembership>();
-                    });
-                }
-            }
-            Ok(ServerMessage::ClanDisbanded) => {
-                log::info!("Your clan has been disbanded");
-                if let Some(player_entity) = client_entity_list.player_entity {
-                    commands.add(move |world: &mut World| {
-                        let mut entity_mut = world.entity_mut(player_entity);
-                        entity_mut.remove::<Clan>();
-                        entity_mut.remove::<ClanMembership>();
-                    });
-                }
-            }
             Ok(ServerMessage::CraftInsertGem { .. }) => {
                 log::warn!("Received unimplemented ServerMessage::CraftInsertGem");
             }
@@ -2469,8 +2098,11 @@ pub fn game_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("Game server connection error: {}", error);
         commands.remove_resource::<GameConnection>();
+
+        if connection_state.active_server() == Some(ConnectionServer::Game) {
+            *connection_state = ConnectionState::error(ConnectionServer::Game, error);
+        }
     }
 }
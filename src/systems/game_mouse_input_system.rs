@@ -11,14 +11,20 @@ use bevy::{
 use bevy_egui::EguiContexts;
 use bevy_rapier3d::prelude::{CollisionGroups, QueryFilter, RapierContext};
 
-use rose_game_common::components::{ItemDrop, Team};
+use rose_data::EquipmentIndex;
+use rose_game_common::components::{Equipment, ItemDrop, Team};
 
 use crate::{
     components::{
-        ClientEntity, ClientEntityType, ColliderParent, PlayerCharacter, Position, ZoneObject,
-        COLLISION_FILTER_CLICKABLE, COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_PLAYER,
+        ClientEntity, ClientEntityType, ColliderParent, GrowthObject, HiddenPlayer,
+        MonsterSpawnTelegraph, PlayerCharacter, Position, ZoneObject, COLLISION_FILTER_CLICKABLE,
+        COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_PLAYER,
     },
     events::{MoveDestinationEffectEvent, PlayerCommandEvent},
+    minigame::{
+        ActiveMinigame, FishingSettings, MinigameEvent, FISHING_MAX_CAST_DISTANCE,
+        FISHING_MINIGAME_ID,
+    },
     resources::{SelectedTarget, UiCursorType, UiRequestedCursor},
 };
 
@@ -26,6 +32,8 @@ use crate::{
 pub struct PlayerQuery<'w> {
     entity: Entity,
     team: &'w Team,
+    position: &'w Position,
+    equipment: &'w Equipment,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -42,12 +50,18 @@ pub fn game_mouse_input_system(
         Option<&ItemDrop>,
         Option<&ZoneObject>,
         Option<&ClientEntity>,
+        Option<&GrowthObject>,
+        Option<&MonsterSpawnTelegraph>,
+        Option<&HiddenPlayer>,
     )>,
     query_player: Query<PlayerQuery, With<PlayerCharacter>>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     mut move_destination_effect_events: EventWriter<MoveDestinationEffectEvent>,
     mut selected_target: ResMut<SelectedTarget>,
     mut ui_requested_cursor: ResMut<UiRequestedCursor>,
+    active_minigame: Res<ActiveMinigame>,
+    fishing_settings: Res<FishingSettings>,
+    mut minigame_events: EventWriter<MinigameEvent>,
 ) {
     selected_target.hover = None;
     ui_requested_cursor.world_cursor = UiCursorType::Default;
@@ -56,7 +70,7 @@ pub fn game_mouse_input_system(
         return;
     };
 
-    if !matches!(window.cursor.grab_mode, CursorGrabMode::None) {
+    if matches!(window.cursor.grab_mode, CursorGrabMode::Locked) {
         // Cursor is currently grabbed
         return;
     }
@@ -79,16 +93,31 @@ pub fn game_mouse_input_system(
         return;
     };
 
+    // Monsters cannot be targeted until they have finished emerging, and hidden
+    // players cannot be targeted at all, so the ray passes through to what is behind
+    let is_targetable = |collider_entity: Entity| {
+        let entity = query_collider_parent
+            .get(collider_entity)
+            .map_or(collider_entity, |collider_parent| collider_parent.entity);
+        query_hit_entity
+            .get(entity)
+            .map_or(true, |(.., monster_spawn_telegraph, hidden_player)| {
+                monster_spawn_telegraph.is_none() && hidden_player.is_none()
+            })
+    };
+
     if let Some(ray) = camera.viewport_to_world(camera_transform, cursor_position) {
         if let Some((collider_entity, distance)) = rapier_context.cast_ray(
             ray.origin,
             ray.direction,
             10000000.0,
             false,
-            QueryFilter::new().groups(CollisionGroups::new(
-                COLLISION_FILTER_CLICKABLE,
-                !COLLISION_GROUP_PLAYER & !COLLISION_GROUP_PHYSICS_TOY,
-            )),
+            QueryFilter::new()
+                .groups(CollisionGroups::new(
+                    COLLISION_FILTER_CLICKABLE,
+                    !COLLISION_GROUP_PLAYER & !COLLISION_GROUP_PHYSICS_TOY,
+                ))
+                .predicate(&is_targetable),
         ) {
             let hit_position = ray.get_point(distance);
             let hit_entity = query_collider_parent
@@ -101,6 +130,9 @@ pub fn game_mouse_input_system(
                 hit_item_drop,
                 hit_zone_object,
                 hit_client_entity,
+                hit_growth_object,
+                _,
+                _,
             )) = query_hit_entity.get(hit_entity)
             {
                 if let Some(hit_client_entity) = hit_client_entity {
@@ -126,7 +158,29 @@ pub fn game_mouse_input_system(
                     }
                 }
 
-                if hit_zone_object.is_some() {
+                let is_holding_rod = player.equipment.equipped_items[EquipmentIndex::Weapon]
+                    .as_ref()
+                    .map_or(false, |weapon| {
+                        fishing_settings.is_rod(weapon.item.item_number)
+                    });
+                let fishing_distance = Vec3::new(
+                    hit_position.x * 100.0,
+                    -hit_position.z * 100.0,
+                    player.position.position.z,
+                )
+                .distance(player.position.position)
+                    / 100.0;
+
+                if matches!(hit_zone_object, Some(ZoneObject::Water))
+                    && is_holding_rod
+                    && fishing_distance < FISHING_MAX_CAST_DISTANCE
+                {
+                    if mouse_button_input.just_pressed(MouseButton::Left)
+                        && !active_minigame.is_active()
+                    {
+                        minigame_events.send(MinigameEvent::Request(FISHING_MINIGAME_ID));
+                    }
+                } else if hit_zone_object.is_some() {
                     if mouse_button_input.just_pressed(MouseButton::Left) {
                         player_command_events.send(PlayerCommandEvent::Move(
                             Position::new(Vec3::new(
@@ -154,6 +208,19 @@ pub fn game_mouse_input_system(
                             ));
                         }
                     }
+                } else if let Some(hit_growth_object) = hit_growth_object {
+                    selected_target.hover = Some(hit_entity);
+
+                    if hit_growth_object.is_harvestable() {
+                        ui_requested_cursor.world_cursor = UiCursorType::PickupItem;
+
+                        if mouse_button_input.just_pressed(MouseButton::Left) {
+                            // The server harvests a fully grown plant when it is attacked
+                            player_command_events.send(PlayerCommandEvent::Attack(hit_entity));
+                        }
+                    } else if mouse_button_input.just_pressed(MouseButton::Left) {
+                        selected_target.selected = Some(hit_entity);
+                    }
                 } else if let Some(hit_team) = hit_team {
                     selected_target.hover = Some(hit_entity);
 
@@ -1,6 +1,6 @@
 use bevy::{
     math::Vec3,
-    prelude::{Camera3d, Commands, Entity, EventReader, Query, Res, With},
+    prelude::{Camera3d, Commands, Entity, EventReader, Query, Res, ResMut, With},
 };
 use rose_game_common::messages::client::ClientMessage;
 
@@ -8,7 +8,7 @@ use crate::{
     animation::CameraAnimation,
     components::PlayerCharacter,
     events::ZoneEvent,
-    resources::GameConnection,
+    resources::{GameConnection, ZoneTransition},
     systems::{FreeCamera, OrbitCamera},
 };
 
@@ -16,7 +16,11 @@ pub fn game_state_enter_system(
     mut commands: Commands,
     query_cameras: Query<Entity, With<Camera3d>>,
     query_player: Query<Entity, With<PlayerCharacter>>,
+    mut zone_transition: ResMut<ZoneTransition>,
 ) {
+    // Any transition left over from a previous session will never complete
+    zone_transition.active = None;
+
     // Reset camera
     let player_entity = query_player.single();
     for entity in query_cameras.iter() {
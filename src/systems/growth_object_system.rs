@@ -0,0 +1,60 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Changed, Commands, Entity, Query, Res, Time, Transform},
+};
+
+use rose_game_common::components::Npc;
+
+use crate::{
+    components::GrowthObject,
+    resources::{FarmingSettings, GameData},
+};
+
+// Plants are scaled up from this fraction of their npc scale as each stage grows
+const GROWTH_STAGE_START_SCALE: f32 = 0.75;
+
+pub fn growth_object_system(
+    mut commands: Commands,
+    query_changed_npc: Query<(Entity, &Npc, Option<&GrowthObject>), Changed<Npc>>,
+    mut query_growth_objects: Query<(&mut GrowthObject, &mut Transform)>,
+    farming_settings: Res<FarmingSettings>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    for (entity, npc, growth_object) in query_changed_npc.iter() {
+        let Some((seed, stage)) = farming_settings.find_stage(npc.id) else {
+            if growth_object.is_some() {
+                commands.entity(entity).remove::<GrowthObject>();
+            }
+            continue;
+        };
+
+        // The server does not tell us when a stage was spawned, so progress for
+        // a plant which was already growing before it came into view starts at 0
+        commands.entity(entity).insert(GrowthObject {
+            stage,
+            num_stages: seed.stages.len(),
+            stage_duration: seed.stages[stage].duration,
+            stage_elapsed: Default::default(),
+            base_scale: game_data
+                .npcs
+                .get_npc(npc.id)
+                .map_or(1.0, |npc_data| npc_data.scale),
+        });
+    }
+
+    for (mut growth_object, mut transform) in query_growth_objects.iter_mut() {
+        growth_object.stage_elapsed += time.delta();
+
+        let scale = if growth_object.is_harvestable() {
+            growth_object.base_scale
+        } else {
+            growth_object.base_scale
+                * (GROWTH_STAGE_START_SCALE
+                    + (1.0 - GROWTH_STAGE_START_SCALE) * growth_object.stage_progress())
+        };
+        if transform.scale.x != scale {
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
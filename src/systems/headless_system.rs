@@ -0,0 +1,59 @@
+use bevy::{
+    app::AppExit,
+    prelude::{EventWriter, Query, Res, ResMut, State, Time, With},
+};
+use std::sync::atomic::Ordering;
+
+use crate::{
+    components::PlayerCharacter,
+    resources::{
+        AppState, CurrentZone, GameConnection, HeadlessState, HEADLESS_EXIT_DISCONNECTED,
+        HEADLESS_EXIT_SUCCESS, HEADLESS_EXIT_TIMEOUT,
+    },
+};
+
+pub fn headless_system(
+    mut headless_state: ResMut<HeadlessState>,
+    app_state: Res<State<AppState>>,
+    current_zone: Option<Res<CurrentZone>>,
+    game_connection: Option<Res<GameConnection>>,
+    query_player: Query<(), With<PlayerCharacter>>,
+    time: Res<Time>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    let elapsed = time.elapsed_seconds();
+
+    let exit_code = if let Some(entered_game_time) = headless_state.entered_game_time {
+        if game_connection.is_none() {
+            log::error!("Headless: lost connection to game server");
+            HEADLESS_EXIT_DISCONNECTED
+        } else if elapsed - entered_game_time >= headless_state.run_duration {
+            log::info!(
+                "Headless: ran in game for {} seconds",
+                headless_state.run_duration
+            );
+            HEADLESS_EXIT_SUCCESS
+        } else {
+            return;
+        }
+    } else if let (AppState::Game, Some(current_zone), false) = (
+        app_state.get(),
+        current_zone.as_ref(),
+        query_player.is_empty(),
+    ) {
+        log::info!("Headless: entered zone {}", current_zone.id.get());
+        headless_state.entered_game_time = Some(elapsed);
+        return;
+    } else if elapsed >= headless_state.timeout {
+        log::error!(
+            "Headless: timed out after {} seconds waiting to enter the game",
+            headless_state.timeout
+        );
+        HEADLESS_EXIT_TIMEOUT
+    } else {
+        return;
+    };
+
+    headless_state.exit_code.store(exit_code, Ordering::SeqCst);
+    exit_events.send(AppExit);
+}
@@ -10,8 +10,8 @@ use rose_game_common::{
 
 use crate::{
     components::{
-        ClientEntity, ClientEntityType, Dead, ModelHeight, NextCommand, PendingDamageList,
-        PendingSkillEffectList, PendingSkillTargetList,
+        ClientEntity, ClientEntityType, Dead, DynamicPointLight, ModelHeight, NextCommand,
+        PendingDamageList, PendingSkillEffectList, PendingSkillTargetList,
     },
     events::{HitEvent, SpawnEffectData, SpawnEffectEvent},
     resources::{ClientEntityList, DamageDigitsSpawner, GameData},
@@ -162,7 +162,8 @@ pub fn hit_event_system(
                 spawn_effect_events.send(SpawnEffectEvent::OnEntity(
                     defender.entity,
                     skill_data.hit_link_dummy_bone_id,
-                    SpawnEffectData::with_file_id(effect_file_id),
+                    SpawnEffectData::with_file_id(effect_file_id)
+                        .with_point_light(DynamicPointLight::skill_impact()),
                 ));
             }
         }
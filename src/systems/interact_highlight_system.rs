@@ -0,0 +1,76 @@
+use bevy::{
+    hierarchy::{Children, HierarchyQueryExt},
+    math::Vec3,
+    prelude::{Assets, Commands, Component, Entity, Handle, Query, Res, ResMut, Without},
+};
+
+use crate::{
+    components::{ClientEntity, ClientEntityType, GrowthObject},
+    render::ObjectMaterial,
+    resources::SelectedTarget,
+};
+
+const INTERACT_HIGHLIGHT_RIM_COLOR: Vec3 = Vec3::new(0.9, 0.75, 0.35);
+
+// Stores the material which was swapped out for the highlighted rim light material
+#[derive(Component)]
+pub struct InteractHighlightMaterial {
+    original: Handle<ObjectMaterial>,
+}
+
+// NPCs can be talked to, and growth objects can be harvested once fully grown
+pub fn is_interactable(client_entity: &ClientEntity, growth_object: Option<&GrowthObject>) -> bool {
+    matches!(client_entity.entity_type, ClientEntityType::Npc)
+        && growth_object.map_or(true, |growth_object| growth_object.is_harvestable())
+}
+
+pub fn interact_highlight_system(
+    mut commands: Commands,
+    query_interactable: Query<(&ClientEntity, Option<&GrowthObject>)>,
+    query_children: Query<&Children>,
+    query_materials: Query<&Handle<ObjectMaterial>, Without<InteractHighlightMaterial>>,
+    query_highlighted: Query<(Entity, &InteractHighlightMaterial)>,
+    selected_target: Res<SelectedTarget>,
+    mut object_materials: ResMut<Assets<ObjectMaterial>>,
+) {
+    let highlight_entity = selected_target.hover.filter(|entity| {
+        query_interactable
+            .get(*entity)
+            .map_or(false, |(client_entity, growth_object)| {
+                is_interactable(client_entity, growth_object)
+            })
+    });
+    let highlight_parts: Vec<Entity> = highlight_entity
+        .map(|entity| query_children.iter_descendants(entity).collect())
+        .unwrap_or_default();
+
+    // Restore parts which are no longer highlighted, this includes any parts
+    // which have since been removed from the model
+    for (entity, highlight_material) in query_highlighted.iter() {
+        if !highlight_parts.contains(&entity) {
+            if let Some(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands
+                    .insert(highlight_material.original.clone())
+                    .remove::<InteractHighlightMaterial>();
+            }
+        }
+    }
+
+    for entity in highlight_parts {
+        let Ok(material_handle) = query_materials.get(entity) else {
+            continue;
+        };
+        let Some(material) = object_materials.get(material_handle) else {
+            continue;
+        };
+
+        let mut highlight_material = material.clone();
+        highlight_material.rim_light = Some(INTERACT_HIGHLIGHT_RIM_COLOR);
+        commands.entity(entity).insert((
+            object_materials.add(highlight_material),
+            InteractHighlightMaterial {
+                original: material_handle.clone(),
+            },
+        ));
+    }
+}
@@ -1,15 +1,16 @@
 use bevy::prelude::{Commands, EventWriter, Res, ResMut};
 
-use rose_game_common::{
-    data::Password,
-    messages::{client::ClientMessage, server::ServerMessage},
-};
+use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
 use rose_network_common::ConnectionError;
 
 use crate::{
+    credential_store::{store_credential, CredentialKind},
     events::NetworkEvent,
+    protocol::CLIENT_CAPABILITIES,
     resources::{
-        Account, LoginConnection, ServerList, ServerListGameServer, ServerListWorldServer,
+        Account, ClientCapabilities, ClientSettings, ConnectionServer, ConnectionState,
+        LoginConnection, LoginState, ServerConfiguration, ServerList, ServerListGameServer,
+        ServerListWorldServer,
     },
 };
 
@@ -17,35 +18,78 @@ pub fn login_connection_system(
     mut commands: Commands,
     account: Option<Res<Account>>,
     login_connection: Option<Res<LoginConnection>>,
+    login_state: Option<ResMut<LoginState>>,
     mut server_list: Option<ResMut<ServerList>>,
     mut network_events: EventWriter<NetworkEvent>,
+    mut connection_state: ResMut<ConnectionState>,
+    mut client_capabilities: ResMut<ClientCapabilities>,
+    client_settings: Res<ClientSettings>,
+    server_configuration: Res<ServerConfiguration>,
 ) {
     if login_connection.is_none() {
         return;
     }
 
     let login_connection = login_connection.unwrap();
+
+    if let Some(mut login_state) = login_state {
+        while let Ok(challenge) = login_connection.login_challenge_rx.try_recv() {
+            *login_state = LoginState::Challenge(challenge);
+        }
+    }
+
+    while let Ok(server_capabilities) = login_connection.server_capabilities_rx.try_recv() {
+        log::info!(
+            "Server protocol version {} with capabilities {:?}",
+            server_capabilities.protocol_version,
+            server_capabilities.capabilities
+        );
+        client_capabilities.server_protocol_version = Some(server_capabilities.protocol_version);
+        // Only features which both sides support are used
+        client_capabilities.capabilities = server_capabilities.capabilities & CLIENT_CAPABILITIES;
+    }
+
     let result: Result<(), anyhow::Error> = loop {
         match login_connection.server_message_rx.try_recv() {
             Ok(ServerMessage::ConnectionRequestSuccess {
                 packet_sequence_id: _,
             }) => {
-                if let Some(account) = account.as_ref() {
-                    login_connection
-                        .client_message_tx
-                        .send(ClientMessage::LoginRequest {
-                            username: account.username.clone(),
-                            password: Password::Plaintext(account.password.clone()),
-                        })
-                        .ok();
-                } else {
+                let Some(account) = account.as_ref() else {
+                    break Err(anyhow::anyhow!("No account details to log in with"));
+                };
+
+                if login_connection
+                    .client_message_tx
+                    .send(ClientMessage::LoginRequest {
+                        username: account.username.clone(),
+                        password: account.password.clone(),
+                    })
+                    .is_err()
+                {
                     break Err(ConnectionError::ConnectionLost.into());
                 }
+                *connection_state = ConnectionState::Authenticating;
             }
-            Ok(ServerMessage::ConnectionRequestError { error: _ }) => {
-                break Err(ConnectionError::ConnectionLost.into());
+            Ok(ServerMessage::ConnectionRequestError { error }) => {
+                break Err(anyhow::anyhow!("Connection was refused: {:?}", error));
             }
             Ok(ServerMessage::LoginSuccess { server_list }) => {
+                // Keep the password hash for the remembered account so auto login can use it
+                if let Some(account) = account.as_ref() {
+                    if client_settings.login.use_os_keychain
+                        && client_settings.login.remembered_username.as_ref()
+                            == Some(&account.username)
+                    {
+                        store_credential(
+                            CredentialKind::PasswordMd5,
+                            &server_configuration.ip,
+                            &server_configuration.port,
+                            &account.username,
+                            &account.password.to_md5(),
+                        );
+                    }
+                }
+
                 let mut world_servers = Vec::new();
                 for (id, name) in server_list {
                     login_connection
@@ -61,6 +105,7 @@ pub fn login_connection_system(
                     });
                 }
                 commands.insert_resource(ServerList { world_servers });
+                *connection_state = ConnectionState::SelectingServer;
             }
             Ok(ServerMessage::LoginError { error }) => {
                 break Err(error.into());
@@ -100,8 +145,11 @@ pub fn login_connection_system(
                         login_token,
                         password: account.password.clone(),
                     });
+                    *connection_state = ConnectionState::Connecting(ConnectionServer::World);
                 } else {
-                    break Err(ConnectionError::ConnectionLost.into());
+                    break Err(anyhow::anyhow!(
+                        "No account details to join the server with"
+                    ));
                 }
             }
             Ok(ServerMessage::JoinServerError { error }) => {
@@ -118,8 +166,11 @@ pub fn login_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("Login server connection error: {}", error);
         commands.remove_resource::<LoginConnection>();
+
+        if connection_state.active_server() == Some(ConnectionServer::Login) {
+            *connection_state = ConnectionState::error(ConnectionServer::Login, error);
+        }
     }
 }
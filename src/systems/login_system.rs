@@ -2,7 +2,7 @@ use bevy::{
     prelude::{
         AssetServer, Camera3d, Commands, Entity, EventReader, EventWriter, Query, Res, ResMut, With,
     },
-    window::{CursorGrabMode, PrimaryWindow, Window},
+    window::{PrimaryWindow, Window},
 };
 use bevy_egui::{egui, EguiContexts};
 
@@ -12,7 +12,10 @@ use rose_game_common::messages::client::ClientMessage;
 use crate::{
     animation::CameraAnimation,
     events::{LoadZoneEvent, LoginEvent, NetworkEvent},
-    resources::{Account, LoginConnection, LoginState, ServerConfiguration, ServerList},
+    resources::{
+        Account, LoginConnection, LoginQueue, LoginState, RenderConfiguration, ServerConfiguration,
+        ServerList,
+    },
     systems::{FreeCamera, OrbitCamera},
 };
 
@@ -22,10 +25,11 @@ pub fn login_state_enter_system(
     mut query_window: Query<&mut Window, With<PrimaryWindow>>,
     query_cameras: Query<Entity, With<Camera3d>>,
     asset_server: Res<AssetServer>,
+    render_configuration: Res<RenderConfiguration>,
 ) {
     // Ensure cursor is not locked
     if let Ok(mut window) = query_window.get_single_mut() {
-        window.cursor.grab_mode = CursorGrabMode::None;
+        window.cursor.grab_mode = render_configuration.cursor_grab_mode();
         window.cursor.visible = true;
     }
 
@@ -55,6 +59,7 @@ pub fn login_state_exit_system(mut commands: Commands) {
 pub fn login_system(
     mut egui_context: EguiContexts,
     login_connection: Option<Res<LoginConnection>>,
+    login_queue: Option<Res<LoginQueue>>,
     mut login_state: ResMut<LoginState>,
     server_list: Option<Res<ServerList>>,
 ) {
@@ -77,7 +82,8 @@ pub fn login_system(
                     ui.label("Logging in");
                 });
         }
-        LoginState::JoiningServer => {
+        // While in the login queue, ui_login_queue_system shows the waiting screen
+        LoginState::JoiningServer if login_queue.is_none() => {
             egui::Window::new("Connecting...")
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .collapsible(false)
@@ -129,6 +135,17 @@ pub fn login_event_system(
                 }
                 *login_state = LoginState::JoiningServer;
             }
+            LoginEvent::SubmitChallenge { code } => {
+                if matches!(*login_state, LoginState::Challenge(_)) {
+                    if let Some(login_connection) = &login_connection {
+                        login_connection
+                            .login_challenge_response_tx
+                            .send(code.clone())
+                            .ok();
+                    }
+                    *login_state = LoginState::WaitServerList;
+                }
+            }
         }
     }
 }
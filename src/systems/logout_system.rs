@@ -0,0 +1,84 @@
+use bevy::{
+    app::AppExit,
+    prelude::{Commands, DespawnRecursiveExt, EventReader, EventWriter, NextState, Res, ResMut},
+};
+
+use rose_data::ZoneId;
+use rose_game_common::messages::client::ClientMessage;
+
+use crate::{
+    events::{LoadZoneEvent, LogoutEvent},
+    resources::{
+        AppState, CharacterSwitch, ClientEntityList, ConnectionState, GameConnection,
+        WorldConnection,
+    },
+};
+
+pub fn logout_system(
+    mut commands: Commands,
+    mut logout_events: EventReader<LogoutEvent>,
+    mut app_state_next: ResMut<NextState<AppState>>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut load_zone_events: EventWriter<LoadZoneEvent>,
+    mut client_entity_list: ResMut<ClientEntityList>,
+    mut character_switch: ResMut<CharacterSwitch>,
+    mut connection_state: ResMut<ConnectionState>,
+    world_connection: Option<Res<WorldConnection>>,
+) {
+    let Some(event) = logout_events.iter().last().copied() else {
+        return;
+    };
+
+    if event == LogoutEvent::Exit {
+        app_exit_events.send(AppExit);
+        return;
+    }
+
+    // Despawn the player and every other entity from the game server
+    let player_entity = client_entity_list.player_entity;
+    for entity in client_entity_list
+        .client_entities
+        .iter()
+        .flatten()
+        .filter(|entity| Some(**entity) != player_entity)
+        .chain(player_entity.iter())
+    {
+        commands.entity(*entity).despawn_recursive();
+    }
+    client_entity_list.clear();
+    client_entity_list.player_entity = None;
+    client_entity_list.player_entity_id = None;
+    client_entity_list.zone_id = None;
+    commands.remove_resource::<GameConnection>();
+
+    // Keep showing any connection error which caused the logout
+    let show_error = connection_state.is_error();
+
+    match (event, world_connection) {
+        (
+            LogoutEvent::ReturnToCharacterSelect | LogoutEvent::SwitchCharacter,
+            Some(world_connection),
+        ) => {
+            // Character select uses the same zone as the login screen
+            load_zone_events.send(LoadZoneEvent::new(ZoneId::new(4).unwrap()));
+            world_connection
+                .client_message_tx
+                .send(ClientMessage::GetCharacterList)
+                .ok();
+            app_state_next.set(AppState::GameCharacterSelect);
+
+            if !show_error {
+                *connection_state = ConnectionState::SelectingCharacter;
+            }
+        }
+        _ => {
+            character_switch.character_name = None;
+            commands.remove_resource::<WorldConnection>();
+            app_state_next.set(AppState::GameLogin);
+
+            if !show_error {
+                *connection_state = ConnectionState::Disconnected;
+            }
+        }
+    }
+}
@@ -1,140 +1,210 @@
 mod ability_values_system;
+mod account_settings_system;
 mod animation_effect_system;
 mod animation_sound_system;
 mod auto_login_system;
 mod background_music_system;
+mod bank_message_handler;
 mod character_model_add_collider_system;
 mod character_model_blink_system;
 mod character_model_system;
 mod character_select_system;
+mod chat_command_system;
+mod cinematic_mode_system;
+mod clan_message_handler;
 mod clan_system;
 mod client_entity_event_system;
+mod client_entity_registry_system;
+mod client_settings_system;
 mod collision_system;
 mod command_system;
 mod conversation_dialog_system;
 mod cooldown_system;
+mod crowd_density_system;
 mod damage_digit_render_system;
 mod debug_inspector_system;
 mod debug_render_collider_system;
 mod debug_render_directional_light_system;
 mod debug_render_skeleton_system;
 mod directional_light_system;
+mod dynamic_point_light_system;
 mod effect_system;
 mod facing_direction_system;
 mod free_camera_system;
 mod game_connection_system;
 mod game_mouse_input_system;
 mod game_system;
+mod growth_object_system;
+mod headless_system;
 mod hit_event_system;
+mod interact_highlight_system;
 mod item_drop_model_system;
 mod login_connection_system;
 mod login_system;
+mod logout_system;
 mod model_viewer_system;
+mod monster_spawn_telegraph_system;
 mod move_destination_effect_system;
+mod movement_validation_system;
 mod name_tag_system;
 mod name_tag_update_color_system;
 mod name_tag_update_healthbar_system;
+mod name_tag_update_lifetimebar_system;
 mod name_tag_vehicle_height_system;
 mod name_tag_visibility_system;
 mod network_thread_system;
+mod npc_ambient_system;
 mod npc_idle_sound_system;
 mod npc_model_add_collider_system;
 mod npc_model_system;
 mod orbit_camera_system;
 mod particle_sequence_system;
+mod party_member_system;
+mod party_target_system;
 mod passive_recovery_system;
 mod pending_damage_system;
 mod pending_skill_effect_system;
+mod personal_store_message_handler;
 mod personal_store_model_add_collider_system;
 mod personal_store_model_system;
 mod player_command_system;
+mod player_visibility_system;
+mod position_correction_system;
 mod projectile_system;
 mod quest_trigger_system;
+mod replay_record_system;
+mod replay_viewer_system;
 mod spawn_effect_system;
 mod spawn_projectile_system;
 mod status_effect_system;
+mod store_low_power_mode_system;
+mod summon_system;
 mod systemfunc_event_system;
+mod texture_streaming_system;
 mod update_position_system;
 mod use_item_event_system;
+mod user_script_system;
 mod vehicle_model_system;
 mod vehicle_sound_system;
+mod vfs_mod_hot_reload_system;
 mod visible_status_effects_system;
 mod world_connection_system;
 mod world_time_system;
+mod zone_data_verification_system;
+mod zone_navigation_system;
 mod zone_time_system;
+mod zone_transition_system;
 mod zone_viewer_system;
 
 pub use ability_values_system::ability_values_system;
+pub use account_settings_system::account_settings_system;
 pub use animation_effect_system::animation_effect_system;
 pub use animation_sound_system::animation_sound_system;
 pub use auto_login_system::auto_login_system;
 pub use background_music_system::background_music_system;
+pub use bank_message_handler::{bank_message_handler, BANK_SERVER_MESSAGES};
 pub use character_model_add_collider_system::character_model_add_collider_system;
 pub use character_model_blink_system::character_model_blink_system;
 pub use character_model_system::character_model_update_system;
 pub use character_select_system::{
     character_select_enter_system, character_select_event_system, character_select_exit_system,
     character_select_input_system, character_select_models_system, character_select_system,
+    character_switch_system,
+};
+pub use chat_command_system::chat_command_system;
+pub use cinematic_mode_system::cinematic_mode_system;
+pub use clan_message_handler::{
+    clan_extended_message_handler, clan_message_handler, CLAN_EXTENDED_SERVER_MESSAGES,
+    CLAN_SERVER_MESSAGES,
 };
 pub use clan_system::clan_system;
 pub use client_entity_event_system::client_entity_event_system;
+pub use client_entity_registry_system::client_entity_registry_system;
+pub use client_settings_system::client_settings_system;
 pub use collision_system::{
     collision_height_only_system, collision_player_system, collision_player_system_join_zoin,
 };
 pub use command_system::command_system;
 pub use conversation_dialog_system::conversation_dialog_system;
 pub use cooldown_system::cooldown_system;
+pub use crowd_density_system::crowd_density_system;
 pub use damage_digit_render_system::damage_digit_render_system;
 pub use debug_inspector_system::DebugInspectorPlugin;
 pub use debug_render_collider_system::debug_render_collider_system;
 pub use debug_render_directional_light_system::debug_render_directional_light_system;
 pub use debug_render_skeleton_system::debug_render_skeleton_system;
 pub use directional_light_system::directional_light_system;
+pub use dynamic_point_light_system::dynamic_point_light_system;
 pub use effect_system::effect_system;
 pub use facing_direction_system::facing_direction_system;
 pub use free_camera_system::{free_camera_system, FreeCamera};
 pub use game_connection_system::game_connection_system;
 pub use game_mouse_input_system::game_mouse_input_system;
 pub use game_system::{game_state_enter_system, game_zone_change_system};
+pub use growth_object_system::growth_object_system;
+pub use headless_system::headless_system;
 pub use hit_event_system::hit_event_system;
+pub use interact_highlight_system::{interact_highlight_system, is_interactable};
 pub use item_drop_model_system::{item_drop_model_add_collider_system, item_drop_model_system};
 pub use login_connection_system::login_connection_system;
 pub use login_system::{
     login_event_system, login_state_enter_system, login_state_exit_system, login_system,
 };
+pub use logout_system::logout_system;
 pub use model_viewer_system::{
     model_viewer_enter_system, model_viewer_exit_system, model_viewer_system,
 };
+pub use monster_spawn_telegraph_system::monster_spawn_telegraph_system;
 pub use move_destination_effect_system::move_destination_effect_system;
+pub use movement_validation_system::movement_validation_system;
 pub use name_tag_system::name_tag_system;
 pub use name_tag_update_color_system::name_tag_update_color_system;
 pub use name_tag_update_healthbar_system::name_tag_update_healthbar_system;
+pub use name_tag_update_lifetimebar_system::name_tag_update_lifetimebar_system;
 pub use name_tag_vehicle_height_system::name_tag_vehicle_height_system;
 pub use name_tag_visibility_system::name_tag_visibility_system;
 pub use network_thread_system::network_thread_system;
+pub use npc_ambient_system::npc_ambient_system;
 pub use npc_idle_sound_system::npc_idle_sound_system;
 pub use npc_model_add_collider_system::npc_model_add_collider_system;
 pub use npc_model_system::npc_model_update_system;
 pub use orbit_camera_system::{orbit_camera_system, OrbitCamera};
 pub use particle_sequence_system::particle_sequence_system;
+pub use party_member_system::party_member_system;
+pub use party_target_system::party_target_system;
 pub use passive_recovery_system::passive_recovery_system;
 pub use pending_damage_system::pending_damage_system;
 pub use pending_skill_effect_system::pending_skill_effect_system;
+pub use personal_store_message_handler::{
+    personal_store_message_handler, PERSONAL_STORE_SERVER_MESSAGES,
+};
 pub use personal_store_model_add_collider_system::personal_store_model_add_collider_system;
 pub use personal_store_model_system::personal_store_model_system;
 pub use player_command_system::player_command_system;
+pub use player_visibility_system::player_visibility_system;
+pub use position_correction_system::{position_correction_system, POSITION_CORRECTION_DURATION};
 pub use projectile_system::projectile_system;
 pub use quest_trigger_system::quest_trigger_system;
+pub use replay_record_system::replay_record_system;
+pub use replay_viewer_system::{replay_viewer_enter_system, replay_viewer_system};
 pub use spawn_effect_system::spawn_effect_system;
 pub use spawn_projectile_system::spawn_projectile_system;
 pub use status_effect_system::status_effect_system;
+pub use store_low_power_mode_system::store_low_power_mode_system;
+pub use summon_system::summon_system;
 pub use systemfunc_event_system::system_func_event_system;
+pub use texture_streaming_system::texture_streaming_system;
 pub use update_position_system::update_position_system;
 pub use use_item_event_system::use_item_event_system;
+pub use user_script_system::user_script_system;
 pub use vehicle_model_system::vehicle_model_system;
 pub use vehicle_sound_system::vehicle_sound_system;
+pub use vfs_mod_hot_reload_system::vfs_mod_hot_reload_system;
 pub use visible_status_effects_system::visible_status_effects_system;
 pub use world_connection_system::world_connection_system;
 pub use world_time_system::world_time_system;
+pub use zone_data_verification_system::zone_data_verification_system;
+pub use zone_navigation_system::zone_navigation_system;
 pub use zone_time_system::zone_time_system;
+pub use zone_transition_system::zone_transition_system;
 pub use zone_viewer_system::zone_viewer_enter_system;
@@ -108,6 +108,7 @@ pub fn model_viewer_enter_system(
     name_tag_settings.show_all[NameTagType::Character] = false;
     name_tag_settings.show_all[NameTagType::Npc] = true;
     name_tag_settings.show_all[NameTagType::Monster] = true;
+    name_tag_settings.show_all[NameTagType::Summon] = true;
 }
 
 pub fn model_viewer_exit_system(
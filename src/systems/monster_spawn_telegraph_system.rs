@@ -0,0 +1,100 @@
+use bevy::{
+    math::{Vec3, Vec3Swizzles},
+    prelude::{Commands, Entity, EventReader, Local, Query, Res, Time, Transform, With},
+};
+
+use rose_game_common::{components::Npc, messages::ClientEntityId};
+
+use crate::{
+    components::{DynamicPointLight, MonsterSpawnTelegraph, PlayerCharacter, Position},
+    events::ExtendedServerMessageEvent,
+    protocol::ExtendedServerMessage,
+    resources::{ClientEntityList, GameData, ZoneTransition},
+};
+
+// How long to wait for the spawn packet of a monster the server told us has just spawned
+const MONSTER_SPAWNED_PENDING_TIMEOUT: f32 = 2.0;
+
+// Monsters spawning further away than this are not telegraphed
+const MONSTER_SPAWN_TELEGRAPH_DISTANCE: f32 = 4000.0;
+
+// Scale of the model at the start of the emerge animation
+const MONSTER_SPAWN_START_SCALE: f32 = 0.05;
+
+pub fn monster_spawn_telegraph_system(
+    mut commands: Commands,
+    mut query_monsters: Query<(
+        Entity,
+        &Npc,
+        &Position,
+        &mut MonsterSpawnTelegraph,
+        &mut Transform,
+    )>,
+    query_npcs: Query<(), With<Npc>>,
+    query_player: Query<&Position, With<PlayerCharacter>>,
+    client_entity_list: Res<ClientEntityList>,
+    game_data: Res<GameData>,
+    zone_transition: Res<ZoneTransition>,
+    time: Res<Time>,
+    mut pending_spawned: Local<Vec<(ClientEntityId, f32)>>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        if let &ExtendedServerMessage::MonsterSpawned(entity_id) = message {
+            pending_spawned.push((entity_id, 0.0));
+        }
+    }
+
+    // The spawned message can arrive before the monster's spawn packet has been handled
+    pending_spawned.retain_mut(|(entity_id, age)| {
+        if let Some(entity) = client_entity_list.get(*entity_id) {
+            if query_npcs.contains(entity) {
+                commands
+                    .entity(entity)
+                    .insert(MonsterSpawnTelegraph::default());
+            }
+            return false;
+        }
+
+        *age += time.delta_seconds();
+        *age < MONSTER_SPAWNED_PENDING_TIMEOUT
+    });
+    let player_position = query_player.get_single().ok();
+
+    for (entity, npc, position, mut spawn_telegraph, mut transform) in query_monsters.iter_mut() {
+        let base_scale = game_data
+            .npcs
+            .get_npc(npc.id)
+            .map_or(1.0, |npc_data| npc_data.scale);
+
+        if spawn_telegraph.elapsed == 0.0 {
+            // Monsters spawned whilst loading a zone, or far away, appear immediately
+            let in_range = player_position.map_or(false, |player_position| {
+                player_position.xy().distance(position.xy()) < MONSTER_SPAWN_TELEGRAPH_DISTANCE
+            });
+            if zone_transition.active.is_some() || !in_range {
+                commands.entity(entity).remove::<MonsterSpawnTelegraph>();
+                continue;
+            }
+
+            commands
+                .entity(entity)
+                .insert(DynamicPointLight::monster_spawn());
+        }
+
+        spawn_telegraph.elapsed += time.delta_seconds();
+
+        let progress = spawn_telegraph.progress();
+        let scale = if progress >= 1.0 {
+            commands.entity(entity).remove::<MonsterSpawnTelegraph>();
+            base_scale
+        } else {
+            // Ease out so the monster rises quickly then settles
+            let eased = 1.0 - (1.0 - progress) * (1.0 - progress);
+            base_scale * (MONSTER_SPAWN_START_SCALE + (1.0 - MONSTER_SPAWN_START_SCALE) * eased)
+        };
+        if transform.scale.x != scale {
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
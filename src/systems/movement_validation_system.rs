@@ -0,0 +1,34 @@
+use bevy::prelude::{Query, ResMut, With};
+
+use rose_game_common::components::{AbilityValues, MoveMode, MoveSpeed};
+
+use crate::{components::PlayerCharacter, resources::PositionCorrectionStats};
+
+// Allow for rounding in the speed sent by the server
+const MOVE_SPEED_TOLERANCE: f32 = 1.0;
+
+pub fn movement_validation_system(
+    mut query_player: Query<(&AbilityValues, &MoveMode, &mut MoveSpeed), With<PlayerCharacter>>,
+    mut position_correction_stats: ResMut<PositionCorrectionStats>,
+) {
+    let Ok((ability_values, move_mode, mut move_speed)) = query_player.get_single_mut() else {
+        return;
+    };
+
+    // Never move our own character faster than our ability values allow, if the
+    // server really is faster it will correct our position which we can detect
+    let max_move_speed = ability_values.get_move_speed(move_mode);
+    if move_speed.speed <= max_move_speed + MOVE_SPEED_TOLERANCE {
+        return;
+    }
+
+    if position_correction_stats.num_speed_clamped == 0 {
+        log::warn!(
+            "Clamped our move speed from {} to the maximum of {} from our ability values",
+            move_speed.speed,
+            max_move_speed
+        );
+    }
+    position_correction_stats.record_speed_clamp(move_speed.speed, max_move_speed);
+    move_speed.speed = max_move_speed;
+}
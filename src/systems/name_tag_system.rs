@@ -4,13 +4,13 @@ use arrayvec::ArrayVec;
 use bevy::{
     ecs::query::WorldQuery,
     prelude::{
-        Assets, BuildChildren, Changed, Color, Commands, ComputedVisibility,
-        DespawnRecursiveExt, Entity, EventReader, GlobalTransform, Handle, Image, Local, Or,
-        Query, RemovedComponents, Res, ResMut, Transform, Vec2, Vec3, Visibility, With, Without,
+        Added, Assets, BuildChildren, Changed, Color, Commands, ComputedVisibility,
+        DespawnRecursiveExt, Entity, EventReader, GlobalTransform, Handle, Image, Local, Or, Query,
+        RemovedComponents, Res, ResMut, Transform, Vec2, Vec3, Visibility, With, Without,
     },
     render::{
         render_resource::{Extent3d, TextureDimension, TextureFormat},
-        texture::ImageSampler,
+        texture::{ImageSampler, DEFAULT_IMAGE_HANDLE},
         view::NoFrustumCulling,
     },
     utils::HashMap,
@@ -23,18 +23,23 @@ use rose_game_common::components::{Level, Npc, Team};
 use crate::{
     components::{
         ClanMembership, ClientEntityName, ModelHeight, NameTag, NameTagClanName, NameTagEntity,
-        NameTagHealthbarBackground, NameTagHealthbarForeground, NameTagName, NameTagTargetMark,
-        NameTagType, PersonalStore, PlayerCharacter,
+        NameTagHealthbarBackground, NameTagHealthbarForeground, NameTagHealthbarGhost,
+        NameTagLifetimebarBackground, NameTagLifetimebarForeground, NameTagName, NameTagTargetMark,
+        NameTagType, PersonalStore, PlayerCharacter, Summon,
     },
     events::LoadZoneEvent,
     render::WorldUiRect,
-    resources::{GameData, NameTagSettings, UiResources, UiSpriteSheetType},
+    resources::{GameData, NameTagSettings, NpcRank, UiResources, UiSpriteSheetType},
 };
 
 const ORDER_HEALTH_BACKGROUND: u8 = 0;
-const ORDER_HEALTH_FOREGROUND: u8 = 1;
-const ORDER_NAME: u8 = 2;
-const ORDER_TARGET_MARK: u8 = 2;
+const ORDER_LIFETIME_BACKGROUND: u8 = 0;
+const ORDER_HEALTH_GHOST: u8 = 1;
+const ORDER_LIFETIME_FOREGROUND: u8 = 1;
+const ORDER_HEALTH_FOREGROUND: u8 = 2;
+const ORDER_NAME: u8 = 3;
+const ORDER_TARGET_MARK: u8 = 3;
+const HEALTH_GHOST_COLOR: Color = Color::rgba(1.0, 0.85, 0.7, 0.85);
 const MAX_NAME_ROWS: usize = 2;
 
 pub struct NameTagData {
@@ -72,18 +77,45 @@ pub struct NameTagObjectQuery<'w> {
     level: Option<&'w Level>,
     team: Option<&'w Team>,
     clan_membership: Option<&'w ClanMembership>,
+    summon: Option<&'w Summon>,
+}
+
+pub fn get_npc_rank(
+    game_data: &GameData,
+    name_tag_settings: &NameTagSettings,
+    npc: Option<&Npc>,
+) -> NpcRank {
+    name_tag_settings.get_npc_rank(
+        npc.and_then(|npc| game_data.npcs.get_npc(npc.id))
+            .and_then(|npc_data| npc_data.npc_type_index)
+            .map(|npc_type_index| npc_type_index.get()),
+    )
 }
 
 pub fn get_monster_name_tag_color(
+    name_tag_settings: &NameTagSettings,
     player_level: Option<&Level>,
     monster_level: Option<&Level>,
     monster_team: Option<&Team>,
+    monster_rank: NpcRank,
 ) -> egui::Color32 {
     let level_diff = player_level.map_or(1, |level| level.level) as i32
         - monster_level.map_or(1, |level| level.level) as i32;
 
     if monster_team.map_or(false, |team| team.id == Team::DEFAULT_NPC_TEAM_ID) {
         egui::Color32::GREEN
+    } else if monster_rank == NpcRank::Boss {
+        egui::Color32::from_rgb(255, 90, 60)
+    } else if monster_rank == NpcRank::Elite {
+        egui::Color32::from_rgb(255, 190, 60)
+    } else if name_tag_settings.simple_level_colors {
+        if level_diff >= 10 {
+            egui::Color32::from_rgb(160, 160, 160)
+        } else if level_diff >= -3 {
+            egui::Color32::from_rgb(120, 230, 100)
+        } else {
+            egui::Color32::from_rgb(255, 90, 80)
+        }
     } else if level_diff <= -23 {
         egui::Color32::from_rgb(224, 149, 255)
     } else if level_diff <= -16 {
@@ -106,6 +138,7 @@ pub fn get_monster_name_tag_color(
 }
 
 fn create_pending_nametag(
+    game_data: &GameData,
     name_tag_settings: &NameTagSettings,
     egui_context: &mut EguiContexts,
     object: &NameTagObjectQueryItem,
@@ -169,15 +202,37 @@ fn create_pending_nametag(
                 )
             }
         }
-        NameTagType::Monster => egui::epaint::text::LayoutJob::single_section(
+        NameTagType::Monster => {
+            let rank = get_npc_rank(game_data, name_tag_settings, object.npc);
+            let display_name = match rank {
+                NpcRank::Normal => display_name,
+                NpcRank::Elite => format!("\u{2605} {}", display_name),
+                NpcRank::Boss => format!("\u{265b} {}", display_name),
+            };
+
+            egui::epaint::text::LayoutJob::single_section(
+                display_name,
+                egui::TextFormat::simple(
+                    egui::FontId::proportional(name_tag_settings.font_size[name_tag_type]),
+                    get_monster_name_tag_color(
+                        name_tag_settings,
+                        player.map(|player| player.level),
+                        object.level,
+                        object.team,
+                        rank,
+                    ),
+                ),
+            )
+        }
+        NameTagType::Summon => egui::epaint::text::LayoutJob::single_section(
             display_name,
             egui::TextFormat::simple(
                 egui::FontId::proportional(name_tag_settings.font_size[name_tag_type]),
-                get_monster_name_tag_color(
-                    player.map(|player| player.level),
-                    object.level,
-                    object.team,
-                ),
+                if object.summon.map_or(false, |summon| summon.is_player_owned) {
+                    egui::Color32::from_rgb(100, 220, 255)
+                } else {
+                    egui::Color32::from_rgb(200, 160, 255)
+                },
             ),
         ),
         NameTagType::Npc => {
@@ -398,6 +453,9 @@ fn create_nametag_data(
     if matches!(pending_data.name_tag_type, NameTagType::Monster) {
         // Give some space for monster health bar under name
         row_offset_y += 15.0;
+    } else if matches!(pending_data.name_tag_type, NameTagType::Summon) {
+        // Give some space for the health and lifetime bars under name
+        row_offset_y += 22.0;
     }
 
     // Create WorldUiRect for the outlined text
@@ -434,7 +492,12 @@ pub fn name_tag_system(
     query_add: Query<NameTagObjectQuery, Without<NameTagEntity>>,
     query_changed: Query<
         (Entity, Option<&NameTagEntity>),
-        Or<(Changed<ClientEntityName>, Changed<PersonalStore>, Changed<ClanMembership>)>,
+        Or<(
+            Changed<ClientEntityName>,
+            Changed<PersonalStore>,
+            Changed<ClanMembership>,
+            Added<Summon>,
+        )>,
     >,
     mut removed_personal_store: RemovedComponents<PersonalStore>,
     mut removed_clan_membership: RemovedComponents<ClanMembership>,
@@ -502,7 +565,9 @@ pub fn name_tag_system(
     }
 
     for object in query_add.iter() {
-        let name_tag_type = if let Some(npc) = object.npc {
+        let name_tag_type = if object.summon.is_some() {
+            NameTagType::Summon
+        } else if let Some(npc) = object.npc {
             if object
                 .team
                 .map_or(false, |team| team.id == Team::DEFAULT_NPC_TEAM_ID)
@@ -521,8 +586,17 @@ pub fn name_tag_system(
 
         let cache_key = if let Some(store) = object.personal_store {
             store.title.clone()
+        } else if let Some(summon) = object.summon {
+            // Summon names are coloured by owner, so must not share a cache entry with monsters
+            format!("{}\nsummon:{}", object.name.name, summon.is_player_owned)
         } else if let Some(clan_membership) = &object.clan_membership {
             format!("{}\n{}", object.name.name, clan_membership.name)
+        } else if name_tag_type == NameTagType::Monster {
+            // Elite and boss monsters have an icon, so must not share a cache entry
+            match get_npc_rank(&game_data, &name_tag_settings, object.npc) {
+                NpcRank::Normal => object.name.name.clone(),
+                rank => format!("{}\nrank:{:?}", object.name.name, rank),
+            }
         } else {
             object.name.name.clone()
         };
@@ -550,6 +624,7 @@ pub fn name_tag_system(
             name_tag_cache.pending.insert(
                 object.entity,
                 create_pending_nametag(
+                    &game_data,
                     &name_tag_settings,
                     &mut egui_context,
                     &object,
@@ -607,7 +682,7 @@ pub fn name_tag_system(
                     .get_sprite(0, "UI00_GUAGE_BG_AVATAR")
                     .zip(ui_resources.get_sprite_image(0, "UI00_GUAGE_BG_AVATAR")),
             ),
-            NameTagType::Monster => (
+            NameTagType::Monster | NameTagType::Summon => (
                 ui_resources
                     .get_sprite(0, "UI00_GUAGE_RED")
                     .zip(ui_resources.get_sprite_image(0, "UI00_GUAGE_RED")),
@@ -619,6 +694,7 @@ pub fn name_tag_system(
         };
 
         let mut health_bar_size = Vec2::ZERO;
+        let mut health_bar_offset_y = 0.0;
         let mut health_bar_foreground_uv_x_bounds = (0.0, 0.0);
         if let (
             Some((health_foreground_sprite, health_foreground_image)),
@@ -636,6 +712,7 @@ pub fn name_tag_system(
                 name_tag_data.rects[0].screen_offset.y - bar_height
             };
             health_bar_size = Vec2::new(bar_width, bar_height);
+            health_bar_offset_y = bar_offset_y;
 
             healthbar_bg_rect = Some(WorldUiRect {
                 screen_offset: Vec2::new(-bar_width / 2.0, bar_offset_y),
@@ -653,24 +730,100 @@ pub fn name_tag_system(
                 order: ORDER_HEALTH_BACKGROUND,
             });
 
-            health_bar_foreground_uv_x_bounds = (
-                health_foreground_sprite.uv.min.x,
-                health_foreground_sprite.uv.max.x,
-            );
+            // Character health bars are coloured by health, so use a plain white
+            // image rather than the red gauge sprite
+            let (foreground_image, foreground_uv_min, foreground_uv_max) =
+                if matches!(name_tag_type, NameTagType::Character) {
+                    (DEFAULT_IMAGE_HANDLE.typed(), Vec2::ZERO, Vec2::ONE)
+                } else {
+                    (
+                        health_foreground_image.clone_weak(),
+                        Vec2::new(
+                            health_foreground_sprite.uv.min.x,
+                            health_foreground_sprite.uv.min.y,
+                        ),
+                        Vec2::new(
+                            health_foreground_sprite.uv.max.x,
+                            health_foreground_sprite.uv.max.y,
+                        ),
+                    )
+                };
+
+            health_bar_foreground_uv_x_bounds = (foreground_uv_min.x, foreground_uv_max.x);
             healthbar_fg_rect = Some(WorldUiRect {
                 screen_offset: Vec2::new(-bar_width / 2.0, bar_offset_y),
                 screen_size: Vec2::new(bar_width, bar_height),
-                image: health_foreground_image.clone_weak(),
+                image: foreground_image,
+                uv_min: foreground_uv_min,
+                uv_max: foreground_uv_max,
+                color: Color::WHITE,
+                order: ORDER_HEALTH_FOREGROUND,
+            });
+        }
+
+        // Summons which expire have a lifetime bar under their health bar
+        let mut lifetimebar_fg_rect = None;
+        let mut lifetimebar_bg_rect = None;
+        let mut lifetime_bar_foreground_uv_x_bounds = (0.0, 0.0);
+        let (lifetime_foreground, lifetime_background) = if object
+            .summon
+            .map_or(false, |summon| summon.total_lifetime.is_some())
+        {
+            (
+                ui_resources
+                    .get_sprite(0, "UI00_GUAGE_BLUE")
+                    .zip(ui_resources.get_sprite_image(0, "UI00_GUAGE_BLUE")),
+                ui_resources
+                    .get_sprite(0, "UI00_GUAGE_BACKGROUND")
+                    .zip(ui_resources.get_sprite_image(0, "UI00_GUAGE_BACKGROUND")),
+            )
+        } else {
+            (None, None)
+        };
+
+        if let (
+            Some((lifetime_foreground_sprite, lifetime_foreground_image)),
+            Some((lifetime_background_sprite, lifetime_background_image)),
+        ) = (lifetime_foreground, lifetime_background)
+        {
+            let bar_width = lifetime_background_sprite.width * pixels_per_point;
+            let bar_height = lifetime_background_sprite.height * pixels_per_point;
+            let bar_offset_y = health_bar_offset_y - bar_height - 1.0;
+
+            lifetimebar_bg_rect = Some(WorldUiRect {
+                screen_offset: Vec2::new(-bar_width / 2.0, bar_offset_y),
+                screen_size: Vec2::new(bar_width, bar_height),
+                image: lifetime_background_image.clone_weak(),
                 uv_min: Vec2::new(
-                    health_foreground_sprite.uv.min.x,
-                    health_foreground_sprite.uv.min.y,
+                    lifetime_background_sprite.uv.min.x,
+                    lifetime_background_sprite.uv.min.y,
                 ),
                 uv_max: Vec2::new(
-                    health_foreground_sprite.uv.max.x,
-                    health_foreground_sprite.uv.max.y,
+                    lifetime_background_sprite.uv.max.x,
+                    lifetime_background_sprite.uv.max.y,
                 ),
                 color: Color::WHITE,
-                order: ORDER_HEALTH_FOREGROUND,
+                order: ORDER_LIFETIME_BACKGROUND,
+            });
+
+            lifetime_bar_foreground_uv_x_bounds = (
+                lifetime_foreground_sprite.uv.min.x,
+                lifetime_foreground_sprite.uv.max.x,
+            );
+            lifetimebar_fg_rect = Some(WorldUiRect {
+                screen_offset: Vec2::new(-bar_width / 2.0, bar_offset_y),
+                screen_size: Vec2::new(bar_width, bar_height),
+                image: lifetime_foreground_image.clone_weak(),
+                uv_min: Vec2::new(
+                    lifetime_foreground_sprite.uv.min.x,
+                    lifetime_foreground_sprite.uv.min.y,
+                ),
+                uv_max: Vec2::new(
+                    lifetime_foreground_sprite.uv.max.x,
+                    lifetime_foreground_sprite.uv.max.y,
+                ),
+                color: Color::WHITE,
+                order: ORDER_LIFETIME_FOREGROUND,
             });
         }
 
@@ -775,12 +928,36 @@ pub fn name_tag_system(
         }
 
         if let Some(rect) = healthbar_fg_rect.take() {
+            commands
+                .spawn((
+                    NameTagHealthbarGhost {
+                        full_width: health_bar_size.x,
+                        uv_min_x: health_bar_foreground_uv_x_bounds.0,
+                        uv_max_x: health_bar_foreground_uv_x_bounds.1,
+                        displayed_percent: None,
+                        last_health_percent: 1.0,
+                        hold_time: 0.0,
+                    },
+                    WorldUiRect {
+                        color: HEALTH_GHOST_COLOR,
+                        order: ORDER_HEALTH_GHOST,
+                        ..rect.clone()
+                    },
+                    Transform::default(),
+                    GlobalTransform::default(),
+                    Visibility::Hidden,
+                    ComputedVisibility::default(),
+                    NoFrustumCulling,
+                ))
+                .set_parent(name_tag_entity);
+
             commands
                 .spawn((
                     NameTagHealthbarForeground {
                         full_width: health_bar_size.x,
                         uv_min_x: health_bar_foreground_uv_x_bounds.0,
                         uv_max_x: health_bar_foreground_uv_x_bounds.1,
+                        displayed_percent: None,
                     },
                     rect,
                     Transform::default(),
@@ -792,6 +969,39 @@ pub fn name_tag_system(
                 .set_parent(name_tag_entity);
         }
 
+        if let Some(rect) = lifetimebar_bg_rect.take() {
+            commands
+                .spawn((
+                    NameTagLifetimebarBackground,
+                    rect,
+                    Transform::default(),
+                    GlobalTransform::default(),
+                    Visibility::default(),
+                    ComputedVisibility::default(),
+                    NoFrustumCulling,
+                ))
+                .set_parent(name_tag_entity);
+        }
+
+        if let Some(rect) = lifetimebar_fg_rect.take() {
+            let full_width = rect.screen_size.x;
+            commands
+                .spawn((
+                    NameTagLifetimebarForeground {
+                        full_width,
+                        uv_min_x: lifetime_bar_foreground_uv_x_bounds.0,
+                        uv_max_x: lifetime_bar_foreground_uv_x_bounds.1,
+                    },
+                    rect,
+                    Transform::default(),
+                    GlobalTransform::default(),
+                    Visibility::default(),
+                    ComputedVisibility::default(),
+                    NoFrustumCulling,
+                ))
+                .set_parent(name_tag_entity);
+        }
+
         commands
             .entity(object.entity)
             .insert(NameTagEntity(name_tag_entity))
@@ -1,43 +1,73 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Changed, Children, Color, Or, Parent, Query, With},
+    prelude::{Added, Changed, Children, Color, Or, Parent, Query, Res, With},
 };
 
-use rose_game_common::components::{Level, Team};
+use rose_game_common::components::{Level, Npc, Team};
 
 use crate::{
-    components::{NameTag, NameTagName, NameTagType, PlayerCharacter},
+    components::{Clan, ClanMembership, NameTag, NameTagName, NameTagType, PlayerCharacter},
     render::WorldUiRect,
-    systems::name_tag_system::get_monster_name_tag_color,
+    resources::{GameData, NameTagSettings},
+    systems::name_tag_system::{get_monster_name_tag_color, get_npc_rank},
 };
 
 #[derive(WorldQuery)]
 pub struct PlayerQuery<'w> {
     level: &'w Level,
     team: &'w Team,
+    clan: Option<&'w Clan>,
 }
 
 pub fn name_tag_update_color_system(
-    query_player: Query<PlayerQuery, (With<PlayerCharacter>, Or<(Changed<Level>, Changed<Team>)>)>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    query_player_changed: Query<
+        (),
+        (
+            With<PlayerCharacter>,
+            Or<(Changed<Level>, Changed<Team>, Changed<Clan>)>,
+        ),
+    >,
+    query_clan_membership_changed: Query<(), Changed<ClanMembership>>,
+    query_nametags_added: Query<(), Added<NameTag>>,
     query_nametags: Query<(&Parent, &NameTag, &Children)>,
+    query_clan_membership: Query<&ClanMembership>,
     query_level: Query<&Level>,
     query_team: Query<&Team>,
+    query_npc: Query<&Npc>,
     mut query_name_rects: Query<&mut WorldUiRect, With<NameTagName>>,
+    game_data: Res<GameData>,
+    name_tag_settings: Res<NameTagSettings>,
 ) {
-    let player = if let Ok(player) = query_player.get_single() {
-        player
-    } else {
+    // Clan war enemies are only known once their name tag and clan membership exist
+    if query_player_changed.is_empty()
+        && query_clan_membership_changed.is_empty()
+        && query_nametags_added.is_empty()
+        && !name_tag_settings.is_changed()
+    {
+        return;
+    }
+
+    let Ok(player) = query_player.get_single() else {
         return;
     };
 
     for (parent, nametag, children) in query_nametags.iter() {
         let color = match nametag.name_tag_type {
-            NameTagType::Npc => continue,
+            NameTagType::Npc | NameTagType::Summon => continue,
             NameTagType::Character => {
-                if query_team
+                let is_enemy_team = query_team
                     .get(parent.get())
-                    .map_or(false, |team| team.id != player.team.id)
-                {
+                    .map_or(false, |team| team.id != player.team.id);
+                let is_clan_war_enemy = player.clan.map_or(false, |clan| {
+                    query_clan_membership
+                        .get(parent.get())
+                        .map_or(false, |clan_membership| {
+                            clan.find_war(&clan_membership.name).is_some()
+                        })
+                });
+
+                if is_enemy_team || is_clan_war_enemy {
                     Color::RED
                 } else {
                     Color::WHITE
@@ -45,9 +75,15 @@ pub fn name_tag_update_color_system(
             }
             NameTagType::Monster => {
                 let color = get_monster_name_tag_color(
+                    &name_tag_settings,
                     Some(player.level),
                     query_level.get(parent.get()).ok(),
                     query_team.get(parent.get()).ok(),
+                    get_npc_rank(
+                        &game_data,
+                        &name_tag_settings,
+                        query_npc.get(parent.get()).ok(),
+                    ),
                 )
                 .to_array();
 
@@ -1,26 +1,127 @@
-use bevy::prelude::{Parent, Query};
+use bevy::prelude::{Color, Parent, Query, Res, Time, Without};
 
 use rose_game_common::components::{AbilityValues, HealthPoints};
 
-use crate::{components::NameTagHealthbarForeground, render::WorldUiRect};
+use crate::{
+    components::{NameTag, NameTagHealthbarForeground, NameTagHealthbarGhost, NameTagType},
+    render::WorldUiRect,
+};
+
+// Rate at which the health bar moves towards the current health
+const HEALTH_BAR_LERP_SPEED: f32 = 12.0;
+
+// The recent damage bar waits before shrinking, so consecutive hits are shown together
+const HEALTH_GHOST_HOLD_TIME: f32 = 0.6;
+const HEALTH_GHOST_SHRINK_SPEED: f32 = 0.8;
+
+// Character health bars change colour as health drops
+fn get_health_bar_color(health_percent: f32) -> Color {
+    if health_percent > 0.5 {
+        Color::rgb(0.3, 0.85, 0.3)
+    } else if health_percent > 0.25 {
+        Color::rgb(0.95, 0.8, 0.2)
+    } else {
+        Color::rgb(0.9, 0.2, 0.2)
+    }
+}
+
+fn set_bar_percent(
+    rect: &mut WorldUiRect,
+    uv_min_x: f32,
+    uv_max_x: f32,
+    full_width: f32,
+    percent: f32,
+) {
+    rect.uv_max.x = uv_min_x + percent * (uv_max_x - uv_min_x);
+    rect.screen_size.x = full_width * percent;
+}
 
 pub fn name_tag_update_healthbar_system(
-    mut query_nametag_healthbar: Query<(&Parent, &NameTagHealthbarForeground, &mut WorldUiRect)>,
+    mut query_nametag_healthbar: Query<
+        (&Parent, &mut NameTagHealthbarForeground, &mut WorldUiRect),
+        Without<NameTagHealthbarGhost>,
+    >,
+    mut query_nametag_healthbar_ghost: Query<
+        (&Parent, &mut NameTagHealthbarGhost, &mut WorldUiRect),
+        Without<NameTagHealthbarForeground>,
+    >,
     query_parent: Query<&Parent>,
+    query_name_tag: Query<&NameTag>,
     query_health: Query<(&HealthPoints, &AbilityValues)>,
+    time: Res<Time>,
 ) {
-    for (parent, name_tag_healthbar_fg, mut rect) in query_nametag_healthbar.iter_mut() {
-        if let Ok((health_points, ability_values)) = query_parent
+    let delta_time = time.delta_seconds();
+    let get_health_percent = |parent: &Parent| {
+        query_parent
             .get(parent.get())
             .and_then(|parent| query_health.get(parent.get()))
-        {
-            let health_percent =
-                (health_points.hp as f32 / ability_values.get_max_health() as f32).max(0.0);
-
-            rect.uv_max.x = name_tag_healthbar_fg.uv_min_x
-                + health_percent
-                    * (name_tag_healthbar_fg.uv_max_x - name_tag_healthbar_fg.uv_min_x);
-            rect.screen_size.x = name_tag_healthbar_fg.full_width * health_percent;
+            .ok()
+            .map(|(health_points, ability_values)| {
+                (health_points.hp as f32 / ability_values.get_max_health() as f32).clamp(0.0, 1.0)
+            })
+    };
+
+    for (parent, mut name_tag_healthbar_fg, mut rect) in query_nametag_healthbar.iter_mut() {
+        let is_character = query_name_tag.get(parent.get()).map_or(false, |name_tag| {
+            matches!(name_tag.name_tag_type, NameTagType::Character)
+        });
+
+        let Some(health_percent) = get_health_percent(parent) else {
+            continue;
+        };
+
+        let displayed_percent = match name_tag_healthbar_fg.displayed_percent {
+            Some(displayed_percent) if (health_percent - displayed_percent).abs() > 0.001 => {
+                displayed_percent
+                    + (health_percent - displayed_percent)
+                        * (1.0 - (-HEALTH_BAR_LERP_SPEED * delta_time).exp())
+            }
+            _ => health_percent,
+        };
+        name_tag_healthbar_fg.displayed_percent = Some(displayed_percent);
+
+        set_bar_percent(
+            &mut rect,
+            name_tag_healthbar_fg.uv_min_x,
+            name_tag_healthbar_fg.uv_max_x,
+            name_tag_healthbar_fg.full_width,
+            displayed_percent,
+        );
+
+        if is_character {
+            rect.color = get_health_bar_color(displayed_percent);
         }
     }
+
+    for (parent, mut ghost, mut rect) in query_nametag_healthbar_ghost.iter_mut() {
+        let Some(health_percent) = get_health_percent(parent) else {
+            continue;
+        };
+
+        if health_percent < ghost.last_health_percent {
+            ghost.hold_time = HEALTH_GHOST_HOLD_TIME;
+        }
+        ghost.last_health_percent = health_percent;
+
+        let displayed_percent = match ghost.displayed_percent {
+            Some(displayed_percent) if displayed_percent > health_percent => {
+                if ghost.hold_time > 0.0 {
+                    ghost.hold_time -= delta_time;
+                    displayed_percent
+                } else {
+                    (displayed_percent - HEALTH_GHOST_SHRINK_SPEED * delta_time).max(health_percent)
+                }
+            }
+            _ => health_percent,
+        };
+        ghost.displayed_percent = Some(displayed_percent);
+
+        set_bar_percent(
+            &mut rect,
+            ghost.uv_min_x,
+            ghost.uv_max_x,
+            ghost.full_width,
+            displayed_percent,
+        );
+    }
 }
@@ -0,0 +1,30 @@
+use bevy::prelude::{Parent, Query};
+
+use crate::{
+    components::{NameTagLifetimebarForeground, Summon},
+    render::WorldUiRect,
+};
+
+pub fn name_tag_update_lifetimebar_system(
+    mut query_nametag_lifetimebar: Query<(
+        &Parent,
+        &NameTagLifetimebarForeground,
+        &mut WorldUiRect,
+    )>,
+    query_parent: Query<&Parent>,
+    query_summon: Query<&Summon>,
+) {
+    for (parent, name_tag_lifetimebar_fg, mut rect) in query_nametag_lifetimebar.iter_mut() {
+        if let Some(lifetime_percent) = query_parent
+            .get(parent.get())
+            .ok()
+            .and_then(|parent| query_summon.get(parent.get()).ok())
+            .and_then(|summon| summon.lifetime_percent())
+        {
+            rect.uv_max.x = name_tag_lifetimebar_fg.uv_min_x
+                + lifetime_percent
+                    * (name_tag_lifetimebar_fg.uv_max_x - name_tag_lifetimebar_fg.uv_min_x);
+            rect.screen_size.x = name_tag_lifetimebar_fg.full_width * lifetime_percent;
+        }
+    }
+}
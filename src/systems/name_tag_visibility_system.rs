@@ -1,13 +1,17 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Children, Entity, Local, Or, Parent, Query, Res, ResMut, Visibility, With},
+    prelude::{
+        Children, DetectChangesMut, Entity, Local, Or, Parent, Query, Res, ResMut, Visibility,
+        With, Without,
+    },
 };
 use rose_game_common::components::Npc;
 
 use crate::{
     components::{
-        Dead, NameTag, NameTagEntity, NameTagHealthbarBackground, NameTagHealthbarForeground,
-        NameTagTargetMark, NameTagType, PersonalStore,
+        Clan, ClientEntityName, Dead, NameTag, NameTagEntity, NameTagHealthbarBackground,
+        NameTagHealthbarForeground, NameTagHealthbarGhost, NameTagTargetMark, NameTagType,
+        PartyMember, PersonalStore, PlayerCharacter,
     },
     resources::{NameTagSettings, SelectedTarget},
 };
@@ -16,6 +20,7 @@ use crate::{
 pub struct NameTagVisibility {
     pub hover: Option<Entity>,
     pub selected: Option<Entity>,
+    pub always_show_health: Vec<Entity>,
 }
 
 #[derive(WorldQuery)]
@@ -50,6 +55,7 @@ pub fn name_tag_visibility_system(
             With<NameTagTargetMark>,
             With<NameTagHealthbarBackground>,
             With<NameTagHealthbarForeground>,
+            With<NameTagHealthbarGhost>,
         )>,
     >,
     query_name_tag_healthbar: Query<
@@ -57,9 +63,15 @@ pub fn name_tag_visibility_system(
         Or<(
             With<NameTagHealthbarBackground>,
             With<NameTagHealthbarForeground>,
+            With<NameTagHealthbarGhost>,
         )>,
     >,
     query_npc_dead: Query<&Dead, With<Npc>>,
+    query_player_clan: Query<&Clan, With<PlayerCharacter>>,
+    query_characters: Query<
+        (&NameTagEntity, &ClientEntityName, Option<&PartyMember>),
+        Without<PlayerCharacter>,
+    >,
     name_tag_settings: Res<NameTagSettings>,
 ) {
     if selected_target
@@ -80,6 +92,23 @@ pub fn name_tag_visibility_system(
         selected_target.hover = None;
     }
 
+    // Party and clan members can be set to always show their health bar
+    let player_clan = query_player_clan.get_single().ok();
+    let always_show_health: Vec<Entity> = query_characters
+        .iter()
+        .filter(|(_, name, party_member)| {
+            (name_tag_settings.show_party_health && party_member.is_some())
+                || (name_tag_settings.show_clan_health
+                    && player_clan.map_or(false, |clan| clan.find_member(&name.name).is_some()))
+        })
+        .map(|(name_tag_entity, _, _)| name_tag_entity.0)
+        .filter(|&entity| {
+            query_name_tag.get(entity).map_or(false, |name_tag| {
+                matches!(name_tag.name_tag.name_tag_type, NameTagType::Character)
+            }) && !is_store_name_tag(entity, &query_name_tag_parent, &query_personal_store)
+        })
+        .collect();
+
     let hover_name_tag_entity = selected_target
         .hover
         .and_then(|entity| query_name_tag_entity.get(entity).ok())
@@ -160,12 +189,14 @@ pub fn name_tag_visibility_system(
             }
 
             // All name tag children are visible when selected, except store owner
-            // health bars which should never be shown for personal stores or characters.
+            // health bars which should never be shown for personal stores or characters,
+            // unless the character is a party or clan member which always shows health.
             for &child in name_tag.children.iter() {
                 if let Ok(mut visibility) = query_visibility.get_mut(child) {
                     if query_name_tag_healthbar.contains(child)
                         && (is_store_tag
-                            || matches!(name_tag.name_tag.name_tag_type, NameTagType::Character))
+                            || (matches!(name_tag.name_tag.name_tag_type, NameTagType::Character)
+                                && !always_show_health.contains(&entity)))
                     {
                         *visibility = Visibility::Hidden;
                     } else {
@@ -175,4 +206,58 @@ pub fn name_tag_visibility_system(
             }
         }
     }
+
+    // Restore name tags which no longer always show their health bar
+    for &entity in state.always_show_health.iter() {
+        if always_show_health.contains(&entity) {
+            continue;
+        }
+
+        let Ok(name_tag) = query_name_tag.get(entity) else {
+            continue;
+        };
+
+        if state.hover != Some(entity) && state.selected != Some(entity) {
+            if let Ok(mut visibility) = query_visibility.get_mut(entity) {
+                if is_store_name_tag(entity, &query_name_tag_parent, &query_personal_store)
+                    || name_tag_settings.show_all[name_tag.name_tag.name_tag_type]
+                {
+                    *visibility = Visibility::Inherited;
+                } else {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+        }
+
+        if state.selected != Some(entity) {
+            for &child in name_tag.children.iter() {
+                if query_name_tag_healthbar.contains(child) {
+                    if let Ok(mut visibility) = query_visibility.get_mut(child) {
+                        *visibility = Visibility::Hidden;
+                    }
+                }
+            }
+        }
+    }
+
+    // Health bars are spawned after the name tag, so keep updating them every frame
+    for &entity in always_show_health.iter() {
+        let Ok(name_tag) = query_name_tag.get(entity) else {
+            continue;
+        };
+
+        if let Ok(mut visibility) = query_visibility.get_mut(entity) {
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+
+        for &child in name_tag.children.iter() {
+            if query_name_tag_healthbar.contains(child) {
+                if let Ok(mut visibility) = query_visibility.get_mut(child) {
+                    visibility.set_if_neq(Visibility::Inherited);
+                }
+            }
+        }
+    }
+
+    state.always_show_health = always_show_health;
 }
@@ -1,15 +1,18 @@
-use bevy::prelude::{Commands, EventReader, Res};
+use std::net::SocketAddr;
 
-use rose_game_common::{
-    data::Password,
-    messages::{client::ClientMessage, server::ServerMessage},
-};
+use bevy::prelude::{Commands, EventReader, Res, ResMut};
+
+use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
 
 use crate::{
     events::NetworkEvent,
-    protocol::irose,
+    protocol::{
+        irose, CharacterSlotInfo, ExtendedClientMessage, ExtendedServerMessage, LoginChallenge,
+        LoginQueueStatus, ServerCapabilities,
+    },
     resources::{
-        GameConnection, LoginConnection, NetworkThread, NetworkThreadMessage, WorldConnection,
+        ClientCapabilities, ConnectionServer, ConnectionState, GameConnection, LoginConnection,
+        NetworkThread, NetworkThreadMessage, UnsupportedPackets, WorldConnection,
     },
 };
 
@@ -17,6 +20,8 @@ pub fn network_thread_system(
     mut commands: Commands,
     network_thread: Res<NetworkThread>,
     mut network_events: EventReader<NetworkEvent>,
+    mut connection_state: ResMut<ConnectionState>,
+    unsupported_packets: Res<UnsupportedPackets>,
 ) {
     for event in network_events.iter() {
         match *event {
@@ -25,7 +30,19 @@ pub fn network_thread_system(
                     crossbeam_channel::unbounded::<ServerMessage>();
                 let (client_message_tx, client_message_rx) =
                     tokio::sync::mpsc::unbounded_channel::<ClientMessage>();
-                let server_address = format!("{}:{}", ip, port).parse().unwrap();
+                let (login_challenge_tx, login_challenge_rx) =
+                    crossbeam_channel::unbounded::<LoginChallenge>();
+                let (login_challenge_response_tx, login_challenge_response_rx) =
+                    tokio::sync::mpsc::unbounded_channel::<String>();
+                let (server_capabilities_tx, server_capabilities_rx) =
+                    crossbeam_channel::unbounded::<ServerCapabilities>();
+                let Ok(server_address) = format!("{}:{}", ip, port).parse::<SocketAddr>() else {
+                    *connection_state = ConnectionState::error(
+                        ConnectionServer::Login,
+                        format!("Invalid server address {}:{}", ip, port),
+                    );
+                    continue;
+                };
 
                 network_thread
                     .control_tx
@@ -34,12 +51,23 @@ pub fn network_thread_system(
                             server_address,
                             client_message_rx,
                             server_message_tx,
+                            login_challenge_tx,
+                            login_challenge_response_rx,
+                            server_capabilities_tx,
+                            unsupported_packets.packet_tx.clone(),
                         ),
                     )))
                     .ok();
 
-                commands
-                    .insert_resource(LoginConnection::new(client_message_tx, server_message_rx));
+                *connection_state = ConnectionState::Connecting(ConnectionServer::Login);
+                commands.insert_resource(ClientCapabilities::default());
+                commands.insert_resource(LoginConnection::new(
+                    client_message_tx,
+                    server_message_rx,
+                    login_challenge_rx,
+                    login_challenge_response_tx,
+                    server_capabilities_rx,
+                ));
             }
             NetworkEvent::ConnectWorld {
                 ref ip,
@@ -50,9 +78,21 @@ pub fn network_thread_system(
             } => {
                 let (server_message_tx, server_message_rx) =
                     crossbeam_channel::unbounded::<ServerMessage>();
+                let (login_queue_tx, login_queue_rx) =
+                    crossbeam_channel::unbounded::<LoginQueueStatus>();
+                let (character_slots_tx, character_slots_rx) =
+                    crossbeam_channel::unbounded::<CharacterSlotInfo>();
+                let (purchase_character_slot_tx, purchase_character_slot_rx) =
+                    tokio::sync::mpsc::unbounded_channel::<usize>();
                 let (client_message_tx, client_message_rx) =
                     tokio::sync::mpsc::unbounded_channel::<ClientMessage>();
-                let server_address = format!("{}:{}", ip, port).parse().unwrap();
+                let Ok(server_address) = format!("{}:{}", ip, port).parse::<SocketAddr>() else {
+                    *connection_state = ConnectionState::error(
+                        ConnectionServer::World,
+                        format!("Invalid server address {}:{}", ip, port),
+                    );
+                    continue;
+                };
 
                 network_thread
                     .control_tx
@@ -62,15 +102,23 @@ pub fn network_thread_system(
                             packet_codec_seed,
                             client_message_rx,
                             server_message_tx,
+                            login_queue_tx,
+                            character_slots_tx,
+                            purchase_character_slot_rx,
+                            unsupported_packets.packet_tx.clone(),
                         ),
                     )))
                     .ok();
 
+                *connection_state = ConnectionState::Connecting(ConnectionServer::World);
                 commands.insert_resource(WorldConnection::new(
                     client_message_tx,
                     server_message_rx,
+                    login_queue_rx,
+                    character_slots_rx,
+                    purchase_character_slot_tx,
                     login_token,
-                    Password::Plaintext(password.clone()),
+                    password.clone(),
                 ));
             }
             NetworkEvent::ConnectGame {
@@ -84,7 +132,17 @@ pub fn network_thread_system(
                     crossbeam_channel::unbounded::<ServerMessage>();
                 let (client_message_tx, client_message_rx) =
                     tokio::sync::mpsc::unbounded_channel::<ClientMessage>();
-                let server_address = format!("{}:{}", ip, port).parse().unwrap();
+                let (extended_server_message_tx, extended_server_message_rx) =
+                    crossbeam_channel::unbounded::<ExtendedServerMessage>();
+                let (extended_client_message_tx, extended_client_message_rx) =
+                    tokio::sync::mpsc::unbounded_channel::<ExtendedClientMessage>();
+                let Ok(server_address) = format!("{}:{}", ip, port).parse::<SocketAddr>() else {
+                    *connection_state = ConnectionState::error(
+                        ConnectionServer::Game,
+                        format!("Invalid server address {}:{}", ip, port),
+                    );
+                    continue;
+                };
 
                 network_thread
                     .control_tx
@@ -94,15 +152,21 @@ pub fn network_thread_system(
                             packet_codec_seed,
                             client_message_rx,
                             server_message_tx,
+                            extended_client_message_rx,
+                            extended_server_message_tx,
+                            unsupported_packets.packet_tx.clone(),
                         ),
                     )))
                     .ok();
 
+                *connection_state = ConnectionState::Connecting(ConnectionServer::Game);
                 commands.insert_resource(GameConnection::new(
                     client_message_tx,
                     server_message_rx,
+                    extended_client_message_tx,
+                    extended_server_message_rx,
                     login_token,
-                    Password::Plaintext(password.clone()),
+                    password.clone(),
                 ));
             }
         }
@@ -0,0 +1,146 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    math::{Vec3, Vec3Swizzles},
+    prelude::{Commands, Component, Entity, Query, Res, Time, With},
+};
+use rand::Rng;
+
+use rose_data::NpcMotionAction;
+use rose_game_common::components::{Npc, Team};
+
+use crate::{
+    animation::SkeletalAnimation,
+    components::{CharacterModel, Command, FacingDirection, GrowthObject, NpcModel, Position},
+    resources::GameData,
+};
+
+// Players within this distance are greeted, until they move further than the leave distance
+const NPC_GREET_DISTANCE: f32 = 400.0;
+const NPC_GREET_LEAVE_DISTANCE: f32 = 600.0;
+
+// Ambient behaviours are only visual, the NPC position and commands always come from the server
+#[derive(Component)]
+pub struct NpcAmbientState {
+    can_turn: bool,
+    next_action_timer: f32,
+    idle_animation_speed: f32,
+    greeting: Option<Entity>,
+}
+
+pub fn npc_ambient_system(
+    mut commands: Commands,
+    mut query_npcs: Query<(
+        Entity,
+        &Npc,
+        &Team,
+        &NpcModel,
+        &Position,
+        &Command,
+        &mut FacingDirection,
+        Option<&mut SkeletalAnimation>,
+        Option<&mut NpcAmbientState>,
+        Option<&GrowthObject>,
+    )>,
+    query_characters: Query<(Entity, &Position), With<CharacterModel>>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (
+        entity,
+        npc,
+        team,
+        npc_model,
+        position,
+        command,
+        mut facing_direction,
+        skeletal_animation,
+        ambient_state,
+        growth_object,
+    ) in query_npcs.iter_mut()
+    {
+        // Only non-combat NPCs have ambient behaviours
+        if team.id != Team::DEFAULT_NPC_TEAM_ID || growth_object.is_some() {
+            continue;
+        }
+
+        let Some(mut ambient_state) = ambient_state else {
+            // NPCs without a walk speed are fixed in place, e.g. statues and signposts
+            let can_turn = game_data
+                .npcs
+                .get_npc(npc.id)
+                .map_or(false, |npc_data| npc_data.walk_speed > 0);
+
+            commands.entity(entity).insert(NpcAmbientState {
+                can_turn,
+                next_action_timer: rng.gen_range(4.0..12.0),
+                idle_animation_speed: rng.gen_range(0.85..1.15),
+                greeting: None,
+            });
+            continue;
+        };
+
+        // Never interfere with a command sent by the server
+        if !command.is_stop() {
+            ambient_state.greeting = None;
+            continue;
+        }
+
+        // Vary the idle animation speed so nearby NPCs do not animate in sync
+        if let Some(mut skeletal_animation) = skeletal_animation {
+            if skeletal_animation.motion().id()
+                == npc_model.action_motions[NpcMotionAction::Stop].id()
+            {
+                skeletal_animation.set_animation_speed(ambient_state.idle_animation_speed);
+            }
+        }
+
+        if !ambient_state.can_turn {
+            continue;
+        }
+
+        // Turn to face any player who comes close
+        let greeting_position = ambient_state
+            .greeting
+            .and_then(|greeting_entity| query_characters.get(greeting_entity).ok())
+            .map(|(_, character_position)| character_position.position)
+            .filter(|character_position| {
+                character_position.xy().distance(position.xy()) < NPC_GREET_LEAVE_DISTANCE
+            });
+        let greeting_position = if greeting_position.is_some() {
+            greeting_position
+        } else {
+            let nearest_character = query_characters
+                .iter()
+                .map(|(character_entity, character_position)| {
+                    (
+                        character_entity,
+                        character_position.position,
+                        character_position.xy().distance(position.xy()),
+                    )
+                })
+                .filter(|(_, _, distance)| *distance < NPC_GREET_DISTANCE)
+                .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+            ambient_state.greeting =
+                nearest_character.map(|(character_entity, _, _)| character_entity);
+            nearest_character.map(|(_, character_position, _)| character_position)
+        };
+
+        if let Some(greeting_position) = greeting_position {
+            facing_direction.set_desired_vector(greeting_position - position.position);
+            continue;
+        }
+
+        ambient_state.next_action_timer -= time.delta_seconds();
+        if ambient_state.next_action_timer > 0.0 {
+            continue;
+        }
+        ambient_state.next_action_timer = rng.gen_range(6.0..15.0);
+
+        // Look around
+        let angle = rng.gen_range(0.0..TAU);
+        facing_direction.set_desired_vector(Vec3::new(angle.cos(), angle.sin(), 0.0));
+    }
+}
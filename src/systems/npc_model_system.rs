@@ -1,8 +1,8 @@
 use bevy::{
     math::Vec3,
     prelude::{
-        AssetServer, Assets, Changed, Commands, DespawnRecursiveExt, Entity, Query, Res, ResMut,
-        Transform,
+        AssetServer, Assets, Changed, Commands, DespawnRecursiveExt, Entity, Or, Query, Res,
+        ResMut, Transform, With,
     },
     render::mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
 };
@@ -11,10 +11,13 @@ use enum_map::EnumMap;
 use rose_game_common::components::Npc;
 
 use crate::{
-    components::{ClientEntityName, DummyBoneOffset, ModelHeight, NpcModel, RemoveColliderCommand},
+    components::{
+        ClientEntityName, DummyBoneOffset, ModelHeight, NpcModel, PendingModel,
+        RemoveColliderCommand,
+    },
     model_loader::ModelLoader,
     render::{EffectMeshMaterial, ObjectMaterial, ParticleMaterial},
-    resources::GameData,
+    resources::{GameData, PlaceholderModelCommands, SkeletonCache, SkeletonCacheState},
 };
 
 pub fn npc_model_update_system(
@@ -28,7 +31,7 @@ pub fn npc_model_update_system(
             Option<&mut SkinnedMesh>,
             Option<&mut DummyBoneOffset>,
         ),
-        Changed<Npc>,
+        Or<(Changed<Npc>, With<PendingModel>)>,
     >,
     asset_server: Res<AssetServer>,
     model_loader: Res<ModelLoader>,
@@ -36,8 +39,11 @@ pub fn npc_model_update_system(
     mut particle_materials: ResMut<Assets<ParticleMaterial>>,
     mut object_materials: ResMut<Assets<ObjectMaterial>>,
     mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+    mut skeleton_cache: ResMut<SkeletonCache>,
     game_data: Res<GameData>,
 ) {
+    skeleton_cache.update(&mut skinned_mesh_inverse_bindposes_assets);
+
     for (
         entity,
         npc,
@@ -47,12 +53,31 @@ pub fn npc_model_update_system(
         current_dummy_bone_offset,
     ) in query.iter_mut()
     {
-        if let Some(previous_npc_model) = current_npc_model.as_mut() {
+        if let Some(previous_npc_model) = current_npc_model.as_ref() {
             if npc.id == previous_npc_model.npc_id {
                 // NPC model has not changed
+                commands.entity(entity).remove::<PendingModel>();
+                continue;
+            }
+        }
+
+        // Keep any previous model until the new skeleton has loaded
+        let skeleton = match model_loader
+            .get_npc_skeleton_path(npc.id)
+            .map(|path| skeleton_cache.get(&path))
+        {
+            Some(SkeletonCacheState::Loading) => {
+                commands.entity(entity).insert(PendingModel);
+                if current_npc_model.is_none() {
+                    commands.spawn_placeholder_model(entity);
+                }
                 continue;
             }
+            Some(SkeletonCacheState::Loaded(skeleton)) => Some(skeleton),
+            Some(SkeletonCacheState::Failed) | None => None,
+        };
 
+        if let Some(previous_npc_model) = current_npc_model.as_mut() {
             // Despawn model parts
             for part_entity in previous_npc_model.model_parts.drain(..) {
                 commands.entity(part_entity).despawn_recursive();
@@ -72,6 +97,13 @@ pub fn npc_model_update_system(
                 .remove::<ModelHeight>();
         }
 
+        // Keep showing the placeholder if the skeleton failed to load
+        if skeleton.is_some() {
+            commands.despawn_placeholder_model(entity);
+        } else {
+            commands.spawn_placeholder_model(entity);
+        }
+
         let (npc_model, skinned_mesh, dummy_bone_offset) =
             if let Some((npc_model, skinned_mesh, dummy_bone_offset)) = model_loader
                 .spawn_npc_model(
@@ -80,9 +112,9 @@ pub fn npc_model_update_system(
                     &mut effect_mesh_materials,
                     &mut particle_materials,
                     &mut object_materials,
-                    &mut skinned_mesh_inverse_bindposes_assets,
                     entity,
                     npc.id,
+                    skeleton.as_ref(),
                 )
             {
                 (npc_model, skinned_mesh, dummy_bone_offset)
@@ -101,6 +133,7 @@ pub fn npc_model_update_system(
             };
 
         let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<PendingModel>();
 
         // Update scale
         if let Some(npc_data) = game_data.npcs.get_npc(npc.id) {
@@ -17,14 +17,33 @@ use bevy_rapier3d::{
 };
 use dolly::prelude::{Arm, CameraRig, LeftHanded, Position, Smooth, YawPitch};
 
-use crate::components::{
-    COLLISION_FILTER_COLLIDABLE, COLLISION_FILTER_MOVEABLE, COLLISION_GROUP_PHYSICS_TOY,
+use crate::{
+    components::{
+        COLLISION_FILTER_COLLIDABLE, COLLISION_FILTER_MOVEABLE, COLLISION_GROUP_PHYSICS_TOY,
+    },
+    resources::{CinematicMode, RenderConfiguration},
 };
 
+// Fraction of the orbit radius and rate at which the idle camera zooms in and out
+const IDLE_ZOOM_AMOUNT: f32 = 0.25;
+const IDLE_ZOOM_SPEED: f32 = 0.1;
+
+// Seconds to blend from the previous camera view after snapping to the follow entity
+const SNAP_BLEND_DURATION: f32 = 0.6;
+
+// Camera offset from the follow position and rotation from before a snap
+struct OrbitCameraSnapBlend {
+    offset: Vec3,
+    rotation: Quat,
+    elapsed: f32,
+}
+
 #[derive(Component)]
 pub struct OrbitCamera {
     pub rig: CameraRig<LeftHanded>,
     pub has_initial_position: bool,
+    pub snap_to_follow_entity: bool,
+    snap_blend: Option<OrbitCameraSnapBlend>,
     pub follow_entity: Entity,
     pub follow_offset: Vec3,
     pub follow_distance: f32,
@@ -43,6 +62,8 @@ impl OrbitCamera {
                 .with(Arm::new(Vec3::Z * 4.0))
                 .build(),
             has_initial_position: false,
+            snap_to_follow_entity: false,
+            snap_blend: None,
             follow_entity,
             follow_offset,
             follow_distance,
@@ -70,6 +91,8 @@ pub fn orbit_camera_system(
     mouse_buttons: Res<Input<MouseButton>>,
     time: Res<Time>,
     rapier_context: Res<RapierContext>,
+    cinematic_mode: Res<CinematicMode>,
+    render_configuration: Res<RenderConfiguration>,
 ) {
     let Ok(mut window) = query_window.get_single_mut() else {
         return;
@@ -84,7 +107,7 @@ pub fn orbit_camera_system(
                 window.set_cursor_position(Some(saved_cursor_position));
             }
 
-            window.cursor.grab_mode = CursorGrabMode::None;
+            window.cursor.grab_mode = render_configuration.cursor_grab_mode();
             window.cursor.visible = true;
             control_state.is_dragging = false;
         }
@@ -107,6 +130,35 @@ pub fn orbit_camera_system(
         return;
     }
 
+    // Jump straight to the entity, e.g. after a teleport, keeping the current rotation,
+    // then blend from the previous view relative to the entity
+    if orbit_camera.snap_to_follow_entity {
+        if let Ok(follow_transform) = query_global_transform.get(orbit_camera.follow_entity) {
+            orbit_camera.snap_blend = Some(OrbitCameraSnapBlend {
+                offset: camera_transform.translation
+                    - orbit_camera.rig.driver::<Position>().position,
+                rotation: camera_transform.rotation,
+                elapsed: 0.0,
+            });
+
+            let yaw_pitch = orbit_camera.rig.driver::<YawPitch>();
+            let (yaw_degrees, pitch_degrees) = (yaw_pitch.yaw_degrees, yaw_pitch.pitch_degrees);
+            orbit_camera.rig = CameraRig::builder()
+                .with(Position::new(
+                    follow_transform.translation() + orbit_camera.follow_offset,
+                ))
+                .with(
+                    YawPitch::new()
+                        .yaw_degrees(yaw_degrees)
+                        .pitch_degrees(pitch_degrees),
+                )
+                .with(Smooth::new_position_rotation(1.0, 1.0))
+                .with(Arm::new(Vec3::Z * orbit_camera.follow_distance))
+                .build();
+            orbit_camera.snap_to_follow_entity = false;
+        }
+    }
+
     let allow_mouse_input = control_state.is_dragging || !egui_ctx.ctx_mut().wants_pointer_input();
     let right_pressed = mouse_buttons.pressed(MouseButton::Right);
     let mut drag_delta = Vec2::ZERO;
@@ -132,7 +184,7 @@ pub fn orbit_camera_system(
                 window.set_cursor_position(Some(saved_cursor_position));
             }
 
-            window.cursor.grab_mode = CursorGrabMode::None;
+            window.cursor.grab_mode = render_configuration.cursor_grab_mode();
             window.cursor.visible = true;
         }
 
@@ -182,6 +234,20 @@ pub fn orbit_camera_system(
             .rotate_yaw_pitch(-sensitivity * drag_delta.x, -sensitivity * drag_delta.y);
     }
 
+    // Slowly orbit around the player in cinematic mode
+    if cinematic_mode.enabled {
+        orbit_camera
+            .rig
+            .driver_mut::<YawPitch>()
+            .rotate_yaw_pitch(cinematic_mode.orbit_speed * time.delta_seconds(), 0.0);
+
+        // The idle camera also slowly drifts in and out
+        if cinematic_mode.idle {
+            orbit_camera.follow_distance = cinematic_mode.orbit_radius
+                * (1.0 + IDLE_ZOOM_AMOUNT * (time.elapsed_seconds() * IDLE_ZOOM_SPEED).sin());
+        }
+    }
+
     // Adjust zoom with mouse wheel
     orbit_camera.follow_distance = (orbit_camera.follow_distance * zoom_multiplier)
         .clamp(orbit_camera.min_distance, orbit_camera.max_distance);
@@ -207,6 +273,23 @@ pub fn orbit_camera_system(
     let calculated_transform = orbit_camera.rig.update(time.delta_seconds());
     camera_transform.translation = calculated_transform.position;
     camera_transform.rotation = calculated_transform.rotation;
+
+    let follow_position = orbit_camera.rig.driver::<Position>().position;
+    if let Some(snap_blend) = orbit_camera.snap_blend.as_mut() {
+        snap_blend.elapsed += time.delta_seconds();
+
+        let t = (snap_blend.elapsed / SNAP_BLEND_DURATION).min(1.0);
+        let t = t * t * (3.0 - 2.0 * t);
+        camera_transform.translation = follow_position
+            + snap_blend
+                .offset
+                .lerp(calculated_transform.position - follow_position, t);
+        camera_transform.rotation = snap_blend.rotation.slerp(calculated_transform.rotation, t);
+
+        if snap_blend.elapsed >= SNAP_BLEND_DURATION {
+            orbit_camera.snap_blend = None;
+        }
+    }
 }
 
 pub trait Interpolate {
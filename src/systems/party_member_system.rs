@@ -0,0 +1,49 @@
+use bevy::prelude::{Commands, Entity, Query, Res, With};
+
+use crate::{
+    components::{PartyInfo, PartyMember, PlayerCharacter},
+    resources::ClientEntityList,
+};
+
+pub fn party_member_system(
+    mut commands: Commands,
+    query_player_party: Query<&PartyInfo, With<PlayerCharacter>>,
+    query_party_members: Query<(Entity, &PartyMember)>,
+    client_entity_list: Res<ClientEntityList>,
+) {
+    let mut members: Vec<(Entity, usize)> = query_player_party
+        .get_single()
+        .map(|party_info| {
+            party_info
+                .members
+                .iter()
+                .enumerate()
+                .filter_map(|(index, member)| {
+                    member
+                        .get_client_entity_id()
+                        .and_then(|client_entity_id| client_entity_list.get(client_entity_id))
+                        .filter(|entity| Some(*entity) != client_entity_list.player_entity)
+                        .map(|entity| (entity, index))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for (entity, party_member) in query_party_members.iter() {
+        if let Some(position) = members
+            .iter()
+            .position(|(member_entity, _)| *member_entity == entity)
+        {
+            let (_, index) = members.swap_remove(position);
+            if party_member.index != index {
+                commands.entity(entity).insert(PartyMember { index });
+            }
+        } else {
+            commands.entity(entity).remove::<PartyMember>();
+        }
+    }
+
+    for (entity, index) in members {
+        commands.entity(entity).insert(PartyMember { index });
+    }
+}
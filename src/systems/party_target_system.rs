@@ -0,0 +1,58 @@
+use bevy::prelude::{EventReader, Local, Query, Res, ResMut, With};
+
+use rose_game_common::messages::ClientEntityId;
+
+use crate::{
+    components::{ClientEntity, PartyInfo, PlayerCharacter},
+    events::ExtendedServerMessageEvent,
+    protocol::{ExtendedClientMessage, ExtendedServerMessage, ProtocolCapabilities},
+    resources::{ClientCapabilities, GameConnection, PartyMemberTargets, SelectedTarget},
+};
+
+pub fn party_target_system(
+    game_connection: Option<Res<GameConnection>>,
+    query_player_party: Query<Option<&PartyInfo>, With<PlayerCharacter>>,
+    query_client_entity: Query<&ClientEntity>,
+    selected_target: Res<SelectedTarget>,
+    client_capabilities: Res<ClientCapabilities>,
+    mut party_member_targets: ResMut<PartyMemberTargets>,
+    mut sent_target: Local<Option<Option<ClientEntityId>>>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    let Some(game_connection) = game_connection else {
+        party_member_targets.targets.clear();
+        *sent_target = None;
+        return;
+    };
+
+    // Each list of targets replaces the previous one
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        if let ExtendedServerMessage::PartyMemberTargets(targets) = message {
+            party_member_targets.targets = targets.clone();
+        }
+    }
+
+    let in_party = query_player_party
+        .get_single()
+        .map_or(false, |party_info| party_info.is_some());
+    if !in_party || !client_capabilities.supports(ProtocolCapabilities::PARTY_TARGETS) {
+        if !party_member_targets.targets.is_empty() {
+            party_member_targets.targets.clear();
+        }
+        *sent_target = None;
+        return;
+    }
+
+    // Let the server know our target so it can be shared with the rest of the party
+    let target_entity_id = selected_target
+        .selected
+        .and_then(|entity| query_client_entity.get(entity).ok())
+        .map(|client_entity| client_entity.id);
+    if *sent_target != Some(target_entity_id) {
+        game_connection
+            .extended_client_message_tx
+            .send(ExtendedClientMessage::PartyTarget(target_entity_id))
+            .ok();
+        *sent_target = Some(target_entity_id);
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::{ecs::event::Events, prelude::World};
+
+use rose_data::Item;
+use rose_game_common::{
+    components::{Inventory, ItemSlot, Money},
+    messages::server::{PersonalStoreTransactionStatus, ServerMessage},
+};
+
+use crate::{
+    components::{Command, NextCommand, PersonalStore},
+    events::{ChatboxEvent, PersonalStoreEvent},
+    resources::{ClientEntityList, GameData, ServerMessageKind, StoreLowPowerMode},
+    systems::game_connection_system::update_inventory_and_money,
+};
+
+pub const PERSONAL_STORE_SERVER_MESSAGES: [ServerMessageKind; 5] = [
+    ServerMessageKind::OpenPersonalStore,
+    ServerMessageKind::ClosePersonalStore,
+    ServerMessageKind::PersonalStoreItemList,
+    ServerMessageKind::PersonalStoreTransaction,
+    ServerMessageKind::PersonalStoreTransactionUpdateInventory,
+];
+
+// Describes the item which was bought or sold, using the inventory from before it is updated
+fn transaction_message(
+    game_data: &GameData,
+    inventory: &Inventory,
+    money: Money,
+    items: &[(ItemSlot, Option<Item>)],
+) -> Option<(String, i64)> {
+    let transaction_price = money.0 - inventory.money.0;
+
+    let (item_slot, transaction_item) = items.first()?;
+    let transaction_item = transaction_item.as_ref();
+    let inventory_item = inventory.get_item(*item_slot);
+    let (transaction_quantity, transaction_item) = match (transaction_item, inventory_item) {
+        (Some(transaction_item), Some(inventory_item)) => (
+            transaction_item.get_quantity() as i32 - inventory_item.get_quantity() as i32,
+            Some(inventory_item.get_item_reference()),
+        ),
+        (None, Some(inventory_item)) => (
+            inventory_item.get_quantity() as i32,
+            Some(inventory_item.get_item_reference()),
+        ),
+        (Some(transaction_item), None) => (
+            transaction_item.get_quantity() as i32,
+            Some(transaction_item.get_item_reference()),
+        ),
+        (None, None) => (0, None),
+    };
+
+    let item_data = transaction_item.and_then(|item| game_data.items.get_base_item(item))?;
+    let verb = if transaction_price < 0 {
+        "purchased"
+    } else {
+        "sold"
+    };
+    let message = if transaction_quantity > 1 {
+        format!(
+            "You have {} {}x {} for {} Zuly.",
+            verb,
+            transaction_quantity,
+            item_data.name,
+            transaction_price.abs()
+        )
+    } else {
+        format!(
+            "You have {} {} for {} Zuly.",
+            verb,
+            item_data.name,
+            transaction_price.abs()
+        )
+    };
+
+    Some((message, transaction_price))
+}
+
+pub fn personal_store_message_handler(message: &ServerMessage, world: &mut World) {
+    match message {
+        &ServerMessage::OpenPersonalStore {
+            entity_id,
+            skin,
+            ref title,
+        } => {
+            let entity = world.resource::<ClientEntityList>().get(entity_id);
+            if let Some(mut entity_mut) = entity.and_then(|entity| world.get_entity_mut(entity)) {
+                entity_mut.insert(PersonalStore {
+                    title: title.clone(),
+                    skin: skin as usize,
+                });
+            }
+        }
+        &ServerMessage::ClosePersonalStore { entity_id } => {
+            let client_entity_list = world.resource::<ClientEntityList>();
+            let target_entity = client_entity_list.get(entity_id).or_else(|| {
+                if client_entity_list.player_entity_id == Some(entity_id) {
+                    client_entity_list.player_entity
+                } else {
+                    None
+                }
+            });
+
+            let Some(mut entity_mut) =
+                target_entity.and_then(|entity| world.get_entity_mut(entity))
+            else {
+                log::warn!(
+                    "personal-store: close received for unknown entity_id={}",
+                    entity_id.0
+                );
+                return;
+            };
+            log::info!(
+                "personal-store: close received entity_id={} entity={:?}",
+                entity_id.0,
+                entity_mut.id()
+            );
+            entity_mut.remove::<PersonalStore>();
+
+            // Ensure local command state exits PersonalStore immediately so movement
+            // can resume even before further server movement updates arrive.
+            entity_mut.insert((Command::with_stop(), NextCommand::with_stop()));
+        }
+        ServerMessage::PersonalStoreItemList {
+            sell_items,
+            buy_items,
+        } => {
+            world.resource_mut::<Events<PersonalStoreEvent>>().send(
+                PersonalStoreEvent::SetItemList {
+                    sell_items: sell_items.clone(),
+                    buy_items: buy_items.clone(),
+                },
+            );
+        }
+        ServerMessage::PersonalStoreTransaction {
+            status,
+            store_entity_id,
+            update_store,
+        } => {
+            let store_entity = world
+                .resource::<ClientEntityList>()
+                .get(*store_entity_id)
+                .filter(|_| !update_store.is_empty());
+            if let Some(entity) = store_entity {
+                let item_list = update_store.clone();
+                let event = match status {
+                    PersonalStoreTransactionStatus::Cancelled => None,
+                    PersonalStoreTransactionStatus::SoldOut
+                    | PersonalStoreTransactionStatus::BoughtFromStore => {
+                        Some(PersonalStoreEvent::UpdateSellList { entity, item_list })
+                    }
+                    PersonalStoreTransactionStatus::NoMoreNeed
+                    | PersonalStoreTransactionStatus::SoldToStore => {
+                        Some(PersonalStoreEvent::UpdateBuyList { entity, item_list })
+                    }
+                };
+
+                if let Some(event) = event {
+                    world
+                        .resource_mut::<Events<PersonalStoreEvent>>()
+                        .send(event);
+                }
+            }
+
+            let message = match status {
+                PersonalStoreTransactionStatus::Cancelled => "Transaction failed.",
+                PersonalStoreTransactionStatus::SoldOut => "Transaction failed. Item has sold out.",
+                PersonalStoreTransactionStatus::NoMoreNeed => {
+                    "Transaction failed. Item is no longer wanted."
+                }
+                _ => return,
+            };
+            world
+                .resource_mut::<Events<ChatboxEvent>>()
+                .send(ChatboxEvent::System(message.to_string()));
+        }
+        ServerMessage::PersonalStoreTransactionUpdateInventory { money, items } => {
+            let Some(player_entity) = world.resource::<ClientEntityList>().player_entity else {
+                return;
+            };
+
+            let transaction = world.get::<Inventory>(player_entity).and_then(|inventory| {
+                transaction_message(world.resource::<GameData>(), inventory, *money, items)
+            });
+            if let Some((message, transaction_price)) = transaction {
+                if transaction_price > 0 {
+                    world
+                        .resource_mut::<StoreLowPowerMode>()
+                        .record_sale(message.clone(), transaction_price);
+                }
+
+                world
+                    .resource_mut::<Events<ChatboxEvent>>()
+                    .send(ChatboxEvent::System(message));
+            }
+
+            update_inventory_and_money(world, player_entity, items.clone(), Some(*money));
+        }
+        _ => {}
+    }
+}
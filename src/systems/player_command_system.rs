@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use bevy::{
     ecs::query::WorldQuery,
-    math::Vec3Swizzles,
+    math::{Vec3, Vec3Swizzles},
     prelude::{Entity, EventReader, EventWriter, Query, Res, ResMut, With},
 };
 
@@ -18,13 +18,20 @@ use rose_game_common::{
 use crate::{
     components::{
         Bank, Clan, ClientEntity, ClientEntityType, Command, ConsumableCooldownGroup, Cooldowns,
-        PartyInfo, PlayerCharacter, Position,
+        GrowthObject, PartyInfo, PlayerCharacter, Position,
     },
     events::{ChatboxEvent, PlayerCommandEvent},
-    resources::{GameConnection, GameData, SelectedTarget},
-    ui::UiStateWindows,
+    protocol::{ExtendedClientMessage, ItemStackSplit, ProtocolCapabilities},
+    resources::{
+        ClientCapabilities, FarmingSettings, GameConnection, GameData, SelectedTarget,
+        ZoneNavigation,
+    },
+    ui::{UiStateWindows, UiWindowId},
 };
 
+// Minimum distance between two plants, in the same units as Position
+const FARMING_MIN_PLANT_DISTANCE: f32 = 200.0;
+
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct PlayerQuery<'w> {
@@ -61,11 +68,15 @@ pub fn player_command_system(
     query_dropped_items: Query<(&ClientEntity, &Position), With<ItemDrop>>,
     query_team: Query<(&ClientEntity, &Team)>,
     query_skill_target: Query<SkillTargetQuery>,
+    query_growth_objects: Query<&Position, With<GrowthObject>>,
     mut chatbox_events: EventWriter<ChatboxEvent>,
     mut ui_state_windows: ResMut<UiStateWindows>,
     game_connection: Option<Res<GameConnection>>,
+    client_capabilities: Res<ClientCapabilities>,
     game_data: Res<GameData>,
     selected_target: Res<SelectedTarget>,
+    farming_settings: Res<FarmingSettings>,
+    zone_navigation: Res<ZoneNavigation>,
 ) {
     let query_player_result = query_player.get_single_mut();
     if query_player_result.is_err() {
@@ -242,8 +253,7 @@ pub fn player_command_system(
                             }
                             Some(SkillBasicCommand::PrivateStore) => {
                                 // Use the same UI path as /pshop chat command.
-                                ui_state_windows.player_shop_open =
-                                    !ui_state_windows.player_shop_open;
+                                ui_state_windows.toggle(UiWindowId::PLAYER_SHOP);
                             }
                             /*
                             Some(SkillBasicCommand::AutoTarget) => {}
@@ -463,6 +473,31 @@ pub fn player_command_system(
                                 continue;
                             }
 
+                            // Seeds are planted where the player is standing, the server
+                            // spawns the growth stage npc when it accepts the use item
+                            if farming_settings.is_seed(item.get_item_number()) {
+                                let player_position = player.position.position;
+                                let is_valid_ground =
+                                    zone_navigation.grid.as_ref().map_or(true, |grid| {
+                                        grid.is_walkable(Vec3::new(
+                                            player_position.x / 100.0,
+                                            player_position.z / 100.0,
+                                            -player_position.y / 100.0,
+                                        ))
+                                    });
+                                let is_near_plant = query_growth_objects.iter().any(|position| {
+                                    position.position.xy().distance(player_position.xy())
+                                        < FARMING_MIN_PLANT_DISTANCE
+                                });
+
+                                if !is_valid_ground || is_near_plant {
+                                    chatbox_events.send(ChatboxEvent::System(
+                                        "You cannot plant a seed here".to_string(),
+                                    ));
+                                    continue;
+                                }
+                            }
+
                             // Check if item is on cooldown
                             if cooldown_group
                                 .and_then(|cooldown_group| {
@@ -669,20 +704,38 @@ pub fn player_command_system(
                         .ok();
                 }
             }
-            PlayerCommandEvent::DropItem(item_slot) => {
+            PlayerCommandEvent::DropItem(item_slot, quantity) => {
                 if let Some(item) = player.inventory.get_item(item_slot) {
-                    // TODO: if item.get_quantity() > 1, show number input dialog for quantity
                     if let Some(game_connection) = game_connection.as_ref() {
                         game_connection
                             .client_message_tx
                             .send(ClientMessage::DropItem {
                                 item_slot,
-                                quantity: item.get_quantity() as usize,
+                                quantity: quantity.min(item.get_quantity() as usize),
                             })
                             .ok();
                     }
                 }
             }
+            PlayerCommandEvent::SplitItemStack(source, destination, quantity) => {
+                if let Some(item) = player.inventory.get_item(source) {
+                    let quantity = quantity.min(item.get_quantity() as usize);
+                    if quantity > 0
+                        && client_capabilities.supports(ProtocolCapabilities::ITEM_STACK_SPLIT)
+                    {
+                        if let Some(game_connection) = game_connection.as_ref() {
+                            game_connection
+                                .extended_client_message_tx
+                                .send(ExtendedClientMessage::ItemStackSplit(ItemStackSplit {
+                                    source,
+                                    destination,
+                                    quantity,
+                                }))
+                                .ok();
+                        }
+                    }
+                }
+            }
             PlayerCommandEvent::DropMoney(quantity) => {
                 if let Some(game_connection) = game_connection.as_ref() {
                     game_connection
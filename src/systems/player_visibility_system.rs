@@ -0,0 +1,62 @@
+use bevy::{
+    input::Input,
+    prelude::{Commands, Entity, KeyCode, Query, Res, ResMut, Visibility, Without},
+};
+use bevy_egui::EguiContexts;
+
+use crate::{
+    components::{
+        ClientEntity, ClientEntityType, HiddenPlayer, PartyMember, PersonalStore, PlayerCharacter,
+    },
+    resources::{CrowdSettings, SelectedTarget},
+};
+
+pub fn player_visibility_system(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    mut query_characters: Query<
+        (
+            Entity,
+            &ClientEntity,
+            &mut Visibility,
+            Option<&PartyMember>,
+            Option<&PersonalStore>,
+            Option<&HiddenPlayer>,
+        ),
+        Without<PlayerCharacter>,
+    >,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut crowd_settings: ResMut<CrowdSettings>,
+    mut selected_target: ResMut<SelectedTarget>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F12) && !egui_context.ctx_mut().wants_keyboard_input() {
+        crowd_settings.hide_other_players = !crowd_settings.hide_other_players;
+    }
+
+    for (entity, client_entity, mut visibility, party_member, personal_store, hidden_player) in
+        query_characters.iter_mut()
+    {
+        if !matches!(client_entity.entity_type, ClientEntityType::Character) {
+            continue;
+        }
+
+        // Party members are always shown, and stores stay visible so towns are usable for trading
+        let hide =
+            crowd_settings.hide_other_players && party_member.is_none() && personal_store.is_none();
+        if hide == hidden_player.is_some() {
+            continue;
+        }
+
+        if hide {
+            commands.entity(entity).insert(HiddenPlayer);
+            *visibility = Visibility::Hidden;
+
+            if selected_target.selected == Some(entity) {
+                selected_target.selected = None;
+            }
+        } else {
+            commands.entity(entity).remove::<HiddenPlayer>();
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::{
+    math::Vec3Swizzles,
+    prelude::{Commands, Entity, Query, Res, ResMut, Time},
+};
+
+use crate::{
+    components::{PlayerCharacter, Position, PositionCorrection},
+    resources::PositionCorrectionStats,
+};
+
+// Corrections smaller than this are not worth blending
+const MIN_CORRECTION_DISTANCE: f32 = 10.0;
+
+// Corrections larger than this are treated as teleports and applied immediately
+const MAX_SMOOTHED_DISTANCE: f32 = 1000.0;
+
+pub const POSITION_CORRECTION_DURATION: f32 = 0.15;
+
+pub fn position_correction_system(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Position,
+        &mut PositionCorrection,
+        Option<&PlayerCharacter>,
+    )>,
+    mut position_correction_stats: ResMut<PositionCorrectionStats>,
+    time: Res<Time>,
+) {
+    for (entity, mut position, mut correction, player_character) in query.iter_mut() {
+        let offset = if let Some(offset) = correction.offset {
+            offset
+        } else {
+            // The offset is measured once, later movement is applied on top of the blend
+            let offset = correction.server_position - position.xy();
+            let distance = offset.length();
+
+            if distance < MIN_CORRECTION_DISTANCE {
+                commands.entity(entity).remove::<PositionCorrection>();
+                continue;
+            }
+
+            let snapped = distance > MAX_SMOOTHED_DISTANCE;
+            position_correction_stats.record(distance, snapped);
+            log::debug!(
+                "Position correction of {:.1} for entity {:?}{}",
+                distance,
+                entity,
+                if snapped { " (snapped)" } else { "" }
+            );
+
+            // Snapped corrections are usually teleports so are not counted
+            if player_character.is_some()
+                && !snapped
+                && position_correction_stats.record_player_correction(time.elapsed_seconds_f64())
+            {
+                log::warn!(
+                    "Our position is being corrected by the server persistently, the client and server movement speeds may not match"
+                );
+            }
+
+            if snapped {
+                position.x = correction.server_position.x;
+                position.y = correction.server_position.y;
+                commands.entity(entity).remove::<PositionCorrection>();
+                continue;
+            }
+
+            offset
+        };
+
+        let delta = time.delta_seconds();
+        let step = offset * (delta / correction.remaining_time.max(delta)).min(1.0);
+        position.x += step.x;
+        position.y += step.y;
+
+        correction.offset = Some(offset - step);
+        correction.remaining_time -= delta;
+        if correction.remaining_time <= 0.0 {
+            commands.entity(entity).remove::<PositionCorrection>();
+        }
+    }
+}
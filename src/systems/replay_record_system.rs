@@ -0,0 +1,67 @@
+use bevy::{
+    math::EulerRot,
+    prelude::{EventReader, Query, Res, ResMut, Time, Transform},
+};
+
+use crate::{
+    components::{ClientEntity, Command},
+    events::ChatboxEvent,
+    resources::{CurrentZone, ReplayCommand, ReplayEntity, ReplayRecorder},
+};
+
+pub fn replay_record_system(
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    mut chatbox_events: EventReader<ChatboxEvent>,
+    query_entities: Query<(&ClientEntity, &Transform, &Command)>,
+    current_zone: Option<Res<CurrentZone>>,
+    time: Res<Time>,
+) {
+    if !replay_recorder.is_recording() {
+        return;
+    }
+
+    let elapsed = time.elapsed_seconds();
+    let replay_time = elapsed - *replay_recorder.start_time.get_or_insert(elapsed);
+
+    if let Some(current_zone) = current_zone.as_ref() {
+        if replay_recorder.last_zone_id != Some(current_zone.id) {
+            replay_recorder.write_zone(replay_time, current_zone.id);
+            replay_recorder.last_zone_id = Some(current_zone.id);
+        }
+    }
+
+    for event in chatbox_events.iter() {
+        match event {
+            ChatboxEvent::Say(name, text)
+            | ChatboxEvent::Shout(name, text)
+            | ChatboxEvent::Trade(name, text)
+            | ChatboxEvent::Clan(name, text)
+            | ChatboxEvent::Whisper(name, text) => {
+                replay_recorder.write_chat(replay_time, Some(name), text)
+            }
+            ChatboxEvent::Announce(name, text) => {
+                replay_recorder.write_chat(replay_time, name.as_deref(), text)
+            }
+            ChatboxEvent::System(text) | ChatboxEvent::Quest(text) => {
+                replay_recorder.write_chat(replay_time, None, text)
+            }
+        }
+    }
+
+    replay_recorder.frame_timer.tick(time.delta());
+    if !replay_recorder.frame_timer.just_finished() {
+        return;
+    }
+
+    let entities: Vec<ReplayEntity> = query_entities
+        .iter()
+        .map(|(client_entity, transform, command)| ReplayEntity {
+            id: client_entity.id,
+            entity_type: client_entity.entity_type,
+            position: transform.translation,
+            yaw: transform.rotation.to_euler(EulerRot::YXZ).0,
+            command: ReplayCommand::from_command(command),
+        })
+        .collect();
+    replay_recorder.write_frame(replay_time, &entities);
+}
@@ -0,0 +1,102 @@
+use bevy::{
+    math::{Quat, Vec3},
+    prelude::{
+        Camera3d, Color, Commands, Entity, EventWriter, Gizmos, Query, Res, ResMut, Time,
+        Transform, With,
+    },
+};
+
+use crate::{
+    animation::CameraAnimation,
+    components::ClientEntityType,
+    events::LoadZoneEvent,
+    resources::{ReplayCommand, ReplayPlayback},
+    systems::{FreeCamera, OrbitCamera},
+};
+
+pub fn replay_viewer_enter_system(
+    mut commands: Commands,
+    query_cameras: Query<Entity, With<Camera3d>>,
+    replay_playback: Res<ReplayPlayback>,
+) {
+    // Start the camera looking at where the first recorded entity was
+    let look_at = replay_playback
+        .replay
+        .entities_at(0.0)
+        .first()
+        .map_or(Vec3::new(5200.0, 0.0, -5200.0), |entity| entity.position);
+
+    for entity in query_cameras.iter() {
+        commands
+            .entity(entity)
+            .remove::<OrbitCamera>()
+            .remove::<CameraAnimation>()
+            .insert(FreeCamera::new(
+                look_at + Vec3::new(0.0, 15.0, 20.0),
+                0.0,
+                -35.0,
+            ));
+    }
+}
+
+fn entity_color(entity_type: ClientEntityType, command: ReplayCommand) -> Color {
+    if command == ReplayCommand::Die {
+        return Color::GRAY;
+    }
+
+    match entity_type {
+        ClientEntityType::Character => Color::YELLOW,
+        ClientEntityType::Monster => Color::RED,
+        ClientEntityType::Npc => Color::GREEN,
+        ClientEntityType::ItemDrop => Color::GOLD,
+    }
+}
+
+pub fn replay_viewer_system(
+    mut replay_playback: ResMut<ReplayPlayback>,
+    mut load_zone_events: EventWriter<LoadZoneEvent>,
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+) {
+    let duration = replay_playback.replay.duration();
+    if replay_playback.playing {
+        replay_playback.time += time.delta_seconds() * replay_playback.speed;
+        if replay_playback.time >= duration {
+            replay_playback.time = duration;
+            replay_playback.playing = false;
+        }
+    }
+
+    let replay_time = replay_playback.time;
+    if let Some(zone_id) = replay_playback.replay.zone_at(replay_time) {
+        if replay_playback.loaded_zone_id != Some(zone_id) {
+            load_zone_events.send(LoadZoneEvent::new(zone_id));
+            replay_playback.loaded_zone_id = Some(zone_id);
+        }
+    }
+
+    for entity in replay_playback.replay.entities_at(replay_time) {
+        let color = entity_color(entity.entity_type, entity.command);
+        let rotation = Quat::from_rotation_y(entity.yaw);
+        let size = if entity.entity_type == ClientEntityType::ItemDrop {
+            Vec3::splat(0.4)
+        } else {
+            Vec3::new(0.6, 1.8, 0.6)
+        };
+
+        gizmos.cuboid(
+            Transform::from_translation(entity.position + Vec3::new(0.0, size.y / 2.0, 0.0))
+                .with_rotation(rotation)
+                .with_scale(size),
+            color,
+        );
+
+        if entity.entity_type != ClientEntityType::ItemDrop {
+            gizmos.ray(
+                entity.position + Vec3::new(0.0, size.y / 2.0, 0.0),
+                rotation * Vec3::Z,
+                color,
+            );
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use bevy::{
     hierarchy::BuildChildren,
     prelude::{
-        AssetServer, Assets, Commands, EventReader, GlobalTransform, Query, Res, ResMut, Transform,
+        AssetServer, Assets, Commands, Entity, EventReader, GlobalTransform, Query, Res, ResMut,
+        Transform, With,
     },
     render::mesh::skinning::SkinnedMesh,
 };
 use rose_file_readers::VfsPath;
 
 use crate::{
-    components::DummyBoneOffset,
+    components::{CrowdReduced, DummyBoneOffset},
     effect_loader::spawn_effect,
     events::{SpawnEffect, SpawnEffectData, SpawnEffectEvent},
     render::{EffectMeshMaterial, ParticleMaterial},
@@ -29,11 +30,22 @@ fn get_effect_file_path<'a>(
     }
 }
 
+fn insert_point_light(
+    commands: &mut Commands,
+    effect_entity: Entity,
+    spawn_effect_data: &SpawnEffectData,
+) {
+    if let Some(point_light) = spawn_effect_data.point_light {
+        commands.entity(effect_entity).insert(point_light);
+    }
+}
+
 pub fn spawn_effect_system(
     mut commands: Commands,
     mut events: EventReader<SpawnEffectEvent>,
     query_transform: Query<&GlobalTransform>,
     query_skeleton: Query<(&SkinnedMesh, &DummyBoneOffset)>,
+    query_crowd_reduced: Query<(), With<CrowdReduced>>,
     game_data: Res<GameData>,
     asset_server: Res<AssetServer>,
     vfs_resource: Res<VfsResource>,
@@ -45,7 +57,7 @@ pub fn spawn_effect_system(
             SpawnEffectEvent::InEntity(effect_entity, spawn_effect_data) => {
                 if let Some(effect_file_path) = get_effect_file_path(spawn_effect_data, &game_data)
                 {
-                    spawn_effect(
+                    if let Some(effect_entity) = spawn_effect(
                         &vfs_resource.vfs,
                         &mut commands,
                         &asset_server,
@@ -54,10 +66,16 @@ pub fn spawn_effect_system(
                         effect_file_path,
                         spawn_effect_data.manual_despawn,
                         Some(*effect_entity),
-                    );
+                    ) {
+                        insert_point_light(&mut commands, effect_entity, spawn_effect_data);
+                    }
                 }
             }
             SpawnEffectEvent::AtEntity(at_entity, spawn_effect_data) => {
+                if query_crowd_reduced.contains(*at_entity) {
+                    continue;
+                }
+
                 if let Some(effect_file_path) = get_effect_file_path(spawn_effect_data, &game_data)
                 {
                     if let Ok(at_global_transform) = query_transform.get(*at_entity) {
@@ -76,11 +94,16 @@ pub fn spawn_effect_system(
                                 .insert(Transform::from_translation(
                                     at_global_transform.translation(),
                                 ));
+                            insert_point_light(&mut commands, effect_entity, spawn_effect_data);
                         }
                     }
                 }
             }
             SpawnEffectEvent::OnEntity(on_entity, dummy_bone_id, spawn_effect_data) => {
+                if query_crowd_reduced.contains(*on_entity) {
+                    continue;
+                }
+
                 let mut link_entity = *on_entity;
 
                 if let Some(dummy_bone_id) = dummy_bone_id {
@@ -107,6 +130,7 @@ pub fn spawn_effect_system(
                         None,
                     ) {
                         commands.entity(link_entity).add_child(effect_entity);
+                        insert_point_light(&mut commands, effect_entity, spawn_effect_data);
                     }
                 }
             }
@@ -124,6 +148,7 @@ pub fn spawn_effect_system(
                         None,
                     ) {
                         commands.entity(effect_entity).insert(*transform);
+                        insert_point_light(&mut commands, effect_entity, spawn_effect_data);
                     }
                 }
             }
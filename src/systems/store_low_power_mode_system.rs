@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::{DirectionalLight, Entity, Query, ResMut, Visibility, Window, With},
+    window::PrimaryWindow,
+    winit::{UpdateMode, WinitSettings},
+};
+
+use crate::{
+    components::{Effect, PersonalStore, PlayerCharacter},
+    resources::StoreLowPowerMode,
+};
+
+const LOW_POWER_WINDOW_SIZE: (f32, f32) = (360.0, 240.0);
+
+// Frame rate while low power mode is active, the store only needs to show sales
+const LOW_POWER_FPS: f32 = 5.0;
+
+#[allow(clippy::type_complexity)]
+pub fn store_low_power_mode_system(
+    mut store_low_power_mode: ResMut<StoreLowPowerMode>,
+    mut query_window: Query<&mut Window, With<PrimaryWindow>>,
+    mut query_directional_lights: Query<(Entity, &mut DirectionalLight)>,
+    mut query_effects: Query<(Entity, &mut Visibility), With<Effect>>,
+    query_player: Query<Option<&PersonalStore>, With<PlayerCharacter>>,
+    winit_settings: Option<ResMut<WinitSettings>>,
+) {
+    let low_power_mode = &mut *store_low_power_mode;
+
+    // Automatically restore once the player's store is closed
+    let has_store = matches!(query_player.get_single(), Ok(Some(_)));
+    if !has_store {
+        low_power_mode.enabled = false;
+    }
+
+    if low_power_mode.enabled != low_power_mode.active {
+        low_power_mode.active = low_power_mode.enabled;
+
+        if low_power_mode.active {
+            low_power_mode.recent_sales.clear();
+            low_power_mode.total_sales = 0;
+        }
+
+        if let Ok(mut window) = query_window.get_single_mut() {
+            if low_power_mode.active {
+                low_power_mode.saved_window_size =
+                    Some((window.resolution.width(), window.resolution.height()));
+                window
+                    .resolution
+                    .set(LOW_POWER_WINDOW_SIZE.0, LOW_POWER_WINDOW_SIZE.1);
+            } else if let Some((width, height)) = low_power_mode.saved_window_size.take() {
+                window.resolution.set(width, height);
+            }
+        }
+
+        if let Some(mut winit_settings) = winit_settings {
+            if low_power_mode.active {
+                low_power_mode.saved_update_modes =
+                    Some((winit_settings.focused_mode, winit_settings.unfocused_mode));
+                let update_mode = UpdateMode::ReactiveLowPower {
+                    wait: Duration::from_secs_f32(1.0 / LOW_POWER_FPS),
+                };
+                winit_settings.focused_mode = update_mode;
+                winit_settings.unfocused_mode = update_mode;
+            } else if let Some((focused_mode, unfocused_mode)) =
+                low_power_mode.saved_update_modes.take()
+            {
+                winit_settings.focused_mode = focused_mode;
+                winit_settings.unfocused_mode = unfocused_mode;
+            }
+        }
+
+        if low_power_mode.active {
+            for (entity, mut directional_light) in query_directional_lights.iter_mut() {
+                low_power_mode
+                    .saved_shadows_enabled
+                    .push((entity, directional_light.shadows_enabled));
+                directional_light.shadows_enabled = false;
+            }
+        } else {
+            for (entity, shadows_enabled) in low_power_mode.saved_shadows_enabled.drain(..) {
+                if let Ok((_, mut directional_light)) = query_directional_lights.get_mut(entity) {
+                    directional_light.shadows_enabled = shadows_enabled;
+                }
+            }
+
+            for (entity, visibility) in low_power_mode.saved_effect_visibility.drain(..) {
+                if let Ok((_, mut effect_visibility)) = query_effects.get_mut(entity) {
+                    *effect_visibility = visibility;
+                }
+            }
+        }
+    }
+
+    if !low_power_mode.active {
+        return;
+    }
+
+    // Hide effects every frame, as new effects are spawned while the store is open
+    for (entity, mut visibility) in query_effects.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        if !low_power_mode
+            .saved_effect_visibility
+            .iter()
+            .any(|(saved_entity, _)| *saved_entity == entity)
+        {
+            low_power_mode
+                .saved_effect_visibility
+                .push((entity, *visibility));
+        }
+        *visibility = Visibility::Hidden;
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::{Commands, EventReader, Local, Query, Res, ResMut, Time};
+
+use crate::{
+    components::Summon,
+    events::ExtendedServerMessageEvent,
+    protocol::{ExtendedServerMessage, SummonInfo},
+    resources::{ClientEntityList, GameConnection, PlayerSummons},
+};
+
+pub fn summon_system(
+    mut commands: Commands,
+    game_connection: Option<Res<GameConnection>>,
+    client_entity_list: Res<ClientEntityList>,
+    mut player_summons: ResMut<PlayerSummons>,
+    mut pending_summons: Local<Vec<SummonInfo>>,
+    mut query_summons: Query<&mut Summon>,
+    time: Res<Time>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    if game_connection.is_none() {
+        if !player_summons.entity_ids.is_empty() {
+            player_summons.entity_ids.clear();
+        }
+        pending_summons.clear();
+        return;
+    }
+
+    // Each summon list replaces the previous one
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        if let ExtendedServerMessage::SummonList(summons) = message {
+            player_summons.entity_ids = summons
+                .iter()
+                .filter(|summon| {
+                    Some(summon.owner_entity_id) == client_entity_list.player_entity_id
+                })
+                .map(|summon| summon.entity_id)
+                .collect();
+            *pending_summons = summons.clone();
+        }
+    }
+
+    for mut summon in query_summons.iter_mut() {
+        if let Some(remaining_lifetime) = summon.remaining_lifetime.as_mut() {
+            *remaining_lifetime = remaining_lifetime.saturating_sub(time.delta());
+        }
+    }
+
+    // Summons may be listed before their entity has spawned, so keep them until it has
+    pending_summons.retain(|summon_info| {
+        let Some(entity) = client_entity_list.get(summon_info.entity_id) else {
+            return true;
+        };
+
+        let summon = Summon {
+            owner_entity_id: summon_info.owner_entity_id,
+            is_player_owned: Some(summon_info.owner_entity_id)
+                == client_entity_list.player_entity_id,
+            remaining_lifetime: summon_info.remaining_lifetime,
+            total_lifetime: summon_info.total_lifetime,
+        };
+        if let Ok(mut current) = query_summons.get_mut(entity) {
+            *current = summon;
+        } else {
+            commands.entity(entity).insert(summon);
+        }
+        false
+    });
+}
@@ -0,0 +1,210 @@
+use bevy::{
+    asset::HandleId,
+    prelude::{
+        AssetServer, Assets, Camera3d, ComputedVisibility, GlobalTransform, Handle, Image, Query,
+        Res, ResMut, With,
+    },
+    time::Time,
+    utils::HashMap,
+};
+
+use crate::{
+    render::ObjectMaterial,
+    resources::{
+        drop_image_mip_levels, TextureStreaming, TextureStreamingImage, TextureStreamingState,
+    },
+};
+
+fn touch_materials(
+    object_materials: &mut Assets<ObjectMaterial>,
+    texture_materials: &HashMap<HandleId, Vec<HandleId>>,
+    image_id: HandleId,
+) {
+    // Material bind groups are only rebuilt when the material is modified, so
+    // after changing a texture we must also mark the materials which use it.
+    for material_id in texture_materials.get(&image_id).into_iter().flatten() {
+        object_materials.get_mut(&Handle::weak(*material_id));
+    }
+}
+
+pub fn texture_streaming_system(
+    mut texture_streaming: ResMut<TextureStreaming>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut object_materials: ResMut<Assets<ObjectMaterial>>,
+    query_camera: Query<&GlobalTransform, With<Camera3d>>,
+    query_materials: Query<(
+        &Handle<ObjectMaterial>,
+        &GlobalTransform,
+        &ComputedVisibility,
+    )>,
+) {
+    if !texture_streaming
+        .update_timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        return;
+    }
+
+    // Index the materials using each texture once, rather than searching all
+    // materials every time a texture is changed
+    let mut texture_materials: HashMap<HandleId, Vec<HandleId>> = HashMap::default();
+    for (material_id, material) in object_materials.iter() {
+        if let Some(texture) = material.base_texture.as_ref() {
+            texture_materials
+                .entry(texture.id())
+                .or_default()
+                .push(material_id);
+        }
+    }
+
+    // Forget unloaded textures and complete any finished reloads
+    texture_streaming.images.retain(|id, streamed| {
+        let Some(image) = images.get(&Handle::weak(*id)) else {
+            return false;
+        };
+
+        if streamed.state == TextureStreamingState::Reloading
+            && image.data.len() > streamed.reduced_size
+        {
+            touch_materials(&mut object_materials, &texture_materials, *id);
+            return false;
+        }
+
+        true
+    });
+
+    let Some(budget) = texture_streaming.budget else {
+        // Streaming has been disabled, restore any reduced textures
+        for streamed in texture_streaming.images.values_mut() {
+            if streamed.state == TextureStreamingState::Reduced {
+                asset_server.reload_asset(streamed.path.clone());
+                streamed.state = TextureStreamingState::Reloading;
+            }
+        }
+        texture_streaming.total_bytes = images.iter().map(|(_, image)| image.data.len()).sum();
+        texture_streaming.saved_bytes = 0;
+        return;
+    };
+
+    let texture_streaming = &mut *texture_streaming;
+    let Ok(camera_transform) = query_camera.get_single() else {
+        return;
+    };
+    let camera_position = camera_transform.translation();
+
+    // Find the nearest visible use of each base texture
+    let mut texture_distances: HashMap<HandleId, f32> = HashMap::default();
+    for (material_handle, transform, computed_visibility) in query_materials.iter() {
+        let Some(texture) = object_materials
+            .get(material_handle)
+            .and_then(|material| material.base_texture.as_ref())
+        else {
+            continue;
+        };
+
+        let distance = if computed_visibility.is_visible() {
+            transform.translation().distance(camera_position)
+        } else {
+            f32::MAX
+        };
+
+        let nearest = texture_distances.entry(texture.id()).or_insert(f32::MAX);
+        *nearest = nearest.min(distance);
+    }
+
+    let mut candidates: Vec<(HandleId, f32, usize)> = texture_distances
+        .into_iter()
+        .filter_map(|(id, distance)| {
+            images
+                .get(&Handle::weak(id))
+                .map(|image| (id, distance, image.data.len()))
+        })
+        .collect();
+    candidates.sort_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+
+    // Textures which are reloading will soon be back at their full size
+    let mut used_bytes: usize = images
+        .iter()
+        .map(|(_, image)| image.data.len())
+        .sum::<usize>()
+        + texture_streaming
+            .images
+            .values()
+            .filter(|streamed| streamed.state == TextureStreamingState::Reloading)
+            .map(|streamed| streamed.full_size.saturating_sub(streamed.reduced_size))
+            .sum::<usize>();
+
+    // Only reduce textures while over budget, starting with the furthest away
+    for &(id, _, current_size) in candidates.iter().rev() {
+        if used_bytes <= budget {
+            break;
+        }
+
+        if texture_streaming.images.contains_key(&id) {
+            continue;
+        }
+
+        // Textures which were not loaded through the asset server cannot be reloaded
+        let Some(path) = asset_server.get_handle_path(id) else {
+            continue;
+        };
+        let Some(image) = images.get_mut(&Handle::weak(id)) else {
+            continue;
+        };
+
+        if drop_image_mip_levels(image, texture_streaming.dropped_mip_levels) == 0 {
+            continue;
+        }
+
+        let reduced_size = image.data.len();
+        used_bytes -= current_size.saturating_sub(reduced_size);
+        texture_streaming.images.insert(
+            id,
+            TextureStreamingImage {
+                path: path.to_owned(),
+                state: TextureStreamingState::Reduced,
+                full_size: current_size,
+                reduced_size,
+            },
+        );
+        touch_materials(&mut object_materials, &texture_materials, id);
+    }
+
+    // Restore reduced textures near the camera, nearest first, while they fit in the budget
+    let mut num_reloads = 0;
+    for &(id, distance, _) in candidates.iter() {
+        if distance > texture_streaming.high_detail_distance
+            || num_reloads >= texture_streaming.max_reloads_per_update
+        {
+            break;
+        }
+
+        let Some(streamed) = texture_streaming.images.get_mut(&id) else {
+            continue;
+        };
+        if streamed.state != TextureStreamingState::Reduced {
+            continue;
+        }
+
+        let restored_bytes = streamed.full_size.saturating_sub(streamed.reduced_size);
+        if used_bytes + restored_bytes > budget {
+            continue;
+        }
+
+        asset_server.reload_asset(streamed.path.clone());
+        streamed.state = TextureStreamingState::Reloading;
+        used_bytes += restored_bytes;
+        num_reloads += 1;
+    }
+
+    texture_streaming.total_bytes = images.iter().map(|(_, image)| image.data.len()).sum();
+    texture_streaming.saved_bytes = texture_streaming
+        .images
+        .values()
+        .filter(|streamed| streamed.state == TextureStreamingState::Reduced)
+        .map(|streamed| streamed.full_size - streamed.reduced_size)
+        .sum();
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use bevy::{
+    ecs::query::WorldQuery,
+    prelude::{EventReader, EventWriter, Local, Query, Res, ResMut, Time, With},
+};
+
+use rose_game_common::{
+    components::{AbilityValues, CharacterInfo, ExperiencePoints, HealthPoints, Level, ManaPoints},
+    messages::client::ClientMessage,
+};
+
+use crate::{
+    client_plugin::ClientPluginRegistry,
+    components::PlayerCharacter,
+    events::{ChatboxEvent, ZoneEvent},
+    resources::{CurrentZone, GameConnection},
+    scripting::{UserScriptCommand, UserScriptEvent, UserScriptManager, UserScriptPlayer},
+    ui::{UiStateWindows, UiWindowId},
+};
+
+// Minimum seconds between chat messages sent by the same script
+const USER_SCRIPT_CHAT_INTERVAL: f64 = 2.0;
+
+#[derive(WorldQuery)]
+pub struct PlayerQuery<'w> {
+    ability_values: &'w AbilityValues,
+    character_info: &'w CharacterInfo,
+    level: &'w Level,
+    health_points: &'w HealthPoints,
+    mana_points: &'w ManaPoints,
+    experience_points: &'w ExperiencePoints,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn user_script_system(
+    mut user_script_manager: ResMut<UserScriptManager>,
+    mut last_player_level: Local<Option<u32>>,
+    mut last_chat_times: Local<HashMap<String, f64>>,
+    mut zone_events: EventReader<ZoneEvent>,
+    mut chatbox_events: EventWriter<ChatboxEvent>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    client_plugin_registry: Res<ClientPluginRegistry>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    current_zone: Option<Res<CurrentZone>>,
+    game_connection: Option<Res<GameConnection>>,
+    time: Res<Time>,
+) {
+    let player = query_player.get_single().ok();
+    user_script_manager.set_player(player.as_ref().map(|player| UserScriptPlayer {
+        name: player.character_info.name.clone(),
+        level: player.level.level,
+        health_points: player.health_points.hp,
+        max_health_points: player.ability_values.get_max_health(),
+        mana_points: player.mana_points.mp,
+        max_mana_points: player.ability_values.get_max_mana(),
+        experience_points: player.experience_points.xp,
+        zone_id: current_zone.as_ref().map(|current_zone| current_zone.id),
+    }));
+
+    for event in zone_events.iter() {
+        let &ZoneEvent::Loaded(zone_id) = event;
+        user_script_manager.handle_event(&UserScriptEvent::ZoneChange(zone_id));
+    }
+
+    let player_level = player.as_ref().map(|player| player.level.level);
+    if let (Some(last_level), Some(level)) = (*last_player_level, player_level) {
+        if level > last_level {
+            user_script_manager.handle_event(&UserScriptEvent::LevelUp(level));
+        }
+    }
+    *last_player_level = player_level;
+
+    for command in user_script_manager.take_commands() {
+        match command {
+            UserScriptCommand::SendChat { script_name, text } => {
+                // Scripts may only talk, server and GM commands must be typed by the player
+                if text.trim_start().starts_with('/') {
+                    chatbox_events.send(ChatboxEvent::System(format!(
+                        "Script {} is not allowed to send commands",
+                        script_name
+                    )));
+                    continue;
+                }
+
+                let now = time.elapsed_seconds_f64();
+                if let Some(last_sent) = last_chat_times.get(&script_name) {
+                    if now - last_sent < USER_SCRIPT_CHAT_INTERVAL {
+                        log::warn!(
+                            "Dropped chat message from user script {}, sending too fast",
+                            script_name
+                        );
+                        continue;
+                    }
+                }
+
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::Chat { text })
+                        .ok();
+                    last_chat_times.insert(script_name, now);
+                }
+            }
+            UserScriptCommand::OpenWindow(name) => {
+                let window_id = UiWindowId::PLAYER_WINDOWS
+                    .iter()
+                    .copied()
+                    .find(|id| id.0 == name)
+                    .or_else(|| client_plugin_registry.find_window(&name));
+
+                if let Some(window_id) = window_id {
+                    ui_state_windows.set_open(window_id, true);
+                } else {
+                    chatbox_events.send(ChatboxEvent::System(format!(
+                        "Script tried to open unknown window {}",
+                        name
+                    )));
+                }
+            }
+            UserScriptCommand::Print(text) => {
+                chatbox_events.send(ChatboxEvent::System(text));
+            }
+        }
+    }
+}
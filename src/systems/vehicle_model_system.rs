@@ -1,8 +1,8 @@
 use bevy::{
     prelude::{
-        AssetServer, Assets, BuildChildren, Changed, Commands, ComputedVisibility,
-        DespawnRecursiveExt, Entity, GlobalTransform, Query, Res, ResMut, Transform, Visibility,
-        World,
+        Added, AssetServer, Assets, BuildChildren, Changed, Commands, ComputedVisibility,
+        DespawnRecursiveExt, Entity, GlobalTransform, Or, Query, Res, ResMut, Transform,
+        Visibility, With, World,
     },
     render::mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
 };
@@ -11,9 +11,13 @@ use rose_game_common::components::{Equipment, MoveMode};
 
 use crate::{
     animation::SkeletalAnimation,
-    components::{CharacterModel, CharacterModelPart, DummyBoneOffset, Vehicle, VehicleModel},
+    components::{
+        CharacterModel, CharacterModelPart, DummyBoneOffset, PendingVehicleModel, Vehicle,
+        VehicleModel,
+    },
     model_loader::ModelLoader,
     render::{EffectMeshMaterial, ObjectMaterial, ParticleMaterial},
+    resources::{SkeletonCache, SkeletonCacheState},
 };
 
 pub fn vehicle_model_system(
@@ -26,7 +30,12 @@ pub fn vehicle_model_system(
             &SkinnedMesh,
             Option<&Vehicle>,
         ),
-        Changed<MoveMode>,
+        // The character skeleton may still be loading when the move mode changes
+        Or<(
+            Changed<MoveMode>,
+            Added<SkinnedMesh>,
+            With<PendingVehicleModel>,
+        )>,
     >,
     query_vehicle_model: Query<&VehicleModel>,
     asset_server: Res<AssetServer>,
@@ -35,9 +44,16 @@ pub fn vehicle_model_system(
     mut particle_materials: ResMut<Assets<ParticleMaterial>>,
     mut effect_mesh_materials: ResMut<Assets<EffectMeshMaterial>>,
     mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+    mut skeleton_cache: ResMut<SkeletonCache>,
 ) {
+    skeleton_cache.update(&mut skinned_mesh_inverse_bindposes_assets);
+
     // Vehicle entity, where entity becomes a child of it.
     for (entity, equipment, move_mode, skinned_mesh, vehicle) in query.iter_mut() {
+        if !matches!(move_mode, MoveMode::Drive) {
+            commands.entity(entity).remove::<PendingVehicleModel>();
+        }
+
         if let Some(vehicle) = vehicle {
             let vehicle_model = query_vehicle_model
                 .get(vehicle.vehicle_model_entity)
@@ -110,6 +126,24 @@ pub fn vehicle_model_system(
                 .despawn_recursive();
             commands.entity(entity).remove::<Vehicle>();
         } else if matches!(move_mode, MoveMode::Drive) {
+            // Wait until the vehicle skeleton has loaded before mounting the vehicle
+            let skeleton = match model_loader
+                .get_vehicle_type(equipment)
+                .map(|vehicle_type| {
+                    skeleton_cache.get(ModelLoader::get_vehicle_skeleton_path(vehicle_type))
+                }) {
+                Some(SkeletonCacheState::Loaded(skeleton)) => skeleton,
+                Some(SkeletonCacheState::Loading) => {
+                    commands.entity(entity).insert(PendingVehicleModel);
+                    continue;
+                }
+                Some(SkeletonCacheState::Failed) | None => {
+                    commands.entity(entity).remove::<PendingVehicleModel>();
+                    continue;
+                }
+            };
+            commands.entity(entity).remove::<PendingVehicleModel>();
+
             let driver_model_entity = skinned_mesh.joints[0];
             let vehicle_model_entity = commands
                 .spawn((
@@ -128,10 +162,10 @@ pub fn vehicle_model_system(
                     &mut object_materials,
                     &mut particle_materials,
                     &mut effect_mesh_materials,
-                    &mut skinned_mesh_inverse_bindposes_assets,
                     vehicle_model_entity,
                     driver_model_entity,
                     equipment,
+                    &skeleton,
                 );
 
             commands
@@ -0,0 +1,33 @@
+use bevy::prelude::{AssetServer, Res, ResMut, Time};
+
+use crate::resources::VfsModHotReload;
+
+pub fn vfs_mod_hot_reload_system(
+    mut vfs_mod_hot_reload: ResMut<VfsModHotReload>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    if !vfs_mod_hot_reload
+        .scan_timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        return;
+    }
+
+    let vfs_mod_hot_reload = &mut *vfs_mod_hot_reload;
+    for layer in vfs_mod_hot_reload.layers.iter() {
+        for file in layer.list_files() {
+            let previous_modified = vfs_mod_hot_reload
+                .file_modified
+                .insert(file.host_path, file.modified);
+
+            if previous_modified != Some(file.modified) {
+                // Only assets which are already loaded are reloaded, anything else
+                // will pick up the mod file when it is first loaded
+                log::info!("Reloading modified asset {}", file.vfs_path);
+                asset_server.reload_asset(file.vfs_path);
+            }
+        }
+    }
+}
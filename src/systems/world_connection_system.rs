@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use bevy::prelude::{Commands, EventWriter, NextState, Res, ResMut, State};
 
 use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
@@ -5,17 +7,22 @@ use rose_network_common::ConnectionError;
 
 use crate::{
     events::{NetworkEvent, WorldConnectionEvent},
-    resources::{Account, AppState, CharacterList, WorldConnection},
+    resources::{
+        Account, AppState, CharacterList, CharacterSlots, ConnectionServer, ConnectionState,
+        LoginQueue, WorldConnection,
+    },
 };
 
 pub fn world_connection_system(
     mut commands: Commands,
     world_connection: Option<Res<WorldConnection>>,
     account: Option<Res<Account>>,
+    mut login_queue: Option<ResMut<LoginQueue>>,
     app_state_current: Res<State<AppState>>,
     mut app_state_next: ResMut<NextState<AppState>>,
     mut network_events: EventWriter<NetworkEvent>,
     mut world_connection_events: EventWriter<WorldConnectionEvent>,
+    mut connection_state: ResMut<ConnectionState>,
 ) {
     let world_connection = if let Some(world_connection) = world_connection {
         world_connection
@@ -29,27 +36,70 @@ pub fn world_connection_system(
         return;
     };
 
+    while let Ok(status) = world_connection.login_queue_rx.try_recv() {
+        if status.position == 0 {
+            commands.remove_resource::<LoginQueue>();
+            login_queue = None;
+        } else if let Some(login_queue) = login_queue.as_mut() {
+            login_queue.position = status.position;
+            login_queue.estimated_wait = status.estimated_wait;
+            login_queue.updated = Instant::now();
+
+            // Servers may only send the message of the day with the first update
+            if !status.message_of_the_day.is_empty() {
+                login_queue.message_of_the_day = status.message_of_the_day;
+            }
+        } else {
+            commands.insert_resource(LoginQueue {
+                position: status.position,
+                estimated_wait: status.estimated_wait,
+                message_of_the_day: status.message_of_the_day,
+                updated: Instant::now(),
+            });
+        }
+    }
+
+    while let Ok(character_slots) = world_connection.character_slots_rx.try_recv() {
+        commands.insert_resource(CharacterSlots {
+            slot_count: character_slots.slot_count,
+            max_slot_count: character_slots.max_slot_count,
+            unlock_price: character_slots.unlock_price,
+        });
+
+        // Refresh the character list, e.g. after a slot was purchased
+        world_connection
+            .client_message_tx
+            .send(ClientMessage::GetCharacterList)
+            .ok();
+    }
+
     let result: Result<(), anyhow::Error> = loop {
         match world_connection.server_message_rx.try_recv() {
             Ok(ServerMessage::ConnectionRequestSuccess {
                 packet_sequence_id: _,
             }) => {
-                world_connection
+                if world_connection
                     .client_message_tx
                     .send(ClientMessage::GetCharacterList)
-                    .ok();
+                    .is_err()
+                {
+                    break Err(ConnectionError::ConnectionLost.into());
+                }
             }
-            Ok(ServerMessage::ConnectionRequestError { error: _ }) => {
-                break Err(ConnectionError::ConnectionLost.into());
+            Ok(ServerMessage::ConnectionRequestError { error }) => {
+                break Err(anyhow::anyhow!("Connection was refused: {:?}", error));
             }
             Ok(ServerMessage::CharacterList {
                 character_list: characters,
             }) => {
+                commands.remove_resource::<LoginQueue>();
+
                 if !matches!(app_state_current.get(), AppState::GameCharacterSelect) {
                     app_state_next.set(AppState::GameCharacterSelect);
                 }
 
                 commands.insert_resource(CharacterList { characters });
+                *connection_state = ConnectionState::SelectingCharacter;
             }
             Ok(ServerMessage::SelectCharacterSuccess {
                 login_token,
@@ -64,9 +114,12 @@ pub fn world_connection_system(
                     login_token,
                     password: account.password.clone(),
                 });
+                *connection_state = ConnectionState::Connecting(ConnectionServer::Game);
             }
             Ok(ServerMessage::SelectCharacterError) => {
-                break Err(ConnectionError::ConnectionLost.into());
+                break Err(anyhow::anyhow!(
+                    "The selected character could not be played"
+                ));
             }
             Ok(ServerMessage::CreateCharacterSuccess { character_slot }) => {
                 world_connection_events
@@ -97,8 +150,13 @@ pub fn world_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("World server connection error: {}", error);
         commands.remove_resource::<WorldConnection>();
+        commands.remove_resource::<LoginQueue>();
+        commands.remove_resource::<CharacterSlots>();
+
+        if connection_state.active_server() == Some(ConnectionServer::World) {
+            *connection_state = ConnectionState::error(ConnectionServer::World, error);
+        }
     }
 }
@@ -0,0 +1,95 @@
+use bevy::prelude::{EventReader, EventWriter, Local, Res, ResMut};
+
+use rose_data::ZoneId;
+
+use crate::{
+    data_integrity::{check_zone_data_checksums, ZoneDataIssue},
+    events::{ExtendedServerMessageEvent, LogoutEvent},
+    protocol::{ExtendedClientMessage, ExtendedServerMessage, ProtocolCapabilities},
+    resources::{
+        ClientCapabilities, ClientEntityList, ClientSettings, ConnectionServer, ConnectionState,
+        GameConnection, VfsResource, ZoneDataVerification,
+    },
+};
+
+fn zone_data_issue_text(issue: &ZoneDataIssue) -> String {
+    match issue {
+        ZoneDataIssue::Missing { path } => format!("{} (missing)", path),
+        ZoneDataIssue::Mismatch { path } => format!("{} (different)", path),
+    }
+}
+
+pub fn zone_data_verification_system(
+    game_connection: Option<Res<GameConnection>>,
+    client_entity_list: Res<ClientEntityList>,
+    client_capabilities: Res<ClientCapabilities>,
+    client_settings: Res<ClientSettings>,
+    vfs_resource: Res<VfsResource>,
+    mut zone_data_verification: ResMut<ZoneDataVerification>,
+    mut connection_state: ResMut<ConnectionState>,
+    mut logout_events: EventWriter<LogoutEvent>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+    mut requested_zone_id: Local<Option<ZoneId>>,
+) {
+    let Some(game_connection) = game_connection else {
+        *requested_zone_id = None;
+        return;
+    };
+
+    // Older servers do not know the checksums request
+    if !client_capabilities.supports(ProtocolCapabilities::ZONE_DATA_CHECKSUMS) {
+        return;
+    }
+
+    if client_entity_list.zone_id != *requested_zone_id {
+        *requested_zone_id = client_entity_list.zone_id;
+
+        if zone_data_verification.zone_id.is_some() {
+            *zone_data_verification = ZoneDataVerification::default();
+        }
+
+        if let Some(zone_id) = client_entity_list.zone_id {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::RequestZoneDataChecksums(zone_id))
+                .ok();
+        }
+    }
+
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        let ExtendedServerMessage::ZoneDataChecksums(checksums) = message else {
+            continue;
+        };
+
+        // Ignore replies for a zone we have already left
+        if client_entity_list.zone_id != Some(checksums.zone_id) {
+            continue;
+        }
+
+        let issues = check_zone_data_checksums(&vfs_resource.vfs, &checksums.files);
+        if issues.is_empty() {
+            continue;
+        }
+
+        let details: Vec<String> = issues.iter().map(zone_data_issue_text).collect();
+        log::warn!(
+            "Game data for zone {} differs from the server: {}",
+            checksums.zone_id.get(),
+            details.join(", ")
+        );
+
+        if client_settings.login.refuse_mismatched_zone_data {
+            *connection_state = ConnectionState::error(
+                ConnectionServer::Game,
+                format!(
+                    "Your game data for this zone differs from the server, please update your client:\n{}",
+                    details.join("\n")
+                ),
+            );
+            logout_events.send(LogoutEvent::ReturnToCharacterSelect);
+        } else {
+            zone_data_verification.zone_id = Some(checksums.zone_id);
+            zone_data_verification.issues = issues;
+        }
+    }
+}
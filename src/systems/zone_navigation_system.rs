@@ -0,0 +1,97 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Assets, EventReader, GlobalTransform, Query, Res, ResMut, With},
+    render::primitives::Aabb,
+};
+use bevy_rapier3d::prelude::CollisionGroups;
+
+use crate::{
+    components::{ColliderParent, COLLISION_FILTER_COLLIDABLE, COLLISION_GROUP_ZONE_OBJECT},
+    events::ZoneEvent,
+    resources::{CurrentZone, NavigationGridInput, NavigationHeightmap, ZoneNavigation},
+    zone_loader::ZoneLoaderAsset,
+};
+
+pub fn zone_navigation_system(
+    mut zone_events: EventReader<ZoneEvent>,
+    mut zone_navigation: ResMut<ZoneNavigation>,
+    current_zone: Option<Res<CurrentZone>>,
+    zone_loader_assets: Res<Assets<ZoneLoaderAsset>>,
+    query_obstacles: Query<(&Aabb, &GlobalTransform, &CollisionGroups), With<ColliderParent>>,
+) {
+    zone_navigation.update();
+
+    for event in zone_events.iter() {
+        let &ZoneEvent::Loaded(zone_id) = event;
+        let Some(zone_data) = current_zone
+            .as_ref()
+            .filter(|current_zone| current_zone.id == zone_id)
+            .and_then(|current_zone| zone_loader_assets.get(&current_zone.handle))
+        else {
+            continue;
+        };
+
+        // Only copy the data needed to generate the grid, the work is done on a background task
+        let heightmaps = zone_data
+            .blocks
+            .iter()
+            .filter_map(|block| block.as_ref())
+            .map(|block| {
+                let heightmap = &block.him;
+                let mut heights = Vec::with_capacity(heightmap.width * heightmap.height);
+                for y in 0..heightmap.height as i32 {
+                    for x in 0..heightmap.width as i32 {
+                        heights.push(heightmap.get_clamped(x, y) / 100.0);
+                    }
+                }
+
+                NavigationHeightmap {
+                    block_x: block.block_x,
+                    block_y: block.block_y,
+                    width: heightmap.width,
+                    height: heightmap.height,
+                    heights,
+                }
+            })
+            .collect();
+
+        let obstacles = query_obstacles
+            .iter()
+            .filter(|(_, _, collision_groups)| {
+                collision_groups
+                    .memberships
+                    .contains(COLLISION_GROUP_ZONE_OBJECT)
+                    && collision_groups
+                        .filters
+                        .contains(COLLISION_FILTER_COLLIDABLE)
+            })
+            .map(|(aabb, global_transform, _)| {
+                let center = Vec3::from(aabb.center);
+                let half_extents = Vec3::from(aabb.half_extents);
+                let mut min = Vec3::splat(f32::MAX);
+                let mut max = Vec3::splat(f32::MIN);
+                for corner in [
+                    Vec3::new(-1.0, -1.0, -1.0),
+                    Vec3::new(-1.0, -1.0, 1.0),
+                    Vec3::new(-1.0, 1.0, -1.0),
+                    Vec3::new(-1.0, 1.0, 1.0),
+                    Vec3::new(1.0, -1.0, -1.0),
+                    Vec3::new(1.0, -1.0, 1.0),
+                    Vec3::new(1.0, 1.0, -1.0),
+                    Vec3::new(1.0, 1.0, 1.0),
+                ] {
+                    let point = global_transform.transform_point(center + corner * half_extents);
+                    min = min.min(point);
+                    max = max.max(point);
+                }
+                (min, max)
+            })
+            .collect();
+
+        zone_navigation.generate(NavigationGridInput {
+            zone_id,
+            heightmaps,
+            obstacles,
+        });
+    }
+}
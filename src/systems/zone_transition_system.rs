@@ -0,0 +1,63 @@
+use bevy::prelude::{EventReader, Query, Res, ResMut, Time, With};
+use rand::Rng;
+
+use crate::{
+    components::{ClientEntity, PlayerCharacter},
+    events::{LoadZoneEvent, ZoneEvent},
+    resources::{ActiveZoneTransition, ZoneTransition, ZONE_TRANSITION_TIPS},
+    systems::OrbitCamera,
+};
+
+pub fn zone_transition_system(
+    mut zone_transition: ResMut<ZoneTransition>,
+    mut load_zone_events: EventReader<LoadZoneEvent>,
+    mut zone_events: EventReader<ZoneEvent>,
+    query_player: Query<Option<&ClientEntity>, With<PlayerCharacter>>,
+    mut query_orbit_camera: Query<&mut OrbitCamera>,
+    time: Res<Time>,
+) {
+    let player_in_zone = query_player
+        .get_single()
+        .map_or(false, |client_entity| client_entity.is_some());
+
+    // Teleporting removes the player from the current zone until the server
+    // has placed them in the next one
+    for event in load_zone_events.iter() {
+        if !player_in_zone {
+            zone_transition.active = Some(ActiveZoneTransition {
+                zone_id: event.id,
+                zone_loaded: false,
+                tip_index: rand::thread_rng().gen_range(0..ZONE_TRANSITION_TIPS.len()),
+                fade_elapsed: None,
+            });
+        }
+    }
+
+    let Some(active) = zone_transition.active.as_mut() else {
+        zone_events.clear();
+        return;
+    };
+
+    for event in zone_events.iter() {
+        let &ZoneEvent::Loaded(zone_id) = event;
+        if zone_id == active.zone_id {
+            active.zone_loaded = true;
+        }
+    }
+
+    if let Some(fade_elapsed) = active.fade_elapsed.as_mut() {
+        *fade_elapsed += time.delta();
+        if *fade_elapsed >= ZoneTransition::FADE_DURATION {
+            zone_transition.active = None;
+        }
+    } else if active.zone_loaded && player_in_zone {
+        // Move the camera straight to the new position rather than smoothly
+        // flying across the world, the view is blended in while the transition
+        // screen fades out
+        for mut orbit_camera in query_orbit_camera.iter_mut() {
+            orbit_camera.snap_to_follow_entity = true;
+        }
+
+        active.fade_elapsed = Some(Default::default());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::events::ChatCommandEvent;
+
+pub struct ChatCommand {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+    parse: fn(&[&str]) -> Result<ChatCommandEvent, String>,
+}
+
+fn expect_no_arguments(arguments: &[&str]) -> Result<(), String> {
+    if arguments.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Unexpected argument '{}'", arguments[0]))
+    }
+}
+
+// Client side commands, any other command is sent to the server as chat
+pub const CHAT_COMMANDS: [ChatCommand; 8] = [
+    ChatCommand {
+        name: "clan",
+        usage: "/clan <message>",
+        description: "Send a message to your clan, the same as starting with &",
+        parse: |arguments| {
+            if arguments.is_empty() {
+                Err("Missing message".to_string())
+            } else {
+                Ok(ChatCommandEvent::ClanChat(arguments.join(" ")))
+            }
+        },
+    },
+    ChatCommand {
+        name: "clear",
+        usage: "/clear",
+        description: "Clear the chat log",
+        parse: |arguments| expect_no_arguments(arguments).map(|_| ChatCommandEvent::Clear),
+    },
+    ChatCommand {
+        name: "fps",
+        usage: "/fps",
+        description: "Show the current frame rate",
+        parse: |arguments| expect_no_arguments(arguments).map(|_| ChatCommandEvent::Fps),
+    },
+    ChatCommand {
+        name: "help",
+        usage: "/help [command]",
+        description: "List the client commands or show help for one command",
+        parse: |arguments| match arguments {
+            [] => Ok(ChatCommandEvent::Help(None)),
+            [name] => Ok(ChatCommandEvent::Help(Some(
+                name.trim_start_matches('/').to_string(),
+            ))),
+            [_, unexpected, ..] => Err(format!("Unexpected argument '{}'", unexpected)),
+        },
+    },
+    ChatCommand {
+        name: "pshop",
+        usage: "/pshop",
+        description: "Open the personal shop window",
+        parse: |arguments| expect_no_arguments(arguments).map(|_| ChatCommandEvent::PlayerShop),
+    },
+    ChatCommand {
+        name: "reload",
+        usage: "/reload ui",
+        description: "Reload the UI dialog files",
+        parse: |arguments| match arguments {
+            [target] if target.eq_ignore_ascii_case("ui") => Ok(ChatCommandEvent::ReloadUi),
+            [] => Err("Missing what to reload".to_string()),
+            [target, ..] => Err(format!("Cannot reload '{}'", target)),
+        },
+    },
+    ChatCommand {
+        name: "time",
+        usage: "/time",
+        description: "Show the local and zone time",
+        parse: |arguments| expect_no_arguments(arguments).map(|_| ChatCommandEvent::Time),
+    },
+    ChatCommand {
+        name: "where",
+        usage: "/where",
+        description: "Show your current zone and position",
+        parse: |arguments| expect_no_arguments(arguments).map(|_| ChatCommandEvent::Where),
+    },
+];
+
+pub fn find_chat_command(name: &str) -> Option<&'static ChatCommand> {
+    CHAT_COMMANDS
+        .iter()
+        .find(|command| command.name.eq_ignore_ascii_case(name))
+}
+
+// Returns None when the text is not a client command
+pub fn parse_chat_command(text: &str) -> Option<Result<ChatCommandEvent, String>> {
+    let mut words = text.strip_prefix('/')?.split_whitespace();
+    let command = find_chat_command(words.next()?)?;
+    let arguments = words.collect::<Vec<_>>();
+    Some(
+        (command.parse)(&arguments).map_err(|error| format!("{}, usage: {}", error, command.usage)),
+    )
+}
+
+pub const CHAT_MACRO_PLACEHOLDERS: [(&str, &str); 3] = [
+    ("%name%", "Your character name"),
+    ("%level%", "Your level"),
+    ("%zone%", "The current zone name"),
+];
+
+pub fn expand_chat_macro(text: &str, name: &str, level: u32, zone: &str) -> String {
+    text.replace("%name%", name)
+        .replace("%level%", &level.to_string())
+        .replace("%zone%", zone)
+}
+
+pub fn chat_command_help(name: Option<&str>) -> Vec<String> {
+    if let Some(name) = name {
+        return match find_chat_command(name) {
+            Some(command) => vec![format!("{} - {}", command.usage, command.description)],
+            None => vec![format!("Unknown client command '/{}'", name)],
+        };
+    }
+
+    let mut lines = vec!["Client commands:".to_string()];
+    lines.extend(
+        CHAT_COMMANDS
+            .iter()
+            .map(|command| format!("{} - {}", command.usage, command.description)),
+    );
+    lines.push("Other commands are sent to the server.".to_string());
+    lines
+}
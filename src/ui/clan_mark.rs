@@ -0,0 +1,200 @@
+use std::{num::NonZeroU16, path::Path};
+
+use bevy_egui::egui;
+
+use rose_game_common::components::ClanMark;
+
+use crate::resources::{UiResources, UiSpriteSheetType};
+
+const CLAN_MARK_PICKER_SIZE: f32 = 28.0;
+const CLAN_MARK_PICKER_COLUMNS: usize = 10;
+
+// Custom clan marks are 20x20 16 bit images, the same as the original client uploads
+pub const CUSTOM_CLAN_MARK_SIZE: u32 = 20;
+
+pub fn load_custom_clan_mark(path: &Path) -> Result<image::RgbImage, image::ImageError> {
+    let image = image::open(path)?.into_rgb8();
+    Ok(image::imageops::resize(
+        &image,
+        CUSTOM_CLAN_MARK_SIZE,
+        CUSTOM_CLAN_MARK_SIZE,
+        image::imageops::FilterType::Triangle,
+    ))
+}
+
+pub fn encode_custom_clan_mark(image: &image::RgbImage) -> Vec<u16> {
+    image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b] = pixel.0;
+            ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3)
+        })
+        .collect()
+}
+
+fn clan_mark_sprite_count(
+    ui_resources: &UiResources,
+    sprite_sheet_type: UiSpriteSheetType,
+) -> usize {
+    ui_resources.sprite_sheets[sprite_sheet_type]
+        .as_ref()
+        .map_or(0, |sprite_sheet| sprite_sheet.sprites.len())
+}
+
+fn draw_premade_clan_mark(
+    ui: &mut egui::Ui,
+    ui_resources: &UiResources,
+    rect: egui::Rect,
+    background: NonZeroU16,
+    foreground: NonZeroU16,
+) {
+    // Clan mark ids are 1-based indices into the sprite sheets
+    if let Some(sprite) = ui_resources.get_sprite_by_index(
+        UiSpriteSheetType::ClanMarkBackground,
+        background.get() as usize - 1,
+    ) {
+        sprite.draw_stretched(ui, rect);
+    }
+
+    if let Some(sprite) = ui_resources.get_sprite_by_index(
+        UiSpriteSheetType::ClanMarkForeground,
+        foreground.get() as usize - 1,
+    ) {
+        sprite.draw_stretched(ui, rect);
+    }
+}
+
+pub fn ui_add_clan_mark(
+    ui: &mut egui::Ui,
+    ui_resources: &UiResources,
+    clan_mark: &ClanMark,
+    size: f32,
+) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+
+    match *clan_mark {
+        ClanMark::Premade {
+            background,
+            foreground,
+        } => {
+            draw_premade_clan_mark(ui, ui_resources, rect, background, foreground);
+            response
+        }
+        ClanMark::Custom { .. } => {
+            // Custom marks are downloaded from the clan mark server which we do not support
+            ui.painter()
+                .rect_filled(rect, 0.0, egui::Color32::from_gray(48));
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "?",
+                egui::FontId::proportional(size * 0.6),
+                egui::Color32::GRAY,
+            );
+            response.on_hover_text("Custom clan marks are not supported")
+        }
+    }
+}
+
+fn ui_add_clan_mark_grid(
+    ui: &mut egui::Ui,
+    ui_resources: &UiResources,
+    id_source: &str,
+    count: usize,
+    selected: &mut NonZeroU16,
+    get_mark: impl Fn(NonZeroU16) -> (NonZeroU16, NonZeroU16),
+) {
+    egui::ScrollArea::vertical()
+        .id_source(id_source)
+        .max_height(CLAN_MARK_PICKER_SIZE * 4.0 + 16.0)
+        .show(ui, |ui| {
+            egui::Grid::new(id_source)
+                .spacing(egui::vec2(2.0, 2.0))
+                .show(ui, |ui| {
+                    for (index, id) in (1..=count as u16).filter_map(NonZeroU16::new).enumerate() {
+                        let (rect, response) = ui.allocate_exact_size(
+                            egui::vec2(CLAN_MARK_PICKER_SIZE, CLAN_MARK_PICKER_SIZE),
+                            egui::Sense::click(),
+                        );
+                        let (background, foreground) = get_mark(id);
+                        draw_premade_clan_mark(ui, ui_resources, rect, background, foreground);
+
+                        if *selected == id {
+                            ui.painter().rect_stroke(
+                                rect,
+                                0.0,
+                                egui::Stroke::new(2.0, egui::Color32::YELLOW),
+                            );
+                        } else if response.hovered() {
+                            ui.painter().rect_stroke(
+                                rect,
+                                0.0,
+                                egui::Stroke::new(1.0, egui::Color32::WHITE),
+                            );
+                        }
+
+                        if response.clicked() {
+                            *selected = id;
+                        }
+
+                        if (index + 1) % CLAN_MARK_PICKER_COLUMNS == 0 {
+                            ui.end_row();
+                        }
+                    }
+                });
+        });
+}
+
+// Each grid previews its options combined with the current selection from the other grid
+pub fn ui_add_clan_mark_picker(
+    ui: &mut egui::Ui,
+    ui_resources: &UiResources,
+    background: &mut NonZeroU16,
+    foreground: &mut NonZeroU16,
+) {
+    let num_backgrounds =
+        clan_mark_sprite_count(ui_resources, UiSpriteSheetType::ClanMarkBackground);
+    let num_foregrounds =
+        clan_mark_sprite_count(ui_resources, UiSpriteSheetType::ClanMarkForeground);
+
+    ui.horizontal(|ui| {
+        ui_add_clan_mark(
+            ui,
+            ui_resources,
+            &ClanMark::Premade {
+                background: *background,
+                foreground: *foreground,
+            },
+            64.0,
+        );
+
+        ui.vertical(|ui| {
+            ui.label(format!("Background {} / {}", background, num_backgrounds));
+            ui.label(format!("Symbol {} / {}", foreground, num_foregrounds));
+        });
+    });
+
+    ui.add_space(4.0);
+    ui.label("Background");
+    let current_foreground = *foreground;
+    ui_add_clan_mark_grid(
+        ui,
+        ui_resources,
+        "clan_mark_backgrounds",
+        num_backgrounds,
+        background,
+        |id| (id, current_foreground),
+    );
+
+    ui.add_space(4.0);
+    ui.label("Symbol");
+    let current_background = *background;
+    ui_add_clan_mark_grid(
+        ui,
+        ui_resources,
+        "clan_mark_foregrounds",
+        num_foregrounds,
+        foreground,
+        |id| (current_background, id),
+    );
+}
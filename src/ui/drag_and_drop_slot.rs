@@ -8,6 +8,12 @@ use crate::{
     resources::{GameData, UiResources, UiSprite, UiSpriteSheetType},
 };
 
+// Set in egui temp data when a dragged item is released over a slot which does
+// not accept it, so ui_drag_and_drop_system can play the rejected drop sound
+pub const DRAG_AND_DROP_REJECTED_ID: &str = "dnd_rejected";
+
+const REJECTED_FLASH_DURATION: f64 = 0.4;
+
 #[derive(Copy, Clone, Debug)]
 pub enum DragAndDropId {
     NotDraggable,
@@ -302,7 +308,12 @@ fn generate_cooldown_mesh(cooldown: f32, content_rect: egui::Rect) -> egui::epai
 }
 
 impl<'w> DragAndDropSlot<'w> {
-    pub fn draw(&self, ui: &mut egui::Ui, accepts_dragged_item: bool) -> (bool, egui::Response) {
+    pub fn draw(
+        &self,
+        ui: &mut egui::Ui,
+        dragging: bool,
+        accepts_dragged_item: bool,
+    ) -> (bool, bool, egui::Response) {
         let (rect, response) = ui.allocate_exact_size(
             self.size,
             if self.sprite.is_some() && !matches!(self.dnd_id, DragAndDropId::NotDraggable) {
@@ -312,6 +323,7 @@ impl<'w> DragAndDropSlot<'w> {
             },
         );
         let mut dropped = false;
+        let mut rejected = false;
 
         if ui.is_rect_visible(rect) {
             use egui::epaint::*;
@@ -322,18 +334,23 @@ impl<'w> DragAndDropSlot<'w> {
                     .pointer
                     .interact_pos()
                     .map_or(false, |cursor_pos| rect.contains(cursor_pos));
+                let released = input.pointer.any_released()
+                    && !input.pointer.button_down(egui::PointerButton::Primary);
 
                 if accepts_dragged_item && hovered {
-                    if input.pointer.any_released()
-                        && !input.pointer.button_down(egui::PointerButton::Primary)
-                    {
+                    if released {
                         dropped = true;
                     }
                     true
                 } else {
+                    // Releasing an item back onto the slot it was dragged from is not an error
+                    if dragging && hovered && released && !response.drag_released() {
+                        rejected = true;
+                    }
                     false
                 }
             });
+            let is_dragged = response.dragged_by(egui::PointerButton::Primary);
 
             if let Some(sprite) = self.sprite.as_ref() {
                 let content_rect = rect;
@@ -341,10 +358,12 @@ impl<'w> DragAndDropSlot<'w> {
                 mesh.add_rect_with_uv(
                     content_rect,
                     sprite.uv,
-                    if !self.broken {
-                        egui::Color32::WHITE
-                    } else {
+                    if self.broken {
                         egui::Color32::LIGHT_RED
+                    } else if is_dragged {
+                        egui::Color32::GRAY
+                    } else {
+                        egui::Color32::WHITE
                     },
                 );
                 ui.painter().add(Shape::mesh(mesh));
@@ -407,28 +426,67 @@ impl<'w> DragAndDropSlot<'w> {
                     ));
                 }
 
-                if response.dragged_by(egui::PointerButton::Primary) {
+                // Draw a semi-transparent ghost of the dragged icon under the cursor
+                if is_dragged {
                     if let Some(pointer_pos) = response.interact_pointer_pos() {
-                        if !response.rect.contains(pointer_pos) {
-                            let tooltip_painter = ui.ctx().layer_painter(egui::LayerId::new(
-                                egui::Order::Tooltip,
-                                egui::Id::new("dnd_tooltip"),
-                            ));
-                            let mut tooltip_mesh =
-                                egui::epaint::Mesh::with_texture(sprite.texture_id);
-                            tooltip_mesh.add_rect_with_uv(
-                                response
-                                    .rect
-                                    .translate(pointer_pos - response.rect.center()),
-                                sprite.uv,
-                                egui::Color32::WHITE,
-                            );
-                            tooltip_painter.add(egui::epaint::Shape::mesh(tooltip_mesh));
-                        }
+                        let tooltip_painter = ui.ctx().layer_painter(egui::LayerId::new(
+                            egui::Order::Tooltip,
+                            egui::Id::new("dnd_tooltip"),
+                        ));
+                        let mut tooltip_mesh = egui::epaint::Mesh::with_texture(sprite.texture_id);
+                        tooltip_mesh.add_rect_with_uv(
+                            response
+                                .rect
+                                .translate(pointer_pos - response.rect.center()),
+                            sprite.uv,
+                            egui::Color32::from_white_alpha(160),
+                        );
+                        tooltip_painter.add(egui::epaint::Shape::mesh(tooltip_mesh));
                     }
                 }
             }
 
+            if accepts_dragged_item && !is_active {
+                ui.painter().add(egui::Shape::Rect(egui::epaint::RectShape {
+                    rect: rect.shrink(self.border_width),
+                    rounding: egui::Rounding::none(),
+                    fill: Default::default(),
+                    stroke: egui::Stroke {
+                        width: self.border_width,
+                        color: egui::Color32::from_rgba_unmultiplied(120, 200, 120, 160),
+                    },
+                }));
+            }
+
+            // Highlight the slot selected with keyboard or gamepad navigation
+            if response.has_focus() {
+                ui.painter().rect_stroke(
+                    rect,
+                    egui::Rounding::same(2.0),
+                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 220, 120)),
+                );
+            }
+
+            let flash_id = response.id.with(DRAG_AND_DROP_REJECTED_ID);
+            let time = ui.ctx().input(|input| input.time);
+            if rejected {
+                ui.ctx().data_mut(|data| data.insert_temp(flash_id, time));
+            }
+
+            if let Some(flash_time) = ui.ctx().data_mut(|data| data.get_temp::<f64>(flash_id)) {
+                let flash = 1.0 - (time - flash_time) / REJECTED_FLASH_DURATION;
+                if flash > 0.0 {
+                    ui.painter().rect_filled(
+                        rect,
+                        egui::Rounding::none(),
+                        egui::Color32::from_rgba_unmultiplied(255, 0, 0, (flash * 120.0) as u8),
+                    );
+                    ui.ctx().request_repaint();
+                } else {
+                    ui.ctx().data_mut(|data| data.remove::<f64>(flash_id));
+                }
+            }
+
             if is_active {
                 ui.painter().add(egui::Shape::Rect(egui::epaint::RectShape {
                     rect: rect.shrink(self.border_width),
@@ -441,7 +499,7 @@ impl<'w> DragAndDropSlot<'w> {
                 }));
             }
         }
-        (dropped, response)
+        (dropped, rejected, response)
     }
 }
 
@@ -455,13 +513,17 @@ impl<'w> egui::Widget for DragAndDropSlot<'w> {
             .map(|dnd_id| (self.accepts)(dnd_id))
             .unwrap_or(false);
 
-        let (dropped, mut response) = self.draw(ui, accepts_dragged_item);
+        let (dropped, rejected, mut response) =
+            self.draw(ui, dragged_item.is_some(), accepts_dragged_item);
 
         if response.dragged_by(egui::PointerButton::Primary) {
             *dragged_item = Some(dnd_id);
         } else if dropped {
             *dropped_item = dragged_item.take();
             response.mark_changed();
+        } else if rejected {
+            ui.ctx()
+                .data_mut(|data| data.insert_temp(egui::Id::new(DRAG_AND_DROP_REJECTED_ID), true));
         }
 
         response
@@ -0,0 +1,211 @@
+use std::{sync::OnceLock, time::Duration};
+
+// Separators used when displaying numbers, chosen from the system locale
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumberLocale {
+    pub group_separator: char,
+    pub decimal_separator: char,
+}
+
+impl NumberLocale {
+    pub const ENGLISH: NumberLocale = NumberLocale {
+        group_separator: ',',
+        decimal_separator: '.',
+    };
+
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(['_', '-', '.']).next().unwrap_or_default();
+
+        match language {
+            "da" | "de" | "es" | "id" | "it" | "nl" | "pt" | "tr" => NumberLocale {
+                group_separator: '.',
+                decimal_separator: ',',
+            },
+            "cs" | "fi" | "fr" | "hu" | "nb" | "pl" | "ru" | "sv" | "uk" => NumberLocale {
+                group_separator: '\u{a0}',
+                decimal_separator: ',',
+            },
+            _ => NumberLocale::ENGLISH,
+        }
+    }
+
+    pub fn current() -> Self {
+        static LOCALE: OnceLock<NumberLocale> = OnceLock::new();
+        *LOCALE.get_or_init(NumberLocale::from_env)
+    }
+}
+
+fn group_digits(digits: &str, group_separator: char) -> String {
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (index, ch) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            formatted.push(group_separator);
+        }
+
+        formatted.push(ch);
+    }
+
+    formatted
+}
+
+// Formats a number with thousands separators, e.g. 1,250,000
+pub fn format_number(value: u64) -> String {
+    group_digits(&value.to_string(), NumberLocale::current().group_separator)
+}
+
+pub fn format_signed_number(value: i64) -> String {
+    if value < 0 {
+        format!("-{}", format_number(value.unsigned_abs()))
+    } else {
+        format_number(value as u64)
+    }
+}
+
+fn format_decimal_with_locale(value: f64, decimals: usize, locale: NumberLocale) -> String {
+    let text = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let mut formatted = String::with_capacity(text.len() + 8);
+    if value < 0.0 && text.chars().any(|ch| ch.is_ascii_digit() && ch != '0') {
+        formatted.push('-');
+    }
+    formatted.push_str(&group_digits(integer, locale.group_separator));
+    if !fraction.is_empty() {
+        formatted.push(locale.decimal_separator);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    format_decimal_with_locale(value, decimals, NumberLocale::current())
+}
+
+fn format_percent_with_locale(value: f32, locale: NumberLocale) -> String {
+    // Round to one decimal place first, so values such as 12.96 are shown as 13%
+    let tenths = (value as f64 * 10.0).round();
+    let decimals = if tenths % 10.0 == 0.0 { 0 } else { 1 };
+    format!(
+        "{}%",
+        format_decimal_with_locale(tenths / 10.0, decimals, locale)
+    )
+}
+
+// Formats a value which is already a percentage, with a single decimal place
+// only when it is not a whole number, e.g. 45% or 12.5%
+pub fn format_percent(value: f32) -> String {
+    format_percent_with_locale(value, NumberLocale::current())
+}
+
+// Formats a duration using its two largest units, e.g. 1h 05m or 45s
+pub fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let days = total_seconds / 86400;
+    let hours = (total_seconds / 3600) % 24;
+    let minutes = (total_seconds / 60) % 60;
+    let seconds = total_seconds % 60;
+
+    if days > 0 {
+        format!("{}d {:02}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+// Formats how long ago something happened, e.g. 5 minutes ago
+pub fn format_relative_time(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    let (value, unit) = if seconds < 60 {
+        return "just now".to_string();
+    } else if seconds < 3600 {
+        (seconds / 60, "minute")
+    } else if seconds < 86400 {
+        (seconds / 3600, "hour")
+    } else {
+        (seconds / 86400, "day")
+    };
+
+    if value == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", format_number(value), unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: NumberLocale = NumberLocale {
+        group_separator: '.',
+        decimal_separator: ',',
+    };
+
+    #[test]
+    fn group_digits_inserts_separators() {
+        assert_eq!(group_digits("0", ','), "0");
+        assert_eq!(group_digits("999", ','), "999");
+        assert_eq!(group_digits("1000", ','), "1,000");
+        assert_eq!(group_digits("1250000", ','), "1,250,000");
+        assert_eq!(group_digits("1250000", '.'), "1.250.000");
+    }
+
+    #[test]
+    fn format_decimal_uses_locale() {
+        let english = NumberLocale::ENGLISH;
+        assert_eq!(format_decimal_with_locale(1234.5, 1, english), "1,234.5");
+        assert_eq!(format_decimal_with_locale(1234.5, 1, GERMAN), "1.234,5");
+        assert_eq!(format_decimal_with_locale(-1234.0, 0, english), "-1,234");
+        assert_eq!(format_decimal_with_locale(-0.01, 1, english), "0.0");
+    }
+
+    #[test]
+    fn format_percent_trims_whole_numbers() {
+        let english = NumberLocale::ENGLISH;
+        assert_eq!(format_percent_with_locale(45.0, english), "45%");
+        assert_eq!(format_percent_with_locale(12.5, english), "12.5%");
+        assert_eq!(format_percent_with_locale(12.96, english), "13%");
+        assert_eq!(format_percent_with_locale(12.04, english), "12%");
+        assert_eq!(format_percent_with_locale(12.06, english), "12.1%");
+        assert_eq!(format_percent_with_locale(12.5, GERMAN), "12,5%");
+    }
+
+    #[test]
+    fn format_duration_uses_two_largest_units() {
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 05s");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h 05m");
+        assert_eq!(format_duration(Duration::from_secs(90000)), "1d 01h");
+    }
+
+    #[test]
+    fn format_relative_time_pluralises_units() {
+        assert_eq!(format_relative_time(Duration::from_secs(30)), "just now");
+        assert_eq!(
+            format_relative_time(Duration::from_secs(60)),
+            "1 minute ago"
+        );
+        assert_eq!(
+            format_relative_time(Duration::from_secs(300)),
+            "5 minutes ago"
+        );
+        assert_eq!(
+            format_relative_time(Duration::from_secs(7200)),
+            "2 hours ago"
+        );
+        assert_eq!(
+            format_relative_time(Duration::from_secs(86400)),
+            "1 day ago"
+        );
+    }
+}
@@ -1,15 +1,31 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Resource;
+
+mod chat_commands;
+mod clan_mark;
 mod dialog_loader;
 mod drag_and_drop_slot;
+pub mod format;
+mod skin;
+mod tooltip_controller;
 mod tooltips;
+mod ui_accessibility_system;
 mod ui_bank_system;
 mod ui_character_create_system;
 mod ui_character_info_system;
 mod ui_character_select_name_tag_system;
 mod ui_character_select_system;
+mod ui_character_stats_system;
+mod ui_chat_bubble_system;
 mod ui_chatbox_system;
 mod ui_clan_invite_system;
 mod ui_clan_system;
+mod ui_compass_system;
+mod ui_connection_error_system;
 mod ui_create_clan;
+mod ui_data_integrity_system;
+mod ui_data_profile_select_system;
 mod ui_debug_camera_info_system;
 mod ui_debug_client_entity_list_system;
 mod ui_debug_command_viewer_system;
@@ -22,74 +38,177 @@ mod ui_debug_npc_list_system;
 mod ui_debug_physics;
 mod ui_debug_render_system;
 mod ui_debug_skill_list_system;
+mod ui_debug_unsupported_packets_system;
 mod ui_debug_window_system;
 mod ui_debug_zone_lighting_system;
 mod ui_debug_zone_list_system;
 mod ui_debug_zone_time_system;
 mod ui_drag_and_drop_system;
+mod ui_drop_item_system;
+mod ui_escape_menu_system;
+mod ui_focus_navigation_system;
 mod ui_game_menu_system;
+mod ui_growth_object_system;
 mod ui_hotbar_system;
+mod ui_idle_warning_system;
+mod ui_input_display_system;
+mod ui_interact_prompt_system;
 mod ui_inventory_system;
 mod ui_item_browser_system;
 mod ui_item_drop_name_system;
+mod ui_login_challenge_system;
+mod ui_login_queue_system;
 mod ui_login_system;
+mod ui_macros_system;
 mod ui_message_box_system;
 mod ui_minimap_system;
+mod ui_mount_system;
+mod ui_movement_warning_system;
 mod ui_npc_store_system;
 mod ui_number_input_dialog_system;
+mod ui_party_marker_system;
 mod ui_party_option_system;
 mod ui_party_system;
 mod ui_personal_store_system;
 mod ui_player_info_system;
 mod ui_player_shop_system;
 mod ui_quest_list_system;
+mod ui_replay_viewer_system;
+mod ui_report_player_system;
 mod ui_respawn_system;
+mod ui_resurrection_system;
+mod ui_script_manager_system;
 mod ui_selected_target_system;
 mod ui_server_select_system;
 mod ui_settings_system;
 mod ui_skill_list_system;
 mod ui_skill_tree_system;
+mod ui_social_system;
 mod ui_sound_event_system;
 mod ui_status_effects_system;
+mod ui_store_low_power_system;
+mod ui_subtitle_system;
+mod ui_summon_system;
+mod ui_texture_memory_overlay_system;
+mod ui_union_system;
+mod ui_vehicle_garage_system;
+mod ui_warp_gate_system;
 mod ui_window_sound_system;
+mod ui_zone_data_warning_system;
+mod ui_zone_title_system;
+mod ui_zone_transition_system;
 pub mod widgets;
+mod zuly_input;
 
-#[derive(Default, Resource)]
-pub struct UiStateWindows {
-    pub character_info_open: bool,
-    pub clan_open: bool,
-    pub inventory_open: bool,
-    pub skill_list_open: bool,
-    pub skill_tree_open: bool,
-    pub quest_list_open: bool,
-    pub settings_open: bool,
-    pub menu_open: bool,
-    pub party_open: bool,
-    pub party_options_open: bool,
-    pub item_browser_open: bool,
-    pub player_shop_open: bool,
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UiWindowId(pub &'static str);
+
+impl UiWindowId {
+    pub const CHARACTER_INFO: Self = Self("character_info");
+    pub const CHARACTER_STATS: Self = Self("character_stats");
+    pub const CLAN: Self = Self("clan");
+    pub const INVENTORY: Self = Self("inventory");
+    pub const SKILL_LIST: Self = Self("skill_list");
+    pub const SKILL_TREE: Self = Self("skill_tree");
+    pub const QUEST_LIST: Self = Self("quest_list");
+    pub const SETTINGS: Self = Self("settings");
+    pub const MENU: Self = Self("menu");
+    pub const PARTY: Self = Self("party");
+    pub const PARTY_OPTIONS: Self = Self("party_options");
+    pub const ITEM_BROWSER: Self = Self("item_browser");
+    pub const PLAYER_SHOP: Self = Self("player_shop");
+    pub const SCRIPT_MANAGER: Self = Self("script_manager");
+    pub const SOCIAL: Self = Self("social");
+    pub const UNION: Self = Self("union");
+    pub const VEHICLE_GARAGE: Self = Self("vehicle_garage");
+    pub const MACROS: Self = Self("macros");
+    pub const ESCAPE_MENU: Self = Self("escape_menu");
 
     // Below are only opened via in game events rather than directly
-    pub bank_open: bool,
-    pub create_clan_open: bool,
+    pub const BANK: Self = Self("bank");
+    pub const CREATE_CLAN: Self = Self("create_clan");
+    pub const REPORT_PLAYER: Self = Self("report_player");
 
     // Test ui
-    pub selected_target_ui_open: bool,
+    pub const SELECTED_TARGET_UI: Self = Self("selected_target_ui");
+
+    // Windows which the player can open directly, e.g. from the game menu
+    pub const PLAYER_WINDOWS: [Self; 15] = [
+        Self::CHARACTER_INFO,
+        Self::CHARACTER_STATS,
+        Self::CLAN,
+        Self::INVENTORY,
+        Self::SKILL_LIST,
+        Self::SKILL_TREE,
+        Self::QUEST_LIST,
+        Self::SETTINGS,
+        Self::PARTY,
+        Self::PLAYER_SHOP,
+        Self::SCRIPT_MANAGER,
+        Self::SOCIAL,
+        Self::UNION,
+        Self::VEHICLE_GARAGE,
+        Self::MACROS,
+    ];
 }
 
-use bevy::prelude::Resource;
+// Windows are identified by UiWindowId so plugins can add their own windows
+// without needing to add a field here
+#[derive(Default, Resource)]
+pub struct UiStateWindows {
+    open_windows: HashMap<UiWindowId, bool>,
+}
+
+impl UiStateWindows {
+    pub fn is_open(&self, id: UiWindowId) -> bool {
+        self.open_windows.get(&id).copied().unwrap_or(false)
+    }
+
+    pub fn set_open(&mut self, id: UiWindowId, open: bool) {
+        self.open_windows.insert(id, open);
+    }
+
+    pub fn toggle(&mut self, id: UiWindowId) {
+        let open = self.open_mut(id);
+        *open = !*open;
+    }
+
+    pub fn open_mut(&mut self, id: UiWindowId) -> &mut bool {
+        self.open_windows.entry(id).or_default()
+    }
+}
+
+pub use chat_commands::{
+    chat_command_help, expand_chat_macro, find_chat_command, parse_chat_command, ChatCommand,
+    CHAT_COMMANDS, CHAT_MACRO_PLACEHOLDERS,
+};
+pub use clan_mark::{
+    encode_custom_clan_mark, load_custom_clan_mark, ui_add_clan_mark, ui_add_clan_mark_picker,
+    CUSTOM_CLAN_MARK_SIZE,
+};
 pub use dialog_loader::{load_dialog_sprites_system, DialogInstance, DialogLoader};
-pub use drag_and_drop_slot::{DragAndDropId, DragAndDropSlot};
+pub use drag_and_drop_slot::{DragAndDropId, DragAndDropSlot, DRAG_AND_DROP_REJECTED_ID};
+pub use skin::{UiSkin, SKIN_WINDOW_MARGIN};
+pub use tooltip_controller::{
+    set_tooltip_summary, take_tooltip_summary, ui_tooltip_settings_system, TooltipExt,
+};
 pub use tooltips::{get_item_name_color, ui_add_item_tooltip, ui_add_skill_tooltip};
+pub use ui_accessibility_system::ui_accessibility_system;
 pub use ui_bank_system::ui_bank_system;
 pub use ui_character_create_system::ui_character_create_system;
 pub use ui_character_info_system::ui_character_info_system;
 pub use ui_character_select_name_tag_system::ui_character_select_name_tag_system;
 pub use ui_character_select_system::ui_character_select_system;
-pub use ui_chatbox_system::ui_chatbox_system;
+pub use ui_character_stats_system::ui_character_stats_system;
+pub use ui_chat_bubble_system::ui_chat_bubble_system;
+pub use ui_chatbox_system::{set_chatbox_text, ui_chatbox_system};
 pub use ui_clan_invite_system::ui_clan_invite_system;
-pub use ui_clan_system::ui_clan_system;
+pub use ui_clan_system::{clan_position_name, ui_clan_system};
+pub use ui_compass_system::ui_compass_system;
+pub use ui_connection_error_system::ui_connection_error_system;
 pub use ui_create_clan::ui_create_clan_system;
+pub use ui_data_integrity_system::ui_data_integrity_system;
+pub use ui_data_profile_select_system::ui_data_profile_select_system;
 pub use ui_debug_camera_info_system::ui_debug_camera_info_system;
 pub use ui_debug_client_entity_list_system::ui_debug_client_entity_list_system;
 pub use ui_debug_command_viewer_system::ui_debug_command_viewer_system;
@@ -102,34 +221,66 @@ pub use ui_debug_npc_list_system::ui_debug_npc_list_system;
 pub use ui_debug_physics::ui_debug_physics_system;
 pub use ui_debug_render_system::ui_debug_render_system;
 pub use ui_debug_skill_list_system::ui_debug_skill_list_system;
+pub use ui_debug_unsupported_packets_system::ui_debug_unsupported_packets_system;
 pub use ui_debug_window_system::{ui_debug_menu_system, UiStateDebugWindows};
 pub use ui_debug_zone_lighting_system::ui_debug_zone_lighting_system;
 pub use ui_debug_zone_list_system::ui_debug_zone_list_system;
 pub use ui_debug_zone_time_system::ui_debug_zone_time_system;
 pub use ui_drag_and_drop_system::{ui_drag_and_drop_system, UiStateDragAndDrop};
+pub use ui_drop_item_system::ui_drop_item_system;
+pub use ui_escape_menu_system::ui_escape_menu_system;
+pub use ui_focus_navigation_system::{
+    ui_focus_navigation_system, ui_focus_navigation_text_input_system, UiFocusNavigation,
+};
 pub use ui_game_menu_system::ui_game_menu_system;
+pub use ui_growth_object_system::ui_growth_object_system;
 pub use ui_hotbar_system::ui_hotbar_system;
+pub use ui_idle_warning_system::ui_idle_warning_system;
+pub use ui_input_display_system::ui_input_display_system;
+pub use ui_interact_prompt_system::ui_interact_prompt_system;
 pub use ui_inventory_system::ui_inventory_system;
 pub use ui_item_browser_system::ui_item_browser_system;
 pub use ui_item_drop_name_system::ui_item_drop_name_system;
+pub use ui_login_challenge_system::ui_login_challenge_system;
+pub use ui_login_queue_system::ui_login_queue_system;
 pub use ui_login_system::ui_login_system;
+pub use ui_macros_system::ui_macros_system;
 pub use ui_message_box_system::ui_message_box_system;
 pub use ui_minimap_system::ui_minimap_system;
+pub use ui_mount_system::ui_mount_system;
+pub use ui_movement_warning_system::ui_movement_warning_system;
 pub use ui_npc_store_system::ui_npc_store_system;
 pub use ui_number_input_dialog_system::ui_number_input_dialog_system;
+pub use ui_party_marker_system::ui_party_marker_system;
 pub use ui_party_option_system::ui_party_option_system;
-pub use ui_party_system::ui_party_system;
+pub use ui_party_system::{get_party_member_color, paint_party_member_diamond, ui_party_system};
 pub use ui_personal_store_system::ui_personal_store_system;
 pub use ui_player_info_system::ui_player_info_system;
 pub use ui_player_shop_system::ui_player_shop_system;
-pub use ui_quest_list_system::ui_quest_list_system;
+pub use ui_quest_list_system::{set_quest_list_selection, ui_quest_list_system};
+pub use ui_replay_viewer_system::ui_replay_viewer_system;
+pub use ui_report_player_system::ui_report_player_system;
 pub use ui_respawn_system::ui_respawn_system;
+pub use ui_resurrection_system::ui_resurrection_system;
+pub use ui_script_manager_system::ui_script_manager_system;
 pub use ui_selected_target_system::ui_selected_target_system;
 pub use ui_server_select_system::ui_server_select_system;
 pub use ui_settings_system::ui_settings_system;
 pub use ui_skill_list_system::ui_skill_list_system;
 pub use ui_skill_tree_system::ui_skill_tree_system;
+pub use ui_social_system::ui_social_system;
 pub use ui_sound_event_system::{ui_sound_event_system, UiSoundEvent};
 pub use ui_status_effects_system::ui_status_effects_system;
+pub use ui_store_low_power_system::ui_store_low_power_system;
+pub use ui_subtitle_system::{set_dialog_subtitle, ui_subtitle_system};
+pub use ui_summon_system::ui_summon_system;
+pub use ui_texture_memory_overlay_system::ui_texture_memory_overlay_system;
+pub use ui_union_system::ui_union_system;
+pub use ui_vehicle_garage_system::ui_vehicle_garage_system;
+pub use ui_warp_gate_system::ui_warp_gate_system;
 pub use ui_window_sound_system::ui_window_sound_system;
+pub use ui_zone_data_warning_system::ui_zone_data_warning_system;
+pub use ui_zone_title_system::ui_zone_title_system;
+pub use ui_zone_transition_system::ui_zone_transition_system;
 pub use widgets::DataBindings;
+pub use zuly_input::ZulyInput;
@@ -0,0 +1,225 @@
+use bevy::prelude::Assets;
+use bevy_egui::egui::{
+    self,
+    scroll_area::{ScrollAreaOutput, ScrollBarVisibility},
+};
+
+use crate::{
+    resources::{UiResources, UiSprite},
+    ui::widgets::{Dialog, Widget},
+};
+
+// The message box has the most generic frame and button art, so it is used to
+// skin windows which are laid out with egui
+const IID_IMAGE_TOP: i32 = 5;
+const IID_IMAGE_MIDDLE: i32 = 6;
+const IID_IMAGE_BOTTOM: i32 = 7;
+const IID_BUTTON_OK: i32 = 255;
+
+// The message box has no scrollbar, the quest list scrollbar is used instead
+const IID_QUEST_LIST_SCROLLBAR: i32 = 21;
+
+// Width of the sprite edges which are not stretched
+const FRAME_BORDER: f32 = 12.0;
+const BUTTON_BORDER: f32 = 6.0;
+
+pub const SKIN_WINDOW_MARGIN: f32 = 10.0;
+
+const SKIN_TITLE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 140);
+const SKIN_WIDGET_COLOR: egui::Color32 = egui::Color32::from_rgb(92, 64, 38);
+const SKIN_WIDGET_HOVER_COLOR: egui::Color32 = egui::Color32::from_rgb(134, 94, 52);
+const SKIN_WIDGET_ACTIVE_COLOR: egui::Color32 = egui::Color32::from_rgb(176, 126, 64);
+
+pub struct UiSkin<'a> {
+    frame_top: &'a UiSprite,
+    frame_middle: &'a UiSprite,
+    frame_bottom: &'a UiSprite,
+    button_normal: Option<&'a UiSprite>,
+    button_over: Option<&'a UiSprite>,
+    button_down: Option<&'a UiSprite>,
+    scroll_thumb: Option<&'a UiSprite>,
+}
+
+impl<'a> UiSkin<'a> {
+    pub fn new(ui_resources: &UiResources, dialog_assets: &'a Assets<Dialog>) -> Option<Self> {
+        let dialog = dialog_assets.get(&ui_resources.dialog_message_box)?;
+        let get_image_sprite = |id| match dialog.get_widget(id) {
+            Some(Widget::Image(image)) => image.sprite.as_ref(),
+            _ => None,
+        };
+        let button = match dialog.get_widget(IID_BUTTON_OK) {
+            Some(Widget::Button(button)) => Some(button),
+            _ => None,
+        };
+        let scroll_thumb = dialog_assets
+            .get(&ui_resources.dialog_quest_list)
+            .and_then(|dialog| match dialog.get_widget(IID_QUEST_LIST_SCROLLBAR) {
+                Some(Widget::Scrollbar(scrollbar)) => scrollbar.scrollbox.as_ref(),
+                _ => None,
+            })
+            .and_then(|scrollbox| scrollbox.sprite.as_ref());
+
+        Some(Self {
+            frame_top: get_image_sprite(IID_IMAGE_TOP)?,
+            frame_middle: get_image_sprite(IID_IMAGE_MIDDLE)?,
+            frame_bottom: get_image_sprite(IID_IMAGE_BOTTOM)?,
+            button_normal: button.and_then(|button| button.normal_sprite.as_ref()),
+            button_over: button.and_then(|button| button.over_sprite.as_ref()),
+            button_down: button.and_then(|button| button.down_sprite.as_ref()),
+            scroll_thumb,
+        })
+    }
+
+    // Windows using the skin should use this frame and disable the egui title bar
+    pub fn window_frame(&self) -> egui::Frame {
+        egui::Frame::none().inner_margin(egui::Margin::same(SKIN_WINDOW_MARGIN))
+    }
+
+    // Paints the window frame and applies the skin colours, call before adding
+    // any contents to the window
+    pub fn begin_window(&self, ui: &mut egui::Ui) {
+        let rect = ui.max_rect().expand(SKIN_WINDOW_MARGIN);
+        let painter = ui.painter().with_clip_rect(rect);
+        let border = egui::Margin::symmetric(FRAME_BORDER, 0.0);
+
+        let top_height = self.frame_top.height.min(rect.height() / 2.0);
+        let bottom_height = self
+            .frame_bottom
+            .height
+            .min(rect.height() - top_height)
+            .max(0.0);
+        let top_rect = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), top_height));
+        let bottom_rect =
+            egui::Rect::from_min_max(egui::pos2(rect.min.x, rect.max.y - bottom_height), rect.max);
+        let middle_rect = egui::Rect::from_min_max(
+            egui::pos2(rect.min.x, top_rect.max.y),
+            egui::pos2(rect.max.x, bottom_rect.min.y),
+        );
+
+        painter.add(self.frame_middle.nine_slice(middle_rect, border));
+        painter.add(self.frame_top.nine_slice(top_rect, border));
+        painter.add(self.frame_bottom.nine_slice(bottom_rect, border));
+
+        self.apply_style(ui);
+    }
+
+    // Applies the skin colours to egui widgets, for windows which already have a
+    // frame such as those drawn from a dialog
+    pub fn apply_style(&self, ui: &mut egui::Ui) {
+        let visuals = &mut ui.style_mut().visuals;
+        visuals.override_text_color = Some(egui::Color32::WHITE);
+        visuals.selection.bg_fill = SKIN_WIDGET_ACTIVE_COLOR;
+        visuals.widgets.inactive.bg_fill = SKIN_WIDGET_COLOR;
+        visuals.widgets.inactive.weak_bg_fill = SKIN_WIDGET_COLOR;
+        visuals.widgets.hovered.bg_fill = SKIN_WIDGET_HOVER_COLOR;
+        visuals.widgets.hovered.weak_bg_fill = SKIN_WIDGET_HOVER_COLOR;
+        visuals.widgets.active.bg_fill = SKIN_WIDGET_ACTIVE_COLOR;
+        visuals.widgets.active.weak_bg_fill = SKIN_WIDGET_ACTIVE_COLOR;
+        visuals.extreme_bg_color = egui::Color32::from_black_alpha(160);
+    }
+
+    // Replacement for the egui title bar, returns true when close is clicked
+    pub fn title_bar(&self, ui: &mut egui::Ui, title: &str) -> bool {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(title)
+                    .color(SKIN_TITLE_COLOR)
+                    .strong()
+                    .size(15.0),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                self.button(ui, "X").clicked()
+            })
+            .inner
+        })
+        .inner
+    }
+
+    pub fn button(&self, ui: &mut egui::Ui, text: impl Into<egui::WidgetText>) -> egui::Response {
+        let Some(button_normal) = self.button_normal else {
+            return ui.button(text);
+        };
+
+        // Reserve a shape behind the text to paint the sprite once the state is known
+        let background = ui.painter().add(egui::Shape::Noop);
+        let response = ui.add(
+            egui::Button::new(text)
+                .frame(false)
+                .min_size(egui::vec2(button_normal.height, button_normal.height)),
+        );
+
+        let sprite = if response.is_pointer_button_down_on() {
+            self.button_down
+        } else if response.hovered() {
+            self.button_over
+        } else {
+            None
+        }
+        .unwrap_or(button_normal);
+        ui.painter().set(
+            background,
+            sprite.nine_slice(response.rect, egui::Margin::same(BUTTON_BORDER)),
+        );
+
+        response
+    }
+
+    // Shows a scroll area with the scrollbar drawn from the thumb sprite in a
+    // gutter to the right of the contents, add_contents must call show on the
+    // given scroll area
+    pub fn scroll_area<R>(
+        &self,
+        ui: &mut egui::Ui,
+        scroll_area: egui::ScrollArea,
+        add_contents: impl FnOnce(egui::ScrollArea, &mut egui::Ui) -> ScrollAreaOutput<R>,
+    ) -> R {
+        let Some(scroll_thumb) = self.scroll_thumb else {
+            return add_contents(scroll_area, ui).inner;
+        };
+
+        ui.horizontal_top(|ui| {
+            let gutter_width = scroll_thumb.width;
+            let output = ui
+                .allocate_ui(
+                    egui::vec2(
+                        (ui.available_width() - gutter_width).max(0.0),
+                        ui.available_height(),
+                    ),
+                    |ui| {
+                        add_contents(
+                            scroll_area.scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden),
+                            ui,
+                        )
+                    },
+                )
+                .inner;
+
+            let gutter_rect = egui::Rect::from_min_size(
+                output.inner_rect.right_top(),
+                egui::vec2(gutter_width, output.inner_rect.height()),
+            );
+            let response = ui.allocate_rect(gutter_rect, egui::Sense::click_and_drag());
+            ui.painter()
+                .rect_filled(gutter_rect, 0.0, ui.visuals().extreme_bg_color);
+
+            let max_offset = (output.content_size.y - output.inner_rect.height()).max(0.0);
+            if max_offset > 0.0 {
+                let track_height = (gutter_rect.height() - scroll_thumb.height).max(0.0);
+                let mut state = output.state;
+
+                if let Some(pointer_pos) = response.interact_pointer_pos() {
+                    let pos = (pointer_pos.y - gutter_rect.top() - scroll_thumb.height / 2.0)
+                        .clamp(0.0, track_height);
+                    state.offset.y = max_offset * pos / track_height.max(1.0);
+                    state.store(ui.ctx(), output.id);
+                }
+
+                let thumb_y = track_height * (state.offset.y / max_offset).clamp(0.0, 1.0);
+                scroll_thumb.draw(ui, gutter_rect.min + egui::vec2(0.0, thumb_y));
+            }
+
+            output.inner
+        })
+        .inner
+    }
+}
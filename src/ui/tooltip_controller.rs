@@ -0,0 +1,158 @@
+use bevy::prelude::Res;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::ClientSettings;
+
+const TOOLTIP_DELAY_ID: &str = "tooltip_show_delay";
+const DEFAULT_TOOLTIP_DELAY: f32 = 0.3;
+const TOOLTIP_SUMMARY_ID: &str = "tooltip_summary";
+
+// Gap between the hovered widget and its tooltip
+const TOOLTIP_ANCHOR_GAP: f32 = 6.0;
+
+// Shows tooltips next to the hovered widget after the configured delay, instead
+// of following the cursor like the egui tooltips
+pub trait TooltipExt {
+    fn on_hover_tooltip_ui(self, add_contents: impl FnOnce(&mut egui::Ui)) -> Self;
+    fn on_hover_tooltip_text(self, text: impl Into<egui::WidgetText>) -> Self;
+}
+
+impl TooltipExt for egui::Response {
+    fn on_hover_tooltip_ui(self, add_contents: impl FnOnce(&mut egui::Ui)) -> Self {
+        let hover_start_id = self.id.with("tooltip_hover_start");
+
+        // Disabled widgets are never hovered, but still explain why they are disabled
+        let hovered = self.hovered()
+            || (!self.enabled && self.ctx.rect_contains_pointer(self.layer_id, self.rect));
+        if !hovered || self.ctx.memory(|mem| mem.is_anything_being_dragged()) {
+            self.ctx.data_mut(|data| data.remove::<f64>(hover_start_id));
+            return self;
+        }
+
+        let time = self.ctx.input(|input| input.time);
+        let hover_start = self
+            .ctx
+            .data_mut(|data| *data.get_temp_mut_or_insert_with(hover_start_id, || time));
+        let show_delay = self
+            .ctx
+            .data(|data| data.get_temp::<f32>(egui::Id::new(TOOLTIP_DELAY_ID)))
+            .unwrap_or(DEFAULT_TOOLTIP_DELAY);
+
+        if time - hover_start < show_delay as f64 {
+            self.ctx.request_repaint();
+            return self;
+        }
+
+        show_anchored_tooltip(&self.ctx, self.id.with("tooltip"), self.rect, add_contents);
+        self
+    }
+
+    fn on_hover_tooltip_text(self, text: impl Into<egui::WidgetText>) -> Self {
+        self.on_hover_tooltip_ui(|ui| {
+            let text = text.into();
+            set_tooltip_summary(ui.ctx(), text.text().to_string());
+            ui.label(text);
+        })
+    }
+}
+
+// Plain text description of the tooltip shown this frame, used by the
+// accessibility log
+pub fn set_tooltip_summary(ctx: &egui::Context, summary: String) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(TOOLTIP_SUMMARY_ID), summary));
+}
+
+pub fn take_tooltip_summary(ctx: &egui::Context) -> Option<String> {
+    ctx.data_mut(|data| {
+        let id = egui::Id::new(TOOLTIP_SUMMARY_ID);
+        let summary = data.get_temp::<String>(id);
+        data.remove::<String>(id);
+        summary
+    })
+}
+
+fn get_tooltip_position(
+    screen_rect: egui::Rect,
+    anchor_rect: egui::Rect,
+    size: egui::Vec2,
+    pointer_position: Option<egui::Pos2>,
+) -> egui::Pos2 {
+    // Prefer the right of the widget, then the left if there is not enough room
+    let mut position = egui::pos2(anchor_rect.right() + TOOLTIP_ANCHOR_GAP, anchor_rect.top());
+    if position.x + size.x > screen_rect.right() {
+        position.x = anchor_rect.left() - TOOLTIP_ANCHOR_GAP - size.x;
+    }
+    position.y = position.y.min(screen_rect.bottom() - size.y);
+
+    // Wide widgets leave no room at the sides, so use below or above instead
+    let covers_pointer = pointer_position.map_or(false, |pointer_position| {
+        egui::Rect::from_min_size(position, size).contains(pointer_position)
+    });
+    if position.x < screen_rect.left() || covers_pointer {
+        position.x = anchor_rect.left();
+        position.y = anchor_rect.bottom() + TOOLTIP_ANCHOR_GAP;
+        if position.y + size.y > screen_rect.bottom() {
+            position.y = anchor_rect.top() - TOOLTIP_ANCHOR_GAP - size.y;
+        }
+    }
+
+    egui::pos2(
+        position
+            .x
+            .min(screen_rect.right() - size.x)
+            .max(screen_rect.left()),
+        position
+            .y
+            .min(screen_rect.bottom() - size.y)
+            .max(screen_rect.top()),
+    )
+}
+
+fn show_anchored_tooltip(
+    ctx: &egui::Context,
+    id: egui::Id,
+    anchor_rect: egui::Rect,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    // The size from the previous frame is used to position the tooltip, it is
+    // hidden on the first frame so it does not flicker in the wrong place
+    let previous_rect = ctx.memory(|mem| mem.area_rect(id));
+    let position = previous_rect.map_or(anchor_rect.right_top(), |previous_rect| {
+        get_tooltip_position(
+            ctx.screen_rect(),
+            anchor_rect,
+            previous_rect.size(),
+            ctx.pointer_hover_pos(),
+        )
+    });
+
+    egui::Area::new(id)
+        .order(egui::Order::Tooltip)
+        .fixed_pos(position)
+        .interactable(false)
+        .show(ctx, |ui| {
+            if previous_rect.is_none() {
+                ui.set_visible(false);
+                ctx.request_repaint();
+            }
+
+            egui::Frame::popup(&ctx.style()).show(ui, |ui| {
+                ui.set_max_width(ui.spacing().tooltip_width);
+                add_contents(ui);
+            });
+        });
+}
+
+pub fn ui_tooltip_settings_system(
+    mut egui_context: EguiContexts,
+    client_settings: Res<ClientSettings>,
+) {
+    if client_settings.is_changed() {
+        egui_context.ctx_mut().data_mut(|data| {
+            data.insert_temp(
+                egui::Id::new(TOOLTIP_DELAY_ID),
+                client_settings.interface.tooltip_delay,
+            )
+        });
+    }
+}
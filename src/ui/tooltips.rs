@@ -5,15 +5,24 @@ use bevy::ecs::query::WorldQuery;
 use bevy_egui::egui;
 
 use rose_data::{
-    AbilityType, BaseItemData, EquipmentItem, Item, ItemClass, ItemGradeData, ItemType, JobId,
-    SkillAddAbility, SkillData, SkillId, SkillType, StackableItem, StatusEffectType,
+    AbilityType, BaseItemData, EquipmentItem, Item, ItemClass, ItemGradeData, ItemReference,
+    ItemType, JobId, SkillAddAbility, SkillData, SkillId, SkillType, StackableItem,
+    StatusEffectType,
 };
 use rose_game_common::components::{
     AbilityValues, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Inventory, Level,
-    ManaPoints, MoveSpeed, SkillList, SkillPoints, Stamina, StatPoints, Team, UnionMembership,
+    ManaPoints, MoveSpeed, QuestState, SkillList, SkillPoints, Stamina, StatPoints, Team,
+    UnionMembership,
 };
 
-use crate::{bundles::ability_values_get_value, resources::GameData};
+use crate::{
+    bundles::ability_values_get_value,
+    resources::GameData,
+    ui::{
+        format::{format_number, format_percent},
+        set_tooltip_summary,
+    },
+};
 
 const TOOLTIP_MAX_WIDTH: f32 = 300.0;
 
@@ -28,6 +37,7 @@ pub struct PlayerTooltipQuery<'w> {
     pub level: &'w Level,
     pub mana_points: &'w ManaPoints,
     pub move_speed: &'w MoveSpeed,
+    pub quest_state: Option<&'w QuestState>,
     pub skill_list: &'w SkillList,
     pub skill_points: &'w SkillPoints,
     pub stamina: &'w Stamina,
@@ -281,6 +291,15 @@ fn add_item_equip_requirement(
     }
 }
 
+fn set_summary(ui: &egui::Ui, name: &str, description: &str) {
+    let summary = if description.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}: {}", name, description)
+    };
+    set_tooltip_summary(ui.ctx(), summary);
+}
+
 fn add_item_description(ui: &mut egui::Ui, game_data: &GameData, item_data: &BaseItemData) {
     ui.label(format!(
         "{}:{}",
@@ -289,6 +308,65 @@ fn add_item_description(ui: &mut egui::Ui, game_data: &GameData, item_data: &Bas
     ui.label(item_data.description);
 }
 
+fn add_item_set_bonuses(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    equipment_item: &EquipmentItem,
+) {
+    let Some(item_set) = game_data.item_sets.find_item_set(equipment_item.item) else {
+        return;
+    };
+    let equipped_count = player.map_or(0, |player| item_set.count_equipped(player.equipment));
+
+    ui.separator();
+    ui.label(
+        egui::RichText::new(format!(
+            "{} ({}/{})",
+            item_set.name,
+            equipped_count,
+            item_set.items.len()
+        ))
+        .color(egui::Color32::from_rgb(0, 255, 128)),
+    );
+
+    for set_item in item_set.items.iter() {
+        let equipped = player.map_or(false, |player| {
+            player
+                .equipment
+                .equipped_items
+                .values()
+                .flatten()
+                .any(|equipped_item| equipped_item.item == *set_item)
+        });
+        let name = game_data
+            .items
+            .get_base_item(*set_item)
+            .map_or("???", |item_data| item_data.name);
+
+        ui.colored_label(
+            if equipped {
+                egui::Color32::WHITE
+            } else {
+                egui::Color32::GRAY
+            },
+            format!("  {}", name),
+        );
+    }
+
+    for bonus in item_set.bonuses.iter() {
+        let active = equipped_count >= bonus.required_pieces;
+        ui.colored_label(
+            if active {
+                egui::Color32::GREEN
+            } else {
+                egui::Color32::GRAY
+            },
+            format!("({}) {}", bonus.required_pieces, bonus.description),
+        );
+    }
+}
+
 pub fn ui_add_item_tooltip(
     ui: &mut egui::Ui,
     game_data: &GameData,
@@ -309,6 +387,7 @@ pub fn ui_add_item_tooltip(
         return;
     }
     let item_data = item_data.unwrap();
+    set_summary(ui, item_data.name, item_data.description);
 
     match item {
         Item::Equipment(equipment_item) => {
@@ -521,6 +600,8 @@ pub fn ui_add_item_tooltip(
                 }
                 _ => panic!("Unexpected item type"),
             }
+
+            add_item_set_bonuses(ui, game_data, player, equipment_item);
         }
         Item::Stackable(stackable_item) => {
             add_stackable_item_name(ui, stackable_item, item_data);
@@ -609,15 +690,49 @@ pub fn ui_add_item_tooltip(
 
                     add_item_description(ui, game_data, item_data);
                 }
-                ItemType::Quest => {}
+                ItemType::Quest => {
+                    add_item_description(ui, game_data, item_data);
+                    add_quest_item_usage(ui, game_data, player, stackable_item.item);
+                }
                 _ => panic!("Unexpected item type"),
             }
         }
     }
 }
 
-fn add_skill_name(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillData) {
-    let text = if skill_data.name.is_empty() {
+fn add_quest_item_usage(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    item_reference: ItemReference,
+) {
+    ui.colored_label(
+        egui::Color32::from_rgb(255, 160, 60),
+        "Quest item, cannot be dropped or traded",
+    );
+
+    let Some(quest_state) = player.and_then(|player| player.quest_state) else {
+        return;
+    };
+
+    for active_quest in quest_state.active_quests.iter().flatten() {
+        let has_item = active_quest
+            .items
+            .iter()
+            .flatten()
+            .any(|item| item.get_item_reference() == item_reference);
+        if !has_item {
+            continue;
+        }
+
+        if let Some(quest_data) = game_data.quests.get_quest_data(active_quest.quest_id) {
+            ui.label(format!("Used in: {}", quest_data.name));
+        }
+    }
+}
+
+fn get_skill_name(game_data: &GameData, skill_data: &SkillData) -> String {
+    if skill_data.name.is_empty() {
         format!("??? [Skill ID: {}]", skill_data.id.get())
     } else if skill_data.level > 1 {
         format!(
@@ -626,10 +741,12 @@ fn add_skill_name(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillDat
         )
     } else {
         skill_data.name.to_string()
-    };
+    }
+}
 
+fn add_skill_name(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillData) {
     ui.add(egui::Label::new(
-        egui::RichText::new(text)
+        egui::RichText::new(get_skill_name(game_data, skill_data))
             .color(egui::Color32::YELLOW)
             .font(egui::FontId::new(
                 16.0,
@@ -724,10 +841,122 @@ fn add_skill_power(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillDa
     ));
 }
 
+// Rough damage before the target's defence, without the random and critical
+// terms of the server formula, so the value does not change while hovering
+fn estimate_skill_damage(player: &PlayerTooltipQueryItem, skill_data: &SkillData) -> i32 {
+    let attack_power = player.ability_values.get_attack_power();
+    attack_power * (skill_data.power as i32 + 100) / 100
+}
+
+fn estimate_skill_heal(
+    game_data: &GameData,
+    player: &PlayerTooltipQueryItem,
+    skill_data: &SkillData,
+) -> i32 {
+    // Estimated for using the skill on yourself
+    skill_data
+        .add_ability
+        .iter()
+        .flatten()
+        .filter(|add_ability| matches!(add_ability.ability_type, AbilityType::Health))
+        .map(|add_ability| {
+            game_data
+                .ability_value_calculator
+                .calculate_skill_adjust_value(
+                    add_ability,
+                    player.ability_values.get_intelligence(),
+                    player.ability_values.get_max_health(),
+                )
+        })
+        .sum()
+}
+
+fn get_skill_previous_level<'a>(
+    game_data: &'a GameData,
+    skill_data: &SkillData,
+) -> Option<&'a SkillData> {
+    let previous_level_skill_data = game_data
+        .skills
+        .get_skill(SkillId::new(skill_data.id.get().checked_sub(1)?)?)?;
+    (previous_level_skill_data.base_skill_id == skill_data.base_skill_id
+        && previous_level_skill_data.level + 1 == skill_data.level)
+        .then_some(previous_level_skill_data)
+}
+
+fn add_skill_estimate(ui: &mut egui::Ui, label: &str, value: i32, previous_value: Option<i32>) {
+    if value <= 0 {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 4.0;
+        ui.label(format!("{}: {}", label, format_number(value as u64)));
+
+        if let Some(previous_value) = previous_value {
+            let delta = value - previous_value;
+            if delta != 0 {
+                ui.colored_label(
+                    if delta > 0 {
+                        egui::Color32::GREEN
+                    } else {
+                        egui::Color32::RED
+                    },
+                    format!("({:+})", delta),
+                );
+            }
+        }
+    });
+}
+
+fn add_skill_estimated_values(
+    ui: &mut egui::Ui,
+    tooltip_type: &SkillTooltipType,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    skill_data: &SkillData,
+) {
+    let Some(player) = player else {
+        return;
+    };
+
+    // The next level tooltip shows how much each value improves
+    let previous_level_skill_data = if matches!(tooltip_type, SkillTooltipType::NextLevel) {
+        get_skill_previous_level(game_data, skill_data)
+    } else {
+        None
+    };
+
+    if skill_data.power > 0
+        && matches!(
+            skill_data.skill_type,
+            SkillType::Immediate
+                | SkillType::EnforceWeapon
+                | SkillType::EnforceBullet
+                | SkillType::FireBullet
+                | SkillType::AreaTarget
+        )
+    {
+        add_skill_estimate(
+            ui,
+            "Estimated Damage",
+            estimate_skill_damage(player, skill_data),
+            previous_level_skill_data.map(|previous| estimate_skill_damage(player, previous)),
+        );
+    }
+
+    add_skill_estimate(
+        ui,
+        "Estimated Heal",
+        estimate_skill_heal(game_data, player, skill_data),
+        previous_level_skill_data.map(|previous| estimate_skill_heal(game_data, player, previous)),
+    );
+}
+
 fn add_skill_recover_xp(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillData) {
     ui.label(format!(
-        "{}: {}%",
-        game_data.client_strings.skill_recover_xp, skill_data.power
+        "{}: {}",
+        game_data.client_strings.skill_recover_xp,
+        format_percent(skill_data.power as f32)
     ));
 }
 
@@ -852,6 +1081,51 @@ fn add_skill_require_skill(
     }
 }
 
+// The skill points spent on this and all previous levels of the skill
+pub fn get_skill_refund_points(game_data: &GameData, skill_data: &SkillData) -> u32 {
+    let mut refund_points = skill_data.learn_point_cost;
+    let mut previous_level_skill_data = get_skill_previous_level(game_data, skill_data);
+    while let Some(previous_skill_data) = previous_level_skill_data {
+        refund_points += previous_skill_data.learn_point_cost;
+        previous_level_skill_data = get_skill_previous_level(game_data, previous_skill_data);
+    }
+    refund_points
+}
+
+// Points returned by a skill reset item, only shown when the player has one
+fn add_skill_refund_points(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    skill_data: &SkillData,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    if !game_data.skill_reset_items.has_reset_item(player.inventory) {
+        return;
+    }
+
+    let is_learned = player
+        .skill_list
+        .find_skill_level(
+            &game_data.skills,
+            skill_data.base_skill_id.unwrap_or(skill_data.id),
+        )
+        .map_or(false, |(_, _, skill_level)| skill_level == skill_data.level);
+    if !is_learned {
+        return;
+    }
+
+    let refund_points = get_skill_refund_points(game_data, skill_data);
+    if refund_points > 0 {
+        ui.colored_label(
+            egui::Color32::LIGHT_BLUE,
+            format!("[Refundable: {} points]", refund_points),
+        );
+    }
+}
+
 fn add_skill_require_skill_point(
     ui: &mut egui::Ui,
     game_data: &GameData,
@@ -965,7 +1239,7 @@ fn add_skill_status_effects(
             };
 
             if matches!(skill_add_ability.ability_type, AbilityType::PassiveSaveMana) {
-                write!(text, "{}%", value).ok();
+                text.push_str(&format_percent(value as f32));
             } else {
                 write!(text, "{}", value).ok();
             }
@@ -975,7 +1249,7 @@ fn add_skill_status_effects(
             if skill_add_ability.value > 0 {
                 text.push(' ');
             }
-            write!(text, "{}%", skill_add_ability.rate).ok();
+            text.push_str(&format_percent(skill_add_ability.rate as f32));
         }
     };
 
@@ -1167,15 +1441,22 @@ pub fn ui_add_skill_tooltip(
         return;
     }
     let skill_data = skill_data.unwrap();
+    set_summary(
+        ui,
+        &get_skill_name(game_data, skill_data),
+        skill_data.description,
+    );
 
     if matches!(tooltip_type, SkillTooltipType::Simple) {
         add_skill_name(ui, game_data, skill_data);
+        add_skill_refund_points(ui, game_data, player, skill_data);
         add_skill_use_ability_value(ui, game_data, player, skill_data);
     } else {
         match skill_data.skill_type {
             SkillType::BasicAction => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1185,6 +1466,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::CreateWindow => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1212,12 +1494,14 @@ pub fn ui_add_skill_tooltip(
             SkillType::Immediate | SkillType::EnforceWeapon | SkillType::EnforceBullet => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
                 add_skill_use_ability_value(ui, game_data, player, skill_data);
 
                 add_skill_power(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1241,6 +1525,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::FireBullet => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1248,6 +1533,7 @@ pub fn ui_add_skill_tooltip(
 
                 add_skill_power(ui, game_data, skill_data);
                 add_skill_cast_range(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1271,6 +1557,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::AreaTarget => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1279,6 +1566,7 @@ pub fn ui_add_skill_tooltip(
                 add_skill_power(ui, game_data, skill_data);
                 add_skill_cast_range(ui, game_data, skill_data);
                 add_skill_aoe_range(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1302,12 +1590,14 @@ pub fn ui_add_skill_tooltip(
             SkillType::SelfBound | SkillType::SelfBoundDuration | SkillType::SelfStateDuration => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
                 add_skill_use_ability_value(ui, game_data, player, skill_data);
 
                 add_skill_aoe_range(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1333,6 +1623,7 @@ pub fn ui_add_skill_tooltip(
             | SkillType::TargetStateDuration => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1340,6 +1631,7 @@ pub fn ui_add_skill_tooltip(
 
                 add_skill_cast_range(ui, game_data, skill_data);
                 add_skill_aoe_range(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1363,6 +1655,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::SummonPet => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type(ui, game_data, skill_data);
@@ -1391,6 +1684,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::Passive => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type(ui, game_data, skill_data);
@@ -1419,6 +1713,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::Emote => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type(ui, game_data, skill_data);
@@ -1443,6 +1738,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::SelfDamage => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1473,6 +1769,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::SelfAndTarget => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1480,6 +1777,7 @@ pub fn ui_add_skill_tooltip(
 
                 add_skill_power(ui, game_data, skill_data);
                 add_skill_steal_ability_value(ui, game_data, skill_data);
+                add_skill_estimated_values(ui, &tooltip_type, game_data, player, skill_data);
                 add_skill_status_effects(ui, game_data, player, skill_data);
 
                 add_skill_requirements(ui, game_data, player, skill_data);
@@ -1503,6 +1801,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::Resurrection => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type_and_target(ui, game_data, skill_data);
@@ -1533,6 +1832,7 @@ pub fn ui_add_skill_tooltip(
             SkillType::Warp => {
                 if !matches!(tooltip_type, SkillTooltipType::NextLevel) {
                     add_skill_name(ui, game_data, skill_data);
+                    add_skill_refund_points(ui, game_data, player, skill_data);
                 }
 
                 add_skill_type(ui, game_data, skill_data);
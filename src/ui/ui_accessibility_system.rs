@@ -0,0 +1,184 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+};
+
+use bevy::prelude::{
+    Camera, Camera3d, EventReader, GlobalTransform, Local, Query, Res, Vec3, With,
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::{AbilityValues, HealthPoints};
+
+use crate::{
+    components::PlayerCharacter,
+    events::{ChatboxEvent, ClientEntityEvent},
+    resources::{ClientSettings, SelectedTarget},
+    ui::take_tooltip_summary,
+};
+
+// Written next to the settings file, so screen readers can follow it with a
+// file watcher or a tail of the log
+const ACCESSIBILITY_LOG_FILE_NAME: &str = "accessibility.log";
+
+const HIGH_VISIBILITY_CURSOR_RADIUS: f32 = 18.0;
+const HIGH_VISIBILITY_TARGET_RADIUS: f32 = 40.0;
+const HIGH_VISIBILITY_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 235, 0);
+
+#[derive(Default)]
+pub struct UiStateAccessibility {
+    log_file: Option<File>,
+    log_file_failed: bool,
+    last_health: Option<u32>,
+    last_tooltip_summary: Option<String>,
+}
+
+impl UiStateAccessibility {
+    fn write_line(&mut self, client_settings: &ClientSettings, text: &str) {
+        if self.log_file.is_none() && !self.log_file_failed {
+            let path = client_settings
+                .path
+                .with_file_name(ACCESSIBILITY_LOG_FILE_NAME);
+            match OpenOptions::new().create(true).append(true).open(&path) {
+                Ok(file) => self.log_file = Some(file),
+                Err(error) => {
+                    log::warn!(
+                        "Failed to open accessibility log {} with error {}",
+                        path.to_string_lossy(),
+                        error
+                    );
+                    self.log_file_failed = true;
+                }
+            }
+        }
+
+        // Flush every line so screen readers do not have to wait for a full buffer
+        if let Some(file) = self.log_file.as_mut() {
+            if writeln!(file, "{}", text)
+                .and_then(|_| file.flush())
+                .is_err()
+            {
+                self.log_file = None;
+            }
+        }
+    }
+}
+
+fn paint_high_visibility_ring(painter: &egui::Painter, center: egui::Pos2, radius: f32) {
+    painter.circle_stroke(
+        center,
+        radius + 2.0,
+        egui::Stroke::new(6.0, egui::Color32::BLACK),
+    );
+    painter.circle_stroke(
+        center,
+        radius,
+        egui::Stroke::new(3.0, HIGH_VISIBILITY_COLOR),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_accessibility_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateAccessibility>,
+    mut chatbox_events: EventReader<ChatboxEvent>,
+    mut client_entity_events: EventReader<ClientEntityEvent>,
+    query_player: Query<(&HealthPoints, &AbilityValues), With<PlayerCharacter>>,
+    query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query_global_transform: Query<&GlobalTransform>,
+    client_settings: Res<ClientSettings>,
+    selected_target: Res<SelectedTarget>,
+) {
+    let ui_state = &mut *ui_state;
+    let accessibility = &client_settings.accessibility;
+    let ctx = egui_context.ctx_mut();
+
+    let player_health = query_player
+        .get_single()
+        .ok()
+        .map(|(health_points, ability_values)| (health_points.hp, ability_values.get_max_health()));
+    let last_health = ui_state.last_health;
+    ui_state.last_health = player_health.map(|(hp, _)| hp);
+
+    let tooltip_summary = take_tooltip_summary(ctx);
+    let tooltip_changed = tooltip_summary != ui_state.last_tooltip_summary;
+    ui_state.last_tooltip_summary = tooltip_summary;
+
+    if accessibility.screen_reader_log {
+        let mut lines = Vec::new();
+
+        if let (Some(last_health), Some((health, max_health))) = (last_health, player_health) {
+            if health < last_health {
+                lines.push(format!(
+                    "Damage taken: {}. Health {} of {}",
+                    last_health - health,
+                    health,
+                    max_health
+                ));
+            }
+        }
+
+        for event in client_entity_events.iter() {
+            if let ClientEntityEvent::LevelUp(entity, Some(level)) = *event {
+                if query_player.contains(entity) {
+                    lines.push(format!("Level up: you are now level {}", level));
+                }
+            }
+        }
+
+        for event in chatbox_events.iter() {
+            if let ChatboxEvent::Whisper(name, text) = event {
+                lines.push(format!("Whisper from {}: {}", name, text));
+            }
+        }
+
+        if tooltip_changed {
+            if let Some(summary) = ui_state.last_tooltip_summary.clone() {
+                lines.push(format!("Tooltip: {}", summary));
+            }
+        }
+
+        for line in lines {
+            ui_state.write_line(&client_settings, &line);
+        }
+    } else {
+        client_entity_events.clear();
+        chatbox_events.clear();
+    }
+
+    if !accessibility.high_visibility_cursor && !accessibility.high_visibility_target {
+        return;
+    }
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("accessibility_overlay"),
+    ));
+
+    if accessibility.high_visibility_target {
+        if let (Some(target_transform), Ok((camera, camera_transform))) = (
+            selected_target
+                .selected
+                .and_then(|entity| query_global_transform.get(entity).ok()),
+            query_camera.get_single(),
+        ) {
+            if let Some(screen_pos) = camera.world_to_viewport(
+                camera_transform,
+                target_transform.translation() + Vec3::new(0.0, 1.0, 0.0),
+            ) {
+                paint_high_visibility_ring(
+                    &painter,
+                    egui::pos2(screen_pos.x, screen_pos.y),
+                    HIGH_VISIBILITY_TARGET_RADIUS,
+                );
+            }
+        }
+    }
+
+    if accessibility.high_visibility_cursor {
+        if let Some(pointer_position) = ctx.pointer_hover_pos() {
+            paint_high_visibility_ring(&painter, pointer_position, HIGH_VISIBILITY_CURSOR_RADIUS);
+            painter.circle_filled(pointer_position, 3.0, HIGH_VISIBILITY_COLOR);
+        }
+    }
+}
@@ -18,7 +18,8 @@ use crate::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateDragAndDrop,
+        UiStateWindows, UiWindowId,
     },
 };
 
@@ -99,7 +100,7 @@ fn ui_add_bank_slot(
         .inner;
 
     if let Some(item) = item {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
         });
     }
@@ -146,16 +147,16 @@ pub fn ui_bank_system(
                 }
             }
             BankEvent::Show => {
-                ui_state_windows.bank_open = true;
+                ui_state_windows.set_open(UiWindowId::BANK, true);
 
-                if !ui_state_windows.inventory_open {
-                    ui_state_windows.inventory_open = true;
+                if !ui_state_windows.is_open(UiWindowId::INVENTORY) {
+                    ui_state_windows.set_open(UiWindowId::INVENTORY, true);
                 }
             }
         }
     }
 
-    if !ui_state_windows.bank_open {
+    if !ui_state_windows.is_open(UiWindowId::BANK) {
         return;
     }
 
@@ -178,7 +179,7 @@ pub fn ui_bank_system(
             .distance(bank_position.position.xy())
             > 1000.0
         {
-            ui_state_windows.bank_open = false;
+            ui_state_windows.set_open(UiWindowId::BANK, false);
             ui_state.bank_entity = None;
             return;
         }
@@ -266,6 +267,6 @@ pub fn ui_bank_system(
         });
 
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.bank_open = false;
+        ui_state_windows.set_open(UiWindowId::BANK, false);
     }
 }
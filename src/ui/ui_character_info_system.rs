@@ -16,8 +16,9 @@ use crate::{
     components::PlayerCharacter,
     resources::{GameConnection, GameData, UiResources},
     ui::{
+        format::format_number,
         widgets::{DataBindings, Dialog, DrawText},
-        UiSoundEvent, UiStateWindows,
+        UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -99,7 +100,7 @@ pub fn ui_character_info_system(
 
     egui::Window::new("Character Info")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.character_info_open)
+        .open(ui_state_windows.open_mut(UiWindowId::CHARACTER_INFO))
         .title_bar(false)
         .resizable(false)
         .default_width(dialog.width)
@@ -147,7 +148,11 @@ pub fn ui_character_info_system(
                         );
                         ui.add_label_at(
                             egui::pos2(59.0, 193.0),
-                            &format!("{} / {}", player.experience_points.xp, need_xp),
+                            &format!(
+                                "{} / {}",
+                                format_number(player.experience_points.xp),
+                                format_number(need_xp)
+                            ),
                         );
                     }
                     Some(&mut IID_TAB_ABILITY) => {
@@ -220,7 +225,7 @@ pub fn ui_character_info_system(
         });
 
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.character_info_open = false;
+        ui_state_windows.set_open(UiWindowId::CHARACTER_INFO, false);
     }
 
     let stat_button_response = |basic_stat_type: BasicStatType,
@@ -1,16 +1,70 @@
-use bevy::prelude::{Camera, Camera3d, GlobalTransform, Query, Res, Vec3, With};
+use bevy::prelude::{Camera, Camera3d, EventWriter, GlobalTransform, Query, Res, Vec3, With};
 use bevy_egui::{egui, EguiContexts};
 
-use crate::resources::{CharacterList, CharacterSelectState, GameData};
+use crate::{
+    events::CharacterSelectEvent,
+    resources::{CharacterList, CharacterSelectState, CharacterSlots, GameData},
+    ui::format::format_duration,
+};
 
 pub fn ui_character_select_name_tag_system(
     mut egui_context: EguiContexts,
     query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
     character_list: Option<Res<CharacterList>>,
+    character_slots: Option<Res<CharacterSlots>>,
     character_select_state: Res<CharacterSelectState>,
     game_data: Res<GameData>,
+    mut character_select_events: EventWriter<CharacterSelectEvent>,
 ) {
     for (camera, camera_transform) in query_camera.iter() {
+        // Show a placeholder for each locked slot, the next one can be unlocked
+        if let (CharacterSelectState::CharacterSelect(_), Some(character_slots)) =
+            (&*character_select_state, character_slots.as_ref())
+        {
+            let locked_slots = character_slots.slot_count
+                ..character_slots
+                    .max_slot_count
+                    .min(game_data.character_select_positions.len());
+
+            for index in locked_slots {
+                let Some(screen_pos) = camera.world_to_viewport(
+                    camera_transform,
+                    game_data.character_select_positions[index].translation
+                        + Vec3::new(0.0, 2.0, 0.0),
+                ) else {
+                    continue;
+                };
+
+                let ctx = egui_context.ctx_mut();
+                let screen_size = ctx.input(|input| input.screen_rect().size());
+
+                egui::Area::new(egui::Id::new("locked_character_slot").with(index))
+                    .fixed_pos(egui::Pos2::new(
+                        screen_pos.x - 40.0,
+                        screen_size.y - screen_pos.y,
+                    ))
+                    .show(ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(
+                                egui::RichText::new("Locked Slot")
+                                    .font(egui::FontId::proportional(18.0))
+                                    .color(egui::Color32::GRAY),
+                            );
+
+                            // The server only sends a price when the next slot can be purchased
+                            if index == character_slots.slot_count {
+                                if let Some(price) = character_slots.unlock_price {
+                                    if ui.button(format!("Unlock ({})", price)).clicked() {
+                                        character_select_events
+                                            .send(CharacterSelectEvent::PurchaseSlot(index));
+                                    }
+                                }
+                            }
+                        });
+                    });
+            }
+        }
+
         if let CharacterSelectState::CharacterSelect(Some(index)) = *character_select_state {
             if let Some(selected_character) = character_list
                 .as_ref()
@@ -52,10 +106,10 @@ pub fn ui_character_select_name_tag_system(
                             ));
 
                             if let Some(delete_time) = selected_character.delete_time.as_ref() {
-                                let duration = delete_time.get_time_until_delete();
-                                let seconds = duration.as_secs() % 60;
-                                let minutes = (duration.as_secs() / 60) % 60;
-                                ui.label(format!("Deleted in {:02}m {:02}s", minutes, seconds));
+                                ui.label(format!(
+                                    "Deleted in {}",
+                                    format_duration(delete_time.get_time_until_delete())
+                                ));
                             }
                         },
                     );
@@ -6,7 +6,7 @@ use bevy_egui::{egui, EguiContexts};
 use crate::{
     animation::CameraAnimation,
     events::CharacterSelectEvent,
-    resources::{CharacterList, CharacterSelectState, GameData, UiResources},
+    resources::{CharacterList, CharacterSelectState, CharacterSlots, GameData, UiResources},
     ui::{
         widgets::{DataBindings, Dialog, Widget},
         DialogInstance, UiSoundEvent,
@@ -37,6 +37,7 @@ pub fn ui_character_select_system(
     mut egui_context: EguiContexts,
     query_camera: Query<Entity, With<Camera3d>>,
     character_list: Option<Res<CharacterList>>,
+    character_slots: Option<Res<CharacterSlots>>,
     asset_server: Res<AssetServer>,
     dialog_assets: Res<Assets<Dialog>>,
     game_data: Res<GameData>,
@@ -110,9 +111,18 @@ pub fn ui_character_select_system(
             );
         });
 
+    let slot_count = character_slots.map_or(
+        game_data.character_select_positions.len(),
+        |character_slots| {
+            character_slots
+                .slot_count
+                .min(game_data.character_select_positions.len())
+        },
+    );
+
     if response_create_button.map_or(false, |r| r.clicked())
         && character_list.as_ref().map_or(true, |character_list| {
-            character_list.characters.len() < game_data.character_select_positions.len()
+            character_list.characters.len() < slot_count
         })
     {
         commands
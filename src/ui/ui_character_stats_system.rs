@@ -0,0 +1,151 @@
+use bevy::{
+    ecs::query::WorldQuery,
+    prelude::{Query, Res, ResMut, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::{
+    AbilityValues, BasicStats, CharacterInfo, Equipment, Level, MoveSpeed, SkillList, StatusEffects,
+};
+
+use crate::{
+    components::PlayerCharacter,
+    resources::GameData,
+    ui::{TooltipExt, UiStateWindows, UiWindowId},
+};
+
+const DERIVED_STATS: [(&str, fn(&AbilityValues) -> f32); 9] = [
+    ("Max HP", |ability_values| {
+        ability_values.get_max_health() as f32
+    }),
+    ("Max MP", |ability_values| {
+        ability_values.get_max_mana() as f32
+    }),
+    ("Attack", |ability_values| {
+        ability_values.get_attack_power() as f32
+    }),
+    ("Defence", |ability_values| {
+        ability_values.get_defence() as f32
+    }),
+    ("Magic Resistance", |ability_values| {
+        ability_values.get_resistance() as f32
+    }),
+    ("Hit", |ability_values| ability_values.get_hit() as f32),
+    ("Dodge", |ability_values| ability_values.get_avoid() as f32),
+    ("Critical", |ability_values| {
+        ability_values.get_critical() as f32
+    }),
+    ("Attack Speed", |ability_values| {
+        ability_values.get_attack_speed() as f32
+    }),
+];
+
+#[derive(WorldQuery)]
+pub struct PlayerQuery<'w> {
+    ability_values: &'w AbilityValues,
+    basic_stats: &'w BasicStats,
+    character_info: &'w CharacterInfo,
+    equipment: &'w Equipment,
+    level: &'w Level,
+    move_speed: &'w MoveSpeed,
+    skill_list: &'w SkillList,
+}
+
+fn ui_add_stat_breakdown(ui: &mut egui::Ui, breakdown: [(&str, f32); 4]) {
+    egui::Grid::new("stat_breakdown")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (index, (source, value)) in breakdown.into_iter().enumerate() {
+                ui.label(source);
+                if index == 0 {
+                    ui.label(format!("{}", value.round()));
+                } else if value.abs() < 0.5 {
+                    ui.weak("-");
+                } else if value > 0.0 {
+                    ui.colored_label(egui::Color32::GREEN, format!("+{}", value.round()));
+                } else {
+                    ui.colored_label(egui::Color32::RED, format!("{}", value.round()));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn ui_add_stat_row(ui: &mut egui::Ui, name: &str, total: f32, breakdown: [(&str, f32); 4]) {
+    ui.label(name);
+    ui.label(format!("{}", total.round()))
+        .on_hover_tooltip_ui(|ui| ui_add_stat_breakdown(ui, breakdown));
+    ui.end_row();
+}
+
+pub fn ui_character_stats_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+) {
+    if !ui_state_windows.is_open(UiWindowId::CHARACTER_STATS) {
+        return;
+    }
+
+    let Ok(player) = query_player.get_single() else {
+        return;
+    };
+
+    let calculate = |equipment: &Equipment, skill_list: &SkillList| {
+        game_data.ability_value_calculator.calculate(
+            player.character_info,
+            player.level,
+            equipment,
+            player.basic_stats,
+            skill_list,
+            &StatusEffects::default(),
+        )
+    };
+
+    // Ability values are recalculated with each source added in turn, the
+    // difference between each step is the contribution of that source
+    let base_values = calculate(&Equipment::default(), &SkillList::default());
+    let equipment_values = calculate(player.equipment, &SkillList::default());
+    let passive_values = calculate(player.equipment, player.skill_list);
+
+    // Buffs are whatever remains of the current values after the other sources
+    let get_breakdown = |get_value: &dyn Fn(&AbilityValues) -> f32, total: f32| {
+        let base = get_value(&base_values);
+        let equipment = get_value(&equipment_values);
+        let passives = get_value(&passive_values);
+        [
+            ("Base Stats", base),
+            ("Equipment", equipment - base),
+            ("Passive Skills", passives - equipment),
+            ("Buffs", total - passives),
+        ]
+    };
+
+    egui::Window::new("Character Stats")
+        .open(ui_state_windows.open_mut(UiWindowId::CHARACTER_STATS))
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::Grid::new("character_stats")
+                .num_columns(2)
+                .striped(true)
+                .min_col_width(120.0)
+                .show(ui, |ui| {
+                    for (name, get_value) in DERIVED_STATS {
+                        let total = get_value(player.ability_values);
+                        ui_add_stat_row(ui, name, total, get_breakdown(&get_value, total));
+                    }
+
+                    let move_speed = player.move_speed.speed;
+                    ui_add_stat_row(
+                        ui,
+                        "Move Speed",
+                        move_speed,
+                        get_breakdown(&|ability_values| ability_values.run_speed, move_speed),
+                    );
+                });
+
+            ui.separator();
+            ui.weak("Hover a value to see where it comes from");
+        });
+}
@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::{
+    Camera, Camera3d, ComputedVisibility, Entity, EventReader, GlobalTransform, Local, Query, Res,
+    Time, Vec2, Vec3, With,
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntity, ModelHeight},
+    events::ChatboxEvent,
+    resources::{ClientEntityRegistry, NameTagSettings},
+};
+
+// Bubbles stay for a base duration plus a little longer for each character
+const CHAT_BUBBLE_BASE_DURATION: f32 = 4.0;
+const CHAT_BUBBLE_DURATION_PER_CHARACTER: f32 = 0.05;
+const CHAT_BUBBLE_MAX_DURATION: f32 = 10.0;
+const CHAT_BUBBLE_FADE_DURATION: f32 = 0.5;
+
+// Older messages are dropped when someone talks faster than the bubbles can show them
+const CHAT_BUBBLE_MAX_QUEUED: usize = 3;
+
+const CHAT_BUBBLE_WRAP_WIDTH: f32 = 220.0;
+
+// Screen space offset above the head so the bubble sits above the name tag
+const CHAT_BUBBLE_NAME_TAG_OFFSET: f32 = 36.0;
+
+struct ChatBubbleMessage {
+    text: String,
+    shout: bool,
+}
+
+impl ChatBubbleMessage {
+    fn duration(&self) -> f32 {
+        (CHAT_BUBBLE_BASE_DURATION
+            + self.text.chars().count() as f32 * CHAT_BUBBLE_DURATION_PER_CHARACTER)
+            .min(CHAT_BUBBLE_MAX_DURATION)
+    }
+}
+
+struct ChatBubble {
+    entity: Entity,
+    message: ChatBubbleMessage,
+    elapsed: f32,
+    queued: VecDeque<ChatBubbleMessage>,
+}
+
+#[derive(Default)]
+pub struct UiStateChatBubbles {
+    bubbles: Vec<ChatBubble>,
+}
+
+pub fn ui_chat_bubble_system(
+    mut ui_state: Local<UiStateChatBubbles>,
+    mut egui_context: EguiContexts,
+    mut chatbox_events: EventReader<ChatboxEvent>,
+    query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query_characters: Query<
+        (&GlobalTransform, &ComputedVisibility, Option<&ModelHeight>),
+        With<ClientEntity>,
+    >,
+    client_entity_registry: Res<ClientEntityRegistry>,
+    name_tag_settings: Res<NameTagSettings>,
+    time: Res<Time>,
+) {
+    if !name_tag_settings.show_chat_bubbles {
+        chatbox_events.clear();
+        ui_state.bubbles.clear();
+        return;
+    }
+
+    for event in chatbox_events.iter() {
+        let (name, text, shout) = match event {
+            ChatboxEvent::Say(name, text) => (name, text, false),
+            ChatboxEvent::Shout(name, text) => (name, text, true),
+            _ => continue,
+        };
+
+        let Some(entity) = client_entity_registry.find_character(name) else {
+            continue;
+        };
+
+        let message = ChatBubbleMessage {
+            text: text.clone(),
+            shout,
+        };
+        if let Some(bubble) = ui_state
+            .bubbles
+            .iter_mut()
+            .find(|bubble| bubble.entity == entity)
+        {
+            if bubble.queued.len() >= CHAT_BUBBLE_MAX_QUEUED {
+                bubble.queued.pop_front();
+            }
+            bubble.queued.push_back(message);
+        } else {
+            ui_state.bubbles.push(ChatBubble {
+                entity,
+                message,
+                elapsed: 0.0,
+                queued: VecDeque::new(),
+            });
+        }
+    }
+
+    // Advance to the next queued message once the current one has expired
+    let delta = time.delta_seconds();
+    ui_state.bubbles.retain_mut(|bubble| {
+        if !query_characters.contains(bubble.entity) {
+            return false;
+        }
+
+        bubble.elapsed += delta;
+        if bubble.elapsed < bubble.message.duration() {
+            return true;
+        }
+
+        let Some(next_message) = bubble.queued.pop_front() else {
+            return false;
+        };
+        bubble.message = next_message;
+        bubble.elapsed = 0.0;
+        true
+    });
+
+    if ui_state.bubbles.is_empty() {
+        return;
+    }
+
+    let Ok((camera, camera_transform)) = query_camera.get_single() else {
+        return;
+    };
+
+    let ctx = egui_context.ctx_mut();
+    let screen_size = ctx.input(|input| input.screen_rect().size());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("chat_bubbles"),
+    ));
+
+    for bubble in ui_state.bubbles.iter() {
+        let Ok((global_transform, computed_visibility, model_height)) =
+            query_characters.get(bubble.entity)
+        else {
+            continue;
+        };
+        if !computed_visibility.is_visible() {
+            continue;
+        }
+
+        let height = model_height.map_or(2.0, |model_height| model_height.height);
+        let Some(ndc_space_coords) = camera
+            .world_to_ndc(
+                camera_transform,
+                global_transform.translation() + Vec3::new(0.0, height, 0.0),
+            )
+            .filter(|ndc| (0.0..=1.0).contains(&ndc.z))
+        else {
+            continue;
+        };
+        let screen_pos = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0
+            * Vec2::new(screen_size.x, screen_size.y);
+
+        let remaining = bubble.message.duration() - bubble.elapsed;
+        let opacity = (remaining / CHAT_BUBBLE_FADE_DURATION).clamp(0.0, 1.0);
+        let text_color = if bubble.message.shout {
+            egui::Color32::from_rgb(160, 90, 0)
+        } else {
+            egui::Color32::from_gray(20)
+        };
+
+        let galley = painter.layout(
+            bubble.message.text.clone(),
+            egui::FontId::proportional(14.0),
+            text_color.linear_multiply(opacity),
+            CHAT_BUBBLE_WRAP_WIDTH,
+        );
+        let tail = egui::pos2(
+            screen_pos.x,
+            screen_size.y - screen_pos.y - CHAT_BUBBLE_NAME_TAG_OFFSET,
+        );
+        let text_rect = egui::Align2::CENTER_BOTTOM.anchor_rect(egui::Rect::from_min_size(
+            tail - egui::vec2(0.0, 14.0),
+            galley.size(),
+        ));
+
+        let fill = egui::Color32::from_white_alpha(220).linear_multiply(opacity);
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(80).linear_multiply(opacity));
+        painter.rect(
+            text_rect.expand(6.0),
+            egui::Rounding::same(6.0),
+            fill,
+            stroke,
+        );
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                tail - egui::vec2(6.0, 8.0),
+                tail - egui::vec2(-6.0, 8.0),
+                tail,
+            ],
+            fill,
+            egui::Stroke::NONE,
+        ));
+        painter.galley(text_rect.min, galley);
+    }
+}
@@ -1,19 +1,25 @@
-use bevy::prelude::{Assets, EventReader, EventWriter, Local, Res, ResMut};
+use bevy::prelude::{Assets, EventReader, EventWriter, Local, Query, Res, ResMut, Time, With};
 use bevy_egui::{egui, EguiContexts};
 
 use rose_game_common::messages::client::ClientMessage;
 
 use crate::{
-    events::ChatboxEvent,
-    resources::{GameConnection, UiResources},
+    components::{Clan, PlayerCharacter},
+    events::{ChatCommandEvent, ChatboxEvent},
+    protocol::{ExtendedClientMessage, ProtocolCapabilities},
+    resources::{ClientCapabilities, ClientSettings, GameConnection, GameData, UiResources},
     ui::{
+        chat_command_help, clan_position_name, parse_chat_command,
         widgets::{DataBindings, Dialog},
-        UiSoundEvent, UiStateWindows,
+        UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
 const MAX_CHATBOX_ENTRIES: usize = 100;
 
+// Minimum time in seconds between sending shout or trade messages
+const SHOUT_COOLDOWN: f64 = 10.0;
+
 // TODO: Implement the chat filters
 // const IID_BTN_FILTER: i32 = 10;
 const IID_EDITBOX: i32 = 15;
@@ -56,11 +62,81 @@ const CHAT_COLOR_SYSTEM: egui::Color32 = egui::Color32::from_rgb(255, 224, 229);
 const CHAT_COLOR_QUEST: egui::Color32 = egui::Color32::from_rgb(151, 221, 241);
 const CHAT_COLOR_ALLIED: egui::Color32 = egui::Color32::from_rgb(255, 228, 122);
 const CHAT_COLOR_CLAN: egui::Color32 = egui::Color32::from_rgb(255, 228, 122);
+const CHAT_COLOR_TRADE: egui::Color32 = egui::Color32::from_rgb(255, 190, 110);
+const CHAT_BACKGROUND_BROADCAST: egui::Color32 =
+    egui::Color32::from_rgba_premultiplied(40, 40, 60, 96);
+
+const CHATBOX_TEXT_ID: &str = "chatbox_text";
+
+// Replaces the chat input text and focuses it, used by other windows to
+// start a whisper to someone
+pub fn set_chatbox_text(ctx: &egui::Context, text: String) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(CHATBOX_TEXT_ID), text));
+}
+
+fn take_chatbox_text(ctx: &egui::Context) -> Option<String> {
+    ctx.data_mut(|data| {
+        let id = egui::Id::new(CHATBOX_TEXT_ID);
+        let text = data.get_temp::<String>(id);
+        data.remove::<String>(id);
+        text
+    })
+}
+
+#[derive(Default)]
+struct ChatLog {
+    layout_job: egui::text::LayoutJob,
+    cleanup_layout_text_counter: usize,
+}
+
+impl ChatLog {
+    fn append(&mut self, timestamp: &str, text: &str, format: egui::TextFormat) {
+        if self.layout_job.sections.len() == MAX_CHATBOX_ENTRIES {
+            self.layout_job.sections.remove(0);
+            self.cleanup_layout_text_counter += 1;
+
+            if self.cleanup_layout_text_counter == MAX_CHATBOX_ENTRIES {
+                let offset = self.layout_job.sections[0].byte_range.start;
+                self.layout_job.text = self.layout_job.text.split_off(offset);
+
+                for section in self.layout_job.sections.iter_mut() {
+                    section.byte_range.start -= offset;
+                    section.byte_range.end -= offset;
+                }
+
+                self.cleanup_layout_text_counter = 0;
+            }
+        }
+
+        self.layout_job.append(
+            timestamp,
+            0.0,
+            egui::TextFormat {
+                color: CHAT_COLOR_TIMESTAMP,
+                ..Default::default()
+            },
+        );
+        self.layout_job.append(text, 0.0, format);
+    }
+
+    fn append_system(&mut self, timestamp: &str, text: &str) {
+        self.append(
+            timestamp,
+            &format!("{}\n", text),
+            egui::TextFormat {
+                color: CHAT_COLOR_SYSTEM,
+                ..Default::default()
+            },
+        );
+    }
+}
 
 pub struct UiStateChatbox {
     textbox_text: String,
-    textbox_layout_job: egui::text::LayoutJob,
-    cleanup_layout_text_counter: usize,
+    chat_log: ChatLog,
+    trade_log: ChatLog,
+    clan_log: ChatLog,
+    last_shout_time: Option<f64>,
     selected_channel: i32,
 }
 
@@ -68,8 +144,10 @@ impl Default for UiStateChatbox {
     fn default() -> Self {
         Self {
             textbox_text: Default::default(),
-            textbox_layout_job: Default::default(),
-            cleanup_layout_text_counter: 0,
+            chat_log: Default::default(),
+            trade_log: Default::default(),
+            clan_log: Default::default(),
+            last_shout_time: None,
             selected_channel: IID_BTN_ALL,
         }
     }
@@ -79,11 +157,17 @@ pub fn ui_chatbox_system(
     mut egui_context: EguiContexts,
     mut ui_state_chatbox: Local<UiStateChatbox>,
     mut chatbox_events: EventReader<ChatboxEvent>,
+    mut chat_command_events: EventWriter<ChatCommandEvent>,
     game_connection: Option<Res<GameConnection>>,
     mut ui_state_windows: ResMut<UiStateWindows>,
     ui_resources: Res<UiResources>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     dialog_assets: Res<Assets<Dialog>>,
+    time: Res<Time>,
+    mut client_settings: ResMut<ClientSettings>,
+    query_clan: Query<&Clan, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+    client_capabilities: Res<ClientCapabilities>,
 ) {
     let ui_state_chatbox = &mut *ui_state_chatbox;
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_chatbox) {
@@ -93,110 +177,88 @@ pub fn ui_chatbox_system(
     };
 
     let local_time = chrono::Local::now();
-    let timestamp = local_time.format("%H:%M:%S");
-
-    for event in chatbox_events.iter() {
-        if ui_state_chatbox.textbox_layout_job.sections.len() == MAX_CHATBOX_ENTRIES {
-            ui_state_chatbox.textbox_layout_job.sections.remove(0);
-            ui_state_chatbox.cleanup_layout_text_counter += 1;
-
-            if ui_state_chatbox.cleanup_layout_text_counter == MAX_CHATBOX_ENTRIES {
-                let offset = ui_state_chatbox.textbox_layout_job.sections[0]
-                    .byte_range
-                    .start;
-                ui_state_chatbox.textbox_layout_job.text =
-                    ui_state_chatbox.textbox_layout_job.text.split_off(offset);
-
-                for section in ui_state_chatbox.textbox_layout_job.sections.iter_mut() {
-                    section.byte_range.start -= offset;
-                    section.byte_range.end -= offset;
-                }
+    let timestamp = format!("[{}] ", local_time.format("%H:%M:%S"));
 
-                ui_state_chatbox.cleanup_layout_text_counter = 0;
-            }
-        }
-
-        ui_state_chatbox.textbox_layout_job.append(
-            &format!("[{}] ", timestamp),
-            0.0,
-            egui::TextFormat {
-                color: CHAT_COLOR_TIMESTAMP,
-                ..Default::default()
-            },
-        );
+    let focus_editbox = if let Some(text) = take_chatbox_text(egui_context.ctx_mut()) {
+        ui_state_chatbox.textbox_text = text;
+        true
+    } else {
+        false
+    };
 
-        match event {
+    for event in chatbox_events.iter() {
+        let (text, color, broadcast) = match event {
             ChatboxEvent::Say(name, text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}> {}\n", name, text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_NORMAL,
-                        ..Default::default()
-                    },
-                );
+                (format!("{}> {}\n", name, text), CHAT_COLOR_NORMAL, false)
             }
-            ChatboxEvent::Shout(name, text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}> {}\n", name, text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_SHOUT,
-                        ..Default::default()
-                    },
-                );
+            ChatboxEvent::Shout(name, text) => (
+                format!("[Shout] {}> {}\n", name, text),
+                CHAT_COLOR_SHOUT,
+                true,
+            ),
+            ChatboxEvent::Trade(name, text) => (
+                format!("[Trade] {}> {}\n", name, text),
+                CHAT_COLOR_TRADE,
+                true,
+            ),
+            ChatboxEvent::Clan(name, text) => {
+                let rank = query_clan
+                    .get_single()
+                    .ok()
+                    .and_then(|clan| clan.find_member(name))
+                    .map(|member| clan_position_name(&game_data, member.position));
+                let text = if let Some(rank) = rank {
+                    format!("[Clan] [{}] {}> {}\n", rank, name, text)
+                } else {
+                    format!("[Clan] {}> {}\n", name, text)
+                };
+                (text, CHAT_COLOR_CLAN, false)
             }
             ChatboxEvent::Whisper(name, text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}> {}\n", name, text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_WHISPER,
-                        ..Default::default()
-                    },
-                );
+                (format!("{}> {}\n", name, text), CHAT_COLOR_WHISPER, false)
             }
             ChatboxEvent::Announce(Some(name), text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}> {}\n", name, text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_ANNOUNCE,
-                        ..Default::default()
-                    },
-                );
+                (format!("{}> {}\n", name, text), CHAT_COLOR_ANNOUNCE, false)
             }
             ChatboxEvent::Announce(None, text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}\n", text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_ANNOUNCE,
-                        ..Default::default()
-                    },
-                );
+                (format!("{}\n", text), CHAT_COLOR_ANNOUNCE, false)
             }
-            ChatboxEvent::System(text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}\n", text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_SYSTEM,
-                        ..Default::default()
-                    },
-                );
+            ChatboxEvent::System(text) => (format!("{}\n", text), CHAT_COLOR_SYSTEM, false),
+            ChatboxEvent::Quest(text) => (format!("{}\n", text), CHAT_COLOR_QUEST, false),
+        };
+
+        // Zone wide messages are highlighted so they stand out from local chat
+        let format = egui::TextFormat {
+            color,
+            background: if broadcast {
+                CHAT_BACKGROUND_BROADCAST
+            } else {
+                egui::Color32::TRANSPARENT
+            },
+            ..Default::default()
+        };
+
+        if matches!(event, ChatboxEvent::Trade(..)) {
+            ui_state_chatbox
+                .trade_log
+                .append(&timestamp, &text, format.clone());
+
+            if !client_settings.social.show_trade_in_all_tab {
+                continue;
             }
-            ChatboxEvent::Quest(text) => {
-                ui_state_chatbox.textbox_layout_job.append(
-                    &format!("{}\n", text),
-                    0.0,
-                    egui::TextFormat {
-                        color: CHAT_COLOR_QUEST,
-                        ..Default::default()
-                    },
-                );
+        }
+
+        if matches!(event, ChatboxEvent::Clan(..)) {
+            ui_state_chatbox
+                .clan_log
+                .append(&timestamp, &text, format.clone());
+
+            if !client_settings.social.show_clan_in_all_tab {
+                continue;
             }
         }
+
+        ui_state_chatbox.chat_log.append(&timestamp, &text, format);
     }
 
     let mut chatbox_style = (*egui_context.ctx_mut().style()).clone();
@@ -212,6 +274,18 @@ pub fn ui_chatbox_system(
     let frame_fill =
         egui::Color32::from_rgba_unmultiplied(frame_fill.r(), frame_fill.g(), frame_fill.b(), 128);
 
+    let chat_layout_job = match ui_state_chatbox.selected_channel {
+        IID_BTN_TRADE => ui_state_chatbox.trade_log.layout_job.clone(),
+        IID_BTN_CLAN => ui_state_chatbox.clan_log.layout_job.clone(),
+        _ => ui_state_chatbox.chat_log.layout_job.clone(),
+    };
+
+    let shout_cooldown = ui_state_chatbox
+        .last_shout_time
+        .map_or(0.0, |last_shout_time| {
+            (SHOUT_COOLDOWN - (time.elapsed_seconds_f64() - last_shout_time)).max(0.0)
+        });
+
     let mut response_editbox = None;
     let mut response_all_button = None;
     let mut response_whisper_button = None;
@@ -231,6 +305,7 @@ pub fn ui_chatbox_system(
             ui.visuals_mut().override_text_color =
                 match ui_state_chatbox.textbox_text.chars().next() {
                     Some('!') => Some(CHAT_COLOR_SHOUT),
+                    Some('$') => Some(CHAT_COLOR_TRADE),
                     Some('@') => Some(CHAT_COLOR_WHISPER),
                     Some('#') => Some(CHAT_COLOR_PARTY),
                     Some('&') => Some(CHAT_COLOR_CLAN),
@@ -271,25 +346,37 @@ pub fn ui_chatbox_system(
                     ..Default::default()
                 },
                 |ui, _bindings| {
-                    ui.allocate_ui_at_rect(
-                        egui::Rect::from_min_size(
-                            ui.min_rect().min + egui::vec2(1.0, 0.0),
-                            egui::vec2(390.0, 179.0),
-                        ),
-                        |ui| {
-                            egui::ScrollArea::vertical()
-                                .auto_shrink([false; 2])
-                                .stick_to_bottom(true)
-                                .show(ui, |ui| {
-                                    ui.label(ui_state_chatbox.textbox_layout_job.clone());
-                                });
-                        },
+                    let chat_rect = egui::Rect::from_min_size(
+                        ui.min_rect().min + egui::vec2(1.0, 0.0),
+                        egui::vec2(390.0, 179.0),
                     );
+                    ui.allocate_ui_at_rect(chat_rect, |ui| {
+                        egui::ScrollArea::vertical()
+                            .auto_shrink([false; 2])
+                            .stick_to_bottom(true)
+                            .show(ui, |ui| {
+                                ui.label(chat_layout_job);
+                            });
+                    });
+
+                    if shout_cooldown > 0.0 {
+                        ui.painter().text(
+                            chat_rect.right_top() + egui::vec2(-16.0, 4.0),
+                            egui::Align2::RIGHT_TOP,
+                            format!("Shout ready in {:.0}s", shout_cooldown.ceil()),
+                            egui::FontId::proportional(12.0),
+                            CHAT_COLOR_SHOUT,
+                        );
+                    }
                 },
             );
         });
 
     if let Some(response) = response_editbox {
+        if focus_editbox {
+            response.request_focus();
+        }
+
         if response
             .ctx
             .input(|input| input.key_pressed(egui::Key::Enter))
@@ -297,21 +384,109 @@ pub fn ui_chatbox_system(
             if response.lost_focus() {
                 if !ui_state_chatbox.textbox_text.is_empty() {
                     let text = ui_state_chatbox.textbox_text.trim();
-                    if text.eq_ignore_ascii_case("/pshop") {
-                        ui_state_windows.player_shop_open = !ui_state_windows.player_shop_open;
+                    let command = parse_chat_command(text).or_else(|| {
+                        text.strip_prefix('&')
+                            .filter(|_| {
+                                client_capabilities.supports(ProtocolCapabilities::CLAN_CHAT)
+                            })
+                            .map(|clan_text| Ok(ChatCommandEvent::ClanChat(clan_text.to_string())))
+                    });
+                    if let Some(command) = command {
+                        match command {
+                            Ok(ChatCommandEvent::Clear) => {
+                                ui_state_chatbox.chat_log = Default::default();
+                                ui_state_chatbox.trade_log = Default::default();
+                                ui_state_chatbox.clan_log = Default::default();
+                            }
+                            Ok(ChatCommandEvent::ClanChat(text)) => {
+                                if !client_capabilities.supports(ProtocolCapabilities::CLAN_CHAT) {
+                                    ui_state_chatbox.chat_log.append_system(
+                                        &timestamp,
+                                        "This server does not support clan chat",
+                                    );
+                                } else if let Some(game_connection) = game_connection.as_ref() {
+                                    game_connection
+                                        .extended_client_message_tx
+                                        .send(ExtendedClientMessage::ClanChat(text))
+                                        .ok();
+                                }
+                            }
+                            Ok(ChatCommandEvent::Help(name)) => {
+                                for line in chat_command_help(name.as_deref()) {
+                                    ui_state_chatbox.chat_log.append_system(&timestamp, &line);
+                                }
+
+                                let chat_macros = &client_settings.social.chat_macros;
+                                if name.is_none() && !chat_macros.is_empty() {
+                                    let names = chat_macros
+                                        .iter()
+                                        .map(|chat_macro| format!("/{}", chat_macro.name))
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    ui_state_chatbox
+                                        .chat_log
+                                        .append_system(&timestamp, &format!("Macros: {}", names));
+                                }
+                            }
+                            Ok(ChatCommandEvent::PlayerShop) => {
+                                ui_state_windows.toggle(UiWindowId::PLAYER_SHOP);
+                            }
+                            Ok(command) => {
+                                chat_command_events.send(command);
+                            }
+                            Err(error) => {
+                                ui_state_chatbox.chat_log.append_system(&timestamp, &error);
+                            }
+                        }
+                        ui_state_chatbox.textbox_text.clear();
+                        return;
+                    }
+
+                    if let Some(chat_macro) = text.strip_prefix('/').and_then(|name| {
+                        client_settings
+                            .social
+                            .chat_macros
+                            .iter()
+                            .find(|chat_macro| chat_macro.name.eq_ignore_ascii_case(name))
+                    }) {
+                        chat_command_events.send(ChatCommandEvent::Macro(chat_macro.text.clone()));
                         ui_state_chatbox.textbox_text.clear();
                         return;
                     }
 
-                    // TODO: Parse text line to decide whether its chat, shout, etc
+                    let trade_text = text.strip_prefix('$');
+                    let is_shout = trade_text.is_some() || text.starts_with('!');
+
+                    // Keep the text while on cooldown so it can be sent once the shout is ready,
+                    // the server also rate limits shouts and trade chat
+                    if is_shout && shout_cooldown > 0.0 {
+                        return;
+                    }
+
                     if let Some(game_connection) = game_connection.as_ref() {
-                        game_connection
-                            .client_message_tx
-                            .send(ClientMessage::Chat {
-                                text: ui_state_chatbox.textbox_text.clone(),
-                            })
-                            .ok();
+                        if let Some(trade_text) = trade_text.filter(|_| {
+                            client_capabilities.supports(ProtocolCapabilities::TRADE_CHAT)
+                        }) {
+                            game_connection
+                                .extended_client_message_tx
+                                .send(ExtendedClientMessage::TradeChat(trade_text.to_string()))
+                                .ok();
+                        } else {
+                            // Servers without a trade channel receive trade chat as a normal shout
+                            let text = match trade_text {
+                                Some(trade_text) => format!("!{}", trade_text),
+                                None => text.to_string(),
+                            };
+                            game_connection
+                                .client_message_tx
+                                .send(ClientMessage::Chat { text })
+                                .ok();
+                        }
                         ui_state_chatbox.textbox_text.clear();
+
+                        if is_shout {
+                            ui_state_chatbox.last_shout_time = Some(time.elapsed_seconds_f64());
+                        }
                     }
                 }
             } else {
@@ -330,8 +505,19 @@ pub fn ui_chatbox_system(
         ui_state_chatbox.textbox_text.push('@');
     }
 
-    if response_trade_button.map_or(false, |r| r.clicked()) {
-        ui_state_chatbox.textbox_text.clear();
+    if let Some(response) = response_trade_button {
+        if response.clicked() {
+            ui_state_chatbox.textbox_text.clear();
+            ui_state_chatbox.textbox_text.push('$');
+        }
+
+        let mut show_trade_in_all = client_settings.social.show_trade_in_all_tab;
+        response.context_menu(|ui| {
+            ui.checkbox(&mut show_trade_in_all, "Show trade chat in all tab");
+        });
+        if show_trade_in_all != client_settings.social.show_trade_in_all_tab {
+            client_settings.social.show_trade_in_all_tab = show_trade_in_all;
+        }
     }
 
     if response_party_button.map_or(false, |r| r.clicked()) {
@@ -339,9 +525,19 @@ pub fn ui_chatbox_system(
         ui_state_chatbox.textbox_text.push('#');
     }
 
-    if response_clan_button.map_or(false, |r| r.clicked()) {
-        ui_state_chatbox.textbox_text.clear();
-        ui_state_chatbox.textbox_text.push('&');
+    if let Some(response) = response_clan_button {
+        if response.clicked() {
+            ui_state_chatbox.textbox_text.clear();
+            ui_state_chatbox.textbox_text.push('&');
+        }
+
+        let mut show_clan_in_all = client_settings.social.show_clan_in_all_tab;
+        response.context_menu(|ui| {
+            ui.checkbox(&mut show_clan_in_all, "Show clan chat in all tab");
+        });
+        if show_clan_in_all != client_settings.social.show_clan_in_all_tab {
+            client_settings.social.show_clan_in_all_tab = show_clan_in_all;
+        }
     }
 
     if response_allied_button.map_or(false, |r| r.clicked()) {
@@ -1,86 +1,109 @@
-use bevy::prelude::{Res, ResMut};
+use bevy::prelude::{Res, ResMut, Time};
 use bevy_egui::{egui, EguiContexts};
 use rose_game_common::messages::client::ClientMessage;
 
-use crate::resources::{GameConnection, PendingClanInvites};
+use crate::resources::{GameConnection, IgnoreList, PendingClanInvites, CLAN_INVITE_TIMEOUT};
+
+// Older invites are queued behind the visible ones
+const MAX_VISIBLE_CLAN_INVITES: usize = 3;
+
+const CLAN_INVITE_TOAST_WIDTH: f32 = 260.0;
+
+fn send_clan_invite_response(
+    game_connection: Option<&GameConnection>,
+    inviter_name: String,
+    accepted: bool,
+) {
+    if let Some(game_connection) = game_connection {
+        let message = if accepted {
+            ClientMessage::ClanAcceptInvite { inviter_name }
+        } else {
+            ClientMessage::ClanRejectInvite { inviter_name }
+        };
+        game_connection.client_message_tx.send(message).ok();
+    }
+}
 
 pub fn ui_clan_invite_system(
     mut egui_context: EguiContexts,
     mut pending_clan_invites: ResMut<PendingClanInvites>,
     game_connection: Option<Res<GameConnection>>,
+    ignore_list: Res<IgnoreList>,
+    time: Res<Time>,
 ) {
-    let mut i = 0;
-    while i < pending_clan_invites.invites.len() {
-        let mut accepted = false;
-        let mut rejected = false;
-
-        let inviter_name = pending_clan_invites.invites[i].inviter_name.clone();
-        let clan_name = pending_clan_invites.invites[i].clan_name.clone();
-        let clan_level = pending_clan_invites.invites[i].clan_level.0;
-
-        let mut window_open = true;
-        egui::Window::new("Clan Invite")
-            .id(egui::Id::new(format!("clan_invite_{}", &inviter_name)))
-            .collapsible(false)
-            .resizable(false)
-            .default_pos(egui_context.ctx_mut().screen_rect().center())
-            .pivot(egui::Align2::CENTER_CENTER)
-            .open(&mut window_open)
-            .show(egui_context.ctx_mut(), |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.add_space(8.0);
-                    ui.label(
-                        egui::RichText::new(format!(
-                            "{} has invited you to join clan \"{}\" (Lv.{})",
-                            &inviter_name, &clan_name, clan_level,
-                        ))
-                        .size(16.0),
+    if pending_clan_invites.invites.is_empty() {
+        return;
+    }
+
+    let game_connection = game_connection.as_deref();
+    let delta_seconds = time.delta_seconds();
+
+    // Invites from ignored players and unanswered invites are rejected without being shown
+    pending_clan_invites.invites.retain_mut(|invite| {
+        invite.remaining_time -= delta_seconds;
+
+        if ignore_list.is_ignored(&invite.inviter_name) || invite.remaining_time <= 0.0 {
+            send_clan_invite_response(game_connection, invite.inviter_name.clone(), false);
+            false
+        } else {
+            true
+        }
+    });
+
+    let num_invites = pending_clan_invites.invites.len();
+    if num_invites == 0 {
+        return;
+    }
+
+    let mut response = None;
+    egui::Area::new("clan_invite_toasts")
+        .anchor(egui::Align2::RIGHT_TOP, [-16.0, 120.0])
+        .order(egui::Order::Foreground)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.set_width(CLAN_INVITE_TOAST_WIDTH);
+
+            for (index, invite) in pending_clan_invites
+                .invites
+                .iter()
+                .enumerate()
+                .take(MAX_VISIBLE_CLAN_INVITES)
+            {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.strong("Clan Invite");
+                    ui.label(format!(
+                        "{} has invited you to join clan \"{}\" (Lv.{})",
+                        invite.inviter_name, invite.clan_name, invite.clan_level.0,
+                    ));
+
+                    ui.add(
+                        egui::ProgressBar::new(invite.remaining_time / CLAN_INVITE_TIMEOUT)
+                            .desired_width(CLAN_INVITE_TOAST_WIDTH - 16.0)
+                            .text(format!("{:.0}s", invite.remaining_time.ceil())),
                     );
-                    ui.add_space(16.0);
-                });
-                ui.vertical_centered(|ui| {
+
                     ui.horizontal(|ui| {
-                        let total_width = ui.available_width();
-                        let button_width = 80.0;
-                        let spacing = 16.0;
-                        let offset = (total_width - button_width * 2.0 - spacing) / 2.0;
-                        ui.add_space(offset);
-                        if ui.add_sized([button_width, 28.0], egui::Button::new(egui::RichText::new("Accept").size(15.0))).clicked() {
-                            accepted = true;
+                        if ui.button("Accept").clicked() {
+                            response = Some((index, true));
                         }
-                        ui.add_space(spacing);
-                        if ui.add_sized([button_width, 28.0], egui::Button::new(egui::RichText::new("Reject").size(15.0))).clicked() {
-                            rejected = true;
+
+                        if ui.button("Decline").clicked() {
+                            response = Some((index, false));
                         }
                     });
-                    ui.add_space(8.0);
                 });
-            });
-
-        if !window_open {
-            rejected = true;
-        }
-
-        if accepted {
-            if let Some(game_connection) = &game_connection {
-                game_connection
-                    .client_message_tx
-                    .send(ClientMessage::ClanAcceptInvite { inviter_name: inviter_name.clone() })
-                    .ok();
+                ui.add_space(4.0);
             }
-            pending_clan_invites.invites.remove(i);
-            continue;
-        } else if rejected {
-            if let Some(game_connection) = &game_connection {
-                game_connection
-                    .client_message_tx
-                    .send(ClientMessage::ClanRejectInvite { inviter_name: inviter_name.clone() })
-                    .ok();
+
+            if num_invites > MAX_VISIBLE_CLAN_INVITES {
+                ui.weak(format!(
+                    "{} more clan invites waiting",
+                    num_invites - MAX_VISIBLE_CLAN_INVITES
+                ));
             }
-            pending_clan_invites.invites.remove(i);
-            continue;
-        }
+        });
 
-        i += 1;
+    if let Some((index, accepted)) = response {
+        let invite = pending_clan_invites.invites.remove(index);
+        send_clan_invite_response(game_connection, invite.inviter_name, accepted);
     }
 }
@@ -0,0 +1,315 @@
+use bevy_egui::egui;
+use rose_data::ClanMemberPosition;
+
+use crate::{
+    components::{Clan, ClanMembership, ClanWar},
+    resources::{GameData, UiResources},
+    ui::{
+        format::format_number,
+        ui_add_clan_mark,
+        widgets::{Dialog, DrawText},
+        UiSkin,
+    },
+};
+
+use super::{
+    add_clan_button, ClanAction, ClanActionConfirm, UiStateClan, CLAN_LABEL_COLOR, CLAN_VALUE_COLOR,
+};
+
+// Clan points needed to advance from each clan grade, the ability value
+// calculator only provides the member limit for a grade
+const CLAN_GRADE_POINTS: [u64; 6] = [1_000, 3_000, 7_500, 15_000, 30_000, 60_000];
+
+fn clan_grade_required_points(level: usize) -> Option<u64> {
+    CLAN_GRADE_POINTS.get(level.checked_sub(1)?).copied()
+}
+
+fn format_war_remaining_time(seconds: i64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+// The server checks everything else, such as whether the clan exists
+fn get_war_declare_error(clan: &Clan, opponent: &str) -> Option<&'static str> {
+    if opponent.is_empty() {
+        Some("Enter the name of the clan to declare war on")
+    } else if opponent.eq_ignore_ascii_case(&clan.name) {
+        Some("You cannot declare war on your own clan")
+    } else if clan.find_war(opponent).is_some() {
+        Some("You are already at war with this clan")
+    } else {
+        None
+    }
+}
+
+fn draw_clan_wars(
+    ui: &mut egui::Ui,
+    clan: &Clan,
+    clan_membership: &ClanMembership,
+    ui_skin: Option<&UiSkin>,
+    ui_state: &mut UiStateClan,
+    wars_supported: bool,
+) -> Option<ClanAction> {
+    let is_master = clan_membership.position == ClanMemberPosition::Master;
+    let now = chrono::Utc::now().timestamp();
+    let active_wars: Vec<&ClanWar> = clan.wars.iter().filter(|war| war.is_active(now)).collect();
+    let mut action = None;
+
+    ui.colored_label(CLAN_LABEL_COLOR, "Clan Wars");
+    if !wars_supported {
+        ui.weak("This server does not support clan wars.");
+        return None;
+    } else if active_wars.is_empty() {
+        ui.weak("Your clan is not at war.");
+    } else {
+        egui::Grid::new("clan_wars")
+            .num_columns(if is_master { 4 } else { 3 })
+            .striped(true)
+            .spacing(egui::vec2(16.0, 4.0))
+            .show(ui, |ui| {
+                ui.colored_label(CLAN_LABEL_COLOR, "Opponent");
+                ui.colored_label(CLAN_LABEL_COLOR, "Score");
+                ui.colored_label(CLAN_LABEL_COLOR, "Time Left");
+                if is_master {
+                    ui.label("");
+                }
+                ui.end_row();
+
+                for war in active_wars.iter() {
+                    ui.colored_label(egui::Color32::RED, &war.opponent);
+                    ui.colored_label(CLAN_VALUE_COLOR, format!("{} - {}", war.kills, war.deaths));
+                    ui.colored_label(
+                        CLAN_VALUE_COLOR,
+                        format_war_remaining_time(war.remaining_seconds(now)),
+                    );
+
+                    if is_master && add_clan_button(ui, ui_skin, true, "Surrender").clicked() {
+                        action = Some(ClanAction::Confirm(ClanActionConfirm::Surrender {
+                            opponent: war.opponent.clone(),
+                        }));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    if !is_master {
+        return action;
+    }
+
+    ui.add_space(4.0);
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut ui_state.war_opponent_buffer)
+                .hint_text("Clan name")
+                .desired_width(140.0),
+        );
+
+        let opponent = ui_state.war_opponent_buffer.trim().to_string();
+        let error = get_war_declare_error(clan, &opponent);
+        let response = add_clan_button(ui, ui_skin, error.is_none(), "Declare War");
+        if response.clicked() {
+            action = Some(ClanAction::Confirm(ClanActionConfirm::DeclareWar {
+                opponent,
+            }));
+            ui_state.war_opponent_buffer.clear();
+        } else if let Some(error) = error {
+            response.on_disabled_hover_text(error);
+        }
+    });
+
+    action
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_info_tab(
+    ui: &mut egui::Ui,
+    dialog: &Dialog,
+    clan: &Clan,
+    clan_membership: &ClanMembership,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+    ui_skin: Option<&UiSkin>,
+    ui_state: &mut UiStateClan,
+    wars_supported: bool,
+    marks_supported: bool,
+) -> Option<ClanAction> {
+    let max_members = game_data
+        .ability_value_calculator
+        .calculate_clan_max_members(clan.level.0);
+    let is_master = clan_membership.position == ClanMemberPosition::Master;
+    let mut action = None;
+
+    if !ui_state.is_editing_slogan {
+        ui_state.slogan_edit_buffer = clan.description.clone();
+    } else if !is_master {
+        ui_state.is_editing_slogan = false;
+    }
+
+    let origin = ui.min_rect().min;
+    let content_width = dialog.width - 40.0;
+    let mut y = 70.0;
+
+    ui.allocate_ui_at_rect(
+        egui::Rect::from_min_size(origin + egui::vec2(20.0, y), egui::vec2(32.0, 32.0)),
+        |ui| ui_add_clan_mark(ui, ui_resources, &clan.mark, 32.0),
+    );
+    ui.add_label_at(
+        egui::pos2(60.0, y + 8.0),
+        egui::RichText::new(&clan.name)
+            .size(16.0)
+            .color(CLAN_VALUE_COLOR),
+    )
+    .on_hover_text(game_data.client_strings.clan_name);
+
+    if is_master
+        && marks_supported
+        && ui
+            .add_at(
+                egui::pos2(dialog.width - 110.0, y + 6.0),
+                |ui: &mut egui::Ui| add_clan_button(ui, ui_skin, true, "Change Mark"),
+            )
+            .clicked()
+    {
+        action = Some(ClanAction::EditMark);
+    }
+    y += 44.0;
+
+    let mut add_info_row = |ui: &mut egui::Ui, label: &str, value: String| {
+        ui.add_label_at(
+            egui::pos2(20.0, y),
+            egui::RichText::new(label).color(CLAN_LABEL_COLOR),
+        );
+        ui.add_label_at(
+            egui::pos2(120.0, y),
+            egui::RichText::new(value).color(CLAN_VALUE_COLOR),
+        );
+        y += 20.0;
+    };
+    add_info_row(ui, "Clan Grade", format!("{}", clan.level.0.get()));
+    add_info_row(
+        ui,
+        game_data.client_strings.clan_point,
+        format_number(clan.points.0),
+    );
+    add_info_row(
+        ui,
+        game_data.client_strings.clan_member_count,
+        format!("{} / {}", clan.members.len(), max_members),
+    );
+
+    ui.add_label_at(
+        egui::pos2(20.0, y),
+        egui::RichText::new("Next Grade").color(CLAN_LABEL_COLOR),
+    );
+    match clan_grade_required_points(clan.level.0.get() as usize) {
+        Some(required_points) => {
+            ui.add_at(
+                egui::pos2(120.0, y),
+                egui::ProgressBar::new((clan.points.0 as f32 / required_points as f32).min(1.0))
+                    .desired_width(content_width - 100.0)
+                    .text(format!(
+                        "{} / {}",
+                        format_number(clan.points.0),
+                        format_number(required_points)
+                    )),
+            );
+        }
+        None => {
+            ui.add_label_at(
+                egui::pos2(120.0, y),
+                egui::RichText::new("Maximum grade").color(CLAN_VALUE_COLOR),
+            );
+        }
+    }
+    y += 26.0;
+
+    ui.add_label_at(
+        egui::pos2(20.0, y),
+        egui::RichText::new(game_data.client_strings.clan_slogan).color(CLAN_LABEL_COLOR),
+    );
+    y += 18.0;
+
+    let slogan_rect = egui::Rect::from_min_size(
+        origin + egui::vec2(20.0, y),
+        egui::vec2(content_width, 60.0),
+    );
+    if ui_state.is_editing_slogan && is_master {
+        ui.put(
+            slogan_rect,
+            egui::TextEdit::multiline(&mut ui_state.slogan_edit_buffer).desired_rows(3),
+        );
+        y += 64.0;
+
+        if ui
+            .add_at(egui::pos2(20.0, y), |ui: &mut egui::Ui| {
+                add_clan_button(ui, ui_skin, true, "Save")
+            })
+            .clicked()
+        {
+            action = Some(ClanAction::SetDescription(
+                ui_state.slogan_edit_buffer.trim_end().to_string(),
+            ));
+            ui_state.is_editing_slogan = false;
+        }
+
+        if ui
+            .add_at(egui::pos2(70.0, y), |ui: &mut egui::Ui| {
+                add_clan_button(ui, ui_skin, true, "Cancel")
+            })
+            .clicked()
+        {
+            ui_state.slogan_edit_buffer = clan.description.clone();
+            ui_state.is_editing_slogan = false;
+        }
+        y += 24.0;
+    } else {
+        let slogan = if clan.description.is_empty() {
+            "-"
+        } else {
+            &clan.description
+        };
+        let response = ui.put(
+            slogan_rect,
+            egui::Label::new(egui::RichText::new(slogan).color(CLAN_VALUE_COLOR))
+                .wrap(true)
+                .sense(if is_master {
+                    egui::Sense::click()
+                } else {
+                    egui::Sense::hover()
+                }),
+        );
+
+        if is_master && response.on_hover_text("Click to edit").clicked() {
+            ui_state.slogan_edit_buffer = clan.description.clone();
+            ui_state.is_editing_slogan = true;
+        }
+        y += 64.0;
+    }
+
+    // Clan wars are not part of the original dialog, they fill the rest of the tab
+    let wars_rect = egui::Rect::from_min_max(
+        origin + egui::vec2(20.0, y),
+        origin + egui::vec2(20.0 + content_width, (dialog.height - 16.0).max(y + 40.0)),
+    );
+    let scroll_area = egui::ScrollArea::vertical()
+        .id_source("clan_wars_scroll")
+        .auto_shrink([false, false]);
+    let show_wars = |scroll_area: egui::ScrollArea, ui: &mut egui::Ui| {
+        scroll_area.show(ui, |ui| {
+            draw_clan_wars(ui, clan, clan_membership, ui_skin, ui_state, wars_supported)
+        })
+    };
+    let war_action = ui
+        .allocate_ui_at_rect(wars_rect, |ui| match ui_skin {
+            Some(ui_skin) => ui_skin.scroll_area(ui, scroll_area, show_wars),
+            None => show_wars(scroll_area, ui).inner,
+        })
+        .inner;
+
+    action.or(war_action)
+}
@@ -0,0 +1,168 @@
+use std::{num::NonZeroU16, path::Path};
+
+use bevy_egui::egui;
+use rose_game_common::components::ClanMark;
+
+use crate::{
+    protocol::{ClanMarkChange, ExtendedClientMessage},
+    resources::{GameConnection, UiResources},
+    ui::{
+        encode_custom_clan_mark, load_custom_clan_mark, ui_add_clan_mark_picker, UiSkin,
+        CUSTOM_CLAN_MARK_SIZE,
+    },
+};
+
+use super::{add_clan_button, UiStateClan};
+
+pub struct ClanMarkEdit {
+    background: NonZeroU16,
+    foreground: NonZeroU16,
+    custom_path: String,
+    custom_image: Option<(image::RgbImage, egui::TextureHandle)>,
+    custom_error: Option<String>,
+}
+
+impl ClanMarkEdit {
+    pub fn new(mark: &ClanMark) -> Self {
+        let (background, foreground) = match *mark {
+            ClanMark::Premade {
+                background,
+                foreground,
+            } => (background, foreground),
+            ClanMark::Custom { .. } => (NonZeroU16::new(1).unwrap(), NonZeroU16::new(1).unwrap()),
+        };
+
+        Self {
+            background,
+            foreground,
+            custom_path: String::new(),
+            custom_image: None,
+            custom_error: None,
+        }
+    }
+}
+
+fn load_custom_mark_preview(
+    ctx: &egui::Context,
+    path: &str,
+) -> Result<(image::RgbImage, egui::TextureHandle), String> {
+    let image = load_custom_clan_mark(Path::new(path.trim())).map_err(|error| error.to_string())?;
+    let texture = ctx.load_texture(
+        "clan_mark_custom_preview",
+        egui::ColorImage::from_rgb(
+            [image.width() as usize, image.height() as usize],
+            image.as_raw(),
+        ),
+        egui::TextureOptions::NEAREST,
+    );
+    Ok((image, texture))
+}
+
+pub fn draw_clan_mark_dialog(
+    ctx: &egui::Context,
+    ui_resources: &UiResources,
+    ui_skin: Option<&UiSkin>,
+    ui_state: &mut UiStateClan,
+    game_connection: Option<&GameConnection>,
+) {
+    let Some(mark_edit) = ui_state.mark_edit.as_mut() else {
+        return;
+    };
+
+    let mut window_open = true;
+    let mut close_clicked = false;
+    let mut change = None;
+
+    let mut window = egui::Window::new("Change Clan Mark")
+        .id(egui::Id::new("clan_mark_dialog"))
+        .collapsible(false)
+        .resizable(false)
+        .pivot(egui::Align2::CENTER_CENTER)
+        .default_pos(ctx.screen_rect().center());
+    if let Some(ui_skin) = ui_skin {
+        window = window.frame(ui_skin.window_frame()).title_bar(false);
+    }
+
+    window.open(&mut window_open).show(ctx, |ui| {
+        if let Some(ui_skin) = ui_skin {
+            ui_skin.begin_window(ui);
+            close_clicked = ui_skin.title_bar(ui, "Change Clan Mark");
+        }
+
+        ui_add_clan_mark_picker(
+            ui,
+            ui_resources,
+            &mut mark_edit.background,
+            &mut mark_edit.foreground,
+        );
+        ui.add_space(4.0);
+        if add_clan_button(ui, ui_skin, true, "Save Mark").clicked() {
+            change = Some(ClanMarkChange::Premade {
+                background: mark_edit.background,
+                foreground: mark_edit.foreground,
+            });
+        }
+
+        ui.separator();
+        ui.label(format!(
+            "Custom Mark, resized to {}x{}",
+            CUSTOM_CLAN_MARK_SIZE, CUSTOM_CLAN_MARK_SIZE
+        ));
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut mark_edit.custom_path)
+                    .hint_text("Path to a .png or .bmp image")
+                    .desired_width(220.0),
+            );
+
+            let has_path = !mark_edit.custom_path.trim().is_empty();
+            if add_clan_button(ui, ui_skin, has_path, "Load").clicked() {
+                match load_custom_mark_preview(ui.ctx(), &mark_edit.custom_path) {
+                    Ok(custom_image) => {
+                        mark_edit.custom_image = Some(custom_image);
+                        mark_edit.custom_error = None;
+                    }
+                    Err(error) => {
+                        mark_edit.custom_image = None;
+                        mark_edit.custom_error = Some(error);
+                    }
+                }
+            }
+        });
+
+        if let Some(error) = mark_edit.custom_error.as_ref() {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        ui.horizontal(|ui| {
+            if let Some((_, texture)) = mark_edit.custom_image.as_ref() {
+                ui.image(texture, egui::vec2(32.0, 32.0));
+            }
+
+            if add_clan_button(ui, ui_skin, mark_edit.custom_image.is_some(), "Upload Mark")
+                .clicked()
+            {
+                if let Some((image, _)) = mark_edit.custom_image.as_ref() {
+                    change = Some(ClanMarkChange::Custom {
+                        width: image.width() as u16,
+                        height: image.height() as u16,
+                        pixels: encode_custom_clan_mark(image),
+                    });
+                }
+            }
+        });
+    });
+
+    // The server sends the new mark to all clan members once it has been changed
+    if let Some(change) = change {
+        if let Some(game_connection) = game_connection {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::ClanMarkChange(change))
+                .ok();
+        }
+        ui_state.mark_edit = None;
+    } else if !window_open || close_clicked {
+        ui_state.mark_edit = None;
+    }
+}
@@ -0,0 +1,514 @@
+use std::time::Duration;
+
+use bevy_egui::egui;
+use rose_data::ClanMemberPosition;
+
+use crate::{
+    components::{Clan, ClanMembership},
+    resources::GameData,
+    ui::{
+        format::{format_number, format_relative_time},
+        widgets::{Dialog, DrawText},
+    },
+};
+
+use super::{
+    clan_position_name, get_zlist_rect, paint_list_text, position_to_rank, ClanAction,
+    ClanActionConfirm, UiStateClan, CLAN_LABEL_COLOR, CLAN_SELECTED_COLOR, CLAN_VALUE_COLOR,
+    IID_ZLIST_MEMBER,
+};
+
+// Formatted member table row, rebuilt only when the Clan component changes
+pub struct ClanMemberRow {
+    pub name: String,
+    is_online: bool,
+    status: String,
+    last_online: Option<i64>,
+    rank: String,
+    rank_order: u8,
+    class_name: String,
+    level: String,
+    level_value: u32,
+    contribution: String,
+    contribution_value: u64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ClanMemberSort {
+    Status,
+    Name,
+    Rank,
+    Class,
+    Level,
+    Contribution,
+}
+
+impl ClanMemberSort {
+    const ALL: [ClanMemberSort; 6] = [
+        ClanMemberSort::Status,
+        ClanMemberSort::Name,
+        ClanMemberSort::Rank,
+        ClanMemberSort::Class,
+        ClanMemberSort::Level,
+        ClanMemberSort::Contribution,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ClanMemberSort::Status => "Status",
+            ClanMemberSort::Name => "Name",
+            ClanMemberSort::Rank => "Rank",
+            ClanMemberSort::Class => "Class",
+            ClanMemberSort::Level => "Level",
+            ClanMemberSort::Contribution => "Contribution",
+        }
+    }
+
+    fn compare(self, a: &ClanMemberRow, b: &ClanMemberRow) -> std::cmp::Ordering {
+        match self {
+            // Online members, recently seen members and higher ranks are listed first
+            ClanMemberSort::Status => b
+                .is_online
+                .cmp(&a.is_online)
+                .then(b.last_online.cmp(&a.last_online)),
+            ClanMemberSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ClanMemberSort::Rank => b.rank_order.cmp(&a.rank_order),
+            ClanMemberSort::Class => a.class_name.cmp(&b.class_name),
+            ClanMemberSort::Level => a.level_value.cmp(&b.level_value),
+            ClanMemberSort::Contribution => b.contribution_value.cmp(&a.contribution_value),
+        }
+    }
+}
+
+fn rank_to_position(rank: u8) -> Option<ClanMemberPosition> {
+    match rank {
+        0 => Some(ClanMemberPosition::Penalty),
+        1 => Some(ClanMemberPosition::Junior),
+        2 => Some(ClanMemberPosition::Senior),
+        3 => Some(ClanMemberPosition::Veteran),
+        4 => Some(ClanMemberPosition::Commander),
+        5 => Some(ClanMemberPosition::DeputyMaster),
+        6 => Some(ClanMemberPosition::Master),
+        _ => None,
+    }
+}
+
+fn next_promoted_position(
+    actor_position: ClanMemberPosition,
+    target_position: ClanMemberPosition,
+) -> Option<ClanMemberPosition> {
+    let actor_rank = position_to_rank(actor_position);
+    let target_rank = position_to_rank(target_position);
+    let promoted_rank = target_rank.checked_add(1)?;
+    if target_rank >= actor_rank || promoted_rank >= actor_rank {
+        return None;
+    }
+
+    rank_to_position(promoted_rank)
+}
+
+fn next_demoted_position(
+    actor_position: ClanMemberPosition,
+    target_position: ClanMemberPosition,
+) -> Option<ClanMemberPosition> {
+    let actor_rank = position_to_rank(actor_position);
+    let target_rank = position_to_rank(target_position);
+    if target_rank >= actor_rank {
+        return None;
+    }
+
+    let demoted_rank = target_rank.checked_sub(1)?;
+    rank_to_position(demoted_rank)
+}
+
+pub fn build_member_rows(game_data: &GameData, clan: &Clan) -> Vec<ClanMemberRow> {
+    let total_contribution: u64 = clan
+        .members
+        .iter()
+        .map(|member| member.contribution.0)
+        .sum();
+    let now = chrono::Utc::now().timestamp();
+
+    clan.members
+        .iter()
+        .map(|member| {
+            let status = if member.channel_id.is_some() {
+                "Online".to_string()
+            } else if let Some(last_online) = member.last_online {
+                format_relative_time(Duration::from_secs((now - last_online).max(0) as u64))
+            } else {
+                "Offline".to_string()
+            };
+            let class_name = game_data.string_database.get_job_name(member.job);
+            let contribution = if total_contribution > 0 {
+                format!(
+                    "{} ({}%)",
+                    format_number(member.contribution.0),
+                    member.contribution.0 * 100 / total_contribution
+                )
+            } else {
+                format_number(member.contribution.0)
+            };
+            ClanMemberRow {
+                name: member.name.clone(),
+                is_online: member.channel_id.is_some(),
+                status,
+                last_online: member.last_online,
+                rank: clan_position_name(game_data, member.position),
+                rank_order: position_to_rank(member.position),
+                class_name: if class_name.is_empty() {
+                    format!("Job {}", member.job)
+                } else {
+                    class_name.to_string()
+                },
+                level: format!("{}", member.level.level),
+                level_value: member.level.level,
+                contribution,
+                contribution_value: member.contribution.0,
+            }
+        })
+        .collect()
+}
+
+pub fn get_visible_member_rows(ui_state: &UiStateClan) -> Vec<usize> {
+    let filter = ui_state.member_filter.trim().to_lowercase();
+    let mut visible_rows = ui_state
+        .member_rows
+        .iter()
+        .enumerate()
+        .filter(|(_, member)| !ui_state.member_online_only || member.is_online)
+        .filter(|(_, member)| {
+            filter.is_empty()
+                || member.name.to_lowercase().contains(&filter)
+                || member.class_name.to_lowercase().contains(&filter)
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if let Some(sort) = ui_state.member_sort {
+        visible_rows.sort_by(|a, b| {
+            let ordering = sort.compare(&ui_state.member_rows[*a], &ui_state.member_rows[*b]);
+            if ui_state.member_sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+    visible_rows
+}
+
+// Actions on the selected member, or the reason the action is not available
+pub struct ClanMemberActions {
+    pub expel: Result<ClanActionConfirm, &'static str>,
+    pub promote: Result<ClanActionConfirm, &'static str>,
+    pub demote: Result<ClanActionConfirm, &'static str>,
+    pub entrust: Result<ClanActionConfirm, &'static str>,
+}
+
+pub fn get_member_actions(
+    clan: &Clan,
+    clan_membership: &ClanMembership,
+    game_data: &GameData,
+    selected_member_name: Option<&str>,
+    player_name: Option<&str>,
+    entrust_supported: bool,
+) -> ClanMemberActions {
+    let Some(selected_member_name) = selected_member_name else {
+        return ClanMemberActions {
+            expel: Err("Select a member first."),
+            promote: Err("Select a member first."),
+            demote: Err("Select a member first."),
+            entrust: Err("Select a member first."),
+        };
+    };
+    let Some(selected_member) = clan.find_member(selected_member_name) else {
+        return ClanMemberActions {
+            expel: Err("Selected member is no longer in this clan."),
+            promote: Err("Selected member is no longer in this clan."),
+            demote: Err("Selected member is no longer in this clan."),
+            entrust: Err("Selected member is no longer in this clan."),
+        };
+    };
+
+    let is_self = player_name.map_or(false, |name| selected_member_name == name);
+    let can_manage_members = matches!(
+        clan_membership.position,
+        ClanMemberPosition::Master | ClanMemberPosition::DeputyMaster
+    );
+    let actor_rank = position_to_rank(clan_membership.position);
+    let target_rank = position_to_rank(selected_member.position);
+
+    let expel = if is_self {
+        Err("You cannot expel yourself.")
+    } else if selected_member.position == ClanMemberPosition::Master {
+        Err("You cannot expel the clan master.")
+    } else {
+        Ok(ClanActionConfirm::Expel {
+            name: selected_member_name.to_string(),
+        })
+    };
+
+    let promote = if is_self {
+        Err("You cannot promote yourself.")
+    } else if !can_manage_members {
+        Err("Only clan master and deputy master can promote members.")
+    } else if target_rank >= actor_rank {
+        Err("You can only promote members below your rank.")
+    } else if target_rank
+        .checked_add(1)
+        .map_or(true, |rank| rank >= actor_rank)
+    {
+        Err("You cannot promote a member to your rank.")
+    } else if let Some(next_position) =
+        next_promoted_position(clan_membership.position, selected_member.position)
+    {
+        Ok(ClanActionConfirm::Promote {
+            name: selected_member_name.to_string(),
+            next_position_label: clan_position_name(game_data, next_position),
+        })
+    } else {
+        Err("Selected member cannot be promoted.")
+    };
+
+    let demote = if is_self {
+        Err("You cannot demote yourself.")
+    } else if !can_manage_members {
+        Err("Only clan master and deputy master can demote members.")
+    } else if target_rank >= actor_rank {
+        Err("You can only demote members below your rank.")
+    } else if target_rank == 0 {
+        Err("Selected member is already at the lowest rank.")
+    } else if let Some(next_position) =
+        next_demoted_position(clan_membership.position, selected_member.position)
+    {
+        Ok(ClanActionConfirm::Demote {
+            name: selected_member_name.to_string(),
+            next_position_label: clan_position_name(game_data, next_position),
+        })
+    } else {
+        Err("Selected member cannot be demoted.")
+    };
+
+    let entrust = if !entrust_supported {
+        Err("This server does not support handing over the clan.")
+    } else if clan_membership.position != ClanMemberPosition::Master {
+        Err("Only the clan master can hand over the clan.")
+    } else if is_self {
+        Err("You are already the clan master.")
+    } else {
+        Ok(ClanActionConfirm::Entrust {
+            name: selected_member_name.to_string(),
+        })
+    };
+
+    ClanMemberActions {
+        expel,
+        promote,
+        demote,
+        entrust,
+    }
+}
+
+// The list item and its context menu share this id, so the context menu can be
+// shown by draw_member_tab after the list has been drawn
+fn member_list_item_id(index: i32) -> egui::Id {
+    egui::Id::new("clan_member_list_item").with(index)
+}
+
+fn get_member_list_item_rect(
+    list_rect: egui::Rect,
+    index: i32,
+    scroll: i32,
+    item_height: f32,
+) -> egui::Rect {
+    egui::Rect::from_min_size(
+        list_rect.min + egui::vec2(0.0, (index - scroll) as f32 * item_height),
+        egui::vec2(list_rect.width(), item_height),
+    )
+}
+
+pub fn draw_member_list_item(
+    ui: &mut egui::Ui,
+    member: Option<&ClanMemberRow>,
+    index: i32,
+    rect: egui::Rect,
+    selected: bool,
+) -> egui::Response {
+    ui.allocate_rect(rect, egui::Sense::hover());
+    let response = ui.interact(rect, member_list_item_id(index), egui::Sense::click());
+    let Some(member) = member else {
+        return response;
+    };
+
+    if selected {
+        ui.painter().rect_filled(rect, 0.0, CLAN_SELECTED_COLOR);
+    }
+
+    let status_color = if member.is_online {
+        egui::Color32::from_rgb(95, 230, 116)
+    } else {
+        egui::Color32::from_rgb(142, 142, 142)
+    };
+    let name_color = if selected {
+        egui::Color32::YELLOW
+    } else {
+        CLAN_VALUE_COLOR
+    };
+    let width = rect.width();
+    paint_list_text(ui, rect, 0.0, width * 0.38, &member.name, name_color);
+    paint_list_text(
+        ui,
+        rect,
+        width * 0.38,
+        width * 0.27,
+        &member.rank,
+        CLAN_VALUE_COLOR,
+    );
+    paint_list_text(
+        ui,
+        rect,
+        width * 0.65,
+        width * 0.12,
+        &member.level,
+        CLAN_VALUE_COLOR,
+    );
+    paint_list_text(
+        ui,
+        rect,
+        width * 0.77,
+        width * 0.23,
+        &member.status,
+        status_color,
+    );
+
+    response.on_hover_text(format!(
+        "{}\nContribution: {}",
+        member.class_name, member.contribution
+    ))
+}
+
+fn draw_member_context_menu(
+    ui: &mut egui::Ui,
+    member: &ClanMemberRow,
+    player_name: Option<&str>,
+    trade_supported: bool,
+) -> Option<ClanAction> {
+    let is_self = player_name.map_or(false, |name| name == member.name);
+    let can_interact = member.is_online && !is_self;
+    let mut action = None;
+
+    if ui
+        .add_enabled(can_interact, egui::Button::new("Whisper"))
+        .clicked()
+    {
+        action = Some(ClanAction::Whisper(member.name.clone()));
+    }
+
+    if ui
+        .add_enabled(can_interact, egui::Button::new("Invite to Party"))
+        .clicked()
+    {
+        action = Some(ClanAction::InviteToParty(member.name.clone()));
+    }
+
+    if ui
+        .add_enabled(
+            can_interact && trade_supported,
+            egui::Button::new("Request Trade"),
+        )
+        .on_disabled_hover_text(if trade_supported {
+            "The member must be online to trade."
+        } else {
+            "This server does not support trade requests."
+        })
+        .clicked()
+    {
+        action = Some(ClanAction::RequestTrade(member.name.clone()));
+    }
+
+    if ui.button("View Info").clicked() {
+        action = Some(ClanAction::ViewInfo(member.name.clone()));
+    }
+
+    if action.is_some() {
+        ui.close_menu();
+    }
+    action
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_member_tab(
+    ui: &mut egui::Ui,
+    dialog: &Dialog,
+    ui_state: &mut UiStateClan,
+    visible_member_rows: &[usize],
+    member_extent: i32,
+    member_item_height: f32,
+    player_name: Option<&str>,
+    trade_supported: bool,
+) -> Option<ClanAction> {
+    let list_rect = get_zlist_rect(dialog, IID_ZLIST_MEMBER)?;
+    let origin = ui.min_rect().min;
+    let mut action = None;
+
+    let filter_rect = egui::Rect::from_min_size(
+        origin + egui::vec2(list_rect.min.x, list_rect.min.y - 24.0),
+        egui::vec2(list_rect.width(), 22.0),
+    );
+    ui.allocate_ui_at_rect(filter_rect, |ui| {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.0;
+            ui.add(
+                egui::TextEdit::singleline(&mut ui_state.member_filter)
+                    .hint_text("Filter by name or class")
+                    .desired_width(list_rect.width() * 0.45),
+            );
+            ui.checkbox(&mut ui_state.member_online_only, "Online");
+            egui::ComboBox::from_id_source("clan_member_sort")
+                .width(80.0)
+                .selected_text(ui_state.member_sort.map_or("Sort", |sort| sort.name()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut ui_state.member_sort, None, "-");
+                    for sort in ClanMemberSort::ALL {
+                        ui.selectable_value(&mut ui_state.member_sort, Some(sort), sort.name());
+                    }
+                });
+            if ui_state.member_sort.is_some() {
+                ui.checkbox(&mut ui_state.member_sort_descending, "Desc");
+            }
+        });
+    });
+
+    if visible_member_rows.is_empty() {
+        ui.add_label_in(
+            list_rect,
+            egui::RichText::new(if ui_state.member_rows.is_empty() {
+                "No members available."
+            } else {
+                "No members match the filter."
+            })
+            .color(CLAN_LABEL_COLOR),
+        );
+    }
+
+    let scroll = ui_state.member_scroll;
+    for index in scroll..(scroll + member_extent).min(visible_member_rows.len() as i32) {
+        let member = &ui_state.member_rows[visible_member_rows[index as usize]];
+        let rect = get_member_list_item_rect(
+            list_rect.translate(origin.to_vec2()),
+            index,
+            scroll,
+            member_item_height,
+        );
+        ui.interact(rect, member_list_item_id(index), egui::Sense::click())
+            .context_menu(|ui| {
+                if let Some(member_action) =
+                    draw_member_context_menu(ui, member, player_name, trade_supported)
+                {
+                    action = Some(member_action);
+                }
+            });
+    }
+
+    action
+}
@@ -0,0 +1,978 @@
+use bevy::prelude::{
+    Assets, Changed, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With, Without,
+};
+use bevy_egui::{egui, EguiContexts};
+use rose_data::{ClanMemberPosition, SkillId};
+use rose_game_common::messages::client::ClientMessage;
+
+use crate::{
+    components::{
+        Clan, ClanMembership, ClientEntity, ClientEntityName, ClientEntityType, PartyInfo,
+        PlayerCharacter,
+    },
+    events::{ChatboxEvent, ClanDialogEvent},
+    protocol::{ExtendedClientMessage, ProtocolCapabilities},
+    resources::{
+        ClientCapabilities, ClientEntityRegistry, GameConnection, GameData, SelectedTarget,
+        UiResources,
+    },
+    ui::{
+        format::format_number,
+        set_chatbox_text,
+        widgets::{DataBindings, Dialog, Widget},
+        UiSkin, UiSoundEvent, UiStateWindows, UiWindowId,
+    },
+};
+
+mod info_tab;
+mod mark_dialog;
+mod member_tab;
+mod notice_tab;
+mod skill_tab;
+
+use info_tab::draw_info_tab;
+use mark_dialog::{draw_clan_mark_dialog, ClanMarkEdit};
+use member_tab::{
+    build_member_rows, draw_member_list_item, draw_member_tab, get_member_actions,
+    get_visible_member_rows, ClanMemberRow, ClanMemberSort,
+};
+use notice_tab::{draw_notice_list_item, draw_notice_tab};
+use skill_tab::{draw_skill_list_item, draw_skill_tab, get_visible_clan_skills};
+
+const IID_BTN_CLOSE: i32 = 10;
+// const IID_BTN_ICONIZE: i32 = 11;
+const IID_TABBEDPANE: i32 = 20;
+
+const IID_TAB_INFO: i32 = 100;
+// const IID_BTN_TAB_INFO: i32 = 101;
+
+const IID_TAB_MEMBER: i32 = 200;
+// const IID_BTN_TAB_MEMBER: i32 = 201;
+const IID_BTN_ENTRUST: i32 = 210;
+const IID_BTN_BAN: i32 = 211;
+const IID_BTN_CLASS_UP: i32 = 212;
+const IID_BTN_CLASS_DOWN: i32 = 213;
+const IID_BTN_REQJOIN: i32 = 214;
+const IID_BTN_WITHDRAWAL: i32 = 215;
+const IID_ZLIST_MEMBER: i32 = 220;
+// const IID_SCROLLBAR_MEMBER: i32 = 221;
+
+const IID_TAB_SKILL: i32 = 300;
+// const IID_BTN_TAB_SKILL: i32 = 301;
+const IID_ZLIST_SKILL: i32 = 310;
+// const IID_SCROLLBAR_SKILL: i32 = 311;
+
+const IID_TAB_NOTICE: i32 = 400;
+// const IID_BTN_TAB_NOTICE: i32 = 401;
+const IID_ZLIST_NOTICE: i32 = 410;
+// const IID_SCROLLBAR_NOTICE: i32 = 411;
+const IID_BTN_REGIST_NOTICE: i32 = 420;
+const IID_BTN_DELETE_NOTICE: i32 = 421;
+
+const CLAN_LABEL_COLOR: egui::Color32 = egui::Color32::from_rgb(214, 214, 214);
+const CLAN_VALUE_COLOR: egui::Color32 = egui::Color32::from_rgb(240, 240, 240);
+const CLAN_SELECTED_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(80, 60, 20, 120);
+
+#[derive(Clone)]
+pub enum ClanActionConfirm {
+    Leave,
+    Expel {
+        name: String,
+    },
+    Promote {
+        name: String,
+        next_position_label: String,
+    },
+    Demote {
+        name: String,
+        next_position_label: String,
+    },
+    DeclareWar {
+        opponent: String,
+    },
+    Surrender {
+        opponent: String,
+    },
+    Entrust {
+        name: String,
+    },
+}
+
+// Actions chosen while drawing the tabs, handled after the window is drawn
+pub enum ClanAction {
+    Confirm(ClanActionConfirm),
+    SetDescription(String),
+    EditMark,
+    Whisper(String),
+    InviteToParty(String),
+    RequestTrade(String),
+    ViewInfo(String),
+    UseSkill(SkillId),
+}
+
+pub struct UiStateClan {
+    current_tab: i32,
+    member_scroll: i32,
+    skill_scroll: i32,
+    notice_scroll: i32,
+    selected_notice_index: i32,
+    selected_skill_id: Option<SkillId>,
+    had_clan_last_frame: bool,
+    is_editing_slogan: bool,
+    slogan_edit_buffer: String,
+    notice_edit_buffer: String,
+    war_opponent_buffer: String,
+    selected_member_name: Option<String>,
+    pending_action_confirm: Option<ClanActionConfirm>,
+    mark_edit: Option<ClanMarkEdit>,
+    info_member_name: Option<String>,
+    member_rows: Vec<ClanMemberRow>,
+    member_sort: Option<ClanMemberSort>,
+    member_sort_descending: bool,
+    member_filter: String,
+    member_online_only: bool,
+}
+
+impl Default for UiStateClan {
+    fn default() -> Self {
+        Self {
+            current_tab: IID_TAB_INFO,
+            member_scroll: 0,
+            skill_scroll: 0,
+            notice_scroll: 0,
+            selected_notice_index: -1,
+            selected_skill_id: None,
+            had_clan_last_frame: false,
+            is_editing_slogan: false,
+            slogan_edit_buffer: String::new(),
+            notice_edit_buffer: String::new(),
+            war_opponent_buffer: String::new(),
+            selected_member_name: None,
+            pending_action_confirm: None,
+            mark_edit: None,
+            info_member_name: None,
+            member_rows: Vec::new(),
+            member_sort: None,
+            member_sort_descending: false,
+            member_filter: String::new(),
+            member_online_only: false,
+        }
+    }
+}
+
+fn position_to_rank(position: ClanMemberPosition) -> u8 {
+    match position {
+        ClanMemberPosition::Penalty => 0,
+        ClanMemberPosition::Junior => 1,
+        ClanMemberPosition::Senior => 2,
+        ClanMemberPosition::Veteran => 3,
+        ClanMemberPosition::Commander => 4,
+        ClanMemberPosition::DeputyMaster => 5,
+        ClanMemberPosition::Master => 6,
+    }
+}
+
+pub fn clan_position_name(game_data: &GameData, position: ClanMemberPosition) -> String {
+    let name = game_data
+        .string_database
+        .get_clan_member_position(position)
+        .trim();
+    if !name.is_empty() {
+        return name.to_string();
+    }
+
+    match position {
+        ClanMemberPosition::Penalty => "Penalty".to_string(),
+        ClanMemberPosition::Junior => "Junior".to_string(),
+        ClanMemberPosition::Senior => "Senior".to_string(),
+        ClanMemberPosition::Veteran => "Veteran".to_string(),
+        ClanMemberPosition::Commander => "Commander".to_string(),
+        ClanMemberPosition::DeputyMaster => "Deputy Master".to_string(),
+        ClanMemberPosition::Master => "Master".to_string(),
+    }
+}
+
+fn add_clan_button(
+    ui: &mut egui::Ui,
+    ui_skin: Option<&UiSkin>,
+    enabled: bool,
+    text: &str,
+) -> egui::Response {
+    ui.add_enabled_ui(enabled, |ui| match ui_skin {
+        Some(ui_skin) => ui_skin.button(ui, text),
+        None => ui.button(text),
+    })
+    .inner
+}
+
+// Number of visible items and the height of each item in a list
+fn get_zlist_metrics(dialog: &Dialog, id: i32) -> (i32, f32) {
+    if let Some(Widget::ZListbox(zlistbox)) = dialog.get_widget(id) {
+        let extent = zlistbox.extent.max(1);
+        (extent, zlistbox.height / extent as f32)
+    } else {
+        (1, 20.0)
+    }
+}
+
+// Dialog relative rect of a list in the tabbed pane, used to place the controls
+// which do not exist in DLGCLAN.XML next to it
+fn get_zlist_rect(dialog: &Dialog, id: i32) -> Option<egui::Rect> {
+    let Some(Widget::TabbedPane(tabbed_pane)) = dialog.get_widget(IID_TABBEDPANE) else {
+        return None;
+    };
+    let Some(Widget::ZListbox(zlistbox)) = dialog.get_widget(id) else {
+        return None;
+    };
+
+    Some(zlistbox.widget_rect(egui::pos2(tabbed_pane.x, tabbed_pane.y)))
+}
+
+fn get_list_row_rect(ui: &egui::Ui, index: i32, scroll: i32, item_height: f32) -> egui::Rect {
+    egui::Rect::from_min_size(
+        ui.min_rect().min + egui::vec2(0.0, (index - scroll) as f32 * item_height),
+        egui::vec2(ui.max_rect().width(), item_height),
+    )
+}
+
+fn paint_list_text(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    x: f32,
+    width: f32,
+    text: &str,
+    color: egui::Color32,
+) {
+    let column_rect = egui::Rect::from_min_size(
+        rect.min + egui::vec2(x, 0.0),
+        egui::vec2(width, rect.height()),
+    )
+    .intersect(rect);
+    ui.painter().with_clip_rect(column_rect).text(
+        column_rect.left_center() + egui::vec2(2.0, 0.0),
+        egui::Align2::LEFT_CENTER,
+        text,
+        egui::FontId::proportional(12.0),
+        color,
+    );
+}
+
+fn resolve_invite_target(
+    selected_target: &SelectedTarget,
+    client_entity_registry: &ClientEntityRegistry,
+    player_entity: Option<Entity>,
+) -> (Option<String>, String) {
+    let Some(selected_entity) = selected_target.selected else {
+        return (None, "No target selected.".to_string());
+    };
+
+    if Some(selected_entity) == player_entity {
+        return (None, "You cannot invite yourself.".to_string());
+    }
+
+    let Some(target) = client_entity_registry.get(selected_entity) else {
+        return (None, "Invalid selected target.".to_string());
+    };
+
+    if target.entity_type != ClientEntityType::Character {
+        return (None, "Target must be a character.".to_string());
+    }
+
+    (Some(target.name.clone()), String::new())
+}
+
+fn draw_clan_action_confirm_dialog(
+    ctx: &egui::Context,
+    ui_skin: Option<&UiSkin>,
+    ui_state: &mut UiStateClan,
+    game_connection: Option<&GameConnection>,
+) {
+    let Some(pending_action) = ui_state.pending_action_confirm.clone() else {
+        return;
+    };
+
+    let mut window_open = true;
+    let mut cancel_clicked = false;
+    let mut confirmed = false;
+
+    let message = match &pending_action {
+        ClanActionConfirm::Leave => "Leave clan?".to_string(),
+        ClanActionConfirm::Expel { name } => format!("Expel {}?", name),
+        ClanActionConfirm::Promote {
+            name,
+            next_position_label,
+        } => format!("Promote {} to {}?", name, next_position_label),
+        ClanActionConfirm::Demote {
+            name,
+            next_position_label,
+        } => format!("Demote {} to {}?", name, next_position_label),
+        ClanActionConfirm::DeclareWar { opponent } => {
+            format!("Declare war on the clan {}?", opponent)
+        }
+        ClanActionConfirm::Surrender { opponent } => {
+            format!("Surrender the war against {}?", opponent)
+        }
+        ClanActionConfirm::Entrust { name } => {
+            format!("Hand over the clan to {}?", name)
+        }
+    };
+
+    let mut window = egui::Window::new("Confirm")
+        .id(egui::Id::new("clan_action_confirm_dialog"))
+        .collapsible(false)
+        .resizable(false)
+        .pivot(egui::Align2::CENTER_CENTER)
+        .default_pos(ctx.screen_rect().center());
+    if let Some(ui_skin) = ui_skin {
+        window = window.frame(ui_skin.window_frame()).title_bar(false);
+    }
+
+    window.open(&mut window_open).show(ctx, |ui| {
+        if let Some(ui_skin) = ui_skin {
+            ui_skin.begin_window(ui);
+            cancel_clicked = ui_skin.title_bar(ui, "Confirm");
+        }
+
+        ui.label(&message);
+        ui.add_space(10.0);
+        ui.horizontal(|ui| {
+            if add_clan_button(ui, ui_skin, true, "Confirm").clicked() {
+                confirmed = true;
+            }
+            if add_clan_button(ui, ui_skin, true, "Cancel").clicked() {
+                cancel_clicked = true;
+            }
+        });
+    });
+
+    if confirmed {
+        if let Some(game_connection) = game_connection {
+            match pending_action {
+                ClanActionConfirm::Leave => {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::ClanLeave)
+                        .ok();
+                }
+                ClanActionConfirm::Expel { name } => {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::ClanKick { name })
+                        .ok();
+                }
+                ClanActionConfirm::Promote { name, .. } => {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::ClanPromote { name })
+                        .ok();
+                }
+                ClanActionConfirm::Demote { name, .. } => {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::ClanDemote { name })
+                        .ok();
+                }
+                ClanActionConfirm::DeclareWar { opponent } => {
+                    game_connection
+                        .extended_client_message_tx
+                        .send(ExtendedClientMessage::ClanWarDeclare(opponent))
+                        .ok();
+                }
+                ClanActionConfirm::Surrender { opponent } => {
+                    game_connection
+                        .extended_client_message_tx
+                        .send(ExtendedClientMessage::ClanWarSurrender(opponent))
+                        .ok();
+                }
+                ClanActionConfirm::Entrust { name } => {
+                    game_connection
+                        .extended_client_message_tx
+                        .send(ExtendedClientMessage::ClanEntrust(name))
+                        .ok();
+                }
+            }
+        }
+        ui_state.pending_action_confirm = None;
+    } else if !window_open || cancel_clicked {
+        ui_state.pending_action_confirm = None;
+    }
+}
+
+pub fn ui_clan_system(
+    mut egui_context: EguiContexts,
+    query_clan: Query<(&Clan, &ClanMembership), With<PlayerCharacter>>,
+    query_clan_changed: Query<(), (With<PlayerCharacter>, Changed<Clan>)>,
+    query_player: Query<(Entity, &ClientEntityName, Option<&PartyInfo>), With<PlayerCharacter>>,
+    query_characters: Query<(&ClientEntity, &ClientEntityName), Without<PlayerCharacter>>,
+    mut ui_state: Local<UiStateClan>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    mut clan_dialog_events: EventReader<ClanDialogEvent>,
+    mut chatbox_events: EventWriter<ChatboxEvent>,
+    game_data: Res<GameData>,
+    selected_target: Res<SelectedTarget>,
+    client_entity_registry: Res<ClientEntityRegistry>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    (game_connection, client_capabilities): (Option<Res<GameConnection>>, Res<ClientCapabilities>),
+) {
+    let ui_state = &mut *ui_state;
+    let clan_result = query_clan.get_single();
+    let player = query_player.get_single().ok();
+    let player_name = player.map(|(_, name, _)| name.name.as_str());
+
+    for event in clan_dialog_events.iter() {
+        if matches!(event, ClanDialogEvent::Open) && clan_result.is_ok() {
+            ui_state_windows.set_open(UiWindowId::CLAN, true);
+        }
+    }
+
+    let has_clan = clan_result.is_ok();
+    if ui_state.had_clan_last_frame && !has_clan {
+        ui_state.is_editing_slogan = false;
+        ui_state.slogan_edit_buffer.clear();
+        ui_state.notice_edit_buffer.clear();
+        ui_state.selected_member_name = None;
+        ui_state.selected_notice_index = -1;
+        ui_state.selected_skill_id = None;
+        ui_state.pending_action_confirm = None;
+        ui_state.mark_edit = None;
+        ui_state.info_member_name = None;
+        ui_state.member_rows.clear();
+    }
+    ui_state.had_clan_last_frame = has_clan;
+
+    let Ok((clan, clan_membership)) = clan_result else {
+        ui_state_windows.set_open(UiWindowId::CLAN, false);
+        return;
+    };
+
+    if !query_clan_changed.is_empty() || ui_state.member_rows.len() != clan.members.len() {
+        ui_state.member_rows = build_member_rows(&game_data, clan);
+    }
+
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_clan) {
+        dialog
+    } else {
+        return;
+    };
+    let ui_skin = UiSkin::new(&ui_resources, &dialog_assets);
+
+    if ui_state
+        .selected_member_name
+        .as_ref()
+        .map_or(false, |selected_name| {
+            clan.find_member(selected_name).is_none()
+        })
+    {
+        ui_state.selected_member_name = None;
+    }
+    if ui_state.selected_notice_index >= clan.notices.len() as i32 {
+        ui_state.selected_notice_index = -1;
+    }
+
+    let is_master = clan_membership.position == ClanMemberPosition::Master;
+    let can_manage_members = matches!(
+        clan_membership.position,
+        ClanMemberPosition::Master | ClanMemberPosition::DeputyMaster
+    );
+    let can_post_notices =
+        can_manage_members && client_capabilities.supports(ProtocolCapabilities::CLAN_NOTICES);
+    let can_leave = !(is_master && clan.members.len() > 1);
+    let (invite_target_name, invite_invalid_reason) = resolve_invite_target(
+        &selected_target,
+        &client_entity_registry,
+        player.map(|(entity, _, _)| entity),
+    );
+    let member_actions = get_member_actions(
+        clan,
+        clan_membership,
+        &game_data,
+        ui_state.selected_member_name.as_deref(),
+        player_name,
+        client_capabilities.supports(ProtocolCapabilities::CLAN_ENTRUST),
+    );
+
+    let visible_member_rows = get_visible_member_rows(ui_state);
+    let mut selected_member_index = ui_state
+        .selected_member_name
+        .as_ref()
+        .and_then(|selected_name| {
+            visible_member_rows
+                .iter()
+                .position(|&row| &ui_state.member_rows[row].name == selected_name)
+        })
+        .map_or(-1, |index| index as i32);
+    let visible_skills = get_visible_clan_skills(&game_data, clan);
+    let mut selected_skill_index = ui_state
+        .selected_skill_id
+        .and_then(|selected_skill_id| {
+            visible_skills
+                .iter()
+                .position(|&skill_id| skill_id == selected_skill_id)
+        })
+        .map_or(-1, |index| index as i32);
+    let mut clan_action = None;
+
+    let (member_extent, member_item_height) = get_zlist_metrics(dialog, IID_ZLIST_MEMBER);
+    let (skill_extent, skill_item_height) = get_zlist_metrics(dialog, IID_ZLIST_SKILL);
+    let (notice_extent, notice_item_height) = get_zlist_metrics(dialog, IID_ZLIST_NOTICE);
+
+    // The member list shrinks when filtered, so keep the scroll position in range
+    ui_state.member_scroll = ui_state
+        .member_scroll
+        .min(visible_member_rows.len() as i32 - member_extent)
+        .max(0);
+    ui_state.skill_scroll = ui_state
+        .skill_scroll
+        .min(visible_skills.len() as i32 - skill_extent)
+        .max(0);
+    ui_state.notice_scroll = ui_state
+        .notice_scroll
+        .min(clan.notices.len() as i32 - notice_extent)
+        .max(0);
+    let member_scroll = ui_state.member_scroll;
+    let skill_scroll = ui_state.skill_scroll;
+    let notice_scroll = ui_state.notice_scroll;
+    let notice_text = ui_state.notice_edit_buffer.trim().to_string();
+
+    let mut response_close_button = None;
+    let mut response_ban_button = None;
+    let mut response_class_up_button = None;
+    let mut response_class_down_button = None;
+    let mut response_reqjoin_button = None;
+    let mut response_withdrawal_button = None;
+    let mut response_entrust_button = None;
+    let mut response_regist_notice_button = None;
+    let mut response_delete_notice_button = None;
+
+    egui::Window::new("Clan")
+        .frame(egui::Frame::none())
+        .open(ui_state_windows.open_mut(UiWindowId::CLAN))
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    visible: &mut [
+                        (IID_BTN_BAN, can_manage_members),
+                        (IID_BTN_CLASS_UP, can_manage_members),
+                        (IID_BTN_CLASS_DOWN, can_manage_members),
+                        (IID_BTN_REQJOIN, can_manage_members),
+                        (IID_BTN_ENTRUST, is_master),
+                        (IID_BTN_REGIST_NOTICE, can_post_notices),
+                        (IID_BTN_DELETE_NOTICE, can_post_notices),
+                    ],
+                    enabled: &mut [
+                        (IID_BTN_BAN, member_actions.expel.is_ok()),
+                        (IID_BTN_CLASS_UP, member_actions.promote.is_ok()),
+                        (IID_BTN_CLASS_DOWN, member_actions.demote.is_ok()),
+                        (IID_BTN_REQJOIN, invite_target_name.is_some()),
+                        (IID_BTN_WITHDRAWAL, can_leave),
+                        (IID_BTN_ENTRUST, member_actions.entrust.is_ok()),
+                        (IID_BTN_REGIST_NOTICE, !notice_text.is_empty()),
+                        (IID_BTN_DELETE_NOTICE, ui_state.selected_notice_index >= 0),
+                    ],
+                    tabs: &mut [(IID_TABBEDPANE, &mut ui_state.current_tab)],
+                    scroll: &mut [
+                        (
+                            IID_ZLIST_MEMBER,
+                            (
+                                &mut ui_state.member_scroll,
+                                0..visible_member_rows.len() as i32,
+                                member_extent,
+                            ),
+                        ),
+                        (
+                            IID_ZLIST_SKILL,
+                            (
+                                &mut ui_state.skill_scroll,
+                                0..visible_skills.len() as i32,
+                                skill_extent,
+                            ),
+                        ),
+                        (
+                            IID_ZLIST_NOTICE,
+                            (
+                                &mut ui_state.notice_scroll,
+                                0..clan.notices.len() as i32,
+                                notice_extent,
+                            ),
+                        ),
+                    ],
+                    zlist: &mut [
+                        (
+                            IID_ZLIST_MEMBER,
+                            (&mut selected_member_index, &|ui, index, selected| {
+                                draw_member_list_item(
+                                    ui,
+                                    visible_member_rows
+                                        .get(index as usize)
+                                        .map(|&row| &ui_state.member_rows[row]),
+                                    index,
+                                    get_list_row_rect(ui, index, member_scroll, member_item_height),
+                                    selected,
+                                )
+                            }),
+                        ),
+                        (
+                            IID_ZLIST_SKILL,
+                            (&mut selected_skill_index, &|ui, index, selected| {
+                                draw_skill_list_item(
+                                    ui,
+                                    visible_skills.get(index as usize).copied(),
+                                    get_list_row_rect(ui, index, skill_scroll, skill_item_height),
+                                    selected,
+                                    clan,
+                                    &game_data,
+                                    &ui_resources,
+                                )
+                            }),
+                        ),
+                        (
+                            IID_ZLIST_NOTICE,
+                            (
+                                &mut ui_state.selected_notice_index,
+                                &|ui, index, selected| {
+                                    draw_notice_list_item(
+                                        ui,
+                                        clan.notices.get(index as usize),
+                                        get_list_row_rect(
+                                            ui,
+                                            index,
+                                            notice_scroll,
+                                            notice_item_height,
+                                        ),
+                                        selected,
+                                    )
+                                },
+                            ),
+                        ),
+                    ],
+                    response: &mut [
+                        (IID_BTN_CLOSE, &mut response_close_button),
+                        (IID_BTN_BAN, &mut response_ban_button),
+                        (IID_BTN_CLASS_UP, &mut response_class_up_button),
+                        (IID_BTN_CLASS_DOWN, &mut response_class_down_button),
+                        (IID_BTN_REQJOIN, &mut response_reqjoin_button),
+                        (IID_BTN_WITHDRAWAL, &mut response_withdrawal_button),
+                        (IID_BTN_ENTRUST, &mut response_entrust_button),
+                        (IID_BTN_REGIST_NOTICE, &mut response_regist_notice_button),
+                        (IID_BTN_DELETE_NOTICE, &mut response_delete_notice_button),
+                    ],
+                    ..Default::default()
+                },
+                |_, _| {},
+            );
+
+            // Contents which are not part of the dialog widget tree
+            if let Some(ui_skin) = ui_skin.as_ref() {
+                ui_skin.apply_style(ui);
+            }
+
+            clan_action = match ui_state.current_tab {
+                IID_TAB_INFO => draw_info_tab(
+                    ui,
+                    dialog,
+                    clan,
+                    clan_membership,
+                    &game_data,
+                    &ui_resources,
+                    ui_skin.as_ref(),
+                    ui_state,
+                    client_capabilities.supports(ProtocolCapabilities::CLAN_WARS),
+                    client_capabilities.supports(ProtocolCapabilities::CLAN_MARKS),
+                ),
+                IID_TAB_MEMBER => draw_member_tab(
+                    ui,
+                    dialog,
+                    ui_state,
+                    &visible_member_rows,
+                    member_extent,
+                    member_item_height,
+                    player_name,
+                    client_capabilities.supports(ProtocolCapabilities::TRADE_REQUESTS),
+                ),
+                IID_TAB_SKILL => draw_skill_tab(
+                    ui,
+                    dialog,
+                    clan,
+                    clan_membership,
+                    &game_data,
+                    ui_skin.as_ref(),
+                    ui_state.selected_skill_id,
+                    &visible_skills,
+                    client_capabilities.supports(ProtocolCapabilities::CLAN_SKILLS),
+                ),
+                IID_TAB_NOTICE => {
+                    draw_notice_tab(
+                        ui,
+                        dialog,
+                        clan,
+                        ui_state,
+                        can_post_notices,
+                        client_capabilities.supports(ProtocolCapabilities::CLAN_NOTICES),
+                    );
+                    None
+                }
+                _ => None,
+            };
+        });
+
+    match clan_action {
+        Some(ClanAction::Confirm(action)) => {
+            ui_state.pending_action_confirm = Some(action);
+        }
+        Some(ClanAction::SetDescription(description)) => {
+            if let Some(game_connection) = game_connection.as_ref() {
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::ClanSetDescription { description })
+                    .ok();
+            }
+        }
+        Some(ClanAction::EditMark) => {
+            ui_state.mark_edit = Some(ClanMarkEdit::new(&clan.mark));
+        }
+        Some(ClanAction::Whisper(name)) => {
+            set_chatbox_text(egui_context.ctx_mut(), format!("@{} ", name));
+        }
+        Some(ClanAction::InviteToParty(name)) => {
+            // Party invites are sent by entity id so the member must be nearby
+            let target = query_characters
+                .iter()
+                .find(|(client_entity, entity_name)| {
+                    client_entity.entity_type == ClientEntityType::Character
+                        && entity_name.name == name
+                });
+
+            if let Some((target_client_entity, _)) = target {
+                if let Some(game_connection) = game_connection.as_ref() {
+                    let message = if player.map_or(true, |(_, _, party_info)| party_info.is_none())
+                    {
+                        ClientMessage::PartyCreate {
+                            invited_entity_id: target_client_entity.id,
+                        }
+                    } else {
+                        ClientMessage::PartyInvite {
+                            invited_entity_id: target_client_entity.id,
+                        }
+                    };
+                    game_connection.client_message_tx.send(message).ok();
+                }
+            } else {
+                chatbox_events.send(ChatboxEvent::System(format!(
+                    "{} must be nearby to be invited to a party.",
+                    name
+                )));
+            }
+        }
+        Some(ClanAction::RequestTrade(name)) => {
+            // Trade requests are sent by entity id so the member must be nearby
+            let target = query_characters
+                .iter()
+                .find(|(client_entity, entity_name)| {
+                    client_entity.entity_type == ClientEntityType::Character
+                        && entity_name.name == name
+                });
+
+            if let Some((target_client_entity, _)) = target {
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .extended_client_message_tx
+                        .send(ExtendedClientMessage::TradeRequest(target_client_entity.id))
+                        .ok();
+                }
+            } else {
+                chatbox_events.send(ChatboxEvent::System(format!(
+                    "{} must be nearby to trade with.",
+                    name
+                )));
+            }
+        }
+        Some(ClanAction::ViewInfo(name)) => {
+            ui_state.info_member_name = Some(name);
+        }
+        Some(ClanAction::UseSkill(skill_id)) => {
+            // The server checks the requirements and takes the clan points
+            if let Some(game_connection) = game_connection.as_ref() {
+                game_connection
+                    .extended_client_message_tx
+                    .send(ExtendedClientMessage::ClanSkillActivate(skill_id))
+                    .ok();
+            }
+        }
+        None => {}
+    }
+
+    for (response, action) in [
+        (response_ban_button, &member_actions.expel),
+        (response_class_up_button, &member_actions.promote),
+        (response_class_down_button, &member_actions.demote),
+        (response_entrust_button, &member_actions.entrust),
+    ] {
+        match (response, action) {
+            (Some(response), Ok(action)) if response.clicked() => {
+                ui_state.pending_action_confirm = Some(action.clone());
+            }
+            (Some(response), Err(reason)) => {
+                response.on_hover_text(*reason);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(response) = response_reqjoin_button {
+        match invite_target_name {
+            Some(name) if response.clicked() => {
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::ClanInvite { name })
+                        .ok();
+                }
+            }
+            None => {
+                response.on_hover_text(invite_invalid_reason);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(response) = response_withdrawal_button {
+        if !can_leave {
+            response.on_hover_text("Clan master can only leave when they are the last member.");
+        } else if response.clicked() {
+            ui_state.pending_action_confirm = Some(ClanActionConfirm::Leave);
+        }
+    }
+
+    // The server sets the author and time of a notice and sends the updated notices to all members
+    if response_regist_notice_button.map_or(false, |r| r.clicked()) && !notice_text.is_empty() {
+        if let Some(game_connection) = game_connection.as_ref() {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::ClanNoticePost(notice_text))
+                .ok();
+        }
+        ui_state.notice_edit_buffer.clear();
+    }
+
+    if response_delete_notice_button.map_or(false, |r| r.clicked()) {
+        let notice = usize::try_from(ui_state.selected_notice_index)
+            .ok()
+            .and_then(|index| clan.notices.get(index));
+        if let (Some(notice), Some(game_connection)) = (notice, game_connection.as_ref()) {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::ClanNoticeDelete(notice.id))
+                .ok();
+        }
+        ui_state.selected_notice_index = -1;
+    }
+
+    if let Some(&row) = usize::try_from(selected_member_index)
+        .ok()
+        .and_then(|index| visible_member_rows.get(index))
+    {
+        ui_state.selected_member_name = Some(ui_state.member_rows[row].name.clone());
+    }
+    ui_state.selected_skill_id = usize::try_from(selected_skill_index)
+        .ok()
+        .and_then(|index| visible_skills.get(index))
+        .copied();
+
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.set_open(UiWindowId::CLAN, false);
+    }
+
+    if ui_state_windows.is_open(UiWindowId::CLAN) {
+        draw_clan_action_confirm_dialog(
+            egui_context.ctx_mut(),
+            ui_skin.as_ref(),
+            ui_state,
+            game_connection.as_deref(),
+        );
+        draw_clan_mark_dialog(
+            egui_context.ctx_mut(),
+            &ui_resources,
+            ui_skin.as_ref(),
+            ui_state,
+            game_connection.as_deref(),
+        );
+    } else {
+        ui_state.pending_action_confirm = None;
+        ui_state.mark_edit = None;
+    }
+
+    if let Some(info_member_name) = ui_state.info_member_name.clone() {
+        let mut info_open = true;
+        let mut window = egui::Window::new("Clan Member")
+            .id(egui::Id::new("clan_member_info"))
+            .collapsible(false)
+            .resizable(false);
+        if let Some(ui_skin) = ui_skin.as_ref() {
+            window = window.frame(ui_skin.window_frame()).title_bar(false);
+        }
+
+        let mut close_clicked = false;
+        window
+            .open(&mut info_open)
+            .show(egui_context.ctx_mut(), |ui| {
+                if let Some(ui_skin) = ui_skin.as_ref() {
+                    ui_skin.begin_window(ui);
+                    close_clicked = ui_skin.title_bar(ui, "Clan Member");
+                }
+
+                let Some(member) = clan.find_member(&info_member_name) else {
+                    ui.label(format!("{} is no longer in this clan.", info_member_name));
+                    return;
+                };
+
+                let class_name = game_data.string_database.get_job_name(member.job);
+                egui::Grid::new("clan_member_info_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Name");
+                        ui.label(&member.name);
+                        ui.end_row();
+
+                        ui.label("Rank");
+                        ui.label(clan_position_name(&game_data, member.position));
+                        ui.end_row();
+
+                        ui.label("Class");
+                        if class_name.is_empty() {
+                            ui.label(format!("Job {}", member.job));
+                        } else {
+                            ui.label(class_name);
+                        }
+                        ui.end_row();
+
+                        ui.label("Level");
+                        ui.label(format!("{}", member.level.level));
+                        ui.end_row();
+
+                        ui.label("Contribution");
+                        ui.label(format_number(member.contribution.0));
+                        ui.end_row();
+
+                        ui.label("Status");
+                        if let Some(channel_id) = member.channel_id {
+                            ui.label(format!("Online (Channel {})", channel_id));
+                        } else {
+                            ui.label("Offline");
+                        }
+                        ui.end_row();
+                    });
+            });
+
+        if !info_open || close_clicked {
+            ui_state.info_member_name = None;
+        }
+    }
+}
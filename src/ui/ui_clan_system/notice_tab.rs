@@ -0,0 +1,94 @@
+use bevy_egui::egui;
+
+use crate::{
+    components::{Clan, MAX_CLAN_NOTICE_LENGTH},
+    protocol::ClanNotice,
+    ui::widgets::{Dialog, DrawText},
+};
+
+use super::{
+    get_zlist_rect, paint_list_text, UiStateClan, CLAN_LABEL_COLOR, CLAN_SELECTED_COLOR,
+    CLAN_VALUE_COLOR, IID_ZLIST_NOTICE,
+};
+
+pub fn draw_notice_list_item(
+    ui: &mut egui::Ui,
+    notice: Option<&ClanNotice>,
+    rect: egui::Rect,
+    selected: bool,
+) -> egui::Response {
+    let response = ui.allocate_rect(rect, egui::Sense::click());
+    let Some(notice) = notice else {
+        return response;
+    };
+
+    if selected {
+        ui.painter().rect_filled(rect, 0.0, CLAN_SELECTED_COLOR);
+    }
+
+    let width = rect.width();
+    paint_list_text(ui, rect, 0.0, width * 0.3, &notice.author, CLAN_LABEL_COLOR);
+    paint_list_text(
+        ui,
+        rect,
+        width * 0.3,
+        width * 0.7,
+        &notice.text,
+        if selected {
+            egui::Color32::YELLOW
+        } else {
+            CLAN_VALUE_COLOR
+        },
+    );
+
+    let timestamp = chrono::DateTime::from_timestamp(notice.timestamp, 0)
+        .map(|timestamp| {
+            timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    response.on_hover_text(format!("{} {}\n{}", timestamp, notice.author, notice.text))
+}
+
+pub fn draw_notice_tab(
+    ui: &mut egui::Ui,
+    dialog: &Dialog,
+    clan: &Clan,
+    ui_state: &mut UiStateClan,
+    can_post_notices: bool,
+    notices_supported: bool,
+) {
+    let Some(list_rect) = get_zlist_rect(dialog, IID_ZLIST_NOTICE) else {
+        return;
+    };
+
+    if !notices_supported {
+        ui.add_label_in(
+            list_rect,
+            egui::RichText::new("This server does not support clan notices.")
+                .color(CLAN_LABEL_COLOR),
+        );
+        return;
+    }
+
+    if clan.notices.is_empty() {
+        ui.add_label_in(
+            list_rect,
+            egui::RichText::new("There are no notices.").color(CLAN_LABEL_COLOR),
+        );
+    }
+
+    if can_post_notices {
+        ui.put(
+            egui::Rect::from_min_size(
+                ui.min_rect().min + egui::vec2(list_rect.min.x, list_rect.max.y + 4.0),
+                egui::vec2(list_rect.width(), 20.0),
+            ),
+            egui::TextEdit::singleline(&mut ui_state.notice_edit_buffer)
+                .char_limit(MAX_CLAN_NOTICE_LENGTH)
+                .hint_text("Post a notice"),
+        );
+    }
+}
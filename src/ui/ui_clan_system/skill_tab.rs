@@ -0,0 +1,222 @@
+use bevy_egui::egui;
+use rose_data::{AbilityType, SkillData, SkillId, SkillType};
+
+use crate::{
+    components::{Clan, ClanMembership},
+    resources::{GameData, UiResources, UiSpriteSheetType},
+    ui::{
+        format::format_number,
+        tooltips::SkillTooltipType,
+        ui_add_skill_tooltip,
+        widgets::{Dialog, DrawText},
+        UiSkin,
+    },
+};
+
+use super::{
+    add_clan_button, get_zlist_rect, paint_list_text, position_to_rank, ClanAction,
+    CLAN_LABEL_COLOR, CLAN_SELECTED_COLOR, CLAN_VALUE_COLOR, IID_ZLIST_SKILL,
+};
+
+// Clan skills are paid for with clan points and each level of a clan skill
+// needs the clan to have reached the same grade
+pub struct ClanSkillRequirements {
+    pub clan_point_cost: i32,
+    pub clan_grade: u32,
+    pub rank: u8,
+}
+
+pub fn get_clan_skill_requirements(skill_data: &SkillData) -> ClanSkillRequirements {
+    ClanSkillRequirements {
+        clan_point_cost: skill_data
+            .use_ability
+            .iter()
+            .filter(|(ability_type, _)| matches!(ability_type, AbilityType::GuildScore))
+            .map(|&(_, value)| value)
+            .sum(),
+        clan_grade: skill_data.level,
+        rank: skill_data
+            .required_ability
+            .iter()
+            .filter(|(ability_type, _)| matches!(ability_type, AbilityType::GuildPosition))
+            .map(|&(_, value)| value.clamp(0, u8::MAX as i32) as u8)
+            .max()
+            .unwrap_or(0),
+    }
+}
+
+// The skills of the clan which are available at its current grade
+pub fn get_visible_clan_skills(game_data: &GameData, clan: &Clan) -> Vec<SkillId> {
+    clan.skills
+        .iter()
+        .copied()
+        .filter(|&skill_id| {
+            game_data
+                .skills
+                .get_skill(skill_id)
+                .map_or(true, |skill_data| {
+                    get_clan_skill_requirements(skill_data).clan_grade <= clan.level.0.get()
+                })
+        })
+        .collect()
+}
+
+pub fn draw_skill_list_item(
+    ui: &mut egui::Ui,
+    skill_id: Option<SkillId>,
+    rect: egui::Rect,
+    selected: bool,
+    clan: &Clan,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+) -> egui::Response {
+    let response = ui.allocate_rect(rect, egui::Sense::click());
+    let Some(skill_id) = skill_id else {
+        return response;
+    };
+    let skill_data = game_data.skills.get_skill(skill_id);
+
+    if selected {
+        ui.painter().rect_filled(rect, 0.0, CLAN_SELECTED_COLOR);
+    }
+
+    let icon_size = rect.height().min(32.0);
+    let icon_rect = egui::Rect::from_min_size(
+        rect.left_center() - egui::vec2(0.0, icon_size / 2.0),
+        egui::vec2(icon_size, icon_size),
+    );
+    if let Some(sprite) = skill_data.and_then(|skill_data| {
+        ui_resources.get_sprite_by_index(UiSpriteSheetType::Skill, skill_data.icon_number as usize)
+    }) {
+        sprite.draw_stretched(ui, icon_rect);
+    }
+
+    let text_x = icon_size + 6.0;
+    let text_width = rect.width() - text_x;
+    match skill_data {
+        Some(skill_data) => {
+            let requirements = get_clan_skill_requirements(skill_data);
+            let (detail, detail_color) = if matches!(skill_data.skill_type, SkillType::Passive) {
+                ("Passive".to_string(), CLAN_LABEL_COLOR)
+            } else if requirements.clan_point_cost > 0 {
+                (
+                    format!(
+                        "{} {}",
+                        game_data.client_strings.clan_point,
+                        format_number(requirements.clan_point_cost as u64)
+                    ),
+                    if clan.points.0 >= requirements.clan_point_cost as u64 {
+                        CLAN_LABEL_COLOR
+                    } else {
+                        egui::Color32::RED
+                    },
+                )
+            } else {
+                ("-".to_string(), CLAN_LABEL_COLOR)
+            };
+            let half_rect =
+                egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), rect.height() / 2.0));
+            paint_list_text(
+                ui,
+                half_rect,
+                text_x,
+                text_width,
+                skill_data.name,
+                if selected {
+                    egui::Color32::YELLOW
+                } else {
+                    CLAN_VALUE_COLOR
+                },
+            );
+            paint_list_text(
+                ui,
+                half_rect.translate(egui::vec2(0.0, rect.height() / 2.0)),
+                text_x,
+                text_width,
+                &detail,
+                detail_color,
+            );
+        }
+        None => {
+            paint_list_text(
+                ui,
+                rect,
+                text_x,
+                text_width,
+                &format!("Unknown Skill {}", skill_id.get()),
+                CLAN_VALUE_COLOR,
+            );
+        }
+    }
+
+    response.on_hover_ui(|ui| {
+        ui_add_skill_tooltip(ui, SkillTooltipType::Detailed, game_data, None, skill_id);
+    })
+}
+
+fn get_skill_activate_error(
+    skill_data: &SkillData,
+    clan: &Clan,
+    clan_membership: &ClanMembership,
+    activate_supported: bool,
+) -> Option<&'static str> {
+    let requirements = get_clan_skill_requirements(skill_data);
+
+    if !activate_supported {
+        Some("This server does not support using clan skills.")
+    } else if matches!(skill_data.skill_type, SkillType::Passive) {
+        Some("Passive clan skills are always active.")
+    } else if position_to_rank(clan_membership.position) < requirements.rank {
+        Some("Your clan rank is too low to use this skill.")
+    } else if clan.points.0 < requirements.clan_point_cost.max(0) as u64 {
+        Some("Your clan does not have enough clan points.")
+    } else {
+        None
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_skill_tab(
+    ui: &mut egui::Ui,
+    dialog: &Dialog,
+    clan: &Clan,
+    clan_membership: &ClanMembership,
+    game_data: &GameData,
+    ui_skin: Option<&UiSkin>,
+    selected_skill_id: Option<SkillId>,
+    visible_skills: &[SkillId],
+    activate_supported: bool,
+) -> Option<ClanAction> {
+    let list_rect = get_zlist_rect(dialog, IID_ZLIST_SKILL)?;
+
+    if visible_skills.is_empty() {
+        ui.add_label_in(
+            list_rect,
+            egui::RichText::new("Your clan has not learned any clan skills.")
+                .color(CLAN_LABEL_COLOR),
+        );
+        return None;
+    }
+
+    let selected_skill_data =
+        selected_skill_id.and_then(|skill_id| game_data.skills.get_skill(skill_id));
+    let error = match selected_skill_data {
+        Some(skill_data) => {
+            get_skill_activate_error(skill_data, clan, clan_membership, activate_supported)
+        }
+        None => Some("Select a clan skill first."),
+    };
+
+    let response = ui.add_at(
+        egui::pos2(list_rect.min.x, list_rect.max.y + 4.0),
+        |ui: &mut egui::Ui| add_clan_button(ui, ui_skin, error.is_none(), "Use Skill"),
+    );
+    if let Some(error) = error {
+        response.on_disabled_hover_text(error);
+        None
+    } else if response.clicked() {
+        selected_skill_id.map(ClanAction::UseSkill)
+    } else {
+        None
+    }
+}
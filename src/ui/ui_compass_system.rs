@@ -0,0 +1,169 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::{Camera3d, Query, Transform, Vec2, With, Without};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntityName, Command, PartyMember, PlayerCharacter, Position},
+    ui::{get_party_member_color, paint_party_member_diamond},
+};
+
+const COMPASS_WIDTH: f32 = 360.0;
+const COMPASS_HEIGHT: f32 = 28.0;
+const COMPASS_TOP_MARGIN: f32 = 8.0;
+
+// Half of the field of view shown across the width of the compass, in radians
+const COMPASS_HALF_FOV: f32 = PI / 2.0;
+const COMPASS_TICK_STEP_DEGREES: usize = 15;
+
+const COMPASS_LABELS: [(f32, &str); 8] = [
+    (0.0, "N"),
+    (45.0, "NE"),
+    (90.0, "E"),
+    (135.0, "SE"),
+    (180.0, "S"),
+    (225.0, "SW"),
+    (270.0, "W"),
+    (315.0, "NW"),
+];
+
+// Clockwise angle from north, positions are in world units where north is +y
+fn get_bearing(direction: Vec2) -> f32 {
+    direction.x.atan2(direction.y)
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+pub fn ui_compass_system(
+    mut egui_context: EguiContexts,
+    query_camera: Query<&Transform, With<Camera3d>>,
+    query_player: Query<(&Position, &Command), With<PlayerCharacter>>,
+    query_party_members: Query<
+        (&PartyMember, &ClientEntityName, &Position),
+        Without<PlayerCharacter>,
+    >,
+) {
+    let Ok(camera_transform) = query_camera.get_single() else {
+        return;
+    };
+    let Ok((player_position, player_command)) = query_player.get_single() else {
+        return;
+    };
+
+    let camera_forward = camera_transform.forward();
+    let camera_forward = Vec2::new(camera_forward.x, -camera_forward.z);
+    if camera_forward.length_squared() < 0.0001 {
+        return;
+    }
+    let camera_bearing = get_bearing(camera_forward);
+
+    let ctx = egui_context.ctx_mut();
+    let screen_rect = ctx.input(|input| input.screen_rect());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("compass"),
+    ));
+
+    let compass_rect = egui::Rect::from_center_size(
+        egui::pos2(
+            screen_rect.center().x,
+            screen_rect.top() + COMPASS_TOP_MARGIN + COMPASS_HEIGHT / 2.0,
+        ),
+        egui::vec2(COMPASS_WIDTH, COMPASS_HEIGHT),
+    );
+    painter.rect_filled(
+        compass_rect,
+        egui::Rounding::same(4.0),
+        egui::Color32::from_black_alpha(140),
+    );
+    let painter = painter.with_clip_rect(compass_rect);
+
+    // Returns the x position on the compass for a bearing, if it is in view
+    let get_compass_x = |bearing: f32| {
+        let relative = wrap_angle(bearing - camera_bearing);
+        (relative.abs() <= COMPASS_HALF_FOV)
+            .then(|| compass_rect.center().x + relative / COMPASS_HALF_FOV * COMPASS_WIDTH / 2.0)
+    };
+
+    for degrees in (0..360).step_by(COMPASS_TICK_STEP_DEGREES) {
+        let Some(x) = get_compass_x((degrees as f32).to_radians()) else {
+            continue;
+        };
+
+        if let Some((_, label)) = COMPASS_LABELS
+            .iter()
+            .find(|(label_degrees, _)| *label_degrees as usize == degrees)
+        {
+            let is_cardinal = label.len() == 1;
+            painter.text(
+                egui::pos2(x, compass_rect.center().y),
+                egui::Align2::CENTER_CENTER,
+                label,
+                egui::FontId::proportional(if is_cardinal { 15.0 } else { 12.0 }),
+                if *label == "N" {
+                    egui::Color32::from_rgb(255, 100, 80)
+                } else {
+                    egui::Color32::WHITE
+                },
+            );
+        } else {
+            painter.line_segment(
+                [
+                    egui::pos2(x, compass_rect.bottom() - 8.0),
+                    egui::pos2(x, compass_rect.bottom() - 2.0),
+                ],
+                egui::Stroke::new(1.0, egui::Color32::GRAY),
+            );
+        }
+    }
+
+    let player_position = player_position.position.truncate();
+
+    // The destination the player is currently walking to
+    if let Command::Move(command_move) = player_command {
+        let direction = command_move.destination.truncate() - player_position;
+        if let Some(x) = direction
+            .try_normalize()
+            .and_then(|direction| get_compass_x(get_bearing(direction)))
+        {
+            let center = egui::pos2(x, compass_rect.top() + 5.0);
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    center + egui::vec2(-5.0, -3.0),
+                    center + egui::vec2(5.0, -3.0),
+                    center + egui::vec2(0.0, 4.0),
+                ],
+                egui::Color32::YELLOW,
+                egui::Stroke::new(1.0, egui::Color32::BLACK),
+            ));
+        }
+    }
+
+    for (party_member, name, position) in query_party_members.iter() {
+        let direction = position.position.truncate() - player_position;
+        let Some(x) = direction
+            .try_normalize()
+            .and_then(|direction| get_compass_x(get_bearing(direction)))
+        else {
+            continue;
+        };
+
+        let marker_position = egui::pos2(x, compass_rect.bottom() - 5.0);
+        let color = get_party_member_color(party_member.index);
+        paint_party_member_diamond(&painter, marker_position, 4.0, color);
+
+        if let Some(pointer_position) = ctx.pointer_hover_pos() {
+            if (pointer_position - marker_position).length() < 6.0 {
+                painter.text(
+                    marker_position - egui::vec2(0.0, 10.0),
+                    egui::Align2::CENTER_BOTTOM,
+                    name.as_str(),
+                    egui::FontId::proportional(12.0),
+                    color,
+                );
+            }
+        }
+    }
+}
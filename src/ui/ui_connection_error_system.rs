@@ -0,0 +1,84 @@
+use bevy::prelude::{EventWriter, Res, ResMut, State};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    events::{LoginEvent, LogoutEvent},
+    resources::{Account, AppState, ConnectionServer, ConnectionState, WorldConnection},
+};
+
+pub fn ui_connection_error_system(
+    mut egui_context: EguiContexts,
+    mut connection_state: ResMut<ConnectionState>,
+    app_state: Res<State<AppState>>,
+    account: Option<Res<Account>>,
+    world_connection: Option<Res<WorldConnection>>,
+    mut login_events: EventWriter<LoginEvent>,
+    mut logout_events: EventWriter<LogoutEvent>,
+) {
+    let ConnectionState::Error { server, reason } = &*connection_state else {
+        return;
+    };
+    let server = *server;
+    let in_game = matches!(app_state.get(), AppState::Game);
+
+    let mut retry_login = false;
+    let mut logout_event = None;
+    let mut dismiss = false;
+
+    egui::Window::new("Connection Error")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .order(egui::Order::Foreground)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label(format!("There was a problem with the {}:", server.name()));
+            ui.colored_label(egui::Color32::LIGHT_RED, reason);
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                if in_game {
+                    // The world server connection is kept while in game, so we can
+                    // go back to character select without logging in again
+                    if world_connection.is_some()
+                        && ui.button("Return to Character Select").clicked()
+                    {
+                        logout_event = Some(LogoutEvent::ReturnToCharacterSelect);
+                    }
+
+                    if ui.button("Return to Login").clicked() {
+                        logout_event = Some(LogoutEvent::Logout);
+                    }
+                } else {
+                    if server == ConnectionServer::Login
+                        && account.is_some()
+                        && ui.button("Retry").clicked()
+                    {
+                        retry_login = true;
+                    }
+
+                    if ui.button("OK").clicked() {
+                        dismiss = true;
+                    }
+                }
+            });
+        });
+
+    if retry_login {
+        if let Some(account) = account.as_ref() {
+            login_events.send(LoginEvent::Login {
+                username: account.username.clone(),
+                password: account.password.clone(),
+            });
+        }
+        dismiss = true;
+    }
+
+    if let Some(logout_event) = logout_event {
+        logout_events.send(logout_event);
+        dismiss = true;
+    }
+
+    if dismiss {
+        *connection_state = ConnectionState::Disconnected;
+    }
+}
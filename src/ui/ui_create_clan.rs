@@ -7,13 +7,15 @@ use rose_game_common::{components::ClanMark, messages::client::ClientMessage};
 use crate::{
     components::{ClanMembership, PlayerCharacter},
     events::{ClanDialogEvent, MessageBoxEvent},
-    resources::{GameConnection, GameData},
-    ui::UiStateWindows,
+    resources::{GameConnection, GameData, UiResources},
+    ui::{ui_add_clan_mark_picker, UiStateWindows, UiWindowId},
 };
 
 pub struct UiCreateClanState {
     pub clan_name: String,
     pub clan_slogan: String,
+    pub mark_background: NonZeroU16,
+    pub mark_foreground: NonZeroU16,
 }
 
 impl Default for UiCreateClanState {
@@ -21,6 +23,8 @@ impl Default for UiCreateClanState {
         Self {
             clan_name: String::new(),
             clan_slogan: String::new(),
+            mark_background: NonZeroU16::new(1).unwrap(),
+            mark_foreground: NonZeroU16::new(1).unwrap(),
         }
     }
 }
@@ -29,6 +33,8 @@ impl UiCreateClanState {
     fn clear(&mut self) {
         self.clan_name.clear();
         self.clan_slogan.clear();
+        self.mark_background = NonZeroU16::new(1).unwrap();
+        self.mark_foreground = NonZeroU16::new(1).unwrap();
     }
 }
 
@@ -42,6 +48,7 @@ pub fn ui_create_clan_system(
     query_player_clan: Query<&ClanMembership, With<PlayerCharacter>>,
     game_connection: Option<Res<GameConnection>>,
     game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
 ) {
     let ui_state = &mut *ui_state;
     let player_exists = query_player.get_single().is_ok();
@@ -50,22 +57,22 @@ pub fn ui_create_clan_system(
     for event in clan_dialog_events.iter() {
         if matches!(event, ClanDialogEvent::Open) {
             if player_has_clan {
-                ui_state_windows.create_clan_open = false;
+                ui_state_windows.set_open(UiWindowId::CREATE_CLAN, false);
                 ui_state.clear();
             } else if player_exists {
                 ui_state.clear();
-                ui_state_windows.create_clan_open = true;
+                ui_state_windows.set_open(UiWindowId::CREATE_CLAN, true);
             }
         }
     }
 
-    if !ui_state_windows.create_clan_open {
+    if !ui_state_windows.is_open(UiWindowId::CREATE_CLAN) {
         return;
     }
 
     let mut create_clicked = false;
     let mut cancel_clicked = false;
-    let mut window_open = ui_state_windows.create_clan_open;
+    let mut window_open = ui_state_windows.is_open(UiWindowId::CREATE_CLAN);
 
     egui::Window::new("Create Clan")
         .id(egui::Id::new("create_clan_window"))
@@ -92,6 +99,14 @@ pub fn ui_create_clan_system(
                     .desired_rows(4)
                     .desired_width(f32::INFINITY),
             );
+            ui.add_space(8.0);
+            ui.label("Clan Mark");
+            ui_add_clan_mark_picker(
+                ui,
+                &ui_resources,
+                &mut ui_state.mark_background,
+                &mut ui_state.mark_foreground,
+            );
             ui.add_space(10.0);
             ui.horizontal(|ui| {
                 if ui.button("Create").clicked() {
@@ -103,10 +118,10 @@ pub fn ui_create_clan_system(
             });
         });
 
-    ui_state_windows.create_clan_open = window_open;
+    ui_state_windows.set_open(UiWindowId::CREATE_CLAN, window_open);
 
-    if cancel_clicked || !ui_state_windows.create_clan_open {
-        ui_state_windows.create_clan_open = false;
+    if cancel_clicked || !ui_state_windows.is_open(UiWindowId::CREATE_CLAN) {
+        ui_state_windows.set_open(UiWindowId::CREATE_CLAN, false);
         ui_state.clear();
         return;
     }
@@ -144,15 +159,15 @@ pub fn ui_create_clan_system(
                 name: clan_name.to_string(),
                 description: clan_slogan.to_string(),
                 mark: ClanMark::Premade {
-                    background: NonZeroU16::new(1).unwrap(),
-                    foreground: NonZeroU16::new(1).unwrap(),
+                    background: ui_state.mark_background,
+                    foreground: ui_state.mark_foreground,
                 },
             })
             .ok();
     }
 
     ui_state.clear();
-    if ui_state_windows.create_clan_open {
-        ui_state_windows.create_clan_open = false;
+    if ui_state_windows.is_open(UiWindowId::CREATE_CLAN) {
+        ui_state_windows.set_open(UiWindowId::CREATE_CLAN, false);
     }
 }
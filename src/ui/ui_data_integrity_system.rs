@@ -0,0 +1,56 @@
+use bevy::{
+    app::AppExit,
+    prelude::{EventWriter, Res},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::data_integrity::{DataIntegrityIssue, DataIntegrityReport};
+
+pub fn ui_data_integrity_system(
+    mut egui_context: EguiContexts,
+    data_integrity_report: Res<DataIntegrityReport>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        ui.heading("Game data is missing or corrupt");
+
+        if let Some(profile) = data_integrity_report.profile.as_ref() {
+            ui.label(format!("Profile: {}", profile));
+        }
+
+        ui.label(
+            "The following files are required to run the client, please check your data.idx or data path is correct and reinstall any files listed below.",
+        );
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 40.0)
+            .show(ui, |ui| {
+                egui::Grid::new("data_integrity_issues")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for issue in data_integrity_report.issues.iter() {
+                            match issue {
+                                DataIntegrityIssue::Missing { path } => {
+                                    ui.colored_label(egui::Color32::YELLOW, "Missing");
+                                    ui.label(path);
+                                    ui.label("");
+                                }
+                                DataIntegrityIssue::Corrupt { path, error } => {
+                                    ui.colored_label(egui::Color32::RED, "Corrupt");
+                                    ui.label(path);
+                                    ui.label(error);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.separator();
+        if ui.button("Exit").clicked() {
+            exit_events.send(AppExit);
+        }
+    });
+}
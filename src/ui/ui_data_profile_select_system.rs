@@ -0,0 +1,101 @@
+use bevy::{
+    app::AppExit,
+    prelude::{Commands, EventWriter, NextState, ResMut},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    data_integrity::DataIntegrityIssue,
+    resources::{AppState, DataProfileSelect, VfsResource},
+};
+
+pub fn ui_data_profile_select_system(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    mut data_profile_select: ResMut<DataProfileSelect>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    let mut start_profile = None;
+
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        ui.heading("Select game data profile");
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 60.0)
+            .show(ui, |ui| {
+                egui::Grid::new("data_profiles")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (index, entry) in data_profile_select.profiles.iter().enumerate() {
+                            ui.vertical(|ui| {
+                                ui.strong(&entry.profile.name);
+                                for (device, exists) in
+                                    entry.profile.devices.iter().zip(entry.devices_exist.iter())
+                                {
+                                    if *exists {
+                                        ui.weak(device.path());
+                                    } else {
+                                        ui.colored_label(
+                                            egui::Color32::RED,
+                                            format!("{} (not found)", device.path()),
+                                        );
+                                    }
+                                }
+                            });
+
+                            if ui
+                                .add_enabled(
+                                    entry.devices_exist.iter().all(|exists| *exists),
+                                    egui::Button::new("Start"),
+                                )
+                                .clicked()
+                            {
+                                start_profile = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                if let Some(mount_error) = data_profile_select.mount_error.as_ref() {
+                    ui.colored_label(egui::Color32::RED, mount_error);
+                }
+
+                if let Some(report) = data_profile_select.data_integrity_report.as_ref() {
+                    ui.separator();
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!(
+                            "Game data of profile {} is missing or corrupt:",
+                            report.profile.as_deref().unwrap_or_default()
+                        ),
+                    );
+                    for issue in report.issues.iter() {
+                        match issue {
+                            DataIntegrityIssue::Missing { path } => {
+                                ui.label(format!("Missing {}", path));
+                            }
+                            DataIntegrityIssue::Corrupt { path, error } => {
+                                ui.label(format!("Corrupt {}: {}", path, error));
+                            }
+                        }
+                    }
+                }
+            });
+
+        ui.separator();
+        if ui.button("Exit").clicked() {
+            exit_events.send(AppExit);
+        }
+    });
+
+    if let Some(index) = start_profile {
+        // The game data is loaded when leaving this state, before the next state is entered
+        if let Some(vfs) = data_profile_select.mount(index) {
+            commands.insert_resource(VfsResource { vfs });
+            next_app_state.set(data_profile_select.next_state);
+        }
+    }
+}
@@ -4,12 +4,19 @@ use bevy::{
 };
 use bevy_egui::{egui, EguiContexts};
 
-use crate::ui::UiStateDebugWindows;
+use crate::{
+    resources::{PositionCorrectionStats, ServerMessageMetrics},
+    ui::UiStateDebugWindows,
+};
+
+const MAX_SERVER_MESSAGE_METRICS_ROWS: usize = 15;
 
 pub fn ui_debug_diagnostics_system(
     mut egui_context: EguiContexts,
     ui_state_debug_windows: ResMut<UiStateDebugWindows>,
     diagnostics: Res<DiagnosticsStore>,
+    position_correction_stats: Res<PositionCorrectionStats>,
+    mut server_message_metrics: ResMut<ServerMessageMetrics>,
 ) {
     if !ui_state_debug_windows.debug_ui_open {
         return;
@@ -43,5 +50,76 @@ pub fn ui_debug_diagnostics_system(
                         }
                     }
                 });
+
+            ui.separator();
+            egui::Grid::new("network_diagnostics_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Position corrections smoothed");
+                    ui.label(format!("{}", position_correction_stats.num_smoothed));
+                    ui.end_row();
+
+                    ui.label("Position corrections snapped");
+                    ui.label(format!("{}", position_correction_stats.num_snapped));
+                    ui.end_row();
+
+                    ui.label("Last position correction");
+                    ui.label(format!(
+                        "{:.2}m",
+                        position_correction_stats.last_distance / 100.0
+                    ));
+                    ui.end_row();
+
+                    ui.label("Max position correction");
+                    ui.label(format!(
+                        "{:.2}m",
+                        position_correction_stats.max_distance / 100.0
+                    ));
+                    ui.end_row();
+
+                    ui.label("Player position corrections");
+                    ui.label(format!(
+                        "{}",
+                        position_correction_stats.num_player_corrections
+                    ));
+                    ui.end_row();
+
+                    ui.label("Move speed clamped");
+                    ui.label(format!("{}", position_correction_stats.num_speed_clamped));
+                    ui.end_row();
+                });
+
+            ui.separator();
+            let mut metrics: Vec<_> = server_message_metrics
+                .messages
+                .iter()
+                .filter(|(_, metric)| metric.count > 0)
+                .collect();
+            metrics.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count));
+
+            egui::Grid::new("server_message_metrics_grid")
+                .num_columns(4)
+                .show(ui, |ui| {
+                    ui.label("Server message");
+                    ui.label("Count");
+                    ui.label("Average");
+                    ui.label("Max");
+                    ui.end_row();
+
+                    for (kind, metric) in metrics.iter().take(MAX_SERVER_MESSAGE_METRICS_ROWS) {
+                        ui.label(format!("{:?}", kind));
+                        ui.label(format!("{}", metric.count));
+                        ui.label(format!(
+                            "{:.3}ms",
+                            metric.average_time().as_secs_f64() * 1000.0
+                        ));
+                        ui.label(format!("{:.3}ms", metric.max_time.as_secs_f64() * 1000.0));
+                        ui.end_row();
+                    }
+                });
+
+            if ui.button("Reset server message metrics").clicked() {
+                server_message_metrics.clear();
+            }
         });
 }
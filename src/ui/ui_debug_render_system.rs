@@ -37,6 +37,22 @@ pub fn ui_debug_render_system(
         .open(&mut ui_state_debug_windows.debug_render_open)
         .show(egui_context.ctx_mut(), |ui| {
             ui.checkbox(&mut debug_render_config.colliders, "Show Colliders");
+            ui.checkbox(
+                &mut debug_render_config.collider_wireframes,
+                "Show Collider Wireframes (by type)",
+            );
+            ui.add_enabled(
+                debug_render_config.collider_wireframes,
+                egui::Slider::new(
+                    &mut debug_render_config.collider_wireframe_distance,
+                    10.0..=250.0,
+                )
+                .text("Wireframe distance"),
+            );
+            ui.checkbox(
+                &mut debug_render_config.player_movement_raycast,
+                "Show Player Movement Raycast",
+            );
             if let Some(mut rapier_debug) = rapier_debug {
                 ui.checkbox(&mut rapier_debug.enabled, "Show Rapier Debug");
             }
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use bevy::prelude::ResMut;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{resources::UnsupportedPackets, ui::UiStateDebugWindows};
+
+fn format_hex_dump(data: &[u8]) -> String {
+    let mut output = String::new();
+
+    for (row, chunk) in data.chunks(16).enumerate() {
+        write!(output, "{:04x}  ", row * 16).ok();
+        for index in 0..16 {
+            match chunk.get(index) {
+                Some(byte) => write!(output, "{:02x} ", byte).ok(),
+                None => write!(output, "   ").ok(),
+            };
+        }
+
+        output.push(' ');
+        for &byte in chunk {
+            output.push(if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            });
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+pub fn ui_debug_unsupported_packets_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_debug_windows: ResMut<UiStateDebugWindows>,
+    mut unsupported_packets: ResMut<UnsupportedPackets>,
+) {
+    // Always drain the channel so it does not grow while the window is closed
+    unsupported_packets.update();
+
+    if !ui_state_debug_windows.debug_ui_open {
+        return;
+    }
+
+    let mut clear = false;
+
+    egui::Window::new("Unsupported Packets")
+        .open(&mut ui_state_debug_windows.unsupported_packets_open)
+        .default_width(560.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} unsupported packets received",
+                    unsupported_packets.total_count
+                ));
+                if ui.button("Clear").clicked() {
+                    clear = true;
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, true])
+                .max_height(400.0)
+                .show(ui, |ui| {
+                    if unsupported_packets.packets.is_empty() {
+                        ui.weak("No unsupported packets have been received.");
+                    }
+
+                    // Ids stay stable as older packets are dropped from the front
+                    let first_id =
+                        unsupported_packets.total_count - unsupported_packets.packets.len();
+                    for (index, packet) in unsupported_packets.packets.iter().enumerate().rev() {
+                        egui::CollapsingHeader::new(format!(
+                            "{} [{:03X}] {} bytes: {}",
+                            packet.client,
+                            packet.command,
+                            packet.data.len(),
+                            packet.reason
+                        ))
+                        .id_source(("unsupported_packet", first_id + index))
+                        .show(ui, |ui| {
+                            let hex_dump = format_hex_dump(&packet.data);
+                            ui.label(egui::RichText::new(&hex_dump).monospace());
+                            if ui.button("Copy").clicked() {
+                                ui.output_mut(|output| output.copied_text = hex_dump);
+                            }
+                        });
+                    }
+                });
+        });
+
+    if clear {
+        unsupported_packets.clear();
+    }
+}
@@ -42,6 +42,7 @@ pub struct UiStateDebugWindows {
     pub object_inspector_open: bool,
     pub physics_open: bool,
     pub skill_list_open: bool,
+    pub unsupported_packets_open: bool,
     pub zone_list_open: bool,
     pub zone_lighting_open: bool,
     pub zone_time_open: bool,
@@ -194,6 +195,10 @@ pub fn ui_debug_menu_system(
                 ui.checkbox(&mut ui_state_debug_windows.item_list_open, "Item List");
                 ui.checkbox(&mut ui_state_debug_windows.npc_list_open, "NPC List");
                 ui.checkbox(&mut ui_state_debug_windows.skill_list_open, "Skill List");
+                ui.checkbox(
+                    &mut ui_state_debug_windows.unsupported_packets_open,
+                    "Unsupported Packets",
+                );
                 ui.checkbox(&mut ui_state_debug_windows.zone_list_open, "Zone List");
                 ui.checkbox(
                     &mut ui_state_debug_windows.zone_lighting_open,
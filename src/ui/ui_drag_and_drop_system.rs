@@ -1,16 +1,18 @@
 use bevy::prelude::{EventWriter, Local, ResMut, Resource};
 use bevy_egui::{egui, EguiContexts};
 
+use rose_data::SoundId;
 use rose_game_common::components::ItemSlot;
 
 use crate::{
-    events::{NpcStoreEvent, PlayerCommandEvent},
-    ui::DragAndDropId,
+    events::{DropItemEvent, NpcStoreEvent, PlayerCommandEvent},
+    ui::{DragAndDropId, UiSoundEvent, DRAG_AND_DROP_REJECTED_ID},
 };
 
 #[derive(Default, Resource)]
 pub struct UiStateDragAndDrop {
     pub dragged_item: Option<DragAndDropId>,
+    pub invalid_drop_sound: Option<SoundId>,
 }
 
 pub fn ui_drag_and_drop_system(
@@ -19,6 +21,8 @@ pub fn ui_drag_and_drop_system(
     mut last_dropped_item: Local<Option<DragAndDropId>>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     mut npc_store_events: EventWriter<NpcStoreEvent>,
+    mut drop_item_events: EventWriter<DropItemEvent>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
 ) {
     let ctx = egui_context.ctx_mut();
 
@@ -28,7 +32,7 @@ pub fn ui_drag_and_drop_system(
             match last_dropped_item {
                 DragAndDropId::Inventory(item_slot) => match item_slot {
                     ItemSlot::Inventory(_, _) => {
-                        player_command_events.send(PlayerCommandEvent::DropItem(item_slot));
+                        drop_item_events.send(DropItemEvent { item_slot });
                     }
                     ItemSlot::Ammo(ammo_index) => {
                         player_command_events.send(PlayerCommandEvent::UnequipAmmo(ammo_index));
@@ -64,4 +68,19 @@ pub fn ui_drag_and_drop_system(
             *last_dropped_item = ui_state_dnd.dragged_item.take();
         }
     });
+
+    // Handle a drag and drop which was dropped on a slot which does not accept it
+    let rejected = ctx.data_mut(|data| {
+        let id = egui::Id::new(DRAG_AND_DROP_REJECTED_ID);
+        let rejected = data.get_temp::<bool>(id).unwrap_or(false);
+        data.remove::<bool>(id);
+        rejected
+    });
+    if rejected {
+        *last_dropped_item = None;
+
+        if let Some(sound_id) = ui_state_dnd.invalid_drop_sound {
+            ui_sound_events.send(UiSoundEvent::new(sound_id));
+        }
+    }
 }
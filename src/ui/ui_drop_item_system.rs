@@ -0,0 +1,72 @@
+use bevy::prelude::{EventReader, EventWriter, Events, Query, Res, With, World};
+
+use rose_game_common::components::{Inventory, ItemSlot};
+
+use crate::{
+    components::PlayerCharacter,
+    events::{DropItemEvent, MessageBoxEvent, NumberInputDialogEvent, PlayerCommandEvent},
+    resources::GameData,
+};
+
+fn send_drop_item(world: &mut World, item_slot: ItemSlot, quantity: usize) {
+    if let Some(mut player_command_events) = world.get_resource_mut::<Events<PlayerCommandEvent>>()
+    {
+        player_command_events.send(PlayerCommandEvent::DropItem(item_slot, quantity));
+    }
+}
+
+pub fn ui_drop_item_system(
+    mut drop_item_events: EventReader<DropItemEvent>,
+    mut message_box_events: EventWriter<MessageBoxEvent>,
+    mut number_input_dialog_events: EventWriter<NumberInputDialogEvent>,
+    query_player: Query<&Inventory, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+) {
+    let Ok(inventory) = query_player.get_single() else {
+        drop_item_events.clear();
+        return;
+    };
+
+    for &DropItemEvent { item_slot } in drop_item_events.iter() {
+        if !matches!(item_slot, ItemSlot::Inventory(_, _)) {
+            continue;
+        }
+
+        let Some(item) = inventory.get_item(item_slot) else {
+            continue;
+        };
+
+        let quantity = item.get_quantity() as usize;
+        if quantity > 1 {
+            number_input_dialog_events.send(NumberInputDialogEvent::Show {
+                max_value: Some(quantity),
+                modal: true,
+                ok: Some(Box::new(move |commands, amount| {
+                    if amount > 0 {
+                        commands.add(move |world: &mut World| {
+                            send_drop_item(world, item_slot, amount);
+                        });
+                    }
+                })),
+                cancel: None,
+            });
+        } else {
+            let item_name = game_data
+                .items
+                .get_base_item(item.get_item_reference())
+                .map(|item_data| item_data.name)
+                .unwrap_or("this item");
+
+            message_box_events.send(MessageBoxEvent::Show {
+                message: format!("Are you sure you want to drop {} on the ground?", item_name),
+                modal: true,
+                ok: Some(Box::new(move |commands| {
+                    commands.add(move |world: &mut World| {
+                        send_drop_item(world, item_slot, 1);
+                    });
+                })),
+                cancel: Some(Box::new(|_| {})),
+            });
+        }
+    }
+}
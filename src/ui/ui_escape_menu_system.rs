@@ -0,0 +1,258 @@
+use bevy::prelude::{
+    EventWriter, Input, KeyCode, Local, MouseButton, Query, Res, ResMut, Time, With,
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntityName, PlayerCharacter},
+    events::{ChatboxEvent, LogoutEvent},
+    resources::{CharacterList, CharacterSwitch, CombatState},
+    ui::{UiStateWindows, UiWindowId},
+};
+
+// Seconds to wait before leaving the game, any input during this time cancels it
+const LOGOUT_COUNTDOWN: f32 = 5.0;
+
+// Leaving the game takes longer after being attacked, so it can not be used to escape a fight
+const COMBAT_LOGOUT_COUNTDOWN: f32 = 10.0;
+
+const ESCAPE_MENU_BUTTON_SIZE: [f32; 2] = [200.0, 24.0];
+
+fn logout_description(event: LogoutEvent) -> &'static str {
+    match event {
+        LogoutEvent::ReturnToCharacterSelect => "return to character select",
+        LogoutEvent::SwitchCharacter => "switch character",
+        LogoutEvent::Logout => "log out",
+        LogoutEvent::Exit => "exit the game",
+    }
+}
+
+struct LogoutCountdown {
+    event: LogoutEvent,
+    remaining: f32,
+    started: bool,
+}
+
+#[derive(Default)]
+pub struct UiStateEscapeMenu {
+    confirm: Option<LogoutEvent>,
+    countdown: Option<LogoutCountdown>,
+    switch_character: Option<String>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_escape_menu_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateEscapeMenu>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut chatbox_events: EventWriter<ChatboxEvent>,
+    mut logout_events: EventWriter<LogoutEvent>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    combat_state: Res<CombatState>,
+    time: Res<Time>,
+    query_player_name: Query<&ClientEntityName, With<PlayerCharacter>>,
+    character_list: Option<Res<CharacterList>>,
+    mut character_switch: ResMut<CharacterSwitch>,
+) {
+    let ui_state = &mut *ui_state;
+    let ctx = egui_context.ctx_mut();
+
+    if let Some(countdown) = ui_state.countdown.as_mut() {
+        // Ignore the input which confirmed the logout
+        let any_input = keyboard_input.get_just_pressed().next().is_some()
+            || mouse_input.get_just_pressed().next().is_some();
+        if countdown.started && any_input {
+            chatbox_events.send(ChatboxEvent::System("Logout cancelled".to_string()));
+            ui_state.countdown = None;
+            ui_state.switch_character = None;
+            return;
+        }
+        countdown.started = true;
+
+        if combat_state.is_changed() && combat_state.in_combat() {
+            countdown.remaining = countdown.remaining.max(COMBAT_LOGOUT_COUNTDOWN);
+        }
+
+        countdown.remaining -= time.delta_seconds();
+        if countdown.remaining <= 0.0 {
+            if countdown.event == LogoutEvent::SwitchCharacter {
+                character_switch.character_name = ui_state.switch_character.take();
+            }
+            logout_events.send(countdown.event);
+            ui_state.countdown = None;
+            ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, false);
+            return;
+        }
+
+        egui::Window::new("Leaving Game")
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if combat_state.in_combat() {
+                    ui.label("You were recently attacked");
+                }
+                if let Some(character_name) = ui_state.switch_character.as_ref() {
+                    ui.label(format!(
+                        "You will switch to {} in {} seconds",
+                        character_name,
+                        countdown.remaining.ceil() as i32
+                    ));
+                } else {
+                    ui.label(format!(
+                        "You will {} in {} seconds",
+                        logout_description(countdown.event),
+                        countdown.remaining.ceil() as i32
+                    ));
+                }
+                ui.label("Press any key or click to cancel");
+            });
+        return;
+    }
+
+    if !ctx.wants_keyboard_input()
+        && ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+    {
+        if ui_state.confirm.is_some() {
+            ui_state.confirm = None;
+        } else if ui_state_windows.is_open(UiWindowId::ESCAPE_MENU) {
+            ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, false);
+        } else if UiWindowId::PLAYER_WINDOWS
+            .iter()
+            .any(|window| ui_state_windows.is_open(*window))
+        {
+            // Close any open windows before showing the menu
+            for window in UiWindowId::PLAYER_WINDOWS {
+                ui_state_windows.set_open(window, false);
+            }
+        } else {
+            ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, true);
+        }
+    }
+
+    if !ui_state_windows.is_open(UiWindowId::ESCAPE_MENU) {
+        ui_state.confirm = None;
+        ui_state.switch_character = None;
+        return;
+    }
+
+    // Characters which can be switched to without going through character select
+    let player_name = query_player_name.get_single().ok();
+    let switch_characters = character_list
+        .as_ref()
+        .map(|character_list| {
+            character_list
+                .characters
+                .iter()
+                .filter(|character| {
+                    character.delete_time.is_none()
+                        && player_name.map_or(true, |name| name.name != character.info.name)
+                })
+                .map(|character| character.info.name.clone())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut resume = false;
+    let mut open_settings = false;
+    egui::Window::new("Game Menu")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            if let Some(event) = ui_state.confirm {
+                if let Some(character_name) = ui_state.switch_character.as_ref() {
+                    ui.label(format!(
+                        "Are you sure you want to switch to {}?",
+                        character_name
+                    ));
+                } else {
+                    ui.label(format!(
+                        "Are you sure you want to {}?",
+                        logout_description(event)
+                    ));
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Yes").clicked() {
+                        ui_state.countdown = Some(LogoutCountdown {
+                            event,
+                            remaining: if combat_state.in_combat() {
+                                COMBAT_LOGOUT_COUNTDOWN
+                            } else {
+                                LOGOUT_COUNTDOWN
+                            },
+                            started: false,
+                        });
+                        ui_state.confirm = None;
+                    }
+
+                    if ui.button("No").clicked() {
+                        ui_state.confirm = None;
+                        ui_state.switch_character = None;
+                    }
+                });
+                return;
+            }
+
+            ui.vertical_centered(|ui| {
+                if ui
+                    .add_sized(ESCAPE_MENU_BUTTON_SIZE, egui::Button::new("Resume"))
+                    .clicked()
+                {
+                    resume = true;
+                }
+
+                if ui
+                    .add_sized(ESCAPE_MENU_BUTTON_SIZE, egui::Button::new("Settings"))
+                    .clicked()
+                {
+                    open_settings = true;
+                }
+
+                ui.separator();
+
+                for (event, text) in [
+                    (
+                        LogoutEvent::ReturnToCharacterSelect,
+                        "Return to Character Select",
+                    ),
+                    (LogoutEvent::Logout, "Log Out"),
+                    (LogoutEvent::Exit, "Exit"),
+                ] {
+                    if ui
+                        .add_sized(ESCAPE_MENU_BUTTON_SIZE, egui::Button::new(text))
+                        .clicked()
+                    {
+                        ui_state.confirm = Some(event);
+                        ui_state.switch_character = None;
+                    }
+
+                    if event == LogoutEvent::ReturnToCharacterSelect
+                        && !switch_characters.is_empty()
+                    {
+                        egui::ComboBox::from_id_source("escape_menu_switch_character")
+                            .width(ESCAPE_MENU_BUTTON_SIZE[0] - 8.0)
+                            .selected_text("Switch Character")
+                            .show_ui(ui, |ui| {
+                                for character_name in switch_characters.iter() {
+                                    if ui.selectable_label(false, character_name).clicked() {
+                                        ui_state.switch_character = Some(character_name.clone());
+                                        ui_state.confirm = Some(LogoutEvent::SwitchCharacter);
+                                    }
+                                }
+                            });
+                    }
+                }
+            });
+        });
+
+    if resume {
+        ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, false);
+    }
+
+    if open_settings {
+        ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, false);
+        ui_state_windows.set_open(UiWindowId::SETTINGS, true);
+    }
+}
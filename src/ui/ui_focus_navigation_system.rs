@@ -0,0 +1,87 @@
+use bevy::{
+    input::{
+        gamepad::{GamepadButton, GamepadButtonType, Gamepads},
+        Input,
+    },
+    prelude::{KeyCode, Query, Res, ResMut, Resource, With},
+    window::PrimaryWindow,
+};
+use bevy_egui::{egui, EguiContexts, EguiInput};
+
+#[derive(Default, Resource)]
+pub struct UiFocusNavigation {
+    // Arrow keys are left alone while typing so they can move the text cursor
+    text_input_focused: bool,
+}
+
+fn key_event(key: egui::Key, modifiers: egui::Modifiers) -> egui::Event {
+    egui::Event::Key {
+        key,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    }
+}
+
+// Moves egui focus between widgets with the arrow keys or d-pad, focus moves in
+// the same order as tab. Enter or the gamepad south button clicks the focused
+// widget, and the gamepad east button acts as escape to close windows.
+pub fn ui_focus_navigation_system(
+    mut query_egui_input: Query<&mut EguiInput, With<PrimaryWindow>>,
+    mut egui_context: EguiContexts,
+    ui_focus_navigation: Res<UiFocusNavigation>,
+    keyboard: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+) {
+    let Ok(mut egui_input) = query_egui_input.get_single_mut() else {
+        return;
+    };
+
+    let gamepad_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+
+    let has_focus = egui_context
+        .ctx_mut()
+        .memory(|memory| memory.focus().is_some());
+    let keyboard_navigation = has_focus && !ui_focus_navigation.text_input_focused;
+
+    let mut events = Vec::new();
+    if (keyboard_navigation
+        && (keyboard.just_pressed(KeyCode::Down) || keyboard.just_pressed(KeyCode::Right)))
+        || gamepad_pressed(GamepadButtonType::DPadDown)
+        || gamepad_pressed(GamepadButtonType::DPadRight)
+    {
+        events.push(key_event(egui::Key::Tab, egui::Modifiers::NONE));
+    }
+
+    if (keyboard_navigation
+        && (keyboard.just_pressed(KeyCode::Up) || keyboard.just_pressed(KeyCode::Left)))
+        || gamepad_pressed(GamepadButtonType::DPadUp)
+        || gamepad_pressed(GamepadButtonType::DPadLeft)
+    {
+        events.push(key_event(egui::Key::Tab, egui::Modifiers::SHIFT));
+    }
+
+    if gamepad_pressed(GamepadButtonType::South) {
+        events.push(key_event(egui::Key::Enter, egui::Modifiers::NONE));
+    }
+
+    if gamepad_pressed(GamepadButtonType::East) {
+        events.push(key_event(egui::Key::Escape, egui::Modifiers::NONE));
+    }
+
+    egui_input.events.extend(events);
+}
+
+pub fn ui_focus_navigation_text_input_system(
+    mut egui_context: EguiContexts,
+    mut ui_focus_navigation: ResMut<UiFocusNavigation>,
+) {
+    ui_focus_navigation.text_input_focused = egui_context
+        .ctx_mut()
+        .output(|output| output.text_cursor_pos.is_some());
+}
@@ -1,14 +1,12 @@
-use bevy::{
-    app::AppExit,
-    prelude::{Assets, EventWriter, Local, Res, ResMut},
-};
+use bevy::prelude::{Assets, EventWriter, Local, Res, ResMut};
 use bevy_egui::{egui, EguiContexts};
 
 use crate::{
+    client_plugin::ClientPluginRegistry,
     resources::UiResources,
     ui::{
         widgets::{DataBindings, Dialog},
-        UiSoundEvent, UiStateWindows,
+        UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -35,8 +33,8 @@ pub fn ui_game_menu_system(
     mut ui_state: Local<UiGameMenuState>,
     ui_resources: Res<UiResources>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
-    mut exit_events: EventWriter<AppExit>,
     dialog_assets: Res<Assets<Dialog>>,
+    client_plugin_registry: Res<ClientPluginRegistry>,
 ) {
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_game_menu) {
         dialog
@@ -57,7 +55,7 @@ pub fn ui_game_menu_system(
 
     let response = egui::Window::new("Game Menu")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.menu_open)
+        .open(ui_state_windows.open_mut(UiWindowId::MENU))
         .title_bar(false)
         .resizable(false)
         .fixed_pos([dialog.adjust_x, dialog.adjust_y])
@@ -92,7 +90,7 @@ pub fn ui_game_menu_system(
         // have been released after opening
         if ui_state.mouse_up_after_open {
             if response.response.clicked_elsewhere() {
-                ui_state_windows.menu_open = false;
+                ui_state_windows.set_open(UiWindowId::MENU, false);
             }
         } else if !response
             .response
@@ -106,81 +104,114 @@ pub fn ui_game_menu_system(
     }
 
     if response_button_character_info.map_or(false, |r| r.clicked()) {
-        ui_state_windows.character_info_open = !ui_state_windows.character_info_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::CHARACTER_INFO);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_inventory.map_or(false, |r| r.clicked()) {
-        ui_state_windows.inventory_open = !ui_state_windows.inventory_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::INVENTORY);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_skill_list.map_or(false, |r| r.clicked()) {
-        ui_state_windows.skill_list_open = !ui_state_windows.skill_list_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::SKILL_LIST);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_quest_list.map_or(false, |r| r.clicked()) {
-        ui_state_windows.quest_list_open = !ui_state_windows.quest_list_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::QUEST_LIST);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_options.map_or(false, |r| r.clicked()) {
-        ui_state_windows.settings_open = !ui_state_windows.settings_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::SETTINGS);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_community.map_or(false, |r| r.clicked()) {
-        // TODO: Community dialog
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::SOCIAL);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_clan.map_or(false, |r| r.clicked()) {
-        ui_state_windows.clan_open = !ui_state_windows.clan_open;
-        ui_state_windows.menu_open = false;
+        ui_state_windows.toggle(UiWindowId::CLAN);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_help.map_or(false, |r| r.clicked()) {
         // TODO: Help dialog
-        ui_state_windows.menu_open = false;
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_info.map_or(false, |r| r.clicked()) {
         // TODO: Info dialog
-        ui_state_windows.menu_open = false;
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if response_button_exit.map_or(false, |r| r.clicked()) {
-        exit_events.send(AppExit);
-        ui_state_windows.menu_open = false;
+        // Leaving the game goes through the escape menu for confirmation
+        ui_state_windows.set_open(UiWindowId::ESCAPE_MENU, true);
+        ui_state_windows.set_open(UiWindowId::MENU, false);
     }
 
     if !egui_context.ctx_mut().wants_keyboard_input() {
         egui_context.ctx_mut().input_mut(|input| {
             if input.consume_key(egui::Modifiers::ALT, egui::Key::A) {
-                ui_state_windows.character_info_open = !ui_state_windows.character_info_open;
+                ui_state_windows.toggle(UiWindowId::CHARACTER_INFO);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::D) {
+                ui_state_windows.toggle(UiWindowId::CHARACTER_STATS);
             }
 
             if input.consume_key(egui::Modifiers::ALT, egui::Key::I)
                 || input.consume_key(egui::Modifiers::ALT, egui::Key::V)
             {
-                ui_state_windows.inventory_open = !ui_state_windows.inventory_open;
+                ui_state_windows.toggle(UiWindowId::INVENTORY);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::C) {
+                ui_state_windows.toggle(UiWindowId::SOCIAL);
             }
 
             if input.consume_key(egui::Modifiers::ALT, egui::Key::N) {
-                ui_state_windows.clan_open = !ui_state_windows.clan_open;
+                ui_state_windows.toggle(UiWindowId::CLAN);
             }
 
             if input.consume_key(egui::Modifiers::ALT, egui::Key::S) {
-                ui_state_windows.skill_list_open = !ui_state_windows.skill_list_open;
+                ui_state_windows.toggle(UiWindowId::SKILL_LIST);
             }
 
             if input.consume_key(egui::Modifiers::ALT, egui::Key::Q) {
-                ui_state_windows.quest_list_open = !ui_state_windows.quest_list_open;
+                ui_state_windows.toggle(UiWindowId::QUEST_LIST);
             }
 
             if input.consume_key(egui::Modifiers::ALT, egui::Key::O) {
-                ui_state_windows.settings_open = !ui_state_windows.settings_open;
+                ui_state_windows.toggle(UiWindowId::SETTINGS);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::J) {
+                ui_state_windows.toggle(UiWindowId::SCRIPT_MANAGER);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::U) {
+                ui_state_windows.toggle(UiWindowId::UNION);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::G) {
+                ui_state_windows.toggle(UiWindowId::VEHICLE_GARAGE);
+            }
+
+            if input.consume_key(egui::Modifiers::ALT, egui::Key::M) {
+                ui_state_windows.toggle(UiWindowId::MACROS);
+            }
+
+            for window in client_plugin_registry.windows.iter() {
+                if let Some(hotkey) = window.hotkey {
+                    if input.consume_key(egui::Modifiers::ALT, hotkey) {
+                        ui_state_windows.toggle(window.id);
+                    }
+                }
             }
         });
     }
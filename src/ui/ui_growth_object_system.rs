@@ -0,0 +1,81 @@
+use bevy::prelude::{Camera, Camera3d, GlobalTransform, Query, Vec2, Vec3, With};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::components::{GrowthObject, ModelHeight};
+
+const GROWTH_BAR_WIDTH: f32 = 60.0;
+const GROWTH_BAR_HEIGHT: f32 = 6.0;
+
+pub fn ui_growth_object_system(
+    mut egui_context: EguiContexts,
+    query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query_growth_objects: Query<(&GrowthObject, &GlobalTransform, Option<&ModelHeight>)>,
+) {
+    let ctx = egui_context.ctx_mut();
+    let style = ctx.style();
+    let screen_size = ctx.input(|input| input.screen_rect().size());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("growth_object_progress"),
+    ));
+    let Ok((camera, camera_transform)) = query_camera.get_single() else {
+        return;
+    };
+
+    for (growth_object, global_transform, model_height) in query_growth_objects.iter() {
+        let height = model_height.map_or(1.0, |model_height| model_height.height);
+        let Some(ndc_space_coords) = camera.world_to_ndc(
+            camera_transform,
+            global_transform.translation() + Vec3::new(0.0, height, 0.0),
+        ) else {
+            continue;
+        };
+        if ndc_space_coords.z < 0.0 || ndc_space_coords.z > 1.0 {
+            // Outside near / far plane
+            continue;
+        }
+
+        let screen_pos = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0
+            * Vec2::new(screen_size.x, screen_size.y);
+        let bar_rect = egui::Rect::from_center_size(
+            egui::pos2(screen_pos.x, screen_size.y - screen_pos.y),
+            egui::vec2(GROWTH_BAR_WIDTH, GROWTH_BAR_HEIGHT),
+        );
+
+        if growth_object.is_harvestable() {
+            painter.text(
+                bar_rect.center(),
+                egui::Align2::CENTER_BOTTOM,
+                "Ready to harvest",
+                egui::FontId::default(),
+                egui::Color32::YELLOW,
+            );
+            continue;
+        }
+
+        let mut fill_rect = bar_rect;
+        fill_rect.set_width(bar_rect.width() * growth_object.total_progress());
+        painter.rect(
+            bar_rect,
+            egui::Rounding::none(),
+            style.visuals.window_fill,
+            style.visuals.window_stroke,
+        );
+        painter.rect_filled(
+            fill_rect,
+            egui::Rounding::none(),
+            egui::Color32::from_rgb(80, 200, 80),
+        );
+        painter.text(
+            bar_rect.center_top() - egui::vec2(0.0, 2.0),
+            egui::Align2::CENTER_BOTTOM,
+            format!(
+                "Stage {} / {}",
+                growth_object.stage + 1,
+                growth_object.num_stages
+            ),
+            egui::FontId::default(),
+            egui::Color32::WHITE,
+        );
+    }
+}
@@ -1,24 +1,26 @@
 use bevy::{
     ecs::query::WorldQuery,
     input::Input,
-    prelude::{Assets, EventWriter, KeyCode, Local, Query, Res, ResMut, With},
+    prelude::{Assets, EventWriter, KeyCode, Local, Query, Res, ResMut, Time, With},
 };
 use bevy_egui::{egui, EguiContexts};
 
 use rose_game_common::components::{
-    Equipment, Hotbar, HotbarSlot, Inventory, SkillList, HOTBAR_NUM_PAGES, HOTBAR_PAGE_SIZE,
+    CharacterInfo, Equipment, Hotbar, HotbarSlot, Inventory, SkillList, HOTBAR_NUM_PAGES,
+    HOTBAR_PAGE_SIZE,
 };
 
 use crate::{
     components::{Cooldowns, PlayerCharacter},
     events::PlayerCommandEvent,
-    resources::{GameData, UiResources},
+    resources::{ClientSettings, GameData, UiResources},
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem, SkillTooltipType},
         ui_add_item_tooltip, ui_add_skill_tooltip,
         ui_inventory_system::GetItem,
         widgets::{DataBindings, Dialog, Widget},
-        DialogInstance, DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop,
+        DialogInstance, DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent,
+        UiStateDragAndDrop,
     },
 };
 
@@ -31,10 +33,30 @@ const IID_BTN_VERTICAL_PREV: i32 = 13;
 const IID_BTN_VERTICAL_NEXT: i32 = 14;
 const IID_NUMBER: i32 = 20;
 
+const HOTBAR_FUNCTION_KEYS: [KeyCode; HOTBAR_PAGE_SIZE] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+];
+
+// Scroll distance in points needed to switch page with Shift+scroll
+const HOTBAR_PAGE_SCROLL_STEP: f32 = 40.0;
+
+// Seconds an item must be dragged over the page buttons before the page switches
+const HOTBAR_DRAG_PAGE_SWITCH_DELAY: f32 = 0.6;
+
 pub struct UiStateHotBar {
     dialog_instance: DialogInstance,
     current_page: usize,
     is_vertical: bool,
+    character_name: Option<String>,
+    page_scroll: f32,
+    drag_page_switch_time: f32,
 }
 
 impl Default for UiStateHotBar {
@@ -43,6 +65,9 @@ impl Default for UiStateHotBar {
             dialog_instance: DialogInstance::new("DLGQUICKBAR.XML"),
             current_page: 0,
             is_vertical: false,
+            character_name: None,
+            page_scroll: 0.0,
+            drag_page_switch_time: 0.0,
         }
     }
 }
@@ -50,6 +75,7 @@ impl Default for UiStateHotBar {
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct PlayerQuery<'w> {
+    character_info: &'w CharacterInfo,
     hotbar: &'w mut Hotbar,
     cooldowns: &'w Cooldowns,
     equipment: &'w Equipment,
@@ -138,7 +164,7 @@ fn ui_add_hotbar_slot(
     }
 
     if hotbar_slot.is_some() {
-        response.on_hover_ui(|ui| match hotbar_slot {
+        response.on_hover_tooltip_ui(|ui| match hotbar_slot {
             Some(HotbarSlot::Inventory(item_slot)) => {
                 if let Some(item) = (player.equipment, player.inventory).get_item(*item_slot) {
                     ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
@@ -205,9 +231,11 @@ pub fn ui_hotbar_system(
     query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     keyboard_input: Res<Input<KeyCode>>,
+    mut client_settings: ResMut<ClientSettings>,
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
+    time: Res<Time>,
 ) {
     let ui_state_hot_bar = &mut *ui_state_hot_bar;
     let dialog = if let Some(dialog) = ui_state_hot_bar
@@ -225,31 +253,36 @@ pub fn ui_hotbar_system(
         return;
     };
     let player_tooltip_data = query_player_tooltip.get_single().ok();
+    let previous_page = ui_state_hot_bar.current_page;
 
-    let use_hotbar_index = if !egui_context.ctx_mut().wants_keyboard_input() {
-        if keyboard_input.just_pressed(KeyCode::F1) {
-            Some(0)
-        } else if keyboard_input.just_pressed(KeyCode::F2) {
-            Some(1)
-        } else if keyboard_input.just_pressed(KeyCode::F3) {
-            Some(2)
-        } else if keyboard_input.just_pressed(KeyCode::F4) {
-            Some(3)
-        } else if keyboard_input.just_pressed(KeyCode::F5) {
-            Some(4)
-        } else if keyboard_input.just_pressed(KeyCode::F6) {
-            Some(5)
-        } else if keyboard_input.just_pressed(KeyCode::F7) {
-            Some(6)
-        } else if keyboard_input.just_pressed(KeyCode::F8) {
-            Some(7)
-        } else {
-            None
-        }
+    // Restore the last used page when a different character logs in
+    if ui_state_hot_bar.character_name.as_ref() != Some(&player.character_info.name) {
+        ui_state_hot_bar.character_name = Some(player.character_info.name.clone());
+        ui_state_hot_bar.current_page = client_settings
+            .controls
+            .hotbar_pages
+            .get(&player.character_info.name)
+            .copied()
+            .filter(|page| *page < HOTBAR_NUM_PAGES)
+            .unwrap_or(0);
+    }
+
+    let shift_pressed = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let function_key_index = if !egui_context.ctx_mut().wants_keyboard_input() {
+        HOTBAR_FUNCTION_KEYS
+            .iter()
+            .position(|key| keyboard_input.just_pressed(*key))
     } else {
         None
     };
 
+    // Shift with F1 to F4 selects the hotbar page, otherwise the function keys use the slots
+    let (select_page, use_hotbar_index) = if shift_pressed {
+        (function_key_index, None)
+    } else {
+        (None, function_key_index)
+    };
+
     let mut response_rotate_button = None;
     let mut response_hprev_button = None;
     let mut response_hnext_button = None;
@@ -265,7 +298,7 @@ pub fn ui_hotbar_system(
         screen_size.y - dialog.height,
     );
 
-    egui::Window::new("Hot Bar")
+    let window_response = egui::Window::new("Hot Bar")
         .frame(egui::Frame::none())
         .title_bar(false)
         .resizable(false)
@@ -321,17 +354,60 @@ pub fn ui_hotbar_system(
             );
         });
 
-    let previous_page = ui_state_hot_bar.current_page;
+    let (pointer_pos, scroll_delta) = egui_context
+        .ctx_mut()
+        .input(|input| (input.pointer.hover_pos(), input.scroll_delta));
+    let pointer_over = |rect: Option<egui::Rect>| {
+        rect.zip(pointer_pos)
+            .map_or(false, |(rect, pos)| rect.contains(pos))
+    };
 
-    if response_hnext_button.map_or(false, |r| r.clicked())
-        || response_vnext_button.map_or(false, |r| r.clicked())
-    {
-        ui_state_hot_bar.current_page = (ui_state_hot_bar.current_page + 1) % HOTBAR_NUM_PAGES;
+    let clicked =
+        |response: &Option<egui::Response>| response.as_ref().map_or(false, |r| r.clicked());
+    let mut next_page = clicked(&response_hnext_button) || clicked(&response_vnext_button);
+    let mut prev_page = clicked(&response_hprev_button) || clicked(&response_vprev_button);
+
+    // egui turns Shift+scroll into horizontal scrolling, so accept either axis
+    if shift_pressed && pointer_over(window_response.as_ref().map(|r| r.response.rect)) {
+        ui_state_hot_bar.page_scroll += scroll_delta.x + scroll_delta.y;
+        if ui_state_hot_bar.page_scroll >= HOTBAR_PAGE_SCROLL_STEP {
+            ui_state_hot_bar.page_scroll = 0.0;
+            prev_page = true;
+        } else if ui_state_hot_bar.page_scroll <= -HOTBAR_PAGE_SCROLL_STEP {
+            ui_state_hot_bar.page_scroll = 0.0;
+            next_page = true;
+        }
+    } else {
+        ui_state_hot_bar.page_scroll = 0.0;
     }
 
-    if response_hprev_button.map_or(false, |r| r.clicked())
-        || response_vprev_button.map_or(false, |r| r.clicked())
-    {
+    // Holding a dragged item over the page buttons switches page, so it can be dropped on any page
+    let dragging = ui_state_dnd
+        .dragged_item
+        .as_ref()
+        .map_or(false, hotbar_drag_accepts);
+    let drag_over_next = dragging
+        && (pointer_over(response_hnext_button.as_ref().map(|r| r.rect))
+            || pointer_over(response_vnext_button.as_ref().map(|r| r.rect)));
+    let drag_over_prev = dragging
+        && (pointer_over(response_hprev_button.as_ref().map(|r| r.rect))
+            || pointer_over(response_vprev_button.as_ref().map(|r| r.rect)));
+    if drag_over_next || drag_over_prev {
+        ui_state_hot_bar.drag_page_switch_time += time.delta_seconds();
+        if ui_state_hot_bar.drag_page_switch_time >= HOTBAR_DRAG_PAGE_SWITCH_DELAY {
+            ui_state_hot_bar.drag_page_switch_time = 0.0;
+            next_page |= drag_over_next;
+            prev_page |= drag_over_prev;
+        }
+    } else {
+        ui_state_hot_bar.drag_page_switch_time = 0.0;
+    }
+
+    if let Some(page) = select_page.filter(|page| *page < HOTBAR_NUM_PAGES) {
+        ui_state_hot_bar.current_page = page;
+    } else if next_page {
+        ui_state_hot_bar.current_page = (ui_state_hot_bar.current_page + 1) % HOTBAR_NUM_PAGES;
+    } else if prev_page {
         if ui_state_hot_bar.current_page == 0 {
             ui_state_hot_bar.current_page = HOTBAR_NUM_PAGES - 1;
         } else {
@@ -364,6 +440,18 @@ pub fn ui_hotbar_system(
     }
 
     if ui_state_hot_bar.current_page != previous_page {
+        if client_settings
+            .controls
+            .hotbar_pages
+            .get(&player.character_info.name)
+            != Some(&ui_state_hot_bar.current_page)
+        {
+            client_settings.controls.hotbar_pages.insert(
+                player.character_info.name.clone(),
+                ui_state_hot_bar.current_page,
+            );
+        }
+
         if let Some(Widget::Image(sprite)) = dialog.get_widget_mut(IID_NUMBER) {
             sprite.sprite = match ui_state_hot_bar.current_page {
                 0 => ui_resources.get_sprite(0, "UI21_NUMBER_1"),
@@ -0,0 +1,66 @@
+use bevy::prelude::{EventReader, Local, Res, Time};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    events::ExtendedServerMessageEvent,
+    protocol::{ExtendedClientMessage, ExtendedServerMessage},
+    resources::GameConnection,
+};
+
+#[derive(Default)]
+pub struct UiStateIdleWarning {
+    disconnect_time: Option<f64>,
+}
+
+pub fn ui_idle_warning_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateIdleWarning>,
+    game_connection: Option<Res<GameConnection>>,
+    time: Res<Time>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    let Some(game_connection) = game_connection else {
+        ui_state.disconnect_time = None;
+        return;
+    };
+
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        if let ExtendedServerMessage::IdleWarning(warning) = message {
+            ui_state.disconnect_time =
+                Some(time.elapsed_seconds_f64() + warning.disconnect_in.as_secs_f64());
+        }
+    }
+
+    let Some(disconnect_time) = ui_state.disconnect_time else {
+        return;
+    };
+    let remaining = (disconnect_time - time.elapsed_seconds_f64()).max(0.0);
+
+    let mut stay_connected = false;
+    egui::Window::new("Idle Warning")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 120.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            if remaining > 0.0 {
+                ui.label(format!(
+                    "You will be disconnected for inactivity in {} seconds",
+                    remaining.ceil() as i32
+                ));
+            } else {
+                ui.label("You are about to be disconnected for inactivity");
+            }
+
+            if ui.button("Stay Connected").clicked() {
+                stay_connected = true;
+            }
+        });
+
+    if stay_connected {
+        game_connection
+            .extended_client_message_tx
+            .send(ExtendedClientMessage::IdleKeepAlive)
+            .ok();
+        ui_state.disconnect_time = None;
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::{EventReader, Input, KeyCode, Local, Query, Res, Time, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::{Hotbar, HotbarSlot, Inventory, ItemSlot, SkillList, SkillSlot};
+
+use crate::{
+    components::PlayerCharacter,
+    events::PlayerCommandEvent,
+    resources::{ClientSettings, GameData, InputDisplayPosition},
+};
+
+const INPUT_DISPLAY_MAX_ENTRIES: usize = 8;
+const INPUT_DISPLAY_MARGIN: f32 = 16.0;
+
+// Entries fade out over the last part of their display time
+const INPUT_DISPLAY_FADE_FRACTION: f32 = 0.5;
+
+const INPUT_DISPLAY_COLOR_KEY: egui::Color32 = egui::Color32::WHITE;
+const INPUT_DISPLAY_COLOR_ACTION: egui::Color32 = egui::Color32::from_rgb(255, 220, 120);
+
+const MODIFIER_KEYS: [(KeyCode, KeyCode, &str); 3] = [
+    (KeyCode::ControlLeft, KeyCode::ControlRight, "Ctrl"),
+    (KeyCode::AltLeft, KeyCode::AltRight, "Alt"),
+    (KeyCode::ShiftLeft, KeyCode::ShiftRight, "Shift"),
+];
+
+struct InputDisplayEntry {
+    text: String,
+    color: egui::Color32,
+    elapsed: f32,
+}
+
+#[derive(Default)]
+pub struct UiStateInputDisplay {
+    entries: VecDeque<InputDisplayEntry>,
+}
+
+impl UiStateInputDisplay {
+    fn push(&mut self, text: String, color: egui::Color32) {
+        if self.entries.len() >= INPUT_DISPLAY_MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(InputDisplayEntry {
+            text,
+            color,
+            elapsed: 0.0,
+        });
+    }
+}
+
+fn is_modifier_key(key_code: KeyCode) -> bool {
+    MODIFIER_KEYS
+        .iter()
+        .any(|(left, right, _)| key_code == *left || key_code == *right)
+}
+
+fn get_key_name(key_code: KeyCode) -> String {
+    match key_code {
+        KeyCode::Key1 => "1".to_string(),
+        KeyCode::Key2 => "2".to_string(),
+        KeyCode::Key3 => "3".to_string(),
+        KeyCode::Key4 => "4".to_string(),
+        KeyCode::Key5 => "5".to_string(),
+        KeyCode::Key6 => "6".to_string(),
+        KeyCode::Key7 => "7".to_string(),
+        KeyCode::Key8 => "8".to_string(),
+        KeyCode::Key9 => "9".to_string(),
+        KeyCode::Key0 => "0".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Return => "Enter".to_string(),
+        KeyCode::Back => "Backspace".to_string(),
+        key_code => format!("{:?}", key_code),
+    }
+}
+
+fn get_skill_name(
+    game_data: &GameData,
+    skill_list: &SkillList,
+    skill_slot: SkillSlot,
+) -> Option<String> {
+    skill_list
+        .get_skill(skill_slot)
+        .and_then(|skill_id| game_data.skills.get_skill(skill_id))
+        .map(|skill_data| skill_data.name.to_string())
+}
+
+fn get_item_name(
+    game_data: &GameData,
+    inventory: &Inventory,
+    item_slot: ItemSlot,
+) -> Option<String> {
+    inventory
+        .get_item(item_slot)
+        .and_then(|item| game_data.items.get_base_item(item.get_item_reference()))
+        .map(|item_data| item_data.name.to_string())
+}
+
+pub fn ui_input_display_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateInputDisplay>,
+    mut player_command_events: EventReader<PlayerCommandEvent>,
+    query_player: Query<(&Hotbar, &SkillList, &Inventory), With<PlayerCharacter>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    client_settings: Res<ClientSettings>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let ui_state = &mut *ui_state;
+    let interface = &client_settings.interface;
+    if !interface.show_input_display {
+        player_command_events.clear();
+        ui_state.entries.clear();
+        return;
+    }
+
+    let ctx = egui_context.ctx_mut();
+
+    // Keys typed into text fields such as chat are not shown
+    if !ctx.wants_keyboard_input() {
+        let modifiers = MODIFIER_KEYS
+            .iter()
+            .filter(|(left, right, _)| keyboard_input.any_pressed([*left, *right]))
+            .map(|(_, _, name)| *name)
+            .collect::<Vec<_>>();
+
+        for key_code in keyboard_input.get_just_pressed() {
+            if is_modifier_key(*key_code) {
+                continue;
+            }
+
+            let mut text = modifiers.join(" + ");
+            if !text.is_empty() {
+                text.push_str(" + ");
+            }
+            text.push_str(&get_key_name(*key_code));
+            ui_state.push(text, INPUT_DISPLAY_COLOR_KEY);
+        }
+    }
+
+    let player = query_player.get_single().ok();
+    for event in player_command_events.iter() {
+        let Some((hotbar, skill_list, inventory)) = player else {
+            continue;
+        };
+
+        let hotbar_slot = match *event {
+            PlayerCommandEvent::UseHotbar(page, index) => hotbar
+                .pages
+                .get(page)
+                .and_then(|page| page.get(index))
+                .and_then(|slot| slot.as_ref())
+                .cloned(),
+            PlayerCommandEvent::UseSkill(skill_slot) => Some(HotbarSlot::Skill(skill_slot)),
+            PlayerCommandEvent::UseItem(item_slot) => Some(HotbarSlot::Inventory(item_slot)),
+            _ => None,
+        };
+
+        let name = match hotbar_slot {
+            Some(HotbarSlot::Skill(skill_slot)) => {
+                get_skill_name(&game_data, skill_list, skill_slot)
+            }
+            Some(HotbarSlot::Inventory(item_slot)) => {
+                get_item_name(&game_data, inventory, item_slot)
+            }
+            _ => None,
+        };
+        if let Some(name) = name {
+            ui_state.push(name, INPUT_DISPLAY_COLOR_ACTION);
+        }
+    }
+
+    let fade_time = interface.input_display_fade_time.max(0.1);
+    let delta = time.delta_seconds();
+    ui_state.entries.retain_mut(|entry| {
+        entry.elapsed += delta;
+        entry.elapsed < fade_time
+    });
+
+    if ui_state.entries.is_empty() {
+        return;
+    }
+
+    let (anchor, offset, layout) = match interface.input_display_position {
+        InputDisplayPosition::TopLeft => (
+            egui::Align2::LEFT_TOP,
+            egui::vec2(INPUT_DISPLAY_MARGIN, INPUT_DISPLAY_MARGIN),
+            egui::Layout::top_down(egui::Align::Min),
+        ),
+        InputDisplayPosition::TopRight => (
+            egui::Align2::RIGHT_TOP,
+            egui::vec2(-INPUT_DISPLAY_MARGIN, INPUT_DISPLAY_MARGIN),
+            egui::Layout::top_down(egui::Align::Max),
+        ),
+        InputDisplayPosition::BottomLeft => (
+            egui::Align2::LEFT_BOTTOM,
+            egui::vec2(INPUT_DISPLAY_MARGIN, -INPUT_DISPLAY_MARGIN),
+            egui::Layout::bottom_up(egui::Align::Min),
+        ),
+        InputDisplayPosition::BottomRight => (
+            egui::Align2::RIGHT_BOTTOM,
+            egui::vec2(-INPUT_DISPLAY_MARGIN, -INPUT_DISPLAY_MARGIN),
+            egui::Layout::bottom_up(egui::Align::Max),
+        ),
+    };
+
+    let fade_start = fade_time * (1.0 - INPUT_DISPLAY_FADE_FRACTION);
+
+    egui::Area::new("input_display")
+        .anchor(anchor, offset)
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.with_layout(layout, |ui| {
+                // Newest entry is nearest to the screen edge
+                for entry in ui_state.entries.iter().rev() {
+                    let opacity = (1.0 - (entry.elapsed - fade_start) / (fade_time - fade_start))
+                        .clamp(0.0, 1.0);
+                    egui::Frame::none()
+                        .fill(egui::Color32::from_black_alpha(160).linear_multiply(opacity))
+                        .rounding(egui::Rounding::same(4.0))
+                        .inner_margin(egui::Margin::symmetric(8.0, 4.0))
+                        .show(ui, |ui| {
+                            ui.label(
+                                egui::RichText::new(&entry.text)
+                                    .size(18.0)
+                                    .color(entry.color.linear_multiply(opacity)),
+                            );
+                        });
+                }
+            });
+        });
+}
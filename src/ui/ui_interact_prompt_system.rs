@@ -0,0 +1,144 @@
+use bevy::{
+    input::Input,
+    prelude::{
+        Camera, Camera3d, Entity, EventWriter, GlobalTransform, KeyCode, Query, Res, Vec2, Vec3,
+        With,
+    },
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{
+        ClientEntity, ClientEntityName, GrowthObject, ModelHeight, PlayerCharacter, Position,
+        WarpObject,
+    },
+    events::PlayerCommandEvent,
+    resources::ClientSettings,
+    systems::is_interactable,
+};
+
+use super::ui_warp_gate_system::WARP_GATE_PROMPT_DISTANCE;
+
+// Interactable entities closer than this to the player show the key prompt
+pub const INTERACT_PROMPT_DISTANCE: f32 = 5.0;
+
+pub fn ui_interact_prompt_system(
+    mut egui_context: EguiContexts,
+    query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query_player: Query<&GlobalTransform, With<PlayerCharacter>>,
+    query_interactable: Query<(
+        Entity,
+        &ClientEntity,
+        &ClientEntityName,
+        &Position,
+        &GlobalTransform,
+        Option<&GrowthObject>,
+        Option<&ModelHeight>,
+    )>,
+    query_warp_objects: Query<&GlobalTransform, With<WarpObject>>,
+    client_settings: Res<ClientSettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut player_command_events: EventWriter<PlayerCommandEvent>,
+) {
+    let Ok(player_transform) = query_player.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation();
+
+    let mut nearest = None;
+    for (entity, client_entity, name, position, global_transform, growth_object, model_height) in
+        query_interactable.iter()
+    {
+        if !is_interactable(client_entity, growth_object) {
+            continue;
+        }
+
+        let distance = global_transform.translation().distance(player_position);
+        if distance < INTERACT_PROMPT_DISTANCE
+            && nearest
+                .as_ref()
+                .map_or(true, |(_, nearest_distance)| distance < *nearest_distance)
+        {
+            nearest = Some((
+                (
+                    entity,
+                    name,
+                    position,
+                    global_transform,
+                    growth_object,
+                    model_height,
+                ),
+                distance,
+            ));
+        }
+    }
+    let Some((
+        (entity, name, position, global_transform, growth_object, model_height),
+        nearest_distance,
+    )) = nearest
+    else {
+        return;
+    };
+
+    // Both prompts share the interact key, a closer warp gate takes priority
+    if query_warp_objects.iter().any(|warp_transform| {
+        let distance = warp_transform.translation().distance(player_position);
+        distance < WARP_GATE_PROMPT_DISTANCE && distance < nearest_distance
+    }) {
+        return;
+    }
+
+    let interact_key = client_settings.controls.interact_key;
+    let prompt = if growth_object.is_some() {
+        format!("[{}] Harvest {}", interact_key.name(), name.as_str())
+    } else {
+        format!("[{}] Talk to {}", interact_key.name(), name.as_str())
+    };
+
+    let ctx = egui_context.ctx_mut();
+    let screen_size = ctx.input(|input| input.screen_rect().size());
+    if let Ok((camera, camera_transform)) = query_camera.get_single() {
+        let height = model_height.map_or(2.0, |model_height| model_height.height);
+        if let Some(ndc_space_coords) = camera
+            .world_to_ndc(
+                camera_transform,
+                global_transform.translation() + Vec3::new(0.0, height + 0.5, 0.0),
+            )
+            .filter(|ndc| (0.0..=1.0).contains(&ndc.z))
+        {
+            let screen_pos = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0
+                * Vec2::new(screen_size.x, screen_size.y);
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Background,
+                egui::Id::new("interact_prompt"),
+            ));
+            let galley = painter.layout_no_wrap(
+                prompt,
+                egui::FontId::proportional(14.0),
+                egui::Color32::from_rgb(255, 220, 120),
+            );
+            let text_rect = egui::Align2::CENTER_BOTTOM.anchor_rect(egui::Rect::from_min_size(
+                egui::pos2(screen_pos.x, screen_size.y - screen_pos.y),
+                galley.size(),
+            ));
+            painter.rect_filled(
+                text_rect.expand(4.0),
+                egui::Rounding::same(4.0),
+                egui::Color32::from_black_alpha(160),
+            );
+            painter.galley(text_rect.min, galley);
+        }
+    }
+
+    if ctx.wants_keyboard_input() || !keyboard_input.just_pressed(interact_key.key_code()) {
+        return;
+    }
+
+    if growth_object.is_some() {
+        // The server harvests a fully grown plant when it is attacked
+        player_command_events.send(PlayerCommandEvent::Attack(entity));
+    } else {
+        // Once in range the command_system will open the npc conversation
+        player_command_events.send(PlayerCommandEvent::Move(position.clone(), Some(entity)));
+    }
+}
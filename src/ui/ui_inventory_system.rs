@@ -1,25 +1,28 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Assets, EventWriter, Events, Local, Query, Res, ResMut, With, World},
+    prelude::{Assets, EventWriter, Local, Query, Res, ResMut, With},
 };
 use bevy_egui::{egui, EguiContexts};
 use enum_map::{enum_map, EnumMap};
 
-use rose_data::{AmmoIndex, EquipmentIndex, Item, VehiclePartIndex};
+use rose_data::{AmmoIndex, EquipmentIndex, Item, ItemType, VehiclePartIndex};
 use rose_game_common::components::{
-    Equipment, Inventory, InventoryPageType, ItemSlot, INVENTORY_PAGE_SIZE,
+    Equipment, Inventory, InventoryPageType, ItemSlot, QuestState, INVENTORY_PAGE_SIZE,
 };
 
 use crate::{
     components::{Cooldowns, PlayerCharacter},
-    events::{NumberInputDialogEvent, PersonalStoreEvent, PlayerCommandEvent},
-    resources::{GameData, UiResources},
+    events::{DropItemEvent, PersonalStoreEvent, PlayerCommandEvent},
+    protocol::ProtocolCapabilities,
+    resources::{ClientCapabilities, GameData, UiResources},
     ui::{
+        format::format_signed_number,
+        set_quest_list_selection,
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog, Widget},
-        DialogInstance, DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop,
-        UiStateWindows,
+        DialogInstance, DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent,
+        UiStateDragAndDrop, UiStateWindows, UiWindowId, ZulyInput,
     },
 };
 
@@ -45,13 +48,26 @@ const IID_BTN_MINIMIZE: i32 = 213;
 const IID_BTN_MAXIMIZE: i32 = 214;
 const IID_PANE_INVEN: i32 = 300;
 
+const QUEST_ITEM_SLOT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 160, 60);
+
+// Shown when a stackable item is dragged onto another slot while holding shift
+struct UiStackSplit {
+    source: ItemSlot,
+    destination: ItemSlot,
+    max_quantity: usize,
+    quantity: usize,
+}
+
 pub struct UiStateInventory {
     dialog_instance: DialogInstance,
     item_slot_map: EnumMap<InventoryPageType, Vec<ItemSlot>>,
     current_equipment_tab: i32,
     current_vehicle_tab: i32,
     current_inventory_tab: i32,
+    show_quest_items: bool,
     minimised: bool,
+    stack_split: Option<UiStackSplit>,
+    drop_money: Option<u64>,
 }
 
 impl Default for UiStateInventory {
@@ -66,7 +82,10 @@ impl Default for UiStateInventory {
             current_equipment_tab: IID_TAB_EQUIP_AVATAR,
             current_vehicle_tab: IID_TAB_INVEN_PAT,
             current_inventory_tab: IID_TAB_INVEN_EQUIP,
+            show_quest_items: false,
             minimised: false,
+            stack_split: None,
+            drop_money: None,
         }
     }
 }
@@ -239,9 +258,12 @@ fn ui_add_inventory_slot(
     game_data: &GameData,
     ui_resources: &UiResources,
     item_slot_map: &mut EnumMap<InventoryPageType, Vec<ItemSlot>>,
+    can_split_stacks: bool,
+    stack_split: &mut Option<UiStackSplit>,
     ui_state_dnd: &mut UiStateDragAndDrop,
     player_command_events: &mut EventWriter<PlayerCommandEvent>,
     personal_store_events: &mut EventWriter<PersonalStoreEvent>,
+    drop_item_events: &mut EventWriter<DropItemEvent>,
 ) {
     let drag_accepts = match inventory_slot {
         ItemSlot::Inventory(page_type, _) => match page_type {
@@ -315,6 +337,7 @@ fn ui_add_inventory_slot(
         }
     }
 
+    let item_reference = item.as_ref().map(|item| item.get_item_reference());
     if let Some(item) = item {
         let response = response.context_menu(|ui| {
             if matches!(
@@ -355,12 +378,15 @@ fn ui_add_inventory_slot(
                 use_inventory_slot = Some(inventory_slot);
             }
 
-            if matches!(inventory_slot, ItemSlot::Inventory(_, _)) && ui.button("Drop").clicked() {
+            if matches!(inventory_slot, ItemSlot::Inventory(_, _))
+                && !matches!(item_reference, Some(item) if item.item_type == ItemType::Quest)
+                && ui.button("Drop").clicked()
+            {
                 drop_inventory_slot = Some(inventory_slot);
             }
         });
 
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
         });
     }
@@ -369,7 +395,30 @@ fn ui_add_inventory_slot(
         match inventory_slot {
             ItemSlot::Inventory(_, _) => match dropped_inventory_slot {
                 ItemSlot::Inventory(_, _) => {
-                    swap_inventory_slots = Some((inventory_slot, dropped_inventory_slot))
+                    // Without server support for splitting the slots are swapped as usual
+                    let split_item = (can_split_stacks && ui.input(|input| input.modifiers.shift))
+                        .then(|| player.inventory.get_item(dropped_inventory_slot))
+                        .flatten()
+                        .filter(|split_item| {
+                            matches!(split_item, Item::Stackable(_))
+                                && split_item.get_quantity() > 1
+                                && dropped_inventory_slot != inventory_slot
+                                && item_reference.map_or(true, |item_reference| {
+                                    item_reference == split_item.get_item_reference()
+                                })
+                        });
+
+                    if let Some(split_item) = split_item {
+                        let max_quantity = split_item.get_quantity() as usize;
+                        *stack_split = Some(UiStackSplit {
+                            source: dropped_inventory_slot,
+                            destination: inventory_slot,
+                            max_quantity,
+                            quantity: max_quantity / 2,
+                        });
+                    } else {
+                        swap_inventory_slots = Some((inventory_slot, dropped_inventory_slot))
+                    }
                 }
                 ItemSlot::Equipment(equipment_index) => {
                     unequip_equipment_index = Some(equipment_index);
@@ -449,7 +498,9 @@ fn ui_add_inventory_slot(
     }
 
     if let Some(drop_inventory_slot) = drop_inventory_slot {
-        player_command_events.send(PlayerCommandEvent::DropItem(drop_inventory_slot));
+        drop_item_events.send(DropItemEvent {
+            item_slot: drop_inventory_slot,
+        });
     }
 
     if let Some((ItemSlot::Inventory(page_a, slot_a), ItemSlot::Inventory(page_b, slot_b))) =
@@ -471,11 +522,65 @@ fn ui_add_inventory_slot(
     }
 }
 
+// Quest items are held by the active quests rather than an inventory page,
+// they can not be moved or dropped so the slot only shows the item
+fn ui_add_quest_item_slot(
+    ui: &mut egui::Ui,
+    quest_id: usize,
+    item: &Item,
+    pos: egui::Pos2,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+    show_in_quest_list: &mut Option<usize>,
+) {
+    let mut dragged_item = None;
+    let mut dropped_item = None;
+    let rect = egui::Rect::from_min_size(ui.min_rect().min + pos.to_vec2(), egui::vec2(40.0, 40.0));
+    let response = ui
+        .allocate_ui_at_rect(rect, |ui| {
+            egui::Widget::ui(
+                DragAndDropSlot::with_item(
+                    DragAndDropId::NotDraggable,
+                    Some(item),
+                    None,
+                    game_data,
+                    ui_resources,
+                    |_| false,
+                    &mut dragged_item,
+                    &mut dropped_item,
+                    [40.0, 40.0],
+                ),
+                ui,
+            )
+        })
+        .inner;
+
+    ui.painter()
+        .rect_stroke(rect, 2.0, egui::Stroke::new(1.0, QUEST_ITEM_SLOT_COLOR));
+
+    if response.double_clicked() {
+        *show_in_quest_list = Some(quest_id);
+    }
+
+    response
+        .context_menu(|ui| {
+            if ui.button("Show Quest").clicked() {
+                *show_in_quest_list = Some(quest_id);
+                ui.close_menu();
+            }
+        })
+        .on_hover_tooltip_ui(|ui| {
+            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+        });
+}
+
 #[derive(WorldQuery)]
 pub struct PlayerQuery<'w> {
     equipment: &'w Equipment,
     inventory: &'w Inventory,
     cooldowns: &'w Cooldowns,
+    quest_state: Option<&'w QuestState>,
 }
 
 pub fn ui_inventory_system(
@@ -489,9 +594,10 @@ pub fn ui_inventory_system(
     dialog_assets: Res<Assets<Dialog>>,
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
+    client_capabilities: Res<ClientCapabilities>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     mut personal_store_events: EventWriter<PersonalStoreEvent>,
-    mut number_input_dialog_events: EventWriter<NumberInputDialogEvent>,
+    mut drop_item_events: EventWriter<DropItemEvent>,
 ) {
     let ui_state_inventory = &mut *ui_state_inventory;
     let dialog = if let Some(dialog) = ui_state_inventory
@@ -508,6 +614,7 @@ pub fn ui_inventory_system(
         return;
     };
     let player_tooltip_data = query_player_tooltip.get_single().ok();
+    let can_split_stacks = client_capabilities.supports(ProtocolCapabilities::ITEM_STACK_SPLIT);
 
     let mut response_close_button = None;
     let mut response_minimise_button = None;
@@ -515,10 +622,25 @@ pub fn ui_inventory_system(
     let mut response_drop_money_button = None;
     let is_equipment_tab = ui_state_inventory.current_equipment_tab == IID_TAB_EQUIP_AVATAR;
     let is_minimised = ui_state_inventory.minimised;
+    let previous_inventory_tab = ui_state_inventory.current_inventory_tab;
+    let mut show_in_quest_list = None;
+
+    let quest_items = player
+        .quest_state
+        .iter()
+        .flat_map(|quest_state| quest_state.active_quests.iter().flatten())
+        .flat_map(|active_quest| {
+            active_quest
+                .items
+                .iter()
+                .flatten()
+                .map(|item| (active_quest.quest_id, item))
+        })
+        .collect::<Vec<_>>();
 
     egui::Window::new("Inventory")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.inventory_open)
+        .open(ui_state_windows.open_mut(UiWindowId::INVENTORY))
         .title_bar(false)
         .resizable(false)
         .default_width(dialog.width)
@@ -572,9 +694,12 @@ pub fn ui_inventory_system(
                                         &game_data,
                                         &ui_resources,
                                         &mut ui_state_inventory.item_slot_map,
+                                        can_split_stacks,
+                                        &mut ui_state_inventory.stack_split,
                                         &mut ui_state_dnd,
                                         &mut player_command_events,
                                         &mut personal_store_events,
+                                        &mut drop_item_events,
                                     );
                                 }
                             }
@@ -604,9 +729,12 @@ pub fn ui_inventory_system(
                                         &game_data,
                                         &ui_resources,
                                         &mut ui_state_inventory.item_slot_map,
+                                        can_split_stacks,
+                                        &mut ui_state_inventory.stack_split,
                                         &mut ui_state_dnd,
                                         &mut player_command_events,
                                         &mut personal_store_events,
+                                        &mut drop_item_events,
                                     );
                                 }
                             }
@@ -622,26 +750,53 @@ pub fn ui_inventory_system(
                         283.0
                     };
 
+                    let show_quest_items = ui_state_inventory.show_quest_items
+                        && is_equipment_tab
+                        && bindings
+                            .get_tab(IID_TABBEDPANE_INVEN_ITEM)
+                            .map_or(false, |tab| *tab == previous_inventory_tab);
+
                     for row in 0..6 {
                         for column in 0..5 {
+                            let pos = egui::pos2(
+                                12.0 + column as f32 * 41.0,
+                                y_start + row as f32 * 41.0,
+                            );
+
+                            if show_quest_items {
+                                if let Some((quest_id, item)) = quest_items.get(column + row * 5) {
+                                    ui_add_quest_item_slot(
+                                        ui,
+                                        *quest_id,
+                                        item,
+                                        pos,
+                                        player_tooltip_data.as_ref(),
+                                        &game_data,
+                                        &ui_resources,
+                                        &mut show_in_quest_list,
+                                    );
+                                }
+                                continue;
+                            }
+
                             let inventory_slot =
                                 ui_state_inventory.item_slot_map[current_page][column + row * 5];
 
                             ui_add_inventory_slot(
                                 ui,
                                 inventory_slot,
-                                egui::pos2(
-                                    12.0 + column as f32 * 41.0,
-                                    y_start + row as f32 * 41.0,
-                                ),
+                                pos,
                                 &player,
                                 player_tooltip_data.as_ref(),
                                 &game_data,
                                 &ui_resources,
                                 &mut ui_state_inventory.item_slot_map,
+                                can_split_stacks,
+                                &mut ui_state_inventory.stack_split,
                                 &mut ui_state_dnd,
                                 &mut player_command_events,
                                 &mut personal_store_events,
+                                &mut drop_item_events,
                             );
                         }
 
@@ -682,17 +837,50 @@ pub fn ui_inventory_system(
                         )),
                         |ui| {
                             ui.horizontal_top(|ui| {
-                                ui.add(egui::Label::new(format!("{}", player.inventory.money.0)))
+                                ui.add(egui::Label::new(format_signed_number(
+                                    player.inventory.money.0,
+                                )))
                             })
                             .inner
                         },
                     );
+
+                    if is_equipment_tab {
+                        ui.allocate_ui_at_rect(
+                            ui.min_rect().translate(egui::vec2(
+                                dialog.width - 60.0,
+                                dialog.height - 27.0 - if is_minimised { 200.0 } else { 0.0 },
+                            )),
+                            |ui| {
+                                let text =
+                                    egui::RichText::new(format!("Quest ({})", quest_items.len()))
+                                        .color(QUEST_ITEM_SLOT_COLOR);
+                                if ui
+                                    .selectable_label(show_quest_items, text)
+                                    .on_hover_text("Items held for your active quests")
+                                    .clicked()
+                                {
+                                    ui_state_inventory.show_quest_items = !show_quest_items;
+                                }
+                            },
+                        );
+                    }
                 },
             );
         });
 
+    // Selecting one of the item tabs leaves the quest items page
+    if previous_inventory_tab != ui_state_inventory.current_inventory_tab {
+        ui_state_inventory.show_quest_items = false;
+    }
+
+    if let Some(quest_id) = show_in_quest_list {
+        set_quest_list_selection(egui_context.ctx_mut(), quest_id);
+        ui_state_windows.set_open(UiWindowId::QUEST_LIST, true);
+    }
+
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.inventory_open = false;
+        ui_state_windows.set_open(UiWindowId::INVENTORY, false);
     }
 
     if response_minimise_button.map_or(false, |r| r.clicked()) {
@@ -712,19 +900,92 @@ pub fn ui_inventory_system(
     }
 
     if response_drop_money_button.map_or(false, |r| r.clicked()) && player.inventory.money.0 > 0 {
-        number_input_dialog_events.send(NumberInputDialogEvent::Show {
-            max_value: Some(player.inventory.money.0 as usize),
-            modal: false,
-            ok: Some(Box::new(move |commands, amount| {
-                commands.add(move |world: &mut World| {
-                    if let Some(mut player_command_events) =
-                        world.get_resource_mut::<Events<PlayerCommandEvent>>()
+        ui_state_inventory.drop_money = Some(0);
+    }
+
+    if let Some(drop_money) = ui_state_inventory.drop_money.as_mut() {
+        let max_money = player.inventory.money.0.max(0) as u64;
+        let mut confirm = false;
+        let mut cancel = !ui_state_windows.is_open(UiWindowId::INVENTORY);
+
+        egui::Window::new("Drop Zuly")
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_context.ctx_mut(), |ui| {
+                ui.label("How much zuly do you want to drop?");
+                ui.add(ZulyInput::new("drop_money", drop_money).max_value(max_money));
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(*drop_money > 0, egui::Button::new("OK"))
+                        .clicked()
                     {
-                        player_command_events.send(PlayerCommandEvent::DropMoney(amount));
+                        confirm = true;
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
                     }
                 });
-            })),
-            cancel: None,
-        });
+            });
+
+        if confirm && !cancel {
+            player_command_events.send(PlayerCommandEvent::DropMoney(
+                (*drop_money).min(max_money) as usize
+            ));
+        }
+
+        if confirm || cancel {
+            ui_state_inventory.drop_money = None;
+        }
+    }
+
+    if let Some(stack_split) = ui_state_inventory.stack_split.as_mut() {
+        let mut confirm = false;
+        let mut cancel = !ui_state_windows.is_open(UiWindowId::INVENTORY)
+            || player.inventory.get_item(stack_split.source).is_none();
+
+        egui::Window::new("Split Stack")
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_context.ctx_mut(), |ui| {
+                ui.label("How many items do you want to move?");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::Slider::new(&mut stack_split.quantity, 1..=stack_split.max_quantity)
+                            .show_value(false),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut stack_split.quantity)
+                            .clamp_range(1..=stack_split.max_quantity),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Confirm").clicked()
+                        || ui.input(|input| input.key_pressed(egui::Key::Enter))
+                    {
+                        confirm = true;
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if confirm && !cancel {
+            player_command_events.send(PlayerCommandEvent::SplitItemStack(
+                stack_split.source,
+                stack_split.destination,
+                stack_split.quantity,
+            ));
+        }
+
+        if confirm || cancel {
+            ui_state_inventory.stack_split = None;
+        }
     }
 }
@@ -1,4 +1,4 @@
-use bevy::prelude::{Input, KeyCode, Local, Res, ResMut};
+use bevy::prelude::{Assets, Input, KeyCode, Local, Res, ResMut};
 use bevy_egui::{egui, EguiContexts};
 
 use rose_data::ItemType;
@@ -6,8 +6,8 @@ use rose_data_irose::encode_item_type;
 use rose_game_common::messages::client::ClientMessage;
 
 use crate::{
-    resources::{GameConnection, GameData},
-    ui::UiStateWindows,
+    resources::{GameConnection, GameData, UiResources},
+    ui::{widgets::Dialog, UiSkin, UiStateWindows, UiWindowId},
 };
 
 const ITEM_TYPES: [ItemType; 14] = [
@@ -34,6 +34,11 @@ struct BrowserItemRow {
     item_name: String,
 }
 
+struct ItemNameIndexEntry {
+    row: BrowserItemRow,
+    lowercase_name: String,
+}
+
 pub struct UiStateItemBrowser {
     filter_item_type: Option<ItemType>,
     search_text: String,
@@ -43,7 +48,9 @@ pub struct UiStateItemBrowser {
     grade: u8,
     filtered_items: Vec<BrowserItemRow>,
     last_status: Option<String>,
-    last_search_key: String,
+    last_search_text: Option<String>,
+    last_filter_item_type: Option<ItemType>,
+    item_index: Vec<ItemNameIndexEntry>,
 }
 
 impl Default for UiStateItemBrowser {
@@ -57,28 +64,16 @@ impl Default for UiStateItemBrowser {
             grade: 0,
             filtered_items: Vec::new(),
             last_status: None,
-            last_search_key: String::new(),
+            last_search_text: None,
+            last_filter_item_type: None,
+            item_index: Vec::new(),
         }
     }
 }
 
-fn refresh_item_results(ui_state: &mut UiStateItemBrowser, game_data: &GameData) {
-    let query = ui_state.search_text.to_ascii_lowercase();
-    let filter_key = ui_state
-        .filter_item_type
-        .map(|item_type| format!("{:?}", item_type))
-        .unwrap_or_else(|| String::from("ALL"));
-    ui_state.last_search_key = format!("{}|{}", query, filter_key);
-
-    let mut rows = Vec::new();
+fn build_item_index(game_data: &GameData) -> Vec<ItemNameIndexEntry> {
+    let mut item_index = Vec::new();
     for item_type in ITEM_TYPES {
-        if ui_state
-            .filter_item_type
-            .map_or(false, |filter_type| filter_type != item_type)
-        {
-            continue;
-        }
-
         for item_reference in game_data.items.iter_items(item_type) {
             let Some(item_data) = game_data.items.get_base_item(item_reference) else {
                 continue;
@@ -89,19 +84,113 @@ fn refresh_item_results(ui_state: &mut UiStateItemBrowser, game_data: &GameData)
                 continue;
             }
 
-            if !query.is_empty() && !item_name.to_ascii_lowercase().contains(&query) {
-                continue;
-            }
-
-            rows.push(BrowserItemRow {
-                item_type,
-                item_id: item_reference.item_number,
-                item_name: item_name.to_string(),
+            item_index.push(ItemNameIndexEntry {
+                row: BrowserItemRow {
+                    item_type,
+                    item_id: item_reference.item_number,
+                    item_name: item_name.to_string(),
+                },
+                lowercase_name: item_name.to_lowercase(),
             });
         }
     }
+    item_index
+}
+
+// Higher scores are better matches, returns None when the query does not match.
+// Exact and prefix matches rank first, then substrings starting at a word,
+// then any substring, then the query characters appearing in order.
+fn fuzzy_match_score(query: &str, name: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    if name == query {
+        return Some(4000);
+    }
+
+    if name.starts_with(query) {
+        return Some(3000);
+    }
+
+    let mut substring_score = None;
+    for (index, _) in name.match_indices(query) {
+        let is_word_start = name[..index]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric());
+        if is_word_start {
+            return Some(2000 - index as i32);
+        }
+
+        if substring_score.is_none() {
+            substring_score = Some(1000 - index as i32);
+        }
+    }
+
+    if substring_score.is_some() {
+        return substring_score;
+    }
+
+    let mut query_chars = query.chars().peekable();
+    let mut first_match = None;
+    let mut gaps = 0;
+    let mut last_match = None;
+    for (index, c) in name.chars().enumerate() {
+        let Some(&query_char) = query_chars.peek() else {
+            break;
+        };
+
+        if c == query_char {
+            query_chars.next();
+            first_match.get_or_insert(index);
+            if let Some(last_match) = last_match {
+                gaps += index - last_match - 1;
+            }
+            last_match = Some(index);
+        }
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+
+    Some(500 - (gaps + first_match.unwrap_or(0)) as i32)
+}
+
+fn refresh_item_results(ui_state: &mut UiStateItemBrowser) {
+    let query = ui_state.search_text.trim().to_lowercase();
+    ui_state.last_search_text = Some(ui_state.search_text.clone());
+    ui_state.last_filter_item_type = ui_state.filter_item_type;
+
+    let mut matches: Vec<(i32, &ItemNameIndexEntry)> = ui_state
+        .item_index
+        .iter()
+        .filter(|entry| {
+            ui_state
+                .filter_item_type
+                .map_or(true, |filter_type| filter_type == entry.row.item_type)
+        })
+        .filter_map(|entry| {
+            fuzzy_match_score(&query, &entry.lowercase_name).map(|score| (score, entry))
+        })
+        .collect();
+
+    if !query.is_empty() {
+        matches.sort_by(|(score_a, entry_a), (score_b, entry_b)| {
+            score_b.cmp(score_a).then_with(|| {
+                entry_a
+                    .lowercase_name
+                    .len()
+                    .cmp(&entry_b.lowercase_name.len())
+            })
+        });
+    }
 
-    ui_state.filtered_items = rows;
+    ui_state.filtered_items = matches
+        .into_iter()
+        .map(|(_, entry)| entry.row.clone())
+        .collect();
 }
 
 pub fn ui_item_browser_system(
@@ -111,41 +200,53 @@ pub fn ui_item_browser_system(
     mut ui_state_item_browser: Local<UiStateItemBrowser>,
     game_connection: Option<Res<GameConnection>>,
     game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
 ) {
     if keyboard_input.just_pressed(KeyCode::F9) {
-        ui_state_windows.item_browser_open = !ui_state_windows.item_browser_open;
+        ui_state_windows.toggle(UiWindowId::ITEM_BROWSER);
     }
 
-    if !ui_state_windows.item_browser_open {
+    if !ui_state_windows.is_open(UiWindowId::ITEM_BROWSER) {
         return;
     }
 
-    let current_filter_key = ui_state_item_browser
-        .filter_item_type
-        .map(|item_type| format!("{:?}", item_type))
-        .unwrap_or_else(|| String::from("ALL"));
-    if ui_state_item_browser.last_search_key
-        != format!(
-            "{}|{}",
-            ui_state_item_browser.search_text.to_ascii_lowercase(),
-            current_filter_key
-        )
+    // The name index is only built once each time the game data is loaded
+    if ui_state_item_browser.item_index.is_empty() || game_data.is_changed() {
+        ui_state_item_browser.item_index = build_item_index(&game_data);
+        ui_state_item_browser.last_search_text = None;
+    }
+
+    if ui_state_item_browser.last_search_text.as_ref() != Some(&ui_state_item_browser.search_text)
+        || ui_state_item_browser.last_filter_item_type != ui_state_item_browser.filter_item_type
     {
-        refresh_item_results(&mut ui_state_item_browser, &game_data);
+        refresh_item_results(&mut ui_state_item_browser);
     }
 
-    egui::Window::new("Item Browser")
+    let ui_skin = UiSkin::new(&ui_resources, &dialog_assets);
+    let mut window = egui::Window::new("Item Browser")
         .resizable(true)
-        .default_size([780.0, 520.0])
-        .open(&mut ui_state_windows.item_browser_open)
+        .default_size([780.0, 520.0]);
+    if let Some(ui_skin) = ui_skin.as_ref() {
+        window = window.frame(ui_skin.window_frame()).title_bar(false);
+    }
+
+    let mut close_clicked = false;
+    window
+        .open(ui_state_windows.open_mut(UiWindowId::ITEM_BROWSER))
         .show(egui_context.ctx_mut(), |ui| {
+            if let Some(ui_skin) = ui_skin.as_ref() {
+                ui_skin.begin_window(ui);
+                close_clicked = ui_skin.title_bar(ui, "Item Browser");
+            }
+
             ui.horizontal(|ui| {
                 ui.label("Search:");
                 if ui
                     .text_edit_singleline(&mut ui_state_item_browser.search_text)
                     .changed()
                 {
-                    refresh_item_results(&mut ui_state_item_browser, &game_data);
+                    refresh_item_results(&mut ui_state_item_browser);
                 }
                 ui.separator();
                 ui.label("Type:");
@@ -172,7 +273,7 @@ pub fn ui_item_browser_system(
                         }
                     });
                 if ui_state_item_browser.filter_item_type != previous_filter {
-                    refresh_item_results(&mut ui_state_item_browser, &game_data);
+                    refresh_item_results(&mut ui_state_item_browser);
                 }
                 ui.label(format!(
                     "Matches: {}",
@@ -228,70 +329,82 @@ pub fn ui_item_browser_system(
 
             ui.style_mut().spacing.item_spacing.y = 2.0;
             ui.set_min_width(740.0);
-            egui::ScrollArea::vertical().show_rows(ui, row_height, row_count, |ui, row_range| {
-                for row_index in row_range {
-                    let row = &ui_state_item_browser.filtered_items[row_index];
-                    ui.horizontal(|ui| {
-                        ui.add_sized(
-                            [110.0, row_height],
-                            egui::Label::new(format!("{:?}", row.item_type)),
-                        );
-                        ui.add_sized(
-                            [80.0, row_height],
-                            egui::Label::new(row.item_id.to_string()),
-                        );
-                        ui.add_sized([420.0, row_height], egui::Label::new(&row.item_name));
-
-                        let can_send = game_connection.is_some();
-                        if ui
-                            .add_enabled(
-                                can_send,
-                                egui::Button::new("Give").min_size(egui::vec2(58.0, row_height)),
-                            )
-                            .clicked()
-                        {
-                            let Some(item_type_code) = encode_item_type(row.item_type) else {
-                                pending_status = Some(format!(
-                                    "Failed: cannot encode item type {:?}",
-                                    row.item_type
-                                ));
-                                return;
-                            };
-
-                            let command = format!(
-                                "/item {} {} {} {} {} {}",
-                                item_type_code,
-                                row.item_id,
-                                ui_state_item_browser.quantity,
-                                if ui_state_item_browser.socket { 1 } else { 0 },
-                                ui_state_item_browser.gem,
-                                ui_state_item_browser.grade
+            let mut show_rows = |scroll_area: egui::ScrollArea, ui: &mut egui::Ui| {
+                scroll_area.show_rows(ui, row_height, row_count, |ui, row_range| {
+                    for row_index in row_range {
+                        let row = &ui_state_item_browser.filtered_items[row_index];
+                        ui.horizontal(|ui| {
+                            ui.add_sized(
+                                [110.0, row_height],
+                                egui::Label::new(format!("{:?}", row.item_type)),
                             );
+                            ui.add_sized(
+                                [80.0, row_height],
+                                egui::Label::new(row.item_id.to_string()),
+                            );
+                            ui.add_sized([420.0, row_height], egui::Label::new(&row.item_name));
 
-                            let send_result = game_connection
-                                .as_ref()
-                                .and_then(|connection| {
-                                    connection
-                                        .client_message_tx
-                                        .send(ClientMessage::Chat {
-                                            text: command.clone(),
-                                        })
-                                        .ok()
-                                })
-                                .is_some();
-
-                            pending_status = Some(if send_result {
-                                format!("Sent: {}", command)
-                            } else {
-                                String::from("Failed: not connected")
-                            });
-                        }
-                    });
-                }
-            });
+                            let can_send = game_connection.is_some();
+                            if ui
+                                .add_enabled(
+                                    can_send,
+                                    egui::Button::new("Give")
+                                        .min_size(egui::vec2(58.0, row_height)),
+                                )
+                                .clicked()
+                            {
+                                let Some(item_type_code) = encode_item_type(row.item_type) else {
+                                    pending_status = Some(format!(
+                                        "Failed: cannot encode item type {:?}",
+                                        row.item_type
+                                    ));
+                                    return;
+                                };
+
+                                let command = format!(
+                                    "/item {} {} {} {} {} {}",
+                                    item_type_code,
+                                    row.item_id,
+                                    ui_state_item_browser.quantity,
+                                    if ui_state_item_browser.socket { 1 } else { 0 },
+                                    ui_state_item_browser.gem,
+                                    ui_state_item_browser.grade
+                                );
+
+                                let send_result = game_connection
+                                    .as_ref()
+                                    .and_then(|connection| {
+                                        connection
+                                            .client_message_tx
+                                            .send(ClientMessage::Chat {
+                                                text: command.clone(),
+                                            })
+                                            .ok()
+                                    })
+                                    .is_some();
+
+                                pending_status = Some(if send_result {
+                                    format!("Sent: {}", command)
+                                } else {
+                                    String::from("Failed: not connected")
+                                });
+                            }
+                        });
+                    }
+                })
+            };
+            if let Some(ui_skin) = ui_skin.as_ref() {
+                ui_skin.scroll_area(ui, egui::ScrollArea::vertical(), show_rows);
+            } else {
+                show_rows(egui::ScrollArea::vertical(), ui);
+            }
 
             if let Some(status) = pending_status {
                 ui_state_item_browser.last_status = Some(status);
             }
         });
+
+    if close_clicked {
+        ui_state_windows.set_open(UiWindowId::ITEM_BROWSER, false);
+    }
 }
@@ -0,0 +1,160 @@
+use bevy::prelude::{Commands, EventWriter, Local, Res};
+use bevy_egui::{egui, EguiContexts};
+use rand::seq::SliceRandom;
+
+use crate::{
+    events::LoginEvent,
+    protocol::LoginChallengeKind,
+    resources::{LoginConnection, LoginState},
+};
+
+pub struct UiStateLoginChallenge {
+    code: String,
+    randomize_layout: bool,
+    digit_layout: [u8; 10],
+    is_open: bool,
+}
+
+impl Default for UiStateLoginChallenge {
+    fn default() -> Self {
+        Self {
+            code: String::new(),
+            randomize_layout: false,
+            digit_layout: [1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+            is_open: false,
+        }
+    }
+}
+
+impl UiStateLoginChallenge {
+    fn reset_layout(&mut self) {
+        self.digit_layout = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
+        if self.randomize_layout {
+            self.digit_layout.shuffle(&mut rand::thread_rng());
+        }
+    }
+}
+
+pub fn ui_login_challenge_system(
+    mut commands: Commands,
+    mut ui_state: Local<UiStateLoginChallenge>,
+    mut egui_context: EguiContexts,
+    login_state: Res<LoginState>,
+    mut login_events: EventWriter<LoginEvent>,
+) {
+    let LoginState::Challenge(challenge) = &*login_state else {
+        ui_state.is_open = false;
+        return;
+    };
+
+    let ui_state = &mut *ui_state;
+    if !ui_state.is_open || login_state.is_changed() {
+        // New challenge, clear any previous code and shuffle the pad again
+        ui_state.is_open = true;
+        ui_state.code.clear();
+        ui_state.reset_layout();
+    }
+
+    let title = match challenge.kind {
+        LoginChallengeKind::Pin => "Enter PIN",
+        LoginChallengeKind::OneTimePassword => "Enter One Time Password",
+    };
+    let mut submit = false;
+    let mut cancel = false;
+
+    egui::Window::new(title)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            if challenge.previous_attempt_failed {
+                ui.colored_label(egui::Color32::RED, "Incorrect code, please try again");
+            }
+
+            // Only show how many digits were entered, the code is entered with the mouse
+            // so it never passes through the keyboard
+            ui.label(
+                egui::RichText::new(format!(
+                    "{}{}",
+                    "*".repeat(ui_state.code.len()),
+                    "_".repeat(challenge.code_length.saturating_sub(ui_state.code.len()))
+                ))
+                .monospace()
+                .size(24.0),
+            );
+
+            let button_size = egui::vec2(48.0, 48.0);
+            egui::Grid::new("login_challenge_pad")
+                .spacing([4.0, 4.0])
+                .show(ui, |ui| {
+                    for (index, digit) in ui_state.digit_layout.iter().enumerate() {
+                        // The last row is Clear, the remaining digit, then Backspace
+                        if index == 9
+                            && ui
+                                .add_sized(button_size, egui::Button::new("Clear"))
+                                .clicked()
+                        {
+                            ui_state.code.clear();
+                        }
+
+                        let can_add_digit = challenge.code_length == 0
+                            || ui_state.code.len() < challenge.code_length;
+                        if ui
+                            .add_enabled(
+                                can_add_digit,
+                                egui::Button::new(
+                                    egui::RichText::new(format!("{}", digit)).size(20.0),
+                                )
+                                .min_size(button_size),
+                            )
+                            .clicked()
+                        {
+                            ui_state.code.push(char::from(b'0' + digit));
+                        }
+
+                        if index == 9 && ui.add_sized(button_size, egui::Button::new("<")).clicked()
+                        {
+                            ui_state.code.pop();
+                        }
+
+                        if index % 3 == 2 || index == 9 {
+                            ui.end_row();
+                        }
+                    }
+                });
+
+            if ui
+                .checkbox(&mut ui_state.randomize_layout, "Randomize layout")
+                .changed()
+            {
+                ui_state.reset_layout();
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let code_complete = !ui_state.code.is_empty()
+                    && (challenge.code_length == 0 || ui_state.code.len() == challenge.code_length);
+                if ui
+                    .add_enabled(code_complete, egui::Button::new("OK"))
+                    .clicked()
+                {
+                    submit = true;
+                }
+
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if submit {
+        login_events.send(LoginEvent::SubmitChallenge {
+            code: std::mem::take(&mut ui_state.code),
+        });
+    }
+
+    if cancel {
+        ui_state.code.clear();
+        commands.remove_resource::<LoginConnection>();
+    }
+}
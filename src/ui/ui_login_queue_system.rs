@@ -0,0 +1,62 @@
+use bevy::prelude::{Commands, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    resources::{LoginQueue, LoginState, WorldConnection},
+    ui::format::format_duration,
+};
+
+pub fn ui_login_queue_system(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    mut login_state: ResMut<LoginState>,
+    login_queue: Option<Res<LoginQueue>>,
+) {
+    if !matches!(*login_state, LoginState::JoiningServer) {
+        return;
+    }
+    let Some(login_queue) = login_queue else {
+        return;
+    };
+
+    let mut cancel = false;
+
+    egui::Window::new("Waiting to connect")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .default_width(300.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            if !login_queue.message_of_the_day.is_empty() {
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.label(&login_queue.message_of_the_day);
+                    });
+                ui.separator();
+            }
+
+            ui.label(format!("Position in queue: {}", login_queue.position));
+
+            if let Some(remaining_wait) = login_queue.remaining_wait() {
+                ui.label(format!(
+                    "Estimated wait: {}",
+                    format_duration(remaining_wait)
+                ));
+            } else {
+                ui.label("Estimated wait: unknown");
+            }
+
+            ui.separator();
+            if ui.button("Cancel").clicked() {
+                cancel = true;
+            }
+        });
+
+    if cancel {
+        // Leave the queue by dropping the world server connection
+        commands.remove_resource::<WorldConnection>();
+        commands.remove_resource::<LoginQueue>();
+        *login_state = LoginState::ServerSelect;
+    }
+}
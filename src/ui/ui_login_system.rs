@@ -1,12 +1,15 @@
 use bevy::{
     app::AppExit,
-    prelude::{Assets, EventWriter, Local, Res},
+    prelude::{Assets, EventWriter, Local, Res, ResMut},
 };
 use bevy_egui::{egui, EguiContexts};
 
+use rose_game_common::data::Password;
+
 use crate::{
+    credential_store::{delete_credential, load_credential, store_credential, CredentialKind},
     events::LoginEvent,
-    resources::{LoginState, ServerConfiguration, UiResources},
+    resources::{ClientSettings, LoginProfile, LoginState, ServerConfiguration, UiResources},
     ui::{
         widgets::{DataBindings, Dialog},
         UiSoundEvent,
@@ -25,6 +28,131 @@ pub struct UiStateLogin {
     password: String,
     remember_details: bool,
     initial_focus_set: bool,
+    profile_name: String,
+}
+
+fn apply_login_profile(
+    ui_state: &mut UiStateLogin,
+    server_configuration: &mut ServerConfiguration,
+    profile: &LoginProfile,
+    use_os_keychain: bool,
+) {
+    server_configuration.ip = profile.ip.clone();
+    server_configuration.port = profile.port.clone();
+    ui_state.username = profile.username.clone();
+    ui_state.password.clear();
+    ui_state.profile_name = profile.name.clone();
+
+    if use_os_keychain {
+        if let Some(password) = load_credential(
+            CredentialKind::Password,
+            &profile.ip,
+            &profile.port,
+            &profile.username,
+        ) {
+            ui_state.password = password;
+        }
+    }
+}
+
+fn draw_login_profiles(
+    ui: &mut egui::Ui,
+    ui_state: &mut UiStateLogin,
+    server_configuration: &mut ServerConfiguration,
+    client_settings: &mut ClientSettings,
+) {
+    let mut apply_index = None;
+    let mut remove_index = None;
+
+    if client_settings.login.profiles.is_empty() {
+        ui.weak("No saved profiles");
+    }
+
+    egui::ScrollArea::vertical()
+        .max_height(200.0)
+        .show(ui, |ui| {
+            for (index, profile) in client_settings.login.profiles.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let selected =
+                        client_settings.login.last_profile.as_ref() == Some(&profile.name);
+                    if ui
+                        .selectable_label(selected, &profile.name)
+                        .on_hover_text(format!(
+                            "{}@{}:{}",
+                            profile.username, profile.ip, profile.port
+                        ))
+                        .clicked()
+                    {
+                        apply_index = Some(index);
+                    }
+
+                    if ui
+                        .small_button("x")
+                        .on_hover_text("Delete profile")
+                        .clicked()
+                    {
+                        remove_index = Some(index);
+                    }
+                });
+            }
+        });
+
+    ui.separator();
+    egui::Grid::new("login_profile_edit")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.add(egui::TextEdit::singleline(&mut ui_state.profile_name).desired_width(140.0));
+            ui.end_row();
+
+            ui.label("Address");
+            ui.add(egui::TextEdit::singleline(&mut server_configuration.ip).desired_width(140.0));
+            ui.end_row();
+
+            ui.label("Port");
+            ui.add(egui::TextEdit::singleline(&mut server_configuration.port).desired_width(60.0));
+            ui.end_row();
+        });
+
+    let profile_name = ui_state.profile_name.trim();
+    if ui
+        .add_enabled(!profile_name.is_empty(), egui::Button::new("Save Profile"))
+        .on_hover_text("Saves the server address and username, remembered passwords are kept in the OS keychain")
+        .clicked()
+    {
+        let profile = LoginProfile {
+            name: profile_name.to_string(),
+            ip: server_configuration.ip.trim().to_string(),
+            port: server_configuration.port.trim().to_string(),
+            username: ui_state.username.clone(),
+        };
+
+        if let Some(existing) = client_settings
+            .login
+            .profiles
+            .iter_mut()
+            .find(|existing| existing.name == profile.name)
+        {
+            *existing = profile;
+        } else {
+            client_settings.login.profiles.push(profile);
+        }
+        client_settings.login.last_profile = Some(profile_name.to_string());
+    }
+
+    if let Some(index) = apply_index {
+        let profile = client_settings.login.profiles[index].clone();
+        let use_os_keychain = client_settings.login.use_os_keychain;
+        apply_login_profile(ui_state, server_configuration, &profile, use_os_keychain);
+        client_settings.login.last_profile = Some(profile.name);
+    }
+
+    if let Some(index) = remove_index {
+        let profile = client_settings.login.profiles.remove(index);
+        if client_settings.login.last_profile.as_ref() == Some(&profile.name) {
+            client_settings.login.last_profile = None;
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -34,7 +162,8 @@ pub fn ui_login_system(
     mut egui_context: EguiContexts,
     dialog_assets: Res<Assets<Dialog>>,
     login_state: Res<LoginState>,
-    server_configuration: Res<ServerConfiguration>,
+    mut server_configuration: ResMut<ServerConfiguration>,
+    mut client_settings: ResMut<ClientSettings>,
     ui_resources: Res<UiResources>,
     mut exit_events: EventWriter<AppExit>,
     mut login_events: EventWriter<LoginEvent>,
@@ -63,12 +192,52 @@ pub fn ui_login_system(
     let position = egui::pos2(screen_size.x - dialog.width - 100.0, 100.0);
 
     if !ui_state.initial_focus_set {
-        if let Some(username) = server_configuration.preset_username.as_ref() {
+        let use_os_keychain = client_settings.login.use_os_keychain;
+        let preset_username = server_configuration
+            .preset_username
+            .clone()
+            .filter(|username| !username.is_empty());
+        let last_profile = client_settings
+            .login
+            .last_profile
+            .as_ref()
+            .and_then(|name| {
+                client_settings
+                    .login
+                    .profiles
+                    .iter()
+                    .find(|profile| &profile.name == name)
+            })
+            .cloned();
+
+        // An explicitly configured login takes priority over the last used profile
+        if let Some(username) = preset_username {
+            ui_state.username = username;
+            ui_state.password = server_configuration
+                .preset_password
+                .clone()
+                .unwrap_or_default();
+        } else if let Some(profile) = last_profile {
+            apply_login_profile(
+                ui_state,
+                &mut server_configuration,
+                &profile,
+                use_os_keychain,
+            );
+        } else if let Some(username) = client_settings.login.remembered_username.as_ref() {
             ui_state.username = username.clone();
         }
 
-        if let Some(password) = server_configuration.preset_password.as_ref() {
-            ui_state.password = password.clone();
+        ui_state.remember_details = client_settings.login.remembered_username.is_some();
+        if ui_state.password.is_empty() && use_os_keychain {
+            if let Some(password) = load_credential(
+                CredentialKind::Password,
+                &server_configuration.ip,
+                &server_configuration.port,
+                &ui_state.username,
+            ) {
+                ui_state.password = password;
+            }
         }
     }
 
@@ -106,6 +275,19 @@ pub fn ui_login_system(
             )
         });
 
+    egui::Window::new("Server Profiles")
+        .resizable(false)
+        .default_width(220.0)
+        .default_pos(egui::pos2(position.x - 260.0, position.y))
+        .show(egui_context.ctx_mut(), |ui| {
+            draw_login_profiles(
+                ui,
+                ui_state,
+                &mut server_configuration,
+                &mut client_settings,
+            );
+        });
+
     if !ui_state.initial_focus_set {
         if let Some(r) = response_username.as_ref() {
             r.request_focus();
@@ -123,9 +305,41 @@ pub fn ui_login_system(
                 r.request_focus();
             }
         } else {
+            let login_settings = &mut client_settings.login;
+            if ui_state.remember_details {
+                if login_settings.remembered_username.as_ref() != Some(&ui_state.username) {
+                    login_settings.remembered_username = Some(ui_state.username.clone());
+                }
+
+                if login_settings.use_os_keychain {
+                    store_credential(
+                        CredentialKind::Password,
+                        &server_configuration.ip,
+                        &server_configuration.port,
+                        &ui_state.username,
+                        &ui_state.password,
+                    );
+                }
+            } else {
+                if login_settings.remembered_username.is_some() {
+                    login_settings.remembered_username = None;
+                }
+
+                if login_settings.use_os_keychain {
+                    for kind in [CredentialKind::Password, CredentialKind::PasswordMd5] {
+                        delete_credential(
+                            kind,
+                            &server_configuration.ip,
+                            &server_configuration.port,
+                            &ui_state.username,
+                        );
+                    }
+                }
+            }
+
             login_events.send(LoginEvent::Login {
                 username: ui_state.username.clone(),
-                password: ui_state.password.clone(),
+                password: Password::Plaintext(ui_state.password.clone()),
             });
         }
     }
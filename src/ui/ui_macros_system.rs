@@ -0,0 +1,123 @@
+use bevy::prelude::{EventWriter, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    events::ChatCommandEvent,
+    resources::{ChatMacro, ClientSettings},
+    ui::{find_chat_command, UiStateWindows, UiWindowId, CHAT_MACRO_PLACEHOLDERS},
+};
+
+fn get_chat_macro_error(chat_macros: &[ChatMacro], index: usize) -> Option<&'static str> {
+    let name = chat_macros[index].name.as_str();
+    if name.is_empty() {
+        Some("The macro needs a name")
+    } else if name.contains(char::is_whitespace) || name.starts_with('/') {
+        Some("Macro names cannot contain spaces or start with /")
+    } else if find_chat_command(name).is_some() {
+        Some("This name is used by a client command")
+    } else if chat_macros[..index]
+        .iter()
+        .any(|chat_macro| chat_macro.name.eq_ignore_ascii_case(name))
+    {
+        Some("Another macro already has this name")
+    } else {
+        None
+    }
+}
+
+pub fn ui_macros_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut client_settings: ResMut<ClientSettings>,
+    mut chat_command_events: EventWriter<ChatCommandEvent>,
+) {
+    if !ui_state_windows.is_open(UiWindowId::MACROS) {
+        return;
+    }
+
+    // Edit a copy so the settings are only marked as changed when a macro is edited
+    let mut chat_macros = client_settings.social.chat_macros.clone();
+    let mut remove_index = None;
+
+    egui::Window::new("Macros")
+        .open(ui_state_windows.open_mut(UiWindowId::MACROS))
+        .resizable(false)
+        .default_width(420.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("Type /name in chat to send a macro.");
+            ui.horizontal_wrapped(|ui| {
+                ui.weak("Placeholders:");
+                for (placeholder, description) in CHAT_MACRO_PLACEHOLDERS {
+                    ui.monospace(placeholder).on_hover_text(description);
+                }
+            });
+            ui.separator();
+
+            if chat_macros.is_empty() {
+                ui.weak("You have not created any macros.");
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("chat_macros")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for index in 0..chat_macros.len() {
+                                let error = get_chat_macro_error(&chat_macros, index);
+
+                                ui.horizontal(|ui| {
+                                    ui.label("/");
+                                    let response = ui.add(
+                                        egui::TextEdit::singleline(&mut chat_macros[index].name)
+                                            .desired_width(70.0),
+                                    );
+                                    if let Some(error) = error {
+                                        response.on_hover_text(error);
+                                        ui.colored_label(egui::Color32::RED, "!");
+                                    }
+                                });
+
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut chat_macros[index].text)
+                                        .desired_width(220.0),
+                                );
+
+                                if ui
+                                    .add_enabled(
+                                        !chat_macros[index].text.trim().is_empty(),
+                                        egui::Button::new("Send"),
+                                    )
+                                    .clicked()
+                                {
+                                    chat_command_events.send(ChatCommandEvent::Macro(
+                                        chat_macros[index].text.clone(),
+                                    ));
+                                }
+
+                                if ui.button("Delete").clicked() {
+                                    remove_index = Some(index);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.separator();
+            if ui.button("Add Macro").clicked() {
+                chat_macros.push(ChatMacro {
+                    name: format!("macro{}", chat_macros.len() + 1),
+                    text: String::new(),
+                });
+            }
+        });
+
+    if let Some(index) = remove_index {
+        chat_macros.remove(index);
+    }
+
+    if chat_macros != client_settings.social.chat_macros {
+        client_settings.social.chat_macros = chat_macros;
+    }
+}
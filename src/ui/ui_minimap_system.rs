@@ -13,9 +13,10 @@ use rose_data::ZoneId;
 use rose_game_common::components::{CharacterInfo, Team};
 
 use crate::{
-    components::{PartyInfo, PlayerCharacter, Position},
+    components::{PartyInfo, PartyMember, PlayerCharacter, Position},
     resources::{CurrentZone, GameData, UiResources, UiSpriteSheetType},
     ui::{
+        get_party_member_color,
         widgets::{DataBindings, Dialog, Widget},
         UiSoundEvent,
     },
@@ -88,7 +89,10 @@ pub fn ui_minimap_system(
     mut ui_state: Local<UiStateMinimap>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     query_player: Query<(&Position, &Team, Option<&PartyInfo>), With<PlayerCharacter>>,
-    query_characters: Query<(&CharacterInfo, &Position, &Team), Without<PlayerCharacter>>,
+    query_characters: Query<
+        (&CharacterInfo, &Position, &Team, Option<&PartyMember>),
+        Without<PlayerCharacter>,
+    >,
     asset_server: Res<AssetServer>,
     query_camera: Query<&Transform, With<Camera3d>>,
     images: Res<Assets<Image>>,
@@ -347,7 +351,8 @@ pub fn ui_minimap_system(
                     ui_resources.get_sprite(UiSpriteSheetType::Ui as i32, "ID_OTHER_AVATAR");
 
                 // Draw other characters
-                for (character_info, character_position, character_team) in query_characters.iter()
+                for (character_info, character_position, character_team, party_member) in
+                    query_characters.iter()
                 {
                     let icon_image = if player_team
                         .map_or(false, |player_team| character_team.id != player_team.id)
@@ -377,6 +382,16 @@ pub fn ui_minimap_system(
                     );
 
                     if minimap_rect.contains_rect(icon_rect) {
+                        if let Some(party_member) = party_member {
+                            // Blip behind the icon in the member's party colour
+                            ui.painter().circle(
+                                icon_rect.center(),
+                                icon_size.max_element() / 2.0 + 2.0,
+                                get_party_member_color(party_member.index),
+                                egui::Stroke::new(1.0, egui::Color32::BLACK),
+                            );
+                        }
+
                         icon_image.draw(ui, icon_rect.min);
                     }
                 }
@@ -0,0 +1,162 @@
+use bevy::{
+    input::Input,
+    prelude::{EventWriter, KeyCode, Local, Query, Res, Time, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::{Item, ItemType, VehiclePartIndex};
+use rose_game_common::{
+    components::{
+        Equipment, HealthPoints, Inventory, InventoryPageType, ItemSlot, MoveMode,
+        INVENTORY_PAGE_SIZE,
+    },
+    messages::client::ClientMessage,
+};
+
+use crate::{
+    components::{PlayerCharacter, Vehicle},
+    events::{ChatboxEvent, PlayerCommandEvent},
+    resources::{ClientSettings, GameConnection},
+};
+
+const MOUNT_KEY: KeyCode = KeyCode::R;
+
+struct MountChannel {
+    elapsed: f32,
+    start_hp: u32,
+}
+
+#[derive(Default)]
+pub struct UiStateMount {
+    channel: Option<MountChannel>,
+}
+
+fn find_mount_item_slot(inventory: &Inventory, item_number: usize) -> Option<ItemSlot> {
+    (0..INVENTORY_PAGE_SIZE)
+        .map(|index| ItemSlot::Inventory(InventoryPageType::Vehicles, index))
+        .find(|item_slot| {
+            matches!(inventory.get_item(*item_slot), Some(Item::Equipment(equipment_item))
+                if equipment_item.item.item_type == ItemType::Vehicle
+                    && equipment_item.item.item_number == item_number)
+        })
+}
+
+pub fn ui_mount_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateMount>,
+    query_player: Query<
+        (
+            &MoveMode,
+            &HealthPoints,
+            &Equipment,
+            &Inventory,
+            Option<&Vehicle>,
+        ),
+        With<PlayerCharacter>,
+    >,
+    mut player_command_events: EventWriter<PlayerCommandEvent>,
+    mut chatbox_events: EventWriter<ChatboxEvent>,
+    game_connection: Option<Res<GameConnection>>,
+    client_settings: Res<ClientSettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Ok((move_mode, health_points, equipment, inventory, vehicle)) = query_player.get_single()
+    else {
+        ui_state.channel = None;
+        return;
+    };
+    let is_riding = matches!(move_mode, MoveMode::Drive) || vehicle.is_some();
+    let ctx = egui_context.ctx_mut();
+
+    if !ctx.wants_keyboard_input() && keyboard_input.just_pressed(MOUNT_KEY) {
+        if ui_state.channel.take().is_some() {
+            chatbox_events.send(ChatboxEvent::System("Mounting cancelled.".to_string()));
+        } else if is_riding {
+            // Dismounting is instant
+            if let Some(game_connection) = game_connection.as_ref() {
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::DriveToggle)
+                    .ok();
+            }
+        } else {
+            let mount_vehicle_item = client_settings.controls.mount_vehicle_item;
+            let equipped_body = equipment.equipped_vehicle[VehiclePartIndex::Body]
+                .as_ref()
+                .map(|body| body.item.item_number);
+
+            // Equip the configured frame first, it should arrive before the channel completes
+            let mount_item_slot = mount_vehicle_item
+                .filter(|item_number| equipped_body != Some(*item_number))
+                .and_then(|item_number| find_mount_item_slot(inventory, item_number));
+            if let Some(item_slot) = mount_item_slot {
+                player_command_events.send(PlayerCommandEvent::EquipVehicle(item_slot));
+            }
+
+            if mount_item_slot.is_some() || equipped_body.is_some() {
+                ui_state.channel = Some(MountChannel {
+                    elapsed: 0.0,
+                    start_hp: health_points.hp,
+                });
+            } else {
+                chatbox_events.send(ChatboxEvent::System(
+                    "You do not have a vehicle to mount.".to_string(),
+                ));
+            }
+        }
+    }
+
+    let Some(channel) = ui_state.channel.as_mut() else {
+        return;
+    };
+
+    if health_points.hp < channel.start_hp {
+        ui_state.channel = None;
+        chatbox_events.send(ChatboxEvent::System(
+            "Mounting was interrupted by damage.".to_string(),
+        ));
+        return;
+    }
+
+    let cast_time = client_settings.controls.mount_cast_time.max(0.1);
+    channel.elapsed += time.delta_seconds();
+    if channel.elapsed >= cast_time {
+        ui_state.channel = None;
+
+        if is_riding {
+            return;
+        }
+
+        if equipment.equipped_vehicle[VehiclePartIndex::Body].is_none() {
+            chatbox_events.send(ChatboxEvent::System(
+                "Could not equip the mount vehicle.".to_string(),
+            ));
+        } else if let Some(game_connection) = game_connection.as_ref() {
+            game_connection
+                .client_message_tx
+                .send(ClientMessage::DriveToggle)
+                .ok();
+        }
+        return;
+    }
+
+    let progress = channel.elapsed / cast_time;
+    egui::Area::new("mount_cast_bar")
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -200.0])
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::none()
+                .fill(egui::Color32::from_black_alpha(160))
+                .rounding(egui::Rounding::same(4.0))
+                .inner_margin(egui::Margin::same(6.0))
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::ProgressBar::new(progress)
+                            .desired_width(200.0)
+                            .text("Mounting..."),
+                    );
+                });
+        });
+}
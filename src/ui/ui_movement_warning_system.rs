@@ -0,0 +1,52 @@
+use bevy::prelude::ResMut;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::PositionCorrectionStats;
+
+pub fn ui_movement_warning_system(
+    mut egui_context: EguiContexts,
+    mut position_correction_stats: ResMut<PositionCorrectionStats>,
+) {
+    if !position_correction_stats.movement_mismatch {
+        return;
+    }
+
+    let mut dismiss = false;
+    egui::Window::new("Movement Warning")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 200.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("Your position is often being corrected by the server.");
+            ui.label(
+                "The server may be configured with different movement speeds or game data than your client.",
+            );
+            ui.separator();
+
+            egui::Grid::new("movement_warning_stats")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Recent corrections:");
+                    ui.label(format!(
+                        "{}",
+                        position_correction_stats.recent_player_corrections.len()
+                    ));
+                    ui.end_row();
+
+                    if let Some((speed, max_speed)) = position_correction_stats.last_clamped_speed {
+                        ui.label("Move speed limited:");
+                        ui.label(format!("{:.0} to {:.0}", speed, max_speed));
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            if ui.button("Dismiss").clicked() {
+                dismiss = true;
+            }
+        });
+
+    if dismiss {
+        position_correction_stats.dismiss_movement_mismatch();
+    }
+}
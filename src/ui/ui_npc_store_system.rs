@@ -1,13 +1,11 @@
 use bevy::{
     ecs::query::WorldQuery,
     math::Vec3Swizzles,
-    prelude::{
-        Assets, Entity, EventReader, EventWriter, Events, Local, Query, Res, ResMut, With, World,
-    },
+    prelude::{Assets, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With},
 };
 use bevy_egui::{egui, EguiContexts};
 
-use rose_data::{Item, NpcData, NpcStoreTabData, NpcStoreTabId};
+use rose_data::{Item, ItemReference, NpcData, NpcStoreTabData, NpcStoreTabId};
 use rose_game_common::{
     components::{AbilityValues, Inventory, ItemSlot, Npc},
     messages::{
@@ -18,16 +16,17 @@ use rose_game_common::{
 
 use crate::{
     components::{PlayerCharacter, Position},
-    events::{MessageBoxEvent, NpcStoreEvent, NumberInputDialogEvent},
+    events::{MessageBoxEvent, NpcStoreEvent},
     resources::{
         ClientEntityList, GameConnection, GameData, UiResources, UiSpriteSheetType, WorldRates,
     },
     ui::{
+        format::format_signed_number,
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         ui_drag_and_drop_system::UiStateDragAndDrop,
         widgets::{DataBindings, Dialog, DrawText},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent,
     },
 };
 
@@ -37,6 +36,8 @@ const NUM_BUY_ITEMS_PER_ROW: usize = 5;
 const NUM_SELL_ITEMS: usize = 10;
 const NUM_SELL_ITEMS_PER_ROW: usize = 5;
 
+const MAX_BUY_QUANTITY: usize = 999;
+
 const IID_STORE_BTN_CLOSE: i32 = 20;
 const IID_STORE_RADIOBOX: i32 = 30;
 const IID_STORE_BTN_TAB1: i32 = 31;
@@ -53,6 +54,14 @@ struct PendingBuyItem {
     quantity: usize,
 }
 
+// Shown when buying a stackable item to choose how many to buy
+struct PendingBuyQuantity {
+    store_tab_index: usize,
+    store_tab_slot: usize,
+    buy_slot_index: Option<usize>,
+    quantity: usize,
+}
+
 struct PendingSellItem {
     item_slot: ItemSlot,
     quantity: usize,
@@ -64,6 +73,7 @@ pub struct UiNpcStoreState {
     store_tabs: [Option<(NpcStoreTabId, String)>; 4],
     buy_list: [Option<PendingBuyItem>; NUM_BUY_ITEMS],
     sell_list: [Option<PendingSellItem>; NUM_SELL_ITEMS],
+    buy_quantity: Option<PendingBuyQuantity>,
 }
 
 impl Default for UiNpcStoreState {
@@ -74,10 +84,29 @@ impl Default for UiNpcStoreState {
             store_tabs: Default::default(),
             buy_list: Default::default(),
             sell_list: Default::default(),
+            buy_quantity: None,
         }
     }
 }
 
+fn get_store_item_price(
+    item_reference: ItemReference,
+    player: Option<&NpcStorePlayerWorldQueryItem>,
+    game_data: &GameData,
+    world_rates: Option<&Res<WorldRates>>,
+) -> i64 {
+    game_data
+        .ability_value_calculator
+        .calculate_npc_store_item_buy_price(
+            &game_data.items,
+            item_reference,
+            player.map_or(0, |player| player.ability_values.get_npc_store_buy_rate()),
+            world_rates.map_or(100, |x| x.item_price_rate),
+            world_rates.map_or(100, |x| x.town_price_rate),
+        )
+        .unwrap_or(0) as i64
+}
+
 fn ui_add_store_item_slot(
     ui: &mut egui::Ui,
     ui_state_dnd: &mut UiStateDragAndDrop,
@@ -91,7 +120,7 @@ fn ui_add_store_item_slot(
     game_data: &GameData,
     ui_resources: &UiResources,
     world_rates: Option<&Res<WorldRates>>,
-    number_input_dialog_events: &mut EventWriter<NumberInputDialogEvent>,
+    buy_quantity: &mut Option<PendingBuyQuantity>,
 ) {
     let item_reference =
         store_tab.and_then(|store_tab| store_tab.items.get(&(store_tab_slot as u16)));
@@ -109,20 +138,9 @@ fn ui_add_store_item_slot(
         }
     });
 
-    let item_price = if let Some(item_reference) = item_reference {
-        game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_buy_price(
-                &game_data.items,
-                *item_reference,
-                player.map_or(0, |x| x.ability_values.get_npc_store_buy_rate()),
-                world_rates.map_or(100, |x| x.item_price_rate),
-                world_rates.map_or(100, |x| x.town_price_rate),
-            )
-            .unwrap_or(0) as i64
-    } else {
-        0
-    };
+    let item_price = item_reference.map_or(0, |item_reference| {
+        get_store_item_price(*item_reference, player, game_data, world_rates)
+    });
 
     let mut dropped_item = None;
     let response = ui
@@ -151,21 +169,11 @@ fn ui_add_store_item_slot(
     if let Some(item) = item.as_ref() {
         if response.double_clicked() {
             if item.is_stackable_item() {
-                number_input_dialog_events.send(NumberInputDialogEvent::Show {
-                    max_value: Some(999),
-                    modal: false,
-                    ok: Some(Box::new(move |commands, quantity| {
-                        commands.add(move |world: &mut World| {
-                            let mut npc_store_events =
-                                world.resource_mut::<Events<NpcStoreEvent>>();
-                            npc_store_events.send(NpcStoreEvent::AddToBuyList {
-                                store_tab_index,
-                                store_tab_slot,
-                                quantity,
-                            })
-                        });
-                    })),
-                    cancel: None,
+                *buy_quantity = Some(PendingBuyQuantity {
+                    store_tab_index,
+                    store_tab_slot,
+                    buy_slot_index: None,
+                    quantity: 1,
                 });
             } else {
                 for slot in buy_list.iter_mut() {
@@ -181,10 +189,13 @@ fn ui_add_store_item_slot(
             }
         }
 
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
 
-            ui.colored_label(egui::Color32::YELLOW, format!("Buy Price: {}", item_price));
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Buy Price: {}", format_signed_number(item_price)),
+            );
         });
     }
 }
@@ -212,6 +223,7 @@ fn ui_add_buy_item_slot(
     game_data: &GameData,
     ui_resources: &UiResources,
     world_rates: Option<&Res<WorldRates>>,
+    buy_quantity: &mut Option<PendingBuyQuantity>,
 ) -> i64 {
     let pending_buy_item = &mut buy_list[buy_slot_index];
     let item_reference = pending_buy_item.as_ref().and_then(|pending_buy_item| {
@@ -240,21 +252,10 @@ fn ui_add_buy_item_slot(
         }
     });
 
-    let item_price = if let Some(item_reference) = item_reference {
-        game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_buy_price(
-                &game_data.items,
-                *item_reference,
-                player.map_or(0, |player| player.ability_values.get_npc_store_buy_rate()),
-                world_rates.map_or(100, |x| x.item_price_rate),
-                world_rates.map_or(100, |x| x.town_price_rate),
-            )
-            .unwrap_or(0) as i64
+    let item_price = item_reference.map_or(0, |item_reference| {
+        get_store_item_price(*item_reference, player, game_data, world_rates)
             * quantity.unwrap_or(1) as i64
-    } else {
-        0
-    };
+    });
 
     let mut dropped_item = None;
     let response = ui
@@ -285,19 +286,41 @@ fn ui_add_buy_item_slot(
     }
 
     if let Some(item) = item {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
 
-            ui.colored_label(egui::Color32::YELLOW, format!("Buy Price: {}", item_price));
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Buy Price: {}", format_signed_number(item_price)),
+            );
         });
     }
 
     if let Some(DragAndDropId::NpcStore(store_tab_index, store_tab_slot)) = dropped_item {
-        *pending_buy_item = Some(PendingBuyItem {
-            store_tab_index,
-            store_tab_slot,
-            quantity: 1,
-        });
+        let is_stackable = npc_data
+            .store_tabs
+            .get(store_tab_index)
+            .and_then(|x| x.as_ref())
+            .and_then(|store_tab| game_data.npcs.get_store_tab(*store_tab))
+            .and_then(|store_tab| store_tab.items.get(&(store_tab_slot as u16)))
+            .map_or(false, |item_reference| {
+                item_reference.item_type.is_stackable_item()
+            });
+
+        if is_stackable {
+            *buy_quantity = Some(PendingBuyQuantity {
+                store_tab_index,
+                store_tab_slot,
+                buy_slot_index: Some(buy_slot_index),
+                quantity: 1,
+            });
+        } else {
+            *pending_buy_item = Some(PendingBuyItem {
+                store_tab_index,
+                store_tab_slot,
+                quantity: 1,
+            });
+        }
     }
 
     item_price
@@ -367,7 +390,7 @@ fn ui_add_sell_item_slot(
     }
 
     if let Some(item) = item {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
 
             ui.colored_label(egui::Color32::YELLOW, format!("Sell Value: {}", item_price));
@@ -413,7 +436,6 @@ pub fn ui_npc_store_system(
     dialog_assets: Res<Assets<Dialog>>,
     ui_resources: Res<UiResources>,
     world_rates: Option<Res<WorldRates>>,
-    mut number_input_dialog_events: EventWriter<NumberInputDialogEvent>,
     mut message_box_events: EventWriter<MessageBoxEvent>,
 ) {
     let ui_state = &mut *ui_state;
@@ -581,7 +603,7 @@ pub fn ui_npc_store_system(
                                     &game_data,
                                     &ui_resources,
                                     world_rates.as_ref(),
-                                    &mut number_input_dialog_events,
+                                    &mut ui_state.buy_quantity,
                                 );
                             }
                         }
@@ -591,6 +613,7 @@ pub fn ui_npc_store_system(
         });
 
     let mut transaction_cost = 0;
+    let mut buy_slot_prices = [0; NUM_BUY_ITEMS];
 
     egui::Window::new("NPC Transaction")
         .frame(egui::Frame::none())
@@ -617,7 +640,7 @@ pub fn ui_npc_store_system(
                         let column = (i % NUM_BUY_ITEMS_PER_ROW) as f32;
                         let row = (i / NUM_BUY_ITEMS_PER_ROW) as f32;
 
-                        buy_item_price += ui_add_buy_item_slot(
+                        buy_slot_prices[i] = ui_add_buy_item_slot(
                             ui,
                             ui_state_dnd.as_mut(),
                             egui::pos2(10.0 + column * 41.0, 52.0 + row * 41.0),
@@ -629,9 +652,14 @@ pub fn ui_npc_store_system(
                             &game_data,
                             &ui_resources,
                             world_rates.as_ref(),
+                            &mut ui_state.buy_quantity,
                         );
+                        buy_item_price += buy_slot_prices[i];
                     }
-                    ui.add_label_at(egui::pos2(39.0, 139.0), format!("{}", buy_item_price));
+                    ui.add_label_at(
+                        egui::pos2(39.0, 139.0),
+                        format_signed_number(buy_item_price),
+                    );
                     transaction_cost += buy_item_price;
 
                     let mut sell_item_value = 0;
@@ -652,12 +680,128 @@ pub fn ui_npc_store_system(
                             world_rates.as_ref(),
                         );
                     }
-                    ui.add_label_at(egui::pos2(39.0, 272.0), format!("{}", sell_item_value));
+                    ui.add_label_at(
+                        egui::pos2(39.0, 272.0),
+                        format_signed_number(sell_item_value),
+                    );
                     transaction_cost -= sell_item_value;
                 },
             );
         });
 
+    if let Some(buy_quantity) = ui_state.buy_quantity.as_mut() {
+        let item_data = npc_data
+            .store_tabs
+            .get(buy_quantity.store_tab_index)
+            .and_then(|x| x.as_ref())
+            .and_then(|store_tab| game_data.npcs.get_store_tab(*store_tab))
+            .and_then(|store_tab| store_tab.items.get(&(buy_quantity.store_tab_slot as u16)))
+            .and_then(|item_reference| {
+                Some((
+                    *item_reference,
+                    game_data.items.get_base_item(*item_reference)?,
+                ))
+            });
+        let mut confirm = false;
+        let mut cancel = item_data.is_none();
+
+        if let Some((item_reference, item_data)) = item_data {
+            let unit_price = get_store_item_price(
+                item_reference,
+                player.as_ref(),
+                &game_data,
+                world_rates.as_ref(),
+            );
+
+            // Money left after the rest of the transaction, not counting the buy slot we replace
+            let replaced_price = buy_quantity
+                .buy_slot_index
+                .map_or(0, |buy_slot_index| buy_slot_prices[buy_slot_index]);
+            let available_money = player.as_ref().map_or(i64::MAX, |player| {
+                player.inventory.money.0 - (transaction_cost - replaced_price)
+            });
+            let max_affordable = if unit_price > 0 {
+                (available_money / unit_price).clamp(0, MAX_BUY_QUANTITY as i64) as usize
+            } else {
+                MAX_BUY_QUANTITY
+            };
+
+            egui::Window::new("Purchase Quantity")
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .collapsible(false)
+                .resizable(false)
+                .show(egui_context.ctx_mut(), |ui| {
+                    ui.label(item_data.name);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("-").clicked() {
+                            buy_quantity.quantity = buy_quantity.quantity.saturating_sub(1).max(1);
+                        }
+
+                        ui.add(
+                            egui::DragValue::new(&mut buy_quantity.quantity)
+                                .clamp_range(1..=MAX_BUY_QUANTITY),
+                        );
+
+                        if ui.button("+").clicked() {
+                            buy_quantity.quantity =
+                                (buy_quantity.quantity + 1).min(MAX_BUY_QUANTITY);
+                        }
+
+                        if ui
+                            .add_enabled(max_affordable > 0, egui::Button::new("Max"))
+                            .on_hover_text("Buy as many as you can afford")
+                            .clicked()
+                        {
+                            buy_quantity.quantity = max_affordable;
+                        }
+                    });
+
+                    let total_price = unit_price * buy_quantity.quantity as i64;
+                    ui.label(format!("Price: {} each", format_signed_number(unit_price)));
+                    if total_price > available_money {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!("Total: {}", format_signed_number(total_price)),
+                        );
+                    } else {
+                        ui.label(format!("Total: {}", format_signed_number(total_price)));
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("OK").clicked()
+                            || ui.input(|input| input.key_pressed(egui::Key::Enter))
+                        {
+                            confirm = true;
+                        }
+
+                        if ui.button("Cancel").clicked() {
+                            cancel = true;
+                        }
+                    });
+                });
+        }
+
+        if confirm && !cancel {
+            let buy_slot = buy_quantity
+                .buy_slot_index
+                .and_then(|buy_slot_index| ui_state.buy_list.get_mut(buy_slot_index))
+                .or_else(|| ui_state.buy_list.iter_mut().find(|slot| slot.is_none()));
+
+            if let Some(buy_slot) = buy_slot {
+                *buy_slot = Some(PendingBuyItem {
+                    store_tab_index: buy_quantity.store_tab_index,
+                    store_tab_slot: buy_quantity.store_tab_slot,
+                    quantity: buy_quantity.quantity,
+                });
+            }
+        }
+
+        if confirm || cancel {
+            ui_state.buy_quantity = None;
+        }
+    }
+
     if response_ok.map_or(false, |x| x.clicked()) {
         let can_afford_transaction =
             player.map_or(true, |player| transaction_cost <= player.inventory.money.0);
@@ -0,0 +1,95 @@
+use bevy::prelude::{Camera, Camera3d, GlobalTransform, Query, Vec2, Vec3, With};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntityName, ModelHeight, PartyMember},
+    ui::{get_party_member_color, paint_party_member_diamond},
+};
+
+const MARKER_HEIGHT_OFFSET: f32 = 0.6;
+const MARKER_RADIUS: f32 = 6.0;
+const ARROW_SCREEN_MARGIN: f32 = 32.0;
+const ARROW_LENGTH: f32 = 12.0;
+const ARROW_HALF_WIDTH: f32 = 8.0;
+
+pub fn ui_party_marker_system(
+    mut egui_context: EguiContexts,
+    query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query_party_members: Query<(
+        &PartyMember,
+        &ClientEntityName,
+        &GlobalTransform,
+        Option<&ModelHeight>,
+    )>,
+) {
+    let ctx = egui_context.ctx_mut();
+    let screen_rect = ctx.input(|input| input.screen_rect());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("party_markers"),
+    ));
+    let Ok((camera, camera_transform)) = query_camera.get_single() else {
+        return;
+    };
+    let world_to_view = camera_transform.compute_matrix().inverse();
+
+    for (party_member, name, global_transform, model_height) in query_party_members.iter() {
+        let color = get_party_member_color(party_member.index);
+        let marker_position = global_transform.translation()
+            + Vec3::new(
+                0.0,
+                model_height.map_or(2.0, |model_height| model_height.height) + MARKER_HEIGHT_OFFSET,
+                0.0,
+            );
+
+        if let Some(ndc_space_coords) = camera
+            .world_to_ndc(camera_transform, marker_position)
+            .filter(|ndc| ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && (0.0..=1.0).contains(&ndc.z))
+        {
+            let screen_pos = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0
+                * Vec2::new(screen_rect.width(), screen_rect.height());
+            paint_party_member_diamond(
+                &painter,
+                egui::pos2(screen_pos.x, screen_rect.height() - screen_pos.y),
+                MARKER_RADIUS,
+                color,
+            );
+            continue;
+        }
+
+        // Off screen, point an arrow from the screen edge towards the member. The
+        // view space direction is used as it is still valid when behind the camera.
+        let view_position = world_to_view.transform_point3(marker_position);
+        let direction = Vec2::new(view_position.x, -view_position.y).normalize_or_zero();
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        let center = screen_rect.center();
+        let half_extents = Vec2::new(
+            screen_rect.width() / 2.0 - ARROW_SCREEN_MARGIN,
+            screen_rect.height() / 2.0 - ARROW_SCREEN_MARGIN,
+        );
+        let scale = (half_extents.x / direction.x.abs()).min(half_extents.y / direction.y.abs());
+        let tip = center + egui::vec2(direction.x, direction.y) * scale;
+        let forward = egui::vec2(direction.x, direction.y);
+        let side = egui::vec2(-direction.y, direction.x);
+
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                tip,
+                tip - forward * ARROW_LENGTH + side * ARROW_HALF_WIDTH,
+                tip - forward * ARROW_LENGTH - side * ARROW_HALF_WIDTH,
+            ],
+            color,
+            egui::Stroke::new(1.0, egui::Color32::BLACK),
+        ));
+        painter.text(
+            tip - forward * (ARROW_LENGTH + 12.0),
+            egui::Align2::CENTER_CENTER,
+            name.as_str(),
+            egui::FontId::proportional(12.0),
+            color,
+        );
+    }
+}
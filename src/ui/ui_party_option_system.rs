@@ -8,7 +8,7 @@ use crate::{
     resources::{GameConnection, UiResources},
     ui::{
         widgets::{DataBindings, Dialog, DrawText},
-        UiSoundEvent, UiStateWindows,
+        UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -69,7 +69,7 @@ pub fn ui_party_option_system(
         return;
     };
 
-    if !ui_state_windows.party_options_open {
+    if !ui_state_windows.is_open(UiWindowId::PARTY_OPTIONS) {
         ui_state.item_sharing_rule = match party_info.item_sharing {
             PartyItemSharing::EqualLootDistribution => IID_RADIOBUTTON_ITEM_PICK,
             PartyItemSharing::AcquisitionOrder => IID_RADIOBUTTON_ITEM_SEQUENCE,
@@ -165,7 +165,7 @@ pub fn ui_party_option_system(
         });
 
     if response_close_button.map_or(false, |x| x.clicked()) {
-        ui_state_windows.party_options_open = false;
+        ui_state_windows.set_open(UiWindowId::PARTY_OPTIONS, false);
     }
 
     if response_confirm_button.map_or(false, |x| x.clicked()) {
@@ -187,6 +187,6 @@ pub fn ui_party_option_system(
                 .ok();
         }
 
-        ui_state_windows.party_options_open = false;
+        ui_state_windows.set_open(UiWindowId::PARTY_OPTIONS, false);
     }
 }
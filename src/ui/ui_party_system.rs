@@ -1,6 +1,8 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Assets, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With},
+    prelude::{
+        Assets, Entity, EventReader, EventWriter, Input, KeyCode, Local, Query, Res, ResMut, With,
+    },
 };
 use bevy_egui::{egui, EguiContexts};
 
@@ -12,9 +14,12 @@ use rose_game_common::{
 };
 
 use crate::{
-    components::{ClientEntity, ClientEntityName, PartyInfo, PartyOwner, PlayerCharacter},
+    components::{ClientEntity, ClientEntityName, Command, PartyInfo, PartyOwner, PlayerCharacter},
     events::PartyEvent,
-    resources::{ClientEntityList, GameConnection, SelectedTarget, UiResources},
+    resources::{
+        ClientEntityList, GameConnection, IgnoreList, PartyMemberTargets, SelectedTarget,
+        UiResources,
+    },
     ui::{
         widgets::{Dialog, Gauge},
         UiSoundEvent,
@@ -23,7 +28,7 @@ use crate::{
 
 use super::{
     widgets::{DrawText, DrawWidget, LoadWidget},
-    DataBindings, UiStateWindows,
+    DataBindings, UiStateWindows, UiWindowId,
 };
 
 const IID_BTN_ENTRUST: i32 = 11;
@@ -33,6 +38,63 @@ const IID_BTN_OPTION: i32 = 14;
 const IID_PARTY_XP_GAUGE: i32 = 1001;
 const IID_PARTY_MEMBER_HP_GAUGE: i32 = 1002;
 
+// Each party member keeps the same colour for their party frame entry, world
+// marker and minimap blip
+const PARTY_MEMBER_COLORS: [egui::Color32; 8] = [
+    egui::Color32::from_rgb(255, 90, 90),
+    egui::Color32::from_rgb(90, 170, 255),
+    egui::Color32::from_rgb(120, 230, 90),
+    egui::Color32::from_rgb(255, 210, 60),
+    egui::Color32::from_rgb(210, 110, 255),
+    egui::Color32::from_rgb(60, 225, 215),
+    egui::Color32::from_rgb(255, 150, 50),
+    egui::Color32::from_rgb(255, 130, 200),
+];
+
+pub fn get_party_member_color(index: usize) -> egui::Color32 {
+    PARTY_MEMBER_COLORS[index % PARTY_MEMBER_COLORS.len()]
+}
+
+pub fn paint_party_member_diamond(
+    painter: &egui::Painter,
+    center: egui::Pos2,
+    radius: f32,
+    color: egui::Color32,
+) {
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            center + egui::vec2(0.0, -radius),
+            center + egui::vec2(radius, 0.0),
+            center + egui::vec2(0.0, radius),
+            center + egui::vec2(-radius, 0.0),
+        ],
+        color,
+        egui::Stroke::new(1.0, egui::Color32::BLACK),
+    ));
+}
+
+// Prefer the target shared by the server, otherwise fall back to whatever a
+// visible party member is currently attacking or casting at
+fn get_party_member_target(
+    member_entity_id: ClientEntityId,
+    party_member_targets: &PartyMemberTargets,
+    client_entity_list: &ClientEntityList,
+    query_party_member: &Query<PartyMemberQuery>,
+) -> Option<Entity> {
+    party_member_targets
+        .get(member_entity_id)
+        .and_then(|target_entity_id| client_entity_list.get(target_entity_id))
+        .or_else(|| {
+            let party_member = query_party_member
+                .get(client_entity_list.get(member_entity_id)?)
+                .ok()?;
+            match party_member.command? {
+                Command::PickupItem(_) => None,
+                command => command.get_target(),
+            }
+        })
+}
+
 #[derive(WorldQuery)]
 pub struct PlayerQuery<'w> {
     _player_character: With<PlayerCharacter>,
@@ -50,6 +112,7 @@ pub struct PartyMemberQuery<'w> {
     ability_values: &'w AbilityValues,
     health_points: &'w HealthPoints,
     level: &'w Level,
+    command: Option<&'w Command>,
 }
 
 pub struct PendingPartyInvite {
@@ -108,6 +171,9 @@ pub fn ui_party_system(
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
     mut selected_target: ResMut<SelectedTarget>,
+    party_member_targets: Res<PartyMemberTargets>,
+    keyboard_input: Res<Input<KeyCode>>,
+    ignore_list: Res<IgnoreList>,
 ) {
     let player = if let Ok(player) = query_player.get_single() {
         player
@@ -146,7 +212,10 @@ pub fn ui_party_system(
         let mut rejected = false;
         let pending_invite = &ui_state.pending_invites[i];
 
-        if player.party_info.is_none() {
+        if ignore_list.is_ignored(&pending_invite.name) {
+            // Invites from ignored players are rejected without being shown
+            rejected = true;
+        } else if player.party_info.is_none() {
             egui::Window::new("Party Invite")
                 .id(egui::Id::new(format!(
                     "party_invite_{}",
@@ -244,8 +313,11 @@ pub fn ui_party_system(
     let mut response_kick_button = None;
     let mut response_leave_button = None;
     let mut response_option_button = None;
+    let mut assist_clicked = false;
+    let assist_pressed =
+        !egui_context.ctx_mut().wants_keyboard_input() && keyboard_input.just_pressed(KeyCode::F11);
 
-    ui_state_windows.party_open = player.party_info.is_some();
+    ui_state_windows.set_open(UiWindowId::PARTY, player.party_info.is_some());
 
     if let Some(party_info) = player.party_info {
         let player_is_owner = matches!(party_info.owner, PartyOwner::Player);
@@ -324,6 +396,26 @@ pub fn ui_party_system(
                                                 );
                                             }
 
+                                            if let Some(target_name) = get_party_member_target(
+                                                member_info.entity_id,
+                                                &party_member_targets,
+                                                &client_entity_list,
+                                                &query_party_member,
+                                            )
+                                            .and_then(|target| query_invite.get(target).ok())
+                                            .map(|(_, target_name)| target_name)
+                                            {
+                                                ui.add_label_at(
+                                                    egui::pos2(101.0, 34.0),
+                                                    egui::RichText::new(format!(
+                                                        "> {}",
+                                                        target_name.name
+                                                    ))
+                                                    .size(10.0)
+                                                    .color(egui::Color32::LIGHT_GRAY),
+                                                );
+                                            }
+
                                             (true, &member_info.name)
                                         }
                                         PartyMemberInfo::Offline(member_info) => {
@@ -331,6 +423,15 @@ pub fn ui_party_system(
                                         }
                                     };
 
+                                    if online {
+                                        paint_party_member_diamond(
+                                            ui.painter(),
+                                            rect.min + egui::vec2(210.0, 14.0),
+                                            5.0,
+                                            get_party_member_color(index),
+                                        );
+                                    }
+
                                     ui.add_label_at(
                                         egui::pos2(4.0, 26.0),
                                         egui::RichText::new(name).color(egui::Color32::BLACK),
@@ -358,6 +459,15 @@ pub fn ui_party_system(
                                     ui_state.selected_party_member_index = Some(index);
                                 }
                             }
+
+                            if ui_state.selected_party_member_index.is_some() {
+                                assist_clicked = ui
+                                    .add(egui::Button::new("Assist").small())
+                                    .on_hover_text(
+                                        "Target what the selected party member is targeting (F11)",
+                                    )
+                                    .clicked();
+                            }
                         });
                     },
                 );
@@ -401,6 +511,24 @@ pub fn ui_party_system(
             }
         }
 
+        if assist_clicked || assist_pressed {
+            if let Some(target) = ui_state
+                .selected_party_member_index
+                .and_then(|index| party_info.members.get(index))
+                .and_then(|member| member.get_client_entity_id())
+                .and_then(|member_entity_id| {
+                    get_party_member_target(
+                        member_entity_id,
+                        &party_member_targets,
+                        &client_entity_list,
+                        &query_party_member,
+                    )
+                })
+            {
+                selected_target.selected = Some(target);
+            }
+        }
+
         if response_leave_button
             .as_ref()
             .map_or(false, |x| x.clicked())
@@ -417,7 +545,7 @@ pub fn ui_party_system(
             .as_ref()
             .map_or(false, |x| x.clicked())
         {
-            ui_state_windows.party_options_open = !ui_state_windows.party_options_open;
+            ui_state_windows.toggle(UiWindowId::PARTY_OPTIONS);
         }
 
         if let Some(button) = response_entrust_button {
@@ -16,10 +16,11 @@ use crate::{
     events::{MessageBoxEvent, PersonalStoreEvent},
     resources::{GameConnection, GameData, UiResources},
     ui::{
+        format::format_signed_number,
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateDragAndDrop,
     },
 };
 
@@ -111,13 +112,19 @@ fn ui_add_store_item_slot(
         });
     }
 
-    response.on_hover_ui(|ui| {
+    response.on_hover_tooltip_ui(|ui| {
         ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
 
         if is_sell_item {
-            ui.colored_label(egui::Color32::YELLOW, format!("Price: {}", price.0));
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Price: {}", format_signed_number(price.0)),
+            );
         } else {
-            ui.colored_label(egui::Color32::GREEN, format!("Price: {}", price.0));
+            ui.colored_label(
+                egui::Color32::GREEN,
+                format!("Price: {}", format_signed_number(price.0)),
+            );
         }
     });
 }
@@ -15,7 +15,7 @@ use crate::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog, DrawText},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -97,7 +97,7 @@ fn add_equipped_weapon_slot(
         .inner;
 
     if let Some(item) = item {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
         });
     }
@@ -212,6 +212,6 @@ pub fn ui_player_info_system(
     }
 
     if response_menu_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.menu_open = !ui_state_windows.menu_open;
+        ui_state_windows.toggle(UiWindowId::MENU);
     }
 }
@@ -1,26 +1,33 @@
-use bevy::prelude::{Commands, Entity, Local, Query, Res, ResMut, With};
+use std::cell::RefCell;
+
+use bevy::prelude::{Assets, Commands, Entity, EventWriter, Local, Query, Res, ResMut, With};
 use bevy_egui::{egui, EguiContexts};
 use log::info;
 use rose_data::Item;
 use rose_game_common::{
-    components::{Inventory, InventoryPageType, ItemSlot, INVENTORY_PAGE_SIZE},
+    components::{Inventory, InventoryPageType, ItemSlot},
     messages::client::ClientMessage,
 };
 
 use crate::{
     components::{Command, NextCommand, PersonalStore, PlayerCharacter},
-    resources::{GameConnection, GameData},
-    ui::UiStateWindows,
+    resources::{GameConnection, GameData, StoreLowPowerMode, UiResources},
+    ui::{
+        format::format_number,
+        widgets::{DataBindings, Dialog, DrawText, Widget},
+        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
+        UiWindowId,
+    },
 };
 
-const PLAYER_SHOP_MAX_SLOTS: usize = 30;
+const IID_BTN_CLOSE: i32 = 10;
+const IID_EDITBOX_TITLE: i32 = 20;
+const IID_TABLE_SELL: i32 = 30;
+// const IID_SCROLLBAR_SELL: i32 = 31;
+const IID_BTN_START: i32 = 40;
+const IID_BTN_STOP: i32 = 41;
 
-#[derive(Clone)]
-struct InventoryEntry {
-    item_slot: ItemSlot,
-    item: Item,
-    item_name: String,
-}
+const PLAYER_SHOP_MAX_SLOTS: usize = 30;
 
 struct ShopSetupSlot {
     item_slot: ItemSlot,
@@ -33,6 +40,8 @@ struct ShopSetupSlot {
 #[derive(Default)]
 pub struct UiPlayerShopState {
     title: String,
+    selected_index: i32,
+    scroll: i32,
     debug_buy_slot_index: usize,
     debug_buy_quantity: u32,
     selected_slots: Vec<ShopSetupSlot>,
@@ -49,33 +58,69 @@ fn inventory_page_code(page_type: InventoryPageType) -> char {
     }
 }
 
-fn parse_inventory_entries(inventory: &Inventory, game_data: &GameData) -> Vec<InventoryEntry> {
-    let mut entries = Vec::new();
-    for page_type in [
-        InventoryPageType::Equipment,
-        InventoryPageType::Consumables,
-        InventoryPageType::Materials,
-        InventoryPageType::Vehicles,
-    ] {
-        for index in 0..INVENTORY_PAGE_SIZE {
-            let item_slot = ItemSlot::Inventory(page_type, index);
-            let Some(item) = inventory.get_item(item_slot).cloned() else {
-                continue;
-            };
-            let item_name = game_data
-                .items
-                .get_base_item(item.get_item_reference())
-                .map(|item_data| item_data.name.to_string())
-                .unwrap_or_else(|| String::from("Unknown Item"));
-
-            entries.push(InventoryEntry {
-                item_slot,
-                item,
-                item_name,
-            });
+fn get_item_name(game_data: &GameData, item: &Item) -> String {
+    game_data
+        .items
+        .get_base_item(item.get_item_reference())
+        .map(|item_data| item_data.name.to_string())
+        .unwrap_or_else(|| String::from("Unknown Item"))
+}
+
+fn get_shop_open_command(
+    ui_state: &UiPlayerShopState,
+    player_inventory: &Inventory,
+) -> Result<String, String> {
+    if ui_state.selected_slots.is_empty() {
+        return Err(String::from("Shop must have at least one listing."));
+    }
+
+    for selected in ui_state.selected_slots.iter() {
+        let Some(item) = player_inventory.get_item(selected.item_slot) else {
+            return Err(format!(
+                "Inventory slot {:?} is now empty.",
+                selected.item_slot
+            ));
+        };
+        if selected.quantity == 0 || selected.quantity > item.get_quantity() {
+            return Err(format!(
+                "Invalid quantity for {} (max {}).",
+                selected.item_name,
+                item.get_quantity()
+            ));
+        }
+        if selected.price < 0 {
+            return Err(format!(
+                "Price for {} must be non-negative.",
+                selected.item_name
+            ));
         }
     }
-    entries
+
+    let title = if ui_state.title.trim().is_empty() {
+        String::from("My Shop")
+    } else {
+        ui_state.title.trim().replace('"', "")
+    };
+    let listings = ui_state
+        .selected_slots
+        .iter()
+        .filter_map(|slot| {
+            if let ItemSlot::Inventory(page_type, slot_index) = slot.item_slot {
+                Some(format!(
+                    "{}:{}:{}:{}",
+                    inventory_page_code(page_type),
+                    slot_index,
+                    slot.quantity,
+                    slot.price
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+
+    Ok(format!("/pshop_open \"{}\" \"{}\"", title, listings))
 }
 
 fn send_shop_chat_command(game_connection: &Option<Res<GameConnection>>, text: String) -> bool {
@@ -95,242 +140,221 @@ pub fn ui_player_shop_system(
     mut egui_context: EguiContexts,
     mut ui_state_windows: ResMut<UiStateWindows>,
     mut ui_state: Local<UiPlayerShopState>,
+    mut ui_state_dnd: ResMut<UiStateDragAndDrop>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    mut store_low_power_mode: ResMut<StoreLowPowerMode>,
     query_player: Query<(Entity, &Inventory, Option<&PersonalStore>), With<PlayerCharacter>>,
     game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
     game_connection: Option<Res<GameConnection>>,
 ) {
-    if !ui_state_windows.player_shop_open {
+    if !ui_state_windows.is_open(UiWindowId::PLAYER_SHOP) {
         return;
     }
 
+    let ui_state = &mut *ui_state;
     let Ok((player_entity, player_inventory, open_personal_store)) = query_player.get_single()
     else {
         return;
     };
 
-    let inventory_entries = parse_inventory_entries(player_inventory, &game_data);
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_private_store) {
+        dialog
+    } else {
+        return;
+    };
+
     if ui_state.debug_buy_quantity == 0 {
         ui_state.debug_buy_quantity = 1;
     }
 
-    let mut request_close_window = false;
+    let (table_extent, table_columns, table_rect) =
+        if let Some(Widget::Table(table)) = dialog.get_widget(IID_TABLE_SELL) {
+            (
+                table.extent,
+                table.column_count.max(1),
+                table.widget_rect(egui::Pos2::ZERO),
+            )
+        } else {
+            (
+                1,
+                1,
+                egui::Rect::from_min_size(egui::pos2(10.0, 40.0), egui::Vec2::ZERO),
+            )
+        };
+    let table_rows = (PLAYER_SHOP_MAX_SLOTS as i32 + table_columns - 1) / table_columns;
+    let selected_index = ui_state.selected_index;
+    let is_store_open = open_personal_store.is_some();
+
+    // The table cells are drawn from a shared closure, so the drag and drop state
+    // and any item dropped on the table are collected through a RefCell
+    let table_dnd = RefCell::new((&mut *ui_state_dnd, None));
+
+    let mut response_close_button = None;
+    let mut response_start_button = None;
+    let mut response_stop_button = None;
+
     egui::Window::new("Player Shop Setup")
-        .resizable(true)
-        .default_size([980.0, 640.0])
-        .open(&mut ui_state_windows.player_shop_open)
+        .frame(egui::Frame::none())
+        .open(ui_state_windows.open_mut(UiWindowId::PLAYER_SHOP))
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
         .show(egui_context.ctx_mut(), |ui| {
-            ui.horizontal(|ui| {
-                let status = if open_personal_store.is_some() {
-                    "Open"
-                } else {
-                    "Closed"
-                };
-                ui.label(format!("Shop status: {}", status));
-                if let Some(personal_store) = open_personal_store {
-                    ui.label(format!("Title: {}", personal_store.title));
-                }
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("Title:");
-                ui.text_edit_singleline(&mut ui_state.title);
-            });
-
-            if let Some(error) = ui_state.last_error.as_ref() {
-                ui.colored_label(egui::Color32::RED, error);
-            }
-            if let Some(status) = ui_state.last_status.as_ref() {
-                ui.colored_label(egui::Color32::LIGHT_GREEN, status);
-            }
-
-            ui.separator();
-
-            ui.columns(2, |columns| {
-                columns[0].heading("Inventory");
-                columns[0].label("Select items to add to the shop.");
-                egui::ScrollArea::vertical()
-                    .id_source("player_shop_inventory_scroll")
-                    .show(&mut columns[0], |ui| {
-                        for entry in inventory_entries.iter() {
-                            let already_selected = ui_state
-                                .selected_slots
-                                .iter()
-                                .any(|slot| slot.item_slot == entry.item_slot);
-                            ui.horizontal(|ui| {
-                                let slot_label = match entry.item_slot {
-                                    ItemSlot::Inventory(page_type, index) => {
-                                        format!("{}{}", inventory_page_code(page_type), index)
-                                    }
-                                    _ => String::from("-"),
-                                };
-                                ui.add_sized([56.0, 20.0], egui::Label::new(slot_label));
-                                ui.add_sized(
-                                    [90.0, 20.0],
-                                    egui::Label::new(format!("x{}", entry.item.get_quantity())),
-                                );
-                                ui.add_sized([250.0, 20.0], egui::Label::new(&entry.item_name));
-                                if ui
-                                    .add_enabled(
-                                        !already_selected
-                                            && ui_state.selected_slots.len()
-                                                < PLAYER_SHOP_MAX_SLOTS,
-                                        egui::Button::new("Add"),
-                                    )
-                                    .clicked()
-                                {
-                                    ui_state.selected_slots.push(ShopSetupSlot {
-                                        item_slot: entry.item_slot,
-                                        item_name: entry.item_name.clone(),
-                                        quantity: 1,
-                                        max_quantity: entry.item.get_quantity(),
-                                        price: 1,
-                                    });
-                                }
-                            });
-                        }
-                    });
-
-                columns[1].heading("Shop Listings");
-                columns[1].label(format!(
-                    "Selected slots: {}/{}",
-                    ui_state.selected_slots.len(),
-                    PLAYER_SHOP_MAX_SLOTS
-                ));
-                egui::ScrollArea::vertical()
-                    .id_source("player_shop_selected_scroll")
-                    .show(&mut columns[1], |ui| {
-                        let mut remove_index = None;
-                        for (index, selected) in ui_state.selected_slots.iter_mut().enumerate() {
-                            ui.horizontal(|ui| {
-                                let slot_label = match selected.item_slot {
-                                    ItemSlot::Inventory(page_type, slot_index) => {
-                                        format!("{}{}", inventory_page_code(page_type), slot_index)
-                                    }
-                                    _ => String::from("-"),
-                                };
-                                ui.add_sized([56.0, 20.0], egui::Label::new(slot_label));
-                                ui.add_sized([210.0, 20.0], egui::Label::new(&selected.item_name));
-
-                                ui.label("Qty");
-                                ui.add(
-                                    egui::DragValue::new(&mut selected.quantity)
-                                        .speed(1.0)
-                                        .clamp_range(1..=selected.max_quantity),
-                                );
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    enabled: &mut [
+                        (IID_EDITBOX_TITLE, !is_store_open),
+                        (
+                            IID_BTN_START,
+                            !is_store_open && !ui_state.selected_slots.is_empty(),
+                        ),
+                        (IID_BTN_STOP, is_store_open),
+                    ],
+                    text: &mut [(IID_EDITBOX_TITLE, &mut ui_state.title)],
+                    scroll: &mut [(
+                        IID_TABLE_SELL,
+                        (&mut ui_state.scroll, 0..table_rows, table_extent),
+                    )],
+                    table: &mut [(
+                        IID_TABLE_SELL,
+                        (&mut ui_state.selected_index, &|ui, index, _, _| {
+                            let listing = ui_state.selected_slots.get(index as usize);
+                            let item = listing
+                                .and_then(|listing| player_inventory.get_item(listing.item_slot));
+                            let mut table_dnd = table_dnd.borrow_mut();
+                            let (ui_state_dnd, table_dropped_item) = &mut *table_dnd;
+                            let mut dropped_item = None;
+                            let response = egui::Widget::ui(
+                                DragAndDropSlot::with_item(
+                                    DragAndDropId::NotDraggable,
+                                    item,
+                                    None,
+                                    &game_data,
+                                    &ui_resources,
+                                    |drag_source: &DragAndDropId| -> bool {
+                                        matches!(
+                                            drag_source,
+                                            DragAndDropId::Inventory(ItemSlot::Inventory(_, _))
+                                        )
+                                    },
+                                    &mut ui_state_dnd.dragged_item,
+                                    &mut dropped_item,
+                                    [40.0, 40.0],
+                                ),
+                                ui,
+                            );
+
+                            if dropped_item.is_some() {
+                                *table_dropped_item = dropped_item;
+                            }
 
-                                ui.label("Price");
-                                ui.add(
-                                    egui::DragValue::new(&mut selected.price)
-                                        .speed(1.0)
-                                        .clamp_range(0..=i64::MAX),
+                            if index == selected_index && listing.is_some() {
+                                ui.painter().rect_stroke(
+                                    response.rect,
+                                    0.0,
+                                    egui::Stroke::new(1.0, egui::Color32::YELLOW),
                                 );
+                            }
 
-                                if ui.button("Remove").clicked() {
-                                    remove_index = Some(index);
-                                }
-                            });
-                        }
-
-                        if let Some(index) = remove_index {
-                            ui_state.selected_slots.remove(index);
-                        }
-                    });
-            });
-
-            ui.separator();
-
-            ui.horizontal(|ui| {
-                if ui.button("Open Shop").clicked() {
-                    ui_state.last_error = None;
-                    ui_state.last_status = None;
-
-                    if ui_state.selected_slots.is_empty() {
-                        ui_state.last_error =
-                            Some(String::from("Shop must have at least one listing."));
-                        return;
-                    }
-
-                    for selected in ui_state.selected_slots.iter() {
-                        let Some(item) = player_inventory.get_item(selected.item_slot) else {
-                            ui_state.last_error = Some(format!(
-                                "Inventory slot {:?} is now empty.",
-                                selected.item_slot
-                            ));
-                            return;
-                        };
-                        if selected.quantity == 0 || selected.quantity > item.get_quantity() {
-                            ui_state.last_error = Some(format!(
-                                "Invalid quantity for {} (max {}).",
-                                selected.item_name,
-                                item.get_quantity()
-                            ));
-                            return;
-                        }
-                        if selected.price < 0 {
-                            ui_state.last_error = Some(format!(
-                                "Price for {} must be non-negative.",
-                                selected.item_name
-                            ));
-                            return;
-                        }
-                    }
-
-                    let title = if ui_state.title.trim().is_empty() {
-                        String::from("My Shop")
-                    } else {
-                        ui_state.title.trim().replace('"', "")
-                    };
-                    let listings = ui_state
-                        .selected_slots
-                        .iter()
-                        .filter_map(|slot| {
-                            if let ItemSlot::Inventory(page_type, slot_index) = slot.item_slot {
-                                Some(format!(
-                                    "{}:{}:{}:{}",
-                                    inventory_page_code(page_type),
-                                    slot_index,
-                                    slot.quantity,
-                                    slot.price
+                            if let Some(listing) = listing {
+                                response.on_hover_text(format!(
+                                    "{}\n{} x {}",
+                                    listing.item_name,
+                                    listing.quantity,
+                                    format_number(listing.price as u64)
                                 ))
                             } else {
-                                None
+                                response
                             }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(";");
-
-                    let command = format!("/pshop_open \"{}\" \"{}\"", title, listings);
-                    if send_shop_chat_command(&game_connection, command.clone()) {
-                        info!(
-                            "player-shop: open requested with {} slot(s)",
-                            ui_state.selected_slots.len()
+                        }),
+                    )],
+                    response: &mut [
+                        (IID_BTN_CLOSE, &mut response_close_button),
+                        (IID_BTN_START, &mut response_start_button),
+                        (IID_BTN_STOP, &mut response_stop_button),
+                    ],
+                    ..Default::default()
+                },
+                |_, _| {},
+            );
+
+            // Editing the selected listing is not part of the dialog widget tree
+            let mut y = table_rect.max.y + 6.0;
+            let mut remove_index = None;
+            if let Some((index, selected)) = usize::try_from(ui_state.selected_index)
+                .ok()
+                .and_then(|index| Some((index, ui_state.selected_slots.get_mut(index)?)))
+            {
+                ui.add_label_at(egui::pos2(table_rect.min.x, y), &selected.item_name);
+                ui.add_at(
+                    egui::pos2(table_rect.min.x, y + 20.0),
+                    |ui: &mut egui::Ui| {
+                        ui.spacing_mut().item_spacing.x = 4.0;
+                        ui.label("Qty");
+                        ui.add_enabled(
+                            !is_store_open,
+                            egui::DragValue::new(&mut selected.quantity)
+                                .speed(1.0)
+                                .clamp_range(1..=selected.max_quantity),
                         );
-                        ui_state.last_status = Some(String::from("Shop open request sent."));
-                    } else {
-                        ui_state.last_error =
-                            Some(String::from("Failed to send shop open request."));
-                    }
-                }
+                        ui.label("Price");
+                        ui.add_enabled(
+                            !is_store_open,
+                            egui::DragValue::new(&mut selected.price)
+                                .speed(1.0)
+                                .clamp_range(0..=i64::MAX),
+                        );
+                        let response = ui.add_enabled(!is_store_open, egui::Button::new("Remove"));
+                        if response.clicked() {
+                            remove_index = Some(index);
+                        }
+                        response
+                    },
+                );
+            } else {
+                ui.add_label_at(
+                    egui::pos2(table_rect.min.x, y),
+                    egui::RichText::new("Drag items from your inventory to sell them.")
+                        .color(egui::Color32::GRAY),
+                );
+            }
+            y += 44.0;
 
-                if ui.button("Close Shop").clicked() {
-                    let command = String::from("/pshop_close");
-                    if send_shop_chat_command(&game_connection, command) {
-                        info!("player-shop: close requested");
-                        // Optimistically clear local state so movement/model recover immediately
-                        // even if the authoritative close packet is delayed.
-                        commands
-                            .entity(player_entity)
-                            .remove::<PersonalStore>()
-                            .insert(Command::with_stop())
-                            .insert(NextCommand::with_stop());
-                        ui_state.last_status = Some(String::from("Shop close request sent."));
-                    } else {
-                        ui_state.last_error =
-                            Some(String::from("Failed to send shop close request."));
-                    }
-                }
+            if let Some(index) = remove_index {
+                ui_state.selected_slots.remove(index);
+            }
 
-                ui.separator();
+            if let Some(error) = ui_state.last_error.as_ref() {
+                ui.add_label_at(
+                    egui::pos2(table_rect.min.x, y),
+                    egui::RichText::new(error).color(egui::Color32::RED),
+                );
+            } else if let Some(status) = ui_state.last_status.as_ref() {
+                ui.add_label_at(
+                    egui::pos2(table_rect.min.x, y),
+                    egui::RichText::new(status).color(egui::Color32::LIGHT_GREEN),
+                );
+            }
+            y += 20.0;
+
+            if ui
+                .add_at(egui::pos2(table_rect.min.x, y), |ui: &mut egui::Ui| {
+                    ui.add_enabled(is_store_open, egui::Button::new("Low Power Mode"))
+                })
+                .on_hover_text("Shrink the window to a sales status widget while the shop is open")
+                .clicked()
+            {
+                store_low_power_mode.enabled = true;
+            }
+            y += 24.0;
+
+            ui.add_at(egui::pos2(table_rect.min.x, y), |ui: &mut egui::Ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
                 ui.label("Debug Buy Slot:");
                 ui.add(
                     egui::DragValue::new(&mut ui_state.debug_buy_slot_index)
@@ -343,12 +367,13 @@ pub fn ui_player_shop_system(
                         .speed(1.0)
                         .clamp_range(1..=999u32),
                 );
-                if ui.button("Debug Buy").clicked() {
+                let response = ui.button("Debug Buy");
+                if response.clicked() {
                     let command = format!(
                         "/pshop_test_buy {} {}",
                         ui_state.debug_buy_slot_index, ui_state.debug_buy_quantity
                     );
-                    if send_shop_chat_command(&game_connection, command.clone()) {
+                    if send_shop_chat_command(&game_connection, command) {
                         info!(
                             "player-shop: debug buy requested slot={} qty={}",
                             ui_state.debug_buy_slot_index, ui_state.debug_buy_quantity
@@ -362,14 +387,72 @@ pub fn ui_player_shop_system(
                             Some(String::from("Failed to send debug buy request."));
                     }
                 }
+                response
             });
+        });
 
-            if ui.button("Close Window").clicked() {
-                request_close_window = true;
+    if let (_, Some(DragAndDropId::Inventory(item_slot))) = table_dnd.into_inner() {
+        let already_selected = ui_state
+            .selected_slots
+            .iter()
+            .any(|slot| slot.item_slot == item_slot);
+        if let Some(item) = player_inventory.get_item(item_slot) {
+            if !is_store_open
+                && !already_selected
+                && ui_state.selected_slots.len() < PLAYER_SHOP_MAX_SLOTS
+            {
+                ui_state.selected_slots.push(ShopSetupSlot {
+                    item_slot,
+                    item_name: get_item_name(&game_data, item),
+                    quantity: item.get_quantity(),
+                    max_quantity: item.get_quantity(),
+                    price: 1,
+                });
+                ui_state.selected_index = ui_state.selected_slots.len() as i32 - 1;
             }
-        });
+        }
+    }
+
+    if response_start_button.map_or(false, |r| r.clicked()) {
+        ui_state.last_error = None;
+        ui_state.last_status = None;
+
+        match get_shop_open_command(ui_state, player_inventory) {
+            Ok(command) => {
+                if send_shop_chat_command(&game_connection, command) {
+                    info!(
+                        "player-shop: open requested with {} slot(s)",
+                        ui_state.selected_slots.len()
+                    );
+                    ui_state.last_status = Some(String::from("Shop open request sent."));
+                } else {
+                    ui_state.last_error = Some(String::from("Failed to send shop open request."));
+                }
+            }
+            Err(error) => {
+                ui_state.last_error = Some(error);
+            }
+        }
+    }
+
+    if response_stop_button.map_or(false, |r| r.clicked()) {
+        let command = String::from("/pshop_close");
+        if send_shop_chat_command(&game_connection, command) {
+            info!("player-shop: close requested");
+            // Optimistically clear local state so movement/model recover immediately
+            // even if the authoritative close packet is delayed.
+            commands
+                .entity(player_entity)
+                .remove::<PersonalStore>()
+                .insert(Command::with_stop())
+                .insert(NextCommand::with_stop());
+            ui_state.last_status = Some(String::from("Shop close request sent."));
+        } else {
+            ui_state.last_error = Some(String::from("Failed to send shop close request."));
+        }
+    }
 
-    if request_close_window {
-        ui_state_windows.player_shop_open = false;
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.set_open(UiWindowId::PLAYER_SHOP, false);
     }
 }
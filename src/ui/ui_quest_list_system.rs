@@ -11,7 +11,7 @@ use crate::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog, DrawText, Widget},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -29,6 +29,23 @@ const IID_LIST_QUESTINFO: i32 = 30;
 // const IID_PANE_QUESTLIST: i32 = 100;
 const IID_PANE_QUESTINFO: i32 = 200;
 
+const QUEST_LIST_SELECTION_ID: &str = "quest_list_selection";
+
+// Selects a quest in the quest list next time it is drawn, used by other
+// windows to jump to the quest an item belongs to
+pub fn set_quest_list_selection(ctx: &egui::Context, quest_id: usize) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(QUEST_LIST_SELECTION_ID), quest_id));
+}
+
+fn take_quest_list_selection(ctx: &egui::Context) -> Option<usize> {
+    ctx.data_mut(|data| {
+        let id = egui::Id::new(QUEST_LIST_SELECTION_ID);
+        let quest_id = data.get_temp::<usize>(id);
+        data.remove::<usize>(id);
+        quest_id
+    })
+}
+
 fn ui_add_quest_item_slot(
     ui: &mut egui::Ui,
     pos: egui::Pos2,
@@ -62,7 +79,7 @@ fn ui_add_quest_item_slot(
         .inner;
 
     if let Some(item) = item {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
         });
     }
@@ -113,6 +130,17 @@ pub fn ui_quest_list_system(
     };
     let player_tooltip_data = query_player_tooltip.get_single().ok();
 
+    if let Some(quest_id) = take_quest_list_selection(egui_context.ctx_mut()) {
+        if let Some(index) = player_quest_state
+            .active_quests
+            .iter()
+            .flatten()
+            .position(|active_quest| active_quest.quest_id == quest_id)
+        {
+            ui_state.selected_index = index as i32;
+        }
+    }
+
     let listbox_extent = if let Some(Widget::ZListbox(listbox)) = dialog.get_widget(IID_ZLIST_QUEST)
     {
         listbox.extent
@@ -134,7 +162,7 @@ pub fn ui_quest_list_system(
 
     egui::Window::new("Quest List")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.quest_list_open)
+        .open(ui_state_windows.open_mut(UiWindowId::QUEST_LIST))
         .title_bar(false)
         .resizable(false)
         .default_width(dialog.width)
@@ -290,7 +318,7 @@ pub fn ui_quest_list_system(
         });
 
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.quest_list_open = false;
+        ui_state_windows.set_open(UiWindowId::QUEST_LIST, false);
     }
 
     if response_minimise_button.map_or(false, |r| r.clicked()) {
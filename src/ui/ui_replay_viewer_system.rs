@@ -0,0 +1,76 @@
+use bevy::prelude::ResMut;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::ReplayPlayback;
+
+fn format_replay_time(time: f32) -> String {
+    let seconds = time.max(0.0) as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn ui_replay_viewer_system(
+    mut egui_context: EguiContexts,
+    mut replay_playback: ResMut<ReplayPlayback>,
+) {
+    let replay_playback = &mut *replay_playback;
+    let duration = replay_playback.replay.duration();
+
+    egui::Window::new("Replay")
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -10.0])
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                let play_text = if replay_playback.playing {
+                    "Pause"
+                } else {
+                    "Play"
+                };
+                if ui.button(play_text).clicked() {
+                    if !replay_playback.playing && replay_playback.time >= duration {
+                        replay_playback.time = 0.0;
+                    }
+                    replay_playback.playing = !replay_playback.playing;
+                }
+
+                ui.label(format!(
+                    "{} / {}",
+                    format_replay_time(replay_playback.time),
+                    format_replay_time(duration)
+                ));
+
+                ui.add(
+                    egui::Slider::new(&mut replay_playback.speed, 0.25..=8.0)
+                        .logarithmic(true)
+                        .text("Speed"),
+                );
+            });
+
+            ui.spacing_mut().slider_width = 600.0;
+            ui.add(egui::Slider::new(&mut replay_playback.time, 0.0..=duration).show_value(false));
+
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .max_height(150.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for chat in replay_playback
+                        .replay
+                        .chat
+                        .iter()
+                        .take_while(|chat| chat.time <= replay_playback.time)
+                    {
+                        let text = if let Some(name) = chat.name.as_ref() {
+                            format!(
+                                "[{}] {}> {}",
+                                format_replay_time(chat.time),
+                                name,
+                                chat.text
+                            )
+                        } else {
+                            format!("[{}] {}", format_replay_time(chat.time), chat.text)
+                        };
+                        ui.label(text);
+                    }
+                });
+        });
+}
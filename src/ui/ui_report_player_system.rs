@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::{EventReader, EventWriter, Local, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    events::{ChatboxEvent, MessageBoxEvent, ReportPlayerEvent},
+    protocol::{ExtendedClientMessage, PlayerReport, PlayerReportCategory},
+    resources::{GameConnection, PlayerReports},
+    ui::{UiStateWindows, UiWindowId},
+};
+
+// Number of recent chat lines kept for each player to attach to reports
+const REPORT_MAX_CHAT_LINES: usize = 10;
+const REPORT_MAX_CHAT_PLAYERS: usize = 100;
+
+const REPORT_CATEGORIES: [(PlayerReportCategory, &str); 6] = [
+    (PlayerReportCategory::Harassment, "Harassment"),
+    (PlayerReportCategory::Spam, "Spam"),
+    (PlayerReportCategory::Cheating, "Cheating"),
+    (PlayerReportCategory::Scamming, "Scamming"),
+    (PlayerReportCategory::OffensiveName, "Offensive name"),
+    (PlayerReportCategory::Other, "Other"),
+];
+
+fn category_name(category: PlayerReportCategory) -> &'static str {
+    REPORT_CATEGORIES
+        .iter()
+        .find(|(report_category, _)| *report_category == category)
+        .map_or("", |(_, name)| name)
+}
+
+pub struct UiStateReportPlayer {
+    name: String,
+    category: PlayerReportCategory,
+    description: String,
+    chat_lines: Vec<String>,
+    recent_chat: VecDeque<(String, VecDeque<String>)>,
+    sent_queued_reports: bool,
+}
+
+impl Default for UiStateReportPlayer {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            category: PlayerReportCategory::Harassment,
+            description: String::new(),
+            chat_lines: Vec::new(),
+            recent_chat: VecDeque::new(),
+            sent_queued_reports: false,
+        }
+    }
+}
+
+pub fn ui_report_player_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateReportPlayer>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut chatbox_events: EventReader<ChatboxEvent>,
+    mut report_player_events: EventReader<ReportPlayerEvent>,
+    mut message_box_events: EventWriter<MessageBoxEvent>,
+    game_connection: Option<Res<GameConnection>>,
+    player_reports: Res<PlayerReports>,
+) {
+    let ui_state = &mut *ui_state;
+
+    // Send any reports which were made while we were not connected
+    if let Some(game_connection) = game_connection.as_ref() {
+        if !ui_state.sent_queued_reports {
+            for report in player_reports.take_queued() {
+                game_connection
+                    .extended_client_message_tx
+                    .send(ExtendedClientMessage::PlayerReport(report))
+                    .ok();
+            }
+            ui_state.sent_queued_reports = true;
+        }
+    } else {
+        ui_state.sent_queued_reports = false;
+    }
+
+    let timestamp = chrono::Local::now().format("%H:%M:%S");
+    for event in chatbox_events.iter() {
+        let (name, text) = match event {
+            ChatboxEvent::Say(name, text)
+            | ChatboxEvent::Shout(name, text)
+            | ChatboxEvent::Trade(name, text)
+            | ChatboxEvent::Clan(name, text)
+            | ChatboxEvent::Whisper(name, text) => (name, text),
+            _ => continue,
+        };
+
+        let index = if let Some(index) = ui_state
+            .recent_chat
+            .iter()
+            .position(|(chat_name, _)| chat_name == name)
+        {
+            index
+        } else {
+            if ui_state.recent_chat.len() >= REPORT_MAX_CHAT_PLAYERS {
+                ui_state.recent_chat.pop_front();
+            }
+            ui_state
+                .recent_chat
+                .push_back((name.clone(), VecDeque::new()));
+            ui_state.recent_chat.len() - 1
+        };
+
+        let lines = &mut ui_state.recent_chat[index].1;
+        if lines.len() >= REPORT_MAX_CHAT_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(format!("[{}] {}", timestamp, text));
+    }
+
+    if let Some(event) = report_player_events.iter().last() {
+        ui_state.name = event.name.clone();
+        ui_state.category = PlayerReportCategory::Harassment;
+        ui_state.description.clear();
+        ui_state.chat_lines = ui_state
+            .recent_chat
+            .iter()
+            .find(|(chat_name, _)| *chat_name == event.name)
+            .map(|(_, lines)| lines.iter().cloned().collect())
+            .unwrap_or_default();
+        ui_state_windows.set_open(UiWindowId::REPORT_PLAYER, true);
+    }
+
+    let mut submit = false;
+    let mut cancel = false;
+    egui::Window::new("Report Player")
+        .open(ui_state_windows.open_mut(UiWindowId::REPORT_PLAYER))
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::Grid::new("report_player_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Player:");
+                    ui.label(ui_state.name.as_str());
+                    ui.end_row();
+
+                    ui.label("Category:");
+                    egui::ComboBox::from_id_source("report_player_category")
+                        .selected_text(category_name(ui_state.category))
+                        .show_ui(ui, |ui| {
+                            for (category, name) in REPORT_CATEGORIES {
+                                ui.selectable_value(&mut ui_state.category, category, name);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Description:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut ui_state.description)
+                            .desired_rows(4)
+                            .hint_text("Describe what happened"),
+                    );
+                    ui.end_row();
+                });
+
+            ui.collapsing(
+                format!("Attached chat ({} lines)", ui_state.chat_lines.len()),
+                |ui| {
+                    if ui_state.chat_lines.is_empty() {
+                        ui.label("No recent chat from this player");
+                    }
+
+                    for line in ui_state.chat_lines.iter() {
+                        ui.label(line.as_str());
+                    }
+                },
+            );
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Submit").clicked() {
+                    submit = true;
+                }
+
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if submit {
+        let report = PlayerReport {
+            name: ui_state.name.clone(),
+            category: ui_state.category,
+            description: ui_state.description.trim().to_string(),
+            chat_lines: std::mem::take(&mut ui_state.chat_lines),
+        };
+
+        let message = if let Some(game_connection) = game_connection.as_ref() {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::PlayerReport(report))
+                .ok();
+            format!("Your report of {} has been sent", ui_state.name)
+        } else {
+            player_reports.queue(report);
+            format!(
+                "Your report of {} will be sent the next time you are connected",
+                ui_state.name
+            )
+        };
+
+        message_box_events.send(MessageBoxEvent::Show {
+            message,
+            modal: false,
+            ok: None,
+            cancel: None,
+        });
+    }
+
+    if submit || cancel {
+        ui_state_windows.set_open(UiWindowId::REPORT_PLAYER, false);
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::{Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::SkillType;
+use rose_data_irose::{IroseSkillPageType, SKILL_PAGE_SIZE};
+use rose_game_common::components::{Position, SkillList, SkillSlot};
+
+use crate::{
+    components::{ClientEntityName, Dead, PartyMember, PlayerCharacter},
+    events::{ExtendedServerMessageEvent, PlayerCommandEvent},
+    protocol::{
+        ExtendedClientMessage, ExtendedServerMessage, ResurrectionOffer, ResurrectionResponse,
+    },
+    resources::{ClientEntityList, GameConnection, GameData, SelectedTarget},
+};
+
+// Dead party members further away than this do not prompt for resurrection
+const RESURRECTION_PROMPT_DISTANCE: f32 = 3000.0;
+
+#[derive(Default)]
+pub struct UiStateResurrection {
+    pending_offers: Vec<ResurrectionOffer>,
+    dismissed_members: Vec<Entity>,
+}
+
+fn find_resurrection_skill(skill_list: &SkillList, game_data: &GameData) -> Option<SkillSlot> {
+    [
+        IroseSkillPageType::Basic as usize,
+        IroseSkillPageType::Active as usize,
+    ]
+    .into_iter()
+    .flat_map(|page| (0..SKILL_PAGE_SIZE).map(move |index| SkillSlot(page, index)))
+    .find(|skill_slot| {
+        skill_list
+            .get_skill(*skill_slot)
+            .and_then(|skill_id| game_data.skills.get_skill(skill_id))
+            .map_or(false, |skill_data| {
+                matches!(skill_data.skill_type, SkillType::Resurrection)
+            })
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_resurrection_system(
+    mut ui_state: Local<UiStateResurrection>,
+    mut egui_context: EguiContexts,
+    query_player: Query<(&Position, &SkillList, Option<&Dead>), With<PlayerCharacter>>,
+    query_dead_party_members: Query<
+        (Entity, &ClientEntityName, &Position),
+        (With<PartyMember>, With<Dead>),
+    >,
+    query_name: Query<&ClientEntityName>,
+    client_entity_list: Res<ClientEntityList>,
+    game_connection: Option<Res<GameConnection>>,
+    game_data: Res<GameData>,
+    mut selected_target: ResMut<SelectedTarget>,
+    mut player_command_events: EventWriter<PlayerCommandEvent>,
+    mut extended_server_message_events: EventReader<ExtendedServerMessageEvent>,
+) {
+    let Ok((player_position, player_skill_list, player_dead)) = query_player.get_single() else {
+        return;
+    };
+
+    for ExtendedServerMessageEvent(message) in extended_server_message_events.iter() {
+        if let ExtendedServerMessage::ResurrectionOffer(offer) = message {
+            ui_state
+                .pending_offers
+                .retain(|pending| pending.caster_entity_id != offer.caster_entity_id);
+            ui_state.pending_offers.push(offer.clone());
+        }
+    }
+
+    if player_dead.is_some() {
+        ui_state.dismissed_members.clear();
+
+        // Incoming offers to resurrect the player, the oldest is shown first
+        let Some(offer) = ui_state.pending_offers.first().cloned() else {
+            return;
+        };
+        let caster_name = client_entity_list
+            .get(offer.caster_entity_id)
+            .and_then(|entity| query_name.get(entity).ok())
+            .map_or("Someone", |name| name.as_str());
+        let skill_name = game_data
+            .skills
+            .get_skill(offer.skill_id)
+            .map_or("a resurrection skill", |skill_data| skill_data.name);
+
+        let mut response = None;
+        egui::Window::new("Resurrection")
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 120.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_context.ctx_mut(), |ui| {
+                ui.label(format!(
+                    "{} wants to resurrect you with {}",
+                    caster_name, skill_name
+                ));
+
+                ui.horizontal(|ui| {
+                    if ui.button("Accept").clicked() {
+                        response = Some(true);
+                    }
+
+                    if ui.button("Decline").clicked() {
+                        response = Some(false);
+                    }
+                });
+            });
+
+        if let Some(accepted) = response {
+            if let Some(game_connection) = game_connection.as_ref() {
+                game_connection
+                    .extended_client_message_tx
+                    .send(ExtendedClientMessage::ResurrectionResponse(
+                        ResurrectionResponse {
+                            caster_entity_id: offer.caster_entity_id,
+                            accepted,
+                        },
+                    ))
+                    .ok();
+            }
+            ui_state.pending_offers.remove(0);
+        }
+        return;
+    }
+
+    // Any offers left are stale once the player is alive again
+    ui_state.pending_offers.clear();
+
+    let Some(skill_slot) = find_resurrection_skill(player_skill_list, &game_data) else {
+        return;
+    };
+
+    ui_state
+        .dismissed_members
+        .retain(|entity| query_dead_party_members.contains(*entity));
+
+    let dead_members: Vec<(Entity, &ClientEntityName)> = query_dead_party_members
+        .iter()
+        .filter(|(entity, _, position)| {
+            !ui_state.dismissed_members.contains(entity)
+                && position.position.distance(player_position.position)
+                    < RESURRECTION_PROMPT_DISTANCE
+        })
+        .map(|(entity, name, _)| (entity, name))
+        .collect();
+    if dead_members.is_empty() {
+        return;
+    }
+
+    let mut resurrect_entity = None;
+    let mut dismiss_entity = None;
+    egui::Window::new("Resurrect Party Member")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 120.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            for (entity, name) in dead_members.iter() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} has fallen", name.as_str()));
+
+                    if ui.button("Resurrect").clicked() {
+                        resurrect_entity = Some(*entity);
+                    }
+
+                    if ui.button("Dismiss").clicked() {
+                        dismiss_entity = Some(*entity);
+                    }
+                });
+            }
+        });
+
+    if let Some(entity) = resurrect_entity {
+        // Skill targeting uses the selected target, so select the member before casting
+        selected_target.selected = Some(entity);
+        player_command_events.send(PlayerCommandEvent::UseSkill(skill_slot));
+    }
+
+    if let Some(entity) = dismiss_entity {
+        ui_state.dismissed_members.push(entity);
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::ResMut;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    scripting::UserScriptManager,
+    ui::{UiStateWindows, UiWindowId},
+};
+
+pub fn ui_script_manager_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut user_script_manager: ResMut<UserScriptManager>,
+) {
+    let mut set_enabled = None;
+    let mut reload = false;
+
+    egui::Window::new("Scripts")
+        .open(ui_state_windows.open_mut(UiWindowId::SCRIPT_MANAGER))
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label(format!(
+                "Scripts are loaded from {}",
+                user_script_manager.path.to_string_lossy()
+            ));
+            ui.separator();
+
+            if user_script_manager.scripts.is_empty() {
+                ui.label("No scripts found");
+            }
+
+            egui::Grid::new("script_manager_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (index, script) in user_script_manager.scripts.iter().enumerate() {
+                        let mut enabled = script.enabled;
+                        if ui.checkbox(&mut enabled, &script.name).changed() {
+                            set_enabled = Some((index, enabled));
+                        }
+
+                        if let Some(error) = script.error.as_ref() {
+                            ui.colored_label(egui::Color32::RED, error);
+                        } else if script.enabled {
+                            ui.label("Running");
+                        } else {
+                            ui.label("Disabled");
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            if ui.button("Reload").clicked() {
+                reload = true;
+            }
+        });
+
+    if let Some((index, enabled)) = set_enabled {
+        user_script_manager.set_enabled(index, enabled);
+    }
+
+    if reload {
+        let disabled = user_script_manager.disabled_script_names();
+        user_script_manager.reload(&disabled);
+    }
+}
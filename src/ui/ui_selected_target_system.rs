@@ -6,7 +6,7 @@ use rose_game_common::components::{AbilityValues, HealthPoints, Npc};
 use crate::{
     components::{ClientEntityName, Dead},
     resources::{SelectedTarget, UiResources, UiSprite},
-    ui::UiStateWindows,
+    ui::{UiStateWindows, UiWindowId},
 };
 
 #[derive(Default)]
@@ -40,7 +40,7 @@ pub fn ui_selected_target_system(
         ui_state.hp_gauge_foreground = ui_resources.get_sprite(0, "UI00_GUAGE_RED");
     }
 
-    if !ui_state_windows.selected_target_ui_open {
+    if !ui_state_windows.is_open(UiWindowId::SELECTED_TARGET_UI) {
         return;
     }
 
@@ -1,13 +1,72 @@
-use bevy::prelude::{Local, Query, ResMut};
+use bevy::prelude::{Local, ResMut};
 use bevy_egui::{egui, EguiContexts};
 
 use crate::{
-    audio::SoundGain, components::SoundCategory, resources::SoundSettings, ui::UiStateWindows,
+    resources::{
+        AccessibilitySettings, AudioSettings, ClientSettings, CombatSettings, ControlsSettings,
+        GraphicsSettings, InputDisplayPosition, InteractKey, InterfaceSettings, LoginSettings,
+        SocialSettings,
+    },
+    ui::{UiStateWindows, UiWindowId},
 };
 
+const SETTINGS_KEYBINDS: [(&str, &str); 20] = [
+    ("Esc", "Game menu"),
+    ("Alt + A", "Character info"),
+    ("Alt + D", "Character stats"),
+    ("Alt + I / Alt + V", "Inventory"),
+    ("Alt + S", "Skills"),
+    ("Alt + Q", "Quests"),
+    ("Alt + C", "Social"),
+    ("Alt + N", "Clan"),
+    ("Alt + O", "Settings"),
+    ("Alt + J", "Scripts"),
+    ("Alt + U", "Union"),
+    ("Alt + G", "Vehicle garage"),
+    ("Alt + M", "Chat macros"),
+    ("R", "Mount / dismount vehicle"),
+    ("F10", "Cinematic mode"),
+    ("F11", "Assist party leader"),
+    ("F12", "Hide other players"),
+    ("Page Up / Page Down", "Cinematic orbit speed"),
+    ("Mouse Wheel", "Camera zoom"),
+    ("Shift + Drag", "Split item stack"),
+];
+
+const INPUT_DISPLAY_POSITIONS: [InputDisplayPosition; 4] = [
+    InputDisplayPosition::TopLeft,
+    InputDisplayPosition::TopRight,
+    InputDisplayPosition::BottomLeft,
+    InputDisplayPosition::BottomRight,
+];
+
+const INTERACT_KEYS: [InteractKey; 5] = [
+    InteractKey::E,
+    InteractKey::F,
+    InteractKey::G,
+    InteractKey::T,
+    InteractKey::Space,
+];
+
+fn input_display_position_name(position: InputDisplayPosition) -> &'static str {
+    match position {
+        InputDisplayPosition::TopLeft => "Top Left",
+        InputDisplayPosition::TopRight => "Top Right",
+        InputDisplayPosition::BottomLeft => "Bottom Left",
+        InputDisplayPosition::BottomRight => "Bottom Right",
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 enum SettingsPage {
-    Sound,
+    Graphics,
+    Audio,
+    Interface,
+    Controls,
+    Social,
+    Combat,
+    Accessibility,
+    Login,
 }
 
 pub struct UiStateSettings {
@@ -17,73 +76,423 @@ pub struct UiStateSettings {
 impl Default for UiStateSettings {
     fn default() -> Self {
         Self {
-            page: SettingsPage::Sound,
+            page: SettingsPage::Graphics,
         }
     }
 }
 
+fn ui_add_volume_slider(ui: &mut egui::Ui, text: &str, value: &mut f32) {
+    ui.label(text);
+    ui.add(egui::Slider::new(value, 0.0..=1.0).show_value(true));
+    ui.end_row();
+}
+
+fn ui_graphics_settings(ui: &mut egui::Ui, graphics: &mut GraphicsSettings) {
+    egui::Grid::new("graphics_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Texture Streaming:");
+            let mut enabled = graphics.texture_budget_mb.is_some();
+            if ui.checkbox(&mut enabled, "Enabled").changed() {
+                graphics.texture_budget_mb = enabled.then_some(1024);
+            }
+            ui.end_row();
+
+            if let Some(budget_mb) = graphics.texture_budget_mb.as_mut() {
+                ui.label("Texture Budget:");
+                ui.add(
+                    egui::Slider::new(budget_mb, 256..=4096)
+                        .suffix(" MB")
+                        .show_value(true),
+                );
+                ui.end_row();
+            }
+
+            ui.label("High Detail Distance:");
+            ui.add(
+                egui::Slider::new(&mut graphics.texture_high_detail_distance, 10.0..=500.0)
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Overlay:");
+            ui.checkbox(&mut graphics.show_texture_overlay, "Show texture memory");
+            ui.end_row();
+
+            ui.label("Crowded Area Mode:");
+            ui.checkbox(&mut graphics.crowd_mode, "Enabled")
+                .on_hover_text("Party and clan members are always shown in full");
+            ui.end_row();
+
+            if graphics.crowd_mode {
+                ui.label("Detailed Players:");
+                ui.add(
+                    egui::Slider::new(&mut graphics.crowd_max_detailed_players, 0..=100)
+                        .show_value(true),
+                );
+                ui.end_row();
+
+                ui.label("Detailed Distance:");
+                ui.add(
+                    egui::Slider::new(&mut graphics.crowd_detailed_distance, 5.0..=100.0)
+                        .suffix(" m")
+                        .show_value(true),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+fn ui_audio_settings(ui: &mut egui::Ui, audio: &mut AudioSettings) {
+    egui::Grid::new("audio_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Sound:");
+            ui.checkbox(&mut audio.enabled, "Enabled");
+            ui.end_row();
+
+            ui_add_volume_slider(ui, "Global Volume:", &mut audio.global);
+            ui_add_volume_slider(ui, "Background Music:", &mut audio.background_music);
+            ui_add_volume_slider(ui, "Player Footsteps:", &mut audio.player_footstep);
+            ui_add_volume_slider(ui, "Other Footsteps:", &mut audio.other_footstep);
+            ui_add_volume_slider(ui, "Player Combat:", &mut audio.player_combat);
+            ui_add_volume_slider(ui, "Other Combat:", &mut audio.other_combat);
+            ui_add_volume_slider(ui, "NPC Sounds:", &mut audio.npc_sounds);
+            ui_add_volume_slider(ui, "Interface:", &mut audio.ui_sounds);
+        });
+}
+
+fn ui_interface_settings(ui: &mut egui::Ui, interface: &mut InterfaceSettings) {
+    egui::Grid::new("interface_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name Tags:");
+            ui.checkbox(&mut interface.show_character_names, "Show player names");
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut interface.show_npc_names, "Show NPC names");
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut interface.show_summon_names, "Show summon names");
+            ui.end_row();
+
+            ui.label("Name Tag Size:");
+            ui.add(
+                egui::Slider::new(&mut interface.name_tag_font_size, 10.0..=24.0).show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Chat Bubbles:");
+            ui.checkbox(&mut interface.show_chat_bubbles, "Enabled")
+                .on_hover_text("Show say and shout chat above the speaker");
+            ui.end_row();
+
+            ui.label("Zone Titles:");
+            ui.checkbox(&mut interface.show_zone_titles, "Enabled")
+                .on_hover_text("Show the name of zones and areas when entering them");
+            ui.end_row();
+
+            ui.label("Tooltip Delay:");
+            ui.add(
+                egui::Slider::new(&mut interface.tooltip_delay, 0.0..=2.0)
+                    .suffix("s")
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Input Display:");
+            ui.checkbox(&mut interface.show_input_display, "Enabled")
+                .on_hover_text("Show recently pressed keys and used skills");
+            ui.end_row();
+
+            ui.label("Input Display Position:");
+            ui.add_enabled_ui(interface.show_input_display, |ui| {
+                egui::ComboBox::from_id_source("input_display_position")
+                    .selected_text(input_display_position_name(
+                        interface.input_display_position,
+                    ))
+                    .show_ui(ui, |ui| {
+                        for position in INPUT_DISPLAY_POSITIONS {
+                            ui.selectable_value(
+                                &mut interface.input_display_position,
+                                position,
+                                input_display_position_name(position),
+                            );
+                        }
+                    });
+            });
+            ui.end_row();
+
+            ui.label("Input Display Fade:");
+            ui.add_enabled(
+                interface.show_input_display,
+                egui::Slider::new(&mut interface.input_display_fade_time, 0.5..=10.0)
+                    .suffix("s")
+                    .show_value(true),
+            );
+            ui.end_row();
+        });
+}
+
+fn ui_controls_settings(ui: &mut egui::Ui, controls: &mut ControlsSettings) {
+    egui::Grid::new("controls_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Cinematic Orbit Speed:");
+            ui.add(
+                egui::Slider::new(&mut controls.cinematic_orbit_speed, -90.0..=90.0)
+                    .suffix("°/s")
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Cinematic Orbit Radius:");
+            ui.add(
+                egui::Slider::new(&mut controls.cinematic_orbit_radius, 2.0..=50.0)
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Idle Camera After:")
+                .on_hover_text("Slowly orbit the camera and hide the UI when idle, 0 disables");
+            ui.add(
+                egui::Slider::new(&mut controls.idle_camera_minutes, 0..=60)
+                    .suffix(" min")
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Interact Key:")
+                .on_hover_text("Talk to NPCs, harvest and enter warp gates");
+            egui::ComboBox::from_id_source("interact_key")
+                .selected_text(controls.interact_key.name())
+                .show_ui(ui, |ui| {
+                    for key in INTERACT_KEYS {
+                        ui.selectable_value(&mut controls.interact_key, key, key.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Mount Cast Time:");
+            ui.add(
+                egui::Slider::new(&mut controls.mount_cast_time, 0.5..=5.0)
+                    .suffix(" s")
+                    .show_value(true),
+            );
+            ui.end_row();
+
+            ui.label("Mount Vehicle:")
+                .on_hover_text("Choose the frame to mount with in the vehicle garage");
+            ui.horizontal(|ui| {
+                if controls.mount_vehicle_item.is_some() {
+                    ui.label("Set in vehicle garage");
+                    if ui.button("Clear").clicked() {
+                        controls.mount_vehicle_item = None;
+                    }
+                } else {
+                    ui.label("Equipped vehicle");
+                }
+            });
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.collapsing("Key Bindings", |ui| {
+        egui::Grid::new("controls_keybinds")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (key, action) in SETTINGS_KEYBINDS {
+                    ui.label(key);
+                    ui.label(action);
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn ui_social_settings(ui: &mut egui::Ui, social: &mut SocialSettings) {
+    egui::Grid::new("social_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Other Players:");
+            ui.checkbox(&mut social.hide_other_players, "Hide (F12)")
+                .on_hover_text("Party members and personal stores are still shown");
+            ui.end_row();
+
+            ui.label("Trade Chat:");
+            ui.checkbox(&mut social.show_trade_in_all_tab, "Show in all tab");
+            ui.end_row();
+
+            ui.label("Clan Chat:");
+            ui.checkbox(&mut social.show_clan_in_all_tab, "Show in all tab");
+            ui.end_row();
+        });
+}
+
+fn ui_combat_settings(ui: &mut egui::Ui, combat: &mut CombatSettings) {
+    egui::Grid::new("combat_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Monster Names:");
+            ui.checkbox(&mut combat.show_monster_names, "Always show");
+            ui.end_row();
+
+            ui.label("Health Bars:");
+            ui.checkbox(
+                &mut combat.show_party_health,
+                "Always show for party members",
+            );
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut combat.show_clan_health, "Always show for clan members");
+            ui.end_row();
+
+            ui.label("Level Colours:");
+            ui.checkbox(&mut combat.simple_level_colors, "Simple")
+                .on_hover_text("Grey for weaker, green for similar and red for stronger monsters");
+            ui.end_row();
+        });
+}
+
+fn ui_accessibility_settings(ui: &mut egui::Ui, accessibility: &mut AccessibilitySettings) {
+    egui::Grid::new("accessibility_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Screen Reader:");
+            ui.checkbox(&mut accessibility.screen_reader_log, "Write events to log")
+                .on_hover_text(
+                    "Damage taken, level ups, whispers and tooltips are written to accessibility.log",
+                );
+            ui.end_row();
+
+            ui.label("Cursor:");
+            ui.checkbox(&mut accessibility.high_visibility_cursor, "High visibility");
+            ui.end_row();
+
+            ui.label("Target:");
+            ui.checkbox(&mut accessibility.high_visibility_target, "High visibility outline");
+            ui.end_row();
+
+            ui.label("Subtitles:");
+            ui.checkbox(&mut accessibility.subtitles, "Show chat and NPC dialogue");
+            ui.end_row();
+
+            ui.label("Subtitle Size:");
+            ui.add_enabled(
+                accessibility.subtitles,
+                egui::Slider::new(&mut accessibility.subtitle_font_size, 16.0..=40.0)
+                    .show_value(true),
+            );
+            ui.end_row();
+        });
+}
+
+fn ui_login_settings(ui: &mut egui::Ui, login: &mut LoginSettings) {
+    egui::Grid::new("login_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Passwords:");
+            ui.checkbox(&mut login.use_os_keychain, "Store in OS keychain")
+                .on_hover_text(
+                    "Remembered passwords and the config.toml account password are moved to the OS keychain",
+                );
+            ui.end_row();
+
+            ui.label("Game Data:");
+            ui.checkbox(
+                &mut login.refuse_mismatched_zone_data,
+                "Leave zones where it differs from the server",
+            )
+            .on_hover_text(
+                "When unticked a warning is shown instead, only servers which send zone data checksums can be checked",
+            );
+            ui.end_row();
+
+            ui.label("Remembered Account:");
+            ui.horizontal(|ui| {
+                if let Some(username) = login.remembered_username.as_ref() {
+                    ui.label(username);
+                    if ui
+                        .button("Forget")
+                        .on_hover_text("Log in with remember unticked to also remove the saved password")
+                        .clicked()
+                    {
+                        login.remembered_username = None;
+                    }
+                } else {
+                    ui.weak("None");
+                }
+            });
+            ui.end_row();
+        });
+}
+
 pub fn ui_settings_system(
     mut egui_context: EguiContexts,
     mut ui_state_windows: ResMut<UiStateWindows>,
     mut ui_state_settings: Local<UiStateSettings>,
-    mut sound_settings: ResMut<SoundSettings>,
-    mut query_sounds: Query<(&SoundCategory, &mut SoundGain)>,
+    mut client_settings: ResMut<ClientSettings>,
 ) {
+    let mut settings = client_settings.clone();
+
     egui::Window::new("Settings")
-        .open(&mut ui_state_windows.settings_open)
+        .open(ui_state_windows.open_mut(UiWindowId::SETTINGS))
         .resizable(false)
         .show(egui_context.ctx_mut(), |ui| {
             ui.horizontal(|ui| {
-                ui.selectable_value(&mut ui_state_settings.page, SettingsPage::Sound, "Sound");
+                for (page, text) in [
+                    (SettingsPage::Graphics, "Graphics"),
+                    (SettingsPage::Audio, "Audio"),
+                    (SettingsPage::Interface, "Interface"),
+                    (SettingsPage::Controls, "Controls"),
+                    (SettingsPage::Social, "Social"),
+                    (SettingsPage::Combat, "Combat"),
+                    (SettingsPage::Accessibility, "Accessibility"),
+                    (SettingsPage::Login, "Login"),
+                ] {
+                    ui.selectable_value(&mut ui_state_settings.page, page, text);
+                }
             });
+            ui.separator();
 
-            egui::Grid::new("sound_settings_gain")
-                .num_columns(2)
-                .show(ui, |ui| {
-                    let mut gain_changed = false;
-
-                    ui.label("Sound:");
-                    gain_changed |= ui
-                        .checkbox(&mut sound_settings.enabled, "Enabled")
-                        .changed();
-                    ui.end_row();
-
-                    ui.label("Global Volume:");
-                    gain_changed |= ui
-                        .add(
-                            egui::Slider::new(&mut sound_settings.global_gain, 0.0..=1.0)
-                                .show_value(true),
-                        )
-                        .changed();
-                    ui.end_row();
+            match ui_state_settings.page {
+                SettingsPage::Graphics => ui_graphics_settings(ui, &mut settings.graphics),
+                SettingsPage::Audio => ui_audio_settings(ui, &mut settings.audio),
+                SettingsPage::Interface => ui_interface_settings(ui, &mut settings.interface),
+                SettingsPage::Controls => ui_controls_settings(ui, &mut settings.controls),
+                SettingsPage::Social => ui_social_settings(ui, &mut settings.social),
+                SettingsPage::Combat => ui_combat_settings(ui, &mut settings.combat),
+                SettingsPage::Accessibility => {
+                    ui_accessibility_settings(ui, &mut settings.accessibility)
+                }
+                SettingsPage::Login => ui_login_settings(ui, &mut settings.login),
+            }
 
-                    let mut add_category_slider = |text: &str, category| {
-                        ui.label(text);
-                        gain_changed |= ui
-                            .add(
-                                egui::Slider::new(&mut sound_settings.gains[category], 0.0..=1.0)
-                                    .show_value(true),
-                            )
-                            .changed();
-                        ui.end_row();
-                    };
-
-                    add_category_slider("Background Music:", SoundCategory::BackgroundMusic);
-                    add_category_slider("Player Footsteps:", SoundCategory::PlayerFootstep);
-                    add_category_slider("Other Footsteps:", SoundCategory::OtherFootstep);
-                    add_category_slider("Player Combat:", SoundCategory::PlayerCombat);
-                    add_category_slider("Other Combat:", SoundCategory::OtherCombat);
-                    add_category_slider("NPC Sounds:", SoundCategory::NpcSounds);
-
-                    if gain_changed {
-                        for (category, mut gain) in query_sounds.iter_mut() {
-                            let target_gain = sound_settings.gain(*category);
-
-                            if target_gain != *gain {
-                                *gain = target_gain;
-                            }
-                        }
+            ui.separator();
+            if ui.button("Restore Defaults").clicked() {
+                match ui_state_settings.page {
+                    SettingsPage::Graphics => settings.graphics = GraphicsSettings::default(),
+                    SettingsPage::Audio => settings.audio = AudioSettings::default(),
+                    SettingsPage::Interface => settings.interface = InterfaceSettings::default(),
+                    SettingsPage::Controls => settings.controls = ControlsSettings::default(),
+                    SettingsPage::Social => settings.social = SocialSettings::default(),
+                    SettingsPage::Combat => settings.combat = CombatSettings::default(),
+                    SettingsPage::Accessibility => {
+                        settings.accessibility = AccessibilitySettings::default()
                     }
-                });
+                    // Keep the saved profiles, only the options are restored
+                    SettingsPage::Login => {
+                        settings.login.use_os_keychain = LoginSettings::default().use_os_keychain
+                    }
+                }
+            }
         });
+
+    if settings != *client_settings {
+        *client_settings = settings;
+    }
 }
@@ -1,24 +1,30 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Assets, EventWriter, Local, Query, Res, ResMut, With},
+    prelude::{Assets, EventWriter, Local, Query, Res, ResMut, With, World},
 };
 use bevy_egui::{egui, EguiContexts};
 
-use rose_data::{AbilityType, SkillId};
+use rose_data::{AbilityType, SkillData, SkillId};
 use rose_data_irose::{IroseSkillPageType, SKILL_PAGE_SIZE};
-use rose_game_common::components::{CharacterInfo, SkillList, SkillPoints, SkillSlot};
+use rose_game_common::components::{
+    CharacterInfo, Hotbar, HotbarSlot, SkillList, SkillPoints, SkillSlot,
+};
 use rose_game_common::messages::client::ClientMessage;
 
 use crate::{
     bundles::ability_values_get_value,
     components::{Cooldowns, PlayerCharacter},
-    events::PlayerCommandEvent,
-    resources::{GameConnection, GameData, UiResources},
+    events::{MessageBoxEvent, PlayerCommandEvent},
+    protocol::{ExtendedClientMessage, ProtocolCapabilities, SkillRefund},
+    resources::{ClientCapabilities, GameConnection, GameData, UiResources},
     ui::{
-        tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem, SkillTooltipType},
+        tooltips::{
+            get_skill_refund_points, PlayerTooltipQuery, PlayerTooltipQueryItem, SkillTooltipType,
+        },
         ui_add_skill_tooltip,
         widgets::{DataBindings, Dialog, DrawText, Widget},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateDragAndDrop,
+        UiStateWindows, UiWindowId,
     },
 };
 
@@ -80,49 +86,30 @@ struct SkillUpButtonLayout {
     plus_height: f32,
 }
 
-fn can_level_up_skill_now(
+// Job, skill and ability requirements, shared with the skill tree
+pub fn check_skill_requirements(
     game_data: &GameData,
-    player: &PlayerQueryItem,
+    character_info: &CharacterInfo,
+    skill_list: &SkillList,
     player_tooltip_data: Option<&PlayerTooltipQueryItem>,
-    skill_slot: SkillSlot,
-    current_skill_id: SkillId,
-) -> Result<SkillId, &'static str> {
-    let current_skill_data = game_data
-        .skills
-        .get_skill(current_skill_id)
-        .ok_or("No current skill data")?;
-    let next_skill_id = SkillId::new(current_skill_id.get() + 1).ok_or("At max level")?;
-    let next_skill_data = game_data
-        .skills
-        .get_skill(next_skill_id)
-        .ok_or("At max level")?;
-
-    if next_skill_data.base_skill_id != current_skill_data.base_skill_id
-        || next_skill_data.level != current_skill_data.level + 1
-    {
-        return Err("At max level");
-    }
-
-    if player.skill_points.points < next_skill_data.learn_point_cost {
-        return Err("Not enough skill points");
-    }
-
-    if let Some(job_class_id) = next_skill_data.required_job_class {
+    skill_data: &SkillData,
+) -> Result<(), &'static str> {
+    if let Some(job_class_id) = skill_data.required_job_class {
         if let Some(job_class) = game_data.job_class.get(job_class_id) {
             if !job_class
                 .jobs
-                .contains(&rose_data::JobId::new(player.character_info.job))
+                .contains(&rose_data::JobId::new(character_info.job))
             {
                 return Err("Job requirement not met");
             }
         }
     }
 
-    for &(required_skill_id, required_level) in next_skill_data.required_skills.iter() {
+    for &(required_skill_id, required_level) in skill_data.required_skills.iter() {
         if let Some(required_skill_data) = game_data.skills.get_skill(
             SkillId::new(required_skill_id.get() + required_level.max(1) as u16 - 1).unwrap(),
         ) {
-            let Some((_, _, skill_level)) = player.skill_list.find_skill_level(
+            let Some((_, _, skill_level)) = skill_list.find_skill_level(
                 &game_data.skills,
                 required_skill_data
                     .base_skill_id
@@ -138,7 +125,7 @@ fn can_level_up_skill_now(
     }
 
     if let Some(player_tooltip_data) = player_tooltip_data {
-        for &(ability_type, required_value) in next_skill_data.required_ability.iter() {
+        for &(ability_type, required_value) in skill_data.required_ability.iter() {
             let Some(current_value) = ability_values_get_value(
                 ability_type,
                 player_tooltip_data.ability_values,
@@ -164,7 +151,47 @@ fn can_level_up_skill_now(
         }
     }
 
-    if player.skill_list.get_skill(skill_slot) != Some(current_skill_id) {
+    Ok(())
+}
+
+pub fn can_level_up_skill_now(
+    game_data: &GameData,
+    character_info: &CharacterInfo,
+    skill_list: &SkillList,
+    skill_points: &SkillPoints,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    skill_slot: SkillSlot,
+    current_skill_id: SkillId,
+) -> Result<SkillId, &'static str> {
+    let current_skill_data = game_data
+        .skills
+        .get_skill(current_skill_id)
+        .ok_or("No current skill data")?;
+    let next_skill_id = SkillId::new(current_skill_id.get() + 1).ok_or("At max level")?;
+    let next_skill_data = game_data
+        .skills
+        .get_skill(next_skill_id)
+        .ok_or("At max level")?;
+
+    if next_skill_data.base_skill_id != current_skill_data.base_skill_id
+        || next_skill_data.level != current_skill_data.level + 1
+    {
+        return Err("At max level");
+    }
+
+    if skill_points.points < next_skill_data.learn_point_cost {
+        return Err("Not enough skill points");
+    }
+
+    check_skill_requirements(
+        game_data,
+        character_info,
+        skill_list,
+        player_tooltip_data,
+        next_skill_data,
+    )?;
+
+    if skill_list.get_skill(skill_slot) != Some(current_skill_id) {
         return Err("Invalid skill slot");
     }
 
@@ -237,6 +264,8 @@ fn parse_skill_up_button_layout(
     })
 }
 
+// Returns the skill to refund when chosen from the context menu of the slot
+#[allow(clippy::too_many_arguments)]
 fn ui_add_skill_list_slot(
     ui: &mut egui::Ui,
     pos: egui::Pos2,
@@ -247,7 +276,8 @@ fn ui_add_skill_list_slot(
     ui_resources: &UiResources,
     ui_state_dnd: &mut UiStateDragAndDrop,
     player_command_events: &mut EventWriter<PlayerCommandEvent>,
-) {
+    can_refund: bool,
+) -> Option<SkillId> {
     let skill = player.skill_list.get_skill(skill_slot);
     let mut dropped_item = None;
     let response = ui
@@ -276,8 +306,29 @@ fn ui_add_skill_list_slot(
         player_command_events.send(PlayerCommandEvent::UseSkill(skill_slot));
     }
 
+    let mut refund_skill_id = None;
     if let Some(skill_id) = skill {
-        response.on_hover_ui(|ui| {
+        let refund_points = game_data
+            .skills
+            .get_skill(skill_id)
+            .map_or(0, |skill_data| {
+                get_skill_refund_points(game_data, skill_data)
+            });
+        let response = if can_refund && refund_points > 0 {
+            response.context_menu(|ui| {
+                if ui
+                    .button(format!("Refund {} skill points", refund_points))
+                    .clicked()
+                {
+                    refund_skill_id = Some(skill_id);
+                    ui.close_menu();
+                }
+            })
+        } else {
+            response
+        };
+
+        response.on_hover_tooltip_ui(|ui| {
             let extra = ui.input(|input| input.pointer.secondary_down());
             ui_add_skill_tooltip(
                 ui,
@@ -290,13 +341,42 @@ fn ui_add_skill_list_slot(
                 player_tooltip_data,
                 skill_id,
             );
+
+            for (page, index) in skill_hotbar_locations(player.hotbar, skill_slot) {
+                ui.weak(format!("Hotbar page {}, F{}", page + 1, index + 1));
+            }
         });
     }
+    refund_skill_id
+}
+
+fn skill_hotbar_locations(
+    hotbar: &Hotbar,
+    skill_slot: SkillSlot,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    hotbar
+        .pages
+        .iter()
+        .enumerate()
+        .flat_map(move |(page, slots)| {
+            slots
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, slot)| match slot {
+                    Some(HotbarSlot::Skill(hotbar_skill_slot))
+                        if *hotbar_skill_slot == skill_slot =>
+                    {
+                        Some((page, index))
+                    }
+                    _ => None,
+                })
+        })
 }
 
 #[derive(WorldQuery)]
 pub struct PlayerQuery<'w> {
     character_info: &'w CharacterInfo,
+    hotbar: &'w Hotbar,
     skill_list: &'w SkillList,
     skill_points: &'w SkillPoints,
     cooldowns: &'w Cooldowns,
@@ -309,12 +389,14 @@ pub fn ui_skill_list_system(
     mut ui_state_windows: ResMut<UiStateWindows>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
+    mut message_box_events: EventWriter<MessageBoxEvent>,
     query_player: Query<PlayerQuery, With<PlayerCharacter>>,
     query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
     game_connection: Option<Res<GameConnection>>,
+    client_capabilities: Res<ClientCapabilities>,
 ) {
     let ui_state_skill_list = &mut *ui_state_skill_list;
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_skill_list) {
@@ -329,6 +411,16 @@ pub fn ui_skill_list_system(
         return;
     };
     let player_tooltip_data = query_player_tooltip.get_single().ok();
+    let reset_item_slot = player_tooltip_data
+        .as_ref()
+        .and_then(|player_tooltip_data| {
+            game_data
+                .skill_reset_items
+                .find_reset_item(player_tooltip_data.inventory)
+        });
+    let can_refund = reset_item_slot.is_some()
+        && client_capabilities.supports(ProtocolCapabilities::SKILL_REFUNDS);
+    let mut refund_skill_id = None;
     let skill_up_layout = parse_skill_up_button_layout(dialog, &ui_resources);
     let plus_normal_sprite = ui_resources.get_sprite(0, "UI09_BTN_PLUS_NORMAL");
     let plus_over_sprite = ui_resources.get_sprite(0, "UI09_BTN_PLUS_OVER");
@@ -353,7 +445,7 @@ pub fn ui_skill_list_system(
 
     let window_response = egui::Window::new("Skills")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.skill_list_open)
+        .open(ui_state_windows.open_mut(UiWindowId::SKILL_LIST))
         .title_bar(false)
         .resizable(false)
         .default_width(dialog.width)
@@ -533,7 +625,9 @@ pub fn ui_skill_list_system(
                         if let Some(current_skill_id) = skill {
                             let can_level_up_result = can_level_up_skill_now(
                                 &game_data,
-                                &player,
+                                player.character_info,
+                                player.skill_list,
+                                player.skill_points,
                                 player_tooltip_data.as_ref(),
                                 skill_slot,
                                 current_skill_id,
@@ -630,10 +724,10 @@ pub fn ui_skill_list_system(
 
                             if can_level_up {
                                 response_upgrade_button =
-                                    response_upgrade_button.on_hover_text("Up");
+                                    response_upgrade_button.on_hover_tooltip_text("Up");
                             } else if let Some(reason) = disabled_reason {
                                 response_upgrade_button =
-                                    response_upgrade_button.on_hover_text(reason);
+                                    response_upgrade_button.on_hover_tooltip_text(reason);
                             }
 
                             if can_level_up && response_upgrade_button.clicked() {
@@ -646,7 +740,7 @@ pub fn ui_skill_list_system(
                             }
                         }
 
-                        ui_add_skill_list_slot(
+                        if let Some(skill_id) = ui_add_skill_list_slot(
                             ui,
                             dialog_screen_origin + egui::vec2(start_x, start_y + 3.0),
                             skill_slot,
@@ -656,7 +750,10 @@ pub fn ui_skill_list_system(
                             &ui_resources,
                             &mut ui_state_dnd,
                             &mut player_command_events,
-                        );
+                            can_refund,
+                        ) {
+                            refund_skill_id = Some(skill_id);
+                        }
                     }
 
                     ui.add_label_at(
@@ -715,11 +812,41 @@ pub fn ui_skill_list_system(
         }
     }
 
+    if let (Some(skill_id), Some(item_slot)) = (refund_skill_id, reset_item_slot) {
+        let skill_data = game_data.skills.get_skill(skill_id);
+        let refund_points = skill_data.map_or(0, |skill_data| {
+            get_skill_refund_points(&game_data, skill_data)
+        });
+        message_box_events.send(MessageBoxEvent::Show {
+            message: format!(
+                "Use a skill reset item to refund {} skill points from {}?",
+                refund_points,
+                skill_data.map_or("this skill", |skill_data| skill_data.name)
+            ),
+            modal: true,
+            ok: Some(Box::new(move |commands| {
+                // The server consumes the reset item and sends the updated skills
+                commands.add(move |world: &mut World| {
+                    if let Some(game_connection) = world.get_resource::<GameConnection>() {
+                        game_connection
+                            .extended_client_message_tx
+                            .send(ExtendedClientMessage::SkillRefund(SkillRefund {
+                                item_slot,
+                                skill_id,
+                            }))
+                            .ok();
+                    }
+                });
+            })),
+            cancel: Some(Box::new(|_| {})),
+        });
+    }
+
     if response_skill_tree_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.skill_tree_open = !ui_state_windows.skill_tree_open;
+        ui_state_windows.toggle(UiWindowId::SKILL_TREE);
     }
 
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.skill_list_open = false;
+        ui_state_windows.set_open(UiWindowId::SKILL_LIST, false);
     }
 }
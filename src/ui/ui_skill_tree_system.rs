@@ -5,16 +5,20 @@ use bevy::{
 use bevy_egui::{egui, EguiContexts};
 
 use rose_data::SkillId;
-use rose_game_common::components::{CharacterInfo, SkillList, SkillPoints};
+use rose_game_common::{
+    components::{CharacterInfo, SkillList, SkillPoints, SkillSlot},
+    messages::client::ClientMessage,
+};
 
 use crate::{
     components::PlayerCharacter,
-    resources::{GameData, UiResources, UiSpriteSheetType},
+    resources::{GameConnection, GameData, UiResources, UiSpriteSheetType},
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem, SkillTooltipType},
         ui_add_skill_tooltip,
+        ui_skill_list_system::{can_level_up_skill_now, check_skill_requirements},
         widgets::{DataBindings, Dialog, DrawWidget, Skill, Widget},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, TooltipExt, UiSoundEvent, UiStateWindows, UiWindowId,
     },
 };
 
@@ -31,42 +35,110 @@ pub struct UiStateSkillTree {
     skill_tree: Option<(u16, Dialog)>, // (job id, dialog)
 }
 
+const SKILL_TREE_SLOT_SIZE: f32 = 40.0;
+
+const SKILL_TREE_EDGE_MET_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 200, 80);
+const SKILL_TREE_EDGE_UNMET_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 90, 90);
+
+#[derive(Clone, Copy)]
+enum SkillTreeNodeState {
+    Learned {
+        level: u32,
+        level_up: Result<SkillSlot, &'static str>,
+    },
+    Learnable,
+    Unlearnable(&'static str),
+}
+
+fn skill_tree_slot_rect(ui: &egui::Ui, skill: &Skill) -> egui::Rect {
+    egui::Rect::from_min_size(
+        ui.min_rect().min + egui::vec2(skill.x + 3.0, skill.y + 3.0),
+        egui::vec2(SKILL_TREE_SLOT_SIZE, SKILL_TREE_SLOT_SIZE),
+    )
+}
+
+fn get_skill_tree_node_state(
+    skill: &Skill,
+    base_skill_id: SkillId,
+    player: &PlayerQueryItem,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    game_data: &GameData,
+) -> SkillTreeNodeState {
+    if let Some((skill_slot, current_skill_id, level)) = player
+        .skill_list
+        .find_skill_level(&game_data.skills, base_skill_id)
+    {
+        // Each node covers the levels from skill.level up to skill.limit_level
+        if level + 1 >= skill.level {
+            let level_up = if skill.limit_level > 0 && level >= skill.limit_level {
+                Err("At max level")
+            } else {
+                can_level_up_skill_now(
+                    game_data,
+                    player.character_info,
+                    player.skill_list,
+                    player.skill_points,
+                    player_tooltip_data,
+                    skill_slot,
+                    current_skill_id,
+                )
+                .map(|_| skill_slot)
+            };
+
+            if level >= skill.level {
+                return SkillTreeNodeState::Learned { level, level_up };
+            }
+
+            return match level_up {
+                Ok(_) => SkillTreeNodeState::Learned { level, level_up },
+                Err(reason) => SkillTreeNodeState::Unlearnable(reason),
+            };
+        }
+    }
+
+    let Some(skill_data) = game_data.skills.get_skill(base_skill_id) else {
+        return SkillTreeNodeState::Unlearnable("No skill data");
+    };
+
+    match check_skill_requirements(
+        game_data,
+        player.character_info,
+        player.skill_list,
+        player_tooltip_data,
+        skill_data,
+    ) {
+        Ok(()) => SkillTreeNodeState::Learnable,
+        Err(reason) => SkillTreeNodeState::Unlearnable(reason),
+    }
+}
+
 fn ui_add_skill_tree_slot(
     ui: &mut egui::Ui,
-    pos: egui::Pos2,
     skill: &Skill,
     player: &PlayerQueryItem,
     player_tooltip_data: Option<&PlayerTooltipQueryItem>,
     game_data: &GameData,
     ui_resources: &UiResources,
+    level_up_skill_slot: &mut Option<SkillSlot>,
 ) -> egui::Response {
+    let rect = skill_tree_slot_rect(ui, skill);
     let base_skill_id = if let Some(base_skill_id) = SkillId::new(skill.id as u16) {
         base_skill_id
     } else {
-        return ui
-            .allocate_ui_at_rect(
-                egui::Rect::from_min_size(
-                    ui.min_rect().min + pos.to_vec2(),
-                    egui::vec2(40.0, 40.0),
-                ),
-                |_| {},
-            )
-            .response;
+        return ui.allocate_ui_at_rect(rect, |_| {}).response;
     };
 
-    let learned_level = if let Some((_, _, level)) = player
-        .skill_list
-        .find_skill_level(&game_data.skills, base_skill_id)
-    {
-        if level < skill.level {
-            None
-        } else if skill.limit_level > 0 {
-            Some(level.min(skill.limit_level))
-        } else {
-            Some(level)
+    let state =
+        get_skill_tree_node_state(skill, base_skill_id, player, player_tooltip_data, game_data);
+    let learned_level = match state {
+        SkillTreeNodeState::Learned { level, .. } if level >= skill.level => {
+            if skill.limit_level > 0 {
+                Some(level.min(skill.limit_level))
+            } else {
+                Some(level)
+            }
         }
-    } else {
-        None
+        _ => None,
     };
 
     let skill_data = game_data.skills.get_skill(
@@ -79,38 +151,57 @@ fn ui_add_skill_tree_slot(
     let mut dragged_item = None;
     let mut dropped_item = None;
     let response = ui
-        .allocate_ui_at_rect(
-            egui::Rect::from_min_size(ui.min_rect().min + pos.to_vec2(), egui::vec2(40.0, 40.0)),
-            |ui| {
-                egui::Widget::ui(
-                    DragAndDropSlot::new(
-                        DragAndDropId::NotDraggable,
-                        sprite,
-                        None,
-                        false,
-                        None,
-                        if learned_level.is_some() {
-                            None
-                        } else {
-                            Some(1.0)
-                        },
-                        |_| false,
-                        &mut dragged_item,
-                        &mut dropped_item,
-                        [40.0, 40.0],
-                    ),
-                    ui,
-                )
-            },
-        )
+        .allocate_ui_at_rect(rect, |ui| {
+            egui::Widget::ui(
+                DragAndDropSlot::new(
+                    DragAndDropId::NotDraggable,
+                    sprite,
+                    None,
+                    false,
+                    None,
+                    // Skills which cannot be learned yet are greyed out
+                    if matches!(state, SkillTreeNodeState::Unlearnable(_)) {
+                        Some(1.0)
+                    } else {
+                        None
+                    },
+                    |_| false,
+                    &mut dragged_item,
+                    &mut dropped_item,
+                    [SKILL_TREE_SLOT_SIZE, SKILL_TREE_SLOT_SIZE],
+                ),
+                ui,
+            )
+        })
         .inner;
 
-    if response.double_clicked() {
-        // player_command_events.send(PlayerCommandEvent::UseSkill(skill_slot));
+    let can_level_up = matches!(
+        state,
+        SkillTreeNodeState::Learned {
+            level_up: Ok(_),
+            ..
+        }
+    );
+    if can_level_up {
+        ui.painter()
+            .rect_stroke(rect, 2.0, egui::Stroke::new(2.0, SKILL_TREE_EDGE_MET_COLOR));
+    } else if matches!(state, SkillTreeNodeState::Learnable) {
+        ui.painter()
+            .rect_stroke(rect, 2.0, egui::Stroke::new(1.0, egui::Color32::YELLOW));
+    }
+
+    if let SkillTreeNodeState::Learned {
+        level_up: Ok(skill_slot),
+        ..
+    } = state
+    {
+        if response.clicked() {
+            *level_up_skill_slot = Some(skill_slot);
+        }
     }
 
     if let Some(skill_data) = skill_data {
-        response.on_hover_ui(|ui| {
+        response.on_hover_tooltip_ui(|ui| {
             ui_add_skill_tooltip(
                 ui,
                 SkillTooltipType::Extra,
@@ -118,42 +209,94 @@ fn ui_add_skill_tree_slot(
                 player_tooltip_data,
                 skill_data.id,
             );
+
+            ui.separator();
+            match state {
+                SkillTreeNodeState::Learned {
+                    level_up: Ok(_), ..
+                } => {
+                    ui.colored_label(SKILL_TREE_EDGE_MET_COLOR, "Click to level up");
+                }
+                SkillTreeNodeState::Learned {
+                    level_up: Err(reason),
+                    ..
+                } => {
+                    ui.weak(reason);
+                }
+                SkillTreeNodeState::Learnable => {
+                    ui.colored_label(egui::Color32::YELLOW, "Requirements met, not learned yet");
+                }
+                SkillTreeNodeState::Unlearnable(reason) => {
+                    ui.colored_label(egui::Color32::RED, reason);
+                }
+            }
         })
     } else {
         response
     }
 }
 
-fn draw_skill_slots(
-    ui: &mut egui::Ui,
+fn collect_skill_tree_nodes<'a>(widgets: &'a [Widget], nodes: &mut Vec<&'a Skill>) {
+    for widget in widgets.iter() {
+        if let Widget::Skill(skill) = widget {
+            nodes.push(skill);
+            collect_skill_tree_nodes(&skill.widgets, nodes);
+        }
+    }
+}
+
+// Connect each skill to the skills it requires, coloured by whether the required level is met
+fn draw_skill_tree_edges(
+    ui: &egui::Ui,
+    nodes: &[&Skill],
     player: &PlayerQueryItem,
-    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
     game_data: &GameData,
-    ui_resources: &UiResources,
-    widgets: &[Widget],
 ) {
-    for skill in widgets.iter().filter_map(|x| match x {
-        Widget::Skill(s) => Some(s),
-        _ => None,
-    }) {
-        ui_add_skill_tree_slot(
-            ui,
-            egui::pos2(skill.x + 3.0, skill.y + 3.0),
-            skill,
-            player,
-            player_tooltip_data,
-            game_data,
-            ui_resources,
-        );
-
-        draw_skill_slots(
-            ui,
-            player,
-            player_tooltip_data,
-            game_data,
-            ui_resources,
-            &skill.widgets,
-        );
+    for node in nodes.iter() {
+        let Some(skill_data) =
+            SkillId::new(node.id as u16).and_then(|skill_id| game_data.skills.get_skill(skill_id))
+        else {
+            continue;
+        };
+
+        for &(required_skill_id, required_level) in skill_data.required_skills.iter() {
+            // A skill can have several nodes, prefer the one showing the required level
+            let required_level = required_level as u32;
+            let mut required_nodes = nodes
+                .iter()
+                .filter(|required_node| required_node.id == required_skill_id.get() as u32);
+            let Some(required_node) = required_nodes
+                .clone()
+                .find(|required_node| {
+                    required_node.level <= required_level
+                        && (required_node.limit_level == 0
+                            || required_level <= required_node.limit_level)
+                })
+                .or_else(|| required_nodes.next())
+            else {
+                continue;
+            };
+
+            let met = player
+                .skill_list
+                .find_skill_level(&game_data.skills, required_skill_id)
+                .map_or(false, |(_, _, level)| level >= required_level);
+
+            ui.painter().line_segment(
+                [
+                    skill_tree_slot_rect(ui, required_node).center(),
+                    skill_tree_slot_rect(ui, node).center(),
+                ],
+                egui::Stroke::new(
+                    2.0,
+                    if met {
+                        SKILL_TREE_EDGE_MET_COLOR
+                    } else {
+                        SKILL_TREE_EDGE_UNMET_COLOR
+                    },
+                ),
+            );
+        }
     }
 }
 
@@ -174,6 +317,7 @@ pub fn ui_skill_tree_system(
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
+    game_connection: Option<Res<GameConnection>>,
 ) {
     let ui_state = &mut *ui_state;
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_skill_tree) {
@@ -216,10 +360,11 @@ pub fn ui_skill_tree_system(
 
     let mut response_close_button = None;
     let mut select_base_skill_index = None;
+    let mut level_up_skill_slot = None;
 
     egui::Window::new("Skill Tree")
         .frame(egui::Frame::none())
-        .open(&mut ui_state_windows.skill_tree_open)
+        .open(ui_state_windows.open_mut(UiWindowId::SKILL_TREE))
         .title_bar(false)
         .resizable(false)
         .default_size([dialog.width, dialog.height])
@@ -238,37 +383,49 @@ pub fn ui_skill_tree_system(
                     ..Default::default()
                 },
                 |ui, bindings| {
-                    // Draw all base skill icons
+                    // Draw all base skill icons, clicking another base skill selects its job line
                     for (index, widget) in skill_tree.widgets.iter().enumerate() {
                         if let Widget::Skill(base_skill) = widget {
+                            let mut base_level_up_skill_slot = None;
                             if ui_add_skill_tree_slot(
                                 ui,
-                                egui::pos2(base_skill.x + 3.0, base_skill.y + 3.0),
                                 base_skill,
                                 &player,
                                 player_tooltip_data.as_ref(),
                                 &game_data,
                                 &ui_resources,
+                                &mut base_level_up_skill_slot,
                             )
                             .clicked()
-                                && index != 0
                             {
-                                select_base_skill_index = Some(index);
+                                if index != 0 {
+                                    select_base_skill_index = Some(index);
+                                } else {
+                                    level_up_skill_slot = base_level_up_skill_slot;
+                                }
                             }
                         }
                     }
 
-                    // Draw only background & children of selected base skill
+                    // Draw only background, prerequisites & children of selected base skill
                     if let Some(Widget::Skill(base_skill)) = skill_tree.widgets.get(0) {
                         base_skill.draw_widget(ui, bindings);
-                        draw_skill_slots(
-                            ui,
-                            &player,
-                            player_tooltip_data.as_ref(),
-                            &game_data,
-                            &ui_resources,
-                            &base_skill.widgets,
-                        );
+
+                        let mut nodes = vec![base_skill];
+                        collect_skill_tree_nodes(&base_skill.widgets, &mut nodes);
+                        draw_skill_tree_edges(ui, &nodes, &player, &game_data);
+
+                        for node in nodes.iter().skip(1) {
+                            ui_add_skill_tree_slot(
+                                ui,
+                                node,
+                                &player,
+                                player_tooltip_data.as_ref(),
+                                &game_data,
+                                &ui_resources,
+                                &mut level_up_skill_slot,
+                            );
+                        }
                     }
                 },
             );
@@ -294,7 +451,16 @@ pub fn ui_skill_tree_system(
         }
     }
 
+    if let Some(skill_slot) = level_up_skill_slot {
+        if let Some(game_connection) = game_connection.as_ref() {
+            game_connection
+                .client_message_tx
+                .send(ClientMessage::LevelUpSkill { skill_slot })
+                .ok();
+        }
+    }
+
     if response_close_button.map_or(false, |r| r.clicked()) {
-        ui_state_windows.skill_tree_open = false;
+        ui_state_windows.set_open(UiWindowId::SKILL_TREE, false);
     }
 }
@@ -0,0 +1,175 @@
+use bevy::prelude::{EventWriter, Local, Query, Res, ResMut, With, Without};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntity, ClientEntityName, ClientEntityType, PlayerCharacter, Position},
+    events::ReportPlayerEvent,
+    protocol::ProtocolCapabilities,
+    resources::{ClientCapabilities, IgnoreList},
+    ui::{UiStateWindows, UiWindowId},
+};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum SocialPage {
+    Nearby,
+    Ignored,
+}
+
+pub struct UiStateSocial {
+    page: SocialPage,
+    ignore_name: String,
+}
+
+impl Default for UiStateSocial {
+    fn default() -> Self {
+        Self {
+            page: SocialPage::Nearby,
+            ignore_name: String::new(),
+        }
+    }
+}
+
+pub fn ui_social_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateSocial>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ignore_list: ResMut<IgnoreList>,
+    query_player: Query<&Position, With<PlayerCharacter>>,
+    query_characters: Query<
+        (&ClientEntity, &ClientEntityName, &Position),
+        Without<PlayerCharacter>,
+    >,
+    mut report_player_events: EventWriter<ReportPlayerEvent>,
+    client_capabilities: Res<ClientCapabilities>,
+) {
+    let ui_state = &mut *ui_state;
+    let mut add_ignore = None;
+    let mut remove_ignore = None;
+    let mut report_player = None;
+    let can_report = client_capabilities.supports(ProtocolCapabilities::PLAYER_REPORTS);
+
+    egui::Window::new("Social")
+        .open(ui_state_windows.open_mut(UiWindowId::SOCIAL))
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut ui_state.page, SocialPage::Nearby, "Nearby");
+                ui.selectable_value(&mut ui_state.page, SocialPage::Ignored, "Ignored");
+            });
+            ui.separator();
+
+            match ui_state.page {
+                SocialPage::Nearby => {
+                    let player_position = query_player.get_single().ok();
+                    let mut nearby: Vec<(&ClientEntityName, f32)> = query_characters
+                        .iter()
+                        .filter(|(client_entity, _, _)| {
+                            matches!(client_entity.entity_type, ClientEntityType::Character)
+                        })
+                        .map(|(_, name, position)| {
+                            let distance = player_position.map_or(0.0, |player_position| {
+                                player_position.position.distance(position.position)
+                            });
+                            (name, distance)
+                        })
+                        .collect();
+                    nearby.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+                    if nearby.is_empty() {
+                        ui.label("There are no other players nearby");
+                    }
+
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("social_nearby_grid")
+                                .num_columns(3)
+                                .show(ui, |ui| {
+                                    for (name, distance) in nearby {
+                                        ui.add(
+                                            egui::Label::new(name.as_str())
+                                                .sense(egui::Sense::click()),
+                                        )
+                                        .on_hover_text("Right click for more options")
+                                        .context_menu(
+                                            |ui| {
+                                                if ui
+                                                    .add_enabled(
+                                                        can_report,
+                                                        egui::Button::new("Report..."),
+                                                    )
+                                                    .on_disabled_hover_text(
+                                                        "This server does not support reports.",
+                                                    )
+                                                    .clicked()
+                                                {
+                                                    report_player = Some(name.to_string());
+                                                    ui.close_menu();
+                                                }
+                                            },
+                                        );
+                                        ui.label(format!("{:.0} m", distance / 100.0));
+
+                                        if ignore_list.is_ignored(name.as_str()) {
+                                            if ui.button("Unignore").clicked() {
+                                                remove_ignore = Some(name.to_string());
+                                            }
+                                        } else if ui.button("Ignore").clicked() {
+                                            add_ignore = Some(name.to_string());
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+                SocialPage::Ignored => {
+                    ui.label("Chat, whispers and invites from ignored players are hidden");
+
+                    ui.horizontal(|ui| {
+                        let response = ui.text_edit_singleline(&mut ui_state.ignore_name);
+                        let submitted = response.lost_focus()
+                            && ui.input(|input| input.key_pressed(egui::Key::Enter));
+
+                        if (ui.button("Add").clicked() || submitted)
+                            && !ui_state.ignore_name.trim().is_empty()
+                        {
+                            add_ignore = Some(std::mem::take(&mut ui_state.ignore_name));
+                        }
+                    });
+
+                    if ignore_list.names.is_empty() {
+                        ui.label("You are not ignoring anyone");
+                    }
+
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("social_ignored_grid")
+                                .num_columns(2)
+                                .show(ui, |ui| {
+                                    for name in ignore_list.names.iter() {
+                                        ui.label(name.as_str());
+
+                                        if ui.button("Remove").clicked() {
+                                            remove_ignore = Some(name.clone());
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+            }
+        });
+
+    if let Some(name) = add_ignore {
+        ignore_list.add(&name);
+    }
+
+    if let Some(name) = remove_ignore {
+        ignore_list.remove(&name);
+    }
+
+    if let Some(name) = report_player {
+        report_player_events.send(ReportPlayerEvent { name });
+    }
+}
@@ -12,6 +12,7 @@ use rose_game_common::components::StatusEffects;
 use crate::{
     components::PlayerCharacter,
     resources::{GameData, UiResources, UiSpriteSheetType},
+    ui::format::format_duration,
 };
 
 #[derive(WorldQuery)]
@@ -74,9 +75,9 @@ pub fn ui_status_effects_system(
                                 if response.hovered() {
                                     if let Some(remaining_time) = remaining_time {
                                         response.on_hover_text(format!(
-                                            "{}\n\nTime Remaining: {} seconds",
+                                            "{}\n\nTime Remaining: {}",
                                             status_effect_data.name,
-                                            remaining_time.as_secs()
+                                            format_duration(remaining_time)
                                         ));
                                     } else {
                                         response.on_hover_text(status_effect_data.name);
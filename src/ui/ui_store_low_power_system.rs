@@ -0,0 +1,48 @@
+use bevy::prelude::{Query, ResMut, With};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{PersonalStore, PlayerCharacter},
+    resources::StoreLowPowerMode,
+    ui::format::format_signed_number,
+};
+
+// Status widget shown in place of the game UI while the store is in low power mode
+pub fn ui_store_low_power_system(
+    mut egui_context: EguiContexts,
+    mut store_low_power_mode: ResMut<StoreLowPowerMode>,
+    query_player: Query<&PersonalStore, With<PlayerCharacter>>,
+) {
+    if !store_low_power_mode.active {
+        return;
+    }
+
+    egui::CentralPanel::default().show(egui_context.ctx_mut(), |ui| {
+        if let Ok(personal_store) = query_player.get_single() {
+            ui.heading(&personal_store.title);
+        }
+        ui.label(format!(
+            "Sales this session: {} Zuly",
+            format_signed_number(store_low_power_mode.total_sales)
+        ));
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 32.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                if store_low_power_mode.recent_sales.is_empty() {
+                    ui.weak("No sales yet.");
+                }
+
+                for sale in store_low_power_mode.recent_sales.iter() {
+                    ui.label(sale);
+                }
+            });
+
+        ui.separator();
+        if ui.button("Restore").clicked() {
+            store_low_power_mode.enabled = false;
+        }
+    });
+}
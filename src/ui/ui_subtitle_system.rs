@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::{EventReader, Local, Res, Time};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{events::ChatboxEvent, resources::ClientSettings};
+
+const DIALOG_SUBTITLE_ID: &str = "dialog_subtitle";
+
+// Subtitles stay for a base duration plus a little longer for each character
+const SUBTITLE_BASE_DURATION: f32 = 4.0;
+const SUBTITLE_DURATION_PER_CHARACTER: f32 = 0.06;
+const SUBTITLE_MAX_DURATION: f32 = 15.0;
+const SUBTITLE_FADE_DURATION: f32 = 0.5;
+
+// Older lines are dropped when more arrive than the strip can show
+const SUBTITLE_MAX_LINES: usize = 3;
+
+const SUBTITLE_MAX_WIDTH: f32 = 900.0;
+
+// Keeps the strip above the hotbar and chat input
+const SUBTITLE_BOTTOM_MARGIN: f32 = 140.0;
+
+const SUBTITLE_COLOR_SPEAKER: egui::Color32 = egui::Color32::from_rgb(255, 220, 120);
+const SUBTITLE_COLOR_NORMAL: egui::Color32 = egui::Color32::from_rgb(255, 255, 255);
+const SUBTITLE_COLOR_SHOUT: egui::Color32 = egui::Color32::from_rgb(189, 250, 255);
+const SUBTITLE_COLOR_WHISPER: egui::Color32 = egui::Color32::from_rgb(201, 255, 144);
+const SUBTITLE_COLOR_ANNOUNCE: egui::Color32 = egui::Color32::from_rgb(255, 188, 172);
+
+struct Subtitle {
+    speaker: Option<String>,
+    text: String,
+    color: egui::Color32,
+    elapsed: f32,
+}
+
+impl Subtitle {
+    fn new(speaker: Option<String>, text: String, color: egui::Color32) -> Self {
+        Self {
+            speaker,
+            text,
+            color,
+            elapsed: 0.0,
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        (SUBTITLE_BASE_DURATION
+            + self.text.chars().count() as f32 * SUBTITLE_DURATION_PER_CHARACTER)
+            .min(SUBTITLE_MAX_DURATION)
+    }
+}
+
+#[derive(Default)]
+pub struct UiStateSubtitles {
+    subtitles: VecDeque<Subtitle>,
+    dialog_subtitle: Option<(Option<String>, String)>,
+}
+
+// Called every frame by the conversation dialog with the NPC message being shown
+pub fn set_dialog_subtitle(ctx: &egui::Context, speaker: Option<&str>, text: &str) {
+    ctx.data_mut(|data| {
+        data.insert_temp(
+            egui::Id::new(DIALOG_SUBTITLE_ID),
+            (speaker.map(|speaker| speaker.to_string()), text.to_string()),
+        )
+    });
+}
+
+fn take_dialog_subtitle(ctx: &egui::Context) -> Option<(Option<String>, String)> {
+    ctx.data_mut(|data| {
+        let id = egui::Id::new(DIALOG_SUBTITLE_ID);
+        let subtitle = data.get_temp::<(Option<String>, String)>(id);
+        data.remove::<(Option<String>, String)>(id);
+        subtitle
+    })
+}
+
+pub fn ui_subtitle_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateSubtitles>,
+    mut chatbox_events: EventReader<ChatboxEvent>,
+    client_settings: Res<ClientSettings>,
+    time: Res<Time>,
+) {
+    let ui_state = &mut *ui_state;
+    let ctx = egui_context.ctx_mut();
+    let dialog_subtitle = take_dialog_subtitle(ctx);
+
+    if !client_settings.accessibility.subtitles {
+        chatbox_events.clear();
+        ui_state.subtitles.clear();
+        ui_state.dialog_subtitle = None;
+        return;
+    }
+
+    for event in chatbox_events.iter() {
+        let subtitle = match event {
+            ChatboxEvent::Say(name, text) => {
+                Subtitle::new(Some(name.clone()), text.clone(), SUBTITLE_COLOR_NORMAL)
+            }
+            ChatboxEvent::Shout(name, text) => {
+                Subtitle::new(Some(name.clone()), text.clone(), SUBTITLE_COLOR_SHOUT)
+            }
+            ChatboxEvent::Whisper(name, text) => {
+                Subtitle::new(Some(name.clone()), text.clone(), SUBTITLE_COLOR_WHISPER)
+            }
+            ChatboxEvent::Announce(name, text) => {
+                Subtitle::new(name.clone(), text.clone(), SUBTITLE_COLOR_ANNOUNCE)
+            }
+            _ => continue,
+        };
+        ui_state.subtitles.push_back(subtitle);
+    }
+
+    if dialog_subtitle != ui_state.dialog_subtitle {
+        if let Some((speaker, text)) = dialog_subtitle.clone() {
+            ui_state
+                .subtitles
+                .push_back(Subtitle::new(speaker, text, SUBTITLE_COLOR_NORMAL));
+        }
+        ui_state.dialog_subtitle = dialog_subtitle;
+    }
+
+    while ui_state.subtitles.len() > SUBTITLE_MAX_LINES {
+        ui_state.subtitles.pop_front();
+    }
+
+    let delta = time.delta_seconds();
+    ui_state.subtitles.retain_mut(|subtitle| {
+        subtitle.elapsed += delta;
+        subtitle.elapsed < subtitle.duration()
+    });
+
+    if ui_state.subtitles.is_empty() {
+        return;
+    }
+
+    let screen_rect = ctx.input(|input| input.screen_rect());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("subtitles"),
+    ));
+    let font_id = egui::FontId::proportional(client_settings.accessibility.subtitle_font_size);
+    let wrap_width = SUBTITLE_MAX_WIDTH.min(screen_rect.width() * 0.8);
+
+    // Newest line at the bottom, older lines stacked above it
+    let mut bottom = screen_rect.bottom() - SUBTITLE_BOTTOM_MARGIN;
+    for subtitle in ui_state.subtitles.iter().rev() {
+        let remaining = subtitle.duration() - subtitle.elapsed;
+        let opacity = (remaining / SUBTITLE_FADE_DURATION).clamp(0.0, 1.0);
+
+        let mut job = egui::text::LayoutJob::default();
+        job.wrap.max_width = wrap_width;
+        job.halign = egui::Align::Center;
+        if let Some(speaker) = subtitle.speaker.as_ref() {
+            job.append(
+                &format!("{}: ", speaker),
+                0.0,
+                egui::TextFormat::simple(
+                    font_id.clone(),
+                    SUBTITLE_COLOR_SPEAKER.linear_multiply(opacity),
+                ),
+            );
+        }
+        job.append(
+            &subtitle.text,
+            0.0,
+            egui::TextFormat::simple(font_id.clone(), subtitle.color.linear_multiply(opacity)),
+        );
+
+        let galley = ctx.fonts(|fonts| fonts.layout_job(job));
+        let text_rect = egui::Rect::from_center_size(
+            egui::pos2(screen_rect.center().x, bottom - galley.size().y / 2.0),
+            galley.size(),
+        );
+        painter.rect_filled(
+            text_rect.expand(8.0),
+            egui::Rounding::same(4.0),
+            egui::Color32::from_black_alpha(180).linear_multiply(opacity),
+        );
+        // Centre aligned galleys are laid out around x = 0
+        painter.galley(egui::pos2(text_rect.center().x, text_rect.top()), galley);
+
+        bottom = text_rect.top() - 20.0;
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::{Query, Res};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::{AbilityValues, HealthPoints};
+
+use crate::{
+    components::{ClientEntity, ClientEntityName},
+    protocol::{ExtendedClientMessage, ProtocolCapabilities, SummonCommand, SummonCommandKind},
+    resources::{
+        ClientCapabilities, ClientEntityList, GameConnection, PlayerSummons, SelectedTarget,
+    },
+};
+
+pub fn ui_summon_system(
+    mut egui_context: EguiContexts,
+    player_summons: Res<PlayerSummons>,
+    client_entity_list: Res<ClientEntityList>,
+    query_summon: Query<(&AbilityValues, &ClientEntityName, &HealthPoints)>,
+    query_client_entity: Query<&ClientEntity>,
+    selected_target: Res<SelectedTarget>,
+    game_connection: Option<Res<GameConnection>>,
+    client_capabilities: Res<ClientCapabilities>,
+) {
+    if player_summons.entity_ids.is_empty() {
+        return;
+    }
+
+    // Summons are still listed on servers which do not accept commands for them
+    let can_command = client_capabilities.supports(ProtocolCapabilities::SUMMON_COMMANDS);
+
+    let target_entity_id = selected_target
+        .selected
+        .and_then(|entity| query_client_entity.get(entity).ok())
+        .map(|client_entity| client_entity.id);
+    let mut commands = Vec::new();
+
+    egui::Window::new("Summons")
+        .anchor(egui::Align2::LEFT_TOP, [0.0, 120.0])
+        .resizable(false)
+        .collapsible(true)
+        .show(egui_context.ctx_mut(), |ui| {
+            for &summon_entity_id in player_summons.entity_ids.iter() {
+                // Summons outside of our view range have not been spawned
+                let Some((ability_values, name, health_points)) = client_entity_list
+                    .get(summon_entity_id)
+                    .and_then(|entity| query_summon.get(entity).ok())
+                else {
+                    continue;
+                };
+
+                let max_health = ability_values.get_max_health().max(1);
+                ui.label(name.as_str());
+                ui.add(
+                    egui::ProgressBar::new(health_points.hp as f32 / max_health as f32)
+                        .desired_width(160.0)
+                        .text(format!("{} / {}", health_points.hp, max_health)),
+                );
+
+                ui.horizontal(|ui| {
+                    let attack_target = target_entity_id.filter(|id| *id != summon_entity_id);
+                    if ui
+                        .add_enabled(
+                            can_command && attack_target.is_some(),
+                            egui::Button::new("Attack"),
+                        )
+                        .on_hover_text("Attack my target")
+                        .clicked()
+                    {
+                        if let Some(attack_target) = attack_target {
+                            commands.push(SummonCommand {
+                                summon_entity_id,
+                                kind: SummonCommandKind::Attack(attack_target),
+                            });
+                        }
+                    }
+
+                    if ui
+                        .add_enabled(can_command, egui::Button::new("Passive"))
+                        .clicked()
+                    {
+                        commands.push(SummonCommand {
+                            summon_entity_id,
+                            kind: SummonCommandKind::Passive,
+                        });
+                    }
+
+                    if ui
+                        .add_enabled(can_command, egui::Button::new("Dismiss"))
+                        .clicked()
+                    {
+                        commands.push(SummonCommand {
+                            summon_entity_id,
+                            kind: SummonCommandKind::Dismiss,
+                        });
+                    }
+                });
+                ui.separator();
+            }
+        });
+
+    if let Some(game_connection) = game_connection.as_ref() {
+        for command in commands {
+            game_connection
+                .extended_client_message_tx
+                .send(ExtendedClientMessage::SummonCommand(command))
+                .ok();
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::Res;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::resources::TextureStreaming;
+
+fn megabytes(bytes: usize) -> f32 {
+    bytes as f32 / (1024.0 * 1024.0)
+}
+
+pub fn ui_texture_memory_overlay_system(
+    mut egui_context: EguiContexts,
+    texture_streaming: Res<TextureStreaming>,
+) {
+    if !texture_streaming.show_overlay {
+        return;
+    }
+
+    egui::Area::new("texture_memory_overlay")
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+        .interactable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                let budget = texture_streaming.budget.map_or_else(
+                    || "unlimited".to_string(),
+                    |budget| format!("{:.0} MB", megabytes(budget)),
+                );
+                ui.label(format!(
+                    "Texture memory: {:.1} MB / {}",
+                    megabytes(texture_streaming.total_bytes),
+                    budget
+                ));
+                ui.label(format!(
+                    "Reduced textures: {} ({:.1} MB saved)",
+                    texture_streaming.num_reduced(),
+                    megabytes(texture_streaming.saved_bytes)
+                ));
+            });
+        });
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::{Local, Query, Res, ResMut, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::UnionMembership;
+
+use crate::{
+    components::PlayerCharacter,
+    resources::{GameData, NUM_UNIONS},
+    ui::{format::format_number, UiStateWindows, UiWindowId},
+};
+
+#[derive(Default)]
+pub struct UiStateUnion {
+    selected_union: Option<usize>,
+}
+
+pub fn ui_union_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateUnion>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    query_player: Query<&UnionMembership, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+) {
+    if !ui_state_windows.is_open(UiWindowId::UNION) {
+        return;
+    }
+
+    let Ok(union_membership) = query_player.get_single() else {
+        return;
+    };
+    let current_union = union_membership.current_union.map(|id| id.get());
+    let selected_union = ui_state
+        .selected_union
+        .or(current_union)
+        .unwrap_or(1)
+        .clamp(1, NUM_UNIONS);
+    let get_points = |id: usize| union_membership.points.get(id - 1).copied().unwrap_or(0);
+
+    egui::Window::new("Union")
+        .open(ui_state_windows.open_mut(UiWindowId::UNION))
+        .resizable(false)
+        .default_width(360.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Current Union:");
+                if let Some(current_union) = current_union {
+                    ui.strong(game_data.unions.get_union_name(current_union));
+                } else {
+                    ui.weak("None");
+                }
+            });
+            ui.separator();
+
+            egui::Grid::new("union_points")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Union");
+                    ui.strong("Points");
+                    ui.strong("Rank");
+                    ui.end_row();
+
+                    for id in 1..=NUM_UNIONS {
+                        let points = get_points(id);
+                        let mut text = egui::RichText::new(game_data.unions.get_union_name(id));
+                        if current_union == Some(id) {
+                            text = text.color(egui::Color32::YELLOW);
+                        }
+                        if ui.selectable_label(selected_union == id, text).clicked() {
+                            ui_state.selected_union = Some(id);
+                        }
+
+                        ui.label(format_number(points as u64));
+
+                        let union_data = game_data.unions.get_union(id);
+                        let rank = union_data.and_then(|union_data| union_data.get_rank(points));
+                        let next_rank =
+                            union_data.and_then(|union_data| union_data.get_next_rank(points));
+                        match (rank, next_rank) {
+                            (rank, Some(next_rank)) => {
+                                let previous_points = rank.map_or(0, |rank| rank.required_points);
+                                let progress = (points - previous_points) as f32
+                                    / (next_rank.required_points - previous_points).max(1) as f32;
+                                ui.add(
+                                    egui::ProgressBar::new(progress)
+                                        .desired_width(120.0)
+                                        .text(rank.map_or("-", |rank| rank.name.as_str())),
+                                )
+                                .on_hover_text(format!(
+                                    "{} at {} points",
+                                    next_rank.name,
+                                    format_number(next_rank.required_points as u64)
+                                ));
+                            }
+                            (Some(rank), None) => {
+                                ui.label(&rank.name);
+                            }
+                            (None, None) => {
+                                ui.weak("-");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+            ui.strong(format!(
+                "{} Rewards",
+                game_data.unions.get_union_name(selected_union)
+            ));
+
+            let rewards = game_data
+                .unions
+                .get_union(selected_union)
+                .map_or(&[][..], |union_data| union_data.rewards.as_slice());
+            if rewards.is_empty() {
+                ui.weak("No rewards are known for this union");
+                return;
+            }
+
+            let points = get_points(selected_union);
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("union_rewards")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for reward in rewards {
+                                let name = game_data
+                                    .items
+                                    .get_base_item(reward.item)
+                                    .map_or("???", |item_data| item_data.name);
+                                ui.label(name);
+
+                                let color = if points >= reward.points {
+                                    egui::Color32::GREEN
+                                } else {
+                                    egui::Color32::RED
+                                };
+                                ui.colored_label(
+                                    color,
+                                    format!("{} points", format_number(reward.points as u64)),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+            ui.weak("Rewards are purchased from the union's NPC");
+        });
+}
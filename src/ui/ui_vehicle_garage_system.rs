@@ -0,0 +1,564 @@
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    ecs::query::WorldQuery,
+    prelude::{
+        AssetServer, Assets, Camera, Camera3d, Camera3dBundle, Children, Color, Commands,
+        ComputedVisibility, DespawnRecursiveExt, Entity, EventWriter, GlobalTransform, Handle,
+        Image, Local, Query, Res, ResMut, Transform, Vec3, Visibility, With,
+    },
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+};
+use bevy_egui::{egui, EguiContexts};
+use enum_map::{enum_map, EnumMap};
+
+use rose_data::{EquipmentItem, Item, ItemClass, ItemReference, VehiclePartIndex, VehicleType};
+use rose_game_common::components::{
+    AbilityValues, BasicStats, CharacterInfo, Equipment, Inventory, InventoryPageType, ItemSlot,
+    Level, MoveMode, SkillList, StatusEffects, INVENTORY_PAGE_SIZE,
+};
+
+use crate::{
+    components::PlayerCharacter,
+    events::PlayerCommandEvent,
+    model_loader::ModelLoader,
+    render::{EffectMeshMaterial, ObjectMaterial, ParticleMaterial},
+    resources::{ClientSettings, GameData, SkeletonCache, SkeletonCacheState, UiResources},
+    ui::{
+        tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
+        ui_add_item_tooltip, DragAndDropId, DragAndDropSlot, TooltipExt, UiStateWindows,
+        UiWindowId,
+    },
+};
+
+const VEHICLE_PARTS: [(VehiclePartIndex, &str); 4] = [
+    (VehiclePartIndex::Body, "Frame"),
+    (VehiclePartIndex::Engine, "Engine"),
+    (VehiclePartIndex::Leg, "Wheels"),
+    (VehiclePartIndex::Arms, "Accessory"),
+];
+
+// The preview is rendered on its own layer so the main camera never sees it
+const PREVIEW_RENDER_LAYER: u8 = 1;
+const PREVIEW_IMAGE_WIDTH: u32 = 320;
+const PREVIEW_IMAGE_HEIGHT: u32 = 200;
+
+struct VehiclePreview {
+    image: Handle<Image>,
+    texture_id: egui::TextureId,
+    camera_entity: Entity,
+    model_entity: Option<Entity>,
+    model_parts: EnumMap<VehiclePartIndex, Option<ItemReference>>,
+}
+
+#[derive(Default)]
+pub struct UiStateVehicleGarage {
+    // None keeps the currently equipped part
+    selected_parts: EnumMap<VehiclePartIndex, Option<ItemSlot>>,
+    preview: Option<VehiclePreview>,
+}
+
+#[derive(WorldQuery)]
+pub struct PlayerQuery<'w> {
+    basic_stats: &'w BasicStats,
+    character_info: &'w CharacterInfo,
+    equipment: &'w Equipment,
+    inventory: &'w Inventory,
+    level: &'w Level,
+    move_mode: &'w MoveMode,
+    skill_list: &'w SkillList,
+    status_effects: &'w StatusEffects,
+}
+
+fn get_vehicle_part_index(item_class: ItemClass) -> Option<VehiclePartIndex> {
+    match item_class {
+        ItemClass::CartBody | ItemClass::CastleGearBody => Some(VehiclePartIndex::Body),
+        ItemClass::CartEngine | ItemClass::CastleGearEngine => Some(VehiclePartIndex::Engine),
+        ItemClass::CartWheels | ItemClass::CastleGearLeg => Some(VehiclePartIndex::Leg),
+        ItemClass::CartAccessory | ItemClass::CastleGearWeapon => Some(VehiclePartIndex::Arms),
+        _ => None,
+    }
+}
+
+fn get_vehicle_type(game_data: &GameData, equipment_item: &EquipmentItem) -> Option<VehicleType> {
+    game_data
+        .items
+        .get_vehicle_item(equipment_item.item.item_number)
+        .map(|vehicle_item_data| vehicle_item_data.vehicle_type)
+}
+
+fn get_item_name(game_data: &GameData, equipment_item: &EquipmentItem) -> String {
+    game_data
+        .items
+        .get_base_item(equipment_item.item)
+        .map_or_else(|| "???".to_string(), |item_data| item_data.name.to_string())
+}
+
+fn calculate_driving_values(
+    game_data: &GameData,
+    player: &PlayerQueryItem,
+    equipment: &Equipment,
+) -> AbilityValues {
+    let mut ability_values = game_data.ability_value_calculator.calculate(
+        player.character_info,
+        player.level,
+        equipment,
+        player.basic_stats,
+        player.skill_list,
+        player.status_effects,
+    );
+    ability_values.is_driving = true;
+    ability_values
+}
+
+fn ui_add_part_slot(
+    ui: &mut egui::Ui,
+    equipment_item: Option<&EquipmentItem>,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+) {
+    let item = equipment_item.cloned().map(Item::Equipment);
+    let mut dragged_item = None;
+    let mut dropped_item = None;
+    let response = ui.add(DragAndDropSlot::with_item(
+        DragAndDropId::NotDraggable,
+        item.as_ref(),
+        None,
+        game_data,
+        ui_resources,
+        |_| false,
+        &mut dragged_item,
+        &mut dropped_item,
+        [40.0, 40.0],
+    ));
+
+    if let Some(item) = item.as_ref() {
+        response.on_hover_tooltip_ui(|ui| {
+            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+        });
+    }
+}
+
+fn spawn_vehicle_preview(
+    commands: &mut Commands,
+    egui_context: &mut EguiContexts,
+    images: &mut Assets<Image>,
+) -> VehiclePreview {
+    let size = Extent3d {
+        width: PREVIEW_IMAGE_WIDTH,
+        height: PREVIEW_IMAGE_HEIGHT,
+        ..Default::default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("vehicle_garage_preview"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..Default::default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    let camera_entity = commands
+        .spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    // Render before the main camera so the texture is ready for the UI
+                    order: -1,
+                    target: RenderTarget::Image(image.clone()),
+                    hdr: false,
+                    ..Default::default()
+                },
+                camera_3d: Camera3d {
+                    clear_color: ClearColorConfig::Custom(Color::NONE),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(3.5, 2.0, 3.5)
+                    .looking_at(Vec3::new(0.0, 0.75, 0.0), Vec3::Y),
+                ..Default::default()
+            },
+            RenderLayers::layer(PREVIEW_RENDER_LAYER),
+        ))
+        .id();
+
+    VehiclePreview {
+        texture_id: egui_context.add_image(image.clone_weak()),
+        image,
+        camera_entity,
+        model_entity: None,
+        model_parts: Default::default(),
+    }
+}
+
+fn despawn_vehicle_preview(
+    commands: &mut Commands,
+    egui_context: &mut EguiContexts,
+    preview: VehiclePreview,
+) {
+    egui_context.remove_image(&preview.image);
+    commands.entity(preview.camera_entity).despawn_recursive();
+    if let Some(model_entity) = preview.model_entity {
+        commands.entity(model_entity).despawn_recursive();
+    }
+}
+
+fn ui_add_stat_comparison(ui: &mut egui::Ui, name: &str, current: i32, preview: i32) {
+    ui.label(name);
+    ui.label(format!("{}", current));
+    match preview.cmp(&current) {
+        std::cmp::Ordering::Greater => {
+            ui.colored_label(egui::Color32::GREEN, format!("{}", preview));
+        }
+        std::cmp::Ordering::Less => {
+            ui.colored_label(egui::Color32::RED, format!("{}", preview));
+        }
+        std::cmp::Ordering::Equal => {
+            ui.label(format!("{}", preview));
+        }
+    }
+    ui.end_row();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_vehicle_garage_system(
+    mut commands: Commands,
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateVehicleGarage>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut player_command_events: EventWriter<PlayerCommandEvent>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
+    mut client_settings: ResMut<ClientSettings>,
+    (asset_server, model_loader, mut skeleton_cache): (
+        Res<AssetServer>,
+        Res<ModelLoader>,
+        ResMut<SkeletonCache>,
+    ),
+    (mut images, mut object_materials, mut particle_materials, mut effect_mesh_materials): (
+        ResMut<Assets<Image>>,
+        ResMut<Assets<ObjectMaterial>>,
+        ResMut<Assets<ParticleMaterial>>,
+        ResMut<Assets<EffectMeshMaterial>>,
+    ),
+    query_children: Query<&Children>,
+    query_render_layers: Query<&RenderLayers>,
+) {
+    let is_open = ui_state_windows.is_open(UiWindowId::VEHICLE_GARAGE);
+    let Some(player) = query_player.get_single().ok().filter(|_| is_open) else {
+        if let Some(preview) = ui_state.preview.take() {
+            despawn_vehicle_preview(&mut commands, &mut egui_context, preview);
+        }
+        return;
+    };
+    let player_tooltip_data = query_player_tooltip.get_single().ok();
+
+    // Vehicle parts from the inventory, grouped by the part they replace
+    let mut inventory_parts: EnumMap<VehiclePartIndex, Vec<(ItemSlot, &EquipmentItem)>> =
+        Default::default();
+    for index in 0..INVENTORY_PAGE_SIZE {
+        let item_slot = ItemSlot::Inventory(InventoryPageType::Vehicles, index);
+        let Some(Item::Equipment(equipment_item)) = player.inventory.get_item(item_slot) else {
+            continue;
+        };
+        let Some(vehicle_part_index) = game_data
+            .items
+            .get_base_item(equipment_item.item)
+            .and_then(|item_data| get_vehicle_part_index(item_data.class))
+        else {
+            continue;
+        };
+        inventory_parts[vehicle_part_index].push((item_slot, equipment_item));
+    }
+
+    // Forget any selection which is no longer in the inventory
+    for (vehicle_part_index, selected_part) in ui_state.selected_parts.iter_mut() {
+        if selected_part.map_or(false, |item_slot| {
+            !inventory_parts[vehicle_part_index]
+                .iter()
+                .any(|(part_slot, _)| *part_slot == item_slot)
+        }) {
+            *selected_part = None;
+        }
+    }
+
+    let mut preview_equipment = player.equipment.clone();
+    for (vehicle_part_index, selected_part) in ui_state.selected_parts.iter() {
+        if let Some(Item::Equipment(equipment_item)) =
+            selected_part.and_then(|item_slot| player.inventory.get_item(item_slot))
+        {
+            preview_equipment.equipped_vehicle[vehicle_part_index] = Some(equipment_item.clone());
+        }
+    }
+
+    let preview_vehicle_type = preview_equipment.equipped_vehicle[VehiclePartIndex::Body]
+        .as_ref()
+        .and_then(|body| get_vehicle_type(&game_data, body));
+    let is_compatible = preview_equipment
+        .equipped_vehicle
+        .values()
+        .flatten()
+        .all(|part| get_vehicle_type(&game_data, part) == preview_vehicle_type);
+    let has_changes = ui_state.selected_parts.values().any(|part| part.is_some());
+    let is_driving = matches!(player.move_mode, MoveMode::Drive);
+    let preview_body_item = preview_equipment.equipped_vehicle[VehiclePartIndex::Body]
+        .as_ref()
+        .map(|body| body.item.item_number);
+    let mut mount_vehicle_item = client_settings.controls.mount_vehicle_item;
+
+    // Respawn the preview model whenever the selected parts change
+    let preview = ui_state.preview.get_or_insert_with(|| {
+        spawn_vehicle_preview(&mut commands, &mut egui_context, &mut images)
+    });
+    let preview_parts: EnumMap<VehiclePartIndex, Option<ItemReference>> = enum_map! {
+        vehicle_part_index => preview_equipment.equipped_vehicle[vehicle_part_index]
+            .as_ref()
+            .map(|equipment_item| equipment_item.item),
+    };
+    if preview.model_parts != preview_parts {
+        if let Some(model_entity) = preview.model_entity.take() {
+            commands.entity(model_entity).despawn_recursive();
+        }
+        preview.model_parts = Default::default();
+    }
+
+    if preview.model_entity.is_none() {
+        if let Some(vehicle_type) = preview_vehicle_type {
+            match skeleton_cache.get(ModelLoader::get_vehicle_skeleton_path(vehicle_type)) {
+                SkeletonCacheState::Loaded(skeleton) => {
+                    let model_entity = commands
+                        .spawn((
+                            Visibility::Inherited,
+                            ComputedVisibility::default(),
+                            Transform::default(),
+                            GlobalTransform::default(),
+                        ))
+                        .id();
+                    model_loader.spawn_vehicle_model(
+                        &mut commands,
+                        &asset_server,
+                        &mut object_materials,
+                        &mut particle_materials,
+                        &mut effect_mesh_materials,
+                        model_entity,
+                        model_entity,
+                        &preview_equipment,
+                        &skeleton,
+                    );
+                    preview.model_entity = Some(model_entity);
+                    preview.model_parts = preview_parts;
+                }
+                // Try again next frame once the skeleton has loaded
+                SkeletonCacheState::Loading => {}
+                SkeletonCacheState::Failed => {
+                    preview.model_parts = preview_parts;
+                }
+            }
+        } else {
+            preview.model_parts = preview_parts;
+        }
+    }
+
+    // Model parts and effects are spawned as children, so keep moving them
+    // onto the preview layer as they appear
+    if let Some(model_entity) = preview.model_entity {
+        for entity in query_children.iter_descendants(model_entity) {
+            if query_render_layers.get(entity).is_err() {
+                commands
+                    .entity(entity)
+                    .insert(RenderLayers::layer(PREVIEW_RENDER_LAYER));
+            }
+        }
+    }
+    let preview_texture_id = preview.texture_id;
+
+    let mut equip_set = false;
+
+    egui::Window::new("Vehicle Garage")
+        .open(ui_state_windows.open_mut(UiWindowId::VEHICLE_GARAGE))
+        .resizable(false)
+        .default_width(320.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                ui.image(
+                    preview_texture_id,
+                    egui::vec2(PREVIEW_IMAGE_WIDTH as f32, PREVIEW_IMAGE_HEIGHT as f32),
+                );
+            });
+            ui.separator();
+
+            egui::Grid::new("vehicle_garage_parts")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for (vehicle_part_index, part_name) in VEHICLE_PARTS {
+                        ui_add_part_slot(
+                            ui,
+                            preview_equipment.equipped_vehicle[vehicle_part_index].as_ref(),
+                            player_tooltip_data.as_ref(),
+                            &game_data,
+                            &ui_resources,
+                        );
+                        ui.label(part_name);
+
+                        let selected_part = &mut ui_state.selected_parts[vehicle_part_index];
+                        let selected_text = match selected_part
+                            .and_then(|item_slot| player.inventory.get_item(item_slot))
+                        {
+                            Some(Item::Equipment(equipment_item)) => {
+                                get_item_name(&game_data, equipment_item)
+                            }
+                            _ => player.equipment.equipped_vehicle[vehicle_part_index]
+                                .as_ref()
+                                .map_or_else(
+                                    || "None".to_string(),
+                                    |equipment_item| {
+                                        format!(
+                                            "{} (Equipped)",
+                                            get_item_name(&game_data, equipment_item)
+                                        )
+                                    },
+                                ),
+                        };
+
+                        egui::ComboBox::from_id_source(("vehicle_garage_part", part_name))
+                            .width(180.0)
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(selected_part, None, "Keep equipped");
+                                for (item_slot, equipment_item) in
+                                    inventory_parts[vehicle_part_index].iter()
+                                {
+                                    ui.selectable_value(
+                                        selected_part,
+                                        Some(*item_slot),
+                                        get_item_name(&game_data, equipment_item),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+
+            ui.separator();
+
+            if preview_vehicle_type.is_none() {
+                ui.weak("Select a frame to preview the vehicle");
+                return;
+            }
+
+            let current_values = calculate_driving_values(&game_data, &player, player.equipment);
+            let preview_values = calculate_driving_values(&game_data, &player, &preview_equipment);
+
+            egui::Grid::new("vehicle_garage_stats")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Stat");
+                    ui.strong("Current");
+                    ui.strong("Preview");
+                    ui.end_row();
+
+                    ui_add_stat_comparison(
+                        ui,
+                        "Move Speed",
+                        current_values.get_move_speed(&MoveMode::Drive) as i32,
+                        preview_values.get_move_speed(&MoveMode::Drive) as i32,
+                    );
+                    ui_add_stat_comparison(
+                        ui,
+                        "Attack",
+                        current_values.get_attack_power() as i32,
+                        preview_values.get_attack_power() as i32,
+                    );
+                    ui_add_stat_comparison(
+                        ui,
+                        "Attack Speed",
+                        current_values.get_attack_speed() as i32,
+                        preview_values.get_attack_speed() as i32,
+                    );
+
+                    // Engine life is used as the vehicle fuel
+                    let get_fuel = |equipment: &Equipment| {
+                        equipment.equipped_vehicle[VehiclePartIndex::Engine]
+                            .as_ref()
+                            .map_or(0, |engine| (engine.life as i32 + 9) / 10)
+                    };
+                    ui_add_stat_comparison(
+                        ui,
+                        "Fuel %",
+                        get_fuel(player.equipment),
+                        get_fuel(&preview_equipment),
+                    );
+                });
+
+            ui.separator();
+
+            if !is_compatible {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    "Parts must all be for the same type of vehicle",
+                );
+            }
+            if is_driving {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    "Vehicle parts can not be changed while driving",
+                );
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        has_changes && is_compatible && !is_driving,
+                        egui::Button::new("Equip Set"),
+                    )
+                    .clicked()
+                {
+                    equip_set = true;
+                }
+
+                if ui
+                    .add_enabled(has_changes, egui::Button::new("Reset"))
+                    .clicked()
+                {
+                    ui_state.selected_parts = Default::default();
+                }
+
+                let is_mount =
+                    mount_vehicle_item.is_some() && mount_vehicle_item == preview_body_item;
+                if ui
+                    .add_enabled(!is_mount, egui::Button::new("Use Frame as Mount"))
+                    .on_hover_text("The mount key equips this frame before mounting")
+                    .clicked()
+                {
+                    mount_vehicle_item = preview_body_item;
+                }
+            });
+        });
+
+    if mount_vehicle_item != client_settings.controls.mount_vehicle_item {
+        client_settings.controls.mount_vehicle_item = mount_vehicle_item;
+    }
+
+    if equip_set {
+        for item_slot in ui_state.selected_parts.values().flatten() {
+            player_command_events.send(PlayerCommandEvent::EquipVehicle(*item_slot));
+        }
+        ui_state.selected_parts = Default::default();
+    }
+}
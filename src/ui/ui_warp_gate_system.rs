@@ -0,0 +1,138 @@
+use bevy::{
+    input::Input,
+    prelude::{Entity, GlobalTransform, KeyCode, Query, Res, Time, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::ZoneId;
+use rose_game_common::messages::client::ClientMessage;
+
+use crate::{
+    components::{ClientEntity, DynamicPointLight, GrowthObject, PlayerCharacter, WarpObject},
+    resources::{ClientSettings, GameConnection, GameData},
+    systems::is_interactable,
+};
+
+use super::ui_interact_prompt_system::INTERACT_PROMPT_DISTANCE;
+
+// Warp gates closer than this to the player show the interact prompt
+pub const WARP_GATE_PROMPT_DISTANCE: f32 = 8.0;
+
+// Same cooldown as walking into the gate in collision_system
+const WARP_GATE_REQUEST_COOLDOWN: f64 = 5.0;
+
+pub fn ui_warp_gate_system(
+    mut egui_context: EguiContexts,
+    query_player: Query<&GlobalTransform, With<PlayerCharacter>>,
+    mut query_warp_objects: Query<(
+        Entity,
+        &mut WarpObject,
+        &GlobalTransform,
+        Option<&mut DynamicPointLight>,
+    )>,
+    query_interactable: Query<(&ClientEntity, &GlobalTransform, Option<&GrowthObject>)>,
+    game_connection: Option<Res<GameConnection>>,
+    game_data: Res<GameData>,
+    client_settings: Res<ClientSettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = query_player.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation();
+
+    let mut nearest = None;
+    for (entity, warp_object, global_transform, _) in query_warp_objects.iter() {
+        let distance = global_transform.translation().distance(player_position);
+        if distance < WARP_GATE_PROMPT_DISTANCE
+            && nearest.map_or(true, |(_, _, nearest_distance)| distance < nearest_distance)
+        {
+            nearest = Some((entity, warp_object.warp_id, distance));
+        }
+    }
+
+    // Pulse the portal light of the gate in range
+    let base_intensity = DynamicPointLight::warp_gate().intensity;
+    let pulse = 1.0 + 0.5 * (time.elapsed_seconds() * 4.0).sin();
+    for (entity, _, _, point_light) in query_warp_objects.iter_mut() {
+        if let Some(mut point_light) = point_light {
+            let intensity =
+                if nearest.map_or(false, |(nearest_entity, _, _)| nearest_entity == entity) {
+                    base_intensity * pulse
+                } else {
+                    base_intensity
+                };
+
+            if point_light.intensity != intensity {
+                point_light.intensity = intensity;
+            }
+        }
+    }
+
+    let Some((warp_entity, warp_id, warp_distance)) = nearest else {
+        return;
+    };
+
+    // Both prompts share the interact key, a closer interactable entity takes priority
+    if query_interactable
+        .iter()
+        .any(|(client_entity, global_transform, growth_object)| {
+            let distance = global_transform.translation().distance(player_position);
+            distance < INTERACT_PROMPT_DISTANCE
+                && distance <= warp_distance
+                && is_interactable(client_entity, growth_object)
+        })
+    {
+        return;
+    }
+    let interact_key = client_settings.controls.interact_key;
+
+    let zone_name = ZoneId::new(game_data.stb_warp.get_int(warp_id.get() as usize, 1) as u16)
+        .and_then(|zone_id| game_data.zone_list.get_zone(zone_id))
+        .map_or("???", |zone_data| zone_data.name);
+
+    let ctx = egui_context.ctx_mut();
+    let screen_rect = ctx.input(|input| input.screen_rect());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("warp_gate_prompt"),
+    ));
+    let galley = painter.layout_no_wrap(
+        format!("Press {} to enter {}", interact_key.name(), zone_name),
+        egui::FontId::proportional(16.0),
+        egui::Color32::WHITE,
+    );
+    let text_rect = egui::Align2::CENTER_CENTER.anchor_rect(egui::Rect::from_min_size(
+        egui::pos2(screen_rect.center().x, screen_rect.max.y * 0.7),
+        galley.size(),
+    ));
+    painter.rect_filled(
+        text_rect.expand(6.0),
+        egui::Rounding::same(4.0),
+        egui::Color32::from_black_alpha(160),
+    );
+    painter.galley(text_rect.min, galley);
+
+    if ctx.wants_keyboard_input() || !keyboard_input.just_pressed(interact_key.key_code()) {
+        return;
+    }
+
+    let Ok((_, mut warp_object, _, _)) = query_warp_objects.get_mut(warp_entity) else {
+        return;
+    };
+    if time.elapsed_seconds_f64() - warp_object.last_collision < WARP_GATE_REQUEST_COOLDOWN {
+        return;
+    }
+
+    if let Some(game_connection) = game_connection.as_ref() {
+        game_connection
+            .client_message_tx
+            .send(ClientMessage::WarpGateRequest {
+                warp_gate_id: warp_id,
+            })
+            .ok();
+    }
+
+    warp_object.last_collision = time.elapsed_seconds_f64();
+}
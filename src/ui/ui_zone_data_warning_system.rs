@@ -0,0 +1,57 @@
+use bevy::prelude::ResMut;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{data_integrity::ZoneDataIssue, resources::ZoneDataVerification};
+
+pub fn ui_zone_data_warning_system(
+    mut egui_context: EguiContexts,
+    mut zone_data_verification: ResMut<ZoneDataVerification>,
+) {
+    if zone_data_verification.issues.is_empty() {
+        return;
+    }
+
+    let mut dismiss = false;
+    egui::Window::new("Game Data Mismatch")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("Your game data for this zone differs from the server.");
+            ui.label("You may see walls, NPCs or item names which do not match the server.");
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("zone_data_issues")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for issue in zone_data_verification.issues.iter() {
+                                match issue {
+                                    ZoneDataIssue::Missing { path } => {
+                                        ui.colored_label(egui::Color32::RED, "Missing");
+                                        ui.label(path);
+                                    }
+                                    ZoneDataIssue::Mismatch { path } => {
+                                        ui.colored_label(egui::Color32::YELLOW, "Different");
+                                        ui.label(path);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.separator();
+            if ui.button("Dismiss").clicked() {
+                dismiss = true;
+            }
+        });
+
+    // Keep the zone so the warning is not shown again until the zone changes
+    if dismiss {
+        zone_data_verification.issues.clear();
+    }
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::{Assets, Local, Query, Res, Time, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::ZoneId;
+
+use crate::{
+    components::{PlayerCharacter, Position},
+    resources::{ClientSettings, CurrentZone, GameData, ZoneTransition},
+    zone_loader::ZoneLoaderAsset,
+};
+
+const ZONE_TITLE_DURATION: f32 = 5.0;
+const AREA_TITLE_DURATION: f32 = 3.0;
+const TITLE_FADE_DURATION: f32 = 1.0;
+
+// Distance in world units from an event position to be inside its area
+const AREA_RADIUS: f32 = 3000.0;
+
+// Event positions in the zone file are relative to the centre of the map
+const EVENT_POSITION_OFFSET: f32 = 520000.0;
+
+// Event positions used by the server rather than named areas
+const IGNORED_EVENT_POSITIONS: [&str; 2] = ["start", "restore"];
+
+struct TitleCard {
+    text: String,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl TitleCard {
+    fn new(text: String, duration: f32) -> Self {
+        Self {
+            text,
+            elapsed: 0.0,
+            duration,
+        }
+    }
+
+    fn opacity(&self) -> f32 {
+        let fade_in = self.elapsed / TITLE_FADE_DURATION;
+        let fade_out = (self.duration - self.elapsed) / TITLE_FADE_DURATION;
+        fade_in.min(fade_out).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Default)]
+pub struct UiStateZoneTitle {
+    zone_id: Option<ZoneId>,
+    area_name: Option<String>,
+    zone_title: Option<TitleCard>,
+    area_title: Option<TitleCard>,
+}
+
+fn find_area_name(zone_asset: &ZoneLoaderAsset, position: &Position) -> Option<String> {
+    zone_asset
+        .zon
+        .event_positions
+        .iter()
+        .filter(|(name, _)| {
+            !name.is_empty()
+                && !IGNORED_EVENT_POSITIONS
+                    .iter()
+                    .any(|ignored| name.eq_ignore_ascii_case(ignored))
+        })
+        .map(|(name, event_position)| {
+            let dx = event_position.x + EVENT_POSITION_OFFSET - position.position.x;
+            let dy = event_position.y + EVENT_POSITION_OFFSET - position.position.y;
+            (name, dx * dx + dy * dy)
+        })
+        .filter(|(_, distance_squared)| *distance_squared < AREA_RADIUS * AREA_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(name, _)| name.clone())
+}
+
+fn paint_title(
+    painter: &egui::Painter,
+    position: egui::Pos2,
+    text: &str,
+    font_size: f32,
+    opacity: f32,
+) {
+    let alpha = (opacity * 255.0) as u8;
+    let font_id = egui::FontId::proportional(font_size);
+    let text_color = egui::Color32::from_rgba_unmultiplied(255, 230, 170, alpha);
+
+    painter.text(
+        position + egui::vec2(2.0, 2.0),
+        egui::Align2::CENTER_CENTER,
+        text,
+        font_id.clone(),
+        egui::Color32::from_black_alpha(alpha),
+    );
+    let text_rect = painter.text(
+        position,
+        egui::Align2::CENTER_CENTER,
+        text,
+        font_id,
+        text_color,
+    );
+
+    // Decorative lines either side of the title
+    let line_length = font_size * 3.0;
+    let stroke = egui::Stroke::new(1.5, text_color);
+    painter.line_segment(
+        [
+            egui::pos2(text_rect.left() - 12.0 - line_length, position.y),
+            egui::pos2(text_rect.left() - 12.0, position.y),
+        ],
+        stroke,
+    );
+    painter.line_segment(
+        [
+            egui::pos2(text_rect.right() + 12.0, position.y),
+            egui::pos2(text_rect.right() + 12.0 + line_length, position.y),
+        ],
+        stroke,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_zone_title_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateZoneTitle>,
+    query_player: Query<&Position, With<PlayerCharacter>>,
+    current_zone: Option<Res<CurrentZone>>,
+    zone_loader_assets: Res<Assets<ZoneLoaderAsset>>,
+    zone_transition: Res<ZoneTransition>,
+    client_settings: Res<ClientSettings>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let ui_state = &mut *ui_state;
+    let Some(current_zone) = current_zone else {
+        return;
+    };
+
+    // Wait for the loading screen to finish so the title is not hidden behind it
+    if zone_transition.active.is_some() {
+        return;
+    }
+
+    if ui_state.zone_id != Some(current_zone.id) {
+        ui_state.zone_id = Some(current_zone.id);
+        ui_state.area_name = None;
+        ui_state.area_title = None;
+        ui_state.zone_title = game_data
+            .zone_list
+            .get_zone(current_zone.id)
+            .map(|zone_data| TitleCard::new(zone_data.name.to_string(), ZONE_TITLE_DURATION));
+    }
+
+    if let (Some(zone_asset), Ok(player_position)) = (
+        zone_loader_assets.get(&current_zone.handle),
+        query_player.get_single(),
+    ) {
+        let area_name = find_area_name(zone_asset, player_position);
+        if area_name != ui_state.area_name {
+            if let Some(area_name) = area_name.as_ref() {
+                ui_state.area_title = Some(TitleCard::new(area_name.clone(), AREA_TITLE_DURATION));
+            }
+            ui_state.area_name = area_name;
+        }
+    }
+
+    let delta_time = time.delta_seconds();
+    for title in [&mut ui_state.zone_title, &mut ui_state.area_title] {
+        if let Some(card) = title.as_mut() {
+            card.elapsed += delta_time;
+            if card.elapsed >= card.duration {
+                *title = None;
+            }
+        }
+    }
+
+    if !client_settings.interface.show_zone_titles {
+        return;
+    }
+
+    let ctx = egui_context.ctx_mut();
+    let screen_rect = ctx.input(|input| input.screen_rect());
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("zone_title"),
+    ));
+
+    if let Some(zone_title) = ui_state.zone_title.as_ref() {
+        paint_title(
+            &painter,
+            egui::pos2(
+                screen_rect.center().x,
+                screen_rect.top() + screen_rect.height() * 0.2,
+            ),
+            &zone_title.text,
+            36.0,
+            zone_title.opacity(),
+        );
+    } else if let Some(area_title) = ui_state.area_title.as_ref() {
+        paint_title(
+            &painter,
+            egui::pos2(
+                screen_rect.center().x,
+                screen_rect.top() + screen_rect.height() * 0.25,
+            ),
+            &area_title.text,
+            22.0,
+            area_title.opacity(),
+        );
+    }
+}
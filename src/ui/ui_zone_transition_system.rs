@@ -0,0 +1,110 @@
+use bevy::prelude::{AssetServer, Assets, Handle, Image, Local, Res};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::ZoneId;
+
+use crate::resources::{GameData, ZoneTransition, ZONE_TRANSITION_TIPS};
+
+#[derive(Default)]
+pub struct UiStateZoneTransition {
+    zone_id: Option<ZoneId>,
+    zone_image: Handle<Image>,
+    zone_texture: Option<egui::TextureId>,
+}
+
+pub fn ui_zone_transition_system(
+    mut ui_state: Local<UiStateZoneTransition>,
+    mut egui_context: EguiContexts,
+    zone_transition: Res<ZoneTransition>,
+    game_data: Res<GameData>,
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+) {
+    let Some(active) = zone_transition.active.as_ref() else {
+        if ui_state.zone_id.is_some() {
+            egui_context.remove_image(&ui_state.zone_image);
+            *ui_state = Default::default();
+        }
+        return;
+    };
+    let zone_data = game_data.zone_list.get_zone(active.zone_id);
+
+    // Use the destination zone map as the transition art
+    if ui_state.zone_id != Some(active.zone_id) {
+        egui_context.remove_image(&ui_state.zone_image);
+        ui_state.zone_image = Default::default();
+        ui_state.zone_texture = None;
+
+        if let Some(minimap_path) = zone_data.and_then(|zone_data| zone_data.minimap_path.as_ref())
+        {
+            ui_state.zone_image = asset_server.load(minimap_path.path());
+            ui_state.zone_texture = Some(egui_context.add_image(ui_state.zone_image.clone_weak()));
+        }
+
+        ui_state.zone_id = Some(active.zone_id);
+    }
+
+    let opacity = zone_transition.opacity();
+    let alpha = (opacity * 255.0) as u8;
+    let ctx = egui_context.ctx_mut();
+    let screen_rect = ctx.input(|input| input.screen_rect());
+
+    // Paint below the UI windows so chat and other windows stay usable
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::PanelResizeLine,
+        egui::Id::new("zone_transition"),
+    ));
+    painter.rect_filled(
+        screen_rect,
+        egui::Rounding::none(),
+        egui::Color32::from_black_alpha(alpha),
+    );
+
+    let mut text_y = screen_rect.center().y;
+    if let (Some(zone_texture), Some(zone_image)) =
+        (ui_state.zone_texture, images.get(&ui_state.zone_image))
+    {
+        let image_size = zone_image.size();
+        let scale = (screen_rect.height() * 0.5 / image_size.y)
+            .min(screen_rect.width() * 0.5 / image_size.x);
+        let image_rect = egui::Rect::from_center_size(
+            screen_rect.center() - egui::vec2(0.0, screen_rect.height() * 0.05),
+            egui::vec2(image_size.x * scale, image_size.y * scale),
+        );
+        painter.image(
+            zone_texture,
+            image_rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::from_white_alpha(alpha),
+        );
+        text_y = image_rect.max.y;
+    }
+
+    painter.text(
+        egui::pos2(screen_rect.center().x, text_y + 16.0),
+        egui::Align2::CENTER_TOP,
+        zone_data.map_or("???", |zone_data| zone_data.name),
+        egui::FontId::proportional(24.0),
+        egui::Color32::from_rgba_unmultiplied(255, 255, 255, alpha),
+    );
+
+    if let Some(tip) = ZONE_TRANSITION_TIPS.get(active.tip_index) {
+        painter.text(
+            egui::pos2(screen_rect.center().x, screen_rect.max.y - 48.0),
+            egui::Align2::CENTER_BOTTOM,
+            format!("Tip: {}", tip),
+            egui::FontId::proportional(16.0),
+            egui::Color32::from_rgba_unmultiplied(220, 220, 220, alpha),
+        );
+    }
+
+    if active.fade_elapsed.is_none() {
+        painter.text(
+            egui::pos2(screen_rect.center().x, screen_rect.max.y - 24.0),
+            egui::Align2::CENTER_BOTTOM,
+            "Loading...",
+            egui::FontId::proportional(14.0),
+            egui::Color32::from_rgba_unmultiplied(180, 180, 180, alpha),
+        );
+    }
+}
@@ -52,12 +52,16 @@ impl DrawWidget for Table {
             return;
         }
 
+        let scroll_row = bindings
+            .get_scroll(self.id)
+            .map_or(0, |(scroll_row, _, _)| *scroll_row);
         let (mut current_index, draw_table_item) =
             if let Some((current_index, draw_table_item)) = bindings.get_table(self.id) {
                 (Some(current_index), Some(draw_table_item))
             } else {
                 (None, None)
             };
+
         let rect = self.widget_rect(ui.min_rect().min);
         ui.allocate_ui_at_rect(rect, |ui| {
             egui::Grid::new(self.id)
@@ -68,11 +72,12 @@ impl DrawWidget for Table {
                 .spacing(egui::vec2(self.column_margin, self.row_margin))
                 .show(ui, |ui| {
                     for y in 0..self.extent {
+                        let row = scroll_row + y;
                         for x in 0..self.column_count {
-                            let index = x + y * self.column_count;
+                            let index = x + row * self.column_count;
 
                             if let Some(draw_table_item) = draw_table_item {
-                                if draw_table_item(ui, index, x, y).clicked() {
+                                if draw_table_item(ui, index, x, row).clicked() {
                                     if let Some(current_index) = current_index.as_mut() {
                                         **current_index = index;
                                     }
@@ -84,5 +89,17 @@ impl DrawWidget for Table {
                     }
                 });
         });
+
+        // Scrolling is by row, the scroll range is the number of rows in the table
+        if ui.rect_contains_pointer(rect) {
+            let scroll_delta = ui.input(|input| input.scroll_delta);
+            if let Some((scroll_row, scroll_range, extent)) = bindings.get_scroll(self.id) {
+                if scroll_delta.y > 0.0 && *scroll_row > scroll_range.start {
+                    *scroll_row -= 1;
+                } else if scroll_delta.y < 0.0 && *scroll_row < (scroll_range.end - extent) {
+                    *scroll_row += 1;
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,185 @@
+use bevy_egui::egui;
+use std::hash::Hash;
+
+use crate::ui::format::{format_number, NumberLocale};
+
+// Removes the group separators between digit groups and replaces the decimal
+// separator with '.', a '.' which does not separate a group of three digits is
+// treated as a decimal point so "1.5m" still works when '.' groups digits
+fn normalize_number(number: &str, locale: NumberLocale) -> Option<String> {
+    let chars = number.chars().collect::<Vec<_>>();
+    let mut normalized = String::with_capacity(chars.len());
+
+    for (index, &ch) in chars.iter().enumerate() {
+        if ch == locale.group_separator {
+            let group_length = chars[index + 1..]
+                .iter()
+                .take_while(|ch| ch.is_ascii_digit())
+                .count();
+            let is_group_separator = index > 0
+                && group_length == 3
+                && chars.get(index + 4).map_or(true, |&next| {
+                    next == locale.group_separator || next == locale.decimal_separator
+                });
+
+            if is_group_separator {
+                continue;
+            } else if ch == '.' {
+                normalized.push('.');
+            } else {
+                return None;
+            }
+        } else if ch == locale.decimal_separator {
+            normalized.push('.');
+        } else {
+            normalized.push(ch);
+        }
+    }
+
+    Some(normalized)
+}
+
+fn parse_zuly_with_locale(text: &str, locale: NumberLocale) -> Option<u64> {
+    let text = text
+        .chars()
+        .filter(|ch| *ch == locale.group_separator || !ch.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+
+    if text.is_empty() {
+        return Some(0);
+    }
+
+    let (number, multiplier) = if let Some(number) = text.strip_suffix('k') {
+        (number, 1_000.0)
+    } else if let Some(number) = text.strip_suffix('m') {
+        (number, 1_000_000.0)
+    } else {
+        return normalize_number(&text, locale)?.parse::<u64>().ok();
+    };
+
+    let value = normalize_number(number, locale)?.parse::<f64>().ok()? * multiplier;
+    if value.is_finite() && value >= 0.0 && value < u64::MAX as f64 {
+        Some(value.round() as u64)
+    } else {
+        None
+    }
+}
+
+// Parses an amount of zuly such as "1,250,000", "500k" or "1.5m"
+pub fn parse_zuly(text: &str) -> Option<u64> {
+    parse_zuly_with_locale(text, NumberLocale::current())
+}
+
+pub struct ZulyInput<'a> {
+    id_source: egui::Id,
+    value: &'a mut u64,
+    max_value: Option<u64>,
+}
+
+impl<'a> ZulyInput<'a> {
+    pub fn new(id_source: impl Hash, value: &'a mut u64) -> Self {
+        Self {
+            id_source: egui::Id::new(id_source),
+            value,
+            max_value: None,
+        }
+    }
+
+    pub fn max_value(mut self, max_value: u64) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+}
+
+impl<'a> egui::Widget for ZulyInput<'a> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let max_value = self.max_value.unwrap_or(u64::MAX);
+
+        ui.horizontal(|ui| {
+            // Keep the text being edited so partial input such as "1.5" is not reformatted
+            let text_id = ui.id().with(self.id_source);
+            let mut text = ui
+                .data_mut(|data| data.get_temp::<String>(text_id))
+                .unwrap_or_else(|| format_number(*self.value));
+
+            let mut response = ui.add(
+                egui::TextEdit::singleline(&mut text)
+                    .desired_width(120.0)
+                    .hint_text("e.g. 500k or 1.5m"),
+            );
+
+            if response.changed() {
+                if let Some(value) = parse_zuly(&text) {
+                    *self.value = value.min(max_value);
+                }
+            }
+
+            if self.max_value.is_some() && ui.button("Max").clicked() {
+                *self.value = max_value;
+                response.mark_changed();
+            }
+
+            if !response.has_focus() {
+                text = format_number(*self.value);
+            }
+            ui.data_mut(|data| data.insert_temp(text_id, text));
+
+            ui.label("Zuly");
+            response
+        })
+        .inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: NumberLocale = NumberLocale {
+        group_separator: '.',
+        decimal_separator: ',',
+    };
+
+    const FRENCH: NumberLocale = NumberLocale {
+        group_separator: '\u{a0}',
+        decimal_separator: ',',
+    };
+
+    #[test]
+    fn parse_plain_and_grouped_numbers() {
+        let english = NumberLocale::ENGLISH;
+        assert_eq!(parse_zuly_with_locale("", english), Some(0));
+        assert_eq!(parse_zuly_with_locale("1250", english), Some(1250));
+        assert_eq!(
+            parse_zuly_with_locale("1,250,000", english),
+            Some(1_250_000)
+        );
+        assert_eq!(parse_zuly_with_locale(" 1 250 ", english), Some(1250));
+        assert_eq!(parse_zuly_with_locale("1.250.000", GERMAN), Some(1_250_000));
+        assert_eq!(
+            parse_zuly_with_locale("1\u{a0}250\u{a0}000", FRENCH),
+            Some(1_250_000)
+        );
+        assert_eq!(parse_zuly_with_locale("1,25", english), None);
+        assert_eq!(parse_zuly_with_locale("abc", english), None);
+    }
+
+    #[test]
+    fn parse_suffixes() {
+        let english = NumberLocale::ENGLISH;
+        assert_eq!(parse_zuly_with_locale("500k", english), Some(500_000));
+        assert_eq!(parse_zuly_with_locale("1.5M", english), Some(1_500_000));
+        assert_eq!(parse_zuly_with_locale("1,500k", english), Some(1_500_000));
+        assert_eq!(parse_zuly_with_locale("1,5m", GERMAN), Some(1_500_000));
+        assert_eq!(parse_zuly_with_locale("1,5m", FRENCH), Some(1_500_000));
+        assert_eq!(parse_zuly_with_locale("-1k", english), None);
+    }
+
+    #[test]
+    fn parse_decimal_point_when_dot_groups_digits() {
+        assert_eq!(parse_zuly_with_locale("1.5m", GERMAN), Some(1_500_000));
+        assert_eq!(parse_zuly_with_locale("2.25k", GERMAN), Some(2_250));
+        assert_eq!(parse_zuly_with_locale("1.500.000", GERMAN), Some(1_500_000));
+    }
+}
@@ -1,18 +1,39 @@
 use bevy::asset::{AssetIo, AssetIoError, BoxedFuture, ChangeWatcher, Metadata};
 use std::{
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, RwLock},
 };
 
 use rose_file_readers::{VfsFile, VirtualFilesystem};
 
 pub struct VfsAssetIo {
-    vfs: Arc<VirtualFilesystem>,
+    vfs: Arc<RwLock<Option<Arc<VirtualFilesystem>>>>,
+}
+
+// Mounts the game data after the asset server has been created, used when
+// the data profile is chosen on the profile screen
+#[derive(Clone)]
+pub struct VfsAssetIoMount {
+    vfs: Arc<RwLock<Option<Arc<VirtualFilesystem>>>>,
 }
 
 impl VfsAssetIo {
     pub fn new(vfs: Arc<VirtualFilesystem>) -> Self {
-        Self { vfs }
+        Self {
+            vfs: Arc::new(RwLock::new(Some(vfs))),
+        }
+    }
+
+    // Every asset is not found until the game data is mounted
+    pub fn unmounted() -> (Self, VfsAssetIoMount) {
+        let vfs = Arc::new(RwLock::new(None));
+        (Self { vfs: vfs.clone() }, VfsAssetIoMount { vfs })
+    }
+}
+
+impl VfsAssetIoMount {
+    pub fn mount(&self, vfs: Arc<VirtualFilesystem>) {
+        *self.vfs.write().unwrap() = Some(vfs);
     }
 }
 
@@ -26,10 +47,11 @@ impl AssetIo for VfsAssetIo {
                 .unwrap()
                 .trim_end_matches(".no_skin")
                 .trim_end_matches(".zmo_texture");
+            let vfs = self.vfs.read().unwrap().clone();
             if path.ends_with(".zone_loader") {
                 let zone_id = path.trim_end_matches(".zone_loader").parse::<u8>().unwrap();
                 Ok(vec![zone_id])
-            } else if let Ok(file) = self.vfs.open_file(path) {
+            } else if let Some(Ok(file)) = vfs.as_ref().map(|vfs| vfs.open_file(path)) {
                 match file {
                     VfsFile::Buffer(buffer) => Ok(buffer),
                     VfsFile::View(view) => Ok(view.into()),
@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use rose_file_readers::VirtualFilesystem;
+
+pub struct VfsModLayer {
+    pub name: String,
+    pub path: PathBuf,
+    pub priority: i32,
+}
+
+pub struct VfsModFile {
+    // Path relative to the layer root using forward slashes, as used for vfs lookups
+    pub vfs_path: String,
+    pub host_path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+pub struct VfsModConflict {
+    pub vfs_path: String,
+
+    // Layers which contain this file, the first layer is the one which is used
+    pub layers: Vec<String>,
+    pub overrides_base: bool,
+}
+
+// Files directly inside the mods directory, or inside its game data directories,
+// belong to this layer, which always has the lowest priority of all mod layers
+pub const VFS_MOD_ROOT_LAYER_NAME: &str = "<root>";
+
+// Top level directories of the game data. These directories directly inside
+// mods/ belong to the root layer, e.g. mods/3DDATA/..., rather than being a layer
+const GAME_DATA_DIRECTORIES: &[&str] = &["3DDATA", "ETC", "SOUND"];
+
+fn is_game_data_directory(name: &str) -> bool {
+    GAME_DATA_DIRECTORIES
+        .iter()
+        .any(|directory| directory.eq_ignore_ascii_case(name))
+}
+
+fn contains_game_data_directory(path: &Path) -> bool {
+    std::fs::read_dir(path).map_or(false, |read_dir| {
+        read_dir.flatten().any(|entry| {
+            entry.path().is_dir() && is_game_data_directory(&entry.file_name().to_string_lossy())
+        })
+    })
+}
+
+pub fn discover_mod_layers(
+    mods_path: &Path,
+    priorities: &HashMap<String, i32>,
+) -> Vec<VfsModLayer> {
+    let mut layers = Vec::new();
+    let Ok(read_dir) = std::fs::read_dir(mods_path) else {
+        return layers;
+    };
+
+    let mut has_root_files = false;
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() && is_game_data_directory(&name) {
+            has_root_files = true;
+        } else if path.is_dir() {
+            // Printed as the logger is not installed yet when the game data is loaded
+            if !contains_game_data_directory(&path) {
+                eprintln!(
+                    "Mod layer {} does not contain any of the game data directories {}, its files will not override any game data",
+                    name,
+                    GAME_DATA_DIRECTORIES.join(", ")
+                );
+            }
+
+            let priority = priorities.get(&name).copied().unwrap_or(0);
+            layers.push(VfsModLayer {
+                name,
+                path,
+                priority,
+            });
+        } else {
+            has_root_files = true;
+        }
+    }
+
+    // Sort by priority, then by name, so the load order never depends on the
+    // order the host filesystem returns directory entries in
+    layers.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    if has_root_files {
+        layers.push(VfsModLayer {
+            name: VFS_MOD_ROOT_LAYER_NAME.into(),
+            path: mods_path.into(),
+            priority: i32::MIN,
+        });
+    }
+
+    layers
+}
+
+fn collect_files(root_path: &Path, path: &Path, recursive: bool, files: &mut Vec<VfsModFile>) {
+    let Ok(read_dir) = std::fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in read_dir.flatten() {
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            if recursive {
+                collect_files(root_path, &entry_path, recursive, files);
+            }
+            continue;
+        }
+
+        let Ok(relative_path) = entry_path.strip_prefix(root_path) else {
+            continue;
+        };
+
+        files.push(VfsModFile {
+            vfs_path: relative_path
+                .to_string_lossy()
+                .replace('\\', "/")
+                .to_ascii_uppercase(),
+            modified: entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            host_path: entry_path,
+        });
+    }
+}
+
+impl VfsModLayer {
+    pub fn list_files(&self) -> Vec<VfsModFile> {
+        let mut files = Vec::new();
+        if self.name == VFS_MOD_ROOT_LAYER_NAME {
+            // The root layer only contains the loose files and game data directories,
+            // other sub-directories are layers of their own
+            collect_files(&self.path, &self.path, false, &mut files);
+            if let Ok(read_dir) = std::fs::read_dir(&self.path) {
+                for entry in read_dir.flatten() {
+                    let entry_path = entry.path();
+                    if entry_path.is_dir()
+                        && is_game_data_directory(&entry.file_name().to_string_lossy())
+                    {
+                        collect_files(&self.path, &entry_path, true, &mut files);
+                    }
+                }
+            }
+        } else {
+            collect_files(&self.path, &self.path, true, &mut files);
+        }
+        files.sort_by(|a, b| a.vfs_path.cmp(&b.vfs_path));
+        files
+    }
+}
+
+pub fn find_mod_conflicts(
+    layers: &[VfsModLayer],
+    base_vfs: Option<&VirtualFilesystem>,
+) -> Vec<VfsModConflict> {
+    let mut file_layers: HashMap<String, Vec<String>> = HashMap::new();
+    for layer in layers.iter() {
+        for file in layer.list_files() {
+            file_layers
+                .entry(file.vfs_path)
+                .or_default()
+                .push(layer.name.clone());
+        }
+    }
+
+    let mut conflicts: Vec<VfsModConflict> = file_layers
+        .into_iter()
+        .filter_map(|(vfs_path, layers)| {
+            let overrides_base = base_vfs.map_or(false, |base_vfs| {
+                base_vfs.open_file(vfs_path.as_str()).is_ok()
+            });
+
+            if layers.len() > 1 || overrides_base {
+                Some(VfsModConflict {
+                    vfs_path,
+                    layers,
+                    overrides_base,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.vfs_path.cmp(&b.vfs_path));
+    conflicts
+}
+
+pub fn print_mod_conflict_report(layers: &[VfsModLayer], base_vfs: Option<&VirtualFilesystem>) {
+    if layers.is_empty() {
+        println!("No mod layers found");
+        return;
+    }
+
+    println!("Mod layers, highest priority first:");
+    for layer in layers.iter() {
+        println!(
+            "  {} (priority {}) {}",
+            layer.name,
+            layer.priority,
+            layer.path.to_string_lossy()
+        );
+    }
+
+    let conflicts = find_mod_conflicts(layers, base_vfs);
+    let num_overrides = conflicts
+        .iter()
+        .filter(|conflict| conflict.overrides_base)
+        .count();
+    let num_layer_conflicts = conflicts
+        .iter()
+        .filter(|conflict| conflict.layers.len() > 1)
+        .count();
+
+    println!(
+        "{} files override game data, {} files are provided by multiple mods",
+        num_overrides, num_layer_conflicts
+    );
+
+    for conflict in conflicts.iter() {
+        let overrides = if conflict.overrides_base {
+            " [overrides game data]"
+        } else {
+            ""
+        };
+
+        if conflict.layers.len() > 1 {
+            println!(
+                "  {} used from {}, hidden in {}{}",
+                conflict.vfs_path,
+                conflict.layers[0],
+                conflict.layers[1..].join(", "),
+                overrides
+            );
+        } else {
+            println!(
+                "  {} from {}{}",
+                conflict.vfs_path, conflict.layers[0], overrides
+            );
+        }
+    }
+}
@@ -40,8 +40,8 @@ use crate::{
     animation::{MeshAnimation, TransformAnimation, ZmoTextureAssetLoader},
     audio::{SoundRadius, SpatialSound},
     components::{
-        ColliderParent, EventObject, NightTimeEffect, WarpObject, Zone, ZoneObject,
-        ZoneObjectAnimatedObject, ZoneObjectId, ZoneObjectPart, ZoneObjectTerrain,
+        ColliderParent, DynamicPointLight, EventObject, NightTimeEffect, WarpObject, Zone,
+        ZoneObject, ZoneObjectAnimatedObject, ZoneObjectId, ZoneObjectPart, ZoneObjectTerrain,
         COLLISION_FILTER_CLICKABLE, COLLISION_FILTER_COLLIDABLE, COLLISION_FILTER_INSPECTABLE,
         COLLISION_FILTER_MOVEABLE, COLLISION_GROUP_PHYSICS_TOY, COLLISION_GROUP_ZONE_EVENT_OBJECT,
         COLLISION_GROUP_ZONE_OBJECT, COLLISION_GROUP_ZONE_TERRAIN,
@@ -51,10 +51,10 @@ use crate::{
     events::{LoadZoneEvent, ZoneEvent},
     render::{
         EffectMeshAnimationRenderState, EffectMeshMaterial, ObjectMaterial, ParticleMaterial,
-        SkyMaterial, TerrainMaterial, WaterMaterial, MESH_ATTRIBUTE_UV_1,
+        SkyMaterial, TerrainMaterial, TerrainMaterialQuality, WaterMaterial, MESH_ATTRIBUTE_UV_1,
         TERRAIN_MATERIAL_MAX_TEXTURES, TERRAIN_MESH_ATTRIBUTE_TILE_INFO,
     },
-    resources::{CurrentZone, DebugInspector, GameData, SpecularTexture},
+    resources::{CurrentZone, DebugInspector, GameData, RenderConfiguration, SpecularTexture},
     VfsResource,
 };
 
@@ -357,6 +357,7 @@ pub struct SpawnZoneParams<'w, 's> {
     pub particle_materials: ResMut<'w, Assets<ParticleMaterial>>,
     pub object_materials: ResMut<'w, Assets<ObjectMaterial>>,
     pub water_materials: ResMut<'w, Assets<WaterMaterial>>,
+    pub render_configuration: Res<'w, RenderConfiguration>,
 }
 
 pub struct CachedZone {
@@ -540,6 +541,7 @@ pub fn spawn_zone(
         particle_materials,
         object_materials,
         water_materials,
+        render_configuration,
     } = params;
 
     let zone_list_entry = game_data
@@ -557,6 +559,31 @@ pub fn spawn_zone(
         tile_textures.push(asset_server.load(path));
     }
 
+    // Tiles with a <TILE>_DETAIL.DDS texture next to them use it as their detail
+    // texture, other tiles use the detail texture from the config if there is one
+    let mut tile_detail_textures: Vec<Option<Handle<Image>>> = vec![None; tile_textures.len()];
+    if matches!(
+        render_configuration.terrain_quality,
+        TerrainMaterialQuality::High
+    ) {
+        let default_detail_texture: Option<Handle<Image>> = render_configuration
+            .terrain_detail_texture
+            .as_ref()
+            .map(|path| asset_server.load(path.as_str()));
+
+        for (detail_texture, path) in tile_detail_textures
+            .iter_mut()
+            .zip(zone_data.zon.tile_textures.iter())
+        {
+            let detail_path = tile_detail_texture_path(path);
+            *detail_texture = if vfs_resource.vfs.open_file(&detail_path).is_ok() {
+                Some(asset_server.load(detail_path))
+            } else {
+                default_detail_texture.clone()
+            };
+        }
+    }
+
     let water_material = {
         let mut water_material_textures = Vec::with_capacity(25);
         for i in 1..=25 {
@@ -599,6 +626,8 @@ pub fn spawn_zone(
                     meshes,
                     terrain_materials,
                     &tile_textures,
+                    &tile_detail_textures,
+                    render_configuration.terrain_quality,
                     zone_data,
                     block_data,
                 );
@@ -670,9 +699,10 @@ pub fn spawn_zone(
                             COLLISION_GROUP_ZONE_WARP_OBJECT,
                         );
 
-                        commands
-                            .entity(warp_entity)
-                            .insert(WarpObject::new(WarpGateId::new(warp_object.warp_id)));
+                        commands.entity(warp_entity).insert((
+                            WarpObject::new(WarpGateId::new(warp_object.warp_id)),
+                            DynamicPointLight::warp_gate(),
+                        ));
                         commands.entity(zone_entity).add_child(warp_entity);
                     }
 
@@ -795,6 +825,13 @@ fn spawn_skybox(
         .id()
 }
 
+fn tile_detail_texture_path(tile_texture_path: &str) -> String {
+    let stem = tile_texture_path
+        .rsplit_once('.')
+        .map_or(tile_texture_path, |(stem, _)| stem);
+    format!("{}_DETAIL.DDS", stem)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn spawn_terrain(
     commands: &mut Commands,
@@ -802,6 +839,8 @@ fn spawn_terrain(
     meshes: &mut Assets<Mesh>,
     terrain_materials: &mut Assets<TerrainMaterial>,
     tile_textures: &Vec<Handle<Image>>,
+    tile_detail_textures: &[Option<Handle<Image>>],
+    terrain_quality: TerrainMaterialQuality,
     zone_data: &ZoneLoaderAsset,
     block_data: &ZoneLoaderBlock,
 ) -> Entity {
@@ -821,6 +860,8 @@ fn spawn_terrain(
     let mut tile_texture_map = vec![0; tile_textures.len()];
     let mut terrain_material = TerrainMaterial {
         textures: Vec::with_capacity(tile_textures.len() + 1),
+        quality: terrain_quality,
+        detail_textures: Vec::with_capacity(tile_textures.len() + 1),
     };
 
     terrain_material.textures.push(asset_server.load(format!(
@@ -829,6 +870,7 @@ fn spawn_terrain(
         block_data.block_x,
         block_data.block_y,
     )));
+    terrain_material.detail_textures.push(None);
 
     // Build TerrainMaterial and tile_texture_map
     for tile_x in 0..16 {
@@ -865,6 +907,9 @@ fn spawn_terrain(
                     terrain_material
                         .textures
                         .push(tile_textures[tile_array_index1 as usize].clone());
+                    terrain_material
+                        .detail_textures
+                        .push(tile_detail_textures[tile_array_index1 as usize].clone());
                     tile_texture_map[tile_array_index1 as usize] = index as u32;
                 }
             }
@@ -881,6 +926,9 @@ fn spawn_terrain(
                     terrain_material
                         .textures
                         .push(tile_textures[tile_array_index2 as usize].clone());
+                    terrain_material
+                        .detail_textures
+                        .push(tile_detail_textures[tile_array_index2 as usize].clone());
                     tile_texture_map[tile_array_index2 as usize] = index as u32;
                 }
             }
@@ -1216,6 +1264,7 @@ fn spawn_object(
                     },
                     blend: zsc_material.blend_mode.into(),
                     glow: zsc_material.glow.map(|x| x.into()),
+                    rim_light: None,
                     skinned: zsc_material.is_skin,
                     lightmap_uv_offset,
                     lightmap_uv_scale,
@@ -1349,7 +1398,13 @@ fn spawn_object(
                 commands.entity(effect_entity).insert(effect_transform);
 
                 if matches!(object_effect.effect_type, ZscEffectType::DayNight) {
-                    commands.entity(effect_entity).insert(NightTimeEffect);
+                    commands
+                        .entity(effect_entity)
+                        .insert((NightTimeEffect, DynamicPointLight::zone_light()));
+                } else if let Some(point_light) =
+                    DynamicPointLight::from_effect_path(&effect_path.path().to_string_lossy())
+                {
+                    commands.entity(effect_entity).insert(point_light);
                 }
             }
         }
@@ -1494,6 +1549,12 @@ fn spawn_effect_object(
         Some(effect_object_entity),
     );
 
+    if let Some(point_light) =
+        DynamicPointLight::from_effect_path(&effect_object.effect_path.path().to_string_lossy())
+    {
+        commands.entity(effect_object_entity).insert(point_light);
+    }
+
     effect_object_entity
 }
 